@@ -22,10 +22,13 @@ object PrettyPrinter extends ByteStringPrettyPrinter {
     case Transform.TransformInstance.AddI32(TransformAddInt32(i)) => s"Add($i)"
     case Transform.TransformInstance.AddBigInt(TransformAddBigInt(value)) =>
       s"AddBigInt(${value.get.value})"
+    case Transform.TransformInstance.SubBigInt(TransformSubBigInt(value)) =>
+      s"SubBigInt(${value.get.value})"
     case Transform.TransformInstance.AddKeys(TransformAddKeys(ks)) =>
       s"Insert(${ks.map(buildString).mkString(",")})"
     case Transform.TransformInstance.Failure(_)  => "TransformFailure"
     case Transform.TransformInstance.Identity(_) => "Read"
+    case Transform.TransformInstance.Delete(_)   => "Delete"
     case Transform.TransformInstance.Write(TransformWrite(mv)) =>
       mv match {
         case None    => "Write(Nothing)"
@@ -21,6 +21,8 @@ export declare function add_local(key_ptr: usize, key_size: usize, value_ptr: us
 export declare function new_uref(uref_ptr: usize, value_ptr: usize, value_size: usize): void;
 @external("env", "load_named_keys")
 export declare function load_named_keys(total_keys: usize, result_size: usize): i32;
+@external("env", "load_named_keys_chunk")
+export declare function load_named_keys_chunk(start_index: usize, max_count: usize, total_keys: usize, result_size: usize): i32;
 /** @hidden */
 @external("env", "get_named_arg")
 export declare function get_named_arg(name_ptr: usize, name_size: usize, dest_ptr: usize, dest_size: usize): i32;
@@ -204,4 +206,10 @@ export declare function disable_contract_version(
     contract_package_hash_size: usize,
     contract_hash_ptr: usize,
     contract_hash_size: usize,
-): i32;
\ No newline at end of file
+): i32;
+/** @hidden */
+@external("env", "blake2b")
+export declare function blake2b(in_ptr: usize, in_size: usize, out_ptr: usize, out_size: usize): i32;
+/** @hidden */
+@external("env", "verify_ed25519")
+export declare function verify_ed25519(public_key_ptr: usize, public_key_size: usize, signature_ptr: usize, signature_size: usize, message_ptr: usize, message_size: usize): i32;
@@ -5,6 +5,7 @@ import {CLValue, CLType, CLTypeTag} from "./clvalue";
 import {Key, AccountHash} from "./key";
 import {Pair} from "./pair";
 import {toBytesString,
+        toBytesU32,
         toBytesVecT,
         fromBytesMap,
         fromBytesString,
@@ -401,12 +402,22 @@ export enum EntryPointType {
   Contract = 1,
 }
 
+/**
+ * Written in place of an entry point's name length to mark a versioned layout.
+ */
+const ENTRY_POINT_VERSION_MARKER: u32 = 0xFFFFFFFF;
+/**
+ * The version of the layout which follows the original fields with the `no_schema` flag.
+ */
+const ENTRY_POINT_VERSION_NO_SCHEMA: u8 = 1;
+
 export class EntryPoint {
   constructor(public name: String,
               public args: Array<Pair<String, CLType>>,
               public ret: CLType,
               public access: EntryPointAccess,
-              public entry_point_type: EntryPointType) {}
+              public entry_point_type: EntryPointType,
+              public no_schema: bool = false) {}
 
   toBytes(): Array<u8> {
     let nameBytes = toBytesString(this.name);
@@ -415,7 +426,15 @@ export class EntryPoint {
     let retBytes = this.ret.toBytes();
     let accessBytes = this.access.toBytes();
     let entryPointTypeBytes: Array<u8> = [<u8>this.entry_point_type];
-    return nameBytes.concat(argsBytes).concat(retBytes).concat(accessBytes).concat(entryPointTypeBytes);
+    let bytes = nameBytes.concat(argsBytes).concat(retBytes).concat(accessBytes).concat(entryPointTypeBytes);
+    if (!this.no_schema) {
+      // Entry points keep the original layout unless they need the `no_schema` flag.
+      return bytes;
+    }
+    let versionBytes = toBytesU32(ENTRY_POINT_VERSION_MARKER);
+    versionBytes.push(ENTRY_POINT_VERSION_NO_SCHEMA);
+    let noSchemaBytes: Array<u8> = [<u8>1];
+    return versionBytes.concat(bytes).concat(noSchemaBytes);
   }
 };
 
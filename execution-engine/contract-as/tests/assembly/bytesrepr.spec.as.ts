@@ -405,4 +405,16 @@ export function testToBytesEntryPoint(): bool {
     let truth = hex2bin("010000000800000064656c65676174650800000064656c65676174650100000006000000706172616d3108090101");
     assert(checkArraysEqual(typedToArray(truth), bytes));
     return true;
+}
+
+export function testToBytesEntryPointNoSchema(): bool {
+    let entryPoints = new EntryPoints();
+    let args = new Array<Pair<String, CLType>>();
+    args.push(new Pair("param1", new CLType(CLTypeTag.U512)));
+    let entryPoint = new EntryPoint("delegate", args, new CLType(CLTypeTag.Unit), new PublicAccess(), EntryPointType.Contract, true);
+    entryPoints.addEntryPoint(entryPoint);
+    let bytes = entryPoints.toBytes();
+    let truth = hex2bin("010000000800000064656c6567617465ffffffff010800000064656c65676174650100000006000000706172616d310809010101");
+    assert(checkArraysEqual(typedToArray(truth), bytes));
+    return true;
 }
\ No newline at end of file
@@ -1,17 +1,17 @@
 //! Functions for managing accounts.
 
 use alloc::vec::Vec;
-use core::convert::TryFrom;
+use core::{convert::TryFrom, mem::MaybeUninit};
 
 use casperlabs_types::{
     account::{
         AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
         UpdateKeyFailure, Weight,
     },
-    bytesrepr, URef, UREF_SERIALIZED_LENGTH,
+    api_error, bytesrepr, ApiError, Key, URef, UREF_SERIALIZED_LENGTH,
 };
 
-use super::to_ptr;
+use super::{runtime, to_ptr};
 use crate::{contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
 
 /// Retrieves the ID of the account's main purse.
@@ -28,6 +28,49 @@ pub fn get_main_purse() -> URef {
     bytesrepr::deserialize(bytes).unwrap_or_revert()
 }
 
+/// Creates a new, empty purse and stores it under `name` in the current context's named keys.
+///
+/// Reverts with [`ApiError::NamedKeyExists`] if a named key called `name` already exists, in which
+/// case no purse is created.
+pub fn create_named_purse(name: &str) -> URef {
+    let (name_ptr, name_size, _bytes) = to_ptr(name);
+    let purse_non_null_ptr = contract_api::alloc_bytes(UREF_SERIALIZED_LENGTH);
+    let ret = unsafe {
+        ext_ffi::create_named_purse(
+            name_ptr,
+            name_size,
+            purse_non_null_ptr.as_ptr(),
+            UREF_SERIALIZED_LENGTH,
+        )
+    };
+    let bytes = unsafe {
+        Vec::from_raw_parts(
+            purse_non_null_ptr.as_ptr(),
+            UREF_SERIALIZED_LENGTH,
+            UREF_SERIALIZED_LENGTH,
+        )
+    };
+    api_error::result_from(ret).unwrap_or_revert();
+    bytesrepr::deserialize(bytes).unwrap_or_revert()
+}
+
+/// Returns the purse stored under `name` in the current context's named keys, or `None` if there
+/// is no such named key.
+///
+/// Reverts with [`ApiError::UnexpectedKeyVariant`] if the named key is not a [`URef`].
+pub fn get_named_purse(name: &str) -> Option<URef> {
+    match runtime::get_key(name)? {
+        Key::URef(purse) => Some(purse),
+        _ => runtime::revert(ApiError::UnexpectedKeyVariant),
+    }
+}
+
+/// Returns `true` if `purse` is valid in the current context and carries write access, i.e. funds
+/// could be taken from it by this deploy.
+pub fn validate_purse(purse: URef) -> bool {
+    purse.is_writeable() && runtime::is_valid_uref(purse)
+}
+
 /// Sets the given [`ActionType`]'s threshold to the provided value.
 pub fn set_action_threshold(
     action_type: ActionType,
@@ -43,6 +86,38 @@ pub fn set_action_threshold(
     }
 }
 
+/// Returns the account's current threshold for the given [`ActionType`].
+pub fn get_action_threshold(action_type: ActionType) -> Weight {
+    let threshold = unsafe { ext_ffi::get_action_threshold(action_type as u32) };
+    // The host only ever returns a threshold's `u8` value
+    Weight::new(threshold as u8)
+}
+
+/// Returns `true` if the given keys together meet the account's current threshold for the given
+/// [`ActionType`].
+///
+/// Each key counts once, however many times it is given, and keys which aren't associated with the
+/// account add no weight.  This allows checking, for example, that the remaining keys could still
+/// manage the account before removing one of its keys.
+pub fn can_authorize(keys: &[AccountHash], action_type: ActionType) -> bool {
+    let (keys_ptr, keys_size, _bytes) = to_ptr(keys.to_vec());
+    let result = unsafe { ext_ffi::can_authorize(keys_ptr, keys_size, action_type as u32) };
+    result != 0
+}
+
+/// Returns the account's associated keys together with their [`Weight`]s, ordered by
+/// [`AccountHash`].
+pub fn list_associated_keys() -> Vec<(AccountHash, Weight)> {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::list_associated_keys(output_size.as_mut_ptr()) };
+        api_error::result_from(ret).unwrap_or_revert();
+        unsafe { output_size.assume_init() }
+    };
+    let bytes = runtime::read_host_buffer(output_size).unwrap_or_revert();
+    bytesrepr::deserialize(bytes).unwrap_or_revert()
+}
+
 /// Adds the given [`AccountHash`] with associated [`Weight`] to the account's associated keys.
 pub fn add_associated_key(account_hash: AccountHash, weight: Weight) -> Result<(), AddKeyFailure> {
     let (account_hash_ptr, account_hash_size, _bytes) = to_ptr(account_hash);
@@ -3,8 +3,16 @@
 // Can be removed once https://github.com/rust-lang/rustfmt/issues/3362 is resolved.
 #[rustfmt::skip]
 use alloc::vec;
-use alloc::vec::Vec;
-use core::mem::MaybeUninit;
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    any,
+    fmt::{self, Display, Formatter},
+    mem::MaybeUninit,
+};
 
 use casperlabs_types::{
     account::AccountHash,
@@ -12,7 +20,8 @@ use casperlabs_types::{
     bytesrepr::{self, FromBytes},
     contracts::{ContractVersion, NamedKeys},
     ApiError, BlockTime, CLTyped, CLValue, ContractHash, ContractPackageHash, Key, Phase,
-    RuntimeArgs, URef, BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH,
+    RuntimeArgs, URef, BLAKE2B_DIGEST_LENGTH, BLOCKTIME_SERIALIZED_LENGTH, KEY_HASH_LENGTH,
+    PHASE_SERIALIZED_LENGTH,
 };
 
 use crate::{contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
@@ -149,12 +158,82 @@ fn get_named_arg_size(name: &str) -> Option<usize> {
     }
 }
 
+/// An error describing why a named argument could not be read.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ArgError {
+    /// No argument was passed under the given name.
+    Missing {
+        /// The name of the argument.
+        name: String,
+    },
+    /// The argument's bytes could not be deserialized into the expected type.
+    Invalid {
+        /// The name of the argument.
+        name: String,
+        /// The name of the type the argument was read as.
+        expected_type: &'static str,
+        /// The length in bytes of the argument passed to the host.
+        found_bytes: usize,
+        /// The error returned while deserializing the argument.
+        source: bytesrepr::Error,
+    },
+}
+
+impl ArgError {
+    /// Returns the [`ApiError`] which [`revert`] is called with for this error, i.e.
+    /// [`ApiError::MissingArgument`] or [`ApiError::InvalidArgument`].
+    pub fn api_error(&self) -> ApiError {
+        match self {
+            ArgError::Missing { .. } => ApiError::MissingArgument,
+            ArgError::Invalid { .. } => ApiError::InvalidArgument,
+        }
+    }
+
+    /// Calls [`revert`] with the error's [`ApiError`], first printing the error's details if the
+    /// `test-support` feature is enabled.
+    pub fn revert(self) -> ! {
+        #[cfg(feature = "test-support")]
+        print(&self.to_string());
+        revert(self.api_error())
+    }
+}
+
+impl Display for ArgError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            ArgError::Missing { name } => write!(formatter, "missing argument \"{}\"", name),
+            ArgError::Invalid {
+                name,
+                expected_type,
+                found_bytes,
+                source,
+            } => write!(
+                formatter,
+                "invalid argument \"{}\": expected {}, found {} bytes: {}",
+                name, expected_type, found_bytes, source
+            ),
+        }
+    }
+}
+
 /// Returns given named argument passed to the host for the current module invocation.
 ///
 /// Note that this is only relevant to contracts stored on-chain since a contract deployed directly
 /// is not invoked with any arguments.
+///
+/// Any failure will trigger [`revert`] with [`ApiError::MissingArgument`] or
+/// [`ApiError::InvalidArgument`], printing the details if the `test-support` feature is enabled.
+/// Use [`try_get_named_arg`] to handle the failure instead.
 pub fn get_named_arg<T: FromBytes>(name: &str) -> T {
-    let arg_size = get_named_arg_size(name).unwrap_or_revert_with(ApiError::MissingArgument);
+    try_get_named_arg(name).unwrap_or_revert()
+}
+
+/// Returns given named argument passed to the host for the current module invocation, or an
+/// [`ArgError`] naming the argument and the expected type if it is missing or invalid.
+pub fn try_get_named_arg<T: FromBytes>(name: &str) -> Result<T, ArgError> {
+    let arg_size = get_named_arg_size(name).ok_or_else(|| ArgError::Missing {
+        name: name.to_string(),
+    })?;
     let arg_bytes = if arg_size > 0 {
         let res = {
             let data_non_null_ptr = contract_api::alloc_bytes(arg_size);
@@ -176,7 +255,12 @@ pub fn get_named_arg<T: FromBytes>(name: &str) -> T {
         // Avoids allocation with 0 bytes and a call to get_named_arg
         Vec::new()
     };
-    bytesrepr::deserialize(arg_bytes).unwrap_or_revert_with(ApiError::InvalidArgument)
+    bytesrepr::deserialize(arg_bytes).map_err(|source| ArgError::Invalid {
+        name: name.to_string(),
+        expected_type: any::type_name::<T>(),
+        found_bytes: arg_size,
+        source,
+    })
 }
 
 /// Returns the caller of the current context, i.e. the [`AccountHash`] of the account which made
@@ -206,6 +290,28 @@ pub fn get_blocktime() -> BlockTime {
     bytesrepr::deserialize(bytes).unwrap_or_revert()
 }
 
+/// Returns the hash of the currently executing deploy.
+///
+/// The same value is returned from every contract called during a single deploy.
+///
+/// Only available from protocol version 2.0.0; under earlier versions the contract fails to load.
+pub fn get_deploy_hash() -> [u8; KEY_HASH_LENGTH] {
+    let mut deploy_hash = [0u8; KEY_HASH_LENGTH];
+    unsafe { ext_ffi::get_deploy_hash(deploy_hash.as_mut_ptr()) };
+    deploy_hash
+}
+
+/// Returns a new 32-byte address, e.g. for use as a predictable identifier.
+///
+/// Each call within a deploy returns a different address, drawn from the same stream as the
+/// hashes of contracts created by the deploy.  Executing the same deploy again yields the same
+/// sequence of addresses.
+pub fn new_hash_address() -> [u8; KEY_HASH_LENGTH] {
+    let mut address = [0u8; KEY_HASH_LENGTH];
+    unsafe { ext_ffi::new_hash_address(address.as_mut_ptr()) };
+    address
+}
+
 /// Returns the current [`Phase`].
 pub fn get_phase() -> Phase {
     let dest_non_null_ptr = contract_api::alloc_bytes(PHASE_SERIALIZED_LENGTH);
@@ -220,6 +326,19 @@ pub fn get_phase() -> Phase {
     bytesrepr::deserialize(bytes).unwrap_or_revert()
 }
 
+/// Returns the keys which authorized the deploy being executed.
+pub fn list_authorization_keys() -> BTreeSet<AccountHash> {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::list_authorization_keys(output_size.as_mut_ptr()) };
+        api_error::result_from(ret).unwrap_or_revert();
+        unsafe { output_size.assume_init() }
+    };
+    let bytes = read_host_buffer(output_size).unwrap_or_revert();
+    let authorization_keys: Vec<AccountHash> = bytesrepr::deserialize(bytes).unwrap_or_revert();
+    authorization_keys.into_iter().collect()
+}
+
 /// Returns the requested named [`Key`] from the current context.
 ///
 /// The current context is either the caller's account or a stored contract depending on whether the
@@ -298,6 +417,44 @@ pub fn list_named_keys() -> NamedKeys {
     bytesrepr::deserialize(bytes).unwrap_or_revert()
 }
 
+/// Returns a page of the named keys of the current context, along with a flag which is `true` if
+/// further named keys follow the returned page.
+///
+/// Named keys are ordered by name, and the page consists of at most `count` entries starting from
+/// the entry at index `start`.  The ordering is stable across calls within one execution, so the
+/// whole set of named keys can be walked by repeatedly advancing `start` by `count`.
+///
+/// As with [`list_named_keys`], the current context is either the caller's account or a stored
+/// contract depending on whether the currently-executing module is a direct call or a sub-call
+/// respectively.
+///
+/// Only available from protocol version 2.0.0; under earlier versions the contract fails to load.
+pub fn list_named_keys_paged(start: usize, count: usize) -> (NamedKeys, bool) {
+    let (total_keys, result_size) = {
+        let mut total_keys = MaybeUninit::uninit();
+        let mut result_size = 0;
+        let ret = unsafe {
+            ext_ffi::load_named_keys_chunk(
+                start,
+                count,
+                total_keys.as_mut_ptr(),
+                &mut result_size as *mut usize,
+            )
+        };
+        api_error::result_from(ret).unwrap_or_revert();
+        let total_keys = unsafe { total_keys.assume_init() };
+        (total_keys, result_size)
+    };
+    let named_keys: NamedKeys = if result_size == 0 {
+        NamedKeys::new()
+    } else {
+        let bytes = read_host_buffer(result_size).unwrap_or_revert();
+        bytesrepr::deserialize(bytes).unwrap_or_revert()
+    };
+    let more = start.saturating_add(named_keys.len()) < total_keys;
+    (named_keys, more)
+}
+
 /// Validates uref against named keys.
 pub fn is_valid_uref(uref: URef) -> bool {
     let (uref_ptr, uref_size, _bytes) = contract_api::to_ptr(uref);
@@ -305,6 +462,64 @@ pub fn is_valid_uref(uref: URef) -> bool {
     result != 0
 }
 
+/// Returns the 32-byte BLAKE2b digest of `data`, as calculated by the host.
+///
+/// Only available from protocol version 2.0.0; under earlier versions the contract fails to load.
+pub fn blake2b<T: AsRef<[u8]>>(data: T) -> [u8; BLAKE2B_DIGEST_LENGTH] {
+    let mut digest = [0u8; BLAKE2B_DIGEST_LENGTH];
+    let data = data.as_ref();
+    let ret = unsafe {
+        ext_ffi::blake2b(
+            data.as_ptr(),
+            data.len(),
+            digest.as_mut_ptr(),
+            BLAKE2B_DIGEST_LENGTH,
+        )
+    };
+    api_error::result_from(ret).unwrap_or_revert();
+    digest
+}
+
+/// The length in bytes of an Ed25519 public key.
+pub const ED25519_PUBLIC_KEY_LENGTH: usize = 32;
+
+/// The length in bytes of an Ed25519 signature.
+pub const ED25519_SIGNATURE_LENGTH: usize = 64;
+
+/// Returns `true` if `signature` is a valid Ed25519 signature of `message` by the holder of
+/// `public_key`, as verified by the host.
+///
+/// Only available from protocol version 2.0.0; under earlier versions the contract fails to load.
+pub fn verify_ed25519(
+    public_key: &[u8; ED25519_PUBLIC_KEY_LENGTH],
+    signature: &[u8; ED25519_SIGNATURE_LENGTH],
+    message: &[u8],
+) -> bool {
+    let result = unsafe {
+        ext_ffi::verify_ed25519(
+            public_key.as_ptr(),
+            public_key.len(),
+            signature.as_ptr(),
+            signature.len(),
+            message.as_ptr(),
+            message.len(),
+        )
+    };
+    result != 0
+}
+
+/// Emits an event with the given topic and payload for off-chain consumers.
+///
+/// Events are reported in order alongside the deploy's effects, but are not written to global
+/// state.  Reverts with [`ApiError::EventLimitExceeded`] if the deploy has already emitted the
+/// maximum number or total size of events.
+pub fn emit_event(topic: &str, payload: &[u8]) {
+    let (topic_ptr, topic_size, _bytes) = contract_api::to_ptr(topic);
+    let ret =
+        unsafe { ext_ffi::emit_event(topic_ptr, topic_size, payload.as_ptr(), payload.len()) };
+    api_error::result_from(ret).unwrap_or_revert();
+}
+
 fn read_host_buffer_into(dest: &mut [u8]) -> Result<usize, ApiError> {
     let mut bytes_written = MaybeUninit::uninit();
     let ret = unsafe {
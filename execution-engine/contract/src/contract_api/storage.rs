@@ -1,6 +1,12 @@
 //! Functions for accessing and mutating local and global state.
 
-use alloc::{collections::BTreeSet, string::String, vec, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::String,
+    vec,
+    vec::Vec,
+};
 use core::{convert::From, mem::MaybeUninit};
 
 use casperlabs_types::{
@@ -8,8 +14,9 @@ use casperlabs_types::{
     bytesrepr::{self, FromBytes, ToBytes},
     contracts::{ContractVersion, EntryPoints, NamedKeys},
     AccessRights, ApiError, CLTyped, CLValue, ContractHash, ContractPackageHash, Key, URef,
-    UREF_SERIALIZED_LENGTH,
+    UREF_ADDR_LENGTH, UREF_SERIALIZED_LENGTH,
 };
+use hex_fmt::HexFmt;
 
 use crate::{
     contract_api::{self, runtime, runtime::revert},
@@ -78,6 +85,19 @@ pub fn write<T: CLTyped + ToBytes>(uref: URef, value: T) {
     }
 }
 
+/// Removes the value stored under `uref` from the global state.  `uref` must have `WRITE` access
+/// rights.
+///
+/// Only available from protocol version 2.0.0; under earlier versions the contract fails to load.
+pub fn remove(uref: URef) {
+    let key = Key::from(uref);
+    let (key_ptr, key_size, _bytes) = contract_api::to_ptr(key);
+
+    unsafe {
+        ext_ffi::remove(key_ptr, key_size);
+    }
+}
+
 /// Writes `value` under `key` in the context-local partition of global state.
 pub fn write_local<K: ToBytes, V: CLTyped + ToBytes>(key: K, value: V) {
     let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(key);
@@ -283,6 +303,33 @@ pub fn remove_contract_user_group(
     api_error::result_from(ret)
 }
 
+/// Returns the user groups of the given contract package, as a map from group label to the
+/// `URef`s in the group.  `access_key` must be the package's access key.
+pub fn list_contract_groups(
+    package_hash: ContractPackageHash,
+    access_key: URef,
+) -> Result<BTreeMap<String, Vec<URef>>, ApiError> {
+    let (contract_package_hash_ptr, contract_package_hash_size, _bytes1) =
+        contract_api::to_ptr(package_hash);
+    let (access_key_ptr, access_key_size, _bytes2) = contract_api::to_ptr(access_key);
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::list_contract_user_groups(
+                contract_package_hash_ptr,
+                contract_package_hash_size,
+                access_key_ptr,
+                access_key_size,
+                output_size.as_mut_ptr(),
+            )
+        };
+        api_error::result_from(ret)?;
+        unsafe { output_size.assume_init() }
+    };
+    let bytes = runtime::read_host_buffer(output_size).unwrap_or_revert();
+    Ok(bytesrepr::deserialize(bytes).unwrap_or_revert())
+}
+
 /// Add a new version of a contract to the contract stored at the given
 /// `Key`. Note that this contract must have been created by
 /// `create_contract` or `create_contract_package_at_hash` first.
@@ -347,3 +394,80 @@ pub fn disable_contract_version(
 
     api_error::result_from(result)
 }
+
+/// The prefix of the names of the named keys holding the [`URef`]s of dictionary items.
+pub const DICTIONARY_ITEM_NAME_PREFIX: &str = "dictionary-item-";
+
+/// A handle to a dictionary: a namespace of values owned by the current context, addressed by
+/// string item keys.
+///
+/// The dictionary is identified by its seed [`URef`], which [`new_dictionary`] stores as a named
+/// key of the current context so that the dictionary can be found again by
+/// [`get_dictionary`] in later calls.
+///
+/// Each item is held under a [`URef`] in the current context's named keys rather than in local
+/// storage, as any contract can write to any local key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DictionaryRef(URef);
+
+impl DictionaryRef {
+    /// Returns the seed [`URef`] from which the names of this dictionary's items are derived.
+    pub fn seed(&self) -> URef {
+        self.0
+    }
+}
+
+/// Creates a new, empty dictionary and stores its seed under `name` in the current context's named
+/// keys, replacing any key already stored there.
+pub fn new_dictionary(name: &str) -> DictionaryRef {
+    let seed = new_uref(());
+    runtime::put_key(name, seed.into());
+    DictionaryRef(seed)
+}
+
+/// Returns the dictionary whose seed is stored under `name` in the current context's named keys,
+/// or `None` if there is no such key or it is not a [`URef`].
+pub fn get_dictionary(name: &str) -> Option<DictionaryRef> {
+    runtime::get_key(name)
+        .and_then(Key::into_uref)
+        .map(DictionaryRef)
+}
+
+/// Returns the name of the named key holding the [`URef`] of the value of `item_key` in
+/// `dictionary`.
+///
+/// The name is [`DICTIONARY_ITEM_NAME_PREFIX`] followed by the hex-encoded BLAKE2b digest of the
+/// seed's address followed by the UTF-8 bytes of `item_key`, so items of different dictionaries
+/// never share a name.
+pub fn dictionary_item_name(dictionary: DictionaryRef, item_key: &str) -> String {
+    let mut preimage = Vec::with_capacity(UREF_ADDR_LENGTH + item_key.len());
+    preimage.extend_from_slice(&dictionary.seed().addr());
+    preimage.extend_from_slice(item_key.as_bytes());
+    format!(
+        "{}{}",
+        DICTIONARY_ITEM_NAME_PREFIX,
+        HexFmt(runtime::blake2b(preimage))
+    )
+}
+
+/// Writes `value` under `item_key` in `dictionary`, replacing any value already stored there.
+pub fn dictionary_put<T: CLTyped + ToBytes>(dictionary: DictionaryRef, item_key: &str, value: T) {
+    let name = dictionary_item_name(dictionary, item_key);
+    match runtime::get_key(&name).and_then(Key::into_uref) {
+        Some(item_uref) => write(item_uref, value),
+        None => {
+            let item_uref = new_uref(value);
+            runtime::put_key(&name, item_uref.into());
+        }
+    }
+}
+
+/// Reads the value under `item_key` in `dictionary`, returning `None` if no value has been stored
+/// there.  Reverts if the stored value is not a `T`.
+pub fn dictionary_get<T: CLTyped + FromBytes>(
+    dictionary: DictionaryRef,
+    item_key: &str,
+) -> Option<T> {
+    let item_uref = runtime::get_key(&dictionary_item_name(dictionary, item_key))?.into_uref()?;
+    read(item_uref).unwrap_or_revert_with(ApiError::Read)
+}
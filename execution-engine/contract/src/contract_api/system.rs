@@ -4,8 +4,9 @@ use alloc::vec::Vec;
 use core::mem::MaybeUninit;
 
 use casperlabs_types::{
-    account::AccountHash, api_error, bytesrepr, ApiError, ContractHash, SystemContractType,
-    TransferResult, TransferredTo, URef, U512, UREF_SERIALIZED_LENGTH,
+    account::AccountHash, api_error, bytesrepr, runtime_args, system_contract_errors::mint,
+    ApiError, ContractHash, RuntimeArgs, SystemContractType, TransferResult, TransferredTo, URef,
+    URefAddr, U512, UREF_SERIALIZED_LENGTH,
 };
 
 use crate::{
@@ -57,6 +58,25 @@ pub fn get_standard_payment() -> ContractHash {
     get_system_contract(SystemContractType::StandardPayment)
 }
 
+/// Returns a read-only pointer to the system contract registered under `name`, e.g. `"mint"`.
+///
+/// Any failure, including there being no system contract of that name, will trigger
+/// [`revert`](runtime::revert) with an appropriate [`ApiError`].
+pub fn get_system_contract_by_name(name: &str) -> ContractHash {
+    let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
+    let mut contract_hash = ContractHash::default();
+    let ret = unsafe {
+        ext_ffi::get_system_contract_by_name(
+            name_ptr,
+            name_size,
+            contract_hash.as_mut_ptr(),
+            contract_hash.len(),
+        )
+    };
+    api_error::result_from(ret).unwrap_or_revert();
+    contract_hash
+}
+
 /// Creates a new empty purse and returns its [`URef`].
 pub fn create_purse() -> URef {
     let purse_non_null_ptr = contract_api::alloc_bytes(UREF_SERIALIZED_LENGTH);
@@ -93,6 +113,17 @@ pub fn get_balance(purse: URef) -> Option<U512> {
     Some(value)
 }
 
+/// Returns the total supply of motes, as tracked by the Mint contract.
+pub fn total_supply() -> U512 {
+    runtime::call_contract(get_mint(), "read_total_supply", RuntimeArgs::new())
+}
+
+/// Returns the number of motes the Mint contract creates as seigniorage for a single round, derived
+/// from the total supply and the round seigniorage rate set at genesis.
+pub fn read_base_round_reward() -> U512 {
+    runtime::call_contract(get_mint(), "round_reward", RuntimeArgs::new())
+}
+
 /// Transfers `amount` of motes from the default purse of the account to `target`
 /// account.  If `target` does not exist it will be created.
 pub fn transfer_to_account(target: AccountHash, amount: U512) -> TransferResult {
@@ -126,8 +157,75 @@ pub fn transfer_from_purse_to_account(
     TransferredTo::result_from(return_code)
 }
 
+/// Transfers `amount` of motes from the default purse of the account to `target` account, as
+/// [`transfer_to_account`] does, and records the transfer along with `id`.
+///
+/// The record is a [`TransferRecord`](casperlabs_types::TransferRecord) stored under
+/// [`TransferRecord::addr`](casperlabs_types::TransferRecord::addr) for the deploy's hash and the
+/// index of the transfer within the deploy.
+///
+/// Only available from protocol version 2.0.0; under earlier versions the contract fails to load.
+pub fn transfer_to_account_with_id(
+    target: AccountHash,
+    amount: U512,
+    id: Option<u64>,
+) -> TransferResult {
+    let (target_ptr, target_size, _bytes1) = contract_api::to_ptr(target);
+    let (amount_ptr, amount_size, _bytes2) = contract_api::to_ptr(amount);
+    let (id_ptr, id_size, _bytes3) = contract_api::to_ptr(id);
+    let return_code = unsafe {
+        ext_ffi::transfer_to_account_with_id(
+            target_ptr,
+            target_size,
+            amount_ptr,
+            amount_size,
+            id_ptr,
+            id_size,
+        )
+    };
+    TransferredTo::result_from(return_code)
+}
+
+/// Transfers `amount` of motes from `source` purse to `target` account, as
+/// [`transfer_from_purse_to_account`] does, and records the transfer along with `id`.
+///
+/// The record is a [`TransferRecord`](casperlabs_types::TransferRecord) stored under
+/// [`TransferRecord::addr`](casperlabs_types::TransferRecord::addr) for the deploy's hash and the
+/// index of the transfer within the deploy.
+///
+/// Only available from protocol version 2.0.0; under earlier versions the contract fails to load.
+pub fn transfer_from_purse_to_account_with_id(
+    source: URef,
+    target: AccountHash,
+    amount: U512,
+    id: Option<u64>,
+) -> TransferResult {
+    let (source_ptr, source_size, _bytes1) = contract_api::to_ptr(source);
+    let (target_ptr, target_size, _bytes2) = contract_api::to_ptr(target);
+    let (amount_ptr, amount_size, _bytes3) = contract_api::to_ptr(amount);
+    let (id_ptr, id_size, _bytes4) = contract_api::to_ptr(id);
+    let return_code = unsafe {
+        ext_ffi::transfer_from_purse_to_account_with_id(
+            source_ptr,
+            source_size,
+            target_ptr,
+            target_size,
+            amount_ptr,
+            amount_size,
+            id_ptr,
+            id_size,
+        )
+    };
+    TransferredTo::result_from(return_code)
+}
+
 /// Transfers `amount` of motes from `source` purse to `target` purse.  If `target` does not exist
 /// the transfer fails.
+///
+/// If the Mint rejects the transfer, the returned error is the [`ApiError::Mint`] variant
+/// corresponding to the Mint's [`Error`](casperlabs_types::system_contract_errors::mint::Error),
+/// e.g. `InsufficientFunds`, `SourceNotFound` or `DestNotFound`.  Any other failure is reported as
+/// [`ApiError::Transfer`].
 pub fn transfer_from_purse_to_purse(
     source: URef,
     target: URef,
@@ -146,9 +244,50 @@ pub fn transfer_from_purse_to_purse(
             amount_size,
         )
     };
-    if result == 0 {
-        Ok(())
-    } else {
-        Err(ApiError::Transfer)
-    }
+    api_error::result_from(result)
+}
+
+/// Allows `spender` to transfer up to `amount` motes out of `purse` via
+/// [`transfer_from_purse_with_allowance`], replacing any allowance previously given to `spender`
+/// for `purse`.
+///
+/// `purse` must be writeable by the caller.  If the Mint rejects the approval, the returned error
+/// is the corresponding [`ApiError::Mint`] variant.
+pub fn approve_transfer(purse: URef, spender: AccountHash, amount: U512) -> Result<(), ApiError> {
+    let args = runtime_args! {
+        "purse" => purse,
+        "spender" => spender,
+        "amount" => amount,
+    };
+    let result: Result<(), mint::Error> = runtime::call_contract(get_mint(), "approve", args);
+    result.map_err(ApiError::from)
+}
+
+/// Returns the number of motes `spender` may still transfer out of the purse with address `owner`.
+pub fn get_allowance(owner: URefAddr, spender: AccountHash) -> U512 {
+    let args = runtime_args! {
+        "owner" => owner,
+        "spender" => spender,
+    };
+    runtime::call_contract(get_mint(), "allowance", args)
+}
+
+/// Transfers `amount` of motes from the purse with address `owner` to `target` purse, spending the
+/// allowance given to the calling account via [`approve_transfer`].
+///
+/// If the Mint rejects the transfer, the returned error is the [`ApiError::Mint`] variant
+/// corresponding to the Mint's [`Error`](casperlabs_types::system_contract_errors::mint::Error),
+/// e.g. `InsufficientAllowance`.
+pub fn transfer_from_purse_with_allowance(
+    owner: URefAddr,
+    target: URef,
+    amount: U512,
+) -> Result<(), ApiError> {
+    let args = runtime_args! {
+        "owner" => owner,
+        "target" => target,
+        "amount" => amount,
+    };
+    let result: Result<(), mint::Error> = runtime::call_contract(get_mint(), "transfer_from", args);
+    result.map_err(ApiError::from)
 }
@@ -41,6 +41,17 @@ extern "C" {
     /// * `value_size` - size of the value (in bytes)
     pub fn write(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize);
 
+    /// Removes the value stored under the provided key (read via de-serializing
+    /// the bytes in wasm memory from offset `key_ptr` to `key_ptr + key_size`)
+    /// from the global state. This function will cause a `Trap` if the key fails
+    /// to de-serialize or if writing to that key is not permitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_ptr` - pointer to bytes representing the key to remove
+    /// * `key_size` - size of the key (in bytes)
+    pub fn remove(key_ptr: *const u8, key_size: usize);
+
     /// The bytes in wasm memory from offset `key_ptr` to `key_ptr + key_size`
     /// will be used together with the current context’s seed to form a local key.
     /// This function writes the provided value (read via de-serializing the bytes
@@ -95,6 +106,23 @@ extern "C" {
     pub fn new_uref(uref_ptr: *mut u8, value_ptr: *const u8, value_size: usize);
     ///
     pub fn load_named_keys(total_keys: *mut usize, result_size: *mut usize) -> i32;
+    /// Writes a page of the current context's named keys to the host buffer, returning a status
+    /// code.  Named keys are ordered by name, and the page consists of at most `max_count` entries
+    /// starting from the entry at `start_index`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_index` - index of the first named key to include in the page
+    /// * `max_count` - maximum number of named keys to include in the page
+    /// * `total_keys` - output parameter: total number of named keys in the current context
+    /// * `result_size` - output parameter: size in bytes of the serialized page, or `0` if the page
+    ///   is empty
+    pub fn load_named_keys_chunk(
+        start_index: usize,
+        max_count: usize,
+        total_keys: *mut usize,
+        result_size: *mut usize,
+    ) -> i32;
     /// This function causes a `Trap`, terminating the currently running module,
     /// but first copies the bytes from `value_ptr` to `value_ptr + value_size` to
     /// a buffer which is returned to the calling module (if this module was
@@ -491,6 +519,10 @@ extern "C" {
     /// case of failure, otherwise a successful execution returns zero. Bytes returned from contract
     /// execution are set to `result_size` pointer.
     ///
+    /// If the runtime arguments don't match the parameters declared by the entry point, the call
+    /// fails with `MissingArgument` or `InvalidArgument` without the contract being run, and the
+    /// name of the offending parameter is written to the host buffer as a `String`.
+    ///
     /// # Arguments
     /// * `contract_package_hash_ptr` - pointer to serialized contract package hash.
     /// * `contract_package_hash_size` - size of contract package hash in serialized form.
@@ -516,6 +548,9 @@ extern "C" {
     /// failure, otherwise a successful execution returns zero. Bytes returned from contract
     /// execution are set to `result_size` pointer
     ///
+    /// The runtime arguments are checked against the entry point's declared parameters as for
+    /// [`call_contract`].
+    ///
     /// # Arguments
     ///
     /// * `contract_package_hash_ptr` - pointer to serialized contract package hash.
@@ -627,6 +662,215 @@ extern "C" {
         urefs_size: usize,
     ) -> i32;
 
+    /// Calculates the BLAKE2b digest of the given input and writes it to the output buffer,
+    /// returning a status code.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_ptr` - pointer to the bytes to be hashed
+    /// * `in_size` - size of the bytes to be hashed
+    /// * `out_ptr` - pointer to the output buffer where the digest will be written
+    /// * `out_size` - size of the output buffer; must be at least
+    ///   [`casperlabs_types::BLAKE2B_DIGEST_LENGTH`]
+    pub fn blake2b(in_ptr: *const u8, in_size: usize, out_ptr: *mut u8, out_size: usize) -> i32;
+
+    /// Verifies an Ed25519 signature of the given message, returning `1` if the signature is valid
+    /// for the given public key, or `0` otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `public_key_ptr` - pointer to the 32-byte public key
+    /// * `public_key_size` - size of the public key
+    /// * `signature_ptr` - pointer to the 64-byte signature
+    /// * `signature_size` - size of the signature
+    /// * `message_ptr` - pointer to the signed message
+    /// * `message_size` - size of the signed message
+    pub fn verify_ed25519(
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+        signature_ptr: *const u8,
+        signature_size: usize,
+        message_ptr: *const u8,
+        message_size: usize,
+    ) -> i32;
+
+    /// Writes the 32-byte hash of the currently executing deploy to the given pointer.  The hash is
+    /// the same in every contract called during the deploy.  It is up to the caller to ensure 32
+    /// bytes are allocated at `dest_ptr`, otherwise data corruption in the wasm memory may occur.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest_ptr` - pointer in wasm memory where to write the result
+    pub fn get_deploy_hash(dest_ptr: *mut u8);
+
+    /// Behaves as [`transfer_to_account`], and additionally records a successful transfer along
+    /// with the given identifier.  The record is written under
+    /// [`casperlabs_types::TransferRecord::addr`] for the deploy's hash and the index of the
+    /// transfer within the deploy.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_ptr` - pointer in wasm memory to bytes representing the target account to transfer
+    ///   to
+    /// * `target_size` - size of the target (in bytes)
+    /// * `amount_ptr` - pointer in wasm memory to bytes representing the amount to transfer to the
+    ///   target account
+    /// * `amount_size` - size of the amount (in bytes)
+    /// * `id_ptr` - pointer in wasm memory to bytes representing the optional `u64` identifier of
+    ///   the transfer
+    /// * `id_size` - size of the identifier (in bytes)
+    pub fn transfer_to_account_with_id(
+        target_ptr: *const u8,
+        target_size: usize,
+        amount_ptr: *const u8,
+        amount_size: usize,
+        id_ptr: *const u8,
+        id_size: usize,
+    ) -> i32;
+
+    /// Behaves as [`transfer_from_purse_to_account`], and additionally records a successful
+    /// transfer along with the given identifier.  The record is written under
+    /// [`casperlabs_types::TransferRecord::addr`] for the deploy's hash and the index of the
+    /// transfer within the deploy.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_ptr` - pointer in wasm memory to bytes representing the source
+    ///   [`casperlabs_types::uref::URef`] to transfer from
+    /// * `source_size` - size of the source [`casperlabs_types::uref::URef`] (in bytes)
+    /// * `target_ptr` - pointer in wasm memory to bytes representing the target account to transfer
+    ///   to
+    /// * `target_size` - size of the target (in bytes)
+    /// * `amount_ptr` - pointer in wasm memory to bytes representing the amount to transfer to the
+    ///   target account
+    /// * `amount_size` - size of the amount (in bytes)
+    /// * `id_ptr` - pointer in wasm memory to bytes representing the optional `u64` identifier of
+    ///   the transfer
+    /// * `id_size` - size of the identifier (in bytes)
+    pub fn transfer_from_purse_to_account_with_id(
+        source_ptr: *const u8,
+        source_size: usize,
+        target_ptr: *const u8,
+        target_size: usize,
+        amount_ptr: *const u8,
+        amount_size: usize,
+        id_ptr: *const u8,
+        id_size: usize,
+    ) -> i32;
+
+    /// Writes a new 32-byte address to the given pointer.  Addresses are generated from the same
+    /// deterministic stream as the hashes of new contracts, so they are unique within a deploy and
+    /// replaying the deploy yields the same sequence.  It is up to the caller to ensure 32 bytes
+    /// are allocated at `dest_ptr`, otherwise data corruption in the wasm memory may occur.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest_ptr` - pointer in wasm memory where to write the result
+    pub fn new_hash_address(dest_ptr: *mut u8);
+
+    /// This function uses the mint contract to create a new, empty purse and stores its
+    /// [`casperlabs_types::uref::URef`] in the current context's named keys under the given name.
+    /// If the call is successful then the `URef` (in serialized form) is written to the indicated
+    /// place in wasm memory.  No purse is created if a named key with the given name already
+    /// exists, in which case [`casperlabs_types::ApiError::NamedKeyExists`] is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_ptr` - pointer to the serialized name of the new named key
+    /// * `name_size` - size of the serialized name
+    /// * `purse_ptr` - pointer to position in wasm memory where to write the created
+    ///   [`casperlabs_types::uref::URef`]
+    /// * `purse_size` - allocated size for the [`casperlabs_types::uref::URef`]
+    pub fn create_named_purse(
+        name_ptr: *const u8,
+        name_size: usize,
+        purse_ptr: *const u8,
+        purse_size: usize,
+    ) -> i32;
+
+    /// This function records an event with the given topic and payload, attributed to the current
+    /// context.  Events are reported in the deploy's execution effect and are not written to
+    /// global state.  Nothing is recorded if the deploy's event count or total event size limit
+    /// would be exceeded, in which case [`casperlabs_types::ApiError::EventLimitExceeded`] is
+    /// returned.  May not be called from payment code.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic_ptr` - pointer to the serialized topic of the event
+    /// * `topic_size` - size of the serialized topic
+    /// * `payload_ptr` - pointer to the payload bytes of the event
+    /// * `payload_size` - size of the payload
+    pub fn emit_event(
+        topic_ptr: *const u8,
+        topic_size: usize,
+        payload_ptr: *const u8,
+        payload_size: usize,
+    ) -> i32;
+
+    /// Returns the current value of the account's threshold for the given action type.  Causes a
+    /// `Trap` if `action_type` is not a valid [`casperlabs_types::account::ActionType`].
+    ///
+    /// # Arguments
+    ///
+    /// * `action_type` - index representing the action threshold to get
+    pub fn get_action_threshold(action_type: u32) -> i32;
+    /// Writes the account's associated keys with their weights, serialized as a list of
+    /// [`casperlabs_types::account::AccountHash`] and [`casperlabs_types::account::Weight`] pairs,
+    /// to the host buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_size` - pointer to a value where host will write size of the serialized list
+    pub fn list_associated_keys(output_size: *mut usize) -> i32;
+    /// Writes the contract package's user groups, serialized as a map from group label to the
+    /// list of [`casperlabs_types::URef`]s in the group, to the host buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `contract_package_hash_ptr` - pointer to serialized contract package hash.
+    /// * `contract_package_hash_size` - size of contract package hash in serialized form.
+    /// * `access_key_ptr` - pointer to serialized access key of the contract package
+    /// * `access_key_size` - size of serialized access key
+    /// * `output_size` - pointer to a value where host will write size of the serialized map
+    pub fn list_contract_user_groups(
+        contract_package_hash_ptr: *const u8,
+        contract_package_hash_size: usize,
+        access_key_ptr: *const u8,
+        access_key_size: usize,
+        output_size: *mut usize,
+    ) -> i32;
+    /// Writes the keys which authorized the current deploy, serialized as a list of
+    /// [`casperlabs_types::account::AccountHash`]es, to the host buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_size` - pointer to a value where host will write size of the serialized list
+    pub fn list_authorization_keys(output_size: *mut usize) -> i32;
+    /// Writes the hash of the system contract registered under the given name to `dest_ptr`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_ptr` - pointer to the serialized name of the system contract
+    /// * `name_size` - size of the serialized name
+    /// * `dest_ptr` - pointer to position in wasm memory to write the contract hash
+    /// * `dest_size` - size of the buffer at `dest_ptr`
+    pub fn get_system_contract_by_name(
+        name_ptr: *const u8,
+        name_size: usize,
+        dest_ptr: *mut u8,
+        dest_size: usize,
+    ) -> i32;
+    /// Returns 1 if the given account hashes together meet the account's threshold for the given
+    /// action type, or 0 if they don't.  Causes a `Trap` if `action_type` is not a valid
+    /// [`casperlabs_types::account::ActionType`].
+    ///
+    /// # Arguments
+    ///
+    /// * `keys_ptr` - pointer to a serialized list of [`casperlabs_types::account::AccountHash`]es
+    /// * `keys_size` - size of the serialized list
+    /// * `action_type` - index representing the action threshold to check against
+    pub fn can_authorize(keys_ptr: *const u8, keys_size: usize, action_type: u32) -> i32;
+
     /// Prints data directly to stanadard output on the host.
     ///
     /// # Arguments
@@ -2,7 +2,7 @@
 
 use casperlabs_types::ApiError;
 
-use crate::contract_api::runtime;
+use crate::contract_api::runtime::{self, ArgError};
 
 /// A trait which provides syntactic sugar for unwrapping a type or calling
 /// [`runtime::revert`] if this fails.  It is implemented for `Result` and `Option`.
@@ -26,6 +26,18 @@ impl<T, E: Into<ApiError>> UnwrapOrRevert<T> for Result<T, E> {
     }
 }
 
+impl<T> UnwrapOrRevert<T> for Result<T, ArgError> {
+    /// Unwraps the value or calls [`ArgError::revert`], which prints the error's details if the
+    /// `test-support` feature is enabled.
+    fn unwrap_or_revert(self) -> T {
+        self.unwrap_or_else(|error| error.revert())
+    }
+
+    fn unwrap_or_revert_with<E: Into<ApiError>>(self, error: E) -> T {
+        self.unwrap_or_else(|_| runtime::revert(error.into()))
+    }
+}
+
 impl<T> UnwrapOrRevert<T> for Option<T> {
     fn unwrap_or_revert(self) -> T {
         self.unwrap_or_else(|| runtime::revert(ApiError::None))
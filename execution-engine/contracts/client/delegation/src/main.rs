@@ -0,0 +1,48 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const DELEGATE_METHOD_NAME: &str = "delegate";
+
+const ARG_VALIDATOR: &str = "validator";
+const ARG_AMOUNT: &str = "amount";
+const ARG_PURSE: &str = "purse";
+
+// Delegation contract.
+//
+// Accepts the validator's account hash and the delegated amount (of type `U512`) as arguments.
+// Issues delegation request to the PoS contract.
+#[no_mangle]
+pub extern "C" fn call() {
+    let validator: AccountHash = runtime::get_named_arg(ARG_VALIDATOR);
+    // get delegation amount arg
+    let delegation_amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+
+    // provision delegation purse
+    let delegation_purse = {
+        let delegation_purse = system::create_purse();
+        let source_purse = account::get_main_purse();
+        // transfer amount to be delegated to delegation purse
+        system::transfer_from_purse_to_purse(source_purse, delegation_purse, delegation_amount)
+            .unwrap_or_revert();
+        delegation_purse
+    };
+
+    // delegate
+    {
+        let contract_hash = system::get_proof_of_stake();
+        let args = runtime_args! {
+            ARG_VALIDATOR => validator,
+            ARG_AMOUNT => delegation_amount,
+            ARG_PURSE => delegation_purse,
+        };
+        runtime::call_contract(contract_hash, DELEGATE_METHOD_NAME, args)
+    }
+}
@@ -0,0 +1,29 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::account::{AccountHash, Weight};
+
+const ARG_NEW_KEY: &str = "new_key";
+const ARG_WEIGHT: &str = "weight";
+
+// Key rotation contract.
+//
+// Associates `new_key` with the account at `weight`, then demotes the key which signed this deploy
+// (normally the account's original identity key) to weight zero. The old key stays associated, so
+// it remains visible on the account, but on its own it can no longer meet any non-zero threshold.
+//
+// `weight` must be high enough for the remaining keys to still meet the account's deployment and
+// key management thresholds, otherwise the demotion reverts with a threshold violation.
+#[no_mangle]
+pub extern "C" fn call() {
+    let new_key: AccountHash = runtime::get_named_arg(ARG_NEW_KEY);
+    let weight: Weight = runtime::get_named_arg(ARG_WEIGHT);
+    let old_key = runtime::get_caller();
+
+    account::add_associated_key(new_key, weight).unwrap_or_revert();
+    account::update_associated_key(old_key, Weight::new(0)).unwrap_or_revert();
+}
@@ -0,0 +1,32 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use contract::contract_api::{runtime, system};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const UNDELEGATE_METHOD_NAME: &str = "undelegate";
+const ARG_VALIDATOR: &str = "validator";
+const ARG_AMOUNT: &str = "amount";
+
+// Undelegation contract.
+//
+// Accepts the validator's account hash and the undelegation amount (of type `Option<u64>`) as
+// arguments.
+// Undelegating with `None` withdraws the whole stake delegated to the validator.
+// Otherwise (`Some<u64>`) withdraws part of the delegated stake.
+// The withdrawn stake is paid back to the caller's main purse.
+#[no_mangle]
+pub extern "C" fn call() {
+    let validator: AccountHash = runtime::get_named_arg(ARG_VALIDATOR);
+    let undelegate_amount: Option<U512> =
+        runtime::get_named_arg::<Option<u64>>(ARG_AMOUNT).map(Into::into);
+
+    let contract_hash = system::get_proof_of_stake();
+    let args = runtime_args! {
+        ARG_VALIDATOR => validator,
+        ARG_AMOUNT => undelegate_amount,
+    };
+    runtime::call_contract(contract_hash, UNDELEGATE_METHOD_NAME, args)
+}
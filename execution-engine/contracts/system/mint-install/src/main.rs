@@ -1,14 +1,20 @@
 #![no_std]
 #![no_main]
 
+extern crate alloc;
+
+use alloc::string::ToString;
+
 use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{contracts::NamedKeys, CLValue};
+use mint_token::{ROUND_SEIGNIORAGE_RATE_KEY, TOTAL_SUPPLY_KEY};
+use types::{contracts::NamedKeys, CLValue, U512};
 
 const HASH_KEY_NAME: &str = "mint_hash";
 const ACCESS_KEY_NAME: &str = "mint_access";
+const ARG_ROUND_SEIGNIORAGE_RATE: &str = "round_seigniorage_rate";
 
 #[no_mangle]
 pub extern "C" fn mint() {
@@ -30,15 +36,43 @@ pub extern "C" fn transfer() {
     mint_token::transfer();
 }
 
+#[no_mangle]
+pub extern "C" fn burn() {
+    mint_token::burn();
+}
+
+#[no_mangle]
+pub extern "C" fn read_total_supply() {
+    mint_token::read_total_supply();
+}
+
+#[no_mangle]
+pub extern "C" fn round_reward() {
+    mint_token::round_reward();
+}
+
+#[no_mangle]
+pub extern "C" fn mint_into() {
+    mint_token::mint_into();
+}
+
 #[no_mangle]
 pub extern "C" fn install() {
+    let round_seigniorage_rate: (u64, u64) = runtime::get_named_arg(ARG_ROUND_SEIGNIORAGE_RATE);
     let entry_points = mint_token::get_entry_points();
 
     let (contract_package_hash, access_uref) = storage::create_contract_package_at_hash();
     runtime::put_key(HASH_KEY_NAME, contract_package_hash.into());
     runtime::put_key(ACCESS_KEY_NAME, access_uref.into());
 
-    let named_keys = NamedKeys::new();
+    let mut named_keys = NamedKeys::new();
+    let total_supply_uref = storage::new_uref(U512::zero());
+    named_keys.insert(TOTAL_SUPPLY_KEY.to_string(), total_supply_uref.into());
+    let round_seigniorage_rate_uref = storage::new_uref(round_seigniorage_rate);
+    named_keys.insert(
+        ROUND_SEIGNIORAGE_RATE_KEY.to_string(),
+        round_seigniorage_rate_uref.into(),
+    );
 
     let (contract_key, _contract_version) =
         storage::add_contract_version(contract_package_hash, entry_points, named_keys);
@@ -20,3 +20,38 @@ pub extern "C" fn balance() {
 pub extern "C" fn transfer() {
     mint_token::transfer();
 }
+
+#[no_mangle]
+pub extern "C" fn burn() {
+    mint_token::burn();
+}
+
+#[no_mangle]
+pub extern "C" fn read_total_supply() {
+    mint_token::read_total_supply();
+}
+
+#[no_mangle]
+pub extern "C" fn approve() {
+    mint_token::approve();
+}
+
+#[no_mangle]
+pub extern "C" fn allowance() {
+    mint_token::allowance();
+}
+
+#[no_mangle]
+pub extern "C" fn transfer_from() {
+    mint_token::transfer_from();
+}
+
+#[no_mangle]
+pub extern "C" fn round_reward() {
+    mint_token::round_reward();
+}
+
+#[no_mangle]
+pub extern "C" fn mint_into() {
+    mint_token::mint_into();
+}
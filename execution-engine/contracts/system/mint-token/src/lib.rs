@@ -10,24 +10,34 @@ use contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use mint::{Mint, RuntimeProvider, StorageProvider};
+pub use mint::{ROUND_SEIGNIORAGE_RATE_KEY, TOTAL_SUPPLY_KEY};
 use types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     contracts::Parameters,
     system_contract_errors::mint::Error,
     CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key,
-    Parameter, URef, U512,
+    Parameter, URef, URefAddr, U512,
 };
 
 pub const METHOD_MINT: &str = "mint";
 pub const METHOD_CREATE: &str = "create";
 pub const METHOD_BALANCE: &str = "balance";
 pub const METHOD_TRANSFER: &str = "transfer";
+pub const METHOD_BURN: &str = "burn";
+pub const METHOD_READ_TOTAL_SUPPLY: &str = "read_total_supply";
+pub const METHOD_APPROVE: &str = "approve";
+pub const METHOD_ALLOWANCE: &str = "allowance";
+pub const METHOD_TRANSFER_FROM: &str = "transfer_from";
+pub const METHOD_ROUND_REWARD: &str = "round_reward";
+pub const METHOD_MINT_INTO: &str = "mint_into";
 
 pub const ARG_AMOUNT: &str = "amount";
 pub const ARG_PURSE: &str = "purse";
 pub const ARG_SOURCE: &str = "source";
 pub const ARG_TARGET: &str = "target";
+pub const ARG_OWNER: &str = "owner";
+pub const ARG_SPENDER: &str = "spender";
 
 pub struct MintContract;
 
@@ -36,6 +46,10 @@ impl RuntimeProvider for MintContract {
         runtime::get_caller()
     }
 
+    fn get_key(&self, name: &str) -> Option<Key> {
+        runtime::get_key(name)
+    }
+
     fn put_key(&mut self, name: &str, key: Key) {
         runtime::put_key(name, key)
     }
@@ -70,6 +84,14 @@ impl StorageProvider for MintContract {
         storage::add(uref, value);
         Ok(())
     }
+
+    fn sub(&mut self, uref: URef, amount: U512) -> Result<(), Error> {
+        let balance: U512 = self.read(uref)?.ok_or(Error::Storage)?;
+        let new_balance = balance
+            .checked_sub(amount)
+            .ok_or(Error::InsufficientFunds)?;
+        self.write(uref, new_balance)
+    }
 }
 
 impl Mint for MintContract {}
@@ -107,6 +129,67 @@ pub fn transfer() {
     runtime::ret(ret);
 }
 
+pub fn burn() {
+    let mut mint_contract = MintContract;
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let result: Result<(), Error> = mint_contract.burn(purse, amount);
+    let ret = CLValue::from_t(result).unwrap_or_revert();
+    runtime::ret(ret);
+}
+
+pub fn read_total_supply() {
+    let mut mint_contract = MintContract;
+    let total_supply: U512 = mint_contract.read_total_supply().unwrap_or_revert();
+    let ret = CLValue::from_t(total_supply).unwrap_or_revert();
+    runtime::ret(ret);
+}
+
+pub fn approve() {
+    let mut mint_contract = MintContract;
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
+    let spender: AccountHash = runtime::get_named_arg(ARG_SPENDER);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let result: Result<(), Error> = mint_contract.approve(purse, spender, amount);
+    let ret = CLValue::from_t(result).unwrap_or_revert();
+    runtime::ret(ret);
+}
+
+pub fn allowance() {
+    let mut mint_contract = MintContract;
+    let owner: URefAddr = runtime::get_named_arg(ARG_OWNER);
+    let spender: AccountHash = runtime::get_named_arg(ARG_SPENDER);
+    let allowance: U512 = mint_contract.allowance(owner, spender).unwrap_or_revert();
+    let ret = CLValue::from_t(allowance).unwrap_or_revert();
+    runtime::ret(ret);
+}
+
+pub fn transfer_from() {
+    let mut mint_contract = MintContract;
+    let owner: URefAddr = runtime::get_named_arg(ARG_OWNER);
+    let target: URef = runtime::get_named_arg(ARG_TARGET);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let result: Result<(), Error> = mint_contract.transfer_from(owner, target, amount);
+    let ret = CLValue::from_t(result).unwrap_or_revert();
+    runtime::ret(ret);
+}
+
+pub fn round_reward() {
+    let mut mint_contract = MintContract;
+    let round_reward: U512 = mint_contract.round_reward().unwrap_or_revert();
+    let ret = CLValue::from_t(round_reward).unwrap_or_revert();
+    runtime::ret(ret);
+}
+
+pub fn mint_into() {
+    let mut mint_contract = MintContract;
+    let purse: URef = runtime::get_named_arg(ARG_PURSE);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let result: Result<(), Error> = mint_contract.mint_into(purse, amount);
+    let ret = CLValue::from_t(result).unwrap_or_revert();
+    runtime::ret(ret);
+}
+
 pub fn get_entry_points() -> EntryPoints {
     let mut entry_points = EntryPoints::new();
 
@@ -156,5 +239,97 @@ pub fn get_entry_points() -> EntryPoints {
     );
     entry_points.add_entry_point(entry_point);
 
+    let entry_point = EntryPoint::new(
+        METHOD_BURN,
+        vec![
+            Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_READ_TOTAL_SUPPLY,
+        Parameters::new(),
+        CLType::U512,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_APPROVE,
+        vec![
+            Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_SPENDER, AccountHash::cl_type()),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_ALLOWANCE,
+        vec![
+            Parameter::new(ARG_OWNER, URefAddr::cl_type()),
+            Parameter::new(ARG_SPENDER, AccountHash::cl_type()),
+        ],
+        CLType::U512,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_TRANSFER_FROM,
+        vec![
+            Parameter::new(ARG_OWNER, URefAddr::cl_type()),
+            Parameter::new(ARG_TARGET, CLType::URef),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_ROUND_REWARD,
+        Parameters::new(),
+        CLType::U512,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_MINT_INTO,
+        vec![
+            Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
     entry_points
 }
@@ -11,8 +11,12 @@ use contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use pos::{
-    ARG_ACCOUNT_KEY, ARG_AMOUNT, ARG_PURSE, METHOD_BOND, METHOD_FINALIZE_PAYMENT,
-    METHOD_GET_PAYMENT_PURSE, METHOD_GET_REFUND_PURSE, METHOD_SET_REFUND_PURSE, METHOD_UNBOND,
+    ARG_ACCOUNT_KEY, ARG_AMOUNT, ARG_GAS_PRICE, ARG_PURSE, ARG_VALIDATOR,
+    ARG_VALIDATOR_PUBLIC_KEYS, GAS_PRICE_KEY, MAX_BOND_AMOUNT_KEY, METHOD_BOND, METHOD_DELEGATE,
+    METHOD_DISTRIBUTE_REWARDS, METHOD_FINALIZE_PAYMENT, METHOD_GET_PAYMENT_PURSE,
+    METHOD_GET_REFUND_PURSE, METHOD_PROCESS_UNBOND_REQUESTS, METHOD_SET_GAS_PRICE,
+    METHOD_SET_REFUND_PURSE, METHOD_SLASH, METHOD_UNBOND, METHOD_UNDELEGATE, MIN_BOND_AMOUNT_KEY,
+    UNBONDING_DELAY_KEY,
 };
 use proof_of_stake::Stakes;
 use types::{
@@ -33,6 +37,9 @@ const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
 
 const ARG_MINT_PACKAGE_HASH: &str = "mint_contract_package_hash";
 const ARG_GENESIS_VALIDATORS: &str = "genesis_validators";
+const ARG_UNBONDING_DELAY_MILLIS: &str = "unbonding_delay_millis";
+const ARG_MIN_BOND_AMOUNT: &str = "min_bond_amount";
+const ARG_MAX_BOND_AMOUNT: &str = "max_bond_amount";
 const ENTRY_POINT_MINT: &str = "mint";
 
 const HASH_KEY_NAME: &str = "pos_hash";
@@ -48,6 +55,26 @@ pub extern "C" fn unbond() {
     pos::unbond();
 }
 
+#[no_mangle]
+pub extern "C" fn delegate() {
+    pos::delegate();
+}
+
+#[no_mangle]
+pub extern "C" fn undelegate() {
+    pos::undelegate();
+}
+
+#[no_mangle]
+pub extern "C" fn process_unbond_requests() {
+    pos::process_unbond_requests();
+}
+
+#[no_mangle]
+pub extern "C" fn slash() {
+    pos::slash();
+}
+
 #[no_mangle]
 pub extern "C" fn get_payment_purse() {
     pos::get_payment_purse();
@@ -68,11 +95,25 @@ pub extern "C" fn finalize_payment() {
     pos::finalize_payment();
 }
 
+#[no_mangle]
+pub extern "C" fn distribute_rewards() {
+    pos::distribute_rewards();
+}
+
+#[no_mangle]
+pub extern "C" fn set_gas_price() {
+    pos::set_gas_price();
+}
+
 #[no_mangle]
 pub extern "C" fn install() {
     let mint_package_hash: ContractPackageHash = runtime::get_named_arg(ARG_MINT_PACKAGE_HASH);
     let genesis_validators: BTreeMap<AccountHash, U512> =
         runtime::get_named_arg(ARG_GENESIS_VALIDATORS);
+    let unbonding_delay_millis: u64 = runtime::get_named_arg(ARG_UNBONDING_DELAY_MILLIS);
+    let gas_price: u64 = runtime::get_named_arg(ARG_GAS_PRICE);
+    let min_bond_amount: U512 = runtime::get_named_arg(ARG_MIN_BOND_AMOUNT);
+    let max_bond_amount: U512 = runtime::get_named_arg(ARG_MAX_BOND_AMOUNT);
 
     let stakes = Stakes::new(genesis_validators);
 
@@ -96,6 +137,22 @@ pub extern "C" fn install() {
     .for_each(|(name, uref)| {
         named_keys.insert(String::from(*name), Key::URef(*uref));
     });
+    named_keys.insert(
+        String::from(UNBONDING_DELAY_KEY),
+        storage::new_uref(unbonding_delay_millis).into(),
+    );
+    named_keys.insert(
+        String::from(GAS_PRICE_KEY),
+        storage::new_uref(gas_price).into(),
+    );
+    named_keys.insert(
+        String::from(MIN_BOND_AMOUNT_KEY),
+        storage::new_uref(min_bond_amount).into(),
+    );
+    named_keys.insert(
+        String::from(MAX_BOND_AMOUNT_KEY),
+        storage::new_uref(max_bond_amount).into(),
+    );
 
     let entry_points = {
         let mut entry_points = EntryPoints::new();
@@ -121,6 +178,52 @@ pub extern "C" fn install() {
         );
         entry_points.add_entry_point(unbond);
 
+        let delegate = EntryPoint::new(
+            METHOD_DELEGATE.to_string(),
+            vec![
+                Parameter::new(ARG_VALIDATOR, CLType::FixedList(Box::new(CLType::U8), 32)),
+                Parameter::new(ARG_AMOUNT, CLType::U512),
+                Parameter::new(ARG_PURSE, CLType::URef),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(delegate);
+
+        let undelegate = EntryPoint::new(
+            METHOD_UNDELEGATE.to_string(),
+            vec![
+                Parameter::new(ARG_VALIDATOR, CLType::FixedList(Box::new(CLType::U8), 32)),
+                Parameter::new(ARG_AMOUNT, CLType::Option(Box::new(CLType::U512))),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(undelegate);
+
+        let process_unbond_requests = EntryPoint::new(
+            METHOD_PROCESS_UNBOND_REQUESTS.to_string(),
+            vec![],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(process_unbond_requests);
+
+        let slash = EntryPoint::new(
+            METHOD_SLASH.to_string(),
+            vec![Parameter::new(
+                ARG_VALIDATOR_PUBLIC_KEYS,
+                CLType::List(Box::new(CLType::FixedList(Box::new(CLType::U8), 32))),
+            )],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(slash);
+
         let get_payment_purse = EntryPoint::new(
             METHOD_GET_PAYMENT_PURSE.to_string(),
             vec![],
@@ -160,6 +263,27 @@ pub extern "C" fn install() {
         );
         entry_points.add_entry_point(finalize_payment);
 
+        let distribute_rewards = EntryPoint::new(
+            METHOD_DISTRIBUTE_REWARDS.to_string(),
+            vec![Parameter::new(
+                ARG_VALIDATOR,
+                CLType::FixedList(Box::new(CLType::U8), 32),
+            )],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(distribute_rewards);
+
+        let set_gas_price = EntryPoint::new(
+            METHOD_SET_GAS_PRICE.to_string(),
+            vec![Parameter::new(ARG_GAS_PRICE, CLType::U64)],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(set_gas_price);
+
         entry_points
     };
 
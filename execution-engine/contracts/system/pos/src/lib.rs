@@ -5,6 +5,7 @@ extern crate alloc;
 use alloc::{
     collections::{BTreeMap, BTreeSet},
     string::String,
+    vec::Vec,
 };
 
 use contract::{
@@ -12,26 +13,53 @@ use contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 use proof_of_stake::{
-    MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, Stakes, StakesProvider,
+    Delegations, DelegationsProvider, GasPriceProvider, MintProvider, ProofOfStake, Queue,
+    QueueProvider, RuntimeProvider, Stakes, StakesProvider,
 };
 use types::{
-    account::AccountHash, system_contract_errors::pos::Error, ApiError, BlockTime, CLValue, Key,
-    Phase, TransferResult, URef, U512,
+    account::AccountHash,
+    runtime_args,
+    system_contract_errors::{mint, pos::Error},
+    ApiError, BlockTime, CLValue, Key, Phase, RuntimeArgs, TransferResult, URef, U512,
 };
 
 pub const METHOD_BOND: &str = "bond";
 pub const METHOD_UNBOND: &str = "unbond";
+pub const METHOD_DELEGATE: &str = "delegate";
+pub const METHOD_UNDELEGATE: &str = "undelegate";
+pub const METHOD_PROCESS_UNBOND_REQUESTS: &str = "process_unbond_requests";
+pub const METHOD_SLASH: &str = "slash";
 pub const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
 pub const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
 pub const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
 pub const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
+pub const METHOD_DISTRIBUTE_REWARDS: &str = "distribute_rewards";
+pub const METHOD_SET_GAS_PRICE: &str = "set_gas_price";
+
+/// The mint's method for creating new motes in a purse.
+const METHOD_MINT_INTO: &str = "mint_into";
 
 const BONDING_KEY: u8 = 1;
 const UNBONDING_KEY: u8 = 2;
 
+/// The name of the key under which the unbonding delay in milliseconds is stored.
+pub const UNBONDING_DELAY_KEY: &str = "unbonding_delay_millis";
+
+/// The name of the key under which the number of motes charged per unit of gas is stored.
+pub const GAS_PRICE_KEY: &str = "gas_price";
+
+/// The name of the key under which the smallest nonzero stake a validator may hold is stored.
+pub const MIN_BOND_AMOUNT_KEY: &str = "min_bond_amount";
+
+/// The name of the key under which the largest stake a validator may hold is stored.
+pub const MAX_BOND_AMOUNT_KEY: &str = "max_bond_amount";
+
 pub const ARG_AMOUNT: &str = "amount";
 pub const ARG_PURSE: &str = "purse";
 pub const ARG_ACCOUNT_KEY: &str = "account";
+pub const ARG_VALIDATOR: &str = "validator";
+pub const ARG_VALIDATOR_PUBLIC_KEYS: &str = "validator_public_keys";
+pub const ARG_GAS_PRICE: &str = "gas_price";
 
 pub struct ProofOfStakeContract;
 
@@ -57,6 +85,20 @@ impl MintProvider for ProofOfStakeContract {
     fn balance(&mut self, purse: URef) -> Option<U512> {
         system::get_balance(purse)
     }
+
+    fn round_reward(&mut self) -> Result<U512, ()> {
+        Ok(system::read_base_round_reward())
+    }
+
+    fn mint_into(&mut self, purse: URef, amount: U512) -> Result<(), ()> {
+        let args = runtime_args! {
+            ARG_PURSE => purse,
+            ARG_AMOUNT => amount,
+        };
+        let result: Result<(), mint::Error> =
+            runtime::call_contract(system::get_mint(), METHOD_MINT_INTO, args);
+        result.map_err(|_| ())
+    }
 }
 
 impl QueueProvider for ProofOfStakeContract {
@@ -83,6 +125,24 @@ impl QueueProvider for ProofOfStakeContract {
     fn write_unbonding(&mut self, queue: Queue) {
         storage::write_local(UNBONDING_KEY, queue);
     }
+
+    /// Reads the unbonding delay from the contract's known urefs, defaulting to `0` if unset.
+    fn read_unbonding_delay(&mut self) -> u64 {
+        runtime::get_key(UNBONDING_DELAY_KEY)
+            .and_then(Key::into_uref)
+            .and_then(|uref| storage::read(uref).unwrap_or_default())
+            .unwrap_or_default()
+    }
+}
+
+impl GasPriceProvider for ProofOfStakeContract {
+    /// Writes the gas price to the contract's known urefs, creating the uref if it is unset.
+    fn write_gas_price(&mut self, gas_price: u64) {
+        match runtime::get_key(GAS_PRICE_KEY).and_then(Key::into_uref) {
+            Some(uref) => storage::write(uref, gas_price),
+            None => runtime::put_key(GAS_PRICE_KEY, storage::new_uref(gas_price).into()),
+        }
+    }
 }
 
 impl RuntimeProvider for ProofOfStakeContract {
@@ -109,6 +169,10 @@ impl RuntimeProvider for ProofOfStakeContract {
     fn get_caller(&self) -> AccountHash {
         runtime::get_caller()
     }
+
+    fn is_valid_uref(&self, uref: URef) -> bool {
+        runtime::is_valid_uref(uref)
+    }
 }
 
 impl StakesProvider for ProofOfStakeContract {
@@ -157,6 +221,46 @@ impl StakesProvider for ProofOfStakeContract {
             runtime::put_key(&name, Key::Hash([0; 32]));
         }
     }
+
+    /// Reads the minimum bond amount from the contract's known urefs, defaulting to `0` if unset.
+    fn read_min_bond_amount(&mut self) -> U512 {
+        runtime::get_key(MIN_BOND_AMOUNT_KEY)
+            .and_then(Key::into_uref)
+            .and_then(|uref| storage::read(uref).unwrap_or_default())
+            .unwrap_or_default()
+    }
+
+    /// Reads the maximum bond amount from the contract's known urefs, defaulting to `U512::MAX` if
+    /// unset.
+    fn read_max_bond_amount(&mut self) -> U512 {
+        runtime::get_key(MAX_BOND_AMOUNT_KEY)
+            .and_then(Key::into_uref)
+            .and_then(|uref| storage::read(uref).unwrap_or_default())
+            .unwrap_or(U512::MAX)
+    }
+}
+
+impl DelegationsProvider for ProofOfStakeContract {
+    /// Reads the current delegations from the contract's known urefs.
+    fn read_delegations(&self) -> Result<Delegations, Error> {
+        let named_keys = runtime::list_named_keys();
+        Delegations::from_strings(named_keys.keys().map(String::as_str))
+    }
+
+    /// Writes the current delegations to the contract's known urefs.
+    fn write_delegations(&mut self, delegations: &Delegations) {
+        // Encode the delegations as a set of uref names.
+        let mut new_urefs: BTreeSet<String> = delegations.strings().collect();
+        // Remove and add urefs to update the contract's known urefs accordingly.
+        for (name, _) in runtime::list_named_keys() {
+            if name.starts_with("d_") && !new_urefs.remove(&name) {
+                runtime::remove_key(&name);
+            }
+        }
+        for name in new_urefs {
+            runtime::put_key(&name, Key::Hash([0; 32]));
+        }
+    }
 }
 
 impl ProofOfStake for ProofOfStakeContract {}
@@ -191,6 +295,50 @@ pub fn unbond() {
         .unwrap_or_revert();
 }
 
+pub fn delegate() {
+    if !cfg!(feature = "enable-bonding") {
+        runtime::revert(ApiError::Unhandled)
+    }
+
+    let delegator = runtime::get_caller();
+    let validator: AccountHash = runtime::get_named_arg(ARG_VALIDATOR);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    // source purse
+    let source: URef = runtime::get_named_arg(ARG_PURSE);
+
+    let mut pos_contract = ProofOfStakeContract;
+    pos_contract
+        .delegate(delegator, validator, amount, source)
+        .unwrap_or_revert();
+}
+
+pub fn undelegate() {
+    if !cfg!(feature = "enable-bonding") {
+        runtime::revert(ApiError::Unhandled)
+    }
+
+    let delegator = runtime::get_caller();
+    let validator: AccountHash = runtime::get_named_arg(ARG_VALIDATOR);
+    let maybe_amount = runtime::get_named_arg(ARG_AMOUNT);
+
+    let mut pos_contract = ProofOfStakeContract;
+    pos_contract
+        .undelegate(delegator, validator, maybe_amount)
+        .unwrap_or_revert();
+}
+
+pub fn process_unbond_requests() {
+    let mut pos_contract = ProofOfStakeContract;
+    pos_contract.process_unbond_requests().unwrap_or_revert();
+}
+
+pub fn slash() {
+    let validators: Vec<AccountHash> = runtime::get_named_arg(ARG_VALIDATOR_PUBLIC_KEYS);
+
+    let mut pos_contract = ProofOfStakeContract;
+    pos_contract.slash(validators).unwrap_or_revert();
+}
+
 pub fn get_payment_purse() {
     let pos_contract = ProofOfStakeContract;
     let rights_controlled_purse = pos_contract.get_payment_purse().unwrap_or_revert();
@@ -226,3 +374,19 @@ pub fn finalize_payment() {
         .finalize_payment(amount_spent, account)
         .unwrap_or_revert();
 }
+
+pub fn distribute_rewards() {
+    let mut pos_contract = ProofOfStakeContract;
+
+    let validator: AccountHash = runtime::get_named_arg(ARG_VALIDATOR);
+    pos_contract
+        .distribute_rewards(validator)
+        .unwrap_or_revert();
+}
+
+pub fn set_gas_price() {
+    let mut pos_contract = ProofOfStakeContract;
+
+    let gas_price: u64 = runtime::get_named_arg(ARG_GAS_PRICE);
+    pos_contract.set_gas_price(gas_price).unwrap_or_revert();
+}
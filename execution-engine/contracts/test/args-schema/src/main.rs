@@ -0,0 +1,150 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec};
+use core::mem::MaybeUninit;
+
+use contract::{
+    contract_api::{runtime, storage},
+    ext_ffi,
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    api_error, bytesrepr, runtime_args, ApiError, CLType, ContractHash, EntryPoint,
+    EntryPointAccess, EntryPointType, EntryPoints, Parameter, RuntimeArgs,
+};
+
+const SET_VALUE: &str = "set_value";
+const SET_VALUE_NO_SCHEMA: &str = "set_value_no_schema";
+
+const ARG_COMMAND: &str = "command";
+const ARG_VALUE: &str = "value";
+
+const COMMAND_VALID: &str = "valid";
+const COMMAND_OMIT: &str = "omit";
+const COMMAND_WRONG_TYPE: &str = "wrong_type";
+const COMMAND_OMIT_NO_SCHEMA: &str = "omit_no_schema";
+
+const VALUE_KEY: &str = "value";
+const MISSING_ARGUMENT_KEY: &str = "missing_argument";
+const INVALID_ARGUMENT_KEY: &str = "invalid_argument";
+
+#[no_mangle]
+pub extern "C" fn set_value() {
+    let value: u64 = runtime::get_named_arg(ARG_VALUE);
+    runtime::put_key(VALUE_KEY, storage::new_uref(value).into());
+}
+
+#[no_mangle]
+pub extern "C" fn set_value_no_schema() {
+    set_value()
+}
+
+fn store() -> ContractHash {
+    let entry_points = {
+        let mut entry_points = EntryPoints::new();
+        let parameters = vec![Parameter::new(ARG_VALUE, CLType::U64)];
+        entry_points.add_entry_point(EntryPoint::new(
+            SET_VALUE,
+            parameters.clone(),
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Session,
+        ));
+        entry_points.add_entry_point(
+            EntryPoint::new(
+                SET_VALUE_NO_SCHEMA,
+                parameters,
+                CLType::Unit,
+                EntryPointAccess::Public,
+                EntryPointType::Session,
+            )
+            .with_no_schema(),
+        );
+        entry_points
+    };
+    let (contract_hash, _contract_version) = storage::new_contract(entry_points, None, None, None);
+    contract_hash
+}
+
+/// Calls `entry_point_name` via the FFI so that a failed call returns its error rather than
+/// reverting, along with the contents of the host buffer.
+fn try_call_contract(
+    contract_hash: ContractHash,
+    entry_point_name: &str,
+    runtime_args: RuntimeArgs,
+) -> Result<(), (ApiError, String)> {
+    let contract_hash_bytes = bytesrepr::serialize(contract_hash).unwrap_or_revert();
+    let entry_point_name_bytes = bytesrepr::serialize(entry_point_name).unwrap_or_revert();
+    let runtime_args_bytes = bytesrepr::serialize(runtime_args).unwrap_or_revert();
+
+    let mut result_size = MaybeUninit::uninit();
+    let ret = unsafe {
+        ext_ffi::call_contract(
+            contract_hash_bytes.as_ptr(),
+            contract_hash_bytes.len(),
+            entry_point_name_bytes.as_ptr(),
+            entry_point_name_bytes.len(),
+            runtime_args_bytes.as_ptr(),
+            runtime_args_bytes.len(),
+            result_size.as_mut_ptr(),
+        )
+    };
+    let error = match api_error::result_from(ret) {
+        Ok(()) => return Ok(()),
+        Err(error) => error,
+    };
+
+    let result_size = unsafe { result_size.assume_init() };
+    let mut parameter_name_bytes = vec![0u8; result_size];
+    let mut bytes_written = MaybeUninit::uninit();
+    let ret = unsafe {
+        ext_ffi::read_host_buffer(
+            parameter_name_bytes.as_mut_ptr(),
+            result_size,
+            bytes_written.as_mut_ptr(),
+        )
+    };
+    api_error::result_from(ret).unwrap_or_revert();
+    let parameter_name: String = bytesrepr::deserialize(parameter_name_bytes).unwrap_or_revert();
+    Err((error, parameter_name))
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = runtime::get_named_arg(ARG_COMMAND);
+    let contract_hash = store();
+
+    match command.as_str() {
+        COMMAND_VALID => runtime::call_contract(
+            contract_hash,
+            SET_VALUE,
+            runtime_args! { ARG_VALUE => 42u64 },
+        ),
+        COMMAND_OMIT => match try_call_contract(contract_hash, SET_VALUE, RuntimeArgs::new()) {
+            Err((ApiError::MissingArgument, parameter_name)) => runtime::put_key(
+                MISSING_ARGUMENT_KEY,
+                storage::new_uref(parameter_name).into(),
+            ),
+            _ => runtime::revert(ApiError::User(0)),
+        },
+        COMMAND_WRONG_TYPE => match try_call_contract(
+            contract_hash,
+            SET_VALUE,
+            runtime_args! { ARG_VALUE => String::from("42") },
+        ) {
+            Err((ApiError::InvalidArgument, parameter_name)) => runtime::put_key(
+                INVALID_ARGUMENT_KEY,
+                storage::new_uref(parameter_name).into(),
+            ),
+            _ => runtime::revert(ApiError::User(1)),
+        },
+        COMMAND_OMIT_NO_SCHEMA => {
+            // The callee itself reverts when it fails to find the argument.
+            runtime::call_contract(contract_hash, SET_VALUE_NO_SCHEMA, RuntimeArgs::new())
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
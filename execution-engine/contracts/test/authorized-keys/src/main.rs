@@ -13,23 +13,50 @@ use types::{
 const ARG_KEY_MANAGEMENT_THRESHOLD: &str = "key_management_threshold";
 const ARG_DEPLOY_THRESHOLD: &str = "deploy_threshold";
 
+const ASSOCIATED_KEY: AccountHash = AccountHash::new([123; 32]);
+const ASSOCIATED_KEY_WEIGHT: u8 = 100;
+
+#[repr(u16)]
+enum Error {
+    AssociatedKeyNotListed = 51,
+    KeyManagementThresholdMismatch,
+    DeployThresholdMismatch,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn call() {
-    match account::add_associated_key(AccountHash::new([123; 32]), Weight::new(100)) {
+    let associated_key_weight = Weight::new(ASSOCIATED_KEY_WEIGHT);
+    match account::add_associated_key(ASSOCIATED_KEY, associated_key_weight) {
         Err(AddKeyFailure::DuplicateKey) => {}
         Err(_) => runtime::revert(ApiError::User(50)),
         Ok(_) => {}
     };
 
+    if !account::list_associated_keys().contains(&(ASSOCIATED_KEY, associated_key_weight)) {
+        runtime::revert(Error::AssociatedKeyNotListed)
+    }
+
     let key_management_threshold: Weight = runtime::get_named_arg(ARG_KEY_MANAGEMENT_THRESHOLD);
     let deploy_threshold: Weight = runtime::get_named_arg(ARG_DEPLOY_THRESHOLD);
 
     if key_management_threshold != Weight::new(0) {
         account::set_action_threshold(ActionType::KeyManagement, key_management_threshold)
-            .unwrap_or_revert()
+            .unwrap_or_revert();
+        if account::get_action_threshold(ActionType::KeyManagement) != key_management_threshold {
+            runtime::revert(Error::KeyManagementThresholdMismatch)
+        }
     }
 
     if deploy_threshold != Weight::new(0) {
-        account::set_action_threshold(ActionType::Deployment, deploy_threshold).unwrap_or_revert()
+        account::set_action_threshold(ActionType::Deployment, deploy_threshold).unwrap_or_revert();
+        if account::get_action_threshold(ActionType::Deployment) != deploy_threshold {
+            runtime::revert(Error::DeployThresholdMismatch)
+        }
     }
 }
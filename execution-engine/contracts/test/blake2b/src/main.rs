@@ -0,0 +1,19 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::contract_api::{runtime, storage};
+
+const ARG_BYTES: &str = "bytes";
+const HASH_RESULT: &str = "hash_result";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let bytes: Vec<u8> = runtime::get_named_arg(ARG_BYTES);
+    let digest = runtime::blake2b(bytes);
+    let uref = storage::new_uref(digest);
+    runtime::put_key(HASH_RESULT, uref.into());
+}
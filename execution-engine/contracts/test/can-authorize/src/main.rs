@@ -0,0 +1,37 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use contract::contract_api::{account, runtime};
+use types::{
+    account::{AccountHash, ActionType},
+    ApiError,
+};
+
+const ARG_KEYS: &str = "keys";
+const ARG_ACTION_TYPE: &str = "action_type";
+const ARG_EXPECTED: &str = "expected";
+
+#[repr(u16)]
+enum Error {
+    UnexpectedResult = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let keys: Vec<AccountHash> = runtime::get_named_arg(ARG_KEYS);
+    let action_type: u32 = runtime::get_named_arg(ARG_ACTION_TYPE);
+    let action_type = match ActionType::try_from(action_type) {
+        Ok(action_type) => action_type,
+        Err(_) => runtime::revert(ApiError::InvalidArgument),
+    };
+    let expected: bool = runtime::get_named_arg(ARG_EXPECTED);
+
+    if account::can_authorize(&keys, action_type) != expected {
+        runtime::revert(ApiError::User(Error::UnexpectedResult as u16))
+    }
+}
@@ -0,0 +1,33 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, U512};
+
+const ARG_EXPECTED_BALANCE: &str = "expected_balance";
+const SAVINGS: &str = "savings";
+
+#[repr(u16)]
+enum Error {
+    BalanceMismatch = 1,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let expected_balance: U512 = runtime::get_named_arg(ARG_EXPECTED_BALANCE);
+
+    let savings = account::get_named_purse(SAVINGS).unwrap_or_revert_with(ApiError::GetKey);
+    let balance = system::get_balance(savings).unwrap_or_revert_with(ApiError::InvalidPurse);
+    if balance != expected_balance {
+        runtime::revert(Error::BalanceMismatch);
+    }
+}
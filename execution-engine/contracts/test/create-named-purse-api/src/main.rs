@@ -0,0 +1,20 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::U512;
+
+const ARG_AMOUNT: &str = "amount";
+const SAVINGS: &str = "savings";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+
+    let savings = account::create_named_purse(SAVINGS);
+    system::transfer_from_purse_to_purse(account::get_main_purse(), savings, amount)
+        .unwrap_or_revert();
+}
@@ -0,0 +1,64 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::contract_api::{runtime, storage};
+use types::ApiError;
+
+const DICTIONARY: &str = "dictionary";
+const GREETING: &str = "greeting";
+const COUNT: &str = "count";
+const MISSING: &str = "missing";
+const HELLO: &str = "hello";
+const GOODBYE: &str = "goodbye";
+
+#[repr(u16)]
+enum Error {
+    ValueNotStored = 1,
+    ValueNotOverwritten,
+    MissingKeyFound,
+    ValueNotPersisted,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    match storage::get_dictionary(DICTIONARY) {
+        None => {
+            let dictionary = storage::new_dictionary(DICTIONARY);
+
+            storage::dictionary_put(dictionary, GREETING, String::from(HELLO));
+            if storage::dictionary_get::<String>(dictionary, GREETING).as_deref() != Some(HELLO) {
+                runtime::revert(Error::ValueNotStored);
+            }
+
+            storage::dictionary_put(dictionary, GREETING, String::from(GOODBYE));
+            if storage::dictionary_get::<String>(dictionary, GREETING).as_deref() != Some(GOODBYE) {
+                runtime::revert(Error::ValueNotOverwritten);
+            }
+
+            if storage::dictionary_get::<String>(dictionary, MISSING).is_some() {
+                runtime::revert(Error::MissingKeyFound);
+            }
+
+            storage::dictionary_put(dictionary, COUNT, 1u64);
+        }
+        Some(dictionary) => {
+            // A later call finds the items written by the first one through the named key.
+            if storage::dictionary_get::<String>(dictionary, GREETING).as_deref() != Some(GOODBYE) {
+                runtime::revert(Error::ValueNotPersisted);
+            }
+            let count: u64 = storage::dictionary_get(dictionary, COUNT)
+                .unwrap_or_else(|| runtime::revert(Error::ValueNotPersisted));
+            storage::dictionary_put(dictionary, COUNT, count + 1);
+        }
+    }
+}
@@ -12,13 +12,14 @@ use core::convert::TryInto;
 
 use types::{
     contracts::{EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, NamedKeys},
-    CLType, Key, URef,
+    CLType, ContractPackageHash, Key, URef,
 };
 
 const ENTRY_FUNCTION_NAME: &str = "delegate";
 const DO_NOTHING_PACKAGE_HASH_KEY_NAME: &str = "do_nothing_package_hash";
 const DO_NOTHING_ACCESS_KEY_NAME: &str = "do_nothing_access";
 const CONTRACT_VERSION: &str = "contract_version";
+const ARG_CONTRACT_PACKAGE: &str = "contract_package";
 
 #[no_mangle]
 pub extern "C" fn delegate() {
@@ -43,19 +44,26 @@ pub extern "C" fn call() {
         entry_points
     };
 
-    let do_nothing_package_hash =
-        runtime::get_key(DO_NOTHING_PACKAGE_HASH_KEY_NAME).unwrap_or_revert();
+    // A package named by the caller is upgraded without its access key, as an account which
+    // doesn't own the package would have to.
+    let do_nothing_package_hash: ContractPackageHash =
+        match runtime::try_get_named_arg(ARG_CONTRACT_PACKAGE) {
+            Ok(contract_package_hash) => contract_package_hash,
+            Err(_) => {
+                let _do_nothing_uref: URef = runtime::get_key(DO_NOTHING_ACCESS_KEY_NAME)
+                    .unwrap_or_revert()
+                    .try_into()
+                    .unwrap_or_revert();
 
-    let _do_nothing_uref: URef = runtime::get_key(DO_NOTHING_ACCESS_KEY_NAME)
-        .unwrap_or_revert()
-        .try_into()
-        .unwrap_or_revert();
+                runtime::get_key(DO_NOTHING_PACKAGE_HASH_KEY_NAME)
+                    .unwrap_or_revert()
+                    .into_hash()
+                    .unwrap_or_revert()
+            }
+        };
 
-    let (contract_hash, contract_version) = storage::add_contract_version(
-        do_nothing_package_hash.into_hash().unwrap(),
-        entry_points,
-        NamedKeys::new(),
-    );
+    let (contract_hash, contract_version) =
+        storage::add_contract_version(do_nothing_package_hash, entry_points, NamedKeys::new());
     runtime::put_key(CONTRACT_VERSION, storage::new_uref(contract_version).into());
     runtime::put_key("end of upgrade", contract_hash.into());
 }
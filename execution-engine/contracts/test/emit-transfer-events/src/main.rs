@@ -0,0 +1,21 @@
+#![no_std]
+#![no_main]
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{account::AccountHash, bytesrepr::ToBytes, U512};
+
+const ARG_EVENT_COUNT: &str = "event_count";
+const TOPIC_TRANSFER: &str = "transfer";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let event_count: u8 = runtime::get_named_arg(ARG_EVENT_COUNT);
+    let sender = runtime::get_caller();
+
+    for index in 0..event_count {
+        let recipient = AccountHash::new([index; 32]);
+        let amount = U512::from(index) + 1;
+        let payload = (sender, recipient, amount).to_bytes().unwrap_or_revert();
+        runtime::emit_event(TOPIC_TRANSFER, &payload);
+    }
+}
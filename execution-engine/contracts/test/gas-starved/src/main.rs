@@ -0,0 +1,31 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, Key};
+
+const COUNTER_KEY: &str = "gas_starved_counter";
+
+// Creates the counter on the first run.  Every later run increments it until the deploy runs out
+// of gas.
+#[no_mangle]
+pub extern "C" fn call() {
+    let uref = match runtime::get_key(COUNTER_KEY) {
+        Some(key) => key
+            .into_uref()
+            .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant),
+        None => {
+            let uref = storage::new_uref(0u64);
+            runtime::put_key(COUNTER_KEY, Key::from(uref));
+            return;
+        }
+    };
+    let mut counter = 0u64;
+    loop {
+        counter += 1;
+        storage::write(uref, counter);
+    }
+}
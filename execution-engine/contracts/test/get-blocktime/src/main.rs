@@ -5,15 +5,39 @@ use contract::contract_api::runtime;
 use types::BlockTime;
 
 const ARG_KNOWN_BLOCK_TIME: &str = "known_block_time";
+const ONE_MINUTE_MILLIS: u64 = 60_000;
 
 #[no_mangle]
 pub extern "C" fn call() {
-    let known_block_time: u64 = runtime::get_named_arg(ARG_KNOWN_BLOCK_TIME);
+    let known_block_time_millis: u64 = runtime::get_named_arg(ARG_KNOWN_BLOCK_TIME);
     let actual_block_time: BlockTime = runtime::get_blocktime();
+    let known_block_time = BlockTime::new(known_block_time_millis);
 
     assert_eq!(
-        actual_block_time,
-        BlockTime::new(known_block_time),
+        actual_block_time, known_block_time,
         "actual block time not known block time"
     );
+
+    // The bounds are computed from the known `u64` value rather than via `BlockTime`, clamping to
+    // the range of `u64` where the arithmetic would overflow.
+    let one_minute_later = BlockTime::new(
+        known_block_time_millis
+            .checked_add(ONE_MINUTE_MILLIS)
+            .unwrap_or(u64::max_value()),
+    );
+    let one_minute_earlier = BlockTime::new(
+        known_block_time_millis
+            .checked_sub(ONE_MINUTE_MILLIS)
+            .unwrap_or(0),
+    );
+    assert_eq!(
+        actual_block_time.saturating_add(ONE_MINUTE_MILLIS),
+        one_minute_later,
+        "adding to block time should saturate at u64::MAX"
+    );
+    assert_eq!(
+        actual_block_time.saturating_sub(BlockTime::new(ONE_MINUTE_MILLIS)),
+        one_minute_earlier,
+        "subtracting from block time should saturate at 0"
+    );
 }
@@ -0,0 +1,13 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::{runtime, storage};
+
+const DEPLOY_HASH: &str = "deploy_hash";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let deploy_hash = runtime::get_deploy_hash();
+    let uref = storage::new_uref(deploy_hash);
+    runtime::put_key(DEPLOY_HASH, uref.into());
+}
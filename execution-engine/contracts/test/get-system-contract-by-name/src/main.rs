@@ -0,0 +1,19 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::contract_api::{runtime, system};
+use types::Key;
+
+const ARG_NAME: &str = "name";
+const SYSTEM_CONTRACT: &str = "system_contract";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let name: String = runtime::get_named_arg(ARG_NAME);
+    let contract_hash = system::get_system_contract_by_name(&name);
+    runtime::put_key(SYSTEM_CONTRACT, Key::Hash(contract_hash));
+}
@@ -0,0 +1,19 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{vec, vec::Vec};
+
+use contract::contract_api::{runtime, storage};
+
+const ARG_LENGTH: &str = "length";
+const WRITTEN_VALUE: &str = "written_value";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let length: u32 = runtime::get_named_arg(ARG_LENGTH);
+    let uref = storage::new_uref(Vec::<u8>::new());
+    storage::write(uref, vec![1u8; length as usize]);
+    runtime::put_key(WRITTEN_VALUE, uref.into());
+}
@@ -0,0 +1,15 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::{runtime, storage};
+use types::Key;
+
+const KEY_CYCLE: &str = "key_cycle";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let uref_a = storage::new_uref(Key::Hash([0; 32]));
+    let uref_b = storage::new_uref(Key::from(uref_a));
+    storage::write(uref_a, Key::from(uref_b));
+    runtime::put_key(KEY_CYCLE, uref_a.into());
+}
@@ -0,0 +1,36 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::contract_api::runtime;
+use types::{account::AccountHash, ApiError};
+
+const ARG_EXPECTED_AUTHORIZATION_KEYS: &str = "expected_authorization_keys";
+
+#[repr(u16)]
+enum Error {
+    AuthorizationKeysMismatch = 0,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut expected_authorization_keys: Vec<AccountHash> =
+        runtime::get_named_arg(ARG_EXPECTED_AUTHORIZATION_KEYS);
+    expected_authorization_keys.sort();
+
+    let authorization_keys: Vec<AccountHash> =
+        runtime::list_authorization_keys().into_iter().collect();
+
+    if authorization_keys != expected_authorization_keys {
+        runtime::revert(Error::AuthorizationKeysMismatch)
+    }
+}
@@ -0,0 +1,43 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+
+const GROUP_A: &str = "group_a";
+const GROUP_B: &str = "group_b";
+const GROUPS_BEFORE_REMOVAL: &str = "groups_before_removal";
+const GROUPS_AFTER_REMOVAL: &str = "groups_after_removal";
+const ARG_FORGE_ACCESS_KEY: &str = "forge_access_key";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let forge_access_key: bool = runtime::get_named_arg(ARG_FORGE_ACCESS_KEY);
+
+    let (package_hash, access_key) = storage::create_contract_package_at_hash();
+    let access_key = if forge_access_key {
+        // A valid uref, but not the package's access key
+        storage::new_uref(())
+    } else {
+        access_key
+    };
+
+    storage::create_contract_user_group(package_hash, GROUP_A, 1, BTreeSet::new())
+        .unwrap_or_revert();
+    storage::create_contract_user_group(package_hash, GROUP_B, 3, BTreeSet::new())
+        .unwrap_or_revert();
+
+    let groups = storage::list_contract_groups(package_hash, access_key).unwrap_or_revert();
+    runtime::put_key(GROUPS_BEFORE_REMOVAL, storage::new_uref(groups).into());
+
+    storage::remove_contract_user_group(package_hash, GROUP_B).unwrap_or_revert();
+
+    let groups = storage::list_contract_groups(package_hash, access_key).unwrap_or_revert();
+    runtime::put_key(GROUPS_AFTER_REMOVAL, storage::new_uref(groups).into());
+}
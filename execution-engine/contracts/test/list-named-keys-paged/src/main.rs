@@ -0,0 +1,53 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::format;
+
+use contract::contract_api::{runtime, storage};
+use types::{contracts::NamedKeys, ApiError};
+
+const ARG_TOTAL_NAMED_KEYS: &str = "total_named_keys";
+const ARG_PAGE_SIZE: &str = "page_size";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let total_named_keys: u32 = runtime::get_named_arg(ARG_TOTAL_NAMED_KEYS);
+    let page_size: u32 = runtime::get_named_arg(ARG_PAGE_SIZE);
+    // An empty page would never advance the walk below.
+    if page_size == 0 {
+        runtime::revert(ApiError::InvalidArgument);
+    }
+
+    for index in 0..total_named_keys {
+        let uref = storage::new_uref(index);
+        runtime::put_key(&format!("named-key-{}", index), uref.into());
+    }
+
+    let expected_named_keys = runtime::list_named_keys();
+
+    // Walk the named keys page by page, checking that pages don't overlap and that every page
+    // other than the last one is full.
+    let mut actual_named_keys = NamedKeys::new();
+    let mut start = 0;
+    loop {
+        let (page, more) = runtime::list_named_keys_paged(start, page_size as usize);
+        if more {
+            assert_eq!(page.len(), page_size as usize);
+        }
+        start += page.len();
+        for (name, key) in page {
+            assert!(actual_named_keys.insert(name, key).is_none());
+        }
+        if !more {
+            break;
+        }
+    }
+    assert_eq!(expected_named_keys, actual_named_keys);
+
+    // A page starting beyond the last named key is empty.
+    let (page, more) = runtime::list_named_keys_paged(start, page_size as usize);
+    assert!(page.is_empty());
+    assert!(!more);
+}
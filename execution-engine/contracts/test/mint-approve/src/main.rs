@@ -0,0 +1,19 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::AccountHash, U512};
+
+const ARG_SPENDER: &str = "spender";
+const ARG_AMOUNT: &str = "amount";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let spender: AccountHash = runtime::get_named_arg(ARG_SPENDER);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+
+    system::approve_transfer(account::get_main_purse(), spender, amount).unwrap_or_revert();
+}
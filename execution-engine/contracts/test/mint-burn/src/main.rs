@@ -0,0 +1,37 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{runtime_args, system_contract_errors::mint, ApiError, RuntimeArgs, U512};
+
+const BURN_METHOD_NAME: &str = "burn";
+
+const ARG_AMOUNT: &str = "amount";
+const ARG_PURSE: &str = "purse";
+
+#[repr(u16)]
+enum Error {
+    TotalSupplyMismatch = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+
+    let total_supply_before = system::total_supply();
+
+    let args = runtime_args! {
+        ARG_PURSE => account::get_main_purse(),
+        ARG_AMOUNT => amount,
+    };
+    let result: Result<(), mint::Error> =
+        runtime::call_contract(system::get_mint(), BURN_METHOD_NAME, args);
+    result.unwrap_or_revert();
+
+    if system::total_supply() != total_supply_before - amount {
+        runtime::revert(ApiError::User(Error::TotalSupplyMismatch as u16));
+    }
+}
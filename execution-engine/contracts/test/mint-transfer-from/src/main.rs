@@ -0,0 +1,32 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, URefAddr, U512};
+
+const ARG_OWNER: &str = "owner";
+const ARG_AMOUNT: &str = "amount";
+
+#[repr(u16)]
+enum Error {
+    AllowanceMismatch = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let owner: URefAddr = runtime::get_named_arg(ARG_OWNER);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+
+    let caller = runtime::get_caller();
+    let allowance_before = system::get_allowance(owner, caller);
+
+    system::transfer_from_purse_with_allowance(owner, account::get_main_purse(), amount)
+        .unwrap_or_revert();
+
+    if system::get_allowance(owner, caller) != allowance_before - amount {
+        runtime::revert(ApiError::User(Error::AllowanceMismatch as u16));
+    }
+}
@@ -36,6 +36,10 @@ impl RuntimeProvider for MintContract {
         runtime::get_caller()
     }
 
+    fn get_key(&self, name: &str) -> Option<Key> {
+        runtime::get_key(name)
+    }
+
     fn put_key(&mut self, name: &str, key: Key) {
         runtime::put_key(name, key)
     }
@@ -70,6 +74,14 @@ impl StorageProvider for MintContract {
         storage::add(uref, value);
         Ok(())
     }
+
+    fn sub(&mut self, uref: URef, amount: U512) -> Result<(), Error> {
+        let balance: U512 = self.read(uref)?.ok_or(Error::Storage)?;
+        let new_balance = balance
+            .checked_sub(amount)
+            .ok_or(Error::InsufficientFunds)?;
+        self.write(uref, new_balance)
+    }
 }
 
 impl Mint for MintContract {}
@@ -82,8 +82,10 @@ fn upgrade_mint() -> (ContractHash, ContractVersion) {
 
 fn upgrade_proof_of_stake() -> (ContractHash, ContractVersion) {
     use pos::{
-        ARG_ACCOUNT_KEY, ARG_AMOUNT, ARG_PURSE, METHOD_BOND, METHOD_FINALIZE_PAYMENT,
-        METHOD_GET_PAYMENT_PURSE, METHOD_GET_REFUND_PURSE, METHOD_SET_REFUND_PURSE, METHOD_UNBOND,
+        ARG_ACCOUNT_KEY, ARG_AMOUNT, ARG_PURSE, ARG_VALIDATOR, ARG_VALIDATOR_PUBLIC_KEYS,
+        METHOD_BOND, METHOD_DELEGATE, METHOD_FINALIZE_PAYMENT, METHOD_GET_PAYMENT_PURSE,
+        METHOD_GET_REFUND_PURSE, METHOD_PROCESS_UNBOND_REQUESTS, METHOD_SET_REFUND_PURSE,
+        METHOD_SLASH, METHOD_UNBOND, METHOD_UNDELEGATE,
     };
 
     const HASH_KEY_NAME: &str = "pos_hash";
@@ -122,6 +124,52 @@ fn upgrade_proof_of_stake() -> (ContractHash, ContractVersion) {
         );
         entry_points.add_entry_point(unbond);
 
+        let delegate = EntryPoint::new(
+            METHOD_DELEGATE,
+            vec![
+                Parameter::new(ARG_VALIDATOR, CLType::FixedList(Box::new(CLType::U8), 32)),
+                Parameter::new(ARG_AMOUNT, CLType::U512),
+                Parameter::new(ARG_PURSE, CLType::URef),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(delegate);
+
+        let undelegate = EntryPoint::new(
+            METHOD_UNDELEGATE,
+            vec![
+                Parameter::new(ARG_VALIDATOR, CLType::FixedList(Box::new(CLType::U8), 32)),
+                Parameter::new(ARG_AMOUNT, CLType::Option(Box::new(CLType::U512))),
+            ],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(undelegate);
+
+        let process_unbond_requests = EntryPoint::new(
+            METHOD_PROCESS_UNBOND_REQUESTS,
+            vec![],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(process_unbond_requests);
+
+        let slash = EntryPoint::new(
+            METHOD_SLASH,
+            vec![Parameter::new(
+                ARG_VALIDATOR_PUBLIC_KEYS,
+                CLType::List(Box::new(CLType::FixedList(Box::new(CLType::U8), 32))),
+            )],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        );
+        entry_points.add_entry_point(slash);
+
         let get_payment_purse = EntryPoint::new(
             METHOD_GET_PAYMENT_PURSE,
             vec![],
@@ -179,6 +227,26 @@ pub extern "C" fn unbond() {
     pos::unbond();
 }
 
+#[no_mangle]
+pub extern "C" fn delegate() {
+    pos::delegate();
+}
+
+#[no_mangle]
+pub extern "C" fn undelegate() {
+    pos::undelegate();
+}
+
+#[no_mangle]
+pub extern "C" fn process_unbond_requests() {
+    pos::process_unbond_requests();
+}
+
+#[no_mangle]
+pub extern "C" fn slash() {
+    pos::slash();
+}
+
 #[no_mangle]
 pub extern "C" fn get_payment_purse() {
     pos::get_payment_purse();
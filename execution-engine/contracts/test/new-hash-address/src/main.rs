@@ -0,0 +1,14 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::{runtime, storage};
+
+const ADDRESSES: &str = "addresses";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let address_1 = runtime::new_hash_address();
+    let address_2 = runtime::new_hash_address();
+    let uref = storage::new_uref((address_1, address_2));
+    runtime::put_key(ADDRESSES, uref.into());
+}
@@ -0,0 +1,61 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    runtime_args, CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType,
+    EntryPoints, Parameter, RuntimeArgs, U512,
+};
+
+const ENTRY_POINT_GET_OPTION_TUPLE: &str = "get_option_tuple";
+const ARG_NAME: &str = "name";
+const ARG_AMOUNT: &str = "amount";
+const RESULT_KEY: &str = "option_tuple_result";
+
+type OptionTuple = Option<(String, U512)>;
+
+/// Returns `None` if the given name is empty, otherwise the given name and amount.
+#[no_mangle]
+pub extern "C" fn get_option_tuple() {
+    let name: String = runtime::get_named_arg(ARG_NAME);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let result: OptionTuple = if name.is_empty() {
+        None
+    } else {
+        Some((name, amount))
+    };
+    runtime::ret(CLValue::from_t(result).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let name: String = runtime::get_named_arg(ARG_NAME);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_GET_OPTION_TUPLE,
+        vec![
+            Parameter::new(ARG_NAME, CLType::String),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+        ],
+        OptionTuple::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    let (contract_hash, _contract_version) = storage::new_contract(entry_points, None, None, None);
+
+    let result: OptionTuple = runtime::call_contract(
+        contract_hash,
+        ENTRY_POINT_GET_OPTION_TUPLE,
+        runtime_args! { ARG_NAME => name, ARG_AMOUNT => amount },
+    );
+    runtime::put_key(RESULT_KEY, storage::new_uref(result).into());
+}
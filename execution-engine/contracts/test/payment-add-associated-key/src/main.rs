@@ -0,0 +1,30 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::{AccountHash, Weight},
+    RuntimeArgs, URef, U512,
+};
+
+const GET_PAYMENT_PURSE: &str = "get_payment_purse";
+const ARG_ACCOUNT: &str = "account";
+const ARG_AMOUNT: &str = "amount";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let account: AccountHash = runtime::get_named_arg(ARG_ACCOUNT);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+
+    // Key management is not permitted before the deploy has been paid for, so this should fail.
+    account::add_associated_key(account, Weight::new(1)).unwrap_or_revert();
+
+    let main_purse = account::get_main_purse();
+    let pos_pointer = system::get_proof_of_stake();
+    let payment_purse: URef =
+        runtime::call_contract(pos_pointer, GET_PAYMENT_PURSE, RuntimeArgs::default());
+    system::transfer_from_purse_to_purse(main_purse, payment_purse, amount).unwrap_or_revert()
+}
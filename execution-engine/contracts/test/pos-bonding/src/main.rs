@@ -10,7 +10,10 @@ use contract::{
     unwrap_or_revert::UnwrapOrRevert,
 };
 
-use types::{account::AccountHash, runtime_args, ApiError, ContractHash, RuntimeArgs, URef, U512};
+use types::{
+    account::AccountHash, runtime_args, AccessRights, ApiError, ContractHash, RuntimeArgs, URef,
+    U512,
+};
 
 const ARG_AMOUNT: &str = "amount";
 const ARG_PURSE: &str = "purse";
@@ -18,13 +21,19 @@ const ARG_ENTRY_POINT: &str = "entry_point";
 const ARG_BOND: &str = "bond";
 const ARG_UNBOND: &str = "unbond";
 const ARG_ACCOUNT_HASH: &str = "account_hash";
+const ARG_VALIDATOR: &str = "validator";
+const METHOD_DELEGATE: &str = "delegate";
 const TEST_BOND_FROM_MAIN_PURSE: &str = "bond-from-main-purse";
 const TEST_SEED_NEW_ACCOUNT: &str = "seed_new_account";
+const TEST_BOND_FROM_FORGED_PURSE: &str = "bond-from-forged-purse";
+const TEST_BOND_FROM_ADD_ONLY_PURSE: &str = "bond-from-add-only-purse";
+const TEST_DELEGATE_FROM_ADD_ONLY_PURSE: &str = "delegate-from-add-only-purse";
 
 #[repr(u16)]
 enum Error {
     UnableToSeedAccount,
     UnknownCommand,
+    ForgedPurse,
 }
 
 #[no_mangle]
@@ -36,6 +45,9 @@ pub extern "C" fn call() {
         ARG_UNBOND => unbond(),
         TEST_BOND_FROM_MAIN_PURSE => bond_from_main_purse(),
         TEST_SEED_NEW_ACCOUNT => seed_new_account(),
+        TEST_BOND_FROM_FORGED_PURSE => bond_from_forged_purse(),
+        TEST_BOND_FROM_ADD_ONLY_PURSE => bond_from_add_only_purse(),
+        TEST_DELEGATE_FROM_ADD_ONLY_PURSE => delegate_from_add_only_purse(),
         _ => runtime::revert(ApiError::User(Error::UnknownCommand as u16)),
     }
 }
@@ -58,6 +70,36 @@ fn bond_from_main_purse() {
     bonding(pos_contract_hash, amount, account::get_main_purse());
 }
 
+fn bond_from_forged_purse() {
+    let pos_contract_hash = system::get_proof_of_stake();
+    let amount = runtime::get_named_arg(ARG_AMOUNT);
+    let forged_purse = URef::new([42; 32], AccessRights::READ_ADD_WRITE);
+    if !account::validate_purse(forged_purse) {
+        runtime::revert(ApiError::User(Error::ForgedPurse as u16));
+    }
+    bonding(pos_contract_hash, amount, forged_purse);
+}
+
+fn bond_from_add_only_purse() {
+    let pos_contract_hash = system::get_proof_of_stake();
+    let amount = runtime::get_named_arg(ARG_AMOUNT);
+    let add_only_purse = account::get_main_purse().with_access_rights(AccessRights::ADD);
+    bonding(pos_contract_hash, amount, add_only_purse);
+}
+
+fn delegate_from_add_only_purse() {
+    let pos_contract_hash = system::get_proof_of_stake();
+    let validator: AccountHash = runtime::get_named_arg(ARG_VALIDATOR);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let add_only_purse = account::get_main_purse().with_access_rights(AccessRights::ADD);
+    let args = runtime_args! {
+        ARG_VALIDATOR => validator,
+        ARG_AMOUNT => amount,
+        ARG_PURSE => add_only_purse,
+    };
+    runtime::call_contract(pos_contract_hash, METHOD_DELEGATE, args)
+}
+
 fn bonding(pos: ContractHash, bond_amount: U512, bonding_purse: URef) {
     let args = runtime_args! {
         ARG_AMOUNT => bond_amount,
@@ -0,0 +1,20 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::{runtime, system};
+use types::{runtime_args, RuntimeArgs};
+
+const ARG_GAS_PRICE: &str = "gas_price";
+const METHOD_SET_GAS_PRICE: &str = "set_gas_price";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let gas_price: u64 = runtime::get_named_arg(ARG_GAS_PRICE);
+    runtime::call_contract(
+        system::get_proof_of_stake(),
+        METHOD_SET_GAS_PRICE,
+        runtime_args! {
+            ARG_GAS_PRICE => gas_price,
+        },
+    )
+}
@@ -0,0 +1,22 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::contract_api::{runtime, system};
+use types::{account::AccountHash, runtime_args, RuntimeArgs};
+
+const SLASH_METHOD_NAME: &str = "slash";
+const ARG_VALIDATOR_PUBLIC_KEYS: &str = "validator_public_keys";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let validators: Vec<AccountHash> = runtime::get_named_arg(ARG_VALIDATOR_PUBLIC_KEYS);
+    let contract_hash = system::get_proof_of_stake();
+    let args = runtime_args! {
+        ARG_VALIDATOR_PUBLIC_KEYS => validators,
+    };
+    runtime::call_contract(contract_hash, SLASH_METHOD_NAME, args)
+}
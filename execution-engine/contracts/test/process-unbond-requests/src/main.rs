@@ -0,0 +1,17 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::{runtime, system};
+use types::RuntimeArgs;
+
+const PROCESS_UNBOND_REQUESTS_METHOD_NAME: &str = "process_unbond_requests";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_hash = system::get_proof_of_stake();
+    runtime::call_contract(
+        contract_hash,
+        PROCESS_UNBOND_REQUESTS_METHOD_NAME,
+        RuntimeArgs::default(),
+    )
+}
@@ -0,0 +1,21 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const ARG_COUNT: &str = "count";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let count: u32 = runtime::get_named_arg(ARG_COUNT);
+    let uref = storage::new_uref(0u32);
+    for _ in 0..count {
+        let _value: u32 = storage::read(uref)
+            .unwrap_or_revert()
+            .unwrap_or_revert_with(ApiError::ValueNotFound);
+    }
+}
@@ -0,0 +1,36 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, URef};
+
+const ARG_COMMAND: &str = "command";
+const COMMAND_WRITE: &str = "write";
+const COMMAND_REMOVE: &str = "remove";
+
+const REMOVABLE_KEY: &str = "removable";
+const VALUE: u64 = 42;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let command: String = runtime::get_named_arg(ARG_COMMAND);
+
+    match command.as_str() {
+        COMMAND_WRITE => runtime::put_key(REMOVABLE_KEY, storage::new_uref(VALUE).into()),
+        COMMAND_REMOVE => {
+            let uref: URef = runtime::get_key(REMOVABLE_KEY)
+                .unwrap_or_revert_with(ApiError::MissingKey)
+                .into_uref()
+                .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+            storage::remove(uref);
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
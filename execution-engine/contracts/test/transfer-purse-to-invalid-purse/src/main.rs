@@ -0,0 +1,35 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, URef, U512};
+
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+
+// A URef which the mint doesn't know to be a purse.
+const TARGET_UNKNOWN: &str = "unknown";
+// A purse which the caller may not add to.
+const TARGET_READ_ONLY: &str = "read_only";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let target_kind: String = runtime::get_named_arg(ARG_TARGET);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+
+    let target: URef = match target_kind.as_str() {
+        TARGET_UNKNOWN => storage::new_uref(()),
+        TARGET_READ_ONLY => system::create_purse().into_read(),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    };
+
+    system::transfer_from_purse_to_purse(account::get_main_purse(), target, amount)
+        .unwrap_or_revert();
+}
@@ -0,0 +1,30 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::AccountHash, U512};
+
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+const ARG_ID_1: &str = "id_1";
+const ARG_ID_2: &str = "id_2";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let target: AccountHash = runtime::get_named_arg(ARG_TARGET);
+    let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+    let id_1: u64 = runtime::get_named_arg(ARG_ID_1);
+    let id_2: u64 = runtime::get_named_arg(ARG_ID_2);
+
+    system::transfer_to_account_with_id(target, amount, Some(id_1)).unwrap_or_revert();
+    system::transfer_from_purse_to_account_with_id(
+        account::get_main_purse(),
+        target,
+        amount,
+        Some(id_2),
+    )
+    .unwrap_or_revert();
+}
@@ -0,0 +1,96 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    AccessRights, CLType, CLValue, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key,
+    Parameter, URef,
+};
+
+const CONTRACT_NAME: &str = "uref_attenuation_callee";
+const ENTRY_POINT_CREATE_READ_ONLY: &str = "create_read_only";
+const ENTRY_POINT_WRITE: &str = "write";
+const ENTRY_POINT_ESCALATE: &str = "escalate";
+const ENTRY_POINT_LAUNDER: &str = "launder";
+const LAUNDERED_KEY: &str = "laundered";
+const ARG_UREF: &str = "uref";
+const DATA: &str = "data";
+const REPLACEMENT_DATA: &str = "replacement data";
+
+/// Returns a read-only reference to a new URef.
+#[no_mangle]
+pub extern "C" fn create_read_only() {
+    let reference = storage::new_uref(DATA).attenuate(AccessRights::READ);
+    runtime::ret(CLValue::from_t(reference).unwrap_or_revert())
+}
+
+/// Writes to the given URef as though it had full access rights, which only succeeds if it was
+/// granted with them.
+#[no_mangle]
+pub extern "C" fn write() {
+    let reference: URef = runtime::get_named_arg(ARG_UREF);
+    let forged_reference = URef::new(reference.addr(), AccessRights::READ_ADD_WRITE);
+    storage::write(forged_reference, REPLACEMENT_DATA)
+}
+
+/// Tries to hand the given URef back with full access rights.
+#[no_mangle]
+pub extern "C" fn escalate() {
+    let reference: URef = runtime::get_named_arg(ARG_UREF);
+    let escalated = URef::new(reference.addr(), AccessRights::READ_ADD_WRITE);
+    runtime::ret(CLValue::from_t(escalated).unwrap_or_revert())
+}
+
+/// Tries to keep the given URef with full access rights by storing it inside another value under
+/// one of its own named keys.
+#[no_mangle]
+pub extern "C" fn launder() {
+    let reference: URef = runtime::get_named_arg(ARG_UREF);
+    let escalated = URef::new(reference.addr(), AccessRights::READ_ADD_WRITE);
+    let holder = storage::new_uref(Some(Key::from(escalated)));
+    runtime::put_key(LAUNDERED_KEY, holder.into());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_CREATE_READ_ONLY,
+        vec![],
+        CLType::URef,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_WRITE,
+        vec![Parameter::new(ARG_UREF, CLType::URef)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_ESCALATE,
+        vec![Parameter::new(ARG_UREF, CLType::URef)],
+        CLType::URef,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_LAUNDER,
+        vec![Parameter::new(ARG_UREF, CLType::URef)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let (contract_hash, _contract_version) = storage::new_contract(entry_points, None, None, None);
+    runtime::put_key(CONTRACT_NAME, contract_hash.into());
+}
@@ -0,0 +1,85 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{runtime_args, AccessRights, ApiError, ContractHash, RuntimeArgs, URef};
+
+const CONTRACT_NAME: &str = "uref_attenuation_callee";
+const ENTRY_POINT_CREATE_READ_ONLY: &str = "create_read_only";
+const ENTRY_POINT_WRITE: &str = "write";
+const ENTRY_POINT_ESCALATE: &str = "escalate";
+const ENTRY_POINT_LAUNDER: &str = "launder";
+const ARG_COMMAND: &str = "command";
+const ARG_UREF: &str = "uref";
+const DATA: &str = "data";
+const COMMAND_GRANT_READ: &str = "grant-read";
+const COMMAND_GRANT_READ_ADD_WRITE: &str = "grant-read-add-write";
+const COMMAND_GRANT_ESCALATED: &str = "grant-escalated";
+const COMMAND_RETURN_ESCALATED: &str = "return-escalated";
+const COMMAND_LAUNDER_READ: &str = "launder-read";
+
+#[repr(u16)]
+enum Error {
+    MissingCallee,
+    UnknownCommand,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let callee: ContractHash = runtime::get_key(CONTRACT_NAME)
+        .and_then(|key| key.into_hash())
+        .unwrap_or_revert_with(ApiError::User(Error::MissingCallee as u16));
+    let command: String = runtime::get_named_arg(ARG_COMMAND);
+
+    match command.as_str() {
+        // The callee is only granted READ, so its write must fail.
+        COMMAND_GRANT_READ => {
+            let reference = storage::new_uref(DATA).attenuate(AccessRights::READ);
+            write(callee, reference)
+        }
+        COMMAND_GRANT_READ_ADD_WRITE => write(callee, storage::new_uref(DATA)),
+        // This context only holds READ for the callee's URef, so can't grant WRITE.
+        COMMAND_GRANT_ESCALATED => {
+            let read_only: URef =
+                runtime::call_contract(callee, ENTRY_POINT_CREATE_READ_ONLY, RuntimeArgs::new());
+            write(
+                callee,
+                URef::new(read_only.addr(), AccessRights::READ_WRITE),
+            )
+        }
+        // The callee is only granted READ, so can't return anything more.
+        COMMAND_RETURN_ESCALATED => {
+            let reference = storage::new_uref(DATA).attenuate(AccessRights::READ);
+            let _: URef = runtime::call_contract(
+                callee,
+                ENTRY_POINT_ESCALATE,
+                runtime_args! { ARG_UREF => reference },
+            );
+        }
+        // The callee is only granted READ, so can't store anything more.
+        COMMAND_LAUNDER_READ => {
+            let reference = storage::new_uref(DATA).attenuate(AccessRights::READ);
+            runtime::call_contract::<()>(
+                callee,
+                ENTRY_POINT_LAUNDER,
+                runtime_args! { ARG_UREF => reference },
+            )
+        }
+        _ => runtime::revert(ApiError::User(Error::UnknownCommand as u16)),
+    }
+}
+
+fn write(callee: ContractHash, reference: URef) {
+    runtime::call_contract::<()>(
+        callee,
+        ENTRY_POINT_WRITE,
+        runtime_args! { ARG_UREF => reference },
+    )
+}
@@ -0,0 +1,49 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::contract_api::runtime::{self, ED25519_PUBLIC_KEY_LENGTH, ED25519_SIGNATURE_LENGTH};
+use types::ApiError;
+
+const ARG_PUBLIC_KEY: &str = "public_key";
+const ARG_SIGNATURE: &str = "signature";
+const ARG_MESSAGE: &str = "message";
+
+#[repr(u16)]
+enum Error {
+    ValidSignatureRejected = 0,
+    CorruptedSignatureAccepted = 1,
+    CorruptedMessageAccepted = 2,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let public_key: [u8; ED25519_PUBLIC_KEY_LENGTH] = runtime::get_named_arg(ARG_PUBLIC_KEY);
+    let signature: [u8; ED25519_SIGNATURE_LENGTH] = runtime::get_named_arg(ARG_SIGNATURE);
+    let message: Vec<u8> = runtime::get_named_arg(ARG_MESSAGE);
+
+    if !runtime::verify_ed25519(&public_key, &signature, &message) {
+        runtime::revert(Error::ValidSignatureRejected);
+    }
+
+    let mut corrupted_signature = signature;
+    corrupted_signature[0] ^= 1;
+    if runtime::verify_ed25519(&public_key, &corrupted_signature, &message) {
+        runtime::revert(Error::CorruptedSignatureAccepted);
+    }
+
+    let mut corrupted_message = message;
+    corrupted_message.push(0);
+    if runtime::verify_ed25519(&public_key, &signature, &corrupted_message) {
+        runtime::revert(Error::CorruptedMessageAccepted);
+    }
+}
@@ -0,0 +1,19 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::contract_api::{runtime, storage};
+
+const ARG_BYTES: &str = "bytes";
+const WRITTEN_BYTES: &str = "written_bytes";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let bytes: Vec<u8> = runtime::get_named_arg(ARG_BYTES);
+    let uref = storage::new_uref(Vec::<u8>::new());
+    storage::write(uref, bytes);
+    runtime::put_key(WRITTEN_BYTES, uref.into());
+}
@@ -0,0 +1,31 @@
+use engine_shared::newtypes::Blake2bHash;
+use types::{URef, U512};
+
+pub enum BalanceResult {
+    RootNotFound,
+    PurseNotFound,
+    Success(U512),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceRequest {
+    state_hash: Blake2bHash,
+    purse_uref: URef,
+}
+
+impl BalanceRequest {
+    pub fn new(state_hash: Blake2bHash, purse_uref: URef) -> Self {
+        BalanceRequest {
+            state_hash,
+            purse_uref,
+        }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn purse_uref(&self) -> URef {
+        self.purse_uref
+    }
+}
@@ -0,0 +1,32 @@
+use std::collections::BTreeMap;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::{account::AccountHash, ProtocolVersion, U512};
+
+pub enum BondedValidatorsResult {
+    RootNotFound,
+    Success(BTreeMap<AccountHash, U512>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BondedValidatorsRequest {
+    state_hash: Blake2bHash,
+    protocol_version: ProtocolVersion,
+}
+
+impl BondedValidatorsRequest {
+    pub fn new(state_hash: Blake2bHash, protocol_version: ProtocolVersion) -> Self {
+        BondedValidatorsRequest {
+            state_hash,
+            protocol_version,
+        }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+}
@@ -1,9 +1,56 @@
+use types::account::MAX_ASSOCIATED_KEYS;
+
+use super::{
+    module_cache::DEFAULT_MODULE_CACHE_CAPACITY,
+    state_read_cache::DEFAULT_STATE_READ_CACHE_CAPACITY,
+};
+
+/// The default maximum size in bytes of a value written to global state by a contract.
+pub const DEFAULT_MAX_STORED_VALUE_SIZE: u32 = 8 * 1024 * 1024;
+/// The default maximum number of deploys in a single execute request.
+pub const DEFAULT_MAX_DEPLOYS_PER_REQUEST: u32 = 1_000;
+/// The default maximum size in bytes of a deploy's session or payment module.
+pub const DEFAULT_MAX_MODULE_SIZE: u32 = 10 * 1024 * 1024;
+/// The default maximum combined size in bytes of a deploy's session and payment args.
+pub const DEFAULT_MAX_ARGS_SIZE: u32 = 1024 * 1024;
+/// The default maximum number of `Key` values a query will dereference in following its path.
+pub const DEFAULT_MAX_QUERY_DEPTH: u32 = 64;
+
 /// The runtime configuration of the execution engine
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone)]
 pub struct EngineConfig {
     // feature flags go here
     use_system_contracts: bool,
     enable_bonding: bool,
+    parallel_execution: bool,
+    state_read_cache_capacity: usize,
+    module_cache_capacity: usize,
+    max_associated_keys: u32,
+    max_stored_value_size: u32,
+    max_deploys_per_request: u32,
+    max_module_size: u32,
+    max_args_size: u32,
+    host_function_profiling: bool,
+    max_query_depth: u32,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        EngineConfig {
+            use_system_contracts: false,
+            enable_bonding: false,
+            parallel_execution: false,
+            state_read_cache_capacity: DEFAULT_STATE_READ_CACHE_CAPACITY,
+            module_cache_capacity: DEFAULT_MODULE_CACHE_CAPACITY,
+            max_associated_keys: MAX_ASSOCIATED_KEYS as u32,
+            max_stored_value_size: DEFAULT_MAX_STORED_VALUE_SIZE,
+            max_deploys_per_request: DEFAULT_MAX_DEPLOYS_PER_REQUEST,
+            max_module_size: DEFAULT_MAX_MODULE_SIZE,
+            max_args_size: DEFAULT_MAX_ARGS_SIZE,
+            host_function_profiling: false,
+            max_query_depth: DEFAULT_MAX_QUERY_DEPTH,
+        }
+    }
 }
 
 impl EngineConfig {
@@ -29,4 +76,123 @@ impl EngineConfig {
         self.enable_bonding = enable_bonding;
         self
     }
+
+    /// Returns `true` if the deploys of an execute request are run concurrently.
+    pub fn parallel_execution(self) -> bool {
+        self.parallel_execution
+    }
+
+    /// Sets whether the deploys of an execute request are run concurrently.  Each deploy is
+    /// executed against the request's parent state either way, so this only affects throughput.
+    pub fn with_parallel_execution(mut self, parallel_execution: bool) -> EngineConfig {
+        self.parallel_execution = parallel_execution;
+        self
+    }
+
+    /// Returns the maximum number of global state values shared between executions against the
+    /// same state root.
+    pub fn state_read_cache_capacity(self) -> usize {
+        self.state_read_cache_capacity
+    }
+
+    /// Sets the maximum number of global state values shared between executions against the same
+    /// state root.  A capacity of zero disables the cache.
+    pub fn with_state_read_cache_capacity(mut self, capacity: usize) -> EngineConfig {
+        self.state_read_cache_capacity = capacity;
+        self
+    }
+
+    /// Returns the maximum number of preprocessed session and payment modules reused across
+    /// deploys.
+    pub fn module_cache_capacity(self) -> usize {
+        self.module_cache_capacity
+    }
+
+    /// Sets the maximum number of preprocessed session and payment modules reused across deploys.
+    /// A capacity of zero disables the cache.
+    pub fn with_module_cache_capacity(mut self, capacity: usize) -> EngineConfig {
+        self.module_cache_capacity = capacity;
+        self
+    }
+
+    /// Returns the maximum number of associated keys an account can have.
+    pub fn max_associated_keys(self) -> u32 {
+        self.max_associated_keys
+    }
+
+    /// Sets the maximum number of associated keys an account can have.  The limit can only be
+    /// lowered: values above [`MAX_ASSOCIATED_KEYS`] have the same effect as `MAX_ASSOCIATED_KEYS`.
+    pub fn with_max_associated_keys(mut self, max_associated_keys: u32) -> EngineConfig {
+        self.max_associated_keys = max_associated_keys;
+        self
+    }
+
+    /// Returns the maximum size in bytes of a value which a contract can write to global state.
+    pub fn max_stored_value_size(self) -> u32 {
+        self.max_stored_value_size
+    }
+
+    /// Sets the maximum size in bytes of a value which a contract can write to global state.
+    pub fn with_max_stored_value_size(mut self, max_stored_value_size: u32) -> EngineConfig {
+        self.max_stored_value_size = max_stored_value_size;
+        self
+    }
+
+    /// Returns the maximum number of deploys accepted in a single execute request.
+    pub fn max_deploys_per_request(self) -> u32 {
+        self.max_deploys_per_request
+    }
+
+    /// Sets the maximum number of deploys accepted in a single execute request.  Requests with
+    /// more deploys are rejected as a whole.
+    pub fn with_max_deploys_per_request(mut self, max_deploys_per_request: u32) -> EngineConfig {
+        self.max_deploys_per_request = max_deploys_per_request;
+        self
+    }
+
+    /// Returns the maximum size in bytes of a deploy's session or payment module.
+    pub fn max_module_size(self) -> u32 {
+        self.max_module_size
+    }
+
+    /// Sets the maximum size in bytes of a deploy's session or payment module.
+    pub fn with_max_module_size(mut self, max_module_size: u32) -> EngineConfig {
+        self.max_module_size = max_module_size;
+        self
+    }
+
+    /// Returns the maximum combined size in bytes of a deploy's session and payment args.
+    pub fn max_args_size(self) -> u32 {
+        self.max_args_size
+    }
+
+    /// Sets the maximum combined size in bytes of a deploy's session and payment args.
+    pub fn with_max_args_size(mut self, max_args_size: u32) -> EngineConfig {
+        self.max_args_size = max_args_size;
+        self
+    }
+
+    /// Returns `true` if execution results include a profile of the host function calls made.
+    pub fn host_function_profiling(self) -> bool {
+        self.host_function_profiling
+    }
+
+    /// Sets whether execution results include a profile of the host function calls made.  When
+    /// unset, the calls are only logged individually.
+    pub fn with_host_function_profiling(mut self, host_function_profiling: bool) -> EngineConfig {
+        self.host_function_profiling = host_function_profiling;
+        self
+    }
+
+    /// Returns the maximum number of `Key` values a query will dereference in following its path.
+    pub fn max_query_depth(self) -> u32 {
+        self.max_query_depth
+    }
+
+    /// Sets the maximum number of `Key` values a query will dereference in following its path.
+    /// Queries exceeding it fail rather than following the chain of keys to its end.
+    pub fn with_max_query_depth(mut self, max_query_depth: u32) -> EngineConfig {
+        self.max_query_depth = max_query_depth;
+        self
+    }
 }
@@ -3,8 +3,8 @@ use failure::Fail;
 use engine_shared::newtypes::Blake2bHash;
 use types::{bytesrepr, system_contract_errors::mint};
 
-use crate::execution;
-use types::ProtocolVersion;
+use crate::execution::{self, ErrorCode};
+use types::{account::AccountHash, ProtocolVersion};
 
 #[derive(Fail, Debug)]
 pub enum Error {
@@ -29,6 +29,8 @@ pub enum Error {
     Storage(engine_storage::error::Error),
     #[fail(display = "Authorization failure: not authorized.")]
     Authorization,
+    #[fail(display = "Account not found: {}", _0)]
+    AccountNotFound(AccountHash),
     #[fail(display = "Insufficient payment")]
     InsufficientPayment,
     #[fail(display = "Deploy error")]
@@ -47,6 +49,32 @@ pub enum Error {
     InvalidUpgradeResult,
     #[fail(display = "Unsupported deploy item variant: {}", _0)]
     InvalidDeployItemVariant(String),
+    #[fail(
+        display = "Module of {} bytes exceeds the maximum module size of {} bytes",
+        actual, max
+    )]
+    ModuleTooLarge { actual: usize, max: u32 },
+    #[fail(
+        display = "Args of {} bytes exceed the maximum args size of {} bytes",
+        actual, max
+    )]
+    ArgsTooLarge { actual: usize, max: u32 },
+    #[fail(display = "Engine is closing")]
+    Closing,
+}
+
+impl Error {
+    /// Returns the stable classification of this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::WasmPreprocessing(_) | Error::WasmSerialization(_) => {
+                ErrorCode::WasmPreprocessing
+            }
+            Error::Exec(error) => error.code(),
+            Error::Storage(_) => ErrorCode::Storage,
+            _ => ErrorCode::Unknown,
+        }
+    }
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
@@ -102,3 +130,18 @@ impl RootNotFound {
         self.0.to_vec()
     }
 }
+
+/// The reasons a whole execute request fails, as opposed to one of its deploys.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ExecuteError {
+    /// The parent state root of the request is missing.
+    RootNotFound(RootNotFound),
+    /// The engine is closing and no longer starts new requests.
+    Closing,
+}
+
+impl From<RootNotFound> for ExecuteError {
+    fn from(error: RootNotFound) -> Self {
+        ExecuteError::RootNotFound(error)
+    }
+}
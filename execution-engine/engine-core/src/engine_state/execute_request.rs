@@ -1,7 +1,7 @@
 use std::mem;
 
 use engine_shared::newtypes::Blake2bHash;
-use types::ProtocolVersion;
+use types::{account::AccountHash, ProtocolVersion};
 
 use super::{deploy_item::DeployItem, execution_result::ExecutionResult};
 
@@ -11,6 +11,11 @@ pub struct ExecuteRequest {
     pub block_time: u64,
     pub deploys: Vec<Result<DeployItem, ExecutionResult>>,
     pub protocol_version: ProtocolVersion,
+    /// The proposer of the block, to whom the accumulated rewards are paid out once the deploys
+    /// have been executed.  If `None`, rewards are not distributed.
+    pub proposer: Option<Result<AccountHash, ExecutionResult>>,
+    /// If `true`, each deploy's result includes a profile of the host function calls it made.
+    pub profile_host_functions: bool,
 }
 
 impl ExecuteRequest {
@@ -19,15 +24,24 @@ impl ExecuteRequest {
         block_time: u64,
         deploys: Vec<Result<DeployItem, ExecutionResult>>,
         protocol_version: ProtocolVersion,
+        proposer: Option<Result<AccountHash, ExecutionResult>>,
     ) -> Self {
         Self {
             parent_state_hash,
             block_time,
             deploys,
             protocol_version,
+            proposer,
+            profile_host_functions: false,
         }
     }
 
+    /// Sets whether each deploy's result includes a profile of the host function calls it made.
+    pub fn with_profile_host_functions(mut self, profile_host_functions: bool) -> Self {
+        self.profile_host_functions = profile_host_functions;
+        self
+    }
+
     pub fn take_deploys(&mut self) -> Vec<Result<DeployItem, ExecutionResult>> {
         mem::replace(&mut self.deploys, vec![])
     }
@@ -40,6 +54,8 @@ impl Default for ExecuteRequest {
             block_time: 0,
             deploys: vec![],
             protocol_version: Default::default(),
+            proposer: None,
+            profile_host_functions: false,
         }
     }
 }
@@ -1,16 +1,659 @@
-use engine_shared::{additive_map::AdditiveMap, transform::Transform};
-use types::Key;
-
-use super::op::Op;
-
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
-pub struct ExecutionEffect {
-    pub ops: AdditiveMap<Key, Op>,
-    pub transforms: AdditiveMap<Key, Transform>,
-}
-
-impl ExecutionEffect {
-    pub fn new(ops: AdditiveMap<Key, Op>, transforms: AdditiveMap<Key, Transform>) -> Self {
-        ExecutionEffect { ops, transforms }
-    }
-}
+use std::{
+    cmp,
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+use failure::Fail;
+
+use engine_shared::{additive_map::AdditiveMap, transform::Transform};
+use types::{Key, U512};
+
+use super::op::Op;
+
+/// An event emitted by a contract.  Events are reported alongside a deploy's effects but are never
+/// written to global state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractEvent {
+    /// The key of the account or contract in whose context the event was emitted.
+    pub contract_key: Key,
+    pub topic: String,
+    pub payload: Vec<u8>,
+}
+
+impl ContractEvent {
+    pub fn new(contract_key: Key, topic: String, payload: Vec<u8>) -> Self {
+        ContractEvent {
+            contract_key,
+            topic,
+            payload,
+        }
+    }
+
+    /// The number of bytes counted against a deploy's event size limit.
+    pub fn size(&self) -> usize {
+        self.topic.len() + self.payload.len()
+    }
+}
+
+/// How far the deploys behind an effect lower a `U512` value they subtract from.  Whether two
+/// effects which both add to or subtract from a value commute depends on this, since a subtraction
+/// fails if it would take the value below zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Debit {
+    /// The sum of each deploy's net subtraction from the value.  Deploys which add more than they
+    /// subtract count as zero.
+    pub amount: U512,
+    /// The lowest the value drops to while the deploys are applied to their pre-state, in
+    /// whichever order they run, less any minimum it was checked to be at least.
+    pub floor: U512,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionEffect {
+    pub ops: AdditiveMap<Key, Op>,
+    pub transforms: AdditiveMap<Key, Transform>,
+    /// Events emitted by contracts, in emission order.
+    pub events: Vec<ContractEvent>,
+    /// The debits of values which dropped below their pre-state value at some point.
+    pub debits: BTreeMap<Key, Debit>,
+}
+
+/// Returned by [`ExecutionEffect::merge`] when the two effects touch the same keys in ways which
+/// give a different outcome depending on the order they're applied in.
+#[derive(Fail, Clone, Debug, PartialEq, Eq)]
+pub struct CommutativityViolation {
+    /// The conflicting keys, in ascending order.
+    pub keys: Vec<Key>,
+}
+
+impl Display for CommutativityViolation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Effects don't commute on keys {:?}", self.keys)
+    }
+}
+
+impl ExecutionEffect {
+    pub fn new(ops: AdditiveMap<Key, Op>, transforms: AdditiveMap<Key, Transform>) -> Self {
+        ExecutionEffect {
+            ops,
+            transforms,
+            events: Vec::new(),
+            debits: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_events(mut self, events: Vec<ContractEvent>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Records the lowest value a single deploy left under each key in `floors`, on which it
+    /// dropped below its pre-state value.  The debited amount is taken from the net transform.
+    pub fn with_debit_floors(mut self, floors: &BTreeMap<Key, U512>) -> Self {
+        for (key, floor) in floors {
+            let amount = match self.transforms.get(key) {
+                Some(Transform::SubUInt512(amount)) => *amount,
+                _ => U512::zero(),
+            };
+            self.debits.insert(
+                *key,
+                Debit {
+                    amount,
+                    floor: *floor,
+                },
+            );
+        }
+        self
+    }
+
+    /// Returns the keys on which `self` and `other` don't commute, in ascending order.
+    ///
+    /// Reads commute with reads, and additions commute with additions of the same numeric type or
+    /// of named keys which don't clash.  Additions to and subtractions from a `U512` commute as
+    /// long as neither effect's floor is lower than the other's debit, so that neither can take
+    /// the value below zero whichever runs first.  Anything else touching the same key, including
+    /// a read of a key the other effect writes, conflicts.
+    pub fn conflicts_with(&self, other: &ExecutionEffect) -> Vec<Key> {
+        let mut keys: Vec<Key> = self
+            .touched_keys()
+            .filter(|key| !self.commutes_on(other, key))
+            .collect();
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+
+    /// Combines `self` and `other` into the effect of applying both, which is only well-defined if
+    /// they commute.  Events never conflict; those of `other` follow those of `self`.
+    pub fn merge(
+        mut self,
+        other: ExecutionEffect,
+    ) -> Result<ExecutionEffect, CommutativityViolation> {
+        let keys = self.conflicts_with(&other);
+        if !keys.is_empty() {
+            return Err(CommutativityViolation { keys });
+        }
+        let debits = self
+            .debited_keys()
+            .chain(other.debited_keys())
+            .filter_map(|key| Some((key, self.merged_debit(&other, &key)?)))
+            .collect();
+        for (key, op) in other.ops {
+            self.ops.insert_add(key, op);
+        }
+        for (key, transform) in other.transforms {
+            self.transforms.insert_add(key, transform);
+        }
+        self.events.extend(other.events);
+        self.debits = debits;
+        Ok(self)
+    }
+
+    fn touched_keys(&self) -> impl Iterator<Item = Key> + '_ {
+        self.ops.keys().chain(self.transforms.keys()).copied()
+    }
+
+    fn debited_keys(&self) -> impl Iterator<Item = Key> + '_ {
+        self.debits.keys().chain(self.transforms.keys()).copied()
+    }
+
+    /// The debited amount and floor of `key`, or if no debit is recorded, those implied by its
+    /// transform.  A subtraction without a recorded floor may have emptied the value, so its floor
+    /// is taken to be zero.  A value which never dropped has no floor.
+    fn debit_on(&self, key: &Key) -> (U512, Option<U512>) {
+        if let Some(debit) = self.debits.get(key) {
+            return (debit.amount, Some(debit.floor));
+        }
+        match self.transforms.get(key) {
+            Some(Transform::SubUInt512(amount)) => (*amount, Some(U512::zero())),
+            _ => (U512::zero(), None),
+        }
+    }
+
+    fn debits_commute_on(&self, other: &ExecutionEffect, key: &Key) -> bool {
+        let (amount, floor) = self.debit_on(key);
+        let (other_amount, other_floor) = other.debit_on(key);
+        floor.map_or(true, |floor| floor >= other_amount)
+            && other_floor.map_or(true, |other_floor| other_floor >= amount)
+    }
+
+    /// The debit of applying both `self` and `other`, in either order, or `None` if neither drops
+    /// the value under `key`.  Each effect's floor is lowered by the other's debit, since that
+    /// may be applied first.
+    fn merged_debit(&self, other: &ExecutionEffect, key: &Key) -> Option<Debit> {
+        let (amount, floor) = self.debit_on(key);
+        let (other_amount, other_floor) = other.debit_on(key);
+        let floor = floor.map(|floor| floor.saturating_sub(other_amount));
+        let other_floor = other_floor.map(|other_floor| other_floor.saturating_sub(amount));
+        let floor = match (floor, other_floor) {
+            (Some(floor), Some(other_floor)) => cmp::min(floor, other_floor),
+            (Some(floor), None) | (None, Some(floor)) => floor,
+            (None, None) => return None,
+        };
+        Some(Debit {
+            amount: amount + other_amount,
+            floor,
+        })
+    }
+
+    /// The op recorded for `key`, or if there is none, the op implied by its transform.
+    fn op_on(&self, key: &Key) -> Op {
+        if let Some(op) = self.ops.get(key) {
+            return *op;
+        }
+        match self.transforms.get(key) {
+            None => Op::NoOp,
+            Some(Transform::Identity) => Op::Read,
+            Some(Transform::AddInt32(_))
+            | Some(Transform::AddUInt64(_))
+            | Some(Transform::AddUInt128(_))
+            | Some(Transform::AddUInt256(_))
+            | Some(Transform::AddUInt512(_))
+            | Some(Transform::SubUInt512(_))
+            | Some(Transform::AddKeys(_)) => Op::Add,
+            Some(Transform::Write(_)) | Some(Transform::Delete) | Some(Transform::Failure(_)) => {
+                Op::Write
+            }
+        }
+    }
+
+    fn commutes_on(&self, other: &ExecutionEffect, key: &Key) -> bool {
+        match (self.op_on(key), other.op_on(key)) {
+            (Op::NoOp, _) | (_, Op::NoOp) => true,
+            (Op::Read, Op::Read) => true,
+            (Op::Add, Op::Add) => match (self.transforms.get(key), other.transforms.get(key)) {
+                (Some(transform), Some(other_transform)) => {
+                    additions_commute(transform, other_transform)
+                        && self.debits_commute_on(other, key)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+fn additions_commute(transform: &Transform, other: &Transform) -> bool {
+    match (transform, other) {
+        (Transform::AddInt32(_), Transform::AddInt32(_))
+        | (Transform::AddUInt64(_), Transform::AddUInt64(_))
+        | (Transform::AddUInt128(_), Transform::AddUInt128(_))
+        | (Transform::AddUInt256(_), Transform::AddUInt256(_))
+        | (Transform::AddUInt512(_), Transform::AddUInt512(_))
+        | (Transform::AddUInt512(_), Transform::SubUInt512(_))
+        | (Transform::SubUInt512(_), Transform::AddUInt512(_))
+        | (Transform::SubUInt512(_), Transform::SubUInt512(_)) => true,
+        (Transform::AddKeys(named_keys), Transform::AddKeys(other_named_keys)) => {
+            named_keys.iter().all(|(name, key)| {
+                other_named_keys
+                    .get(name)
+                    .map_or(true, |other| other == key)
+            })
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use proptest::{collection::vec, prelude::*};
+
+    use engine_shared::{
+        newtypes::{Blake2bHash, CorrelationId},
+        stored_value::StoredValue,
+    };
+    use engine_storage::global_state::{
+        in_memory::InMemoryGlobalState, CommitResult, StateProvider,
+    };
+    use types::{contracts::NamedKeys, CLValue, U512};
+
+    use super::*;
+    use crate::tracking_copy::{AddResult, TrackingCopy};
+
+    const KEY_COUNT: u8 = 6;
+    const INITIAL_VALUE: u64 = 1_000;
+
+    fn key(index: u8) -> Key {
+        Key::Hash([index; 32])
+    }
+
+    fn value(amount: u64) -> StoredValue {
+        StoredValue::CLValue(CLValue::from_t(U512::from(amount)).unwrap())
+    }
+
+    fn effect(entries: Vec<(Key, Op, Transform)>) -> ExecutionEffect {
+        let mut effect = ExecutionEffect::default();
+        for (key, op, transform) in entries {
+            effect.ops.insert_add(key, op);
+            effect.transforms.insert_add(key, transform);
+        }
+        effect
+    }
+
+    #[test]
+    fn should_conflict_on_write_write_and_read_write() {
+        let a = effect(vec![
+            (key(0), Op::Write, Transform::Write(value(1))),
+            (key(1), Op::Read, Transform::Identity),
+        ]);
+        let b = effect(vec![
+            (key(0), Op::Write, Transform::Write(value(2))),
+            (key(1), Op::Write, Transform::Write(value(3))),
+        ]);
+        assert_eq!(a.conflicts_with(&b), vec![key(0), key(1)]);
+        assert_eq!(
+            a.merge(b),
+            Err(CommutativityViolation {
+                keys: vec![key(0), key(1)]
+            })
+        );
+    }
+
+    #[test]
+    fn should_merge_reads_and_matching_additions() {
+        let a = effect(vec![
+            (key(0), Op::Read, Transform::Identity),
+            (key(1), Op::Add, Transform::AddUInt512(5.into())),
+            (key(2), Op::Add, Transform::AddUInt512(7.into())),
+        ]);
+        let b = effect(vec![
+            (key(0), Op::Read, Transform::Identity),
+            (key(1), Op::Add, Transform::AddUInt512(3.into())),
+            (key(2), Op::Add, Transform::SubUInt512(2.into())),
+        ]);
+        assert!(a.conflicts_with(&b).is_empty());
+
+        let merged = a.merge(b).expect("should merge");
+        assert_eq!(merged.ops.get(&key(0)), Some(&Op::Read));
+        assert_eq!(merged.ops.get(&key(1)), Some(&Op::Add));
+        assert_eq!(
+            merged.transforms.get(&key(1)),
+            Some(&Transform::AddUInt512(8.into()))
+        );
+        assert_eq!(
+            merged.transforms.get(&key(2)),
+            Some(&Transform::AddUInt512(5.into()))
+        );
+    }
+
+    #[test]
+    fn should_conflict_on_mismatched_or_unsafe_additions() {
+        let a = effect(vec![
+            (key(0), Op::Add, Transform::AddUInt64(1)),
+            (key(1), Op::Add, Transform::SubUInt512(1.into())),
+            (
+                key(2),
+                Op::Add,
+                Transform::AddKeys(named_keys("name", key(3))),
+            ),
+            (
+                key(4),
+                Op::Add,
+                Transform::AddKeys(named_keys("name", key(3))),
+            ),
+        ]);
+        let b = effect(vec![
+            (key(0), Op::Add, Transform::AddUInt512(1.into())),
+            (key(1), Op::Add, Transform::SubUInt512(1.into())),
+            (
+                key(2),
+                Op::Add,
+                Transform::AddKeys(named_keys("name", key(5))),
+            ),
+            (
+                key(4),
+                Op::Add,
+                Transform::AddKeys(named_keys("other", key(5))),
+            ),
+        ]);
+        assert_eq!(a.conflicts_with(&b), vec![key(0), key(1), key(2)]);
+    }
+
+    #[test]
+    fn should_conflict_on_debits_exceeding_floor() {
+        let debit = |amount: u64, floor: u64| {
+            let mut floors = BTreeMap::new();
+            floors.insert(key(0), U512::from(floor));
+            effect(vec![(
+                key(0),
+                Op::Add,
+                Transform::SubUInt512(amount.into()),
+            )])
+            .with_debit_floors(&floors)
+        };
+
+        // Both debits of a balance of 1_000 fit: each leaves enough for the other.
+        let a = debit(300, 700);
+        let b = debit(400, 600);
+        assert!(a.conflicts_with(&b).is_empty());
+        let merged = a.merge(b).expect("should merge");
+        assert_eq!(
+            merged.transforms.get(&key(0)),
+            Some(&Transform::SubUInt512(700.into()))
+        );
+        assert_eq!(
+            merged.debits.get(&key(0)),
+            Some(&Debit {
+                amount: 700.into(),
+                floor: 300.into()
+            })
+        );
+
+        // Together they overdraw it, so whichever runs second fails.
+        let a = debit(300, 700);
+        let b = debit(800, 200);
+        assert_eq!(a.conflicts_with(&b), vec![key(0)]);
+
+        // A third debit may fit alongside either of the merged pair, but not both.
+        let c = debit(600, 400);
+        let merged = debit(300, 700).merge(debit(200, 800)).unwrap();
+        assert!(debit(300, 700).conflicts_with(&c).is_empty());
+        assert!(debit(200, 800).conflicts_with(&c).is_empty());
+        assert_eq!(merged.conflicts_with(&c), vec![key(0)]);
+
+        // An addition never lowers the balance, whatever its debits.
+        let credit = effect(vec![(key(0), Op::Add, Transform::AddUInt512(5.into()))]);
+        assert!(credit.conflicts_with(&debit(1_000, 0)).is_empty());
+    }
+
+    #[test]
+    fn should_append_events_on_merge() {
+        let event = |topic: &str| ContractEvent::new(key(0), topic.to_string(), vec![1, 2, 3]);
+        let a = effect(vec![(key(0), Op::Write, Transform::Write(value(1)))])
+            .with_events(vec![event("first")]);
+        let b = effect(vec![(key(1), Op::Write, Transform::Write(value(2)))])
+            .with_events(vec![event("second"), event("third")]);
+
+        let merged = a.merge(b).expect("should merge");
+        assert_eq!(
+            merged.events,
+            vec![event("first"), event("second"), event("third")]
+        );
+    }
+
+    fn named_keys(name: &str, key: Key) -> NamedKeys {
+        let mut named_keys = NamedKeys::new();
+        named_keys.insert(name.to_string(), key);
+        named_keys
+    }
+
+    /// A step of a simulated deploy.  Amounts are large enough relative to `INITIAL_VALUE` that
+    /// subtractions may fail, depending on the order the deploys run in.
+    #[derive(Clone, Debug)]
+    enum Action {
+        Read(u8),
+        Write(u8, u64),
+        Add(u8, u64),
+        Sub(u8, u64),
+        Copy { from: u8, to: u8 },
+    }
+
+    fn action_arb() -> impl Strategy<Value = Action> {
+        let index = 0..KEY_COUNT;
+        let amount = 0..1_000u64;
+        prop_oneof![
+            index.clone().prop_map(Action::Read),
+            (index.clone(), amount.clone())
+                .prop_map(|(index, amount)| Action::Write(index, INITIAL_VALUE + amount)),
+            (index.clone(), amount.clone()).prop_map(|(index, amount)| Action::Add(index, amount)),
+            (index.clone(), amount).prop_map(|(index, amount)| Action::Sub(index, amount)),
+            (index.clone(), index).prop_map(|(from, to)| Action::Copy { from, to }),
+        ]
+    }
+
+    fn deploy_arb() -> impl Strategy<Value = Vec<Action>> {
+        vec(action_arb(), 0..4)
+    }
+
+    fn genesis() -> (InMemoryGlobalState, Blake2bHash) {
+        let pairs: Vec<(Key, StoredValue)> = (0..KEY_COUNT)
+            .map(|index| (key(index), value(INITIAL_VALUE)))
+            .collect();
+        InMemoryGlobalState::from_pairs(CorrelationId::new(), &pairs).unwrap()
+    }
+
+    /// Runs `deploy` against `state_hash`, returning its effect and the values it read, or `None`
+    /// if a subtraction would drop a value below zero.
+    fn execute(
+        global_state: &InMemoryGlobalState,
+        state_hash: Blake2bHash,
+        deploy: &[Action],
+    ) -> Option<(ExecutionEffect, Vec<Option<StoredValue>>)> {
+        let correlation_id = CorrelationId::new();
+        let view = global_state.checkout(state_hash).unwrap().unwrap();
+        let mut tracking_copy = TrackingCopy::new(view);
+        let mut reads = Vec::new();
+        for action in deploy {
+            match *action {
+                Action::Read(index) => {
+                    reads.push(tracking_copy.read(correlation_id, &key(index)).unwrap())
+                }
+                Action::Write(index, amount) => tracking_copy.write(key(index), value(amount)),
+                Action::Add(index, amount) => assert_matches!(
+                    tracking_copy.add(correlation_id, key(index), value(amount)),
+                    Ok(AddResult::Success)
+                ),
+                Action::Sub(index, amount) => {
+                    match tracking_copy.sub(correlation_id, key(index), U512::from(amount)) {
+                        Ok(AddResult::Success) => (),
+                        Ok(AddResult::TypeMismatch(_)) => return None,
+                        other => panic!("subtraction should succeed or underflow: {:?}", other),
+                    }
+                }
+                Action::Copy { from, to } => {
+                    let copied = tracking_copy.read(correlation_id, &key(from)).unwrap();
+                    reads.push(copied.clone());
+                    tracking_copy.write(key(to), copied.expect("should have value"));
+                }
+            }
+        }
+        Some((tracking_copy.effect(), reads))
+    }
+
+    fn commit(
+        global_state: &InMemoryGlobalState,
+        state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+    ) -> Blake2bHash {
+        match global_state
+            .commit(CorrelationId::new(), state_hash, effect.transforms, None)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            other => panic!("commit should succeed: {}", other),
+        }
+    }
+
+    /// Runs `deploy` against `state_hash`, rejecting the test case if it fails on its own.
+    fn execute_or_reject(
+        global_state: &InMemoryGlobalState,
+        state_hash: Blake2bHash,
+        deploy: &[Action],
+    ) -> Result<(ExecutionEffect, Vec<Option<StoredValue>>), TestCaseError> {
+        execute(global_state, state_hash, deploy)
+            .ok_or_else(|| TestCaseError::reject("deploy overdraws a value on its own"))
+    }
+
+    /// Runs `deploys` one after the other from `state_hash`, asserting that each succeeds and reads
+    /// the values in `reads`, and returns the resulting state hash.
+    fn execute_in_order(
+        global_state: &InMemoryGlobalState,
+        mut state_hash: Blake2bHash,
+        deploys: &[&[Action]],
+        reads: &[&Vec<Option<StoredValue>>],
+    ) -> Result<Blake2bHash, TestCaseError> {
+        for (deploy, expected_reads) in deploys.iter().zip(reads) {
+            let result = execute(global_state, state_hash, deploy);
+            prop_assert!(result.is_some(), "{:?} should succeed", deploy);
+            let (effect, reads) = result.unwrap();
+            prop_assert_eq!(&reads, *expected_reads);
+            state_hash = commit(global_state, state_hash, effect);
+        }
+        Ok(state_hash)
+    }
+
+    proptest! {
+        #[test]
+        fn should_merge_unless_conflicting(a in deploy_arb(), b in deploy_arb()) {
+            let (global_state, root) = genesis();
+            let (effect_a, _) = execute_or_reject(&global_state, root, &a)?;
+            let (effect_b, _) = execute_or_reject(&global_state, root, &b)?;
+
+            let conflicts = effect_a.conflicts_with(&effect_b);
+            prop_assert_eq!(&conflicts, &effect_b.conflicts_with(&effect_a));
+            match effect_a.merge(effect_b) {
+                Ok(_) => prop_assert!(conflicts.is_empty()),
+                Err(violation) => prop_assert_eq!(violation.keys, conflicts),
+            }
+        }
+
+        /// Brute-force check: when no conflicts are detected, running the deploys one after the
+        /// other, in either order, succeeds, reads the same values and reaches the same state as
+        /// committing the merged effect of running both against the same pre-state.
+        #[test]
+        fn merged_effect_should_match_sequential_execution(a in deploy_arb(), b in deploy_arb()) {
+            let (global_state, root) = genesis();
+            let (effect_a, reads_a) = execute_or_reject(&global_state, root, &a)?;
+            let (effect_b, reads_b) = execute_or_reject(&global_state, root, &b)?;
+
+            if let Ok(merged) = effect_a.merge(effect_b) {
+                let merged_root = commit(&global_state, root, merged);
+                for (deploys, reads) in &[
+                    ([&a[..], &b[..]], [&reads_a, &reads_b]),
+                    ([&b[..], &a[..]], [&reads_b, &reads_a]),
+                ] {
+                    let root = execute_in_order(&global_state, root, deploys, reads)?;
+                    prop_assert_eq!(root, merged_root);
+                }
+            }
+        }
+
+        /// As above, for every order of three deploys whose effects merge.  Debits which pairwise
+        /// fit in a balance may overdraw it together, which merging must detect.
+        #[test]
+        fn merged_effect_of_three_should_match_sequential_execution(
+            a in deploy_arb(),
+            b in deploy_arb(),
+            c in deploy_arb(),
+        ) {
+            let (global_state, root) = genesis();
+            let (effect_a, reads_a) = execute_or_reject(&global_state, root, &a)?;
+            let (effect_b, reads_b) = execute_or_reject(&global_state, root, &b)?;
+            let (effect_c, reads_c) = execute_or_reject(&global_state, root, &c)?;
+
+            let merged = effect_a.merge(effect_b).and_then(|ab| ab.merge(effect_c));
+            if let Ok(merged) = merged {
+                let merged_root = commit(&global_state, root, merged);
+                let deploys = [(&a[..], &reads_a), (&b[..], &reads_b), (&c[..], &reads_c)];
+                for &(first, second, third) in &[
+                    (0, 1, 2),
+                    (0, 2, 1),
+                    (1, 0, 2),
+                    (1, 2, 0),
+                    (2, 0, 1),
+                    (2, 1, 0),
+                ] {
+                    let order = [deploys[first], deploys[second], deploys[third]];
+                    let root = execute_in_order(
+                        &global_state,
+                        root,
+                        &[order[0].0, order[1].0, order[2].0],
+                        &[order[0].1, order[1].1, order[2].1],
+                    )?;
+                    prop_assert_eq!(root, merged_root);
+                }
+            }
+        }
+
+        #[test]
+        fn merge_should_be_associative(
+            a in deploy_arb(),
+            b in deploy_arb(),
+            c in deploy_arb(),
+        ) {
+            let (global_state, root) = genesis();
+            let (effect_a, _) = execute_or_reject(&global_state, root, &a)?;
+            let (effect_b, _) = execute_or_reject(&global_state, root, &b)?;
+            let (effect_c, _) = execute_or_reject(&global_state, root, &c)?;
+
+            let left = effect_a
+                .clone()
+                .merge(effect_b.clone())
+                .and_then(|ab| ab.merge(effect_c.clone()));
+            let right = effect_b
+                .merge(effect_c)
+                .and_then(|bc| effect_a.merge(bc));
+            match (left, right) {
+                (Ok(left), Ok(right)) => prop_assert_eq!(left, right),
+                (left, right) => prop_assert!(
+                    left.is_err() && right.is_err(),
+                    "only one grouping merged: {:?} / {:?}",
+                    left,
+                    right
+                ),
+            }
+        }
+    }
+}
@@ -1,10 +1,23 @@
-use super::{error, execution_effect::ExecutionEffect, op::Op, CONV_RATE};
+use std::{cmp, collections::BTreeMap};
+
+use super::{
+    error,
+    execution_effect::{ContractEvent, ExecutionEffect},
+    op::Op,
+    CONV_RATE,
+};
+use crate::execution::HostFunctionProfile;
 use engine_shared::{
-    additive_map::AdditiveMap, gas::Gas, motes::Motes, newtypes::CorrelationId,
-    stored_value::StoredValue, transform::Transform,
+    additive_map::AdditiveMap,
+    gas::{CostBreakdown, Gas},
+    motes::Motes,
+    newtypes::CorrelationId,
+    stored_value::StoredValue,
+    transform::Transform,
 };
 use engine_storage::global_state::StateReader;
-use types::{bytesrepr::FromBytes, CLTyped, CLValue, Key};
+use num_traits::Zero;
+use types::{bytesrepr::FromBytes, CLTyped, CLValue, Key, U512};
 
 fn make_payment_error_effects(
     max_payment_cost: Motes,
@@ -38,6 +51,15 @@ fn make_payment_error_effects(
     ExecutionEffect::new(ops, transforms)
 }
 
+/// The gas consumed by each phase of a deploy.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PhaseCosts {
+    pub payment: Gas,
+    pub session: Gas,
+    /// Finalization is run by the system and isn't charged for.
+    pub finalize: Gas,
+}
+
 #[derive(Debug)]
 pub enum ExecutionResult {
     /// An error condition that happened during execution
@@ -45,9 +67,26 @@ pub enum ExecutionResult {
         error: error::Error,
         effect: ExecutionEffect,
         cost: Gas,
+        cost_breakdown: CostBreakdown,
+        /// The host function calls made, if profiling was enabled.
+        host_function_profile: HostFunctionProfile,
+        /// Motes charged to the deploying account for `cost`.
+        charged: Motes,
+        /// The gas consumed by each phase, which is only set on the combined result of a deploy.
+        phase_costs: PhaseCosts,
     },
     /// Execution was finished successfully
-    Success { effect: ExecutionEffect, cost: Gas },
+    Success {
+        effect: ExecutionEffect,
+        cost: Gas,
+        cost_breakdown: CostBreakdown,
+        /// The host function calls made, if profiling was enabled.
+        host_function_profile: HostFunctionProfile,
+        /// Motes charged to the deploying account for `cost`.
+        charged: Motes,
+        /// The gas consumed by each phase, which is only set on the combined result of a deploy.
+        phase_costs: PhaseCosts,
+    },
 }
 
 pub enum ForcedTransferResult {
@@ -66,6 +105,10 @@ impl ExecutionResult {
             error,
             effect: Default::default(),
             cost: Gas::default(),
+            cost_breakdown: CostBreakdown::default(),
+            host_function_profile: HostFunctionProfile::default(),
+            charged: Motes::zero(),
+            phase_costs: PhaseCosts::default(),
         }
     }
 
@@ -99,6 +142,47 @@ impl ExecutionResult {
         }
     }
 
+    /// Returns the motes charged to the deploying account.  Only the combined result of a deploy
+    /// is charged for; the results of its individual phases report zero.
+    pub fn charged(&self) -> Motes {
+        match self {
+            ExecutionResult::Failure { charged, .. } => *charged,
+            ExecutionResult::Success { charged, .. } => *charged,
+        }
+    }
+
+    /// Returns the cost split by category.  This sums to [`ExecutionResult::cost`] for everything
+    /// but the flat penalty charged for failed payment code, which has no breakdown.
+    pub fn cost_breakdown(&self) -> &CostBreakdown {
+        match self {
+            ExecutionResult::Failure { cost_breakdown, .. } => cost_breakdown,
+            ExecutionResult::Success { cost_breakdown, .. } => cost_breakdown,
+        }
+    }
+
+    /// Returns the host function calls made, which is empty unless profiling was enabled.
+    pub fn host_function_profile(&self) -> &HostFunctionProfile {
+        match self {
+            ExecutionResult::Failure {
+                host_function_profile,
+                ..
+            } => host_function_profile,
+            ExecutionResult::Success {
+                host_function_profile,
+                ..
+            } => host_function_profile,
+        }
+    }
+
+    /// Returns the gas consumed by each phase, which is zero for all but the combined result of a
+    /// deploy.
+    pub fn phase_costs(&self) -> PhaseCosts {
+        match self {
+            ExecutionResult::Failure { phase_costs, .. } => *phase_costs,
+            ExecutionResult::Success { phase_costs, .. } => *phase_costs,
+        }
+    }
+
     pub fn effect(&self) -> &ExecutionEffect {
         match self {
             ExecutionResult::Failure { effect, .. } => effect,
@@ -106,28 +190,121 @@ impl ExecutionResult {
         }
     }
 
-    pub fn with_cost(self, cost: Gas) -> Self {
+    pub fn with_cost(self, cost: Gas, cost_breakdown: CostBreakdown, charged: Motes) -> Self {
         match self {
-            ExecutionResult::Failure { error, effect, .. } => ExecutionResult::Failure {
+            ExecutionResult::Failure {
                 error,
+                effect,
+                host_function_profile,
+                phase_costs,
+                ..
+            } => ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                cost_breakdown,
+                host_function_profile,
+                charged,
+                phase_costs,
+            },
+            ExecutionResult::Success {
+                effect,
+                host_function_profile,
+                phase_costs,
+                ..
+            } => ExecutionResult::Success {
                 effect,
                 cost,
+                cost_breakdown,
+                host_function_profile,
+                charged,
+                phase_costs,
             },
-            ExecutionResult::Success { effect, .. } => ExecutionResult::Success { effect, cost },
         }
     }
 
     pub fn with_effect(self, effect: ExecutionEffect) -> Self {
         match self {
-            ExecutionResult::Failure { error, cost, .. } => ExecutionResult::Failure {
+            ExecutionResult::Failure {
+                error,
+                cost,
+                cost_breakdown,
+                host_function_profile,
+                charged,
+                phase_costs,
+                ..
+            } => ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                cost_breakdown,
+                host_function_profile,
+                charged,
+                phase_costs,
+            },
+            ExecutionResult::Success {
+                cost,
+                cost_breakdown,
+                host_function_profile,
+                charged,
+                phase_costs,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                cost_breakdown,
+                host_function_profile,
+                charged,
+                phase_costs,
             },
-            ExecutionResult::Success { cost, .. } => ExecutionResult::Success { effect, cost },
         }
     }
 
+    pub fn with_host_function_profile(self, host_function_profile: HostFunctionProfile) -> Self {
+        match self {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                cost_breakdown,
+                charged,
+                phase_costs,
+                ..
+            } => ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                cost_breakdown,
+                host_function_profile,
+                charged,
+                phase_costs,
+            },
+            ExecutionResult::Success {
+                effect,
+                cost,
+                cost_breakdown,
+                charged,
+                phase_costs,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                cost_breakdown,
+                host_function_profile,
+                charged,
+                phase_costs,
+            },
+        }
+    }
+
+    pub fn with_phase_costs(mut self, new_phase_costs: PhaseCosts) -> Self {
+        match &mut self {
+            ExecutionResult::Failure { phase_costs, .. } => *phase_costs = new_phase_costs,
+            ExecutionResult::Success { phase_costs, .. } => *phase_costs = new_phase_costs,
+        }
+        self
+    }
+
     pub fn as_error(&self) -> Option<&error::Error> {
         match self {
             ExecutionResult::Failure { error, .. } => Some(error),
@@ -147,10 +324,11 @@ impl ExecutionResult {
     pub fn check_forced_transfer(
         &self,
         payment_purse_balance: Motes,
+        gas_price: u64,
     ) -> Option<ForcedTransferResult> {
-        let payment_result_cost = match Motes::from_gas(self.cost(), CONV_RATE) {
+        let payment_result_cost = match Motes::from_gas(self.cost(), gas_price) {
             Some(cost) => cost,
-            // Multiplying cost by CONV_RATE overflowed the U512 range
+            // Multiplying cost by gas_price overflowed the U512 range
             None => return Some(ForcedTransferResult::InsufficientPayment),
         };
         // payment_code_spec_3_b_ii: if (balance of PoS pay purse) < (gas spent during
@@ -179,6 +357,7 @@ impl ExecutionResult {
         account_main_purse_balance: Motes,
         account_main_purse: Key,
         rewards_purse: Key,
+        gas_price: u64,
     ) -> ExecutionResult {
         let effect = make_payment_error_effects(
             max_payment_cost,
@@ -186,11 +365,15 @@ impl ExecutionResult {
             account_main_purse,
             rewards_purse,
         );
-        let cost = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
+        let cost = Gas::from_motes(max_payment_cost, gas_price).unwrap_or_default();
         ExecutionResult::Failure {
             error,
             effect,
             cost,
+            cost_breakdown: CostBreakdown::default(),
+            host_function_profile: HostFunctionProfile::default(),
+            charged: max_payment_cost,
+            phase_costs: PhaseCosts::default(),
         }
     }
 
@@ -214,6 +397,7 @@ pub struct ExecutionResultBuilder {
     payment_execution_result: Option<ExecutionResult>,
     session_execution_result: Option<ExecutionResult>,
     finalize_execution_result: Option<ExecutionResult>,
+    gas_price: u64,
 }
 
 impl Default for ExecutionResultBuilder {
@@ -222,6 +406,7 @@ impl Default for ExecutionResultBuilder {
             payment_execution_result: None,
             session_execution_result: None,
             finalize_execution_result: None,
+            gas_price: CONV_RATE,
         }
     }
 }
@@ -252,6 +437,18 @@ impl ExecutionResultBuilder {
         self
     }
 
+    /// Sets the number of motes charged per unit of gas.  Defaults to [`CONV_RATE`].
+    pub fn set_gas_price(&mut self, gas_price: u64) -> &mut ExecutionResultBuilder {
+        self.gas_price = gas_price;
+        self
+    }
+
+    /// Returns the motes charged for [`ExecutionResultBuilder::total_cost`], or `None` on
+    /// overflow.
+    pub fn total_charged(&self) -> Option<Motes> {
+        Motes::from_gas(self.total_cost(), self.gas_price)
+    }
+
     pub fn total_cost(&self) -> Gas {
         let payment_cost = self
             .payment_execution_result
@@ -266,26 +463,84 @@ impl ExecutionResultBuilder {
         payment_cost + session_cost
     }
 
+    /// Returns the payment and session cost breakdowns combined, matching
+    /// [`ExecutionResultBuilder::total_cost`].
+    pub fn total_cost_breakdown(&self) -> CostBreakdown {
+        let mut cost_breakdown = CostBreakdown::default();
+        for result in self
+            .payment_execution_result
+            .iter()
+            .chain(self.session_execution_result.iter())
+        {
+            cost_breakdown.merge(result.cost_breakdown());
+        }
+        cost_breakdown
+    }
+
+    /// Returns the gas consumed by each of the phases whose results have been set.
+    pub fn phase_costs(&self) -> PhaseCosts {
+        let cost = |result: &Option<ExecutionResult>| {
+            result
+                .as_ref()
+                .map(ExecutionResult::cost)
+                .unwrap_or_default()
+        };
+        PhaseCosts {
+            payment: cost(&self.payment_execution_result),
+            session: cost(&self.session_execution_result),
+            finalize: cost(&self.finalize_execution_result),
+        }
+    }
+
+    /// Returns the host function calls made by payment and session code combined.
+    pub fn total_host_function_profile(&self) -> HostFunctionProfile {
+        let mut host_function_profile = HostFunctionProfile::default();
+        for result in self
+            .payment_execution_result
+            .iter()
+            .chain(self.session_execution_result.iter())
+        {
+            host_function_profile.merge(result.host_function_profile());
+        }
+        host_function_profile
+    }
+
     pub fn build<R: StateReader<Key, StoredValue>>(
         self,
         reader: &R,
         correlation_id: CorrelationId,
     ) -> Result<ExecutionResult, ExecutionResultBuilderError> {
         let cost = self.total_cost();
+        let cost_breakdown = self.total_cost_breakdown();
+        let host_function_profile = self.total_host_function_profile();
+        let charged = self.total_charged().unwrap_or_default();
+        let phase_costs = self.phase_costs();
         let mut ops = AdditiveMap::new();
         let mut transforms = AdditiveMap::new();
+        let mut events = Vec::new();
+        let mut floors = BTreeMap::new();
 
         let mut ret: ExecutionResult = ExecutionResult::Success {
             effect: Default::default(),
             cost,
+            cost_breakdown: cost_breakdown.clone(),
+            host_function_profile: host_function_profile.clone(),
+            charged,
+            phase_costs,
         };
 
         match self.payment_execution_result {
             Some(result) => {
                 if result.is_failure() {
-                    return Ok(result);
+                    return Ok(result.with_phase_costs(phase_costs));
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(
+                        &mut ops,
+                        &mut transforms,
+                        &mut events,
+                        &mut floors,
+                        result.effect(),
+                    );
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingPaymentExecutionResult),
@@ -296,9 +551,18 @@ impl ExecutionResultBuilder {
         match self.session_execution_result {
             Some(result) => {
                 if result.is_failure() {
-                    ret = result.with_cost(cost);
+                    ret = result
+                        .with_cost(cost, cost_breakdown, charged)
+                        .with_host_function_profile(host_function_profile)
+                        .with_phase_costs(phase_costs);
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(
+                        &mut ops,
+                        &mut transforms,
+                        &mut events,
+                        &mut floors,
+                        result.effect(),
+                    );
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingSessionExecutionResult),
@@ -312,7 +576,13 @@ impl ExecutionResultBuilder {
                         error::Error::Finalization,
                     ));
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(
+                        &mut ops,
+                        &mut transforms,
+                        &mut events,
+                        &mut floors,
+                        result.effect(),
+                    );
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingFinalizeExecutionResult),
@@ -321,12 +591,18 @@ impl ExecutionResultBuilder {
         // Remove redundant writes to allow more opportunity to commute
         let reduced_effect = Self::reduce_identity_writes(ops, transforms, reader, correlation_id);
 
-        Ok(ret.with_effect(reduced_effect))
+        Ok(ret.with_effect(
+            reduced_effect
+                .with_events(events)
+                .with_debit_floors(&floors),
+        ))
     }
 
     fn add_effects(
         ops: &mut AdditiveMap<Key, Op>,
         transforms: &mut AdditiveMap<Key, Transform>,
+        events: &mut Vec<ContractEvent>,
+        floors: &mut BTreeMap<Key, U512>,
         effect: &ExecutionEffect,
     ) {
         for (k, op) in effect.ops.iter() {
@@ -335,6 +611,12 @@ impl ExecutionResultBuilder {
         for (k, t) in effect.transforms.iter() {
             transforms.insert_add(*k, t.clone())
         }
+        events.extend(effect.events.iter().cloned());
+        // Each phase's floors are values it left, so the lowest across phases bounds the deploy's.
+        for (k, debit) in effect.debits.iter() {
+            let floor = floors.entry(*k).or_insert(debit.floor);
+            *floor = cmp::min(*floor, debit.floor);
+        }
     }
 
     /// In the case we are writing the same value as was there originally,
@@ -8,15 +8,18 @@ use rand::{
 
 use engine_shared::{motes::Motes, newtypes::Blake2bHash, TypeMismatch};
 use engine_storage::global_state::CommitResult;
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::wasm_costs::{HostFunctionCost, HostFunctionCosts, WasmCosts};
 use types::{account::AccountHash, bytesrepr, Key, ProtocolVersion, U512};
 
-use crate::engine_state::execution_effect::ExecutionEffect;
+use crate::engine_state::{execution_effect::ExecutionEffect, CONV_RATE};
 
 pub const PLACEHOLDER_KEY: Key = Key::Hash([0u8; 32]);
 pub const POS_BONDING_PURSE: &str = "pos_bonding_purse";
 pub const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
 pub const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
+pub const POS_GAS_PRICE: &str = "gas_price";
+pub const MINT_TOTAL_SUPPLY: &str = "total_supply";
+pub const MINT_ROUND_SEIGNIORAGE_RATE: &str = "round_seigniorage_rate";
 
 pub enum GenesisResult {
     RootNotFound,
@@ -188,6 +191,12 @@ pub struct ExecConfig {
     standard_payment_installer_bytes: Vec<u8>,
     accounts: Vec<GenesisAccount>,
     wasm_costs: WasmCosts,
+    unbonding_delay_millis: u64,
+    gas_price: u64,
+    round_seigniorage_rate: (u64, u64),
+    min_bond_amount: Motes,
+    max_bond_amount: Motes,
+    trie_chunk_threshold: Option<u32>,
 }
 
 impl ExecConfig {
@@ -204,8 +213,57 @@ impl ExecConfig {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            unbonding_delay_millis: 0,
+            gas_price: CONV_RATE,
+            round_seigniorage_rate: (0, 1),
+            min_bond_amount: Motes::zero(),
+            max_bond_amount: Motes::new(U512::MAX),
+            trie_chunk_threshold: None,
         }
     }
+
+    /// Sets the time, in milliseconds, the proof-of-stake contract holds back unbonded stakes
+    /// before paying them out.  Defaults to `0`.
+    pub fn with_unbonding_delay_millis(mut self, unbonding_delay_millis: u64) -> ExecConfig {
+        self.unbonding_delay_millis = unbonding_delay_millis;
+        self
+    }
+
+    /// Sets the number of motes charged per unit of gas, stored by the proof-of-stake contract.
+    /// Defaults to [`CONV_RATE`].
+    pub fn with_gas_price(mut self, gas_price: u64) -> ExecConfig {
+        self.gas_price = gas_price;
+        self
+    }
+
+    /// Sets the share of the total supply the mint creates as seigniorage each round, as a
+    /// `numerator` over a `denominator`.  Defaults to no seigniorage.
+    pub fn with_round_seigniorage_rate(mut self, numerator: u64, denominator: u64) -> ExecConfig {
+        self.round_seigniorage_rate = (numerator, denominator);
+        self
+    }
+
+    /// Sets the smallest nonzero and the largest stake a validator may hold, enforced by the
+    /// proof-of-stake contract on bonding, delegating and unbonding.  Defaults to no bounds.
+    pub fn with_bond_amount_bounds(mut self, min: Motes, max: Motes) -> ExecConfig {
+        self.min_bond_amount = min;
+        self.max_bond_amount = max;
+        self
+    }
+
+    /// Sets the serialized length, in bytes, above which values written to global state are
+    /// stored in chunks.  It is recorded in the genesis protocol data, so it can't change over the
+    /// life of the chain.  Defaults to never chunking values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `trie_chunk_threshold` is `0`.
+    pub fn with_trie_chunk_threshold(mut self, trie_chunk_threshold: u32) -> ExecConfig {
+        assert!(trie_chunk_threshold > 0, "chunk threshold must be > 0");
+        self.trie_chunk_threshold = Some(trie_chunk_threshold);
+        self
+    }
+
     pub fn mint_installer_bytes(&self) -> &[u8] {
         self.mint_installer_bytes.as_slice()
     }
@@ -222,6 +280,31 @@ impl ExecConfig {
         self.wasm_costs
     }
 
+    pub fn unbonding_delay_millis(&self) -> u64 {
+        self.unbonding_delay_millis
+    }
+
+    pub fn gas_price(&self) -> u64 {
+        self.gas_price
+    }
+
+    /// Returns the round seigniorage rate as a `(numerator, denominator)` pair.
+    pub fn round_seigniorage_rate(&self) -> (u64, u64) {
+        self.round_seigniorage_rate
+    }
+
+    pub fn min_bond_amount(&self) -> Motes {
+        self.min_bond_amount
+    }
+
+    pub fn max_bond_amount(&self) -> Motes {
+        self.max_bond_amount
+    }
+
+    pub fn trie_chunk_threshold(&self) -> Option<u32> {
+        self.trie_chunk_threshold
+    }
+
     pub fn get_bonded_validators(&self) -> impl Iterator<Item = (AccountHash, Motes)> + '_ {
         let zero = Motes::zero();
         self.accounts.iter().filter_map(move |genesis_account| {
@@ -272,6 +355,31 @@ impl Distribution<ExecConfig> for Standard {
             max_stack_height: rng.gen(),
             opcodes_mul: rng.gen(),
             opcodes_div: rng.gen(),
+            max_memory_pages: rng.gen_range(1, u32::max_value()),
+            max_table_size: rng.gen_range(1, u32::max_value()),
+            host_function_costs: HostFunctionCosts::uniform(HostFunctionCost::new(
+                rng.gen(),
+                rng.gen(),
+            )),
+        };
+
+        let unbonding_delay_millis = rng.gen();
+
+        let gas_price = rng.gen_range(1, u64::max_value());
+
+        let round_seigniorage_rate = (rng.gen(), rng.gen_range(1, u64::max_value()));
+
+        let mut u512_array = [0u8; 64];
+        rng.fill_bytes(u512_array.as_mut());
+        let min_bond_amount = Motes::new(U512::from(u512_array.as_ref()));
+
+        rng.fill_bytes(u512_array.as_mut());
+        let max_bond_amount = Motes::new(U512::from(u512_array.as_ref()));
+
+        let trie_chunk_threshold = if rng.gen() {
+            Some(rng.gen_range(1, u32::max_value()))
+        } else {
+            None
         };
 
         ExecConfig {
@@ -280,6 +388,12 @@ impl Distribution<ExecConfig> for Standard {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            unbonding_delay_millis,
+            gas_price,
+            round_seigniorage_rate,
+            min_bond_amount,
+            max_bond_amount,
+            trie_chunk_threshold,
         }
     }
 }
@@ -0,0 +1,130 @@
+use std::{
+    sync::{Condvar, Mutex},
+    time::{Duration, Instant},
+};
+
+#[derive(Default, Debug)]
+struct State {
+    count: usize,
+    closing: bool,
+}
+
+/// Tracks the requests currently being processed so that shutdown can wait for them to complete.
+#[derive(Default, Debug)]
+pub struct InFlightRequests {
+    state: Mutex<State>,
+    idle: Condvar,
+}
+
+impl InFlightRequests {
+    /// Registers the start of a request.  The request is considered finished once the returned
+    /// guard is dropped.
+    ///
+    /// Returns `None` without registering the request if [`close`](InFlightRequests::close) has
+    /// been called.  This is checked under the same lock `close` takes, so no request can start
+    /// after `close` has begun waiting.
+    pub fn start(&self) -> Option<InFlightRequest<'_>> {
+        let mut state = self.state.lock().unwrap();
+        if state.closing {
+            return None;
+        }
+        state.count += 1;
+        Some(InFlightRequest(self))
+    }
+
+    /// Returns the number of requests currently being processed.
+    pub fn count(&self) -> usize {
+        self.state.lock().unwrap().count
+    }
+
+    /// Returns `true` if [`close`](InFlightRequests::close) has been called.
+    pub fn is_closing(&self) -> bool {
+        self.state.lock().unwrap().closing
+    }
+
+    /// Marks the tracker as closing and blocks until all in-flight requests have finished or
+    /// `timeout` has elapsed.
+    ///
+    /// Returns `true` if all in-flight requests finished within `timeout`.
+    pub fn close(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.state.lock().unwrap();
+        state.closing = true;
+        while state.count > 0 {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            state = self.idle.wait_timeout(state, deadline - now).unwrap().0;
+        }
+        true
+    }
+
+    fn finish(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.count -= 1;
+        if state.count == 0 {
+            self.idle.notify_all();
+        }
+    }
+}
+
+/// A guard representing a single in-flight request.
+pub struct InFlightRequest<'a>(&'a InFlightRequests);
+
+impl<'a> Drop for InFlightRequest<'a> {
+    fn drop(&mut self) {
+        self.0.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use super::InFlightRequests;
+
+    #[test]
+    fn should_close_immediately_when_idle() {
+        let requests = InFlightRequests::default();
+        assert!(!requests.is_closing());
+        assert!(requests.close(Duration::from_secs(0)));
+        assert!(requests.is_closing());
+    }
+
+    #[test]
+    fn should_time_out_waiting_for_in_flight_request() {
+        let requests = InFlightRequests::default();
+        let _request = requests.start().expect("should start request");
+        assert_eq!(requests.count(), 1);
+        assert!(!requests.close(Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn should_refuse_to_start_once_closing() {
+        let requests = InFlightRequests::default();
+        assert!(requests.close(Duration::from_secs(0)));
+        assert!(requests.start().is_none());
+        assert_eq!(requests.count(), 0);
+    }
+
+    #[test]
+    fn should_wait_for_in_flight_request() {
+        let requests = Arc::new(InFlightRequests::default());
+        let (started_sender, started_receiver) = std::sync::mpsc::channel();
+
+        let handle = {
+            let requests = Arc::clone(&requests);
+            thread::spawn(move || {
+                let _request = requests.start().expect("should start request");
+                started_sender.send(()).unwrap();
+                thread::sleep(Duration::from_millis(50));
+            })
+        };
+
+        started_receiver.recv().unwrap();
+        assert!(requests.close(Duration::from_secs(10)));
+        assert_eq!(requests.count(), 0);
+        handle.join().unwrap();
+    }
+}
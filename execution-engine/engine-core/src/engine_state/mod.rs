@@ -1,3 +1,5 @@
+pub mod balance;
+pub mod bonded_validators;
 pub mod deploy_item;
 pub mod engine_config;
 mod error;
@@ -6,28 +8,36 @@ pub mod execute_request;
 pub mod execution_effect;
 pub mod execution_result;
 pub mod genesis;
+mod in_flight_requests;
+pub mod module_cache;
 pub mod op;
 pub mod query;
+pub mod read_only_view;
 pub mod run_genesis_request;
+pub mod state_read_cache;
 pub mod system_contract_cache;
 mod transfer;
 pub mod upgrade;
 pub mod utils;
+pub mod validation;
 
 use std::{
     cell::RefCell,
-    collections::{BTreeMap, BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet},
+    io::{Read, Write},
     rc::Rc,
+    time::Duration,
 };
 
 use log::{debug, warn};
 use num_traits::Zero;
-use parity_wasm::elements::Module;
+use parity_wasm::elements::{Internal, Module};
+use rayon::prelude::*;
 
 use engine_shared::{
     account::Account,
     additive_map::AdditiveMap,
-    gas::Gas,
+    gas::{CostBreakdown, Gas},
     motes::Motes,
     newtypes::{Blake2bHash, CorrelationId},
     stored_value::StoredValue,
@@ -36,42 +46,55 @@ use engine_shared::{
 use engine_storage::{
     global_state::{CommitResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
+    trie::TrieMerkleProof,
+    trie_store::PruneReport,
 };
-use engine_wasm_prep::{wasm_costs::WasmCosts, Preprocessor};
+use engine_wasm_prep::{wasm_costs::WasmCosts, PreprocessingError, Preprocessor};
 use types::{
     account::AccountHash,
     bytesrepr::{self, ToBytes},
-    contracts::{NamedKeys, ENTRY_POINT_NAME_INSTALL, UPGRADE_ENTRY_POINT_NAME},
+    contracts::{
+        NamedKeys, DEFAULT_ENTRY_POINT_NAME, ENTRY_POINT_NAME_INSTALL, UPGRADE_ENTRY_POINT_NAME,
+    },
     runtime_args,
     system_contract_errors::mint,
     system_contract_type::PROOF_OF_STAKE,
-    AccessRights, BlockTime, Contract, ContractHash, ContractPackage, ContractPackageHash,
+    AccessRights, BlockTime, CLValue, Contract, ContractHash, ContractPackage, ContractPackageHash,
     ContractVersionKey, EntryPoint, EntryPointType, Key, Phase, ProtocolVersion, RuntimeArgs, URef,
-    U512,
+    SYSTEM_CONTRACT_REGISTRY_ADDR, U512,
 };
 
 pub use self::{
     engine_config::EngineConfig,
-    error::{Error, RootNotFound},
+    error::{Error, ExecuteError, RootNotFound},
+    module_cache::ModuleCache,
+    read_only_view::ReadOnlyStateView,
+    state_read_cache::{CachedStateReader, StateReadCache},
     transfer::TransferRuntimeArgsBuilder,
 };
 use crate::{
     engine_state::{
+        balance::{BalanceRequest, BalanceResult},
+        bonded_validators::{BondedValidatorsRequest, BondedValidatorsResult},
         deploy_item::DeployItem,
         error::Error::MissingSystemContract,
         executable_deploy_item::ExecutableDeployItem,
         execute_request::ExecuteRequest,
-        execution_result::{ExecutionResult, ForcedTransferResult},
+        execution_result::{ExecutionResult, ForcedTransferResult, PhaseCosts},
         genesis::{
-            ExecConfig, GenesisAccount, GenesisResult, POS_PAYMENT_PURSE, POS_REWARDS_PURSE,
+            ExecConfig, GenesisAccount, GenesisResult, POS_GAS_PRICE, POS_PAYMENT_PURSE,
+            POS_REWARDS_PURSE,
         },
-        query::{QueryRequest, QueryResult},
+        in_flight_requests::{InFlightRequest, InFlightRequests},
+        query::{BatchQueryRequest, QueryRequest, QueryResult},
         system_contract_cache::SystemContractCache,
         transfer::TransferTargetMode,
         upgrade::{UpgradeConfig, UpgradeResult},
+        validation::ValidationResult,
     },
     execution::{
         self, AddressGenerator, AddressGeneratorBuilder, DirectSystemContractCall, Executor,
+        RngPurpose,
     },
     tracking_copy::{TrackingCopy, TrackingCopyExt},
 };
@@ -79,6 +102,7 @@ use crate::{
 // TODO?: MAX_PAYMENT && CONV_RATE values are currently arbitrary w/ real values
 // TBD gas * CONV_RATE = motes
 pub const MAX_PAYMENT: u64 = 10_000_000;
+/// The default gas price, used unless the genesis config sets another one.
 pub const CONV_RATE: u64 = 10;
 
 pub const SYSTEM_ACCOUNT_ADDR: AccountHash = AccountHash::new([0u8; 32]);
@@ -90,7 +114,10 @@ const ARG_AMOUNT: &str = "amount";
 pub struct EngineState<S> {
     config: EngineConfig,
     system_contract_cache: SystemContractCache,
+    state_read_cache: StateReadCache,
+    module_cache: ModuleCache,
     state: S,
+    in_flight_requests: InFlightRequests,
 }
 
 #[derive(Clone, Debug)]
@@ -126,10 +153,15 @@ where
 {
     pub fn new(state: S, config: EngineConfig) -> EngineState<S> {
         let system_contract_cache = Default::default();
+        let state_read_cache = StateReadCache::new(config.state_read_cache_capacity());
+        let module_cache = ModuleCache::new(config.module_cache_capacity());
         EngineState {
             config,
             system_contract_cache,
+            state_read_cache,
+            module_cache,
             state,
+            in_flight_requests: Default::default(),
         }
     }
 
@@ -137,6 +169,47 @@ where
         &self.config
     }
 
+    /// Returns the cache of global state values shared by the tracking copies of this engine.
+    pub fn state_read_cache(&self) -> &StateReadCache {
+        &self.state_read_cache
+    }
+
+    /// Returns the cache of preprocessed session and payment modules shared by executions of this
+    /// engine.
+    pub fn module_cache(&self) -> &ModuleCache {
+        &self.module_cache
+    }
+
+    /// Returns the number of `run_execute()` and `apply_effect()` calls currently in progress.
+    pub fn in_flight_requests(&self) -> usize {
+        self.in_flight_requests.count()
+    }
+
+    /// Returns `true` if `close()` has been called.  Callers should not start new executions or
+    /// commits once the engine is closing.
+    pub fn is_closing(&self) -> bool {
+        self.in_flight_requests.is_closing()
+    }
+
+    /// Registers the start of a request, failing with [`Error::Closing`] once `close()` has been
+    /// called.
+    fn start_request(&self) -> Result<InFlightRequest<'_>, Error> {
+        self.in_flight_requests.start().ok_or(Error::Closing)
+    }
+
+    /// Marks the engine as closing, waits up to `timeout` for in-progress executions and commits to
+    /// finish, then flushes global state to its backing storage.
+    ///
+    /// Returns `Ok(true)` if all in-progress requests finished within `timeout`.
+    pub fn close(&self, timeout: Duration) -> Result<bool, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let finished = self.in_flight_requests.close(timeout);
+        self.state.flush()?;
+        Ok(finished)
+    }
+
     pub fn wasm_costs(
         &self,
         protocol_version: ProtocolVersion,
@@ -158,6 +231,48 @@ where
         }
     }
 
+    /// Checks whether `module_bytes` would be accepted as session or payment code under
+    /// `protocol_version`, preprocessing it exactly as execution would.
+    pub fn validate_module(
+        &self,
+        protocol_version: ProtocolVersion,
+        module_bytes: &[u8],
+    ) -> Result<ValidationResult, Error> {
+        let wasm_costs = self
+            .wasm_costs(protocol_version)?
+            .ok_or_else(|| Error::InvalidProtocolVersion(protocol_version))?;
+        let preprocessor = Preprocessor::new(wasm_costs);
+
+        let preprocessed =
+            self.module_cache
+                .get_or_preprocess(&preprocessor, protocol_version, module_bytes);
+        let module = match preprocessed {
+            Ok(module) => module,
+            Err(PreprocessingError::Deserialize(error)) => {
+                return Ok(ValidationResult::ParseError(error))
+            }
+            Err(error) => return Ok(ValidationResult::PreprocessingError(error)),
+        };
+
+        let exports_call = module
+            .export_section()
+            .map(|section| section.entries())
+            .unwrap_or_default()
+            .iter()
+            .any(|entry| {
+                entry.field() == DEFAULT_ENTRY_POINT_NAME
+                    && match entry.internal() {
+                        Internal::Function(_) => true,
+                        _ => false,
+                    }
+            });
+        if !exports_call {
+            return Ok(ValidationResult::MissingCallExport);
+        }
+
+        Ok(ValidationResult::Valid)
+    }
+
     pub fn commit_genesis(
         &self,
         correlation_id: CorrelationId,
@@ -204,11 +319,21 @@ where
         // RPC call
 
         let hash_address_generator = {
-            let generator = AddressGenerator::new(&genesis_config_hash.value(), phase);
+            let generator = AddressGenerator::with_purpose(
+                &genesis_config_hash.value(),
+                phase,
+                RngPurpose::Hashes,
+                protocol_version,
+            );
             Rc::new(RefCell::new(generator))
         };
         let uref_address_generator = {
-            let generator = AddressGenerator::new(&genesis_config_hash.value(), phase);
+            let generator = AddressGenerator::with_purpose(
+                &genesis_config_hash.value(),
+                phase,
+                RngPurpose::URefs,
+                protocol_version,
+            );
             Rc::new(RefCell::new(generator))
         };
 
@@ -216,7 +341,9 @@ where
         let (mint_package_hash, mint_hash): (ContractPackageHash, ContractHash) = {
             let mint_installer_bytes = ee_config.mint_installer_bytes();
             let mint_installer_module = preprocessor.preprocess(mint_installer_bytes)?;
-            let args = RuntimeArgs::new();
+            let args = runtime_args! {
+                "round_seigniorage_rate" => ee_config.round_seigniorage_rate(),
+            };
             let authorization_keys: BTreeSet<AccountHash> = BTreeSet::new();
             let install_deploy_hash = genesis_config_hash.into();
             let hash_address_generator = Rc::clone(&hash_address_generator);
@@ -274,6 +401,10 @@ where
             let args = runtime_args! {
                 "mint_contract_package_hash" => mint_package_hash,
                 "genesis_validators" => bonded_validators,
+                "unbonding_delay_millis" => ee_config.unbonding_delay_millis(),
+                "gas_price" => ee_config.gas_price(),
+                "min_bond_amount" => ee_config.min_bond_amount().value(),
+                "max_bond_amount" => ee_config.max_bond_amount().value(),
             };
             let authorization_keys: BTreeSet<AccountHash> = BTreeSet::new();
 
@@ -355,12 +486,15 @@ where
             mint_hash,
             proof_of_stake_hash,
             standard_payment_hash,
-        );
+        )
+        .with_trie_chunk_threshold(ee_config.trie_chunk_threshold());
 
         self.state
             .put_protocol_data(protocol_version, &protocol_data)
             .map_err(Into::into)?;
 
+        write_system_contract_registry(&mut tracking_copy.borrow_mut(), &protocol_data)?;
+
         //
         // NOTE: The following stanzas deviate from the implementation strategy described in the
         // original specification.
@@ -422,6 +556,7 @@ where
                         .seed_with(&genesis_config_hash.value())
                         .seed_with(&account_hash.to_bytes()?)
                         .seed_with(&[phase as u8])
+                        .seed_with_purpose(RngPurpose::URefs, protocol_version)
                         .build();
                     Rc::new(RefCell::new(generator))
                 };
@@ -481,6 +616,7 @@ where
                 correlation_id,
                 initial_root_hash,
                 effects.transforms.to_owned(),
+                protocol_data.trie_chunk_threshold(),
             )
             .map_err(Into::into)?;
 
@@ -541,7 +677,8 @@ where
             current_protocol_data.mint(),
             current_protocol_data.proof_of_stake(),
             current_protocol_data.standard_payment(),
-        );
+        )
+        .with_trie_chunk_threshold(current_protocol_data.trie_chunk_threshold());
 
         self.state
             .put_protocol_data(new_protocol_version, &new_protocol_data)
@@ -606,11 +743,21 @@ where
                 let gas_limit = Gas::new(std::u64::MAX.into());
                 let phase = Phase::System;
                 let hash_address_generator = {
-                    let generator = AddressGenerator::new(&pre_state_hash.value(), phase);
+                    let generator = AddressGenerator::with_purpose(
+                        &pre_state_hash.value(),
+                        phase,
+                        RngPurpose::Hashes,
+                        new_protocol_version,
+                    );
                     Rc::new(RefCell::new(generator))
                 };
                 let uref_address_generator = {
-                    let generator = AddressGenerator::new(&pre_state_hash.value(), phase);
+                    let generator = AddressGenerator::with_purpose(
+                        &pre_state_hash.value(),
+                        phase,
+                        RngPurpose::URefs,
+                        new_protocol_version,
+                    );
                     Rc::new(RefCell::new(generator))
                 };
                 let tracking_copy = Rc::clone(&tracking_copy);
@@ -647,6 +794,9 @@ where
             }
         }
 
+        // keep the registry in step with the protocol data, which the installer may have updated
+        write_system_contract_registry(&mut tracking_copy.borrow_mut(), &new_protocol_data)?;
+
         let effects = tracking_copy.borrow().effect();
 
         // commit
@@ -656,6 +806,7 @@ where
                 correlation_id,
                 pre_state_hash,
                 effects.transforms.to_owned(),
+                new_protocol_data.trie_chunk_threshold(),
             )
             .map_err(Into::into)?;
 
@@ -666,9 +817,27 @@ where
     pub fn tracking_copy(
         &self,
         hash: Blake2bHash,
-    ) -> Result<Option<TrackingCopy<S::Reader>>, Error> {
+    ) -> Result<Option<TrackingCopy<CachedStateReader<S::Reader>>>, Error> {
+        match self.state.checkout(hash).map_err(Into::into)? {
+            Some(reader) => {
+                let cache = StateReadCache::clone(&self.state_read_cache);
+                let cached_reader = CachedStateReader::new(reader, hash, cache);
+                Ok(Some(TrackingCopy::new(cached_reader)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns a read-only view of global state at `hash`, or `None` if the state root is not
+    /// found.
+    pub fn read_only_view(
+        &self,
+        hash: Blake2bHash,
+    ) -> Result<Option<ReadOnlyStateView<S::Reader>>, Error> {
         match self.state.checkout(hash).map_err(Into::into)? {
-            Some(tc) => Ok(Some(TrackingCopy::new(tc))),
+            Some(reader) => Ok(Some(
+                ReadOnlyStateView::new(reader).with_max_query_depth(self.config.max_query_depth()),
+            )),
             None => Ok(None),
         }
     }
@@ -678,72 +847,388 @@ where
         correlation_id: CorrelationId,
         query_request: QueryRequest,
     ) -> Result<QueryResult, Error> {
-        let tracking_copy = match self.tracking_copy(query_request.state_hash())? {
-            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+        let view = match self.read_only_view(query_request.state_hash())? {
+            Some(view) => view,
             None => return Ok(QueryResult::RootNotFound),
         };
 
-        let tracking_copy = tracking_copy.borrow();
+        view.query(correlation_id, query_request.key(), query_request.path())
+            .map_err(|err| Error::Exec(err.into()))
+    }
+
+    /// Runs each of the given queries against a single checkout of the requested state root.
+    ///
+    /// Returns one `QueryResult` per query, in order, or `None` if the state root is not found.
+    pub fn run_batch_query(
+        &self,
+        correlation_id: CorrelationId,
+        batch_query_request: BatchQueryRequest,
+    ) -> Result<Option<Vec<QueryResult>>, Error> {
+        let view = match self.read_only_view(batch_query_request.state_hash())? {
+            Some(view) => view,
+            None => return Ok(None),
+        };
+
+        let mut results = Vec::with_capacity(batch_query_request.queries().len());
+        for (key, path) in batch_query_request.queries() {
+            let result = view
+                .query(correlation_id, *key, path)
+                .map_err(|err| Error::Exec(err.into()))?;
+            results.push(result);
+        }
+        Ok(Some(results))
+    }
+
+    /// Looks up the balance of the purse given in `balance_request`.
+    pub fn get_purse_balance(
+        &self,
+        correlation_id: CorrelationId,
+        balance_request: BalanceRequest,
+    ) -> Result<BalanceResult, Error> {
+        let mut tracking_copy = match self.tracking_copy(balance_request.state_hash())? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(BalanceResult::RootNotFound),
+        };
+
+        let purse_key = Key::URef(balance_request.purse_uref());
+        let balance_key = match tracking_copy.get_purse_balance_key(correlation_id, purse_key) {
+            Ok(balance_key) => balance_key,
+            Err(execution::Error::URefNotFound(_)) => return Ok(BalanceResult::PurseNotFound),
+            Err(error) => return Err(error.into()),
+        };
 
-        Ok(tracking_copy
-            .query(correlation_id, query_request.key(), query_request.path())
-            .map_err(|err| Error::Exec(err.into()))?
-            .into())
+        match tracking_copy.get_purse_balance(correlation_id, balance_key) {
+            Ok(balance) => Ok(BalanceResult::Success(balance.value())),
+            Err(execution::Error::KeyNotFound(_)) => Ok(BalanceResult::PurseNotFound),
+            Err(error) => Err(error.into()),
+        }
     }
 
+    /// Same as `run_query()`, but also returns a Merkle proof for each value read from global
+    /// state while following the query's path.
+    pub fn run_query_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        query_request: QueryRequest,
+    ) -> Result<(QueryResult, Vec<TrieMerkleProof<Key, StoredValue>>), Error> {
+        let view = match self.read_only_view(query_request.state_hash())? {
+            Some(view) => view,
+            None => return Ok((QueryResult::RootNotFound, Vec::new())),
+        };
+
+        view.query_with_proof(correlation_id, query_request.key(), query_request.path())
+            .map_err(|err| Error::Exec(err.into()))
+    }
+
+    /// Returns all keys at the given state root whose serialized form starts with `prefix`, or
+    /// `None` if the state root is not found.
+    pub fn enumerate_keys(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        prefix: &[u8],
+    ) -> Result<Option<Vec<Key>>, Error> {
+        let reader = match self.state.checkout(state_hash).map_err(Into::into)? {
+            Some(reader) => reader,
+            None => return Ok(None),
+        };
+        let keys = reader
+            .keys_with_prefix(correlation_id, prefix)
+            .map_err(Into::into)?;
+        Ok(Some(keys))
+    }
+
+    /// Deletes every trie which isn't reachable from one of `retain_roots`, returning how many
+    /// tries were kept and how many were deleted.
+    pub fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retain_roots: &[Blake2bHash],
+    ) -> Result<PruneReport, Error> {
+        let _in_flight_request = self.start_request()?;
+
+        self.state
+            .prune(correlation_id, retain_roots)
+            .map_err(|err| Error::Exec(err.into()))
+    }
+
+    /// Writes a snapshot of the state under `root` to `writer`, returning the number of tries
+    /// written, or `None` if `root` is not found.
+    pub fn export_trie(
+        &self,
+        correlation_id: CorrelationId,
+        root: Blake2bHash,
+        writer: &mut dyn Write,
+    ) -> Result<Option<usize>, Error> {
+        let _in_flight_request = self.start_request()?;
+
+        self.state
+            .export_trie(correlation_id, root, writer)
+            .map_err(|err| Error::Exec(err.into()))
+    }
+
+    /// Loads a snapshot written by [`EngineState::export_trie`], returning its root hash.
+    pub fn import_trie(
+        &self,
+        correlation_id: CorrelationId,
+        reader: &mut dyn Read,
+    ) -> Result<Blake2bHash, Error> {
+        let _in_flight_request = self.start_request()?;
+
+        self.state
+            .import_trie(correlation_id, reader)
+            .map_err(|err| Error::Exec(err.into()))
+    }
+
+    /// Returns the roots `root` was derived from by commits, starting with its parent.
+    pub fn root_ancestry(&self, root: Blake2bHash) -> Result<Vec<Blake2bHash>, Error> {
+        let _in_flight_request = self.start_request()?;
+
+        self.state
+            .ancestry(root)
+            .map_err(|err| Error::Exec(err.into()))
+    }
+
+    /// Executes the deploys in `exec_request`, returning their results in request order along
+    /// with the result of distributing the rewards, if the request named a proposer.
     pub fn run_execute(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+    ) -> Result<(Vec<ExecutionResult>, Option<ExecutionResult>), ExecuteError>
+    where
+        S: Sync,
+    {
+        let mut results = Vec::new();
+        let rewards_result =
+            self.run_execute_with(correlation_id, exec_request, |result| results.push(result))?;
+        Ok((results, rewards_result))
+    }
+
+    /// Executes the deploys in `exec_request`, passing each result to `on_result` in request order.
+    ///
+    /// Every deploy runs against the request's parent state, so deploys are independent of one
+    /// another.  By default they run one at a time and each result is passed on as soon as it is
+    /// available.  If [`EngineConfig::parallel_execution`] is set, they run concurrently on the
+    /// global rayon pool and results are passed on once all deploys have finished; the results are
+    /// identical in both modes.
+    ///
+    /// If the request names a proposer, the accumulated rewards are then paid out to it and the
+    /// result of doing so is returned.  It is not passed to `on_result`, so exactly one result is
+    /// passed on per deploy.
+    ///
+    /// If the parent state root is missing, execution stops and the error is returned; results
+    /// already passed to `on_result` are unaffected.  If the engine is closing, nothing is executed
+    /// and [`ExecuteError::Closing`] is returned.
+    pub fn run_execute_with<F>(
         &self,
         correlation_id: CorrelationId,
         mut exec_request: ExecuteRequest,
-    ) -> Result<Vec<ExecutionResult>, RootNotFound> {
+        mut on_result: F,
+    ) -> Result<Option<ExecutionResult>, ExecuteError>
+    where
+        S: Sync,
+        F: FnMut(ExecutionResult),
+    {
+        let _in_flight_request = self
+            .in_flight_requests
+            .start()
+            .ok_or(ExecuteError::Closing)?;
+
         // TODO: do not unwrap
         let wasm_costs = self
             .wasm_costs(exec_request.protocol_version)
             .unwrap()
             .unwrap();
-        let executor = Executor::new(self.config);
+        let config = if exec_request.profile_host_functions {
+            self.config.with_host_function_profiling(true)
+        } else {
+            self.config
+        };
+        let executor = Executor::new(config);
         let preprocessor = Preprocessor::new(wasm_costs);
+        let deploys = exec_request.take_deploys();
+        let proposer = exec_request.proposer.take();
 
-        let mut results = Vec::new();
+        let execute = |deploy_item| {
+            self.execute_deploy_item(
+                correlation_id,
+                &executor,
+                &preprocessor,
+                &exec_request,
+                deploy_item,
+            )
+        };
 
-        for deploy_item in exec_request.take_deploys() {
-            let result = match deploy_item {
-                Err(exec_result) => Ok(exec_result),
-                Ok(deploy_item) => match deploy_item.session {
-                    ExecutableDeployItem::Transfer { .. } => self.transfer(
-                        correlation_id,
-                        &executor,
-                        &preprocessor,
-                        exec_request.protocol_version,
-                        exec_request.parent_state_hash,
-                        BlockTime::new(exec_request.block_time),
-                        deploy_item,
-                    ),
-                    _ => self.deploy(
-                        correlation_id,
-                        &executor,
-                        &preprocessor,
-                        exec_request.protocol_version,
-                        exec_request.parent_state_hash,
-                        BlockTime::new(exec_request.block_time),
-                        deploy_item,
-                    ),
-                },
-            };
-            match result {
-                Ok(result) => results.push(result),
-                Err(error) => {
-                    return Err(error);
+        // The rewards paid out to the proposer include the fees paid by this request's deploys.
+        let mut block_transforms: AdditiveMap<Key, Transform> = AdditiveMap::new();
+        let mut on_deploy_result = |result: ExecutionResult| {
+            if proposer.is_some() {
+                for (key, transform) in result.effect().transforms.iter() {
+                    block_transforms.insert_add(*key, transform.to_owned());
                 }
-            };
+            }
+            on_result(result);
+        };
+
+        if self.config.parallel_execution() {
+            let results: Vec<Result<ExecutionResult, RootNotFound>> =
+                deploys.into_par_iter().map(execute).collect();
+            for result in results {
+                on_deploy_result(result?);
+            }
+        } else {
+            for deploy_item in deploys {
+                on_deploy_result(execute(deploy_item)?);
+            }
         }
 
-        Ok(results)
+        let rewards_result = match proposer {
+            None => None,
+            Some(Err(exec_result)) => Some(exec_result),
+            Some(Ok(proposer)) => Some(self.distribute_rewards(
+                correlation_id,
+                &executor,
+                &preprocessor,
+                &exec_request,
+                proposer,
+                block_transforms,
+            )?),
+        };
+
+        Ok(rewards_result)
+    }
+
+    /// Runs the finalization step of a block: the proof of stake contract pays the balance of its
+    /// rewards purse out to the main purse of `proposer`.
+    ///
+    /// The step runs on top of `block_transforms`, the combined effects of the block's deploys, so
+    /// the fees they paid are included.  The returned result only holds the step's own effects.
+    fn distribute_rewards(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        exec_request: &ExecuteRequest,
+        proposer: AccountHash,
+        block_transforms: AdditiveMap<Key, Transform>,
+    ) -> Result<ExecutionResult, RootNotFound> {
+        let protocol_version = exec_request.protocol_version;
+        let protocol_data = match self.state.get_protocol_data(protocol_version) {
+            Ok(Some(protocol_data)) => protocol_data,
+            Ok(None) => {
+                let error = Error::InvalidProtocolVersion(protocol_version);
+                return Ok(ExecutionResult::precondition_failure(error));
+            }
+            Err(error) => {
+                return Ok(ExecutionResult::precondition_failure(Error::Exec(
+                    error.into(),
+                )));
+            }
+        };
+
+        let prestate_hash = exec_request.parent_state_hash;
+        let mut tracking_copy = match self.tracking_copy(prestate_hash) {
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+            Ok(None) => return Err(RootNotFound::new(prestate_hash)),
+            Ok(Some(tracking_copy)) => tracking_copy,
+        };
+        if let Err(error) = tracking_copy.apply_transforms(correlation_id, &block_transforms) {
+            return Ok(ExecutionResult::precondition_failure(error.into()));
+        }
+        let finalization_tc = Rc::new(RefCell::new(tracking_copy.fork()));
+
+        let proof_of_stake_hash = protocol_data.proof_of_stake();
+        let proof_of_stake_contract = match finalization_tc
+            .borrow_mut()
+            .get_contract(correlation_id, proof_of_stake_hash)
+        {
+            Ok(contract) => contract,
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+        };
+        let proof_of_stake_module = match finalization_tc.borrow_mut().get_system_module(
+            correlation_id,
+            proof_of_stake_contract.contract_wasm_hash(),
+            self.config.use_system_contracts(),
+            preprocessor,
+        ) {
+            Ok(module) => module,
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+        };
+        let mut proof_of_stake_keys = proof_of_stake_contract.named_keys().to_owned();
+
+        let system_account = Account::new(
+            SYSTEM_ACCOUNT_ADDR,
+            Default::default(),
+            URef::new(Default::default(), AccessRights::READ_ADD_WRITE),
+            Default::default(),
+            Default::default(),
+        );
+        let authorization_keys: BTreeSet<AccountHash> =
+            std::iter::once(SYSTEM_ACCOUNT_ADDR).collect();
+        const ARG_VALIDATOR: &str = "validator";
+        let proof_of_stake_args = runtime_args! { ARG_VALIDATOR => proposer };
+        let gas_limit = Gas::new(U512::from(std::u64::MAX));
+        let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+
+        let (_, result): (Option<()>, ExecutionResult) = executor.exec_system_contract(
+            DirectSystemContractCall::DistributeRewards,
+            proof_of_stake_module,
+            proof_of_stake_args,
+            &mut proof_of_stake_keys,
+            Default::default(),
+            Key::from(proof_of_stake_hash),
+            &system_account,
+            authorization_keys,
+            BlockTime::new(exec_request.block_time),
+            prestate_hash.value(),
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            finalization_tc,
+            Phase::System,
+            protocol_data,
+            system_contract_cache,
+        );
+        Ok(result)
+    }
+
+    fn execute_deploy_item(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        exec_request: &ExecuteRequest,
+        deploy_item: Result<DeployItem, ExecutionResult>,
+    ) -> Result<ExecutionResult, RootNotFound> {
+        match deploy_item {
+            Err(exec_result) => Ok(exec_result),
+            Ok(deploy_item) => match deploy_item.session {
+                ExecutableDeployItem::Transfer { .. } => self.transfer(
+                    correlation_id,
+                    executor,
+                    preprocessor,
+                    exec_request.protocol_version,
+                    exec_request.parent_state_hash,
+                    BlockTime::new(exec_request.block_time),
+                    deploy_item,
+                ),
+                _ => self.deploy(
+                    correlation_id,
+                    executor,
+                    preprocessor,
+                    exec_request.protocol_version,
+                    exec_request.parent_state_hash,
+                    BlockTime::new(exec_request.block_time),
+                    deploy_item,
+                ),
+            },
+        }
     }
 
     pub fn get_module(
         &self,
-        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        tracking_copy: Rc<RefCell<TrackingCopy<CachedStateReader<<S as StateProvider>::Reader>>>>,
         deploy_item: &ExecutableDeployItem,
         account: &Account,
         correlation_id: CorrelationId,
@@ -752,7 +1237,11 @@ where
     ) -> Result<GetModuleResult, error::Error> {
         let (contract_package, contract, base_key) = match deploy_item {
             ExecutableDeployItem::ModuleBytes { module_bytes, .. } => {
-                let module = preprocessor.preprocess(&module_bytes)?;
+                let module = self.module_cache.get_or_preprocess(
+                    preprocessor,
+                    *protocol_version,
+                    &module_bytes,
+                )?;
                 return Ok(GetModuleResult::Session {
                     module,
                     contract_package: ContractPackage::default(),
@@ -861,7 +1350,7 @@ where
 
     fn get_module_from_contract_hash(
         &self,
-        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        tracking_copy: Rc<RefCell<TrackingCopy<CachedStateReader<<S as StateProvider>::Reader>>>>,
         contract_hash: ContractHash,
         correlation_id: CorrelationId,
         protocol_version: &ProtocolVersion,
@@ -894,13 +1383,16 @@ where
         correlation_id: CorrelationId,
         account_hash: AccountHash,
         authorization_keys: &BTreeSet<AccountHash>,
-        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        tracking_copy: Rc<RefCell<TrackingCopy<CachedStateReader<<S as StateProvider>::Reader>>>>,
     ) -> Result<Account, Error> {
         let account: Account = match tracking_copy
             .borrow_mut()
             .get_account(correlation_id, account_hash)
         {
             Ok(account) => account,
+            Err(execution::Error::KeyNotFound(_)) => {
+                return Err(error::Error::AccountNotFound(account_hash));
+            }
             Err(_) => {
                 return Err(error::Error::Authorization);
             }
@@ -1054,6 +1546,10 @@ where
                     error,
                     effect: Default::default(),
                     cost: Gas::default(),
+                    cost_breakdown: CostBreakdown::default(),
+                    host_function_profile: Default::default(),
+                    charged: Motes::zero(),
+                    phase_costs: Default::default(),
                 });
             }
         }
@@ -1066,6 +1562,10 @@ where
                         error,
                         effect: Default::default(),
                         cost: Gas::default(),
+                        cost_breakdown: CostBreakdown::default(),
+                        host_function_profile: Default::default(),
+                        charged: Motes::zero(),
+                        phase_costs: Default::default(),
                     });
                 }
             };
@@ -1245,6 +1745,19 @@ where
                 .insert(proof_of_stake_hash, proof_of_stake_module.clone());
         }
 
+        // Get the number of motes charged per unit of gas from proof of stake contract; states
+        // created before it was stored there are charged the default rate
+        let gas_price: u64 = match proof_of_stake_contract.named_keys().get(POS_GAS_PRICE) {
+            Some(gas_price_key) => match tracking_copy
+                .borrow_mut()
+                .get_gas_price(correlation_id, *gas_price_key)
+            {
+                Ok(gas_price) => gas_price,
+                Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+            },
+            None => CONV_RATE,
+        };
+
         // Get account main purse balance key
         // validation_spec_5: account main purse minimum balance
         let account_main_purse_balance_key: Key = {
@@ -1260,17 +1773,22 @@ where
             }
         };
 
+        let max_payment_cost: Motes = Motes::new(U512::from(MAX_PAYMENT));
+
         // Get account main purse balance to enforce precondition and in case of forced
         // transfer validation_spec_5: account main purse minimum balance
-        let account_main_purse_balance: Motes = match tracking_copy
-            .borrow_mut()
-            .get_purse_balance(correlation_id, account_main_purse_balance_key)
-        {
-            Ok(balance) => balance,
-            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
-        };
-
-        let max_payment_cost: Motes = Motes::new(U512::from(MAX_PAYMENT));
+        //
+        // Only the check is recorded, not the balance, so that deploys paying from the same purse
+        // can commute.
+        let account_main_purse_balance: Motes =
+            match tracking_copy.borrow_mut().get_purse_balance_at_least(
+                correlation_id,
+                account_main_purse_balance_key,
+                max_payment_cost,
+            ) {
+                Ok(balance) => balance,
+                Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+            };
 
         // Enforce minimum main purse balance validation
         // validation_spec_5: account main purse minimum balance
@@ -1292,12 +1810,13 @@ where
 
         // [`ExecutionResultBuilder`] handles merging of multiple execution results
         let mut execution_result_builder = execution_result::ExecutionResultBuilder::new();
+        execution_result_builder.set_gas_price(gas_price);
 
         // Execute provided payment code
         let payment_result = {
             // payment_code_spec_1: init pay environment w/ gas limit == (max_payment_cost /
-            // conv_rate)
-            let pay_gas_limit = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
+            // gas_price)
+            let pay_gas_limit = Gas::from_motes(max_payment_cost, gas_price).unwrap_or_default();
 
             let module_bytes_is_empty = match payment {
                 ExecutableDeployItem::ModuleBytes {
@@ -1419,11 +1938,21 @@ where
             } else {
                 // use host side standard payment
                 let hash_address_generator = {
-                    let generator = AddressGenerator::new(&deploy_hash, phase);
+                    let generator = AddressGenerator::with_purpose(
+                        &deploy_hash,
+                        phase,
+                        RngPurpose::Hashes,
+                        protocol_version,
+                    );
                     Rc::new(RefCell::new(generator))
                 };
                 let uref_address_generator = {
-                    let generator = AddressGenerator::new(&deploy_hash, phase);
+                    let generator = AddressGenerator::with_purpose(
+                        &deploy_hash,
+                        phase,
+                        RngPurpose::URefs,
+                        protocol_version,
+                    );
                     Rc::new(RefCell::new(generator))
                 };
 
@@ -1460,11 +1989,19 @@ where
                     Ok(()) => ExecutionResult::Success {
                         effect: runtime.context().effect(),
                         cost: runtime.context().gas_counter(),
+                        cost_breakdown: runtime.cost_breakdown().clone(),
+                        host_function_profile: runtime.host_function_profile(),
+                        charged: Motes::zero(),
+                        phase_costs: Default::default(),
                     },
                     Err(error) => ExecutionResult::Failure {
                         error: error.into(),
                         effect: effects_snapshot,
                         cost: runtime.context().gas_counter(),
+                        cost_breakdown: runtime.cost_breakdown().clone(),
+                        host_function_profile: runtime.host_function_profile(),
+                        charged: Motes::zero(),
+                        phase_costs: Default::default(),
                     },
                 }
             }
@@ -1505,7 +2042,9 @@ where
             }
         };
 
-        if let Some(forced_transfer) = payment_result.check_forced_transfer(payment_purse_balance) {
+        if let Some(forced_transfer) =
+            payment_result.check_forced_transfer(payment_purse_balance, gas_price)
+        {
             // Get rewards purse balance key
             // payment_code_spec_6: system contract validity
             let rewards_purse_balance_key: Key = {
@@ -1530,6 +2069,10 @@ where
                 }
             };
 
+            let phase_costs = PhaseCosts {
+                payment: payment_result.cost(),
+                ..PhaseCosts::default()
+            };
             let error = match forced_transfer {
                 ForcedTransferResult::InsufficientPayment => Error::InsufficientPayment,
                 ForcedTransferResult::PaymentFailure => payment_result.take_error().unwrap(),
@@ -1540,7 +2083,9 @@ where
                 account_main_purse_balance,
                 account_main_purse_balance_key,
                 rewards_purse_balance_key,
-            ));
+                gas_price,
+            )
+            .with_phase_costs(phase_costs));
         }
 
         execution_result_builder.set_payment_execution_result(payment_result);
@@ -1592,10 +2137,10 @@ where
         };
         let session_result = {
             // payment_code_spec_3_b_i: if (balance of PoS pay purse) >= (gas spent during
-            // payment code execution) * conv_rate, yes session
-            // session_code_spec_1: gas limit = ((balance of PoS payment purse) / conv_rate)
+            // payment code execution) * gas_price, yes session
+            // session_code_spec_1: gas limit = ((balance of PoS payment purse) / gas_price)
             // - (gas spent during payment execution)
-            let session_gas_limit: Gas = Gas::from_motes(payment_purse_balance, CONV_RATE)
+            let session_gas_limit: Gas = Gas::from_motes(payment_purse_balance, gas_price)
                 .unwrap_or_default()
                 - payment_result_cost;
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
@@ -1640,8 +2185,8 @@ where
             let finalization_tc = Rc::new(RefCell::new(post_session_tc.fork()));
 
             let proof_of_stake_args = {
-                //((gas spent during payment code execution) + (gas spent during session code execution)) * conv_rate
-                let finalize_cost_motes: Motes = Motes::from_gas(execution_result_builder.total_cost(), CONV_RATE).expect("motes overflow");
+                //((gas spent during payment code execution) + (gas spent during session code execution)) * gas_price
+                let finalize_cost_motes: Motes = execution_result_builder.total_charged().expect("motes overflow");
                 const ARG_AMOUNT: &str = "amount";
                 const ARG_ACCOUNT_KEY: &str = "account";
                 runtime_args! {
@@ -1709,32 +2254,58 @@ where
     where
         Error: From<S::Error>,
     {
-        match self.state.commit(correlation_id, pre_state_hash, effects)? {
-            CommitResult::Success { state_root, .. } => {
-                let bonded_validators =
-                    self.get_bonded_validators(correlation_id, protocol_version, state_root)?;
+        let _in_flight_request = self.start_request()?;
+
+        let trie_chunk_threshold = match self.state.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data.trie_chunk_threshold(),
+            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
+        };
+
+        match self.state.commit(
+            correlation_id,
+            pre_state_hash,
+            effects,
+            trie_chunk_threshold,
+        )? {
+            CommitResult::Success {
+                state_root,
+                stats,
+                modified_keys,
+                ..
+            } => {
+                // Executions move on to the new root, so drop values cached against older ones.
+                self.state_read_cache.clear();
+                let request = BondedValidatorsRequest::new(state_root, protocol_version);
+                let bonded_validators = match self.get_bonded_validators(correlation_id, request)? {
+                    BondedValidatorsResult::Success(bonded_validators) => {
+                        bonded_validators.into_iter().collect()
+                    }
+                    BondedValidatorsResult::RootNotFound => {
+                        panic!("state root of a successful commit should exist")
+                    }
+                };
                 Ok(CommitResult::Success {
                     state_root,
                     bonded_validators,
+                    stats,
+                    modified_keys,
                 })
             }
             commit_result => Ok(commit_result),
         }
     }
 
-    /// Calculates bonded validators at `root_hash` state.
-    ///
-    /// Should only be called with a valid root hash after a successful call to
-    /// [`StateProvider::commit`]. Will panic if called with an invalid root hash.
-    fn get_bonded_validators(
+    /// Returns the validators bonded in the PoS contract at the given state root, along with their
+    /// stakes.
+    pub fn get_bonded_validators(
         &self,
         correlation_id: CorrelationId,
-        protocol_version: ProtocolVersion,
-        root_hash: Blake2bHash,
-    ) -> Result<HashMap<AccountHash, U512>, Error>
+        bonded_validators_request: BondedValidatorsRequest,
+    ) -> Result<BondedValidatorsResult, Error>
     where
         Error: From<S::Error>,
     {
+        let protocol_version = bonded_validators_request.protocol_version();
         let protocol_data = match self.state.get_protocol_data(protocol_version)? {
             Some(protocol_data) => protocol_data,
             None => return Err(Error::InvalidProtocolVersion(protocol_version)),
@@ -1742,9 +2313,12 @@ where
 
         let proof_of_stake_key = protocol_data.proof_of_stake().into();
 
-        let reader = match self.state.checkout(root_hash)? {
+        let reader = match self
+            .state
+            .checkout(bonded_validators_request.state_hash())?
+        {
             Some(reader) => reader,
-            None => panic!("get_bonded_validators called with an invalid root hash"),
+            None => return Ok(BondedValidatorsResult::RootNotFound),
         };
 
         let contract = match reader.read(correlation_id, &proof_of_stake_key)? {
@@ -1752,12 +2326,23 @@ where
             _ => return Err(MissingSystemContract(PROOF_OF_STAKE.to_string())),
         };
 
-        let bonded_validators = contract
-            .named_keys()
-            .keys()
-            .filter_map(|entry| utils::pos_validator_key_name_to_tuple(entry))
-            .collect::<HashMap<AccountHash, U512>>();
-
-        Ok(bonded_validators)
+        Ok(BondedValidatorsResult::Success(
+            utils::pos_bonded_validators(&contract),
+        ))
     }
 }
+
+/// Writes the system contract registry holding the system contracts of `protocol_data` to global
+/// state.
+fn write_system_contract_registry<R: StateReader<Key, StoredValue>>(
+    tracking_copy: &mut TrackingCopy<R>,
+    protocol_data: &ProtocolData,
+) -> Result<(), Error> {
+    let registry = protocol_data.system_contract_registry();
+    let cl_value = CLValue::from_t(registry).map_err(execution::Error::CLValue)?;
+    tracking_copy.write(
+        Key::Hash(SYSTEM_CONTRACT_REGISTRY_ADDR),
+        StoredValue::CLValue(cl_value),
+    );
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use std::sync::{Arc, Mutex};
+
+use linked_hash_map::LinkedHashMap;
+use parity_wasm::elements::Module;
+
+use engine_shared::newtypes::Blake2bHash;
+use engine_wasm_prep::{wasm_costs::WasmCosts, PreprocessingError, Preprocessor};
+use types::ProtocolVersion;
+
+/// The default number of modules held by a [`ModuleCache`].
+pub const DEFAULT_MODULE_CACHE_CAPACITY: usize = 64;
+
+#[derive(Debug, Default)]
+struct CacheEntries {
+    modules: LinkedHashMap<(Blake2bHash, ProtocolVersion), (WasmCosts, Module)>,
+    preprocess_count: usize,
+}
+
+/// A least-recently-used cache of preprocessed Wasm modules, shared by every execution of an
+/// `EngineState`.
+///
+/// Modules are cached under the hash of their raw bytes and the protocol version they were
+/// preprocessed for, along with the `WasmCosts` used.  A cached module is only returned when the
+/// costs match, so a module preprocessed under different costs is never reused.  At most
+/// `capacity` modules are held, and a capacity of zero disables caching.
+#[derive(Clone, Debug)]
+pub struct ModuleCache {
+    capacity: usize,
+    entries: Arc<Mutex<CacheEntries>>,
+}
+
+impl ModuleCache {
+    pub fn new(capacity: usize) -> Self {
+        ModuleCache {
+            capacity,
+            entries: Default::default(),
+        }
+    }
+
+    /// Returns `module_bytes` preprocessed by `preprocessor`, reusing a cached module if one was
+    /// preprocessed from the same bytes for `protocol_version` with the same `WasmCosts`.
+    pub fn get_or_preprocess(
+        &self,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        module_bytes: &[u8],
+    ) -> Result<Module, PreprocessingError> {
+        let key = (Blake2bHash::new(module_bytes), protocol_version);
+        let wasm_costs = *preprocessor.wasm_costs();
+
+        if let Some((cached_costs, module)) = self.entries.lock().unwrap().modules.get_refresh(&key)
+        {
+            if *cached_costs == wasm_costs {
+                return Ok(module.clone());
+            }
+        }
+
+        // Preprocess without holding the lock, so other executions aren't held up.
+        let module = preprocessor.preprocess(module_bytes)?;
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.preprocess_count += 1;
+        if self.capacity > 0 {
+            entries.modules.insert(key, (wasm_costs, module.clone()));
+            while entries.modules.len() > self.capacity {
+                entries.modules.pop_front();
+            }
+        }
+        Ok(module)
+    }
+
+    /// Returns the number of modules preprocessed, rather than taken from this cache, since the
+    /// cache was last cleared.
+    pub fn preprocess_count(&self) -> usize {
+        self.entries.lock().unwrap().preprocess_count
+    }
+
+    /// Discards every cached module and resets the preprocess count.
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.modules.clear();
+        entries.preprocess_count = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_wasm_prep::{wasm_costs::WasmCosts, Preprocessor};
+    use types::ProtocolVersion;
+
+    use super::ModuleCache;
+
+    // (module (memory 1) (func (export "call")))
+    const MODULE_1: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00, 0x03,
+        0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x08, 0x01, 0x04, 0x63, 0x61, 0x6c,
+        0x6c, 0x00, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+    ];
+    // (module (memory 1) (func (export "main")))
+    const MODULE_2: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00, 0x03,
+        0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x08, 0x01, 0x04, 0x6d, 0x61, 0x69,
+        0x6e, 0x00, 0x00, 0x0a, 0x04, 0x01, 0x02, 0x00, 0x0b,
+    ];
+
+    fn wasm_costs() -> WasmCosts {
+        WasmCosts::from_version(ProtocolVersion::V1_0_0).unwrap()
+    }
+
+    fn preprocessor() -> Preprocessor {
+        Preprocessor::new(wasm_costs())
+    }
+
+    #[test]
+    fn should_preprocess_identical_bytes_once() {
+        let cache = ModuleCache::new(4);
+        let preprocessor = preprocessor();
+
+        let first = cache
+            .get_or_preprocess(&preprocessor, ProtocolVersion::V1_0_0, MODULE_1)
+            .unwrap();
+        for _ in 0..10 {
+            let module = cache
+                .get_or_preprocess(&preprocessor, ProtocolVersion::V1_0_0, MODULE_1)
+                .unwrap();
+            assert_eq!(module, first);
+        }
+        assert_eq!(cache.preprocess_count(), 1);
+
+        cache.clear();
+        assert_eq!(cache.preprocess_count(), 0);
+    }
+
+    #[test]
+    fn should_scope_modules_by_protocol_version_and_costs() {
+        let cache = ModuleCache::new(4);
+        let preprocessor = preprocessor();
+        let other_version = ProtocolVersion::from_parts(1, 1, 0);
+        let other_costs = WasmCosts {
+            max_stack_height: wasm_costs().max_stack_height + 1,
+            ..wasm_costs()
+        };
+
+        cache
+            .get_or_preprocess(&preprocessor, ProtocolVersion::V1_0_0, MODULE_1)
+            .unwrap();
+        cache
+            .get_or_preprocess(&preprocessor, other_version, MODULE_1)
+            .unwrap();
+        cache
+            .get_or_preprocess(
+                &Preprocessor::new(other_costs),
+                ProtocolVersion::V1_0_0,
+                MODULE_1,
+            )
+            .unwrap();
+        assert_eq!(cache.preprocess_count(), 3);
+    }
+
+    #[test]
+    fn should_evict_least_recently_used() {
+        let cache = ModuleCache::new(1);
+        let preprocessor = preprocessor();
+
+        for module_bytes in &[MODULE_1, MODULE_2, MODULE_1] {
+            cache
+                .get_or_preprocess(&preprocessor, ProtocolVersion::V1_0_0, module_bytes)
+                .unwrap();
+        }
+        assert_eq!(cache.preprocess_count(), 3);
+    }
+
+    #[test]
+    fn should_not_cache_with_zero_capacity() {
+        let cache = ModuleCache::new(0);
+        let preprocessor = preprocessor();
+
+        for _ in 0..2 {
+            cache
+                .get_or_preprocess(&preprocessor, ProtocolVersion::V1_0_0, MODULE_1)
+                .unwrap();
+        }
+        assert_eq!(cache.preprocess_count(), 2);
+    }
+}
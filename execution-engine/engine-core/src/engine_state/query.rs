@@ -7,6 +7,7 @@ pub enum QueryResult {
     RootNotFound,
     ValueNotFound(String),
     CircularReference(String),
+    DepthLimitExceeded(String),
     Success(StoredValue),
 }
 
@@ -39,6 +40,30 @@ impl QueryRequest {
     }
 }
 
+/// A request to resolve several keys against a single state root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchQueryRequest {
+    state_hash: Blake2bHash,
+    queries: Vec<(Key, Vec<String>)>,
+}
+
+impl BatchQueryRequest {
+    pub fn new(state_hash: Blake2bHash, queries: Vec<(Key, Vec<String>)>) -> Self {
+        BatchQueryRequest {
+            state_hash,
+            queries,
+        }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn queries(&self) -> &[(Key, Vec<String>)] {
+        &self.queries
+    }
+}
+
 impl From<TrackingCopyQueryResult> for QueryResult {
     fn from(tracking_copy_query_result: TrackingCopyQueryResult) -> Self {
         match tracking_copy_query_result {
@@ -46,6 +71,9 @@ impl From<TrackingCopyQueryResult> for QueryResult {
             TrackingCopyQueryResult::CircularReference(message) => {
                 QueryResult::CircularReference(message)
             }
+            TrackingCopyQueryResult::DepthLimitExceeded(message) => {
+                QueryResult::DepthLimitExceeded(message)
+            }
             TrackingCopyQueryResult::Success(value) => QueryResult::Success(value),
         }
     }
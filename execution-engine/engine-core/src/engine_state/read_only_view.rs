@@ -0,0 +1,157 @@
+use engine_shared::{newtypes::CorrelationId, stored_value::StoredValue};
+use engine_storage::{global_state::StateReader, trie::TrieMerkleProof};
+use types::Key;
+
+use crate::{
+    engine_state::{engine_config::DEFAULT_MAX_QUERY_DEPTH, query::QueryResult},
+    tracking_copy,
+};
+
+/// A read-only handle onto global state at a single state root.
+///
+/// Unlike a `TrackingCopy`, this holds no caches, ops or transforms, so it is cheap to construct
+/// for pure queries and is `Send` and `Sync` whenever the underlying reader is.
+pub struct ReadOnlyStateView<R> {
+    reader: R,
+    max_query_depth: u32,
+}
+
+impl<R: StateReader<Key, StoredValue>> ReadOnlyStateView<R> {
+    pub fn new(reader: R) -> Self {
+        ReadOnlyStateView {
+            reader,
+            max_query_depth: DEFAULT_MAX_QUERY_DEPTH,
+        }
+    }
+
+    /// Sets the maximum number of `Key` values a query will dereference in following its path.
+    pub fn with_max_query_depth(mut self, max_query_depth: u32) -> Self {
+        self.max_query_depth = max_query_depth;
+        self
+    }
+
+    /// Reads the value stored under `key`, without normalizing it.
+    pub fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, R::Error> {
+        self.reader.read(correlation_id, key)
+    }
+
+    /// Follows `path` from `base_key`, with the same semantics as `TrackingCopy::query()`.
+    pub fn query(
+        &self,
+        correlation_id: CorrelationId,
+        base_key: Key,
+        path: &[String],
+    ) -> Result<QueryResult, R::Error> {
+        tracking_copy::query_reader(
+            &self.reader,
+            correlation_id,
+            base_key,
+            path,
+            self.max_query_depth,
+            None,
+        )
+        .map(Into::into)
+    }
+
+    /// Same as `query()`, but also returns a Merkle proof for each value read while following
+    /// `path`.
+    pub fn query_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        base_key: Key,
+        path: &[String],
+    ) -> Result<(QueryResult, Vec<TrieMerkleProof<Key, StoredValue>>), R::Error> {
+        let mut proofs = Vec::new();
+        let result = tracking_copy::query_reader(
+            &self.reader,
+            correlation_id,
+            base_key,
+            path,
+            self.max_query_depth,
+            Some(&mut proofs),
+        )?;
+        Ok((result.into(), proofs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::{newtypes::CorrelationId, stored_value::StoredValue};
+    use engine_storage::global_state::{
+        in_memory::{InMemoryGlobalState, InMemoryGlobalStateView},
+        lmdb::LmdbGlobalStateView,
+        StateProvider,
+    };
+    use types::{
+        contracts::NamedKeys, AccessRights, CLValue, Contract, EntryPoints, Key, ProtocolVersion,
+        URef,
+    };
+
+    use super::ReadOnlyStateView;
+    use crate::{engine_state::query::QueryResult, tracking_copy::TrackingCopy};
+
+    fn assert_send_and_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn should_be_send_and_sync() {
+        assert_send_and_sync::<ReadOnlyStateView<InMemoryGlobalStateView>>();
+        assert_send_and_sync::<ReadOnlyStateView<LmdbGlobalStateView>>();
+    }
+
+    #[test]
+    fn should_match_tracking_copy_query_results() {
+        let value_key = Key::URef(URef::new([255; 32], AccessRights::READ));
+        let value = StoredValue::CLValue(CLValue::from_t(1_u64).unwrap());
+        let value_name = "value".to_string();
+
+        let contract_key = Key::Hash([1; 32]);
+        let mut named_keys = NamedKeys::new();
+        named_keys.insert(value_name.clone(), value_key);
+        let contract = StoredValue::Contract(Contract::new(
+            [2; 32],
+            [3; 32],
+            named_keys,
+            EntryPoints::default(),
+            ProtocolVersion::V1_0_0,
+        ));
+
+        let correlation_id = CorrelationId::new();
+        let (global_state, root_hash) = InMemoryGlobalState::from_pairs(
+            correlation_id,
+            &[(value_key, value.clone()), (contract_key, contract)],
+        )
+        .unwrap();
+        let view = ReadOnlyStateView::new(global_state.checkout(root_hash).unwrap().unwrap());
+        let tracking_copy = TrackingCopy::new(global_state.checkout(root_hash).unwrap().unwrap());
+
+        assert_eq!(
+            view.read(correlation_id, &value_key.normalize()).unwrap(),
+            Some(value.clone())
+        );
+
+        let found_path = vec![value_name];
+        let missing_path = vec!["missing".to_string()];
+        for _ in 0..1_000 {
+            match view.query(correlation_id, contract_key, &found_path) {
+                Ok(QueryResult::Success(found)) => assert_eq!(found, value),
+                _ => panic!("should find value"),
+            }
+            match (
+                view.query(correlation_id, contract_key, &missing_path),
+                tracking_copy.query(correlation_id, contract_key, &missing_path),
+            ) {
+                (Ok(QueryResult::ValueNotFound(actual)), Ok(expected)) => {
+                    match QueryResult::from(expected) {
+                        QueryResult::ValueNotFound(expected) => assert_eq!(actual, expected),
+                        _ => panic!("tracking copy should not find value"),
+                    }
+                }
+                _ => panic!("should not find value"),
+            }
+        }
+    }
+}
@@ -0,0 +1,217 @@
+#[cfg(any(test, feature = "test-support"))]
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use linked_hash_map::LinkedHashMap;
+
+use engine_shared::{
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+};
+use engine_storage::{global_state::StateReader, trie::TrieMerkleProof};
+use types::Key;
+
+/// The default number of values held by a [`StateReadCache`].
+pub const DEFAULT_STATE_READ_CACHE_CAPACITY: usize = 1024;
+
+#[derive(Debug, Default)]
+struct CacheEntries {
+    values: LinkedHashMap<(Blake2bHash, Key), StoredValue>,
+    /// Only kept for tests, as it grows with every distinct key read.
+    #[cfg(any(test, feature = "test-support"))]
+    fetch_counts: HashMap<(Blake2bHash, Key), usize>,
+}
+
+/// A least-recently-used cache of values read from global state, shared by every `TrackingCopy`
+/// created by an `EngineState`.
+///
+/// Values are cached per state root, as the value under a given key at a given root never
+/// changes.  At most `capacity` values are held, and a capacity of zero disables caching.
+#[derive(Clone, Debug)]
+pub struct StateReadCache {
+    capacity: usize,
+    entries: Arc<Mutex<CacheEntries>>,
+}
+
+impl StateReadCache {
+    pub fn new(capacity: usize) -> Self {
+        StateReadCache {
+            capacity,
+            entries: Default::default(),
+        }
+    }
+
+    /// Returns a clone of the value cached under `key` at `state_hash`, if any.
+    pub fn get(&self, state_hash: Blake2bHash, key: &Key) -> Option<StoredValue> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.values.get_refresh(&(state_hash, *key)).cloned()
+    }
+
+    /// Caches `value` under `key` at `state_hash`, evicting the least-recently-used values if the
+    /// cache is full.
+    pub fn insert(&self, state_hash: Blake2bHash, key: Key, value: StoredValue) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        entries.values.insert((state_hash, key), value);
+        while entries.values.len() > self.capacity {
+            entries.values.pop_front();
+        }
+    }
+
+    /// Returns the number of times the value under `key` at `state_hash` has been read from global
+    /// state, rather than from this cache, since the cache was last cleared.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn fetch_count(&self, state_hash: Blake2bHash, key: &Key) -> usize {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .fetch_counts
+            .get(&(state_hash, *key))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    fn record_fetch(&self, state_hash: Blake2bHash, key: Key) {
+        let mut entries = self.entries.lock().unwrap();
+        *entries.fetch_counts.entry((state_hash, key)).or_default() += 1;
+    }
+
+    /// Discards every cached value, and every fetch count if they are kept.
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.values.clear();
+        #[cfg(any(test, feature = "test-support"))]
+        entries.fetch_counts.clear();
+    }
+}
+
+/// A [`StateReader`] which consults a [`StateReadCache`] before reading from global state at a
+/// single state root.
+pub struct CachedStateReader<R> {
+    reader: R,
+    state_hash: Blake2bHash,
+    cache: StateReadCache,
+}
+
+impl<R> CachedStateReader<R> {
+    pub fn new(reader: R, state_hash: Blake2bHash, cache: StateReadCache) -> Self {
+        CachedStateReader {
+            reader,
+            state_hash,
+            cache,
+        }
+    }
+}
+
+impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for CachedStateReader<R> {
+    type Error = R::Error;
+
+    fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        if let Some(value) = self.cache.get(self.state_hash, key) {
+            return Ok(Some(value));
+        }
+        #[cfg(any(test, feature = "test-support"))]
+        self.cache.record_fetch(self.state_hash, *key);
+        let maybe_value = self.reader.read(correlation_id, key)?;
+        if let Some(value) = maybe_value.as_ref() {
+            self.cache.insert(self.state_hash, *key, value.clone());
+        }
+        Ok(maybe_value)
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<(StoredValue, TrieMerkleProof<Key, StoredValue>)>, Self::Error> {
+        self.reader.read_with_proof(correlation_id, key)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        self.reader.keys_with_prefix(correlation_id, prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::{
+        newtypes::{Blake2bHash, CorrelationId},
+        stored_value::StoredValue,
+    };
+    use engine_storage::global_state::{
+        in_memory::InMemoryGlobalState, StateProvider, StateReader,
+    };
+    use types::{CLValue, Key};
+
+    use super::{CachedStateReader, StateReadCache};
+
+    fn value(n: u64) -> StoredValue {
+        StoredValue::CLValue(CLValue::from_t(n).unwrap())
+    }
+
+    #[test]
+    fn should_evict_least_recently_used() {
+        let cache = StateReadCache::new(2);
+        let root = Blake2bHash::new(b"root");
+        let (key_1, key_2, key_3) = (Key::Hash([1; 32]), Key::Hash([2; 32]), Key::Hash([3; 32]));
+
+        cache.insert(root, key_1, value(1));
+        cache.insert(root, key_2, value(2));
+        assert_eq!(cache.get(root, &key_1), Some(value(1)));
+        cache.insert(root, key_3, value(3));
+
+        assert_eq!(cache.get(root, &key_1), Some(value(1)));
+        assert_eq!(cache.get(root, &key_2), None);
+        assert_eq!(cache.get(root, &key_3), Some(value(3)));
+    }
+
+    #[test]
+    fn should_scope_values_by_state_root() {
+        let cache = StateReadCache::new(2);
+        let key = Key::Hash([1; 32]);
+        cache.insert(Blake2bHash::new(b"root"), key, value(1));
+        assert_eq!(cache.get(Blake2bHash::new(b"other root"), &key), None);
+    }
+
+    #[test]
+    fn should_not_cache_with_zero_capacity() {
+        let cache = StateReadCache::new(0);
+        let root = Blake2bHash::new(b"root");
+        let key = Key::Hash([1; 32]);
+        cache.insert(root, key, value(1));
+        assert_eq!(cache.get(root, &key), None);
+    }
+
+    #[test]
+    fn should_only_fetch_once_from_global_state() {
+        let correlation_id = CorrelationId::new();
+        let key = Key::Hash([1; 32]);
+        let (state, root_hash) =
+            InMemoryGlobalState::from_pairs(correlation_id, &[(key, value(1))]).unwrap();
+        let cache = StateReadCache::new(16);
+
+        for _ in 0..3 {
+            let reader = state.checkout(root_hash).unwrap().unwrap();
+            let cached_reader = CachedStateReader::new(reader, root_hash, cache.clone());
+            assert_eq!(
+                cached_reader.read(correlation_id, &key).unwrap(),
+                Some(value(1))
+            );
+        }
+        assert_eq!(cache.fetch_count(root_hash, &key), 1);
+
+        cache.clear();
+        assert_eq!(cache.get(root_hash, &key), None);
+        assert_eq!(cache.fetch_count(root_hash, &key), 0);
+    }
+}
@@ -1,4 +1,6 @@
-use types::{account::AccountHash, U512};
+use std::iter::FromIterator;
+
+use types::{account::AccountHash, Contract, U512};
 
 /// In PoS, the validators are stored under named keys with names formatted as
 /// "v_<hex-formatted-AccountHash>_<bond-amount>".  This function attempts to parse such a string
@@ -27,6 +29,16 @@ pub fn pos_validator_key_name_to_tuple(pos_key_name: &str) -> Option<(AccountHas
     }
 }
 
+/// Decodes every validator bond held by the PoS contract from its named keys, ignoring any named
+/// keys which aren't bonds.
+pub fn pos_bonded_validators<T: FromIterator<(AccountHash, U512)>>(pos_contract: &Contract) -> T {
+    pos_contract
+        .named_keys()
+        .keys()
+        .filter_map(|name| pos_validator_key_name_to_tuple(name))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use hex_fmt::HexFmt;
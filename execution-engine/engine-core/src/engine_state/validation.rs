@@ -0,0 +1,13 @@
+use engine_wasm_prep::PreprocessingError;
+
+/// The outcome of checking session or payment code without executing it.
+#[derive(Debug)]
+pub enum ValidationResult {
+    Valid,
+    /// The code is not a well-formed Wasm module.
+    ParseError(String),
+    /// The module is well-formed, but would be rejected when preprocessed for execution.
+    PreprocessingError(PreprocessingError),
+    /// The module doesn't export the `call` function which is invoked to execute it.
+    MissingCallExport,
+}
@@ -5,12 +5,31 @@ use blake2::{
 use rand::{RngCore, SeedableRng};
 use rand_chacha::ChaChaRng;
 
-use types::Phase;
+use types::{Phase, ProtocolVersion};
 
 use crate::{Address, ADDRESS_LENGTH};
 
 const SEED_LENGTH: usize = 32;
 
+/// The protocol version from which each [`RngPurpose`] draws from its own stream.  Under earlier
+/// versions all purposes share a single stream, which recorded state roots depend on.
+const PURPOSE_SEPARATION_MAJOR_VERSION: u32 = 2;
+
+/// The uses for which addresses are drawn from an [`AddressGenerator`].
+///
+/// Generators created for different purposes from the same seed produce independent streams, so
+/// that drawing from one doesn't shift the values drawn from another.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RngPurpose {
+    /// Addresses of new [`URef`](types::URef)s.
+    URefs = 0,
+    /// Addresses of new contracts, contract packages and contract wasm.
+    Hashes = 1,
+    /// Random values which aren't used as addresses.
+    Randomness = 2,
+}
+
 /// An [`AddressGenerator`] generates [`URef`](types::URef) addresses.
 pub struct AddressGenerator(ChaChaRng);
 
@@ -23,6 +42,23 @@ impl AddressGenerator {
             .build()
     }
 
+    /// Creates an [`AddressGenerator`] for `purpose` from a 32-byte hash digest and [`Phase`].
+    ///
+    /// Under protocol versions earlier than 2.0.0 the purpose is ignored and the generator is the
+    /// same as one created by [`AddressGenerator::new`].
+    pub fn with_purpose(
+        hash: &[u8],
+        phase: Phase,
+        purpose: RngPurpose,
+        protocol_version: ProtocolVersion,
+    ) -> AddressGenerator {
+        AddressGeneratorBuilder::new()
+            .seed_with(&hash)
+            .seed_with(&[phase as u8])
+            .seed_with_purpose(purpose, protocol_version)
+            .build()
+    }
+
     pub fn create_address(&mut self) -> Address {
         let mut buff = [0u8; ADDRESS_LENGTH];
         self.0.fill_bytes(&mut buff);
@@ -46,6 +82,15 @@ impl AddressGeneratorBuilder {
         self
     }
 
+    /// Mixes `purpose` into the seed if `protocol_version` separates streams by purpose.
+    pub fn seed_with_purpose(self, purpose: RngPurpose, protocol_version: ProtocolVersion) -> Self {
+        if protocol_version.value().major >= PURPOSE_SEPARATION_MAJOR_VERSION {
+            self.seed_with(&[purpose as u8])
+        } else {
+            self
+        }
+    }
+
     pub fn build(self) -> AddressGenerator {
         let mut seed: [u8; SEED_LENGTH] = [0u8; SEED_LENGTH];
         let mut hasher = VarBlake2b::new(SEED_LENGTH).unwrap();
@@ -57,9 +102,9 @@ impl AddressGeneratorBuilder {
 
 #[cfg(test)]
 mod tests {
-    use types::Phase;
+    use types::{Phase, ProtocolVersion};
 
-    use super::AddressGenerator;
+    use super::{AddressGenerator, RngPurpose};
 
     const DEPLOY_HASH_1: [u8; 32] = [1u8; 32];
     const DEPLOY_HASH_2: [u8; 32] = [2u8; 32];
@@ -103,4 +148,87 @@ mod tests {
             "different phase should have different output"
         );
     }
+
+    #[test]
+    fn should_generate_independent_streams_for_different_purposes() {
+        let protocol_version = ProtocolVersion::from_parts(2, 0, 0);
+        let mut urefs = AddressGenerator::with_purpose(
+            &DEPLOY_HASH_1,
+            Phase::Session,
+            RngPurpose::URefs,
+            protocol_version,
+        );
+        let mut hashes = AddressGenerator::with_purpose(
+            &DEPLOY_HASH_1,
+            Phase::Session,
+            RngPurpose::Hashes,
+            protocol_version,
+        );
+        let mut randomness = AddressGenerator::with_purpose(
+            &DEPLOY_HASH_1,
+            Phase::Session,
+            RngPurpose::Randomness,
+            protocol_version,
+        );
+        let uref_addresses: Vec<_> = (0..3).map(|_| urefs.create_address()).collect();
+        let hash_addresses: Vec<_> = (0..3).map(|_| hashes.create_address()).collect();
+        let random_values: Vec<_> = (0..3).map(|_| randomness.create_address()).collect();
+
+        for uref_address in &uref_addresses {
+            assert!(!hash_addresses.contains(uref_address));
+            assert!(!random_values.contains(uref_address));
+        }
+        for hash_address in &hash_addresses {
+            assert!(!random_values.contains(hash_address));
+        }
+
+        // Drawing from one purpose doesn't affect the stream of another.
+        let mut fresh_hashes = AddressGenerator::with_purpose(
+            &DEPLOY_HASH_1,
+            Phase::Session,
+            RngPurpose::Hashes,
+            protocol_version,
+        );
+        assert_eq!(fresh_hashes.create_address(), hash_addresses[0]);
+    }
+
+    #[test]
+    fn should_generate_same_numbers_for_same_purpose() {
+        let protocol_version = ProtocolVersion::from_parts(2, 0, 0);
+        let mut ag_a = AddressGenerator::with_purpose(
+            &DEPLOY_HASH_1,
+            Phase::Session,
+            RngPurpose::URefs,
+            protocol_version,
+        );
+        let mut ag_b = AddressGenerator::with_purpose(
+            &DEPLOY_HASH_1,
+            Phase::Session,
+            RngPurpose::URefs,
+            protocol_version,
+        );
+        for _ in 0..3 {
+            assert_eq!(ag_a.create_address(), ag_b.create_address());
+        }
+    }
+
+    #[test]
+    fn should_ignore_purpose_under_legacy_protocol_version() {
+        for purpose in &[
+            RngPurpose::URefs,
+            RngPurpose::Hashes,
+            RngPurpose::Randomness,
+        ] {
+            let mut legacy = AddressGenerator::new(&DEPLOY_HASH_1, Phase::Session);
+            let mut ag = AddressGenerator::with_purpose(
+                &DEPLOY_HASH_1,
+                Phase::Session,
+                *purpose,
+                ProtocolVersion::V1_0_0,
+            );
+            for _ in 0..3 {
+                assert_eq!(legacy.create_address(), ag.create_address());
+            }
+        }
+    }
 }
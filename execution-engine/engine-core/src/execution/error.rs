@@ -5,10 +5,10 @@ use engine_shared::TypeMismatch;
 use types::{
     account::{AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure},
     bytesrepr, system_contract_errors, AccessRights, ApiError, CLType, CLValueError,
-    ContractPackageHash, ContractVersionKey, Key, URef,
+    ContractPackageHash, ContractVersionKey, Key, Phase, ProtocolVersion, URef,
 };
 
-use crate::resolvers::error::ResolverError;
+use crate::resolvers::{error::ResolverError, v1_function_index::FunctionIndex};
 
 #[derive(Fail, Debug, Clone)]
 pub enum Error {
@@ -30,6 +30,8 @@ pub enum Error {
     InvalidAccess { required: AccessRights },
     #[fail(display = "Forged reference: {}", _0)]
     ForgedReference(URef),
+    #[fail(display = "Invalid access rights escalation: {}", _0)]
+    InvalidAccessRightsEscalation(URef),
     #[fail(display = "URef not found: {}", _0)]
     URefNotFound(String),
     #[fail(display = "Function not found: {}", _0)]
@@ -59,12 +61,19 @@ pub enum Error {
     SystemContract(system_contract_errors::Error),
     #[fail(display = "Deployment authorization failure")]
     DeploymentAuthorizationFailure,
+    #[fail(
+        display = "Upgrade authorization failure: caller lacks the access key of contract package {:?}",
+        _0
+    )]
+    UpgradeAuthorizationFailure(ContractPackageHash),
     #[fail(display = "Expected return value")]
     ExpectedReturnValue,
     #[fail(display = "Unexpected return value")]
     UnexpectedReturnValue,
     #[fail(display = "Invalid context")]
     InvalidContext,
+    #[fail(display = "{:?} cannot be called during the {:?} phase", _0, _1)]
+    InvalidContextForFunction(FunctionIndex, Phase),
     #[fail(
         display = "Incompatible protocol major version. Expected version {} but actual version is {}",
         expected, actual
@@ -89,6 +98,42 @@ pub enum Error {
         expected, actual
     )]
     InvalidKeyLength { expected: usize, actual: usize },
+    #[fail(
+        display = "Host function {} requires protocol version {}",
+        name, required_version
+    )]
+    UnsupportedHostFunction {
+        name: String,
+        required_version: ProtocolVersion,
+    },
+    #[fail(
+        display = "Value of {} bytes exceeds the maximum stored value size of {} bytes",
+        actual, max
+    )]
+    ValueTooLarge { actual: u32, max: u32 },
+}
+
+/// A stable classification of an [`Error`], suitable for matching on by clients.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// Any error which has no more specific classification.
+    Unknown,
+    /// The Wasm module failed preprocessing.
+    WasmPreprocessing,
+    /// The requested function or entry point does not exist.
+    FunctionNotFound,
+    /// The contract called `revert` with the given user error code.
+    Revert(u32),
+    /// Execution ran out of gas.
+    GasLimit,
+    /// A key could not be found in global state or in the named keys.
+    KeyNotFound,
+    /// A value had an unexpected type.
+    TypeMismatch,
+    /// The Wasm interpreter trapped.
+    Interpreter,
+    /// Global state storage failed.
+    Storage,
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
@@ -98,6 +143,21 @@ impl From<engine_wasm_prep::PreprocessingError> for Error {
 }
 
 impl Error {
+    /// Returns the stable classification of this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::WasmPreprocessing(_) => ErrorCode::WasmPreprocessing,
+            Error::FunctionNotFound(_) | Error::NoSuchMethod(_) => ErrorCode::FunctionNotFound,
+            Error::Revert(api_error) => ErrorCode::Revert((*api_error).into()),
+            Error::GasLimit => ErrorCode::GasLimit,
+            Error::KeyNotFound(_) | Error::NamedKeyNotFound(_) => ErrorCode::KeyNotFound,
+            Error::TypeMismatch(_) => ErrorCode::TypeMismatch,
+            Error::Interpreter(_) => ErrorCode::Interpreter,
+            Error::Storage(_) => ErrorCode::Storage,
+            _ => ErrorCode::Unknown,
+        }
+    }
+
     pub fn type_mismatch(expected: CLType, found: CLType) -> Error {
         Error::TypeMismatch(TypeMismatch {
             expected: format!("{:?}", expected),
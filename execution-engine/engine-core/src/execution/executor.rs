@@ -1,11 +1,16 @@
 use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
 
 use log::warn;
+use num_traits::Zero;
 use parity_wasm::elements::Module;
 use wasmi::ModuleRef;
 
 use engine_shared::{
-    account::Account, gas::Gas, newtypes::CorrelationId, stored_value::StoredValue,
+    account::Account,
+    gas::{CostBreakdown, Gas},
+    motes::Motes,
+    newtypes::CorrelationId,
+    stored_value::StoredValue,
 };
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
 use types::{
@@ -19,7 +24,10 @@ use crate::{
         execution_effect::ExecutionEffect, execution_result::ExecutionResult,
         system_contract_cache::SystemContractCache, EngineConfig,
     },
-    execution::{address_generator::AddressGenerator, Error},
+    execution::{
+        address_generator::{AddressGenerator, RngPurpose},
+        Error,
+    },
     runtime::{
         extract_access_rights_from_keys, extract_access_rights_from_urefs, instance_and_memory,
         Runtime,
@@ -41,7 +49,7 @@ macro_rules! on_fail_charge {
             }
         }
     };
-    ($fn:expr, $cost:expr) => {
+    ($fn:expr, $cost:expr, $cost_breakdown:expr) => {
         match $fn {
             Ok(res) => res,
             Err(e) => {
@@ -51,11 +59,15 @@ macro_rules! on_fail_charge {
                     error: exec_err.into(),
                     effect: Default::default(),
                     cost: $cost,
+                    cost_breakdown: $cost_breakdown,
+                    host_function_profile: Default::default(),
+                    charged: Motes::zero(),
+                    phase_costs: Default::default(),
                 };
             }
         }
     };
-    ($fn:expr, $cost:expr, $effect:expr) => {
+    ($fn:expr, $cost:expr, $cost_breakdown:expr, $host_function_profile:expr, $effect:expr) => {
         match $fn {
             Ok(res) => res,
             Err(e) => {
@@ -65,6 +77,10 @@ macro_rules! on_fail_charge {
                     error: exec_err.into(),
                     effect: $effect,
                     cost: $cost,
+                    cost_breakdown: $cost_breakdown,
+                    host_function_profile: $host_function_profile,
+                    charged: Motes::zero(),
+                    phase_costs: Default::default(),
                 };
             }
         }
@@ -122,11 +138,21 @@ impl Executor {
         };
 
         let hash_address_generator = {
-            let generator = AddressGenerator::new(&deploy_hash, phase);
+            let generator = AddressGenerator::with_purpose(
+                &deploy_hash,
+                phase,
+                RngPurpose::Hashes,
+                protocol_version,
+            );
             Rc::new(RefCell::new(generator))
         };
         let uref_address_generator = {
-            let generator = AddressGenerator::new(&deploy_hash, phase);
+            let generator = AddressGenerator::with_purpose(
+                &deploy_hash,
+                phase,
+                RngPurpose::URefs,
+                protocol_version,
+            );
             Rc::new(RefCell::new(generator))
         };
         let gas_counter: Gas = Gas::default();
@@ -182,6 +208,10 @@ impl Executor {
                         return ExecutionResult::Success {
                             effect: runtime.context().effect(),
                             cost: runtime.context().gas_counter(),
+                            cost_breakdown: runtime.cost_breakdown().clone(),
+                            host_function_profile: runtime.host_function_profile(),
+                            charged: Motes::zero(),
+                            phase_costs: Default::default(),
                         };
                     }
                     Err(error) => {
@@ -189,6 +219,10 @@ impl Executor {
                             error: error.into(),
                             effect: effects_snapshot,
                             cost: runtime.context().gas_counter(),
+                            cost_breakdown: runtime.cost_breakdown().clone(),
+                            host_function_profile: runtime.host_function_profile(),
+                            charged: Motes::zero(),
+                            phase_costs: Default::default(),
                         };
                     }
                 }
@@ -204,6 +238,10 @@ impl Executor {
                         return ExecutionResult::Success {
                             effect: runtime.context().effect(),
                             cost: runtime.context().gas_counter(),
+                            cost_breakdown: runtime.cost_breakdown().clone(),
+                            host_function_profile: runtime.host_function_profile(),
+                            charged: Motes::zero(),
+                            phase_costs: Default::default(),
                         };
                     }
                     Err(error) => {
@@ -211,6 +249,10 @@ impl Executor {
                             error: error.into(),
                             effect: effects_snapshot,
                             cost: runtime.context().gas_counter(),
+                            cost_breakdown: runtime.cost_breakdown().clone(),
+                            host_function_profile: runtime.host_function_profile(),
+                            charged: Motes::zero(),
+                            phase_costs: Default::default(),
                         };
                     }
                 }
@@ -220,12 +262,18 @@ impl Executor {
         on_fail_charge!(
             instance.invoke_export(entry_point_name, &[], &mut runtime),
             runtime.context().gas_counter(),
+            runtime.cost_breakdown().clone(),
+            runtime.host_function_profile(),
             effects_snapshot
         );
 
         ExecutionResult::Success {
             effect: runtime.context().effect(),
             cost: runtime.context().gas_counter(),
+            cost_breakdown: runtime.cost_breakdown().clone(),
+            host_function_profile: runtime.host_function_profile(),
+            charged: Motes::zero(),
+            phase_costs: Default::default(),
         }
     }
 
@@ -255,7 +303,8 @@ impl Executor {
         T: FromBytes + CLTyped,
     {
         match direct_system_contract_call {
-            DirectSystemContractCall::FinalizePayment => {
+            DirectSystemContractCall::FinalizePayment
+            | DirectSystemContractCall::DistributeRewards => {
                 if protocol_data.proof_of_stake() != base_key.into_seed() {
                     panic!(
                         "{} should only be called with the proof of stake contract",
@@ -274,11 +323,21 @@ impl Executor {
         }
 
         let hash_address_generator = {
-            let generator = AddressGenerator::new(&deploy_hash, phase);
+            let generator = AddressGenerator::with_purpose(
+                &deploy_hash,
+                phase,
+                RngPurpose::Hashes,
+                protocol_version,
+            );
             Rc::new(RefCell::new(generator))
         };
         let uref_address_generator = {
-            let generator = AddressGenerator::new(&deploy_hash, phase);
+            let generator = AddressGenerator::with_purpose(
+                &deploy_hash,
+                phase,
+                RngPurpose::URefs,
+                protocol_version,
+            );
             Rc::new(RefCell::new(generator))
         };
         let gas_counter = Gas::default(); // maybe const?
@@ -313,6 +372,10 @@ impl Executor {
                 ExecutionResult::Failure {
                     effect: effect_snapshot.clone(),
                     cost: gas_counter,
+                    cost_breakdown: CostBreakdown::default(),
+                    host_function_profile: Default::default(),
+                    charged: Motes::zero(),
+                    phase_costs: Default::default(),
                     error: e.into(),
                 }
                 .take_without_ret::<T>();
@@ -374,6 +437,8 @@ impl Executor {
         let runtime_context = runtime.context();
 
         let cost = runtime_context.gas_counter();
+        let cost_breakdown = runtime.cost_breakdown().clone();
+        let host_function_profile = runtime.host_function_profile();
 
         let effect = if revert_effect {
             effect_snapshot
@@ -386,8 +451,19 @@ impl Executor {
                 error: error.into(),
                 effect,
                 cost,
+                cost_breakdown,
+                host_function_profile,
+                charged: Motes::zero(),
+                phase_costs: Default::default(),
+            },
+            None => ExecutionResult::Success {
+                effect,
+                cost,
+                cost_breakdown,
+                host_function_profile,
+                charged: Motes::zero(),
+                phase_costs: Default::default(),
             },
-            None => ExecutionResult::Success { effect, cost },
         };
 
         match maybe_ret {
@@ -551,6 +627,7 @@ impl Executor {
 
 pub enum DirectSystemContractCall {
     FinalizePayment,
+    DistributeRewards,
     CreatePurse,
     Transfer,
 }
@@ -559,6 +636,7 @@ impl DirectSystemContractCall {
     fn entry_point_name(&self) -> &str {
         match self {
             DirectSystemContractCall::FinalizePayment => "finalize_payment",
+            DirectSystemContractCall::DistributeRewards => "distribute_rewards",
             DirectSystemContractCall::CreatePurse => "create",
             DirectSystemContractCall::Transfer => "transfer",
         }
@@ -580,7 +658,8 @@ impl DirectSystemContractCall {
     {
         let entry_point_name = self.entry_point_name();
         let result = match self {
-            DirectSystemContractCall::FinalizePayment => runtime.call_host_proof_of_stake(
+            DirectSystemContractCall::FinalizePayment
+            | DirectSystemContractCall::DistributeRewards => runtime.call_host_proof_of_stake(
                 protocol_version,
                 entry_point_name,
                 named_keys,
@@ -602,12 +681,20 @@ impl DirectSystemContractCall {
                 Ok(ret) => ExecutionResult::Success {
                     effect: runtime.context().effect(),
                     cost: runtime.context().gas_counter(),
+                    cost_breakdown: runtime.cost_breakdown().clone(),
+                    host_function_profile: runtime.host_function_profile(),
+                    charged: Motes::zero(),
+                    phase_costs: Default::default(),
                 }
                 .take_with_ret(ret),
                 Err(error) => ExecutionResult::Failure {
                     error: Error::CLValue(error).into(),
                     effect: execution_effect,
                     cost: runtime.context().gas_counter(),
+                    cost_breakdown: runtime.cost_breakdown().clone(),
+                    host_function_profile: runtime.host_function_profile(),
+                    charged: Motes::zero(),
+                    phase_costs: Default::default(),
                 }
                 .take_without_ret(),
             },
@@ -615,6 +702,10 @@ impl DirectSystemContractCall {
                 error: error.into(),
                 effect: execution_effect,
                 cost: runtime.context().gas_counter(),
+                cost_breakdown: runtime.cost_breakdown().clone(),
+                host_function_profile: runtime.host_function_profile(),
+                charged: Motes::zero(),
+                phase_costs: Default::default(),
             }
             .take_without_ret(),
         }
@@ -0,0 +1,45 @@
+use std::collections::BTreeSet;
+
+use types::Key;
+
+use crate::{
+    engine_state::{execution_effect::ExecutionEffect, op::Op},
+    resolvers::v1_function_index::FunctionIndex,
+};
+
+/// How far an execution got before it ran out of gas.
+///
+/// Only recorded when the engine is built with the `test-support` feature, in which case it can be
+/// retrieved on the executing thread via `runtime::take_gas_exhaustion_context`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GasExhaustionContext {
+    /// The keys only read so far.
+    pub keys_read: BTreeSet<Key>,
+    /// The keys written or added to so far.
+    pub keys_written: BTreeSet<Key>,
+    /// The last host function invoked, other than the one charging gas for Wasm opcodes.
+    pub last_host_function: Option<FunctionIndex>,
+}
+
+impl GasExhaustionContext {
+    pub fn new(effect: &ExecutionEffect, last_host_function: Option<FunctionIndex>) -> Self {
+        let mut keys_read = BTreeSet::new();
+        let mut keys_written = BTreeSet::new();
+        for (key, op) in effect.ops.iter() {
+            match op {
+                Op::Read => {
+                    keys_read.insert(*key);
+                }
+                Op::Write | Op::Add => {
+                    keys_written.insert(*key);
+                }
+                Op::NoOp => (),
+            }
+        }
+        GasExhaustionContext {
+            keys_read,
+            keys_written,
+            last_host_function,
+        }
+    }
+}
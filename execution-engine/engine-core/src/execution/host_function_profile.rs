@@ -0,0 +1,56 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::resolvers::v1_function_index::FunctionIndex;
+
+/// The calls made to a single host function and the wall time spent in them.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct HostFunctionStats {
+    pub count: u64,
+    pub total_nanos: u64,
+}
+
+/// The host function calls made during an execution, keyed by host function.
+///
+/// Only collected when [`EngineConfig::host_function_profiling`] is set, in which case every call
+/// timed by the runtime's instrumenter is recorded, including calls made by stored contracts.
+///
+/// [`EngineConfig::host_function_profiling`]:
+/// crate::engine_state::engine_config::EngineConfig::host_function_profiling
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct HostFunctionProfile(HashMap<FunctionIndex, HostFunctionStats>);
+
+impl HostFunctionProfile {
+    /// Records one call to `function_index` which took `duration`.
+    pub fn record(&mut self, function_index: FunctionIndex, duration: Duration) {
+        let nanos = duration.as_nanos().min(u128::from(u64::max_value())) as u64;
+        let stats = self.0.entry(function_index).or_default();
+        stats.count += 1;
+        stats.total_nanos = stats.total_nanos.saturating_add(nanos);
+    }
+
+    /// Adds every call recorded in `other` to `self`.
+    pub fn merge(&mut self, other: &HostFunctionProfile) {
+        for (function_index, other_stats) in other.0.iter() {
+            let stats = self.0.entry(*function_index).or_default();
+            stats.count += other_stats.count;
+            stats.total_nanos = stats.total_nanos.saturating_add(other_stats.total_nanos);
+        }
+    }
+
+    /// Returns the calls recorded for `function_index`.
+    pub fn get(&self, function_index: FunctionIndex) -> HostFunctionStats {
+        self.0.get(&function_index).copied().unwrap_or_default()
+    }
+
+    /// Returns `true` if no calls were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over every host function which was called, along with its calls.
+    pub fn iter(&self) -> impl Iterator<Item = (FunctionIndex, HostFunctionStats)> + '_ {
+        self.0
+            .iter()
+            .map(|(function_index, stats)| (*function_index, *stats))
+    }
+}
@@ -2,11 +2,15 @@ mod address_generator;
 mod error;
 #[macro_use]
 mod executor;
+mod gas_exhaustion_context;
+mod host_function_profile;
 #[cfg(test)]
 mod tests;
 
 pub use self::{
-    address_generator::{AddressGenerator, AddressGeneratorBuilder},
-    error::Error,
+    address_generator::{AddressGenerator, AddressGeneratorBuilder, RngPurpose},
+    error::{Error, ErrorCode},
     executor::{DirectSystemContractCall, Executor},
+    gas_exhaustion_context::GasExhaustionContext,
+    host_function_profile::{HostFunctionProfile, HostFunctionStats},
 };
@@ -1,5 +1,10 @@
-use engine_shared::{gas::Gas, transform::Transform};
+use engine_shared::{
+    gas::{CostBreakdown, Gas},
+    motes::Motes,
+    transform::Transform,
+};
 use log::warn;
+use num_traits::Zero;
 use types::{Key, U512};
 
 use super::Error;
@@ -12,10 +17,14 @@ fn on_fail_charge_test_helper<T>(
     success_cost: Gas,
     error_cost: Gas,
 ) -> ExecutionResult {
-    let _result = on_fail_charge!(f(), error_cost);
+    let _result = on_fail_charge!(f(), error_cost, CostBreakdown::default());
     ExecutionResult::Success {
         effect: Default::default(),
         cost: success_cost,
+        cost_breakdown: CostBreakdown::default(),
+        host_function_profile: Default::default(),
+        charged: Motes::zero(),
+        phase_costs: Default::default(),
     }
 }
 
@@ -45,19 +54,29 @@ fn on_fail_charge_err_laziness_test() {
 fn on_fail_charge_with_action() {
     let f = || {
         let input: Result<(), Error> = Err(Error::GasLimit);
-        on_fail_charge!(input, Gas::new(U512::from(456)), {
-            let mut effect = ExecutionEffect::default();
+        on_fail_charge!(
+            input,
+            Gas::new(U512::from(456)),
+            CostBreakdown::default(),
+            Default::default(),
+            {
+                let mut effect = ExecutionEffect::default();
 
-            effect.ops.insert(Key::Hash([42u8; 32]), Op::Read);
-            effect
-                .transforms
-                .insert(Key::Hash([42u8; 32]), Transform::Identity);
+                effect.ops.insert(Key::Hash([42u8; 32]), Op::Read);
+                effect
+                    .transforms
+                    .insert(Key::Hash([42u8; 32]), Transform::Identity);
 
-            effect
-        });
+                effect
+            }
+        );
         ExecutionResult::Success {
             effect: Default::default(),
             cost: Gas::default(),
+            cost_breakdown: CostBreakdown::default(),
+            host_function_profile: Default::default(),
+            charged: Motes::zero(),
+            phase_costs: Default::default(),
         }
     };
     match f() {
@@ -18,7 +18,9 @@ pub fn create_module_resolver(
 ) -> Result<impl ModuleImportResolver + MemoryResolver, ResolverError> {
     // TODO: revisit how protocol_version check here is meant to combine with upgrade
     if protocol_version >= ProtocolVersion::V1_0_0 {
-        return Ok(v1_resolver::RuntimeModuleImportResolver::default());
+        return Ok(v1_resolver::RuntimeModuleImportResolver::new(
+            protocol_version,
+        ));
     }
     Err(ResolverError::UnknownProtocolVersion(protocol_version))
 }
@@ -32,3 +34,74 @@ fn resolve_invalid_module() {
 fn protocol_version_1_always_resolves() {
     assert!(create_module_resolver(ProtocolVersion::V1_0_0).is_ok());
 }
+
+#[cfg(test)]
+fn resolve_host_function(
+    name: &str,
+    protocol_version: ProtocolVersion,
+) -> Result<wasmi::FuncRef, crate::execution::Error> {
+    use wasmi::{Signature, ValueType};
+
+    let resolver = create_module_resolver(protocol_version).expect("should create resolver");
+    // The resolver supplies the signature of a host function itself, so any signature will do.
+    let signature = Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32));
+    resolver.resolve_func(name, &signature).map_err(Into::into)
+}
+
+#[cfg(test)]
+const VERSION_2_HOST_FUNCTIONS: [&str; 7] = [
+    "load_named_keys_chunk",
+    "blake2b",
+    "verify_ed25519",
+    "remove",
+    "get_deploy_hash",
+    "transfer_to_account_with_id",
+    "transfer_from_purse_to_account_with_id",
+];
+
+#[test]
+fn should_not_resolve_host_function_from_later_protocol_version() {
+    for name in VERSION_2_HOST_FUNCTIONS.iter() {
+        match resolve_host_function(name, ProtocolVersion::V1_0_0) {
+            Err(crate::execution::Error::UnsupportedHostFunction {
+                name: reported_name,
+                required_version,
+            }) => {
+                assert_eq!(reported_name, *name);
+                assert_eq!(required_version, ProtocolVersion::from_parts(2, 0, 0));
+            }
+            other => panic!(
+                "expected UnsupportedHostFunction for {}, got {:?}",
+                name,
+                other.err()
+            ),
+        }
+    }
+}
+
+#[test]
+fn should_resolve_host_function_from_current_protocol_version() {
+    for name in VERSION_2_HOST_FUNCTIONS.iter() {
+        assert!(resolve_host_function(name, ProtocolVersion::from_parts(2, 0, 0)).is_ok());
+    }
+}
+
+#[test]
+fn should_resolve_every_host_function_by_its_import_name() {
+    use std::convert::TryFrom;
+
+    use crate::resolvers::v1_function_index::FunctionIndex;
+
+    let protocol_version = ProtocolVersion::from_parts(2, 0, 0);
+    for index in 0.. {
+        let function_index = match FunctionIndex::try_from(index) {
+            Ok(function_index) => function_index,
+            Err(_) => break,
+        };
+        assert!(
+            resolve_host_function(function_index.import_name(), protocol_version).is_ok(),
+            "{:?}",
+            function_index
+        );
+    }
+}
@@ -3,7 +3,9 @@ use std::convert::TryFrom;
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::{FromPrimitive, ToPrimitive};
 
-#[derive(Debug, PartialEq, FromPrimitive, ToPrimitive, Clone, Copy)]
+use types::ProtocolVersion;
+
+#[derive(Debug, PartialEq, Eq, Hash, FromPrimitive, ToPrimitive, Clone, Copy)]
 #[repr(usize)]
 pub enum FunctionIndex {
     WriteFuncIndex,
@@ -42,13 +44,220 @@ pub enum FunctionIndex {
     DisableContractVersion,
     CallVersionedContract,
     CreateContractUserGroup,
-    #[cfg(feature = "test-support")]
-    PrintIndex,
     GetRuntimeArgsizeIndex,
     GetRuntimeArgIndex,
     RemoveContractUserGroupIndex,
     ExtendContractUserGroupURefsIndex,
     RemoveContractUserGroupURefsIndex,
+    LoadNamedKeysChunkFuncIndex,
+    Blake2bFuncIndex,
+    VerifyEd25519Index,
+    RemoveFuncIndex,
+    GetDeployHashIndex,
+    TransferToAccountWithIdIndex,
+    TransferFromPurseToAccountWithIdIndex,
+    NewHashAddressIndex,
+    CreateNamedPurseIndex,
+    EmitEventIndex,
+    GetActionThresholdIndex,
+    ListAssociatedKeysIndex,
+    ListContractUserGroupsIndex,
+    ListAuthorizationKeysIndex,
+    GetSystemContractByNameIndex,
+    CanAuthorizeIndex,
+    // Kept last so that the indices of the other host functions don't depend on the feature.
+    #[cfg(feature = "test-support")]
+    PrintIndex,
+}
+
+impl FunctionIndex {
+    /// Returns the name under which calls to the host function are logged and profiled, or `None`
+    /// for the gas counter, which isn't instrumented.
+    pub fn host_function_name(self) -> Option<&'static str> {
+        match self {
+            FunctionIndex::GasFuncIndex => None,
+            FunctionIndex::WriteFuncIndex => Some("host_function_write"),
+            FunctionIndex::WriteLocalFuncIndex => Some("host_function_write_local"),
+            FunctionIndex::ReadFuncIndex => Some("host_function_read_value"),
+            FunctionIndex::ReadLocalFuncIndex => Some("host_function_read_value_local"),
+            FunctionIndex::AddFuncIndex => Some("host_function_add"),
+            FunctionIndex::NewFuncIndex => Some("host_function_new_uref"),
+            FunctionIndex::RetFuncIndex => Some("host_function_ret"),
+            FunctionIndex::CallContractFuncIndex => Some("host_function_call_contract"),
+            FunctionIndex::GetKeyFuncIndex => Some("host_function_get_key"),
+            FunctionIndex::HasKeyFuncIndex => Some("host_function_has_key"),
+            FunctionIndex::PutKeyFuncIndex => Some("host_function_put_key"),
+            FunctionIndex::IsValidURefFnIndex => Some("host_function_is_valid_uref"),
+            FunctionIndex::RevertFuncIndex => Some("host_function_revert"),
+            FunctionIndex::AddAssociatedKeyFuncIndex => Some("host_function_add_associated_key"),
+            FunctionIndex::RemoveAssociatedKeyFuncIndex => {
+                Some("host_function_remove_associated_key")
+            }
+            FunctionIndex::UpdateAssociatedKeyFuncIndex => {
+                Some("host_function_update_associated_key")
+            }
+            FunctionIndex::SetActionThresholdFuncIndex => {
+                Some("host_function_set_action_threshold")
+            }
+            FunctionIndex::LoadNamedKeysFuncIndex => Some("host_function_load_named_keys"),
+            FunctionIndex::LoadNamedKeysChunkFuncIndex => {
+                Some("host_function_load_named_keys_chunk")
+            }
+            FunctionIndex::RemoveKeyFuncIndex => Some("host_function_remove_key"),
+            FunctionIndex::GetCallerIndex => Some("host_function_get_caller"),
+            FunctionIndex::GetBlocktimeIndex => Some("host_function_get_blocktime"),
+            FunctionIndex::CreatePurseIndex => Some("host_function_create_purse"),
+            FunctionIndex::TransferToAccountIndex => Some("host_function_transfer_to_account"),
+            FunctionIndex::TransferFromPurseToAccountIndex => {
+                Some("host_function_transfer_from_purse_to_account")
+            }
+            FunctionIndex::TransferFromPurseToPurseIndex => {
+                Some("host_function_transfer_from_purse_to_purse")
+            }
+            FunctionIndex::GetBalanceIndex => Some("host_function_get_balance"),
+            FunctionIndex::GetPhaseIndex => Some("host_function_get_phase"),
+            FunctionIndex::GetSystemContractIndex => Some("host_function_get_system_contract"),
+            FunctionIndex::GetMainPurseIndex => Some("host_function_get_main_purse"),
+            FunctionIndex::ReadHostBufferIndex => Some("host_function_read_host_buffer"),
+            FunctionIndex::CreateContractPackageAtHash => {
+                Some("host_function_create_contract_package_at_hash")
+            }
+            FunctionIndex::AddContractVersion => Some("host_function_add_contract_version"),
+            FunctionIndex::DisableContractVersion => Some("host_remove_contract_version"),
+            FunctionIndex::CallVersionedContract => Some("host_call_versioned_contract"),
+            FunctionIndex::CreateContractUserGroup => Some("create_contract_user_group"),
+            FunctionIndex::Blake2bFuncIndex => Some("host_function_blake2b"),
+            FunctionIndex::VerifyEd25519Index => Some("host_function_verify_ed25519"),
+            FunctionIndex::RemoveFuncIndex => Some("host_function_remove"),
+            FunctionIndex::GetDeployHashIndex => Some("host_function_get_deploy_hash"),
+            FunctionIndex::TransferToAccountWithIdIndex => {
+                Some("host_function_transfer_to_account_with_id")
+            }
+            FunctionIndex::TransferFromPurseToAccountWithIdIndex => {
+                Some("host_function_transfer_from_purse_to_account_with_id")
+            }
+            FunctionIndex::NewHashAddressIndex => Some("host_function_new_hash_address"),
+            FunctionIndex::CreateNamedPurseIndex => Some("host_function_create_named_purse"),
+            FunctionIndex::EmitEventIndex => Some("host_function_emit_event"),
+            FunctionIndex::GetActionThresholdIndex => Some("host_function_get_action_threshold"),
+            FunctionIndex::ListAssociatedKeysIndex => Some("host_function_list_associated_keys"),
+            FunctionIndex::ListContractUserGroupsIndex => {
+                Some("host_function_list_contract_user_groups")
+            }
+            FunctionIndex::ListAuthorizationKeysIndex => {
+                Some("host_function_list_authorization_keys")
+            }
+            FunctionIndex::GetSystemContractByNameIndex => {
+                Some("host_function_get_system_contract_by_name")
+            }
+            FunctionIndex::CanAuthorizeIndex => Some("host_function_can_authorize"),
+            #[cfg(feature = "test-support")]
+            FunctionIndex::PrintIndex => Some("host_function_print"),
+            FunctionIndex::GetRuntimeArgsizeIndex => Some("host_get_named_arg_size"),
+            FunctionIndex::GetRuntimeArgIndex => Some("host_get_named_arg"),
+            FunctionIndex::RemoveContractUserGroupIndex => Some("host_remove_contract_user_group"),
+            FunctionIndex::ExtendContractUserGroupURefsIndex => {
+                Some("host_provision_contract_user_group_uref")
+            }
+            FunctionIndex::RemoveContractUserGroupURefsIndex => {
+                Some("host_remove_contract_user_group_urefs")
+            }
+        }
+    }
+
+    /// Returns the name under which the host function is imported by contracts.
+    pub fn import_name(self) -> &'static str {
+        match self {
+            FunctionIndex::WriteFuncIndex => "write",
+            FunctionIndex::WriteLocalFuncIndex => "write_local",
+            FunctionIndex::ReadFuncIndex => "read_value",
+            FunctionIndex::ReadLocalFuncIndex => "read_value_local",
+            FunctionIndex::AddFuncIndex => "add",
+            FunctionIndex::NewFuncIndex => "new_uref",
+            FunctionIndex::RetFuncIndex => "ret",
+            FunctionIndex::CallContractFuncIndex => "call_contract",
+            FunctionIndex::GetKeyFuncIndex => "get_key",
+            FunctionIndex::GasFuncIndex => "gas",
+            FunctionIndex::HasKeyFuncIndex => "has_key",
+            FunctionIndex::PutKeyFuncIndex => "put_key",
+            FunctionIndex::IsValidURefFnIndex => "is_valid_uref",
+            FunctionIndex::RevertFuncIndex => "revert",
+            FunctionIndex::AddAssociatedKeyFuncIndex => "add_associated_key",
+            FunctionIndex::RemoveAssociatedKeyFuncIndex => "remove_associated_key",
+            FunctionIndex::UpdateAssociatedKeyFuncIndex => "update_associated_key",
+            FunctionIndex::SetActionThresholdFuncIndex => "set_action_threshold",
+            FunctionIndex::LoadNamedKeysFuncIndex => "load_named_keys",
+            FunctionIndex::RemoveKeyFuncIndex => "remove_key",
+            FunctionIndex::GetCallerIndex => "get_caller",
+            FunctionIndex::GetBlocktimeIndex => "get_blocktime",
+            FunctionIndex::CreatePurseIndex => "create_purse",
+            FunctionIndex::TransferToAccountIndex => "transfer_to_account",
+            FunctionIndex::TransferFromPurseToAccountIndex => "transfer_from_purse_to_account",
+            FunctionIndex::TransferFromPurseToPurseIndex => "transfer_from_purse_to_purse",
+            FunctionIndex::GetBalanceIndex => "get_balance",
+            FunctionIndex::GetPhaseIndex => "get_phase",
+            FunctionIndex::GetSystemContractIndex => "get_system_contract",
+            FunctionIndex::GetMainPurseIndex => "get_main_purse",
+            FunctionIndex::ReadHostBufferIndex => "read_host_buffer",
+            FunctionIndex::CreateContractPackageAtHash => "create_contract_package_at_hash",
+            FunctionIndex::AddContractVersion => "add_contract_version",
+            FunctionIndex::DisableContractVersion => "disable_contract_version",
+            FunctionIndex::CallVersionedContract => "call_versioned_contract",
+            FunctionIndex::CreateContractUserGroup => "create_contract_user_group",
+            FunctionIndex::GetRuntimeArgsizeIndex => "get_named_arg_size",
+            FunctionIndex::GetRuntimeArgIndex => "get_named_arg",
+            FunctionIndex::RemoveContractUserGroupIndex => "remove_contract_user_group",
+            FunctionIndex::ExtendContractUserGroupURefsIndex => {
+                "provision_contract_user_group_uref"
+            }
+            FunctionIndex::RemoveContractUserGroupURefsIndex => "remove_contract_user_group_urefs",
+            FunctionIndex::LoadNamedKeysChunkFuncIndex => "load_named_keys_chunk",
+            FunctionIndex::Blake2bFuncIndex => "blake2b",
+            FunctionIndex::VerifyEd25519Index => "verify_ed25519",
+            FunctionIndex::RemoveFuncIndex => "remove",
+            FunctionIndex::GetDeployHashIndex => "get_deploy_hash",
+            FunctionIndex::TransferToAccountWithIdIndex => "transfer_to_account_with_id",
+            FunctionIndex::TransferFromPurseToAccountWithIdIndex => {
+                "transfer_from_purse_to_account_with_id"
+            }
+            FunctionIndex::NewHashAddressIndex => "new_hash_address",
+            FunctionIndex::CreateNamedPurseIndex => "create_named_purse",
+            FunctionIndex::EmitEventIndex => "emit_event",
+            FunctionIndex::GetActionThresholdIndex => "get_action_threshold",
+            FunctionIndex::ListAssociatedKeysIndex => "list_associated_keys",
+            FunctionIndex::ListContractUserGroupsIndex => "list_contract_user_groups",
+            FunctionIndex::ListAuthorizationKeysIndex => "list_authorization_keys",
+            FunctionIndex::GetSystemContractByNameIndex => "get_system_contract_by_name",
+            FunctionIndex::CanAuthorizeIndex => "can_authorize",
+            #[cfg(feature = "test-support")]
+            FunctionIndex::PrintIndex => "print",
+        }
+    }
+
+    /// Returns the lowest protocol version under which the host function can be imported and
+    /// called.  Host functions added after version 1.0.0 must be listed here so that contracts
+    /// can't use them on networks which haven't upgraded yet.
+    pub fn min_protocol_version(self) -> ProtocolVersion {
+        match self {
+            FunctionIndex::LoadNamedKeysChunkFuncIndex
+            | FunctionIndex::Blake2bFuncIndex
+            | FunctionIndex::VerifyEd25519Index
+            | FunctionIndex::RemoveFuncIndex
+            | FunctionIndex::GetDeployHashIndex
+            | FunctionIndex::TransferToAccountWithIdIndex
+            | FunctionIndex::TransferFromPurseToAccountWithIdIndex
+            | FunctionIndex::NewHashAddressIndex
+            | FunctionIndex::CreateNamedPurseIndex
+            | FunctionIndex::EmitEventIndex
+            | FunctionIndex::GetActionThresholdIndex
+            | FunctionIndex::ListAssociatedKeysIndex
+            | FunctionIndex::ListContractUserGroupsIndex
+            | FunctionIndex::ListAuthorizationKeysIndex
+            | FunctionIndex::GetSystemContractByNameIndex
+            | FunctionIndex::CanAuthorizeIndex => ProtocolVersion::from_parts(2, 0, 0),
+            _ => ProtocolVersion::V1_0_0,
+        }
+    }
 }
 
 impl Into<usize> for FunctionIndex {
@@ -69,7 +278,9 @@ impl TryFrom<usize> for FunctionIndex {
 #[cfg(test)]
 mod tests {
     use super::FunctionIndex;
+    use engine_wasm_prep::wasm_costs::HOST_FUNCTION_COUNT;
     use std::convert::TryFrom;
+    use types::ProtocolVersion;
 
     #[test]
     fn primitive_to_enum() {
@@ -82,6 +293,33 @@ mod tests {
         let _primitive: usize = element.into();
     }
 
+    #[test]
+    fn host_function_costs_cover_all_indices() {
+        assert!(FunctionIndex::try_from(HOST_FUNCTION_COUNT).is_err());
+        #[cfg(feature = "test-support")]
+        {
+            let last_index: usize = FunctionIndex::PrintIndex.into();
+            assert_eq!(last_index + 1, HOST_FUNCTION_COUNT);
+        }
+    }
+
+    #[test]
+    fn should_require_protocol_version_2_for_transfers_with_id() {
+        let version_2 = ProtocolVersion::from_parts(2, 0, 0);
+        assert_eq!(
+            FunctionIndex::TransferToAccountWithIdIndex.min_protocol_version(),
+            version_2
+        );
+        assert_eq!(
+            FunctionIndex::TransferFromPurseToAccountWithIdIndex.min_protocol_version(),
+            version_2
+        );
+        assert_eq!(
+            FunctionIndex::TransferToAccountIndex.min_protocol_version(),
+            ProtocolVersion::V1_0_0
+        );
+    }
+
     #[test]
     fn invalid_index() {
         assert!(FunctionIndex::try_from(123_456_789usize).is_err());
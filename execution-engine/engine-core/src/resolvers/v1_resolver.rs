@@ -5,20 +5,25 @@ use wasmi::{
     MemoryInstance, MemoryRef, ModuleImportResolver, Signature, ValueType,
 };
 
+use types::ProtocolVersion;
+
 use super::{
     error::ResolverError, memory_resolver::MemoryResolver, v1_function_index::FunctionIndex,
 };
+use crate::execution::Error;
 
 pub struct RuntimeModuleImportResolver {
     memory: RefCell<Option<MemoryRef>>,
     max_memory: u32,
+    protocol_version: ProtocolVersion,
 }
 
-impl Default for RuntimeModuleImportResolver {
-    fn default() -> Self {
+impl RuntimeModuleImportResolver {
+    pub fn new(protocol_version: ProtocolVersion) -> Self {
         RuntimeModuleImportResolver {
             memory: RefCell::new(None),
             max_memory: 64,
+            protocol_version,
         }
     }
 }
@@ -39,175 +44,239 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
         field_name: &str,
         _signature: &Signature,
     ) -> Result<FuncRef, InterpreterError> {
-        let func_ref = match field_name {
-            "read_value" => FuncInstance::alloc_host(
+        let (signature, function_index) = match field_name {
+            "read_value" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::ReadFuncIndex.into(),
+                FunctionIndex::ReadFuncIndex,
             ),
-            "read_value_local" => FuncInstance::alloc_host(
+            "read_value_local" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::ReadLocalFuncIndex.into(),
+                FunctionIndex::ReadLocalFuncIndex,
             ),
-            "load_named_keys" => FuncInstance::alloc_host(
+            "load_named_keys" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::LoadNamedKeysFuncIndex.into(),
+                FunctionIndex::LoadNamedKeysFuncIndex,
+            ),
+            "load_named_keys_chunk" => (
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::LoadNamedKeysChunkFuncIndex,
             ),
-            "write" => FuncInstance::alloc_host(
+            "write" => (
                 Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::WriteFuncIndex.into(),
+                FunctionIndex::WriteFuncIndex,
             ),
-            "write_local" => FuncInstance::alloc_host(
+            "write_local" => (
                 Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::WriteLocalFuncIndex.into(),
+                FunctionIndex::WriteLocalFuncIndex,
             ),
-            "add" => FuncInstance::alloc_host(
+            "add" => (
                 Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::AddFuncIndex.into(),
+                FunctionIndex::AddFuncIndex,
             ),
-            "new_uref" => FuncInstance::alloc_host(
+            "new_uref" => (
                 Signature::new(&[ValueType::I32; 3][..], None),
-                FunctionIndex::NewFuncIndex.into(),
+                FunctionIndex::NewFuncIndex,
             ),
-            "ret" => FuncInstance::alloc_host(
+            "ret" => (
                 Signature::new(&[ValueType::I32; 2][..], None),
-                FunctionIndex::RetFuncIndex.into(),
+                FunctionIndex::RetFuncIndex,
             ),
-            "get_key" => FuncInstance::alloc_host(
+            "get_key" => (
                 Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
-                FunctionIndex::GetKeyFuncIndex.into(),
+                FunctionIndex::GetKeyFuncIndex,
             ),
-            "has_key" => FuncInstance::alloc_host(
+            "has_key" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::HasKeyFuncIndex.into(),
+                FunctionIndex::HasKeyFuncIndex,
             ),
-            "put_key" => FuncInstance::alloc_host(
+            "put_key" => (
                 Signature::new(&[ValueType::I32; 4][..], None),
-                FunctionIndex::PutKeyFuncIndex.into(),
+                FunctionIndex::PutKeyFuncIndex,
             ),
-            "gas" => FuncInstance::alloc_host(
+            "gas" => (
                 Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GasFuncIndex.into(),
+                FunctionIndex::GasFuncIndex,
             ),
-            "is_valid_uref" => FuncInstance::alloc_host(
+            "is_valid_uref" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::IsValidURefFnIndex.into(),
+                FunctionIndex::IsValidURefFnIndex,
             ),
-            "revert" => FuncInstance::alloc_host(
+            "revert" => (
                 Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::RevertFuncIndex.into(),
+                FunctionIndex::RevertFuncIndex,
             ),
-            "add_associated_key" => FuncInstance::alloc_host(
+            "add_associated_key" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::AddAssociatedKeyFuncIndex.into(),
+                FunctionIndex::AddAssociatedKeyFuncIndex,
             ),
-            "remove_associated_key" => FuncInstance::alloc_host(
+            "remove_associated_key" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::RemoveAssociatedKeyFuncIndex.into(),
+                FunctionIndex::RemoveAssociatedKeyFuncIndex,
             ),
-            "update_associated_key" => FuncInstance::alloc_host(
+            "update_associated_key" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::UpdateAssociatedKeyFuncIndex.into(),
+                FunctionIndex::UpdateAssociatedKeyFuncIndex,
             ),
-            "set_action_threshold" => FuncInstance::alloc_host(
+            "set_action_threshold" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::SetActionThresholdFuncIndex.into(),
+                FunctionIndex::SetActionThresholdFuncIndex,
             ),
-            "remove_key" => FuncInstance::alloc_host(
+            "remove_key" => (
                 Signature::new(&[ValueType::I32; 2][..], None),
-                FunctionIndex::RemoveKeyFuncIndex.into(),
+                FunctionIndex::RemoveKeyFuncIndex,
             ),
-            "get_caller" => FuncInstance::alloc_host(
+            "get_caller" => (
                 Signature::new(&[ValueType::I32; 1][..], Some(ValueType::I32)),
-                FunctionIndex::GetCallerIndex.into(),
+                FunctionIndex::GetCallerIndex,
             ),
-            "get_blocktime" => FuncInstance::alloc_host(
+            "get_blocktime" => (
                 Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GetBlocktimeIndex.into(),
+                FunctionIndex::GetBlocktimeIndex,
             ),
-            "create_purse" => FuncInstance::alloc_host(
+            "create_purse" => (
                 Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
-                FunctionIndex::CreatePurseIndex.into(),
+                FunctionIndex::CreatePurseIndex,
             ),
-            "transfer_to_account" => FuncInstance::alloc_host(
+            "transfer_to_account" => (
                 Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::TransferToAccountIndex.into(),
+                FunctionIndex::TransferToAccountIndex,
             ),
-            "transfer_from_purse_to_account" => FuncInstance::alloc_host(
+            "transfer_from_purse_to_account" => (
                 Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
-                FunctionIndex::TransferFromPurseToAccountIndex.into(),
+                FunctionIndex::TransferFromPurseToAccountIndex,
             ),
-            "transfer_from_purse_to_purse" => FuncInstance::alloc_host(
+            "transfer_from_purse_to_purse" => (
                 Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
-                FunctionIndex::TransferFromPurseToPurseIndex.into(),
+                FunctionIndex::TransferFromPurseToPurseIndex,
             ),
-            "get_balance" => FuncInstance::alloc_host(
+            "get_balance" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::GetBalanceIndex.into(),
+                FunctionIndex::GetBalanceIndex,
             ),
-            "get_phase" => FuncInstance::alloc_host(
+            "get_phase" => (
                 Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GetPhaseIndex.into(),
+                FunctionIndex::GetPhaseIndex,
             ),
-            "get_system_contract" => FuncInstance::alloc_host(
+            "get_system_contract" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::GetSystemContractIndex.into(),
+                FunctionIndex::GetSystemContractIndex,
             ),
-            "get_main_purse" => FuncInstance::alloc_host(
+            "get_main_purse" => (
                 Signature::new(&[ValueType::I32; 1][..], None),
-                FunctionIndex::GetMainPurseIndex.into(),
+                FunctionIndex::GetMainPurseIndex,
             ),
-            "read_host_buffer" => FuncInstance::alloc_host(
+            "read_host_buffer" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::ReadHostBufferIndex.into(),
+                FunctionIndex::ReadHostBufferIndex,
             ),
-            "create_contract_package_at_hash" => FuncInstance::alloc_host(
+            "create_contract_package_at_hash" => (
                 Signature::new(&[ValueType::I32; 2][..], None),
-                FunctionIndex::CreateContractPackageAtHash.into(),
+                FunctionIndex::CreateContractPackageAtHash,
             ),
-            "create_contract_user_group" => FuncInstance::alloc_host(
+            "create_contract_user_group" => (
                 Signature::new(&[ValueType::I32; 8][..], Some(ValueType::I32)),
-                FunctionIndex::CreateContractUserGroup.into(),
+                FunctionIndex::CreateContractUserGroup,
             ),
-            "add_contract_version" => FuncInstance::alloc_host(
+            "add_contract_version" => (
                 Signature::new(&[ValueType::I32; 10][..], Some(ValueType::I32)),
-                FunctionIndex::AddContractVersion.into(),
+                FunctionIndex::AddContractVersion,
             ),
-            "disable_contract_version" => FuncInstance::alloc_host(
+            "disable_contract_version" => (
                 Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::DisableContractVersion.into(),
+                FunctionIndex::DisableContractVersion,
             ),
-            "call_contract" => FuncInstance::alloc_host(
+            "call_contract" => (
                 Signature::new(&[ValueType::I32; 7][..], Some(ValueType::I32)),
-                FunctionIndex::CallContractFuncIndex.into(),
+                FunctionIndex::CallContractFuncIndex,
             ),
-            "call_versioned_contract" => FuncInstance::alloc_host(
+            "call_versioned_contract" => (
                 Signature::new(&[ValueType::I32; 9][..], Some(ValueType::I32)),
-                FunctionIndex::CallVersionedContract.into(),
+                FunctionIndex::CallVersionedContract,
             ),
-            "get_named_arg_size" => FuncInstance::alloc_host(
+            "get_named_arg_size" => (
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
-                FunctionIndex::GetRuntimeArgsizeIndex.into(),
+                FunctionIndex::GetRuntimeArgsizeIndex,
             ),
-            "get_named_arg" => FuncInstance::alloc_host(
+            "get_named_arg" => (
                 Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::GetRuntimeArgIndex.into(),
+                FunctionIndex::GetRuntimeArgIndex,
             ),
-            "remove_contract_user_group" => FuncInstance::alloc_host(
+            "remove_contract_user_group" => (
                 Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
-                FunctionIndex::RemoveContractUserGroupIndex.into(),
+                FunctionIndex::RemoveContractUserGroupIndex,
             ),
-            "provision_contract_user_group_uref" => FuncInstance::alloc_host(
+            "provision_contract_user_group_uref" => (
                 Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
-                FunctionIndex::ExtendContractUserGroupURefsIndex.into(),
+                FunctionIndex::ExtendContractUserGroupURefsIndex,
+            ),
+            "remove_contract_user_group_urefs" => (
+                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
+                FunctionIndex::RemoveContractUserGroupURefsIndex,
+            ),
+            "blake2b" => (
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::Blake2bFuncIndex,
+            ),
+            "verify_ed25519" => (
+                Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
+                FunctionIndex::VerifyEd25519Index,
             ),
-            "remove_contract_user_group_urefs" => FuncInstance::alloc_host(
+            "remove" => (
+                Signature::new(&[ValueType::I32; 2][..], None),
+                FunctionIndex::RemoveFuncIndex,
+            ),
+            "get_deploy_hash" => (
+                Signature::new(&[ValueType::I32; 1][..], None),
+                FunctionIndex::GetDeployHashIndex,
+            ),
+            "transfer_to_account_with_id" => (
                 Signature::new(&[ValueType::I32; 6][..], Some(ValueType::I32)),
-                FunctionIndex::RemoveContractUserGroupURefsIndex.into(),
+                FunctionIndex::TransferToAccountWithIdIndex,
+            ),
+            "transfer_from_purse_to_account_with_id" => (
+                Signature::new(&[ValueType::I32; 8][..], Some(ValueType::I32)),
+                FunctionIndex::TransferFromPurseToAccountWithIdIndex,
+            ),
+            "new_hash_address" => (
+                Signature::new(&[ValueType::I32; 1][..], None),
+                FunctionIndex::NewHashAddressIndex,
+            ),
+            "create_named_purse" => (
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::CreateNamedPurseIndex,
+            ),
+            "emit_event" => (
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::EmitEventIndex,
+            ),
+            "get_action_threshold" => (
+                Signature::new(&[ValueType::I32; 1][..], Some(ValueType::I32)),
+                FunctionIndex::GetActionThresholdIndex,
+            ),
+            "list_associated_keys" => (
+                Signature::new(&[ValueType::I32; 1][..], Some(ValueType::I32)),
+                FunctionIndex::ListAssociatedKeysIndex,
+            ),
+            "list_contract_user_groups" => (
+                Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
+                FunctionIndex::ListContractUserGroupsIndex,
+            ),
+            "list_authorization_keys" => (
+                Signature::new(&[ValueType::I32; 1][..], Some(ValueType::I32)),
+                FunctionIndex::ListAuthorizationKeysIndex,
+            ),
+            "get_system_contract_by_name" => (
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::GetSystemContractByNameIndex,
+            ),
+            "can_authorize" => (
+                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
+                FunctionIndex::CanAuthorizeIndex,
             ),
             #[cfg(feature = "test-support")]
-            "print" => FuncInstance::alloc_host(
+            "print" => (
                 Signature::new(&[ValueType::I32; 2][..], None),
-                FunctionIndex::PrintIndex.into(),
+                FunctionIndex::PrintIndex,
             ),
             _ => {
                 return Err(InterpreterError::Function(format!(
@@ -216,7 +285,17 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 )));
             }
         };
-        Ok(func_ref)
+
+        let required_version = function_index.min_protocol_version();
+        if self.protocol_version < required_version {
+            let error = Error::UnsupportedHostFunction {
+                name: field_name.to_string(),
+                required_version,
+            };
+            return Err(InterpreterError::Host(Box::new(error)));
+        }
+
+        Ok(FuncInstance::alloc_host(signature, function_index.into()))
     }
 
     fn resolve_memory(
@@ -10,7 +10,10 @@ use types::{
     ContractHash, ContractPackageHash, ContractVersion, Group, Key, TransferredTo, URef, U512,
 };
 
-use engine_shared::{gas::Gas, stored_value::StoredValue};
+use engine_shared::{
+    gas::{CostCategory, Gas},
+    stored_value::StoredValue,
+};
 use engine_storage::global_state::StateReader;
 
 use super::{args::Args, scoped_instrumenter::ScopedInstrumenter, Error, Runtime};
@@ -27,13 +30,21 @@ where
         args: RuntimeArgs,
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
-        let mut scoped_instrumenter = ScopedInstrumenter::new(func);
+        if func != FunctionIndex::GasFuncIndex {
+            self.last_host_function = Some(func);
+        }
+        let mut scoped_instrumenter =
+            ScopedInstrumenter::new(func, self.host_function_profile.clone());
+        self.charge_host_function_call(func)?;
+        self.check_phase_policy(func)?;
+        self.check_protocol_version_policy(func)?;
         match func {
             FunctionIndex::ReadFuncIndex => {
                 // args(0) = pointer to key in Wasm memory
                 // args(1) = size of key in Wasm memory
                 // args(2) = pointer to output size (output param)
                 let (key_ptr, key_size, output_size_ptr) = Args::parse(args)?;
+                self.charge_host_function_bytes(func, key_size)?;
                 let ret = self.read(key_ptr, key_size, output_size_ptr)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
@@ -44,6 +55,7 @@ where
                 // args(2) = pointer to output size (output param)
                 let (key_ptr, key_size, output_size_ptr): (_, u32, _) = Args::parse(args)?;
                 scoped_instrumenter.add_property("key_size", key_size);
+                self.charge_host_function_bytes(func, key_size)?;
                 let ret = self.read_local(key_ptr, key_size, output_size_ptr)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
@@ -60,6 +72,22 @@ where
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
+            FunctionIndex::LoadNamedKeysChunkFuncIndex => {
+                // args(0) = index of the first named key to load
+                // args(1) = maximum number of named keys to load
+                // args(2) = pointer to total amount of keys (output)
+                // args(3) = pointer to amount of serialized bytes (output)
+                let (start_index, max_count, total_keys_ptr, result_size_ptr) = Args::parse(args)?;
+                let ret = self.load_named_keys_chunk(
+                    start_index,
+                    max_count,
+                    total_keys_ptr,
+                    result_size_ptr,
+                    &mut scoped_instrumenter,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
             FunctionIndex::WriteFuncIndex => {
                 // args(0) = pointer to key in Wasm memory
                 // args(1) = size of key
@@ -67,6 +95,7 @@ where
                 // args(3) = size of value
                 let (key_ptr, key_size, value_ptr, value_size): (_, _, _, u32) = Args::parse(args)?;
                 scoped_instrumenter.add_property("value_size", value_size);
+                self.charge_host_function_bytes(func, value_size)?;
                 self.write(key_ptr, key_size, value_ptr, value_size)?;
                 Ok(None)
             }
@@ -80,6 +109,7 @@ where
                     Args::parse(args)?;
                 scoped_instrumenter.add_property("key_bytes_size", key_bytes_size);
                 scoped_instrumenter.add_property("value_size", value_size);
+                self.charge_host_function_bytes(func, key_bytes_size.saturating_add(value_size))?;
                 self.write_local(key_bytes_ptr, key_bytes_size, value_ptr, value_size)?;
                 Ok(None)
             }
@@ -90,6 +120,7 @@ where
                 // args(2) = pointer to value
                 // args(3) = size of value
                 let (key_ptr, key_size, value_ptr, value_size) = Args::parse(args)?;
+                self.charge_host_function_bytes(func, value_size)?;
                 self.add(key_ptr, key_size, value_ptr, value_size)?;
                 Ok(None)
             }
@@ -100,6 +131,7 @@ where
                 // args(2) = size of initial value
                 let (uref_ptr, value_ptr, value_size): (_, _, u32) = Args::parse(args)?;
                 scoped_instrumenter.add_property("value_size", value_size);
+                self.charge_host_function_bytes(func, value_size)?;
                 self.new_uref(uref_ptr, value_ptr, value_size)?;
                 Ok(None)
             }
@@ -109,6 +141,7 @@ where
                 // args(1) = size of value
                 let (value_ptr, value_size): (_, u32) = Args::parse(args)?;
                 scoped_instrumenter.add_property("value_size", value_size);
+                self.charge_host_function_bytes(func, value_size)?;
                 Err(self.ret(value_ptr, value_size as usize, &mut scoped_instrumenter))
             }
 
@@ -126,6 +159,7 @@ where
                     u32,
                 ) = Args::parse(args)?;
                 scoped_instrumenter.add_property("name_size", name_size);
+                self.charge_host_function_bytes(func, name_size)?;
                 let ret = self.load_key(
                     name_ptr,
                     name_size,
@@ -141,6 +175,7 @@ where
                 // args(1) = size of key name
                 let (name_ptr, name_size): (_, u32) = Args::parse(args)?;
                 scoped_instrumenter.add_property("name_size", name_size);
+                self.charge_host_function_bytes(func, name_size)?;
                 let result = self.has_key(name_ptr, name_size)?;
                 Ok(Some(RuntimeValue::I32(result)))
             }
@@ -152,6 +187,7 @@ where
                 // args(3) = size of key
                 let (name_ptr, name_size, key_ptr, key_size): (_, u32, _, _) = Args::parse(args)?;
                 scoped_instrumenter.add_property("name_size", name_size);
+                self.charge_host_function_bytes(func, name_size)?;
                 self.put_key(name_ptr, name_size, key_ptr, key_size)?;
                 Ok(None)
             }
@@ -161,6 +197,7 @@ where
                 // args(1) = size of key name
                 let (name_ptr, name_size): (_, u32) = Args::parse(args)?;
                 scoped_instrumenter.add_property("name_size", name_size);
+                self.charge_host_function_bytes(func, name_size)?;
                 self.remove_key(name_ptr, name_size)?;
                 Ok(None)
             }
@@ -181,7 +218,7 @@ where
 
             FunctionIndex::GasFuncIndex => {
                 let gas_arg: u32 = Args::parse(args)?;
-                self.gas(Gas::new(gas_arg.into()))?;
+                self.gas(CostCategory::Opcode, Gas::new(gas_arg.into()))?;
                 Ok(None)
             }
 
@@ -275,7 +312,7 @@ where
                     let bytes = self.bytes_from_mem(amount_ptr, amount_size as usize)?;
                     bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
                 };
-                let ret = self.transfer_to_account(account_hash, amount)?;
+                let ret = self.transfer_to_account(account_hash, amount, None)?;
                 Ok(Some(RuntimeValue::I32(TransferredTo::i32_from(ret))))
             }
 
@@ -307,8 +344,12 @@ where
                     let bytes = self.bytes_from_mem(amount_ptr, amount_size as usize)?;
                     bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
                 };
-                let ret =
-                    self.transfer_from_purse_to_account(source_purse, account_hash, amount)?;
+                let ret = self.transfer_from_purse_to_account_with_id(
+                    source_purse,
+                    account_hash,
+                    amount,
+                    None,
+                )?;
                 Ok(Some(RuntimeValue::I32(TransferredTo::i32_from(ret))))
             }
 
@@ -368,6 +409,7 @@ where
                 // args(0) = pointer to Wasm memory where to write size.
                 let (dest_ptr, dest_size, bytes_written_ptr): (_, u32, _) = Args::parse(args)?;
                 scoped_instrumenter.add_property("dest_size", dest_size);
+                self.charge_host_function_bytes(func, dest_size)?;
                 let ret = self.read_host_buffer(dest_ptr, dest_size as usize, bytes_written_ptr)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
@@ -404,6 +446,10 @@ where
                 scoped_instrumenter
                     .add_property("existing_urefs_size", existing_urefs_size.to_string());
                 scoped_instrumenter.add_property("label_size", label_size.to_string());
+                self.charge_host_function_bytes(
+                    func,
+                    existing_urefs_size.saturating_add(label_size),
+                )?;
 
                 let contract_package_hash: ContractPackageHash =
                     self.t_from_mem(package_key_ptr, package_key_size)?;
@@ -447,6 +493,10 @@ where
                 scoped_instrumenter
                     .add_property("entry_points_size", entry_points_size.to_string());
                 scoped_instrumenter.add_property("named_keys_size", named_keys_size.to_string());
+                self.charge_host_function_bytes(
+                    func,
+                    entry_points_size.saturating_add(named_keys_size),
+                )?;
 
                 let contract_package_hash: ContractPackageHash =
                     self.t_from_mem(contract_package_hash_ptr, contract_package_hash_size)?;
@@ -501,6 +551,7 @@ where
                 scoped_instrumenter
                     .add_property("entry_point_name_size", entry_point_name_size.to_string());
                 scoped_instrumenter.add_property("args_size", args_size.to_string());
+                self.charge_host_function_bytes(func, args_size)?;
 
                 let contract_hash: ContractHash =
                     self.t_from_mem(contract_hash_ptr, contract_hash_size)?;
@@ -546,6 +597,7 @@ where
                 scoped_instrumenter
                     .add_property("entry_point_name_size", entry_point_name_size.to_string());
                 scoped_instrumenter.add_property("args_size", args_size.to_string());
+                self.charge_host_function_bytes(func, args_size)?;
 
                 let contract_package_hash: ContractPackageHash =
                     self.t_from_mem(contract_package_hash_ptr, contract_package_hash_size)?;
@@ -569,10 +621,233 @@ where
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
+            FunctionIndex::Blake2bFuncIndex => {
+                // args(0) = pointer to input bytes in Wasm memory
+                // args(1) = size of input bytes
+                // args(2) = pointer to output buffer for the digest
+                // args(3) = size of output buffer
+                let (in_ptr, in_size, out_ptr, out_size): (_, u32, _, _) = Args::parse(args)?;
+                scoped_instrumenter.add_property("in_size", in_size);
+                let ret = self.blake2b(in_ptr, in_size, out_ptr, out_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::VerifyEd25519Index => {
+                // args(0) = pointer to public key in Wasm memory
+                // args(1) = size of public key
+                // args(2) = pointer to signature in Wasm memory
+                // args(3) = size of signature
+                // args(4) = pointer to message in Wasm memory
+                // args(5) = size of message
+                let (
+                    public_key_ptr,
+                    public_key_size,
+                    signature_ptr,
+                    signature_size,
+                    message_ptr,
+                    message_size,
+                ): (_, _, _, _, _, u32) = Args::parse(args)?;
+                scoped_instrumenter.add_property("message_size", message_size);
+                let result = self.verify_ed25519(
+                    public_key_ptr,
+                    public_key_size,
+                    signature_ptr,
+                    signature_size,
+                    message_ptr,
+                    message_size,
+                )?;
+                Ok(Some(RuntimeValue::I32(i32::from(result))))
+            }
+
+            FunctionIndex::RemoveFuncIndex => {
+                // args(0) = pointer to key in Wasm memory
+                // args(1) = size of key
+                let (key_ptr, key_size) = Args::parse(args)?;
+                self.remove(key_ptr, key_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::GetDeployHashIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let dest_ptr = Args::parse(args)?;
+                self.get_deploy_hash(dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::TransferToAccountWithIdIndex => {
+                // args(0) = pointer to array of bytes of an account hash
+                // args(1) = length of array of bytes of an account hash
+                // args(2) = pointer to array of bytes of an amount
+                // args(3) = length of array of bytes of an amount
+                // args(4) = pointer to array of bytes of an optional transfer id
+                // args(5) = length of array of bytes of an optional transfer id
+                let (key_ptr, key_size, amount_ptr, amount_size, id_ptr, id_size): (
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                    u32,
+                ) = Args::parse(args)?;
+                let account_hash: AccountHash = {
+                    let bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
+                    bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+                };
+                let amount: U512 = {
+                    let bytes = self.bytes_from_mem(amount_ptr, amount_size as usize)?;
+                    bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+                };
+                let id: Option<u64> = {
+                    let bytes = self.bytes_from_mem(id_ptr, id_size as usize)?;
+                    bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+                };
+                let ret = self.transfer_to_account(account_hash, amount, id)?;
+                Ok(Some(RuntimeValue::I32(TransferredTo::i32_from(ret))))
+            }
+
+            FunctionIndex::TransferFromPurseToAccountWithIdIndex => {
+                // args(0) = pointer to array of bytes in Wasm memory of a source purse
+                // args(1) = length of array of bytes in Wasm memory of a source purse
+                // args(2) = pointer to array of bytes in Wasm memory of an account hash
+                // args(3) = length of array of bytes in Wasm memory of an account hash
+                // args(4) = pointer to array of bytes in Wasm memory of an amount
+                // args(5) = length of array of bytes in Wasm memory of an amount
+                // args(6) = pointer to array of bytes in Wasm memory of an optional transfer id
+                // args(7) = length of array of bytes in Wasm memory of an optional transfer id
+                let (
+                    source_ptr,
+                    source_size,
+                    key_ptr,
+                    key_size,
+                    amount_ptr,
+                    amount_size,
+                    id_ptr,
+                    id_size,
+                ): (u32, u32, u32, u32, u32, u32, u32, u32) = Args::parse(args)?;
+
+                let source_purse = {
+                    let bytes = self.bytes_from_mem(source_ptr, source_size as usize)?;
+                    bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+                };
+                let account_hash: AccountHash = {
+                    let bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
+                    bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+                };
+                let amount: U512 = {
+                    let bytes = self.bytes_from_mem(amount_ptr, amount_size as usize)?;
+                    bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+                };
+                let id: Option<u64> = {
+                    let bytes = self.bytes_from_mem(id_ptr, id_size as usize)?;
+                    bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+                };
+                let ret = self.transfer_from_purse_to_account_with_id(
+                    source_purse,
+                    account_hash,
+                    amount,
+                    id,
+                )?;
+                Ok(Some(RuntimeValue::I32(TransferredTo::i32_from(ret))))
+            }
+
+            FunctionIndex::NewHashAddressIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let dest_ptr = Args::parse(args)?;
+                self.new_hash_address(dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::CreateNamedPurseIndex => {
+                // args(0) = pointer to name in Wasm memory
+                // args(1) = size of name in Wasm memory
+                // args(2) = pointer to array for return value
+                // args(3) = length of array for return value
+                let (name_ptr, name_size, dest_ptr, dest_size): (u32, u32, u32, u32) =
+                    Args::parse(args)?;
+                let ret = self.create_named_purse(name_ptr, name_size, dest_ptr, dest_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::EmitEventIndex => {
+                // args(0) = pointer to topic in Wasm memory
+                // args(1) = size of topic in Wasm memory
+                // args(2) = pointer to payload in Wasm memory
+                // args(3) = size of payload in Wasm memory
+                let (topic_ptr, topic_size, payload_ptr, payload_size): (u32, u32, u32, u32) =
+                    Args::parse(args)?;
+                scoped_instrumenter.add_property("topic_size", topic_size);
+                scoped_instrumenter.add_property("payload_size", payload_size);
+                self.charge_host_function_bytes(func, topic_size.saturating_add(payload_size))?;
+                let ret = self.emit_event(topic_ptr, topic_size, payload_ptr, payload_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetActionThresholdIndex => {
+                // args(0) = action type
+                let action_type = Args::parse(args)?;
+                let threshold = self.get_action_threshold(action_type)?;
+                Ok(Some(RuntimeValue::I32(threshold)))
+            }
+
+            FunctionIndex::ListAssociatedKeysIndex => {
+                // args(0) = pointer where a size of serialized bytes will be stored
+                let output_size_ptr = Args::parse(args)?;
+                let ret = self.list_associated_keys(output_size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::ListContractUserGroupsIndex => {
+                // args(0) = pointer to package key in wasm memory
+                // args(1) = size of package key in wasm memory
+                // args(2) = pointer to package access key in wasm memory
+                // args(3) = size of package access key in wasm memory
+                // args(4) = pointer where a size of serialized bytes will be stored
+                let (package_ptr, package_size, access_key_ptr, access_key_size, output_size_ptr) =
+                    Args::parse(args)?;
+                let ret = self.list_contract_user_groups(
+                    package_ptr,
+                    package_size,
+                    access_key_ptr,
+                    access_key_size,
+                    output_size_ptr,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::ListAuthorizationKeysIndex => {
+                // args(0) = pointer where a size of serialized bytes will be stored
+                let output_size_ptr = Args::parse(args)?;
+                let ret = self.list_authorization_keys(output_size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetSystemContractByNameIndex => {
+                // args(0) = pointer to the serialized name in Wasm memory
+                // args(1) = size of the serialized name
+                // args(2) = dest pointer for storing serialized result
+                // args(3) = dest pointer size
+                let (name_ptr, name_size, dest_ptr, dest_size) = Args::parse(args)?;
+                let ret =
+                    self.get_system_contract_by_name(name_ptr, name_size, dest_ptr, dest_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::CanAuthorizeIndex => {
+                // args(0) = pointer to the serialized list of account hashes in Wasm memory
+                // args(1) = size of the serialized list
+                // args(2) = action type
+                let (keys_ptr, keys_size, action_type): (_, u32, _) = Args::parse(args)?;
+                scoped_instrumenter.add_property("keys_size", keys_size);
+                self.charge_host_function_bytes(func, keys_size)?;
+                let result = self.can_authorize(keys_ptr, keys_size, action_type)?;
+                Ok(Some(RuntimeValue::I32(result)))
+            }
+
             #[cfg(feature = "test-support")]
             FunctionIndex::PrintIndex => {
                 let (text_ptr, text_size): (_, u32) = Args::parse(args)?;
                 scoped_instrumenter.add_property("text_size", text_size);
+                self.charge_host_function_bytes(func, text_size)?;
                 self.print(text_ptr, text_size)?;
                 Ok(None)
             }
@@ -596,6 +871,7 @@ where
                     Args::parse(args)?;
                 scoped_instrumenter.add_property("name_size", name_size.to_string());
                 scoped_instrumenter.add_property("dest_size", dest_size.to_string());
+                self.charge_host_function_bytes(func, dest_size)?;
                 let ret =
                     self.get_named_arg(name_ptr, name_size as usize, dest_ptr, dest_size as usize)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
@@ -5,7 +5,7 @@ use types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system_contract_errors::mint::Error,
-    CLTyped, CLValue, Key, URef,
+    CLTyped, CLValue, Key, URef, U512,
 };
 
 use crate::{execution, runtime_context::RuntimeContext};
@@ -19,6 +19,10 @@ where
         self.get_caller()
     }
 
+    fn get_key(&self, name: &str) -> Option<Key> {
+        self.named_keys_get(name).cloned()
+    }
+
     fn put_key(&mut self, name: &str, key: Key) {
         // TODO: update RuntimeProvider to better handle errors
         self.put_key(name.to_string(), key).expect("should put key")
@@ -82,6 +86,14 @@ where
         self.add_gs(Key::URef(uref), StoredValue::CLValue(cl_value))
             .map_err(|_| Error::Storage)
     }
+
+    fn sub(&mut self, uref: URef, amount: U512) -> Result<(), Error> {
+        self.sub_gs(Key::URef(uref), amount)
+            .map_err(|error| match error {
+                execution::Error::TypeMismatch(_) => Error::InsufficientFunds,
+                _ => Error::Storage,
+            })
+    }
 }
 
 impl<'a, R> Mint for RuntimeContext<'a, R>
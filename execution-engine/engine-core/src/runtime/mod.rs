@@ -6,23 +6,32 @@ mod scoped_instrumenter;
 mod standard_payment_internal;
 
 use std::{
+    cell::RefCell,
     cmp,
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     convert::TryFrom,
     iter::IntoIterator,
+    rc::Rc,
 };
 
+use ed25519_dalek::{PublicKey, Signature};
 use itertools::Itertools;
 use parity_wasm::elements::Module;
 use wasmi::{ImportsBuilder, MemoryRef, ModuleInstance, ModuleRef, Trap, TrapKind};
 
 use ::mint::Mint;
-use engine_shared::{account::Account, gas::Gas, stored_value::StoredValue};
+use engine_shared::{
+    account::Account,
+    gas::{CostBreakdown, CostCategory, Gas},
+    newtypes::Blake2bHash,
+    stored_value::StoredValue,
+};
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
+use engine_wasm_prep::wasm_costs::HostFunctionCost;
 use proof_of_stake::ProofOfStake;
 use standard_payment::StandardPayment;
 use types::{
-    account::{AccountHash, ActionType, Weight},
+    account::{AccountHash, ActionType, AddKeyFailure, Weight, MAX_ASSOCIATED_KEYS},
     bytesrepr::{self, FromBytes, ToBytes},
     contracts::{
         self, Contract, ContractPackage, EntryPoint, EntryPointAccess, EntryPoints, Group,
@@ -30,20 +39,83 @@ use types::{
     runtime_args, system_contract_errors,
     system_contract_errors::mint,
     AccessRights, ApiError, CLType, CLTyped, CLValue, ContractHash, ContractPackageHash,
-    ContractVersionKey, ContractWasm, EntryPointType, Key, ProtocolVersion, RuntimeArgs,
-    SystemContractType, TransferResult, TransferredTo, URef, U128, U256, U512,
+    ContractVersionKey, ContractWasm, EntryPointType, Key, Phase, ProtocolVersion, RuntimeArgs,
+    SystemContractRegistry, SystemContractType, TransferRecord, TransferResult, TransferredTo,
+    URef, URefAddr, BLAKE2B_DIGEST_LENGTH, SYSTEM_CONTRACT_REGISTRY_ADDR, U128, U256, U512,
+    UREF_SERIALIZED_LENGTH,
 };
 
 use crate::{
-    engine_state::{system_contract_cache::SystemContractCache, EngineConfig},
-    execution::Error,
-    resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
+    engine_state::{
+        execution_effect::ContractEvent, system_contract_cache::SystemContractCache, EngineConfig,
+    },
+    execution::{Error, HostFunctionProfile},
+    resolvers::{
+        create_module_resolver, memory_resolver::MemoryResolver, v1_function_index::FunctionIndex,
+    },
     runtime_context::{self, RuntimeContext},
     Address,
 };
 use contracts::{ContractVersion, ContractVersions, DisabledVersions, Groups, NamedKeys};
 use scoped_instrumenter::ScopedInstrumenter;
 
+#[cfg(feature = "test-support")]
+use crate::execution::GasExhaustionContext;
+
+#[cfg(feature = "test-support")]
+thread_local! {
+    /// Text printed by contracts via the `print` host function on this thread, not yet taken.
+    static PRINTED_MESSAGES: std::cell::RefCell<Vec<String>> = Default::default();
+}
+
+/// Returns the text printed by contracts via the `print` host function on this thread since the
+/// last call, in the order it was printed.
+#[cfg(feature = "test-support")]
+pub fn take_printed_messages() -> Vec<String> {
+    PRINTED_MESSAGES.with(|messages| messages.replace(Vec::new()))
+}
+
+#[cfg(feature = "test-support")]
+thread_local! {
+    /// The context of the last execution on this thread which ran out of gas, not yet taken.
+    static GAS_EXHAUSTION_CONTEXT: std::cell::RefCell<Option<GasExhaustionContext>> =
+        Default::default();
+}
+
+/// Returns the context of the last execution on this thread which ran out of gas since the last
+/// call, if any.
+#[cfg(feature = "test-support")]
+pub fn take_gas_exhaustion_context() -> Option<GasExhaustionContext> {
+    GAS_EXHAUSTION_CONTEXT.with(|context| context.replace(None))
+}
+
+/// The amount of gas charged per byte of input hashed by the `blake2b` host function.
+const BLAKE2B_GAS_PER_BYTE: u32 = 1;
+
+/// The flat amount of gas charged by the `verify_ed25519` host function.
+const VERIFY_ED25519_GAS_BASE: u32 = 10_000;
+
+/// The amount of gas charged per byte of message verified by the `verify_ed25519` host function.
+const VERIFY_ED25519_GAS_PER_BYTE: u32 = 1;
+
+/// Host functions which may not be called while executing payment code or finalizing payment,
+/// i.e. before the account has been charged for the deploy.
+const PAYMENT_RESTRICTED_FUNCTIONS: [FunctionIndex; 5] = [
+    FunctionIndex::AddAssociatedKeyFuncIndex,
+    FunctionIndex::RemoveAssociatedKeyFuncIndex,
+    FunctionIndex::UpdateAssociatedKeyFuncIndex,
+    FunctionIndex::SetActionThresholdFuncIndex,
+    FunctionIndex::EmitEventIndex,
+];
+
+/// Returns `true` if the given phase is one in which [`PAYMENT_RESTRICTED_FUNCTIONS`] apply.
+fn is_payment_phase(phase: Phase) -> bool {
+    match phase {
+        Phase::Payment | Phase::FinalizePayment => true,
+        Phase::System | Phase::Session => false,
+    }
+}
+
 pub struct Runtime<'a, R> {
     system_contract_cache: SystemContractCache,
     config: EngineConfig,
@@ -51,6 +123,12 @@ pub struct Runtime<'a, R> {
     module: Module,
     host_buffer: Option<CLValue>,
     context: RuntimeContext<'a, R>,
+    cost_breakdown: CostBreakdown,
+    /// Shared with the runtimes of contracts called from this one, so that it covers the whole
+    /// execution.  `None` unless [`EngineConfig::host_function_profiling`] is set.
+    host_function_profile: Option<Rc<RefCell<HostFunctionProfile>>>,
+    /// The last host function invoked by this runtime, ignoring gas charges for Wasm opcodes.
+    last_host_function: Option<FunctionIndex>,
 }
 
 /// Rename function called `name` in the `module` to `call`.
@@ -137,8 +215,13 @@ pub fn extract_access_rights_from_keys<I: IntoIterator<Item = Key>>(
         .collect()
 }
 
+/// Returns the `URef`s contained in `cl_value`, including those directly inside an option, list,
+/// fixed list of up to 32 elements, result, map or tuple.
+///
+/// Only one level of nesting is looked into: a `URef` inside e.g. a list of options isn't returned.
+/// Use [`extract_nested_urefs`] to find `URef`s at any depth.
 #[allow(clippy::cognitive_complexity)]
-fn extract_urefs(cl_value: &CLValue) -> Result<Vec<URef>, Error> {
+pub(crate) fn extract_urefs(cl_value: &CLValue) -> Result<Vec<URef>, Error> {
     match cl_value.cl_type() {
         CLType::Bool
         | CLType::I32
@@ -1344,6 +1427,110 @@ fn extract_urefs(cl_value: &CLValue) -> Result<Vec<URef>, Error> {
     }
 }
 
+/// Returns every `URef` in `cl_value`, however deeply it is nested, in the order they are
+/// serialized.
+///
+/// Unlike [`extract_urefs`], which only looks one level into options, lists, results, maps and
+/// pairs, this walks the whole of the value's serialized form as described by its `CLType`.  A
+/// value of type `Any` is opaque: at the top level it is taken to hold no `URef`s, but nested
+/// inside another type its extent can't be known, so the rest of the value can't be walked and an
+/// error is returned rather than leaving any `URef`s after it unchecked.
+pub(crate) fn extract_nested_urefs(cl_value: &CLValue) -> Result<Vec<URef>, Error> {
+    if *cl_value.cl_type() == CLType::Any {
+        return Ok(vec![]);
+    }
+    let mut urefs = vec![];
+    let rem = collect_urefs(cl_value.cl_type(), cl_value.inner_bytes(), &mut urefs)?;
+    if !rem.is_empty() {
+        return Err(bytesrepr::Error::LeftOverBytes.into());
+    }
+    Ok(urefs)
+}
+
+/// Appends the `URef`s in the value of type `cl_type` at the start of `bytes` to `urefs`, returning
+/// the bytes which follow the value.
+fn collect_urefs<'a>(
+    cl_type: &CLType,
+    bytes: &'a [u8],
+    urefs: &mut Vec<URef>,
+) -> Result<&'a [u8], bytesrepr::Error> {
+    match cl_type {
+        CLType::Bool => Ok(bool::from_bytes(bytes)?.1),
+        CLType::I32 => Ok(i32::from_bytes(bytes)?.1),
+        CLType::I64 => Ok(i64::from_bytes(bytes)?.1),
+        CLType::U8 => Ok(u8::from_bytes(bytes)?.1),
+        CLType::U32 => Ok(u32::from_bytes(bytes)?.1),
+        CLType::U64 => Ok(u64::from_bytes(bytes)?.1),
+        CLType::U128 => Ok(U128::from_bytes(bytes)?.1),
+        CLType::U256 => Ok(U256::from_bytes(bytes)?.1),
+        CLType::U512 => Ok(U512::from_bytes(bytes)?.1),
+        CLType::Unit => Ok(bytes),
+        CLType::String => Ok(String::from_bytes(bytes)?.1),
+        CLType::Key => {
+            let (key, rem) = Key::from_bytes(bytes)?;
+            urefs.extend(key.into_uref());
+            Ok(rem)
+        }
+        CLType::URef => {
+            let (uref, rem) = URef::from_bytes(bytes)?;
+            urefs.push(uref);
+            Ok(rem)
+        }
+        CLType::Option(ty) => match u8::from_bytes(bytes)? {
+            (0, rem) => Ok(rem),
+            (1, rem) => collect_urefs(ty, rem, urefs),
+            _ => Err(bytesrepr::Error::Formatting),
+        },
+        CLType::List(ty) => {
+            let (count, rem) = u32::from_bytes(bytes)?;
+            collect_repeated_urefs(&[&**ty], count, rem, urefs)
+        }
+        CLType::FixedList(ty, count) => collect_repeated_urefs(&[&**ty], *count, bytes, urefs),
+        CLType::Result { ok, err } => match u8::from_bytes(bytes)? {
+            (0, rem) => collect_urefs(err, rem, urefs),
+            (1, rem) => collect_urefs(ok, rem, urefs),
+            _ => Err(bytesrepr::Error::Formatting),
+        },
+        CLType::Map { key, value } => {
+            let (count, rem) = u32::from_bytes(bytes)?;
+            collect_repeated_urefs(&[&**key, &**value], count, rem, urefs)
+        }
+        CLType::Tuple1([ty]) => collect_urefs(ty, bytes, urefs),
+        CLType::Tuple2([ty1, ty2]) => {
+            let rem = collect_urefs(ty1, bytes, urefs)?;
+            collect_urefs(ty2, rem, urefs)
+        }
+        CLType::Tuple3([ty1, ty2, ty3]) => {
+            let rem = collect_urefs(ty1, bytes, urefs)?;
+            let rem = collect_urefs(ty2, rem, urefs)?;
+            collect_urefs(ty3, rem, urefs)
+        }
+        CLType::Any => Err(bytesrepr::Error::Formatting),
+    }
+}
+
+/// Appends the `URef`s in `count` consecutive groups of values of types `cl_types` at the start of
+/// `bytes` to `urefs`, returning the bytes which follow them.
+fn collect_repeated_urefs<'a>(
+    cl_types: &[&CLType],
+    count: u32,
+    mut bytes: &'a [u8],
+    urefs: &mut Vec<URef>,
+) -> Result<&'a [u8], bytesrepr::Error> {
+    for _ in 0..count {
+        let group_start = bytes.len();
+        for cl_type in cl_types {
+            bytes = collect_urefs(cl_type, bytes, urefs)?;
+        }
+        // A group which takes up no bytes holds no `URef`s, and neither will the remaining ones,
+        // so a huge count of them needn't be walked.
+        if bytes.len() == group_start {
+            break;
+        }
+    }
+    Ok(bytes)
+}
+
 impl<'a, R> Runtime<'a, R>
 where
     R: StateReader<Key, StoredValue>,
@@ -1363,6 +1550,13 @@ where
             module,
             host_buffer: None,
             context,
+            cost_breakdown: CostBreakdown::default(),
+            host_function_profile: if config.host_function_profiling() {
+                Some(Default::default())
+            } else {
+                None
+            },
+            last_host_function: None,
         }
     }
 
@@ -1382,12 +1576,26 @@ where
         self.context.protocol_data()
     }
 
-    /// Charge specified amount of gas
+    /// Returns the gas charged so far, split by category.  The categories sum to the context's gas
+    /// counter.
+    pub fn cost_breakdown(&self) -> &CostBreakdown {
+        &self.cost_breakdown
+    }
+
+    /// Returns the host function calls made so far, or an empty profile if profiling is disabled.
+    pub fn host_function_profile(&self) -> HostFunctionProfile {
+        self.host_function_profile
+            .as_ref()
+            .map(|profile| profile.borrow().clone())
+            .unwrap_or_default()
+    }
+
+    /// Charge specified amount of gas against the given category
     ///
     /// Returns false if gas limit exceeded and true if not.
     /// Intuition about the return value sense is to answer the question 'are we
     /// allowed to continue?'
-    fn charge_gas(&mut self, amount: Gas) -> bool {
+    fn charge_gas(&mut self, category: CostCategory, amount: Gas) -> bool {
         let prev = self.context.gas_counter();
         match prev.checked_add(amount) {
             // gas charge overflow protection
@@ -1395,19 +1603,68 @@ where
             Some(val) if val > self.context.gas_limit() => false,
             Some(val) => {
                 self.context.set_gas_counter(val);
+                self.cost_breakdown.add(category, amount);
                 true
             }
         }
     }
 
-    fn gas(&mut self, amount: Gas) -> Result<(), Trap> {
-        if self.charge_gas(amount) {
+    fn gas(&mut self, category: CostCategory, amount: Gas) -> Result<(), Trap> {
+        if self.charge_gas(category, amount) {
             Ok(())
         } else {
+            #[cfg(feature = "test-support")]
+            {
+                let context =
+                    GasExhaustionContext::new(&self.context.effect(), self.last_host_function);
+                GAS_EXHAUSTION_CONTEXT.with(|cell| cell.replace(Some(context)));
+            }
             Err(Error::GasLimit.into())
         }
     }
 
+    /// Charges the flat cost of calling the given host function.
+    /// Returns an error if the given host function may not be called in the current phase.
+    fn check_phase_policy(&self, func: FunctionIndex) -> Result<(), Error> {
+        let phase = self.context.phase();
+        if is_payment_phase(phase) && PAYMENT_RESTRICTED_FUNCTIONS.contains(&func) {
+            return Err(Error::InvalidContextForFunction(func, phase));
+        }
+        Ok(())
+    }
+
+    /// Rejects calls to host functions which aren't available under the current protocol version.
+    /// Such imports are already rejected when the module is instantiated, so this only guards
+    /// against modules linked under a different protocol version.
+    fn check_protocol_version_policy(&self, func: FunctionIndex) -> Result<(), Error> {
+        let required_version = func.min_protocol_version();
+        if self.context.protocol_version() < required_version {
+            return Err(Error::UnsupportedHostFunction {
+                name: func.import_name().to_string(),
+                required_version,
+            });
+        }
+        Ok(())
+    }
+
+    fn charge_host_function_call(&mut self, func: FunctionIndex) -> Result<(), Trap> {
+        let cost = self.host_function_cost(func).cost;
+        self.gas(CostCategory::HostFunction, Gas::new(cost.into()))
+    }
+
+    /// Charges the per-byte cost of passing `size` bytes to the given host function.
+    fn charge_host_function_bytes(&mut self, func: FunctionIndex, size: u32) -> Result<(), Trap> {
+        let cost = self.host_function_cost(func).bytes_cost(size);
+        self.gas(CostCategory::HostFunctionBytes, Gas::new(cost.into()))
+    }
+
+    fn host_function_cost(&self, func: FunctionIndex) -> HostFunctionCost {
+        self.protocol_data()
+            .wasm_costs()
+            .host_function_costs
+            .get(func.into())
+    }
+
     fn bytes_from_mem(&self, ptr: u32, size: usize) -> Result<Vec<u8>, Error> {
         self.memory.get(ptr, size).map_err(Into::into)
     }
@@ -1597,6 +1854,24 @@ where
             .map_err(|e| Error::Interpreter(e.into()).into())
     }
 
+    /// Writes the hash of the currently executing deploy to [dest_ptr] in Wasm memory.
+    fn get_deploy_hash(&self, dest_ptr: u32) -> Result<(), Trap> {
+        let deploy_hash = self.context.get_deploy_hash();
+        self.memory
+            .set(dest_ptr, &deploy_hash)
+            .map_err(|e| Error::Interpreter(e.into()).into())
+    }
+
+    /// Writes a new address to [dest_ptr] in Wasm memory.  It is drawn from the same deterministic
+    /// stream as the hashes of new contracts, so it is unique within the deploy and the same every
+    /// time the deploy is executed.
+    fn new_hash_address(&mut self, dest_ptr: u32) -> Result<(), Trap> {
+        let address = self.context.new_hash_address()?;
+        self.memory
+            .set(dest_ptr, &address)
+            .map_err(|e| Error::Interpreter(e.into()).into())
+    }
+
     /// Return some bytes from the memory and terminate the current `sub_call`. Note that the return
     /// type is `Trap`, indicating that this function will always kill the current Wasm instance.
     fn ret(
@@ -1620,7 +1895,14 @@ where
                 let urefs = match &self.host_buffer {
                     Some(buf) => extract_urefs(buf),
                     None => Ok(vec![]),
-                };
+                }
+                .and_then(|urefs| {
+                    // The caller can't be handed more rights than this context holds.
+                    for uref in &urefs {
+                        self.context.validate_granted_uref(uref)?;
+                    }
+                    Ok(urefs)
+                });
                 match urefs {
                     Ok(urefs) => {
                         scoped_instrumenter.add_property(UREF_COUNT, urefs.len());
@@ -1676,6 +1958,13 @@ where
         const METHOD_CREATE: &str = "create";
         const METHOD_BALANCE: &str = "balance";
         const METHOD_TRANSFER: &str = "transfer";
+        const METHOD_BURN: &str = "burn";
+        const METHOD_READ_TOTAL_SUPPLY: &str = "read_total_supply";
+        const METHOD_APPROVE: &str = "approve";
+        const METHOD_ALLOWANCE: &str = "allowance";
+        const METHOD_TRANSFER_FROM: &str = "transfer_from";
+        const METHOD_ROUND_REWARD: &str = "round_reward";
+        const METHOD_MINT_INTO: &str = "mint_into";
 
         let state = self.context.state();
         let access_rights = {
@@ -1746,6 +2035,59 @@ where
                 let result: Result<(), mint::Error> = mint_context.transfer(source, target, amount);
                 CLValue::from_t(result).map_err(Self::reverter)?
             }
+            // Type: `fn burn(purse: URef, amount: U512) -> Result<(), Error>`
+            METHOD_BURN => {
+                let purse: URef = Self::get_named_argument(&runtime_args, "purse")?;
+                let amount: U512 = Self::get_named_argument(&runtime_args, "amount")?;
+                let result: Result<(), mint::Error> = mint_context.burn(purse, amount);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn read_total_supply() -> U512`
+            METHOD_READ_TOTAL_SUPPLY => {
+                let total_supply: U512 =
+                    mint_context.read_total_supply().map_err(Self::reverter)?;
+                CLValue::from_t(total_supply).map_err(Self::reverter)?
+            }
+            // Type: `fn approve(purse: URef, spender: AccountHash, amount: U512) -> Result<(),
+            // Error>`
+            METHOD_APPROVE => {
+                let purse: URef = Self::get_named_argument(&runtime_args, "purse")?;
+                let spender: AccountHash = Self::get_named_argument(&runtime_args, "spender")?;
+                let amount: U512 = Self::get_named_argument(&runtime_args, "amount")?;
+                let result: Result<(), mint::Error> = mint_context.approve(purse, spender, amount);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn allowance(owner: URefAddr, spender: AccountHash) -> U512`
+            METHOD_ALLOWANCE => {
+                let owner: URefAddr = Self::get_named_argument(&runtime_args, "owner")?;
+                let spender: AccountHash = Self::get_named_argument(&runtime_args, "spender")?;
+                let allowance: U512 = mint_context
+                    .allowance(owner, spender)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(allowance).map_err(Self::reverter)?
+            }
+            // Type: `fn transfer_from(owner: URefAddr, target: URef, amount: U512) -> Result<(),
+            // Error>`
+            METHOD_TRANSFER_FROM => {
+                let owner: URefAddr = Self::get_named_argument(&runtime_args, "owner")?;
+                let target: URef = Self::get_named_argument(&runtime_args, "target")?;
+                let amount: U512 = Self::get_named_argument(&runtime_args, "amount")?;
+                let result: Result<(), mint::Error> =
+                    mint_context.transfer_from(owner, target, amount);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn round_reward() -> U512`
+            METHOD_ROUND_REWARD => {
+                let round_reward: U512 = mint_context.round_reward().map_err(Self::reverter)?;
+                CLValue::from_t(round_reward).map_err(Self::reverter)?
+            }
+            // Type: `fn mint_into(purse: URef, amount: U512) -> Result<(), Error>`
+            METHOD_MINT_INTO => {
+                let purse: URef = Self::get_named_argument(&runtime_args, "purse")?;
+                let amount: U512 = Self::get_named_argument(&runtime_args, "amount")?;
+                let result: Result<(), mint::Error> = mint_context.mint_into(purse, amount);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
             _ => CLValue::from_t(()).map_err(Self::reverter)?,
         };
         let urefs = extract_urefs(&ret)?;
@@ -1764,12 +2106,21 @@ where
     ) -> Result<CLValue, Error> {
         const METHOD_BOND: &str = "bond";
         const METHOD_UNBOND: &str = "unbond";
+        const METHOD_DELEGATE: &str = "delegate";
+        const METHOD_UNDELEGATE: &str = "undelegate";
+        const METHOD_PROCESS_UNBOND_REQUESTS: &str = "process_unbond_requests";
+        const METHOD_SLASH: &str = "slash";
         const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
         const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
         const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
         const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
+        const METHOD_DISTRIBUTE_REWARDS: &str = "distribute_rewards";
+        const METHOD_SET_GAS_PRICE: &str = "set_gas_price";
         const ARG_AMOUNT: &str = "amount";
         const ARG_PURSE: &str = "purse";
+        const ARG_VALIDATOR: &str = "validator";
+        const ARG_VALIDATOR_PUBLIC_KEYS: &str = "validator_public_keys";
+        const ARG_GAS_PRICE: &str = "gas_price";
 
         let state = self.context.state();
         let access_rights = {
@@ -1821,6 +2172,18 @@ where
             runtime_context,
         );
 
+        let is_staking_method = [
+            METHOD_BOND,
+            METHOD_UNBOND,
+            METHOD_DELEGATE,
+            METHOD_UNDELEGATE,
+        ]
+        .contains(&entry_point_name);
+        if is_staking_method && is_payment_phase(phase) {
+            // Stake can't change hands before the deploy has been paid for.
+            return Err(Error::InvalidContext);
+        }
+
         let ret: CLValue = match entry_point_name {
             METHOD_BOND => {
                 if !self.config.enable_bonding() {
@@ -1849,6 +2212,48 @@ where
                     .map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)?
             }
+            METHOD_DELEGATE => {
+                if !self.config.enable_bonding() {
+                    let err = Error::Revert(ApiError::Unhandled);
+                    return Err(err);
+                }
+
+                let delegator: AccountHash = runtime.context.get_caller();
+                let validator: AccountHash =
+                    Self::get_named_argument(&runtime_args, ARG_VALIDATOR)?;
+                let amount: U512 = Self::get_named_argument(&runtime_args, ARG_AMOUNT)?;
+                let source_uref: URef = Self::get_named_argument(&runtime_args, ARG_PURSE)?;
+                runtime
+                    .delegate(delegator, validator, amount, source_uref)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_UNDELEGATE => {
+                if !self.config.enable_bonding() {
+                    let err = Error::Revert(ApiError::Unhandled);
+                    return Err(err);
+                }
+
+                let delegator: AccountHash = runtime.context.get_caller();
+                let validator: AccountHash =
+                    Self::get_named_argument(&runtime_args, ARG_VALIDATOR)?;
+                let maybe_amount: Option<U512> =
+                    Self::get_named_argument(&runtime_args, ARG_AMOUNT)?;
+                runtime
+                    .undelegate(delegator, validator, maybe_amount)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_PROCESS_UNBOND_REQUESTS => {
+                runtime.process_unbond_requests().map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_SLASH => {
+                let validators: Vec<AccountHash> =
+                    Self::get_named_argument(&runtime_args, ARG_VALIDATOR_PUBLIC_KEYS)?;
+                runtime.slash(validators).map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
             METHOD_GET_PAYMENT_PURSE => {
                 let rights_controlled_purse =
                     runtime.get_payment_purse().map_err(Self::reverter)?;
@@ -1871,6 +2276,19 @@ where
                     .map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)?
             }
+            METHOD_DISTRIBUTE_REWARDS => {
+                let validator: AccountHash =
+                    Self::get_named_argument(&runtime_args, ARG_VALIDATOR)?;
+                runtime
+                    .distribute_rewards(validator)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
+            METHOD_SET_GAS_PRICE => {
+                let gas_price: u64 = Self::get_named_argument(&runtime_args, ARG_GAS_PRICE)?;
+                runtime.set_gas_price(gas_price).map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
             _ => CLValue::from_t(()).map_err(Self::reverter)?,
         };
         let urefs = extract_urefs(&ret)?;
@@ -1891,6 +2309,36 @@ where
         entry_point_name: &str,
         args: RuntimeArgs,
     ) -> Result<CLValue, Error> {
+        let (contract, entry_point) =
+            self.get_contract_entry_point(contract_hash, entry_point_name)?;
+        self.execute_contract_entry_point(contract_hash, contract, entry_point, args)
+    }
+
+    /// Calls `version` of the contract living at `key`, invoking `method` with
+    /// supplied `args`. This function also checks the args conform with the
+    /// types given in the contract header.
+    pub fn call_versioned_contract(
+        &mut self,
+        contract_package_hash: ContractPackageHash,
+        contract_version: Option<ContractVersion>,
+        entry_point_name: String,
+        args: RuntimeArgs,
+    ) -> Result<CLValue, Error> {
+        let (contract_hash, contract, entry_point) = self.get_versioned_contract_entry_point(
+            contract_package_hash,
+            contract_version,
+            &entry_point_name,
+        )?;
+        self.execute_versioned_contract_entry_point(contract_hash, contract, entry_point, args)
+    }
+
+    /// Reads the contract living at `contract_hash` and looks up its entry point
+    /// `entry_point_name`.
+    fn get_contract_entry_point(
+        &mut self,
+        contract_hash: ContractHash,
+        entry_point_name: &str,
+    ) -> Result<(Contract, EntryPoint), Error> {
         let key = contract_hash.into();
         let contract = match self.context.read_gs(&key)? {
             Some(StoredValue::Contract(contract)) => contract,
@@ -1908,10 +2356,20 @@ where
             .cloned()
             .ok_or_else(|| Error::NoSuchMethod(entry_point_name.to_owned()))?;
 
+        Ok((contract, entry_point))
+    }
+
+    fn execute_contract_entry_point(
+        &mut self,
+        contract_hash: ContractHash,
+        contract: Contract,
+        entry_point: EntryPoint,
+        args: RuntimeArgs,
+    ) -> Result<CLValue, Error> {
         let context_key = self.get_context_key_for_contract_call(contract_hash, &entry_point)?;
 
         self.execute_contract(
-            key,
+            contract_hash.into(),
             context_key,
             contract,
             args,
@@ -1920,16 +2378,14 @@ where
         )
     }
 
-    /// Calls `version` of the contract living at `key`, invoking `method` with
-    /// supplied `args`. This function also checks the args conform with the
-    /// types given in the contract header.
-    pub fn call_versioned_contract(
+    /// Reads `version` of the contract living at `contract_package_hash`, looks up its entry
+    /// point `entry_point_name` and checks the caller may access it.
+    fn get_versioned_contract_entry_point(
         &mut self,
         contract_package_hash: ContractPackageHash,
         contract_version: Option<ContractVersion>,
-        entry_point_name: String,
-        args: RuntimeArgs,
-    ) -> Result<CLValue, Error> {
+        entry_point_name: &str,
+    ) -> Result<(ContractHash, Contract, EntryPoint), Error> {
         let key = contract_package_hash.into();
 
         let contract_package = match self.context.read_gs(&key)? {
@@ -1972,12 +2428,22 @@ where
         };
 
         let entry_point = contract
-            .entry_point(&entry_point_name)
+            .entry_point(entry_point_name)
             .cloned()
             .ok_or_else(|| Error::NoSuchMethod(entry_point_name.to_owned()))?;
 
         self.validate_entry_point_access(&contract_package, entry_point.access())?;
 
+        Ok((contract_hash, contract, entry_point))
+    }
+
+    fn execute_versioned_contract_entry_point(
+        &mut self,
+        contract_hash: ContractHash,
+        contract: Contract,
+        entry_point: EntryPoint,
+        args: RuntimeArgs,
+    ) -> Result<CLValue, Error> {
         for (expected, found) in entry_point
             .args()
             .iter()
@@ -2002,6 +2468,39 @@ where
         )
     }
 
+    /// Checks `args` against the parameters declared by `entry_point`, returning the error to
+    /// report to the caller along with the name of the first parameter which is missing or of the
+    /// wrong type.
+    ///
+    /// Arguments aren't checked for entry points marked as having no schema, nor under protocol
+    /// versions earlier than 2.0.0.
+    fn check_args_schema(
+        &self,
+        entry_point: &EntryPoint,
+        args: &RuntimeArgs,
+    ) -> Result<(), (ApiError, String)> {
+        if entry_point.no_schema()
+            || self.context.protocol_version() < ProtocolVersion::from_parts(2, 0, 0)
+        {
+            return Ok(());
+        }
+
+        for parameter in entry_point.args() {
+            match args.get(parameter.name()) {
+                None => return Err((ApiError::MissingArgument, parameter.name().to_string())),
+                Some(arg)
+                    if *parameter.cl_type() != CLType::Any
+                        && arg.cl_type() != parameter.cl_type() =>
+                {
+                    return Err((ApiError::InvalidArgument, parameter.name().to_string()));
+                }
+                Some(_) => (),
+            }
+        }
+
+        Ok(())
+    }
+
     fn get_context_key_for_contract_call(
         &self,
         contract_hash: ContractHash,
@@ -2057,14 +2556,12 @@ where
             let mut extra_keys = vec![];
             // A loop is needed to be able to use the '?' operator
             for arg in args.to_values() {
-                extra_keys.extend(
-                    extract_urefs(arg)?
-                        .into_iter()
-                        .map(<Key as From<URef>>::from),
-                );
-            }
-            for key in &extra_keys {
-                self.context.validate_key(key)?;
+                let urefs = extract_urefs(arg)?;
+                // The callee can't be handed more rights than the caller holds.
+                for uref in &urefs {
+                    self.context.validate_granted_uref(uref)?;
+                }
+                extra_keys.extend(urefs.into_iter().map(<Key as From<URef>>::from));
             }
 
             if !self.config.use_system_contracts() {
@@ -2156,14 +2653,18 @@ where
             module,
             host_buffer,
             context,
+            cost_breakdown: self.cost_breakdown.clone(),
+            host_function_profile: self.host_function_profile.clone(),
+            last_host_function: None,
         };
 
         let result = instance.invoke_export(entry_point_name, &[], &mut runtime);
 
         // The `runtime`'s context was initialized with our counter from before the call and any gas
         // charged by the sub-call was added to its counter - so let's copy the correct value of the
-        // counter from there to our counter
+        // counter from there to our counter, along with its breakdown
         self.context.set_gas_counter(runtime.context.gas_counter());
+        self.cost_breakdown = runtime.cost_breakdown.clone();
 
         let error = match result {
             Err(error) => error,
@@ -2224,8 +2725,14 @@ where
             return Ok(Err(err));
         }
         let args: RuntimeArgs = bytesrepr::deserialize(args_bytes)?;
+        let (contract, entry_point) =
+            self.get_contract_entry_point(contract_hash, entry_point_name)?;
+        if let Err((error, parameter_name)) = self.check_args_schema(&entry_point, &args) {
+            return self.manage_args_schema_error(result_size_ptr, error, parameter_name);
+        }
         scoped_instrumenter.pause();
-        let result = self.call_contract(contract_hash, entry_point_name, args)?;
+        let result =
+            self.execute_contract_entry_point(contract_hash, contract, entry_point, args)?;
         scoped_instrumenter.unpause();
         self.manage_call_contract_host_buffer(result_size_ptr, result)
     }
@@ -2244,17 +2751,42 @@ where
             return Ok(Err(err));
         }
         let args: RuntimeArgs = bytesrepr::deserialize(args_bytes)?;
-        scoped_instrumenter.pause();
-        let result = self.call_versioned_contract(
+        let (contract_hash, contract, entry_point) = self.get_versioned_contract_entry_point(
             contract_package_hash,
             contract_version,
-            entry_point_name,
+            &entry_point_name,
+        )?;
+        if let Err((error, parameter_name)) = self.check_args_schema(&entry_point, &args) {
+            return self.manage_args_schema_error(result_size_ptr, error, parameter_name);
+        }
+        scoped_instrumenter.pause();
+        let result = self.execute_versioned_contract_entry_point(
+            contract_hash,
+            contract,
+            entry_point,
             args,
         )?;
         scoped_instrumenter.unpause();
         self.manage_call_contract_host_buffer(result_size_ptr, result)
     }
 
+    /// Leaves the name of the offending parameter in the host buffer for the caller, and returns
+    /// `error` without the callee having been run.
+    fn manage_args_schema_error(
+        &mut self,
+        result_size_ptr: u32,
+        error: ApiError,
+        parameter_name: String,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let parameter_name = CLValue::from_t(parameter_name)?;
+        if let Err(host_buffer_error) =
+            self.manage_call_contract_host_buffer(result_size_ptr, parameter_name)?
+        {
+            return Ok(Err(host_buffer_error));
+        }
+        Ok(Err(error))
+    }
+
     fn check_host_buffer(&mut self) -> Result<(), ApiError> {
         if !self.can_write_to_host_buffer() {
             Err(ApiError::HostBufferFull)
@@ -2332,6 +2864,65 @@ where
         Ok(Ok(()))
     }
 
+    /// Writes at most `max_count` named keys of the current context, starting from the entry at
+    /// `start_index`, to the host buffer.  Entries are taken in ascending order of their names, so
+    /// successive calls within one execution yield consistent, non-overlapping pages.
+    ///
+    /// The total number of named keys in the context is written to `total_keys_ptr`, allowing the
+    /// caller to determine whether further pages remain.
+    fn load_named_keys_chunk(
+        &mut self,
+        start_index: u32,
+        max_count: u32,
+        total_keys_ptr: u32,
+        result_size_ptr: u32,
+        scoped_instrumenter: &mut ScopedInstrumenter,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        let total_keys = self.context.named_keys().len() as u32;
+        let total_keys_bytes = total_keys.to_le_bytes();
+        if let Err(error) = self.memory.set(total_keys_ptr, &total_keys_bytes) {
+            return Err(Error::Interpreter(error.into()).into());
+        }
+
+        let chunk: NamedKeys = self
+            .context
+            .named_keys()
+            .iter()
+            .skip(start_index as usize)
+            .take(max_count as usize)
+            .map(|(name, key)| (name.clone(), *key))
+            .collect();
+
+        scoped_instrumenter.add_property(
+            "names_total_length",
+            chunk.keys().map(|name| name.len()).sum::<usize>(),
+        );
+
+        let length = if chunk.is_empty() {
+            // No need to write anything to the host buffer, we leave it empty.
+            0
+        } else {
+            let chunk = CLValue::from_t(chunk).map_err(Error::CLValue)?;
+            let length = chunk.inner_bytes().len() as u32;
+            if let Err(error) = self.write_host_buffer(chunk) {
+                return Ok(Err(error));
+            }
+            length
+        };
+
+        let length_bytes = length.to_le_bytes();
+        if let Err(error) = self.memory.set(result_size_ptr, &length_bytes) {
+            return Err(Error::Interpreter(error.into()).into());
+        }
+
+        Ok(Ok(()))
+    }
+
     fn create_contract_value(&mut self) -> Result<(StoredValue, URef), Error> {
         let access_key = self.context.new_unit_uref()?;
         let contract_package = ContractPackage::new(
@@ -2445,7 +3036,7 @@ where
 
         let mut contract_package: ContractPackage = self
             .context
-            .get_validated_contract_package(contract_package_hash)?;
+            .get_upgradable_contract_package(contract_package_hash)?;
 
         let contract_wasm_hash = self.context.new_hash_address()?;
         let contract_wasm_key = Key::Hash(contract_wasm_hash);
@@ -2563,6 +3154,7 @@ where
     /// Generates new unforgable reference and adds it to the context's
     /// access_rights set.
     fn new_uref(&mut self, uref_ptr: u32, value_ptr: u32, value_size: u32) -> Result<(), Trap> {
+        self.check_stored_value_size(value_size)?;
         let cl_value = self.cl_value_from_mem(value_ptr, value_size)?; // read initial value from memory
         let uref = self.context.new_uref(StoredValue::CLValue(cl_value))?;
         self.memory
@@ -2578,6 +3170,7 @@ where
         value_ptr: u32,
         value_size: u32,
     ) -> Result<(), Trap> {
+        self.check_stored_value_size(value_size)?;
         let key = self.key_from_mem(key_ptr, key_size)?;
         let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
         self.context
@@ -2585,6 +3178,25 @@ where
             .map_err(Into::into)
     }
 
+    /// Fails with `Error::ValueTooLarge` if a serialized value of `value_size` bytes exceeds the
+    /// configured maximum stored value size.
+    fn check_stored_value_size(&self, value_size: u32) -> Result<(), Error> {
+        let max = self.config.max_stored_value_size();
+        if value_size > max {
+            return Err(Error::ValueTooLarge {
+                actual: value_size,
+                max,
+            });
+        }
+        Ok(())
+    }
+
+    /// Removes the value stored under `key` from GlobalState.
+    fn remove(&mut self, key_ptr: u32, key_size: u32) -> Result<(), Trap> {
+        let key = self.key_from_mem(key_ptr, key_size)?;
+        self.context.remove_gs(key).map_err(Into::into)
+    }
+
     /// Writes `value` under a key derived from `key` in the "local cluster" of
     /// GlobalState
     fn write_local(
@@ -2594,6 +3206,7 @@ where
         value_ptr: u32,
         value_size: u32,
     ) -> Result<(), Trap> {
+        self.check_stored_value_size(value_size)?;
         let key_bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
         let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
         self.context
@@ -2705,6 +3318,18 @@ where
         };
         let weight = Weight::new(weight_value);
 
+        let max_associated_keys = self.config.max_associated_keys() as usize;
+        if max_associated_keys < MAX_ASSOCIATED_KEYS {
+            let associated_keys_count = self
+                .context
+                .read_current_account()?
+                .get_associated_keys()
+                .count();
+            if associated_keys_count >= max_associated_keys {
+                return Ok(AddKeyFailure::MaxKeysLimit as i32);
+            }
+        }
+
         match self.context.add_associated_key(account_hash, weight) {
             Ok(_) => Ok(0),
             // This relies on the fact that `AddKeyFailure` is represented as
@@ -2783,6 +3408,87 @@ where
         }
     }
 
+    /// Returns the current value of the account's threshold for the given action type.
+    fn get_action_threshold(&mut self, action_type_value: u32) -> Result<i32, Trap> {
+        let action_type = match ActionType::try_from(action_type_value) {
+            Ok(action_type) => action_type,
+            Err(_) => return Err(Trap::new(TrapKind::Unreachable)),
+        };
+        let account = self.context.read_current_account()?;
+        let threshold = account.action_thresholds().get(action_type);
+        Ok(threshold.value().into())
+    }
+
+    /// Returns 1 if the given keys together meet the account's threshold for the given action
+    /// type, or 0 if they don't.
+    fn can_authorize(
+        &mut self,
+        keys_ptr: u32,
+        keys_size: u32,
+        action_type_value: u32,
+    ) -> Result<i32, Trap> {
+        let action_type = match ActionType::try_from(action_type_value) {
+            Ok(action_type) => action_type,
+            Err(_) => return Err(Trap::new(TrapKind::Unreachable)),
+        };
+        let keys: Vec<AccountHash> = self.t_from_mem(keys_ptr, keys_size)?;
+        let account = self.context.read_current_account()?;
+        Ok(account.can_authorize_action(&keys, action_type).into())
+    }
+
+    /// Writes the account's associated keys and their weights to the host buffer, and the size
+    /// of the serialized list to `output_size_ptr` in Wasm memory.
+    fn list_associated_keys(&mut self, output_size_ptr: u32) -> Result<Result<(), ApiError>, Trap> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+        let associated_keys: Vec<(AccountHash, Weight)> = self
+            .context
+            .read_current_account()?
+            .get_associated_keys()
+            .map(|(account_hash, weight)| (*account_hash, *weight))
+            .collect();
+        let value = CLValue::from_t(associated_keys).map_err(Error::CLValue)?;
+        let value_size = value.inner_bytes().len() as u32;
+
+        if let Err(error) = self.write_host_buffer(value) {
+            return Ok(Err(error));
+        }
+
+        let output_size_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(output_size_ptr, &output_size_bytes) {
+            return Err(Error::Interpreter(error.into()).into());
+        }
+        Ok(Ok(()))
+    }
+
+    /// Writes the keys which authorized the deploy being executed, ordered by [`AccountHash`], to
+    /// the host buffer, and the size of the serialized list to `output_size_ptr` in Wasm memory.
+    fn list_authorization_keys(
+        &mut self,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+        let authorization_keys: Vec<AccountHash> =
+            self.context.authorization_keys().iter().copied().collect();
+        let value = CLValue::from_t(authorization_keys).map_err(Error::CLValue)?;
+        let value_size = value.inner_bytes().len() as u32;
+
+        if let Err(error) = self.write_host_buffer(value) {
+            return Ok(Err(error));
+        }
+
+        let output_size_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(output_size_ptr, &output_size_bytes) {
+            return Err(Error::Interpreter(error.into()).into());
+        }
+        Ok(Ok(()))
+    }
+
     /// Looks up the public mint contract key in the context's protocol data.
     ///
     /// Returned URef is already attenuated depending on the calling account.
@@ -2797,13 +3503,6 @@ where
         self.context.protocol_data().proof_of_stake()
     }
 
-    /// Looks up the public standard payment contract key in the context's protocol data.
-    ///
-    /// Returned URef is already attenuated depending on the calling account.
-    fn get_standard_payment_contract(&self) -> ContractHash {
-        self.context.protocol_data().standard_payment()
-    }
-
     /// Calls the "create" method on the mint contract at the given mint
     /// contract key
     fn mint_create(&mut self, mint_contract_hash: ContractHash) -> Result<URef, Error> {
@@ -2817,6 +3516,50 @@ where
         self.mint_create(self.get_mint_contract())
     }
 
+    /// Creates a new purse, stores it under the given name in the current context's named keys
+    /// and writes it to [dest_ptr] in Wasm memory.  Nothing is created if a named key with that
+    /// name already exists.
+    fn create_named_purse(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        dest_ptr: u32,
+        dest_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if dest_size as usize != UREF_SERIALIZED_LENGTH {
+            return Ok(Err(ApiError::BufferTooSmall));
+        }
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        if self.context.named_keys_contains_key(&name) {
+            return Ok(Err(ApiError::NamedKeyExists));
+        }
+
+        let purse = self.create_purse()?;
+        self.context.put_key(name, Key::from(purse))?;
+
+        let purse_bytes = purse.into_bytes().map_err(Error::BytesRepr)?;
+
+        self.memory
+            .set(dest_ptr, &purse_bytes)
+            .map_err(|e| Error::Interpreter(e.into()))?;
+        Ok(Ok(()))
+    }
+
+    /// Records an event with the topic and payload read from Wasm memory, attributed to the current
+    /// context.  Nothing is recorded if the deploy's event limits would be exceeded.
+    fn emit_event(
+        &mut self,
+        topic_ptr: u32,
+        topic_size: u32,
+        payload_ptr: u32,
+        payload_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let topic = self.string_from_mem(topic_ptr, topic_size)?;
+        let payload = self.bytes_from_mem(payload_ptr, payload_size as usize)?;
+        let event = ContractEvent::new(self.context.base_key(), topic, payload);
+        Ok(self.context.emit_event(event))
+    }
+
     /// Calls the "transfer" method on the mint contract at the given mint
     /// contract key
     fn mint_transfer(
@@ -2841,6 +3584,32 @@ where
         Ok(result.map_err(system_contract_errors::Error::from)?)
     }
 
+    /// Calls the "round_reward" method on the mint contract at the given mint contract key.
+    fn mint_round_reward(&mut self, mint_contract_hash: ContractHash) -> Result<U512, Error> {
+        let result = self.call_contract(mint_contract_hash, "round_reward", RuntimeArgs::new())?;
+        Ok(result.into_t()?)
+    }
+
+    /// Calls the "mint_into" method on the mint contract at the given mint contract key.
+    fn mint_into_purse(
+        &mut self,
+        mint_contract_hash: ContractHash,
+        purse: URef,
+        amount: U512,
+    ) -> Result<(), Error> {
+        const ARG_PURSE: &str = "purse";
+        const ARG_AMOUNT: &str = "amount";
+
+        let args_values: RuntimeArgs = runtime_args! {
+            ARG_PURSE => purse,
+            ARG_AMOUNT => amount,
+        };
+
+        let result = self.call_contract(mint_contract_hash, "mint_into", args_values)?;
+        let result: Result<(), mint::Error> = result.into_t()?;
+        Ok(result.map_err(system_contract_errors::Error::from)?)
+    }
+
     /// Creates a new account at a given public key, transferring a given amount
     /// of motes from the given source purse to the new account's purse.
     fn transfer_to_new_account(
@@ -2897,13 +3666,53 @@ where
 
     /// Transfers `amount` of motes from default purse of the account to
     /// `target` account. If that account does not exist, creates one.
+    ///
+    /// A successful transfer is recorded along with `id`.
     fn transfer_to_account(
         &mut self,
         target: AccountHash,
         amount: U512,
+        id: Option<u64>,
     ) -> Result<TransferResult, Error> {
         let source = self.context.get_main_purse()?;
-        self.transfer_from_purse_to_account(source, target, amount)
+        self.transfer_from_purse_to_account_with_id(source, target, amount, id)
+    }
+
+    /// Transfers `amount` of motes from `source` purse to `target` account, as
+    /// `transfer_from_purse_to_account` does, and records a successful transfer along with `id`.
+    fn transfer_from_purse_to_account_with_id(
+        &mut self,
+        source: URef,
+        target: AccountHash,
+        amount: U512,
+        id: Option<u64>,
+    ) -> Result<TransferResult, Error> {
+        let result = self.transfer_from_purse_to_account(source, target, amount)?;
+        if result.is_ok() {
+            self.record_transfer(source, target, amount, id)?;
+        }
+        Ok(result)
+    }
+
+    /// Writes a [`TransferRecord`] under the address of the deploy's next unused transfer index.
+    fn record_transfer(
+        &mut self,
+        source: URef,
+        target: AccountHash,
+        amount: U512,
+        id: Option<u64>,
+    ) -> Result<(), Error> {
+        let deploy_hash = self.context.get_deploy_hash();
+        let mut index = 0;
+        let addr = loop {
+            let addr = TransferRecord::addr(deploy_hash, index);
+            if self.context.read_ls(&addr)?.is_none() {
+                break addr;
+            }
+            index += 1;
+        };
+        let record = TransferRecord::new(deploy_hash, source.addr(), target, amount, id);
+        self.context.write_ls(&addr, CLValue::from_t(record)?)
     }
 
     /// Transfers `amount` of motes from `source` purse to `target` account.
@@ -2964,13 +3773,14 @@ where
 
         let mint_contract_key = self.get_mint_contract();
 
-        if self
-            .mint_transfer(mint_contract_key, source, target, amount)
-            .is_ok()
-        {
-            Ok(Ok(()))
-        } else {
-            Ok(Err(ApiError::Transfer))
+        match self.mint_transfer(mint_contract_key, source, target, amount) {
+            Ok(()) => Ok(Ok(())),
+            // Surface the mint's reason for failure so that callers can tell e.g. insufficient
+            // funds apart from an unknown purse.
+            Err(Error::SystemContract(system_contract_errors::Error::Mint(mint_error))) => {
+                Ok(Err(ApiError::from(mint_error)))
+            }
+            Err(_) => Ok(Err(ApiError::Transfer)),
         }
     }
 
@@ -3053,13 +3863,35 @@ where
         dest_ptr: u32,
         _dest_size: u32,
     ) -> Result<Result<(), ApiError>, Trap> {
-        let contract_hash: ContractHash = match SystemContractType::try_from(system_contract_index)
-        {
-            Ok(SystemContractType::Mint) => self.get_mint_contract(),
-            Ok(SystemContractType::ProofOfStake) => self.get_pos_contract(),
-            Ok(SystemContractType::StandardPayment) => self.get_standard_payment_contract(),
+        let system_contract_type = match SystemContractType::try_from(system_contract_index) {
+            Ok(system_contract_type) => system_contract_type,
             Err(error) => return Ok(Err(error)),
         };
+        let contract_hash = match self.lookup_system_contract(&system_contract_type.to_string())? {
+            Some(contract_hash) => contract_hash,
+            None => return Ok(Err(ApiError::InvalidSystemContract)),
+        };
+
+        match self.memory.set(dest_ptr, &contract_hash) {
+            Ok(_) => Ok(Ok(())),
+            Err(error) => Err(Error::Interpreter(error.into()).into()),
+        }
+    }
+
+    /// Writes the hash of the system contract registered under the name read from Wasm memory at
+    /// `name_ptr` to `dest_ptr`.
+    fn get_system_contract_by_name(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        dest_ptr: u32,
+        _dest_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        let contract_hash = match self.lookup_system_contract(&name)? {
+            Some(contract_hash) => contract_hash,
+            None => return Ok(Err(ApiError::InvalidSystemContract)),
+        };
 
         match self.memory.set(dest_ptr, &contract_hash) {
             Ok(_) => Ok(Ok(())),
@@ -3067,6 +3899,91 @@ where
         }
     }
 
+    /// Looks up the hash of the system contract named `name` in the system contract registry.
+    ///
+    /// State roots which predate the registry fall back to the context's protocol data.
+    fn lookup_system_contract(&mut self, name: &str) -> Result<Option<ContractHash>, Error> {
+        let registry_key = Key::Hash(SYSTEM_CONTRACT_REGISTRY_ADDR);
+        match self.context.read_gs_direct(&registry_key)? {
+            Some(stored_value) => {
+                let cl_value = CLValue::try_from(stored_value).map_err(Error::TypeMismatch)?;
+                let registry: SystemContractRegistry = cl_value.into_t()?;
+                Ok(registry.get(name).copied().and_then(Key::into_hash))
+            }
+            None => {
+                let protocol_data = self.context.protocol_data();
+                Ok(SystemContractType::ALL
+                    .iter()
+                    .find(|system_contract_type| system_contract_type.to_string() == name)
+                    .map(|system_contract_type| {
+                        protocol_data.system_contract(*system_contract_type)
+                    }))
+            }
+        }
+    }
+
+    /// Calculates the BLAKE2b digest of the `in_size` bytes at `in_ptr` and writes it to
+    /// `out_ptr`.
+    ///
+    /// Gas is charged in proportion to the length of the input.
+    fn blake2b(
+        &mut self,
+        in_ptr: u32,
+        in_size: u32,
+        out_ptr: u32,
+        out_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if (out_size as usize) < BLAKE2B_DIGEST_LENGTH {
+            return Ok(Err(ApiError::BufferTooSmall));
+        }
+
+        let cost = U512::from(in_size) * U512::from(BLAKE2B_GAS_PER_BYTE);
+        self.gas(CostCategory::HostFunction, Gas::new(cost))?;
+
+        let input = self.bytes_from_mem(in_ptr, in_size as usize)?;
+        let digest = Blake2bHash::new(&input);
+
+        if let Err(error) = self.memory.set(out_ptr, &digest.value()) {
+            return Err(Error::Interpreter(error.into()).into());
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Verifies that `signature` is a valid Ed25519 signature of `message` by the holder of
+    /// `public_key`.
+    ///
+    /// Malformed public keys or signatures are treated as failing verification.  Gas is charged as
+    /// a flat cost plus an amount in proportion to the length of the message.
+    fn verify_ed25519(
+        &mut self,
+        public_key_ptr: u32,
+        public_key_size: u32,
+        signature_ptr: u32,
+        signature_size: u32,
+        message_ptr: u32,
+        message_size: u32,
+    ) -> Result<bool, Trap> {
+        let cost = U512::from(VERIFY_ED25519_GAS_BASE)
+            + U512::from(message_size) * U512::from(VERIFY_ED25519_GAS_PER_BYTE);
+        self.gas(CostCategory::HostFunction, Gas::new(cost))?;
+
+        let public_key_bytes = self.bytes_from_mem(public_key_ptr, public_key_size as usize)?;
+        let signature_bytes = self.bytes_from_mem(signature_ptr, signature_size as usize)?;
+        let message = self.bytes_from_mem(message_ptr, message_size as usize)?;
+
+        let public_key = match PublicKey::from_bytes(&public_key_bytes) {
+            Ok(public_key) => public_key,
+            Err(_) => return Ok(false),
+        };
+        let signature = match Signature::from_bytes(&signature_bytes) {
+            Ok(signature) => signature,
+            Err(_) => return Ok(false),
+        };
+
+        Ok(public_key.verify(&message, &signature).is_ok())
+    }
+
     /// If host_buffer set, clears the host_buffer and returns value, else None
     pub fn take_host_buffer(&mut self) -> Option<CLValue> {
         self.host_buffer.take()
@@ -3127,6 +4044,7 @@ where
     fn print(&mut self, text_ptr: u32, text_size: u32) -> Result<(), Trap> {
         let text = self.string_from_mem(text_ptr, text_size)?;
         println!("{}", text);
+        PRINTED_MESSAGES.with(|messages| messages.borrow_mut().push(text));
         Ok(())
     }
 
@@ -3312,6 +4230,53 @@ where
         Ok(Ok(()))
     }
 
+    /// Writes the package's user groups, as a map from group label to the group's urefs, to the
+    /// host buffer, and the size of the serialized map to `output_size_ptr` in Wasm memory.
+    ///
+    /// As with the functions modifying groups, the caller must hold the package's access key.
+    fn list_contract_user_groups(
+        &mut self,
+        package_ptr: u32,
+        package_size: u32,
+        access_key_ptr: u32,
+        access_key_size: u32,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let contract_package_hash: ContractPackageHash =
+            self.t_from_mem(package_ptr, package_size)?;
+        let access_key: URef = self.t_from_mem(access_key_ptr, access_key_size)?;
+
+        let contract_package = self
+            .context
+            .get_validated_contract_package(contract_package_hash)?;
+        if access_key.addr() != contract_package.access_key().addr() {
+            return Err(Error::ForgedReference(access_key));
+        }
+        self.context.validate_uref(&access_key)?;
+
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+        let groups: BTreeMap<String, Vec<URef>> = contract_package
+            .groups()
+            .iter()
+            .map(|(group, urefs)| (group.value().to_string(), urefs.iter().copied().collect()))
+            .collect();
+        let value = CLValue::from_t(groups)?;
+        let value_size = value.inner_bytes().len() as u32;
+
+        if let Err(error) = self.write_host_buffer(value) {
+            return Ok(Err(error));
+        }
+
+        let output_size_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(output_size_ptr, &output_size_bytes) {
+            return Err(Error::Interpreter(error.into()));
+        }
+        Ok(Ok(()))
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn remove_contract_user_group_urefs(
         &mut self,
@@ -3370,7 +4335,7 @@ mod tests {
 
     use types::{gens::*, CLType, CLValue, Key, URef};
 
-    use super::extract_urefs;
+    use super::{extract_nested_urefs, extract_urefs};
 
     fn cl_value_with_urefs_arb() -> impl Strategy<Value = (CLValue, Vec<URef>)> {
         // If compiler brings you here it most probably means you've added a variant to `CLType`
@@ -3511,5 +4476,42 @@ mod tests {
             let extracted_urefs = extract_urefs(&cl_value).unwrap();
             assert_eq!(extracted_urefs, urefs);
         }
+
+        #[test]
+        fn should_extract_nested_urefs((cl_value, mut urefs) in cl_value_with_urefs_arb()) {
+            let mut extracted_urefs = extract_nested_urefs(&cl_value).unwrap();
+            extracted_urefs.sort();
+            urefs.sort();
+            assert_eq!(extracted_urefs, urefs);
+        }
+
+        #[test]
+        fn should_extract_deeply_nested_urefs(
+            x in btree_map(".*", vec(option::of((any::<u64>(), key_arb())), 0..10), 0..10)
+        ) {
+            let urefs: Vec<URef> = x
+                .values()
+                .flatten()
+                .flatten()
+                .filter_map(|(_, key)| key.as_uref())
+                .cloned()
+                .collect();
+            let cl_value = CLValue::from_t(x).expect("should create CLValue");
+            assert_eq!(extract_nested_urefs(&cl_value).unwrap(), urefs);
+        }
+    }
+
+    #[test]
+    fn should_only_extract_nested_urefs_from_top_level_any() {
+        let bytes = CLValue::from_t(vec![1u8])
+            .expect("should create CLValue")
+            .inner_bytes()
+            .clone();
+
+        let cl_value = CLValue::from_components(CLType::Any, bytes.clone());
+        assert!(extract_nested_urefs(&cl_value).unwrap().is_empty());
+
+        let cl_value = CLValue::from_components(CLType::List(Box::new(CLType::Any)), bytes);
+        assert!(extract_nested_urefs(&cl_value).is_err());
     }
 }
@@ -1,12 +1,10 @@
-use std::{
-    collections::{BTreeMap, BTreeSet},
-    fmt::Write,
-};
+use std::collections::{BTreeMap, BTreeSet};
 
 use engine_shared::stored_value::StoredValue;
 use engine_storage::global_state::StateReader;
 use proof_of_stake::{
-    MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, Stakes, StakesProvider,
+    Delegations, DelegationsProvider, GasPriceProvider, MintProvider, ProofOfStake, Queue,
+    QueueProvider, RuntimeProvider, Stakes, StakesProvider,
 };
 use types::{
     account::AccountHash, bytesrepr::ToBytes, system_contract_errors::pos::Error, ApiError,
@@ -27,6 +25,14 @@ const UNBONDING_KEY: [u8; 32] = {
     result
 };
 
+const UNBONDING_DELAY_KEY: &str = "unbonding_delay_millis";
+
+const GAS_PRICE_KEY: &str = "gas_price";
+
+const MIN_BOND_AMOUNT_KEY: &str = "min_bond_amount";
+
+const MAX_BOND_AMOUNT_KEY: &str = "max_bond_amount";
+
 // TODO: Update MintProvider to better handle errors
 impl<'a, R> MintProvider for Runtime<'a, R>
 where
@@ -63,6 +69,17 @@ where
     fn balance(&mut self, purse: URef) -> Option<U512> {
         self.get_balance(purse).expect("should get balance")
     }
+
+    fn round_reward(&mut self) -> Result<U512, ()> {
+        let mint_contract_key = self.get_mint_contract();
+        self.mint_round_reward(mint_contract_key).map_err(|_| ())
+    }
+
+    fn mint_into(&mut self, purse: URef, amount: U512) -> Result<(), ()> {
+        let mint_contract_key = self.get_mint_contract();
+        self.mint_into_purse(mint_contract_key, purse, amount)
+            .map_err(|_| ())
+    }
 }
 
 // TODO: Update QueueProvider to better handle errors
@@ -102,6 +119,45 @@ where
             .write_ls(&key, value)
             .expect("should write local state")
     }
+
+    fn read_unbonding_delay(&mut self) -> u64 {
+        let uref = match self.context.named_keys_get(UNBONDING_DELAY_KEY) {
+            Some(Key::URef(uref)) => *uref,
+            _ => return 0,
+        };
+        match self.context.read_gs(&Key::URef(uref)) {
+            Ok(Some(StoredValue::CLValue(cl_value))) => cl_value.into_t().expect("should convert"),
+            _ => 0,
+        }
+    }
+}
+
+// TODO: Update GasPriceProvider to better handle errors
+impl<'a, R> GasPriceProvider for Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    fn write_gas_price(&mut self, gas_price: u64) {
+        let value = StoredValue::CLValue(CLValue::from_t(gas_price).expect("should convert"));
+        match self.context.named_keys_get(GAS_PRICE_KEY) {
+            Some(Key::URef(uref)) => {
+                let key = Key::URef(*uref);
+                self.context
+                    .write_gs(key, value)
+                    .expect("should write gas price")
+            }
+            _ => {
+                let uref = self
+                    .context
+                    .new_uref(value)
+                    .expect("should create gas price uref");
+                self.context
+                    .put_key(GAS_PRICE_KEY.to_string(), Key::URef(uref))
+                    .expect("should put key")
+            }
+        }
+    }
 }
 
 // TODO: Update RuntimeProvider to better handle errors
@@ -135,6 +191,10 @@ where
     fn get_caller(&self) -> AccountHash {
         self.context.get_caller()
     }
+
+    fn is_valid_uref(&self, uref: URef) -> bool {
+        self.context.validate_uref(&uref).is_ok()
+    }
 }
 
 impl<'a, R> StakesProvider for Runtime<'a, R>
@@ -174,21 +234,7 @@ where
 
     fn write(&mut self, stakes: &Stakes) {
         // Encode the stakes as a set of uref names.
-        let mut new_urefs: BTreeSet<String> = stakes
-            .0
-            .iter()
-            .map(|(pub_key, balance)| {
-                let key_bytes = pub_key.value();
-                let mut hex_key = String::with_capacity(64);
-                for byte in &key_bytes[..32] {
-                    write!(hex_key, "{:02x}", byte).expect("Writing to a string cannot fail");
-                }
-                let mut uref = String::new();
-                uref.write_fmt(format_args!("v_{}_{}", hex_key, balance))
-                    .expect("Writing to a string cannot fail");
-                uref
-            })
-            .collect();
+        let mut new_urefs: BTreeSet<String> = stakes.strings().collect();
         // Remove and add urefs to update the contract's known urefs accordingly.
         let mut removes = Vec::new();
         for (name, _) in self.context.named_keys().iter() {
@@ -205,6 +251,66 @@ where
                 .expect("should put key")
         }
     }
+
+    fn read_min_bond_amount(&mut self) -> U512 {
+        self.read_bond_amount(MIN_BOND_AMOUNT_KEY)
+            .unwrap_or_else(U512::zero)
+    }
+
+    fn read_max_bond_amount(&mut self) -> U512 {
+        self.read_bond_amount(MAX_BOND_AMOUNT_KEY)
+            .unwrap_or(U512::MAX)
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    /// Reads the bond amount bound stored under `name`, or `None` if it is unset.
+    fn read_bond_amount(&mut self, name: &str) -> Option<U512> {
+        let uref = match self.context.named_keys_get(name) {
+            Some(Key::URef(uref)) => *uref,
+            _ => return None,
+        };
+        match self.context.read_gs(&Key::URef(uref)) {
+            Ok(Some(StoredValue::CLValue(cl_value))) => {
+                Some(cl_value.into_t().expect("should convert"))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a, R> DelegationsProvider for Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    fn read_delegations(&self) -> Result<Delegations, Error> {
+        Delegations::from_strings(self.context.named_keys().keys().map(String::as_str))
+    }
+
+    fn write_delegations(&mut self, delegations: &Delegations) {
+        // Encode the delegations as a set of uref names.
+        let mut new_urefs: BTreeSet<String> = delegations.strings().collect();
+        // Remove and add urefs to update the contract's known urefs accordingly.
+        let mut removes = Vec::new();
+        for (name, _) in self.context.named_keys().iter() {
+            if name.starts_with("d_") && !new_urefs.remove(name) {
+                removes.push(name.to_owned())
+            }
+        }
+        for name in removes.iter() {
+            self.context.remove_key(name).expect("should remove key")
+        }
+        for name in new_urefs {
+            self.context
+                .put_key(name, Key::Hash([0; 32]))
+                .expect("should put key")
+        }
+    }
 }
 
 impl<'a, R> ProofOfStake for Runtime<'a, R>
@@ -1,12 +1,14 @@
 use std::{
+    cell::RefCell,
     collections::BTreeMap,
     mem,
+    rc::Rc,
     time::{Duration, Instant},
 };
 
 use engine_shared::logging::log_host_function_metrics;
 
-use crate::resolvers::v1_function_index::FunctionIndex;
+use crate::{execution::HostFunctionProfile, resolvers::v1_function_index::FunctionIndex};
 
 enum PauseState {
     NotStarted,
@@ -51,15 +53,22 @@ pub(super) struct ScopedInstrumenter {
     pause_state: PauseState,
     function_index: FunctionIndex,
     properties: BTreeMap<&'static str, String>,
+    profile: Option<Rc<RefCell<HostFunctionProfile>>>,
 }
 
 impl ScopedInstrumenter {
-    pub fn new(function_index: FunctionIndex) -> Self {
+    /// Starts timing a call to `function_index`.  The duration is always logged when the
+    /// instrumenter is dropped, and is also recorded in `profile` if one is given.
+    pub fn new(
+        function_index: FunctionIndex,
+        profile: Option<Rc<RefCell<HostFunctionProfile>>>,
+    ) -> Self {
         ScopedInstrumenter {
             start: Instant::now(),
             pause_state: PauseState::new(),
             function_index,
             properties: BTreeMap::new(),
+            profile,
         }
     }
 
@@ -85,62 +94,15 @@ impl ScopedInstrumenter {
 impl Drop for ScopedInstrumenter {
     fn drop(&mut self) {
         let duration = self.duration();
-        let host_function = match self.function_index {
-            FunctionIndex::GasFuncIndex => return,
-            FunctionIndex::WriteFuncIndex => "host_function_write",
-            FunctionIndex::WriteLocalFuncIndex => "host_function_write_local",
-            FunctionIndex::ReadFuncIndex => "host_function_read_value",
-            FunctionIndex::ReadLocalFuncIndex => "host_function_read_value_local",
-            FunctionIndex::AddFuncIndex => "host_function_add",
-            FunctionIndex::NewFuncIndex => "host_function_new_uref",
-            FunctionIndex::RetFuncIndex => "host_function_ret",
-            FunctionIndex::CallContractFuncIndex => "host_function_call_contract",
-            FunctionIndex::GetKeyFuncIndex => "host_function_get_key",
-            FunctionIndex::HasKeyFuncIndex => "host_function_has_key",
-            FunctionIndex::PutKeyFuncIndex => "host_function_put_key",
-            FunctionIndex::IsValidURefFnIndex => "host_function_is_valid_uref",
-            FunctionIndex::RevertFuncIndex => "host_function_revert",
-            FunctionIndex::AddAssociatedKeyFuncIndex => "host_function_add_associated_key",
-            FunctionIndex::RemoveAssociatedKeyFuncIndex => "host_function_remove_associated_key",
-            FunctionIndex::UpdateAssociatedKeyFuncIndex => "host_function_update_associated_key",
-            FunctionIndex::SetActionThresholdFuncIndex => "host_function_set_action_threshold",
-            FunctionIndex::LoadNamedKeysFuncIndex => "host_function_load_named_keys",
-            FunctionIndex::RemoveKeyFuncIndex => "host_function_remove_key",
-            FunctionIndex::GetCallerIndex => "host_function_get_caller",
-            FunctionIndex::GetBlocktimeIndex => "host_function_get_blocktime",
-            FunctionIndex::CreatePurseIndex => "host_function_create_purse",
-            FunctionIndex::TransferToAccountIndex => "host_function_transfer_to_account",
-            FunctionIndex::TransferFromPurseToAccountIndex => {
-                "host_function_transfer_from_purse_to_account"
-            }
-            FunctionIndex::TransferFromPurseToPurseIndex => {
-                "host_function_transfer_from_purse_to_purse"
-            }
-            FunctionIndex::GetBalanceIndex => "host_function_get_balance",
-            FunctionIndex::GetPhaseIndex => "host_function_get_phase",
-            FunctionIndex::GetSystemContractIndex => "host_function_get_system_contract",
-            FunctionIndex::GetMainPurseIndex => "host_function_get_main_purse",
-            FunctionIndex::ReadHostBufferIndex => "host_function_read_host_buffer",
-            FunctionIndex::CreateContractPackageAtHash => {
-                "host_function_create_contract_package_at_hash"
-            }
-            FunctionIndex::AddContractVersion => "host_function_add_contract_version",
-            FunctionIndex::DisableContractVersion => "host_remove_contract_version",
-            FunctionIndex::CallVersionedContract => "host_call_versioned_contract",
-            FunctionIndex::CreateContractUserGroup => "create_contract_user_group",
-            #[cfg(feature = "test-support")]
-            FunctionIndex::PrintIndex => "host_function_print",
-            FunctionIndex::GetRuntimeArgsizeIndex => "host_get_named_arg_size",
-            FunctionIndex::GetRuntimeArgIndex => "host_get_named_arg",
-            FunctionIndex::RemoveContractUserGroupIndex => "host_remove_contract_user_group",
-            FunctionIndex::ExtendContractUserGroupURefsIndex => {
-                "host_provision_contract_user_group_uref"
-            }
-            FunctionIndex::RemoveContractUserGroupURefsIndex => {
-                "host_remove_contract_user_group_urefs"
-            }
+        let host_function = match self.function_index.host_function_name() {
+            Some(host_function) => host_function,
+            None => return,
         };
 
+        if let Some(profile) = self.profile.as_ref() {
+            profile.borrow_mut().record(self.function_index, duration);
+        }
+
         let mut properties = mem::take(&mut self.properties);
         properties.insert(
             "duration_in_seconds",
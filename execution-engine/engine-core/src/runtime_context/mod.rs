@@ -22,14 +22,15 @@ use types::{
     },
     bytesrepr,
     contracts::NamedKeys,
-    AccessRights, BlockTime, CLType, CLValue, Contract, ContractPackage, ContractPackageHash,
-    EntryPointAccess, EntryPointType, Key, Phase, ProtocolVersion, RuntimeArgs, URef,
-    KEY_HASH_LENGTH,
+    AccessRights, ApiError, BlockTime, CLType, CLValue, Contract, ContractPackage,
+    ContractPackageHash, EntryPointAccess, EntryPointType, Key, Phase, ProtocolVersion,
+    RuntimeArgs, URef, KEY_HASH_LENGTH, U512,
 };
 
 use crate::{
-    engine_state::execution_effect::ExecutionEffect,
+    engine_state::execution_effect::{ContractEvent, ExecutionEffect},
     execution::{AddressGenerator, Error},
+    runtime,
     tracking_copy::{AddResult, TrackingCopy},
     Address,
 };
@@ -37,6 +38,11 @@ use crate::{
 #[cfg(test)]
 mod tests;
 
+/// The maximum number of events a deploy may emit.
+pub const MAX_EVENTS_PER_DEPLOY: usize = 100;
+/// The maximum combined size in bytes of the topics and payloads of the events a deploy may emit.
+pub const MAX_EVENT_BYTES_PER_DEPLOY: usize = 64 * 1024;
+
 /// Checks whether given uref has enough access rights.
 pub(crate) fn uref_has_access_rights(
     uref: &URef,
@@ -368,9 +374,9 @@ where
             });
         }
         let hash: [u8; KEY_HASH_LENGTH] = key_bytes.try_into().unwrap();
-        self.tracking_copy
-            .borrow_mut()
-            .write(hash.into(), StoredValue::CLValue(cl_value));
+        let value = StoredValue::CLValue(cl_value);
+        self.validate_value(&value)?;
+        self.tracking_copy.borrow_mut().write(hash.into(), value);
         Ok(())
     }
 
@@ -422,6 +428,13 @@ where
         Ok(())
     }
 
+    pub fn remove_gs(&mut self, key: Key) -> Result<(), Error> {
+        self.validate_writeable(&key)?;
+        self.validate_key(&key)?;
+        self.tracking_copy.borrow_mut().delete(key);
+        Ok(())
+    }
+
     pub fn read_account(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
         if let Key::Account(_) = key {
             self.validate_key(key)?;
@@ -484,43 +497,28 @@ where
         self.tracking_copy.borrow_mut().effect()
     }
 
-    /// Validates whether keys used in the `value` are not forged.
+    /// Records `event`, unless doing so would take the deploy past [`MAX_EVENTS_PER_DEPLOY`] or
+    /// [`MAX_EVENT_BYTES_PER_DEPLOY`].
+    pub fn emit_event(&mut self, event: ContractEvent) -> Result<(), ApiError> {
+        let mut tracking_copy = self.tracking_copy.borrow_mut();
+        let event_count = tracking_copy.events().len();
+        let event_bytes: usize = tracking_copy.events().iter().map(ContractEvent::size).sum();
+        if event_count >= MAX_EVENTS_PER_DEPLOY
+            || event_bytes.saturating_add(event.size()) > MAX_EVENT_BYTES_PER_DEPLOY
+        {
+            return Err(ApiError::EventLimitExceeded);
+        }
+        tracking_copy.emit_event(event);
+        Ok(())
+    }
+
+    /// Validates every `URef` in `value`, so that a context can't persist rights it doesn't hold,
+    /// however deeply the `URef` is nested inside a compound value.
     fn validate_value(&self, value: &StoredValue) -> Result<(), Error> {
         match value {
-            StoredValue::CLValue(cl_value) => match cl_value.cl_type() {
-                CLType::Bool
-                | CLType::I32
-                | CLType::I64
-                | CLType::U8
-                | CLType::U32
-                | CLType::U64
-                | CLType::U128
-                | CLType::U256
-                | CLType::U512
-                | CLType::Unit
-                | CLType::String
-                | CLType::Option(_)
-                | CLType::List(_)
-                | CLType::FixedList(..)
-                | CLType::Result { .. }
-                | CLType::Map { .. }
-                | CLType::Tuple1(_)
-                | CLType::Tuple3(_)
-                | CLType::Any => Ok(()),
-                CLType::Key => {
-                    let key: Key = cl_value.to_owned().into_t()?; // TODO: optimize?
-                    self.validate_key(&key)
-                }
-                CLType::URef => {
-                    let uref: URef = cl_value.to_owned().into_t()?; // TODO: optimize?
-                    self.validate_uref(&uref)
-                }
-                tuple @ CLType::Tuple2(_) if *tuple == types::named_key_type() => {
-                    let (_name, key): (String, Key) = cl_value.to_owned().into_t()?; // TODO: optimize?
-                    self.validate_key(&key)
-                }
-                CLType::Tuple2(_) => Ok(()),
-            },
+            StoredValue::CLValue(cl_value) => runtime::extract_nested_urefs(cl_value)?
+                .iter()
+                .try_for_each(|uref| self.validate_uref(uref)),
             StoredValue::Account(account) => {
                 // This should never happen as accounts can't be created by contracts.
                 // I am putting this here for the sake of completeness.
@@ -571,6 +569,24 @@ where
         }
     }
 
+    /// Validates a `uref` which is being handed to another context, either as an argument to a
+    /// contract call or as a return value.
+    ///
+    /// Unlike [`RuntimeContext::validate_uref`], a `uref` whose address is known but which has
+    /// more rights than this context holds is reported as an
+    /// [`Error::InvalidAccessRightsEscalation`].
+    pub fn validate_granted_uref(&self, uref: &URef) -> Result<(), Error> {
+        match self.validate_uref(uref) {
+            Err(Error::ForgedReference(uref))
+                if self.access_rights.contains_key(&uref.addr())
+                    || self.account.main_purse().addr() == uref.addr() =>
+            {
+                Err(Error::InvalidAccessRightsEscalation(uref))
+            }
+            result => result,
+        }
+    }
+
     pub fn deserialize_keys(&self, bytes: Vec<u8>) -> Result<Vec<Key>, Error> {
         let keys: Vec<Key> = bytesrepr::deserialize(bytes)?;
         keys.iter().try_for_each(|k| self.validate_key(k))?;
@@ -664,6 +680,24 @@ where
         }
     }
 
+    /// Subtracts `amount` from the `U512` value under `key`.  The key must be writeable, since
+    /// this can reduce the value.
+    pub fn sub_gs(&mut self, key: Key, amount: U512) -> Result<(), Error> {
+        self.validate_writeable(&key)?;
+        self.validate_key(&key)?;
+        match self
+            .tracking_copy
+            .borrow_mut()
+            .sub(self.correlation_id, key, amount)
+        {
+            Err(storage_error) => Err(storage_error.into()),
+            Ok(AddResult::Success) => Ok(()),
+            Ok(AddResult::KeyNotFound(key)) => Err(Error::KeyNotFound(key)),
+            Ok(AddResult::TypeMismatch(type_mismatch)) => Err(Error::TypeMismatch(type_mismatch)),
+            Ok(AddResult::Serialization(error)) => Err(Error::BytesRepr(error)),
+        }
+    }
+
     pub fn add_associated_key(
         &mut self,
         account_hash: AccountHash,
@@ -814,6 +848,16 @@ where
         Ok(())
     }
 
+    /// Reads the deploy's account from the tracking copy, so that changes made to its keys and
+    /// thresholds earlier in the deploy are visible, unlike in `account()`.
+    pub fn read_current_account(&mut self) -> Result<Account, Error> {
+        let key = Key::Account(self.account().account_hash());
+        match self.read_gs_direct(&key)? {
+            Some(stored_value) => stored_value.try_into().map_err(Error::TypeMismatch),
+            None => Err(Error::KeyNotFound(key)),
+        }
+    }
+
     pub fn protocol_data(&self) -> ProtocolData {
         self.protocol_data
     }
@@ -854,4 +898,21 @@ where
         self.validate_uref(&contract_package.access_key())?;
         Ok(contract_package)
     }
+
+    /// Returns the contract package stored under `package_hash` if this context may add new
+    /// versions to it, i.e. if it holds the package's access key.
+    ///
+    /// Knowing the package hash is enough to read the package and call its contracts, so a
+    /// context without the access key fails with [`Error::UpgradeAuthorizationFailure`].
+    pub(crate) fn get_upgradable_contract_package(
+        &mut self,
+        package_hash: ContractPackageHash,
+    ) -> Result<ContractPackage, Error> {
+        let package_hash_key = Key::from(package_hash);
+        self.validate_key(&package_hash_key)?;
+        let contract_package: ContractPackage = self.read_gs_typed(&package_hash_key)?;
+        self.validate_uref(&contract_package.access_key())
+            .map_err(|_| Error::UpgradeAuthorizationFailure(package_hash))?;
+        Ok(contract_package)
+    }
 }
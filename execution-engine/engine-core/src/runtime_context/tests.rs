@@ -11,7 +11,7 @@ use engine_shared::{
     account::{Account, AssociatedKeys},
     additive_map::AdditiveMap,
     gas::Gas,
-    newtypes::CorrelationId,
+    newtypes::{Blake2bHash, CorrelationId},
     stored_value::StoredValue,
     transform::Transform,
 };
@@ -24,14 +24,17 @@ use types::{
         AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, Weight,
     },
     contracts::NamedKeys,
+    system_contract_errors::mint::Error as MintError,
     AccessRights, BlockTime, CLValue, Contract, EntryPointType, EntryPoints, Key, Phase,
-    ProtocolVersion, RuntimeArgs, URef, KEY_HASH_LENGTH,
+    ProtocolVersion, RuntimeArgs, URef, KEY_HASH_LENGTH, U512,
 };
 
+use mint::Mint;
+
 use super::{Address, Error, RuntimeContext};
 use crate::{
-    execution::AddressGenerator, runtime::extract_access_rights_from_keys,
-    tracking_copy::TrackingCopy,
+    engine_state::execution_effect::ExecutionEffect, execution::AddressGenerator,
+    runtime::extract_access_rights_from_keys, tracking_copy::TrackingCopy,
 };
 
 const DEPLOY_HASH: [u8; 32] = [1u8; 32];
@@ -49,7 +52,7 @@ fn mock_tracking_copy(
     let mut m = AdditiveMap::new();
     m.insert(init_key, transform);
     let commit_result = hist
-        .commit(correlation_id, root_hash, m)
+        .commit(correlation_id, root_hash, m, None)
         .expect("Creation of mocked account should be a success.");
 
     let new_hash = match commit_result {
@@ -205,6 +208,74 @@ fn use_uref_forged() {
     assert_forged_reference(query_result);
 }
 
+#[test]
+fn granted_uref_attenuated() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![uref]);
+    let read_only = uref.into_uref().unwrap().into_read();
+
+    let result = test(access_rights, |rc| rc.validate_granted_uref(&read_only));
+
+    result.expect("granting fewer rights than held should succeed");
+}
+
+#[test]
+fn granted_uref_escalated() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref = create_uref(&mut rng, AccessRights::READ);
+    let access_rights = extract_access_rights_from_keys(vec![uref]);
+    let escalated = uref.into_uref().unwrap().into_read_add_write();
+
+    let result = test(access_rights, |rc| rc.validate_granted_uref(&escalated));
+
+    match result {
+        Err(Error::InvalidAccessRightsEscalation(uref)) => assert_eq!(uref, escalated),
+        other => panic!("expected InvalidAccessRightsEscalation, got {:?}", other),
+    }
+}
+
+#[test]
+fn granted_uref_forged() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref = create_uref(&mut rng, AccessRights::READ);
+
+    let result = test(HashMap::new(), |rc| {
+        rc.validate_granted_uref(&uref.into_uref().unwrap())
+    });
+
+    assert_forged_reference(result);
+}
+
+#[test]
+fn write_nested_uref_escalated() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref = create_uref(&mut rng, AccessRights::READ);
+    let target = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![uref, target]);
+    let escalated = uref.into_uref().unwrap().into_read_add_write();
+
+    // The held rights may be stored nested in a compound value...
+    let value = StoredValue::CLValue(CLValue::from_t(vec![uref]).unwrap());
+    let result = test(access_rights.clone(), |mut rc| rc.write_gs(target, value));
+    result.expect("writing held rights should succeed");
+
+    // ...but not more than those, whether in global or local state.
+    let value = StoredValue::CLValue(CLValue::from_t(vec![Key::URef(escalated)]).unwrap());
+    let result = test(access_rights.clone(), |mut rc| rc.write_gs(target, value));
+    assert_forged_reference(result);
+
+    let value = CLValue::from_t(Some(escalated)).unwrap();
+    let result = test(access_rights.clone(), |mut rc| rc.write_ls(&[1; 32], value));
+    assert_forged_reference(result);
+
+    // However deeply they're nested.
+    let value = vec![Some((String::from("escalated"), Key::URef(escalated)))];
+    let value = StoredValue::CLValue(CLValue::from_t(value).unwrap());
+    let result = test(access_rights, |mut rc| rc.write_gs(target, value));
+    assert_forged_reference(result);
+}
+
 #[test]
 fn account_key_not_writeable() {
     let mut rng = rand::thread_rng();
@@ -831,3 +902,111 @@ fn validate_valid_purse_of_an_account() {
     let purse = URef::new([53; 32], AccessRights::READ_ADD_WRITE);
     assert!(runtime_context.validate_uref(&purse).is_err());
 }
+
+const SOURCE_PURSE: [u8; 32] = [10; 32];
+const TARGET_PURSES: [[u8; 32]; 2] = [[11; 32], [12; 32]];
+const SOURCE_BALANCE: [u8; 32] = [20; 32];
+const TARGET_BALANCES: [[u8; 32]; 2] = [[21; 32], [22; 32]];
+const INITIAL_BALANCE: u64 = 1_000;
+
+fn balance_uref(addr: [u8; 32]) -> URef {
+    URef::new(addr, AccessRights::READ_ADD_WRITE)
+}
+
+/// Global state holding `account` and the source and target purses, as the mint lays them out.
+fn mock_purses(account: &Account) -> (InMemoryGlobalState, Blake2bHash) {
+    let purses = iter::once((SOURCE_PURSE, SOURCE_BALANCE)).chain(
+        TARGET_PURSES
+            .iter()
+            .copied()
+            .zip(TARGET_BALANCES.iter().copied()),
+    );
+    let mut pairs = vec![(
+        Key::Account(account.account_hash()),
+        StoredValue::Account(account.clone()),
+    )];
+    for (purse, balance) in purses {
+        let balance_key = Key::URef(balance_uref(balance));
+        pairs.push((
+            Key::Hash(purse),
+            StoredValue::CLValue(CLValue::from_t(balance_key).unwrap()),
+        ));
+        pairs.push((
+            balance_key.normalize(),
+            StoredValue::CLValue(CLValue::from_t(U512::from(INITIAL_BALANCE)).unwrap()),
+        ));
+    }
+    InMemoryGlobalState::from_pairs(CorrelationId::new(), &pairs).unwrap()
+}
+
+/// Transfers `amount` from the source purse to `target` against `root`, returning the outcome
+/// and the effect of the transfer.
+fn mint_transfer(
+    global_state: &InMemoryGlobalState,
+    root: Blake2bHash,
+    account: &Account,
+    target: [u8; 32],
+    amount: u64,
+) -> (Result<(), MintError>, ExecutionEffect) {
+    let reader = global_state.checkout(root).unwrap().unwrap();
+    let access_rights = extract_access_rights_from_keys(
+        iter::once(SOURCE_BALANCE)
+            .chain(TARGET_BALANCES.iter().copied())
+            .map(|balance| Key::URef(balance_uref(balance))),
+    );
+    let mut named_keys = NamedKeys::new();
+    let mut runtime_context = RuntimeContext::new(
+        Rc::new(RefCell::new(TrackingCopy::new(reader))),
+        EntryPointType::Session,
+        &mut named_keys,
+        access_rights,
+        RuntimeArgs::new(),
+        BTreeSet::from_iter(vec![account.account_hash()]),
+        account,
+        Key::Account(account.account_hash()),
+        BlockTime::new(0),
+        DEPLOY_HASH,
+        Gas::default(),
+        Gas::default(),
+        Rc::new(RefCell::new(AddressGenerator::new(&DEPLOY_HASH, PHASE))),
+        Rc::new(RefCell::new(AddressGenerator::new(&DEPLOY_HASH, PHASE))),
+        ProtocolVersion::V1_0_0,
+        CorrelationId::new(),
+        PHASE,
+        Default::default(),
+    );
+    let result = Mint::transfer(
+        &mut runtime_context,
+        URef::new(SOURCE_PURSE, AccessRights::READ_ADD_WRITE),
+        URef::new(target, AccessRights::ADD),
+        U512::from(amount),
+    );
+    (result, runtime_context.effect())
+}
+
+#[test]
+fn concurrent_transfers_from_same_purse_should_commute_unless_overdrawing_it() {
+    let (_, account) = mock_account(AccountHash::new([0u8; 32]));
+    let (global_state, root) = mock_purses(&account);
+    let source_balance_key = Key::URef(balance_uref(SOURCE_BALANCE)).normalize();
+
+    let (result_1, effect_1) = mint_transfer(&global_state, root, &account, TARGET_PURSES[0], 300);
+    let (result_2, effect_2) = mint_transfer(&global_state, root, &account, TARGET_PURSES[1], 400);
+    assert_eq!(result_1, Ok(()));
+    assert_eq!(result_2, Ok(()));
+    // The source balance is only debited, never read, so the transfers don't conflict.
+    assert!(effect_1.conflicts_with(&effect_2).is_empty());
+
+    let (result_3, effect_3) = mint_transfer(&global_state, root, &account, TARGET_PURSES[1], 800);
+    assert_eq!(result_3, Ok(()));
+    assert_eq!(effect_1.conflicts_with(&effect_3), vec![source_balance_key]);
+
+    let (result_4, _) = mint_transfer(
+        &global_state,
+        root,
+        &account,
+        TARGET_PURSES[0],
+        INITIAL_BALANCE + 1,
+    );
+    assert_eq!(result_4, Err(MintError::InsufficientFunds));
+}
@@ -45,6 +45,22 @@ pub trait TrackingCopyExt<R> {
         balance_key: Key,
     ) -> Result<Motes, Self::Error>;
 
+    /// Gets the balance at a given balance key for a check that it is at least `minimum`, without
+    /// recording a read of it.  See `TrackingCopy::get_at_least`.
+    fn get_purse_balance_at_least(
+        &mut self,
+        correlation_id: CorrelationId,
+        balance_key: Key,
+        minimum: Motes,
+    ) -> Result<Motes, Self::Error>;
+
+    /// Gets the gas price at a given key
+    fn get_gas_price(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: Key,
+    ) -> Result<u64, Self::Error>;
+
     /// Gets a contract by Key
     fn get_contract_wasm(
         &mut self,
@@ -88,7 +104,10 @@ where
         account_hash: AccountHash,
     ) -> Result<Account, Self::Error> {
         let account_key = Key::Account(account_hash);
-        match self.get(correlation_id, &account_key).map_err(Into::into)? {
+        match self
+            .get_recording_read(correlation_id, &account_key)
+            .map_err(Into::into)?
+        {
             Some(StoredValue::Account(account)) => Ok(account),
             Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
                 "Account".to_string(),
@@ -164,6 +183,44 @@ where
         }
     }
 
+    fn get_purse_balance_at_least(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: Key,
+        minimum: Motes,
+    ) -> Result<Motes, Self::Error> {
+        let read_result = match self.get_at_least(correlation_id, &key, minimum.value()) {
+            Ok(read_result) => read_result,
+            Err(_) => return Err(execution::Error::KeyNotFound(key)),
+        };
+        match read_result {
+            Some(stored_value) => {
+                let cl_value: CLValue = stored_value
+                    .try_into()
+                    .map_err(execution::Error::TypeMismatch)?;
+                let balance: U512 = cl_value.into_t()?;
+                Ok(Motes::new(balance))
+            }
+            None => Err(execution::Error::KeyNotFound(key)),
+        }
+    }
+
+    fn get_gas_price(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: Key,
+    ) -> Result<u64, Self::Error> {
+        match self.read(correlation_id, &key).map_err(Into::into)? {
+            Some(stored_value) => {
+                let cl_value: CLValue = stored_value
+                    .try_into()
+                    .map_err(execution::Error::TypeMismatch)?;
+                Ok(cl_value.into_t()?)
+            }
+            None => Err(execution::Error::KeyNotFound(key)),
+        }
+    }
+
     /// Gets a contract wasm by Key
     fn get_contract_wasm(
         &mut self,
@@ -171,7 +228,10 @@ where
         contract_wasm_hash: ContractWasmHash,
     ) -> Result<ContractWasm, Self::Error> {
         let key = contract_wasm_hash.into();
-        match self.get(correlation_id, &key).map_err(Into::into)? {
+        match self
+            .get_recording_read(correlation_id, &key)
+            .map_err(Into::into)?
+        {
             Some(StoredValue::ContractWasm(contract_wasm)) => Ok(contract_wasm),
             Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
                 "ContractHeader".to_string(),
@@ -188,7 +248,10 @@ where
         contract_hash: ContractHash,
     ) -> Result<Contract, Self::Error> {
         let key = contract_hash.into();
-        match self.get(correlation_id, &key).map_err(Into::into)? {
+        match self
+            .get_recording_read(correlation_id, &key)
+            .map_err(Into::into)?
+        {
             Some(StoredValue::Contract(contract)) => Ok(contract),
             Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
                 "ContractHeader".to_string(),
@@ -204,7 +267,10 @@ where
         contract_package_hash: ContractPackageHash,
     ) -> Result<ContractPackage, Self::Error> {
         let key = contract_package_hash.into();
-        match self.get(correlation_id, &key).map_err(Into::into)? {
+        match self
+            .get_recording_read(correlation_id, &key)
+            .map_err(Into::into)?
+        {
             Some(StoredValue::ContractPackage(contract_package)) => Ok(contract_package),
             Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
                 "ContractPackage".to_string(),
@@ -5,7 +5,8 @@ pub(self) mod meter;
 mod tests;
 
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
+    cmp,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     convert::From,
     iter,
 };
@@ -19,10 +20,20 @@ use engine_shared::{
     transform::{self, Transform},
     TypeMismatch,
 };
-use engine_storage::global_state::StateReader;
-use types::{bytesrepr, CLType, CLValueError, Key};
+use engine_storage::{global_state::StateReader, trie::TrieMerkleProof};
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
+    CLType, CLValue, CLValueError, Key, URef, U128, U256, U512,
+};
 
-use crate::engine_state::{execution_effect::ExecutionEffect, op::Op};
+use crate::{
+    engine_state::{
+        engine_config::DEFAULT_MAX_QUERY_DEPTH,
+        execution_effect::{ContractEvent, ExecutionEffect},
+        op::Op,
+    },
+    execution,
+};
 
 pub use self::ext::TrackingCopyExt;
 use self::meter::{heap_meter::HeapSize, Meter};
@@ -32,6 +43,7 @@ pub enum TrackingCopyQueryResult {
     Success(StoredValue),
     ValueNotFound(String),
     CircularReference(String),
+    DepthLimitExceeded(String),
 }
 
 /// Struct containing state relating to a given query.
@@ -40,6 +52,13 @@ struct Query {
     base_key: Key,
     /// A collection of normalized keys which have been visited during the search.
     visited_keys: HashSet<Key>,
+    /// The normalized keys which have been visited during the search, in the order they were
+    /// visited.
+    key_chain: Vec<Key>,
+    /// The number of `Key` values which have been dereferenced during the search.
+    depth: u32,
+    /// The maximum value of `depth` before the search is abandoned.
+    max_depth: u32,
     /// The key currently being processed.
     current_key: Key,
     /// Path components which have not yet been followed, held in the same order in which they were
@@ -51,13 +70,16 @@ struct Query {
 }
 
 impl Query {
-    fn new(base_key: Key, path: &[String]) -> Self {
+    fn new(base_key: Key, path: &[String], max_depth: u32) -> Self {
         Query {
             base_key,
             current_key: base_key.normalize(),
             unvisited_names: path.iter().cloned().collect(),
             visited_names: Vec::new(),
             visited_keys: HashSet::new(),
+            key_chain: Vec::new(),
+            depth: 0,
+            max_depth,
         }
     }
 
@@ -75,13 +97,24 @@ impl Query {
 
     fn into_circular_ref_result(self) -> TrackingCopyQueryResult {
         let msg = format!(
-            "{:?} has formed a circular reference at path: {}",
+            "{:?} has formed a circular reference at path: {} via keys: {}",
             self.current_key,
-            self.current_path()
+            self.current_path(),
+            self.current_key_chain()
         );
         TrackingCopyQueryResult::CircularReference(msg)
     }
 
+    fn into_depth_limit_exceeded_result(self) -> TrackingCopyQueryResult {
+        let msg = format!(
+            "Exceeded the maximum of {} key dereferences at path: {} via keys: {}",
+            self.max_depth,
+            self.current_path(),
+            self.current_key_chain()
+        );
+        TrackingCopyQueryResult::DepthLimitExceeded(msg)
+    }
+
     fn current_path(&self) -> String {
         let mut path = format!("{:?}", self.base_key);
         for name in &self.visited_names {
@@ -90,6 +123,15 @@ impl Query {
         }
         path
     }
+
+    fn current_key_chain(&self) -> String {
+        self.key_chain
+            .iter()
+            .chain(iter::once(&self.current_key))
+            .map(|key| format!("{:?}", key))
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
 }
 
 /// Keeps track of already accessed keys.
@@ -100,6 +142,7 @@ pub struct TrackingCopyCache<M> {
     current_cache_size: usize,
     reads_cached: LinkedHashMap<Key, StoredValue>,
     muts_cached: HashMap<Key, StoredValue>,
+    deletes_cached: HashSet<Key>,
     meter: M,
 }
 
@@ -114,6 +157,7 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
             current_cache_size: 0,
             reads_cached: LinkedHashMap::new(),
             muts_cached: HashMap::new(),
+            deletes_cached: HashSet::new(),
             meter,
         }
     }
@@ -136,9 +180,21 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
 
     /// Inserts `key` and `value` pair to Write/Add cache.
     pub fn insert_write(&mut self, key: Key, value: StoredValue) {
+        self.deletes_cached.remove(&key);
         self.muts_cached.insert(key, value);
     }
 
+    /// Marks `key` as deleted, discarding any cached value for it.
+    pub fn insert_delete(&mut self, key: Key) {
+        self.muts_cached.remove(&key);
+        self.deletes_cached.insert(key);
+    }
+
+    /// Returns `true` if `key` has been deleted and not written to since.
+    pub fn is_deleted(&self, key: &Key) -> bool {
+        self.deletes_cached.contains(key)
+    }
+
     /// Gets value from `key` in the cache.
     pub fn get(&mut self, key: &Key) -> Option<&StoredValue> {
         if let Some(value) = self.muts_cached.get(&key) {
@@ -154,6 +210,10 @@ pub struct TrackingCopy<R> {
     cache: TrackingCopyCache<HeapSize>,
     ops: AdditiveMap<Key, Op>,
     fns: AdditiveMap<Key, Transform>,
+    events: Vec<ContractEvent>,
+    /// The lowest value left by a subtraction under each key whose net transform was a
+    /// subtraction at the time.
+    floors: BTreeMap<Key, U512>,
 }
 
 #[derive(Debug)]
@@ -187,6 +247,8 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
              * limit? */
             ops: AdditiveMap::new(),
             fns: AdditiveMap::new(),
+            events: Vec::new(),
+            floors: BTreeMap::new(),
         }
     }
 
@@ -210,11 +272,51 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         TrackingCopy::new(self)
     }
 
+    /// Applies `transforms` to this `TrackingCopy` as though they had been committed to its base
+    /// state.  They are recorded in this `TrackingCopy`'s effect too, so `fork()` it to execute on
+    /// top of uncommitted transforms without reporting them again.
+    pub fn apply_transforms(
+        &mut self,
+        correlation_id: CorrelationId,
+        transforms: &AdditiveMap<Key, Transform>,
+    ) -> Result<(), execution::Error>
+    where
+        R::Error: Into<execution::Error>,
+    {
+        for (key, transform) in transforms.iter() {
+            match transform {
+                Transform::Identity => {}
+                Transform::Delete => self.delete(*key),
+                Transform::Write(value) => self.write(*key, value.to_owned()),
+                transform => {
+                    let current_value = self
+                        .get(correlation_id, key)
+                        .map_err(Into::into)?
+                        .ok_or_else(|| execution::Error::KeyNotFound(*key))?;
+                    let new_value = match transform.to_owned().apply(current_value) {
+                        Ok(new_value) => new_value,
+                        Err(transform::Error::TypeMismatch(type_mismatch)) => {
+                            return Err(execution::Error::TypeMismatch(type_mismatch))
+                        }
+                        Err(transform::Error::Serialization(error)) => {
+                            return Err(execution::Error::BytesRepr(error))
+                        }
+                    };
+                    self.write(*key, new_value);
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn get(
         &mut self,
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, R::Error> {
+        if self.cache.is_deleted(key) {
+            return Ok(None);
+        }
         if let Some(value) = self.cache.get(key) {
             return Ok(Some(value.to_owned()));
         }
@@ -226,19 +328,74 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         }
     }
 
+    /// Gets the value under `key`, recording the read as an `Op::Read` along with a
+    /// `Transform::Identity`.
+    ///
+    /// Reads of missing keys are recorded as an `Op::Read` only: what was observed would change
+    /// if another deploy wrote the key, but there is no value for a transform to apply to.
     pub fn read(
         &mut self,
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, R::Error> {
         let normalized_key = key.normalize();
-        if let Some(value) = self.get(correlation_id, &normalized_key)? {
-            self.ops.insert_add(normalized_key, Op::Read);
+        let maybe_value = self.get(correlation_id, &normalized_key)?;
+        self.ops.insert_add(normalized_key, Op::Read);
+        if maybe_value.is_some() {
             self.fns.insert_add(normalized_key, Transform::Identity);
-            Ok(Some(value))
-        } else {
-            Ok(None)
         }
+        Ok(maybe_value)
+    }
+
+    /// Same as `get()`, but records the read as an `Op::Read` so that conflicts with other deploys
+    /// writing `key` can be detected.  No transform is recorded, so the key doesn't show up in the
+    /// committed effects.
+    pub fn get_recording_read(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, R::Error> {
+        let normalized_key = key.normalize();
+        let maybe_value = self.get(correlation_id, &normalized_key)?;
+        self.ops.insert_add(normalized_key, Op::Read);
+        Ok(maybe_value)
+    }
+
+    /// Gets the value under `key` for a check that it is a `U512` of at least `minimum`.
+    ///
+    /// If the check passes, rather than a read, the value's margin above `minimum` is recorded as
+    /// a floor, in the same way `sub()` records how low it has taken a value, along with an
+    /// `Op::Add` of zero.  The effect then only conflicts with others which could take the value
+    /// below `minimum`, rather than with any change to it.  Otherwise the read is recorded.
+    pub fn get_at_least(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: &Key,
+        minimum: U512,
+    ) -> Result<Option<StoredValue>, R::Error> {
+        let normalized_key = key.normalize();
+        let maybe_value = self.get(correlation_id, &normalized_key)?;
+        let margin = maybe_value
+            .as_ref()
+            .and_then(StoredValue::as_cl_value)
+            .and_then(|cl_value| cl_value.clone().into_t::<U512>().ok())
+            .and_then(|value| value.checked_sub(minimum));
+        match margin {
+            Some(margin) => {
+                self.ops.insert_add(normalized_key, Op::Add);
+                self.fns
+                    .insert_add(normalized_key, Transform::AddUInt512(U512::zero()));
+                let floor = self.floors.entry(normalized_key).or_insert(margin);
+                *floor = cmp::min(*floor, margin);
+            }
+            None => {
+                self.ops.insert_add(normalized_key, Op::Read);
+                if maybe_value.is_some() {
+                    self.fns.insert_add(normalized_key, Transform::Identity);
+                }
+            }
+        }
+        Ok(maybe_value)
     }
 
     pub fn write(&mut self, key: Key, value: StoredValue) {
@@ -248,6 +405,15 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         self.fns.insert_add(normalized_key, Transform::Write(value));
     }
 
+    /// Removes the value stored under `key`.  Subsequent reads of `key` return `None` until it is
+    /// written to again.
+    pub fn delete(&mut self, key: Key) {
+        let normalized_key = key.normalize();
+        self.cache.insert_delete(normalized_key);
+        self.ops.insert_add(normalized_key, Op::Write);
+        self.fns.insert_add(normalized_key, Transform::Delete);
+    }
+
     /// Ok(None) represents missing key to which we want to "add" some value.
     /// Ok(Some(unit)) represents successful operation.
     /// Err(error) is reserved for unexpected errors when accessing global
@@ -313,9 +479,13 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
 
         match transform.clone().apply(current_value) {
             Ok(new_value) => {
+                let new_balance = new_value
+                    .as_cl_value()
+                    .and_then(|cl_value| cl_value.clone().into_t::<U512>().ok());
                 self.cache.insert_write(normalized_key, new_value);
                 self.ops.insert_add(normalized_key, Op::Add);
                 self.fns.insert_add(normalized_key, transform);
+                self.record_debit_floor(normalized_key, new_balance);
                 Ok(AddResult::Success)
             }
             Err(transform::Error::TypeMismatch(type_mismatch)) => {
@@ -325,8 +495,67 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         }
     }
 
+    /// Subtracts `amount` from the `U512` value under `key`, recording the change as a
+    /// `Transform::SubUInt512` so that it commutes with other additions and subtractions.
+    ///
+    /// Returns `AddResult::TypeMismatch` if the value is not a `U512` or would drop below zero.
+    pub fn sub(
+        &mut self,
+        correlation_id: CorrelationId,
+        key: Key,
+        amount: U512,
+    ) -> Result<AddResult, R::Error> {
+        let normalized_key = key.normalize();
+        let current_value = match self.get(correlation_id, &normalized_key)? {
+            None => return Ok(AddResult::KeyNotFound(normalized_key)),
+            Some(current_value) => current_value,
+        };
+
+        let transform = Transform::SubUInt512(amount);
+        match transform.clone().apply(current_value) {
+            Ok(new_value) => {
+                let new_balance = new_value
+                    .as_cl_value()
+                    .and_then(|cl_value| cl_value.clone().into_t::<U512>().ok());
+                self.cache.insert_write(normalized_key, new_value);
+                self.ops.insert_add(normalized_key, Op::Add);
+                self.fns.insert_add(normalized_key, transform);
+                self.record_debit_floor(normalized_key, new_balance);
+                Ok(AddResult::Success)
+            }
+            Err(transform::Error::TypeMismatch(type_mismatch)) => {
+                Ok(AddResult::TypeMismatch(type_mismatch))
+            }
+            Err(transform::Error::Serialization(error)) => Ok(AddResult::Serialization(error)),
+        }
+    }
+
+    /// Lowers the floor under `normalized_key` to `new_balance` if the net transform under it is
+    /// a subtraction.
+    fn record_debit_floor(&mut self, normalized_key: Key, new_balance: Option<U512>) {
+        if let (Some(Transform::SubUInt512(_)), Some(new_balance)) =
+            (self.fns.get(&normalized_key), new_balance)
+        {
+            let floor = self.floors.entry(normalized_key).or_insert(new_balance);
+            *floor = cmp::min(*floor, new_balance);
+        }
+    }
+
+    /// Records an event emitted by a contract.  Events are reported in the effect rather than
+    /// being written to global state.
+    pub fn emit_event(&mut self, event: ContractEvent) {
+        self.events.push(event);
+    }
+
+    /// Returns the events emitted so far, in emission order.
+    pub fn events(&self) -> &[ContractEvent] {
+        &self.events
+    }
+
     pub fn effect(&self) -> ExecutionEffect {
         ExecutionEffect::new(self.ops.clone(), self.fns.clone())
+            .with_events(self.events.clone())
+            .with_debit_floors(&self.floors)
     }
 
     /// Calling `query()` avoids calling into `self.cache`, so this will not return any values
@@ -342,19 +571,86 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         base_key: Key,
         path: &[String],
     ) -> Result<TrackingCopyQueryResult, R::Error> {
-        let mut query = Query::new(base_key, path);
+        self.query_impl(correlation_id, base_key, path, None)
+    }
+
+    /// Same as `query()`, but also returns a Merkle proof for each value read from global state
+    /// while following `path`, in the order they were read.  The last proof is for the value
+    /// returned on success.
+    pub fn query_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        base_key: Key,
+        path: &[String],
+    ) -> Result<
+        (
+            TrackingCopyQueryResult,
+            Vec<TrieMerkleProof<Key, StoredValue>>,
+        ),
+        R::Error,
+    > {
+        let mut proofs = Vec::new();
+        let result = self.query_impl(correlation_id, base_key, path, Some(&mut proofs))?;
+        Ok((result, proofs))
+    }
+
+    fn query_impl(
+        &self,
+        correlation_id: CorrelationId,
+        base_key: Key,
+        path: &[String],
+        maybe_proofs: Option<&mut Vec<TrieMerkleProof<Key, StoredValue>>>,
+    ) -> Result<TrackingCopyQueryResult, R::Error> {
+        query_reader(
+            &self.reader,
+            correlation_id,
+            base_key,
+            path,
+            DEFAULT_MAX_QUERY_DEPTH,
+            maybe_proofs,
+        )
+    }
+}
 
-        loop {
-            if !query.visited_keys.insert(query.current_key) {
-                return Ok(query.into_circular_ref_result());
+/// Follows `path` from `base_key` by reading directly from `reader`.
+///
+/// At most `max_depth` `Key` values are dereferenced in following the path, so that long chains of
+/// keys referring to keys are abandoned rather than followed to their end.
+///
+/// If `maybe_proofs` is `Some`, a Merkle proof is pushed for each value read while following the
+/// path.
+pub(crate) fn query_reader<R: StateReader<Key, StoredValue>>(
+    reader: &R,
+    correlation_id: CorrelationId,
+    base_key: Key,
+    path: &[String],
+    max_depth: u32,
+    mut maybe_proofs: Option<&mut Vec<TrieMerkleProof<Key, StoredValue>>>,
+) -> Result<TrackingCopyQueryResult, R::Error> {
+    let mut query = Query::new(base_key, path, max_depth);
+
+    loop {
+        if !query.visited_keys.insert(query.current_key) {
+            return Ok(query.into_circular_ref_result());
+        }
+        let maybe_stored_value = match maybe_proofs {
+            Some(ref mut proofs) => reader
+                .read_with_proof(correlation_id, &query.current_key)?
+                .map(|(stored_value, proof)| {
+                    proofs.push(proof);
+                    stored_value
+                }),
+            None => reader.read(correlation_id, &query.current_key)?,
+        };
+        let mut stored_value = match maybe_stored_value {
+            None => {
+                return Ok(query.into_not_found_result("Failed to find base key"));
             }
-            let stored_value = match self.reader.read(correlation_id, &query.current_key)? {
-                None => {
-                    return Ok(query.into_not_found_result("Failed to find base key"));
-                }
-                Some(stored_value) => stored_value,
-            };
+            Some(stored_value) => stored_value,
+        };
 
+        // Values held inline, i.e. list elements, are followed without another read.
+        let next_key = loop {
             if query.unvisited_names.is_empty() {
                 return Ok(TrackingCopyQueryResult::Success(stored_value));
             }
@@ -363,23 +659,62 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
                 StoredValue::Account(account) => {
                     let name = query.next_name();
                     if let Some(key) = account.named_keys().get(name) {
-                        query.current_key = key.normalize();
+                        break key.normalize();
                     } else {
                         let msg_prefix = format!("Name {} not found in Account", name);
                         return Ok(query.into_not_found_result(&msg_prefix));
                     }
                 }
                 StoredValue::CLValue(cl_value) if cl_value.cl_type() == &CLType::Key => {
+                    if query.depth >= query.max_depth {
+                        return Ok(query.into_depth_limit_exceeded_result());
+                    }
+                    query.depth += 1;
                     if let Ok(key) = cl_value.into_t::<Key>() {
-                        query.current_key = key.normalize();
+                        break key.normalize();
                     } else {
                         return Ok(query.into_not_found_result("Failed to parse CLValue as Key"));
                     }
                 }
+                StoredValue::CLValue(cl_value) if is_list(cl_value.cl_type()) => {
+                    let index = match query.unvisited_names[0].parse::<usize>() {
+                        Ok(index) => index,
+                        Err(_) => {
+                            let msg_prefix = format!(
+                                "Query cannot continue as {} value found and {} is not a list \
+                                index",
+                                StoredValue::CLValue(cl_value).type_name(),
+                                query.next_name()
+                            );
+                            return Ok(query.into_not_found_result(&msg_prefix));
+                        }
+                    };
+                    query.next_name();
+                    match list_element(&cl_value, index) {
+                        ListElement::Found(element) => {
+                            stored_value = StoredValue::CLValue(element);
+                        }
+                        ListElement::OutOfRange(length) => {
+                            let msg_prefix = format!(
+                                "Index {} out of range for list of length {}",
+                                index, length
+                            );
+                            return Ok(query.into_not_found_result(&msg_prefix));
+                        }
+                        ListElement::Undecodable => {
+                            let msg_prefix = format!(
+                                "Failed to decode element {} of {} value",
+                                index,
+                                StoredValue::CLValue(cl_value).type_name()
+                            );
+                            return Ok(query.into_not_found_result(&msg_prefix));
+                        }
+                    }
+                }
                 StoredValue::CLValue(cl_value) => {
                     let msg_prefix = format!(
-                        "Query cannot continue as {:?} is not an account, contract nor key to \
-                        such.  Value found",
+                        "Query cannot continue as {:?} is not an account, contract, list nor key \
+                        to such.  Value found",
                         cl_value
                     );
                     return Ok(query.into_not_found_result(&msg_prefix));
@@ -387,7 +722,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
                 StoredValue::Contract(contract) => {
                     let name = query.next_name();
                     if let Some(key) = contract.named_keys().get(name) {
-                        query.current_key = key.normalize();
+                        break key.normalize();
                     } else {
                         let msg_prefix = format!("Name {} not found in Contract", name);
                         return Ok(query.into_not_found_result(&msg_prefix));
@@ -400,8 +735,132 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
                     return Ok(query.into_not_found_result(&"ContractWasm value found."));
                 }
             }
+        };
+        query.key_chain.push(query.current_key);
+        query.current_key = next_key;
+    }
+}
+
+fn is_list(cl_type: &CLType) -> bool {
+    match cl_type {
+        CLType::List(_) | CLType::FixedList(..) => true,
+        _ => false,
+    }
+}
+
+/// The outcome of looking up an element of a list `CLValue`.
+enum ListElement {
+    Found(CLValue),
+    /// The index was out of range for a list of the given length.
+    OutOfRange(usize),
+    Undecodable,
+}
+
+/// Returns the element at `index` of `cl_value`, which must be a `List` or a `FixedList`.
+fn list_element(cl_value: &CLValue, index: usize) -> ListElement {
+    let bytes = cl_value.inner_bytes().as_slice();
+    let (element_type, length, mut offset) = match cl_value.cl_type() {
+        CLType::List(element_type) => match u32::from_bytes(bytes) {
+            Ok((length, _)) => (element_type, length as usize, U32_SERIALIZED_LENGTH),
+            Err(_) => return ListElement::Undecodable,
+        },
+        CLType::FixedList(element_type, length) => (element_type, *length as usize, 0),
+        _ => return ListElement::Undecodable,
+    };
+    if index >= length {
+        return ListElement::OutOfRange(length);
+    }
+    // Elements may be of variable size, so skip over the preceding ones one by one.
+    for _ in 0..index {
+        match bytes
+            .get(offset..)
+            .and_then(|rest| serialized_value_length(element_type, rest))
+        {
+            Some(element_length) => offset += element_length,
+            None => return ListElement::Undecodable,
         }
     }
+    match bytes
+        .get(offset..)
+        .and_then(|rest| serialized_value_length(element_type, rest))
+    {
+        Some(element_length) => ListElement::Found(CLValue::from_components(
+            (**element_type).clone(),
+            bytes[offset..offset + element_length].to_vec(),
+        )),
+        None => ListElement::Undecodable,
+    }
+}
+
+/// Returns the number of bytes taken by the serialized value of `T` at the start of `bytes`.
+fn consumed<T: FromBytes>(bytes: &[u8]) -> Option<usize> {
+    T::from_bytes(bytes)
+        .ok()
+        .map(|(_, remainder)| bytes.len() - remainder.len())
+}
+
+/// Returns the number of bytes taken by the serialized value of type `cl_type` at the start of
+/// `bytes`, or `None` if they don't start with a valid value of that type.
+fn serialized_value_length(cl_type: &CLType, bytes: &[u8]) -> Option<usize> {
+    match cl_type {
+        CLType::Bool => consumed::<bool>(bytes),
+        CLType::I32 => consumed::<i32>(bytes),
+        CLType::I64 => consumed::<i64>(bytes),
+        CLType::U8 => consumed::<u8>(bytes),
+        CLType::U32 => consumed::<u32>(bytes),
+        CLType::U64 => consumed::<u64>(bytes),
+        CLType::U128 => consumed::<U128>(bytes),
+        CLType::U256 => consumed::<U256>(bytes),
+        CLType::U512 => consumed::<U512>(bytes),
+        CLType::Unit => Some(0),
+        CLType::String => consumed::<String>(bytes),
+        CLType::Key => consumed::<Key>(bytes),
+        CLType::URef => consumed::<URef>(bytes),
+        CLType::Option(inner) => match bytes.first()? {
+            0 => Some(U8_SERIALIZED_LENGTH),
+            1 => Some(U8_SERIALIZED_LENGTH + serialized_value_length(inner, &bytes[1..])?),
+            _ => None,
+        },
+        CLType::List(inner) => {
+            let (length, rest) = u32::from_bytes(bytes).ok()?;
+            let elements_length =
+                sequence_length(iter::repeat(&**inner).take(length as usize), rest)?;
+            Some(U32_SERIALIZED_LENGTH + elements_length)
+        }
+        CLType::FixedList(inner, length) => {
+            sequence_length(iter::repeat(&**inner).take(*length as usize), bytes)
+        }
+        CLType::Result { ok, err } => match bytes.first()? {
+            0 => Some(U8_SERIALIZED_LENGTH + serialized_value_length(err, &bytes[1..])?),
+            1 => Some(U8_SERIALIZED_LENGTH + serialized_value_length(ok, &bytes[1..])?),
+            _ => None,
+        },
+        CLType::Map { key, value } => {
+            let (length, rest) = u32::from_bytes(bytes).ok()?;
+            let entry_types = iter::repeat(&**key)
+                .zip(iter::repeat(&**value))
+                .take(length as usize)
+                .flat_map(|(key, value)| iter::once(key).chain(iter::once(value)));
+            Some(U32_SERIALIZED_LENGTH + sequence_length(entry_types, rest)?)
+        }
+        CLType::Tuple1([t1]) => sequence_length(iter::once(&**t1), bytes),
+        CLType::Tuple2([t1, t2]) => sequence_length(vec![&**t1, &**t2], bytes),
+        CLType::Tuple3([t1, t2, t3]) => sequence_length(vec![&**t1, &**t2, &**t3], bytes),
+        CLType::Any => None,
+    }
+}
+
+/// Returns the number of bytes taken by consecutive serialized values of the given types at the
+/// start of `bytes`.
+fn sequence_length<'a, I: IntoIterator<Item = &'a CLType>>(
+    cl_types: I,
+    bytes: &[u8],
+) -> Option<usize> {
+    let mut offset = 0;
+    for cl_type in cl_types {
+        offset += serialized_value_length(cl_type, bytes.get(offset..)?)?;
+    }
+    Some(offset)
 }
 
 /// The purpose of this implementation is to allow a "snapshot" mechanism for
@@ -417,6 +876,9 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
+        if self.cache.is_deleted(key) {
+            return Ok(None);
+        }
         if let Some(value) = self.cache.muts_cached.get(key) {
             return Ok(Some(value.to_owned()));
         }
@@ -426,4 +888,34 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
             Ok(None)
         }
     }
+    /// Values written or mutated in this `TrackingCopy` are not yet part of global state, so no
+    /// proof can be given for them and `None` is returned.
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<(StoredValue, TrieMerkleProof<Key, StoredValue>)>, Self::Error> {
+        if self.cache.muts_cached.contains_key(key) || self.cache.is_deleted(key) {
+            return Ok(None);
+        }
+        self.reader.read_with_proof(correlation_id, key)
+    }
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        let mut ret = self.reader.keys_with_prefix(correlation_id, prefix)?;
+        ret.retain(|key| !self.cache.is_deleted(key));
+        for key in self.cache.muts_cached.keys() {
+            let matches_prefix = key
+                .to_bytes()
+                .map(|key_bytes| key_bytes.starts_with(prefix))
+                .unwrap_or_default();
+            if matches_prefix && !ret.contains(key) {
+                ret.push(*key);
+            }
+        }
+        Ok(ret)
+    }
 }
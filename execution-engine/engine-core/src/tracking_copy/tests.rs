@@ -5,11 +5,15 @@ use proptest::prelude::*;
 
 use engine_shared::{
     account::{Account, AssociatedKeys},
+    additive_map::AdditiveMap,
     newtypes::CorrelationId,
     stored_value::{gens::stored_value_arb, StoredValue},
     transform::Transform,
 };
-use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader};
+use engine_storage::{
+    global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader},
+    trie::{self, TrieMerkleProof},
+};
 use types::{
     account::{AccountHash, Weight, ACCOUNT_HASH_LENGTH},
     contracts::NamedKeys,
@@ -18,9 +22,10 @@ use types::{
 };
 
 use super::{
-    meter::count_meter::Count, AddResult, TrackingCopy, TrackingCopyCache, TrackingCopyQueryResult,
+    meter::count_meter::Count, query_reader, AddResult, TrackingCopy, TrackingCopyCache,
+    TrackingCopyQueryResult,
 };
-use crate::engine_state::op::Op;
+use crate::{engine_state::op::Op, execution};
 
 struct CountingDb {
     count: Rc<Cell<i32>>,
@@ -58,6 +63,20 @@ impl StateReader<Key, StoredValue> for CountingDb {
         self.count.set(count + 1);
         Ok(Some(value))
     }
+    fn read_with_proof(
+        &self,
+        _correlation_id: CorrelationId,
+        _key: &Key,
+    ) -> Result<Option<(StoredValue, TrieMerkleProof<Key, StoredValue>)>, Self::Error> {
+        Ok(None)
+    }
+    fn keys_with_prefix(
+        &self,
+        _correlation_id: CorrelationId,
+        _prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        Ok(Vec::new())
+    }
 }
 
 #[test]
@@ -111,6 +130,37 @@ fn tracking_copy_read() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Read));
 }
 
+#[test]
+fn tracking_copy_read_missing_key() {
+    let correlation_id = CorrelationId::new();
+    let (gs, root_hash) = InMemoryGlobalState::from_pairs(correlation_id, &[]).unwrap();
+    let view = gs.checkout(root_hash).unwrap().unwrap();
+    let mut tc = TrackingCopy::new(view);
+    let k = Key::Hash([0u8; 32]);
+
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), None);
+    // the read is recorded, but there is no value for a transform to apply to
+    assert_eq!(tc.ops.get(&k), Some(&Op::Read));
+    assert!(tc.fns.is_empty());
+}
+
+#[test]
+fn tracking_copy_get_recording_read() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(counter);
+    let mut tc = TrackingCopy::new(db);
+    let k = Key::Hash([0u8; 32]);
+
+    let zero = StoredValue::CLValue(CLValue::from_t(0_i32).unwrap());
+    let value = tc.get_recording_read(correlation_id, &k).unwrap();
+    assert_eq!(value, Some(zero));
+    // the read produces an op, but no transform
+    assert_eq!(tc.ops.len(), 1);
+    assert_eq!(tc.ops.get(&k), Some(&Op::Read));
+    assert!(tc.fns.is_empty());
+}
+
 #[test]
 fn tracking_copy_write() {
     let counter = Rc::new(Cell::new(0));
@@ -143,6 +193,82 @@ fn tracking_copy_write() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Write));
 }
 
+#[test]
+fn tracking_copy_delete() {
+    let correlation_id = CorrelationId::new();
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let two = StoredValue::CLValue(CLValue::from_t(2_i32).unwrap());
+    let db = CountingDb::new_init(one.clone());
+    let mut tc = TrackingCopy::new(db);
+    let k = Key::Hash([0u8; 32]);
+
+    // the value is cached after reading it
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), Some(one));
+
+    // deleting hides the cached value
+    tc.delete(k);
+    assert_eq!(tc.get(correlation_id, &k).unwrap(), None);
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), None);
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Delete));
+    assert_eq!(tc.ops.get(&k), Some(&Op::Write));
+
+    // a forked copy doesn't see the deleted value either
+    assert_eq!(tc.fork().read(correlation_id, &k).unwrap(), None);
+
+    // writing after deleting makes the key readable again
+    tc.write(k, two.clone());
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), Some(two.clone()));
+    assert_eq!(tc.fns.get(&k), Some(&Transform::Write(two)));
+}
+
+#[test]
+fn tracking_copy_apply_transforms() {
+    let correlation_id = CorrelationId::new();
+    let value = |v: i32| StoredValue::CLValue(CLValue::from_t(v).unwrap());
+    let added_key = Key::Hash([1u8; 32]);
+    let written_key = Key::Hash([2u8; 32]);
+    let deleted_key = Key::Hash([3u8; 32]);
+    let (global_state, root_hash) = InMemoryGlobalState::from_pairs(
+        correlation_id,
+        &[(added_key, value(1)), (deleted_key, value(3))],
+    )
+    .unwrap();
+    let view = global_state.checkout(root_hash).unwrap().unwrap();
+    let mut tc = TrackingCopy::new(view);
+
+    let mut transforms = AdditiveMap::new();
+    transforms.insert(added_key, Transform::AddInt32(2));
+    transforms.insert(written_key, Transform::Write(value(2)));
+    transforms.insert(deleted_key, Transform::Delete);
+    tc.apply_transforms(correlation_id, &transforms).unwrap();
+
+    // a fork reads the transformed values, without reporting the transforms in its effect
+    let mut fork = tc.fork();
+    assert_eq!(
+        fork.read(correlation_id, &added_key).unwrap(),
+        Some(value(3))
+    );
+    assert_eq!(
+        fork.read(correlation_id, &written_key).unwrap(),
+        Some(value(2))
+    );
+    assert_eq!(fork.read(correlation_id, &deleted_key).unwrap(), None);
+    assert!(fork
+        .effect()
+        .transforms
+        .values()
+        .all(|transform| *transform == Transform::Identity));
+
+    // transforms other than writes can't be applied to missing keys
+    let missing_key = Key::Hash([4u8; 32]);
+    let mut transforms = AdditiveMap::new();
+    transforms.insert(missing_key, Transform::AddInt32(1));
+    assert_matches!(
+        tc.apply_transforms(correlation_id, &transforms),
+        Err(execution::Error::KeyNotFound(key)) if key == missing_key
+    );
+}
+
 #[test]
 fn tracking_copy_add_i32() {
     let correlation_id = CorrelationId::new();
@@ -544,3 +670,259 @@ fn query_for_circular_references_should_fail() {
         panic!("Query didn't fail with a circular reference error");
     }
 }
+
+#[test]
+fn query_for_key_cycle_should_name_key_chain() {
+    // create two keys each holding the other
+    let key_a = Key::URef(URef::new([1; 32], AccessRights::READ_ADD_WRITE));
+    let key_b = Key::URef(URef::new([2; 32], AccessRights::READ_ADD_WRITE));
+    let value_a = StoredValue::CLValue(CLValue::from_t(key_b).unwrap());
+    let value_b = StoredValue::CLValue(CLValue::from_t(key_a).unwrap());
+
+    let correlation_id = CorrelationId::new();
+    let (global_state, root_hash) =
+        InMemoryGlobalState::from_pairs(correlation_id, &[(key_a, value_a), (key_b, value_b)])
+            .unwrap();
+    let view = global_state.checkout(root_hash).unwrap().unwrap();
+    let tracking_copy = TrackingCopy::new(view);
+
+    let path = vec![String::new()];
+    let result = tracking_copy.query(correlation_id, key_a, &path).unwrap();
+    if let TrackingCopyQueryResult::CircularReference(msg) = result {
+        let expected_chain = format!(
+            "via keys: {:?} -> {:?} -> {:?}",
+            key_a.normalize(),
+            key_b.normalize(),
+            key_a.normalize()
+        );
+        assert!(msg.contains(&expected_chain), "{}", msg);
+    } else {
+        panic!("Query didn't fail with a circular reference error");
+    }
+}
+
+#[test]
+fn query_should_fail_beyond_max_depth() {
+    // create a chain of keys, each holding the next, ending in a non-key value
+    let keys: Vec<Key> = (1..=4u8)
+        .map(|i| Key::URef(URef::new([i; 32], AccessRights::READ)))
+        .collect();
+    let mut pairs: Vec<(Key, StoredValue)> = keys
+        .windows(2)
+        .map(|window| {
+            (
+                window[0],
+                StoredValue::CLValue(CLValue::from_t(window[1]).unwrap()),
+            )
+        })
+        .collect();
+    pairs.push((
+        keys[3],
+        StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()),
+    ));
+
+    let correlation_id = CorrelationId::new();
+    let (global_state, root_hash) =
+        InMemoryGlobalState::from_pairs(correlation_id, &pairs).unwrap();
+    let view = global_state.checkout(root_hash).unwrap().unwrap();
+
+    // the value can't be followed any further, so the query only fails if the chain is abandoned
+    // before it's reached
+    let path = vec![String::new()];
+    let result = query_reader(&view, correlation_id, keys[0], &path, 3, None).unwrap();
+    assert_matches!(result, TrackingCopyQueryResult::ValueNotFound(_));
+
+    let result = query_reader(&view, correlation_id, keys[0], &path, 2, None).unwrap();
+    if let TrackingCopyQueryResult::DepthLimitExceeded(msg) = result {
+        let expected_chain = format!(
+            "via keys: {:?} -> {:?} -> {:?}",
+            keys[0].normalize(),
+            keys[1].normalize(),
+            keys[2].normalize()
+        );
+        assert!(msg.contains(&expected_chain), "{}", msg);
+    } else {
+        panic!("Query didn't fail with a depth limit error");
+    }
+}
+
+#[test]
+fn query_with_proof_should_return_valid_proof_for_each_key_on_path() {
+    let value_key = Key::URef(URef::new([255; 32], AccessRights::READ));
+    let value = StoredValue::CLValue(CLValue::from_t(1_u64).unwrap());
+    let value_name = "value".to_string();
+
+    let contract_key = Key::Hash([1; 32]);
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(value_name.clone(), value_key);
+    let contract = StoredValue::Contract(Contract::new(
+        [2; 32],
+        [3; 32],
+        named_keys,
+        EntryPoints::default(),
+        ProtocolVersion::V1_0_0,
+    ));
+
+    let correlation_id = CorrelationId::new();
+    let (global_state, root_hash) = InMemoryGlobalState::from_pairs(
+        correlation_id,
+        &[(value_key, value.clone()), (contract_key, contract.clone())],
+    )
+    .unwrap();
+    let view = global_state.checkout(root_hash).unwrap().unwrap();
+    let tracking_copy = TrackingCopy::new(view);
+
+    let (result, proofs) = tracking_copy
+        .query_with_proof(correlation_id, contract_key, &[value_name])
+        .unwrap();
+    assert_matches!(result, TrackingCopyQueryResult::Success(ref found) if *found == value);
+    assert_eq!(proofs.len(), 2);
+    assert!(trie::validate_proof(
+        &root_hash,
+        &contract_key,
+        &contract,
+        &proofs[0]
+    ));
+    assert!(trie::validate_proof(
+        &root_hash,
+        &value_key.normalize(),
+        &value,
+        &proofs[1]
+    ));
+    assert!(!trie::validate_proof(
+        &root_hash,
+        &value_key.normalize(),
+        &contract,
+        &proofs[1]
+    ));
+}
+
+#[test]
+fn query_should_index_into_list_values() {
+    let list_key = Key::URef(URef::new([255; 32], AccessRights::READ));
+    let list = vec!["a".to_string(), "bb".to_string(), "ccc".to_string()];
+    let list_value = StoredValue::CLValue(CLValue::from_t(list).unwrap());
+    let fixed_list_key = Key::URef(URef::new([254; 32], AccessRights::READ));
+    let fixed_list_value = StoredValue::CLValue(CLValue::from_t([7_u64, 8, 9]).unwrap());
+
+    let contract_key = Key::Hash([1; 32]);
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert("list".to_string(), list_key);
+    named_keys.insert("fixed_list".to_string(), fixed_list_key);
+    let contract = StoredValue::Contract(Contract::new(
+        [2; 32],
+        [3; 32],
+        named_keys,
+        EntryPoints::default(),
+        ProtocolVersion::V1_0_0,
+    ));
+
+    let correlation_id = CorrelationId::new();
+    let (global_state, root_hash) = InMemoryGlobalState::from_pairs(
+        correlation_id,
+        &[
+            (list_key, list_value),
+            (fixed_list_key, fixed_list_value),
+            (contract_key, contract),
+        ],
+    )
+    .unwrap();
+    let view = global_state.checkout(root_hash).unwrap().unwrap();
+    let tracking_copy = TrackingCopy::new(view);
+
+    let path = vec!["list".to_string(), "2".to_string()];
+    let expected = StoredValue::CLValue(CLValue::from_t("ccc".to_string()).unwrap());
+    let result = tracking_copy
+        .query(correlation_id, contract_key, &path)
+        .unwrap();
+    assert_matches!(result, TrackingCopyQueryResult::Success(ref found) if *found == expected);
+
+    let path = vec!["fixed_list".to_string(), "1".to_string()];
+    let expected = StoredValue::CLValue(CLValue::from_t(8_u64).unwrap());
+    let result = tracking_copy
+        .query(correlation_id, contract_key, &path)
+        .unwrap();
+    assert_matches!(result, TrackingCopyQueryResult::Success(ref found) if *found == expected);
+
+    let path = vec!["list".to_string(), "3".to_string()];
+    let result = tracking_copy
+        .query(correlation_id, contract_key, &path)
+        .unwrap();
+    assert_matches!(
+        result,
+        TrackingCopyQueryResult::ValueNotFound(ref msg) if msg.contains("out of range")
+    );
+
+    let path = vec!["list".to_string(), "first".to_string()];
+    let result = tracking_copy
+        .query(correlation_id, contract_key, &path)
+        .unwrap();
+    assert_matches!(
+        result,
+        TrackingCopyQueryResult::ValueNotFound(ref msg) if msg.contains("is not a list index")
+    );
+}
+
+#[test]
+fn query_should_follow_keys_held_in_lists() {
+    let numbers_key = Key::URef(URef::new([255; 32], AccessRights::READ));
+    let numbers_value = StoredValue::CLValue(CLValue::from_t(vec![10_i32, 20, 30]).unwrap());
+    let other_key = Key::URef(URef::new([254; 32], AccessRights::READ));
+    let other_value = StoredValue::CLValue(CLValue::from_t(1_u64).unwrap());
+
+    let keys_key = Key::URef(URef::new([253; 32], AccessRights::READ));
+    let keys_value = StoredValue::CLValue(CLValue::from_t(vec![other_key, numbers_key]).unwrap());
+
+    let contract_key = Key::Hash([1; 32]);
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert("keys".to_string(), keys_key);
+    let contract = StoredValue::Contract(Contract::new(
+        [2; 32],
+        [3; 32],
+        named_keys,
+        EntryPoints::default(),
+        ProtocolVersion::V1_0_0,
+    ));
+
+    let correlation_id = CorrelationId::new();
+    let (global_state, root_hash) = InMemoryGlobalState::from_pairs(
+        correlation_id,
+        &[
+            (numbers_key, numbers_value),
+            (other_key, other_value),
+            (keys_key, keys_value),
+            (contract_key, contract),
+        ],
+    )
+    .unwrap();
+    let view = global_state.checkout(root_hash).unwrap().unwrap();
+    let tracking_copy = TrackingCopy::new(view);
+
+    // Stopping at the list element returns the key itself.
+    let path = vec!["keys".to_string(), "1".to_string()];
+    let expected = StoredValue::CLValue(CLValue::from_t(numbers_key).unwrap());
+    let result = tracking_copy
+        .query(correlation_id, contract_key, &path)
+        .unwrap();
+    assert_matches!(result, TrackingCopyQueryResult::Success(ref found) if *found == expected);
+
+    // Further path segments continue from the value under that key.
+    let path = vec!["keys".to_string(), "1".to_string(), "2".to_string()];
+    let expected = StoredValue::CLValue(CLValue::from_t(30_i32).unwrap());
+    let (result, proofs) = tracking_copy
+        .query_with_proof(correlation_id, contract_key, &path)
+        .unwrap();
+    assert_matches!(result, TrackingCopyQueryResult::Success(ref found) if *found == expected);
+    // One proof per read: the contract, the key list and the number list.
+    assert_eq!(proofs.len(), 3);
+}
+
+proptest! {
+    #[test]
+    fn serialized_value_length_should_match_encoding(cl_value in cl_value_arb()) {
+        prop_assert_eq!(
+            super::serialized_value_length(cl_value.cl_type(), cl_value.inner_bytes()),
+            Some(cl_value.inner_bytes().len())
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::balance::BalanceRequest;
+
+use crate::engine_server::{
+    ipc,
+    mappings::{parse_blake2b_hash, MappingError},
+};
+
+impl TryFrom<ipc::BalanceRequest> for BalanceRequest {
+    type Error = MappingError;
+
+    fn try_from(mut balance_request: ipc::BalanceRequest) -> Result<Self, Self::Error> {
+        let state_hash = parse_blake2b_hash(balance_request.get_state_hash())?;
+
+        let purse_uref = balance_request
+            .take_purse_uref()
+            .try_into()
+            .map_err(MappingError::Parsing)?;
+
+        Ok(BalanceRequest::new(state_hash, purse_uref))
+    }
+}
@@ -0,0 +1,31 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::query::BatchQueryRequest;
+
+use crate::engine_server::{
+    ipc,
+    mappings::{parse_blake2b_hash, MappingError},
+};
+
+impl TryFrom<ipc::BatchQueryRequest> for BatchQueryRequest {
+    type Error = MappingError;
+
+    fn try_from(mut batch_query_request: ipc::BatchQueryRequest) -> Result<Self, Self::Error> {
+        let state_hash = parse_blake2b_hash(batch_query_request.get_state_hash())?;
+
+        let queries = batch_query_request
+            .take_queries()
+            .into_iter()
+            .map(|mut query| {
+                let key = query
+                    .take_base_key()
+                    .try_into()
+                    .map_err(MappingError::Parsing)?;
+                let path = query.take_path().into_vec();
+                Ok((key, path))
+            })
+            .collect::<Result<Vec<_>, MappingError>>()?;
+
+        Ok(BatchQueryRequest::new(state_hash, queries))
+    }
+}
@@ -0,0 +1,20 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::bonded_validators::BondedValidatorsRequest;
+
+use crate::engine_server::{
+    ipc::GetBondedValidatorsRequest,
+    mappings::{parse_blake2b_hash, MappingError},
+};
+
+impl TryFrom<GetBondedValidatorsRequest> for BondedValidatorsRequest {
+    type Error = MappingError;
+
+    fn try_from(mut request: GetBondedValidatorsRequest) -> Result<Self, Self::Error> {
+        let state_hash = parse_blake2b_hash(request.get_state_hash())?;
+
+        let protocol_version = request.take_protocol_version().into();
+
+        Ok(BondedValidatorsRequest::new(state_hash, protocol_version))
+    }
+}
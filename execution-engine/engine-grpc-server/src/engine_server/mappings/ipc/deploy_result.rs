@@ -1,24 +1,44 @@
 use engine_core::{
     engine_state::{
-        execution_effect::ExecutionEffect, execution_result::ExecutionResult,
+        execution_effect::ExecutionEffect,
+        execution_result::{ExecutionResult, PhaseCosts},
         Error as EngineStateError,
     },
-    execution::Error as ExecutionError,
+    execution::{Error as ExecutionError, ErrorCode, HostFunctionProfile},
 };
-use engine_shared::gas::Gas;
+use engine_shared::gas::{CostBreakdown, Gas};
 
-use crate::engine_server::ipc::{DeployError_OutOfGasError, DeployResult};
+use crate::engine_server::ipc::{
+    DeployError_ExecutionError_Code, DeployError_OutOfGasError, DeployResult,
+    DeployResult_ExecutionResult, DeployResult_HostFunctionStats,
+    DeployResult_PreconditionFailure_Code as PreconditionFailureCode,
+};
 
 impl From<ExecutionResult> for DeployResult {
     fn from(execution_result: ExecutionResult) -> DeployResult {
-        match execution_result {
-            ExecutionResult::Success { effect, cost } => detail::execution_success(effect, cost),
+        let cost_breakdown = execution_result.cost_breakdown().clone();
+        let host_function_profile = execution_result.host_function_profile().clone();
+        let charged = execution_result.charged();
+        let phase_costs = execution_result.phase_costs();
+        let mut pb_deploy_result: DeployResult = match execution_result {
+            ExecutionResult::Success { effect, cost, .. } => {
+                detail::execution_success(effect, cost)
+            }
             ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
+                ..
             } => (error, effect, cost).into(),
+        };
+        if pb_deploy_result.has_execution_result() {
+            let pb_execution_result = pb_deploy_result.mut_execution_result();
+            detail::set_cost_breakdown(pb_execution_result, &cost_breakdown);
+            detail::set_host_function_profile(pb_execution_result, &host_function_profile);
+            pb_execution_result.set_motes_charged(charged.value().into());
+            detail::set_phase_costs(pb_execution_result, phase_costs);
         }
+        pb_deploy_result
     }
 }
 
@@ -31,27 +51,43 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
             error @ EngineStateError::InvalidHashLength { .. }
             | error @ EngineStateError::InvalidAccountHashLength { .. }
             | error @ EngineStateError::InvalidProtocolVersion { .. }
-            | error @ EngineStateError::InvalidUpgradeConfig
-            | error @ EngineStateError::WasmPreprocessing(_)
-            | error @ EngineStateError::WasmSerialization(_)
-            | error @ EngineStateError::Exec(ExecutionError::DeploymentAuthorizationFailure)
             | error @ EngineStateError::InvalidKeyVariant(_)
-            | error @ EngineStateError::Authorization
             | error @ EngineStateError::InvalidDeployItemVariant(_)
+            | error @ EngineStateError::ModuleTooLarge { .. }
+            | error @ EngineStateError::ArgsTooLarge { .. } => {
+                detail::precondition_error(error, PreconditionFailureCode::INVALID_ARGUMENT)
+            }
+            error @ EngineStateError::Exec(ExecutionError::DeploymentAuthorizationFailure)
+            | error @ EngineStateError::Authorization
+            | error @ EngineStateError::AccountNotFound(_) => {
+                detail::precondition_error(error, PreconditionFailureCode::AUTHORIZATION)
+            }
+            error @ EngineStateError::WasmPreprocessing(_)
+            | error @ EngineStateError::WasmSerialization(_) => {
+                detail::precondition_error(error, PreconditionFailureCode::WASM_PREPROCESSING)
+            }
+            error @ EngineStateError::InvalidUpgradeConfig
             | error @ EngineStateError::InvalidUpgradeResult => {
-                detail::precondition_error(error.to_string())
+                detail::precondition_error(error, PreconditionFailureCode::UPGRADE)
+            }
+            // The deploy wasn't started, as the engine is shutting down.
+            error @ EngineStateError::Closing => {
+                detail::precondition_error(error, PreconditionFailureCode::UNKNOWN)
             }
             EngineStateError::Storage(storage_error) => {
-                detail::execution_error(storage_error, effect, cost)
+                detail::execution_error(storage_error, ErrorCode::Storage, effect, cost)
             }
             EngineStateError::MissingSystemContract(msg) => {
-                detail::execution_error(msg, effect, cost)
+                detail::execution_error(msg, ErrorCode::Unknown, effect, cost)
             }
             error @ EngineStateError::InsufficientPayment
             | error @ EngineStateError::Deploy
             | error @ EngineStateError::Finalization
             | error @ EngineStateError::Serialization(_)
-            | error @ EngineStateError::Mint(_) => detail::execution_error(error, effect, cost),
+            | error @ EngineStateError::Mint(_) => {
+                let code = error.code();
+                detail::execution_error(error, code, effect, cost)
+            }
             EngineStateError::Exec(exec_error) => (exec_error, effect, cost).into(),
         }
     }
@@ -59,23 +95,29 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
 
 impl From<(ExecutionError, ExecutionEffect, Gas)> for DeployResult {
     fn from((exec_error, effect, cost): (ExecutionError, ExecutionEffect, Gas)) -> Self {
+        let code = exec_error.code();
         match exec_error {
             ExecutionError::GasLimit => detail::out_of_gas_error(effect, cost),
             ExecutionError::KeyNotFound(key) => {
-                detail::execution_error(format!("Key {:?} not found.", key), effect, cost)
+                detail::execution_error(format!("Key {:?} not found.", key), code, effect, cost)
             }
             ExecutionError::Revert(status) => {
-                detail::execution_error(status.to_string(), effect, cost)
+                detail::execution_error(status.to_string(), code, effect, cost)
+            }
+            ExecutionError::Interpreter(error) => {
+                detail::execution_error(error, code, effect, cost)
             }
-            ExecutionError::Interpreter(error) => detail::execution_error(error, effect, cost),
-            // TODO(mateusz.gorski): Be more specific about execution errors
-            other => detail::execution_error(format!("{:?}", other), effect, cost),
+            other => detail::execution_error(format!("{:?}", other), code, effect, cost),
         }
     }
 }
 
 mod detail {
-    use super::{DeployError_OutOfGasError, DeployResult, ExecutionEffect, Gas};
+    use super::{
+        CostBreakdown, DeployError_ExecutionError_Code, DeployError_OutOfGasError, DeployResult,
+        DeployResult_ExecutionResult, DeployResult_HostFunctionStats, ErrorCode, ExecutionEffect,
+        Gas, HostFunctionProfile, PhaseCosts, PreconditionFailureCode,
+    };
 
     /// Constructs an instance of `DeployResult` with no error set, i.e. a successful
     /// result.
@@ -84,10 +126,16 @@ mod detail {
     }
 
     /// Constructs an instance of `DeployResult` with an error set to
-    /// `ProtobufPreconditionFailure`.
-    pub(super) fn precondition_error(msg: String) -> DeployResult {
+    /// `ProtobufPreconditionFailure`.  Such deploys are never charged for, so the result carries
+    /// neither effects nor cost.
+    pub(super) fn precondition_error<T: ToString>(
+        msg: T,
+        code: PreconditionFailureCode,
+    ) -> DeployResult {
         let mut pb_deploy_result = DeployResult::new();
-        pb_deploy_result.mut_precondition_failure().set_message(msg);
+        let pb_precondition_failure = pb_deploy_result.mut_precondition_failure();
+        pb_precondition_failure.set_message(msg.to_string());
+        pb_precondition_failure.set_code(code);
         pb_deploy_result
     }
 
@@ -95,10 +143,11 @@ mod detail {
     /// `ProtobufExecutionError`.
     pub(super) fn execution_error<T: ToString>(
         msg: T,
+        code: ErrorCode,
         effect: ExecutionEffect,
         cost: Gas,
     ) -> DeployResult {
-        deploy_result(DeployErrorType::Exec(msg.to_string()), effect, cost)
+        deploy_result(DeployErrorType::Exec(msg.to_string(), code), effect, cost)
     }
 
     /// Constructs an instance of `DeployResult` with an error set to
@@ -110,7 +159,27 @@ mod detail {
     enum DeployErrorType {
         None,
         OutOfGas,
-        Exec(String),
+        Exec(String, ErrorCode),
+    }
+
+    /// Maps an `ErrorCode` to its protobuf code and the accompanying revert code, which is zero
+    /// for all but `ErrorCode::Revert`.
+    fn pb_code(code: ErrorCode) -> (DeployError_ExecutionError_Code, u32) {
+        match code {
+            ErrorCode::Unknown => (DeployError_ExecutionError_Code::UNKNOWN, 0),
+            ErrorCode::WasmPreprocessing => {
+                (DeployError_ExecutionError_Code::WASM_PREPROCESSING, 0)
+            }
+            ErrorCode::FunctionNotFound => (DeployError_ExecutionError_Code::FUNCTION_NOT_FOUND, 0),
+            ErrorCode::Revert(revert_code) => {
+                (DeployError_ExecutionError_Code::REVERT, revert_code)
+            }
+            ErrorCode::GasLimit => (DeployError_ExecutionError_Code::GAS_LIMIT, 0),
+            ErrorCode::KeyNotFound => (DeployError_ExecutionError_Code::KEY_NOT_FOUND, 0),
+            ErrorCode::TypeMismatch => (DeployError_ExecutionError_Code::TYPE_MISMATCH, 0),
+            ErrorCode::Interpreter => (DeployError_ExecutionError_Code::INTERPRETER, 0),
+            ErrorCode::Storage => (DeployError_ExecutionError_Code::STORAGE, 0),
+        }
     }
 
     /// Constructs an instance of `DeployResult` with an error set to
@@ -129,24 +198,74 @@ mod detail {
             DeployErrorType::OutOfGas => pb_execution_result
                 .mut_error()
                 .set_gas_error(DeployError_OutOfGasError::new()),
-            DeployErrorType::Exec(msg) => pb_execution_result
-                .mut_error()
-                .mut_exec_error()
-                .set_message(msg),
+            DeployErrorType::Exec(msg, code) => {
+                let (pb_code, revert_code) = pb_code(code);
+                let pb_exec_error = pb_execution_result.mut_error().mut_exec_error();
+                pb_exec_error.set_message(msg);
+                pb_exec_error.set_code(pb_code);
+                pb_exec_error.set_revert_code(revert_code);
+            }
         }
         pb_execution_result.set_effects(effect.into());
         pb_execution_result.set_cost(cost.value().into());
 
         pb_deploy_result
     }
+
+    /// Sets the `cost_breakdown` map of `pb_execution_result`, keyed by category name.
+    pub(super) fn set_cost_breakdown(
+        pb_execution_result: &mut DeployResult_ExecutionResult,
+        cost_breakdown: &CostBreakdown,
+    ) {
+        let pb_cost_breakdown = cost_breakdown
+            .iter()
+            .map(|(category, cost)| (category.name().to_string(), cost.value().into()))
+            .collect();
+        pb_execution_result.set_cost_breakdown(pb_cost_breakdown);
+    }
+
+    /// Sets the per-phase costs of `pb_execution_result`.
+    pub(super) fn set_phase_costs(
+        pb_execution_result: &mut DeployResult_ExecutionResult,
+        phase_costs: PhaseCosts,
+    ) {
+        pb_execution_result.set_payment_cost(phase_costs.payment.value().into());
+        pb_execution_result.set_session_cost(phase_costs.session.value().into());
+        pb_execution_result.set_finalize_cost(phase_costs.finalize.value().into());
+    }
+
+    /// Sets the `host_function_profile` map of `pb_execution_result`, keyed by host function name.
+    /// The map is left empty if profiling was disabled.
+    pub(super) fn set_host_function_profile(
+        pb_execution_result: &mut DeployResult_ExecutionResult,
+        host_function_profile: &HostFunctionProfile,
+    ) {
+        let pb_host_function_profile = host_function_profile
+            .iter()
+            .filter_map(|(function_index, stats)| {
+                let name = function_index.host_function_name()?;
+                let mut pb_stats = DeployResult_HostFunctionStats::new();
+                pb_stats.set_count(stats.count);
+                pb_stats.set_total_nanos(stats.total_nanos);
+                Some((name.to_string(), pb_stats))
+            })
+            .collect();
+        pb_execution_result.set_host_function_profile(pb_host_function_profile);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::convert::TryInto;
+    use std::{convert::TryInto, time::Duration};
 
-    use engine_shared::{additive_map::AdditiveMap, transform::Transform};
-    use types::{bytesrepr::Error as BytesReprError, AccessRights, ApiError, Key, URef, U512};
+    use engine_core::resolvers::v1_function_index::FunctionIndex;
+    use engine_shared::{
+        additive_map::AdditiveMap, gas::CostCategory, motes::Motes, transform::Transform,
+    };
+    use types::{
+        account::AccountHash, bytesrepr::Error as BytesReprError, AccessRights, ApiError, Key,
+        URef, U512,
+    };
 
     use super::*;
 
@@ -162,15 +281,56 @@ mod tests {
         };
         let execution_effect = ExecutionEffect::new(AdditiveMap::new(), input_transforms.clone());
         let cost = Gas::new(U512::from(123));
+        let charged = Motes::new(U512::from(1230));
+        let cost_breakdown = {
+            let mut cost_breakdown = CostBreakdown::default();
+            cost_breakdown.add(CostCategory::Opcode, Gas::new(U512::from(100)));
+            cost_breakdown.add(CostCategory::HostFunctionBytes, Gas::new(U512::from(23)));
+            cost_breakdown
+        };
+        let phase_costs = PhaseCosts {
+            payment: Gas::new(U512::from(23)),
+            session: Gas::new(U512::from(100)),
+            finalize: Gas::new(U512::from(7)),
+        };
         let execution_result = ExecutionResult::Success {
             effect: execution_effect,
             cost,
+            cost_breakdown: cost_breakdown.clone(),
+            host_function_profile: Default::default(),
+            charged,
+            phase_costs,
         };
         let mut ipc_deploy_result: DeployResult = execution_result.into();
         assert!(ipc_deploy_result.has_execution_result());
         let mut success = ipc_deploy_result.take_execution_result();
         let execution_cost: U512 = success.take_cost().try_into().expect("should map to U512");
         assert_eq!(execution_cost, cost.value());
+        let motes_charged: U512 = success
+            .take_motes_charged()
+            .try_into()
+            .expect("should map to U512");
+        assert_eq!(motes_charged, charged.value());
+
+        for (pb_cost, expected_cost) in &[
+            (success.take_payment_cost(), phase_costs.payment),
+            (success.take_session_cost(), phase_costs.session),
+            (success.take_finalize_cost(), phase_costs.finalize),
+        ] {
+            let phase_cost: U512 = pb_cost.clone().try_into().expect("should map to U512");
+            assert_eq!(phase_cost, expected_cost.value());
+        }
+
+        let mut ipc_cost_breakdown = success.take_cost_breakdown();
+        assert_eq!(ipc_cost_breakdown.len(), CostCategory::ALL.len());
+        for (category, expected_cost) in cost_breakdown.iter() {
+            let category_cost: U512 = ipc_cost_breakdown
+                .remove(category.name())
+                .expect("should have category")
+                .try_into()
+                .expect("should map to U512");
+            assert_eq!(category_cost, expected_cost.value());
+        }
 
         // Extract transform map from the IPC message and parse it back to the domain
         let ipc_transforms: AdditiveMap<Key, Transform> = {
@@ -185,11 +345,46 @@ mod tests {
         assert_eq!(input_transforms, ipc_transforms);
     }
 
+    #[test]
+    fn deploy_result_to_ipc_host_function_profile() {
+        let host_function_profile = {
+            let mut host_function_profile = HostFunctionProfile::default();
+            host_function_profile.record(FunctionIndex::ReadFuncIndex, Duration::from_nanos(10));
+            host_function_profile.record(FunctionIndex::ReadFuncIndex, Duration::from_nanos(15));
+            host_function_profile.record(FunctionIndex::WriteFuncIndex, Duration::from_nanos(7));
+            host_function_profile
+        };
+        let execution_result = ExecutionResult::Success {
+            effect: Default::default(),
+            cost: Gas::default(),
+            cost_breakdown: Default::default(),
+            host_function_profile,
+            charged: Motes::new(U512::zero()),
+            phase_costs: Default::default(),
+        };
+        let mut ipc_deploy_result: DeployResult = execution_result.into();
+        let ipc_profile = ipc_deploy_result
+            .mut_execution_result()
+            .take_host_function_profile();
+
+        assert_eq!(ipc_profile.len(), 2);
+        let read = &ipc_profile["host_function_read_value"];
+        assert_eq!(read.get_count(), 2);
+        assert_eq!(read.get_total_nanos(), 25);
+        let write = &ipc_profile["host_function_write"];
+        assert_eq!(write.get_count(), 1);
+        assert_eq!(write.get_total_nanos(), 7);
+    }
+
     fn test_cost<E: Into<EngineStateError>>(expected_cost: Gas, error: E) -> Gas {
         let execution_failure = ExecutionResult::Failure {
             error: error.into(),
             effect: Default::default(),
             cost: expected_cost,
+            cost_breakdown: Default::default(),
+            host_function_profile: Default::default(),
+            charged: Motes::new(U512::zero()),
+            phase_costs: Default::default(),
         };
         let mut ipc_deploy_result: DeployResult = execution_failure.into();
         assert!(ipc_deploy_result.has_execution_result());
@@ -233,6 +428,10 @@ mod tests {
             error: EngineStateError::Exec(revert_error),
             effect: Default::default(),
             cost: Gas::new(amount),
+            cost_breakdown: Default::default(),
+            host_function_profile: Default::default(),
+            charged: Motes::new(U512::zero()),
+            phase_costs: Default::default(),
         };
         let mut ipc_result: DeployResult = exec_result.into();
         assert!(
@@ -245,12 +444,83 @@ mod tests {
             .try_into()
             .expect("should map to U512");
         assert_eq!(execution_cost, amount, "execution cost should equal amount");
+        let ipc_exec_error = ipc_execution_result.get_error().get_exec_error();
+        assert_eq!(ipc_exec_error.get_message(), expected_revert.to_string());
+        assert_eq!(
+            ipc_exec_error.get_code(),
+            DeployError_ExecutionError_Code::REVERT
+        );
+        assert_eq!(ipc_exec_error.get_revert_code(), u32::from(expected_revert));
+    }
+
+    #[test]
+    fn function_not_found_maps_to_error_code() {
+        let exec_result = ExecutionResult::Failure {
+            error: EngineStateError::Exec(ExecutionError::FunctionNotFound("foo".to_string())),
+            effect: Default::default(),
+            cost: Gas::new(U512::from(15)),
+            cost_breakdown: Default::default(),
+            host_function_profile: Default::default(),
+            charged: Motes::new(U512::zero()),
+            phase_costs: Default::default(),
+        };
+        let ipc_result: DeployResult = exec_result.into();
+        let ipc_exec_error = ipc_result
+            .get_execution_result()
+            .get_error()
+            .get_exec_error();
+        assert_eq!(
+            ipc_exec_error.get_code(),
+            DeployError_ExecutionError_Code::FUNCTION_NOT_FOUND
+        );
+        assert_eq!(ipc_exec_error.get_revert_code(), 0);
+        assert_eq!(ipc_exec_error.get_message(), "FunctionNotFound(\"foo\")");
+    }
+
+    fn precondition_code<E: Into<EngineStateError>>(error: E) -> PreconditionFailureCode {
+        let ipc_result: DeployResult = ExecutionResult::precondition_failure(error.into()).into();
+        assert!(
+            ipc_result.has_precondition_failure(),
+            "should have precondition failure"
+        );
+        ipc_result.get_precondition_failure().get_code()
+    }
+
+    #[test]
+    fn precondition_errors_map_to_precondition_failure_codes() {
+        assert_eq!(
+            precondition_code(EngineStateError::Authorization),
+            PreconditionFailureCode::AUTHORIZATION
+        );
+        assert_eq!(
+            precondition_code(ExecutionError::DeploymentAuthorizationFailure),
+            PreconditionFailureCode::AUTHORIZATION
+        );
+        assert_eq!(
+            precondition_code(EngineStateError::AccountNotFound(AccountHash::new([1; 32]))),
+            PreconditionFailureCode::AUTHORIZATION
+        );
+        assert_eq!(
+            precondition_code(EngineStateError::InvalidKeyVariant("foo".to_string())),
+            PreconditionFailureCode::INVALID_ARGUMENT
+        );
+        assert_eq!(
+            precondition_code(EngineStateError::InvalidUpgradeConfig),
+            PreconditionFailureCode::UPGRADE
+        );
+        assert_eq!(
+            precondition_code(EngineStateError::Closing),
+            PreconditionFailureCode::UNKNOWN
+        );
+    }
+
+    #[test]
+    fn precondition_failure_keeps_error_message() {
+        let ipc_result: DeployResult =
+            ExecutionResult::precondition_failure(EngineStateError::Authorization).into();
         assert_eq!(
-            ipc_execution_result
-                .get_error()
-                .get_exec_error()
-                .get_message(),
-            expected_revert.to_string(),
+            ipc_result.get_precondition_failure().get_message(),
+            EngineStateError::Authorization.to_string()
         );
     }
 }
@@ -1,6 +1,7 @@
 use std::convert::{TryFrom, TryInto};
 
 use engine_core::engine_state::genesis::{ExecConfig, GenesisAccount};
+use engine_shared::motes::Motes;
 
 use crate::engine_server::{ipc, mappings::MappingError};
 
@@ -19,13 +20,52 @@ impl TryFrom<ipc::ChainSpec_GenesisConfig_ExecConfig> for ExecConfig {
         let mint_initializer_bytes = pb_exec_config.take_mint_installer();
         let proof_of_stake_initializer_bytes = pb_exec_config.take_pos_installer();
         let standard_payment_installer_bytes = pb_exec_config.take_standard_payment_installer();
-        Ok(ExecConfig::new(
+        let unbonding_delay_millis = pb_exec_config.get_unbonding_delay_millis();
+        let exec_config = ExecConfig::new(
             mint_initializer_bytes,
             proof_of_stake_initializer_bytes,
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
-        ))
+        )
+        .with_unbonding_delay_millis(unbonding_delay_millis);
+        // An unset (zero) gas price keeps the engine's default.
+        let exec_config = match pb_exec_config.get_gas_price() {
+            0 => exec_config,
+            gas_price => exec_config.with_gas_price(gas_price),
+        };
+        // An unset (zero) denominator keeps the default of no seigniorage.
+        let exec_config = match pb_exec_config.get_round_seigniorage_rate_denominator() {
+            0 => exec_config,
+            denominator => exec_config.with_round_seigniorage_rate(
+                pb_exec_config.get_round_seigniorage_rate_numerator(),
+                denominator,
+            ),
+        };
+        // Unset bond amount bounds keep the defaults of no bounds.
+        let min_bond_amount = if pb_exec_config.has_min_bond_amount() {
+            pb_exec_config
+                .take_min_bond_amount()
+                .try_into()
+                .map(Motes::new)?
+        } else {
+            exec_config.min_bond_amount()
+        };
+        let max_bond_amount = if pb_exec_config.has_max_bond_amount() {
+            pb_exec_config
+                .take_max_bond_amount()
+                .try_into()
+                .map(Motes::new)?
+        } else {
+            exec_config.max_bond_amount()
+        };
+        let exec_config = exec_config.with_bond_amount_bounds(min_bond_amount, max_bond_amount);
+        // An unset (zero) threshold keeps the default of never chunking values.
+        let exec_config = match pb_exec_config.get_trie_chunk_threshold() {
+            0 => exec_config,
+            trie_chunk_threshold => exec_config.with_trie_chunk_threshold(trie_chunk_threshold),
+        };
+        Ok(exec_config)
     }
 }
 
@@ -49,6 +89,14 @@ impl From<ExecConfig> for ipc::ChainSpec_GenesisConfig_ExecConfig {
         pb_exec_config
             .mut_costs()
             .set_wasm(exec_config.wasm_costs().into());
+        pb_exec_config.set_unbonding_delay_millis(exec_config.unbonding_delay_millis());
+        pb_exec_config.set_gas_price(exec_config.gas_price());
+        let (numerator, denominator) = exec_config.round_seigniorage_rate();
+        pb_exec_config.set_round_seigniorage_rate_numerator(numerator);
+        pb_exec_config.set_round_seigniorage_rate_denominator(denominator);
+        pb_exec_config.set_min_bond_amount(exec_config.min_bond_amount().value().into());
+        pb_exec_config.set_max_bond_amount(exec_config.max_bond_amount().value().into());
+        pb_exec_config.set_trie_chunk_threshold(exec_config.trie_chunk_threshold().unwrap_or(0));
         pb_exec_config
     }
 }
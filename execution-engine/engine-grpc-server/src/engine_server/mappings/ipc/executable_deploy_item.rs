@@ -144,6 +144,8 @@ impl From<ExecutableDeployItem> for DeployPayload {
 
 #[cfg(test)]
 mod tests {
+    use types::{bytesrepr, runtime_args, RuntimeArgs, U512};
+
     use super::*;
 
     #[test]
@@ -166,4 +168,51 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn should_preserve_named_args_for_all_payloads() {
+        let args = runtime_args! {
+            "amount" => U512::from(1234),
+            "target" => "recipient".to_string(),
+        };
+        let args_bytes = bytesrepr::serialize(args.clone()).unwrap();
+
+        let items = vec![
+            ExecutableDeployItem::ModuleBytes {
+                module_bytes: vec![0, 97, 115, 109],
+                args: args_bytes.clone(),
+            },
+            ExecutableDeployItem::StoredContractByHash {
+                hash: [1; 32],
+                entry_point: "call".to_string(),
+                args: args_bytes.clone(),
+            },
+            ExecutableDeployItem::StoredContractByName {
+                name: "contract".to_string(),
+                entry_point: "call".to_string(),
+                args: args_bytes.clone(),
+            },
+            ExecutableDeployItem::StoredVersionedContractByName {
+                name: "package".to_string(),
+                version: Some(2),
+                entry_point: "call".to_string(),
+                args: args_bytes.clone(),
+            },
+            ExecutableDeployItem::StoredVersionedContractByHash {
+                hash: [2; 32],
+                version: None,
+                entry_point: "call".to_string(),
+                args: args_bytes.clone(),
+            },
+            ExecutableDeployItem::Transfer { args: args_bytes },
+        ];
+
+        for item in items {
+            let deploy_payload = DeployPayload::from(item.clone());
+            let parsed = ExecutableDeployItem::try_from(deploy_payload.payload.unwrap()).unwrap();
+            assert_eq!(parsed, item);
+
+            assert_eq!(parsed.into_runtime_args().unwrap(), args);
+        }
+    }
 }
@@ -3,30 +3,26 @@ use std::convert::{TryFrom, TryInto};
 use engine_core::engine_state::{
     execute_request::ExecuteRequest, execution_result::ExecutionResult,
 };
-use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+use types::account::AccountHash;
 
-use crate::engine_server::{ipc, mappings::MappingError};
+use crate::engine_server::{
+    ipc,
+    mappings::{parse_blake2b_hash, MappingError},
+};
 
 impl TryFrom<ipc::ExecuteRequest> for ExecuteRequest {
     type Error = ipc::ExecuteResponse;
 
     fn try_from(mut request: ipc::ExecuteRequest) -> Result<Self, Self::Error> {
-        let parent_state_hash = {
-            let parent_state_hash = request.take_parent_state_hash();
-            let length = parent_state_hash.len();
-            if length != BLAKE2B_DIGEST_LENGTH {
-                let mut result = ipc::ExecuteResponse::new();
-                result.mut_missing_parent().set_hash(parent_state_hash);
-                return Err(result);
-            }
-            parent_state_hash.as_slice().try_into().map_err(|_| {
+        // A malformed hash can't name an existing root, so it is reported as a missing parent.
+        let parent_state_hash =
+            parse_blake2b_hash(request.get_parent_state_hash()).map_err(|_| {
                 let mut result = ipc::ExecuteResponse::new();
                 result
                     .mut_missing_parent()
-                    .set_hash(parent_state_hash.clone());
+                    .set_hash(request.take_parent_state_hash());
                 result
-            })?
-        };
+            })?;
 
         let block_time = request.get_block_time();
 
@@ -41,12 +37,22 @@ impl TryFrom<ipc::ExecuteRequest> for ExecuteRequest {
 
         let protocol_version = request.take_protocol_version().into();
 
+        let proposer = match request.get_proposer() {
+            [] => None,
+            bytes => Some(AccountHash::try_from(bytes).map_err(|_| {
+                let error = MappingError::invalid_account_hash_length(bytes.len());
+                ExecutionResult::precondition_failure(error.into())
+            })),
+        };
+
         Ok(ExecuteRequest::new(
             parent_state_hash,
             block_time,
             deploys,
             protocol_version,
-        ))
+            proposer,
+        )
+        .with_profile_host_functions(request.get_profile_host_functions()))
     }
 }
 
@@ -65,6 +71,10 @@ impl From<ExecuteRequest> for ipc::ExecuteRequest {
                 .collect(),
         );
         result.set_protocol_version(req.protocol_version.into());
+        if let Some(Ok(proposer)) = req.proposer {
+            result.set_proposer(proposer.as_bytes().to_vec());
+        }
+        result.set_profile_host_functions(req.profile_host_functions);
         result
     }
 }
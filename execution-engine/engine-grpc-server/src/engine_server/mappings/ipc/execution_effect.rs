@@ -1,4 +1,7 @@
-use engine_core::engine_state::{execution_effect::ExecutionEffect, op::Op};
+use engine_core::engine_state::{
+    execution_effect::{ContractEvent, ExecutionEffect},
+    op::Op,
+};
 use types::Key;
 
 use crate::engine_server::{
@@ -23,6 +26,16 @@ impl From<(Key, Op)> for OpEntry {
     }
 }
 
+impl From<ContractEvent> for ipc::ContractEvent {
+    fn from(event: ContractEvent) -> ipc::ContractEvent {
+        let mut pb_event = ipc::ContractEvent::new();
+        pb_event.set_contract_key(event.contract_key.into());
+        pb_event.set_topic(event.topic);
+        pb_event.set_payload(event.payload);
+        pb_event
+    }
+}
+
 impl From<ExecutionEffect> for ipc::ExecutionEffect {
     fn from(execution_effect: ExecutionEffect) -> ipc::ExecutionEffect {
         let mut pb_execution_effect = ipc::ExecutionEffect::new();
@@ -37,6 +50,13 @@ impl From<ExecutionEffect> for ipc::ExecutionEffect {
             .collect();
         pb_execution_effect.set_transform_map(pb_transform_map.into());
 
+        let pb_events: Vec<ipc::ContractEvent> = execution_effect
+            .events
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        pb_execution_effect.set_events(pb_events.into());
+
         pb_execution_effect
     }
 }
@@ -1,7 +1,10 @@
 //! Functions for converting between CasperLabs types and their Protobuf equivalents which are
 //! defined in protobuf/io/casperlabs/ipc/ipc.proto
 
+mod balance_request;
+mod batch_query_request;
 mod bond;
+mod bonded_validators_request;
 mod deploy_item;
 mod deploy_result;
 mod exec_config;
@@ -11,6 +14,7 @@ mod execution_effect;
 mod genesis_account;
 mod genesis_config;
 mod query_request;
+mod query_result;
 mod run_genesis_request;
 mod upgrade_request;
 mod wasm_costs;
@@ -1,35 +1,76 @@
 use std::convert::{TryFrom, TryInto};
 
 use engine_core::engine_state::query::QueryRequest;
-use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+use types::Key;
 
-use crate::engine_server::{ipc, mappings::MappingError};
+use crate::engine_server::{
+    ipc,
+    mappings::{parse_blake2b_hash, MappingError},
+};
 
 impl TryFrom<ipc::QueryRequest> for QueryRequest {
     type Error = MappingError;
 
     fn try_from(mut query_request: ipc::QueryRequest) -> Result<Self, Self::Error> {
-        let state_hash = {
-            let state_hash = query_request.get_state_hash();
-            let length = state_hash.len();
-            if length != BLAKE2B_DIGEST_LENGTH {
-                return Err(MappingError::InvalidStateHashLength {
-                    expected: BLAKE2B_DIGEST_LENGTH,
-                    actual: length,
-                });
-            }
-            state_hash
+        let state_hash = parse_blake2b_hash(query_request.get_state_hash())?;
+
+        let key = if query_request.has_base_key() {
+            query_request
+                .take_base_key()
                 .try_into()
-                .map_err(|_| MappingError::TryFromSlice)?
+                .map_err(MappingError::Parsing)?
+        } else {
+            Key::from_formatted_string(query_request.get_formatted_base_key())
+                .map_err(|error| MappingError::Parsing(error.into()))?
         };
 
-        let key = query_request
-            .take_base_key()
-            .try_into()
-            .map_err(MappingError::Parsing)?;
-
         let path = query_request.take_path().into_vec();
 
         Ok(QueryRequest::new(state_hash, key, path))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+    use types::gens;
+
+    use super::*;
+
+    fn new_query_request() -> ipc::QueryRequest {
+        let mut query_request = ipc::QueryRequest::new();
+        query_request.set_state_hash(vec![1; BLAKE2B_DIGEST_LENGTH]);
+        query_request
+    }
+
+    proptest! {
+        #[test]
+        fn should_accept_formatted_base_key(key in gens::key_arb()) {
+            let mut query_request = new_query_request();
+            query_request.set_formatted_base_key(key.to_formatted_string());
+            let mapped = QueryRequest::try_from(query_request).expect("should map");
+            assert_eq!(mapped.key(), key);
+        }
+
+        #[test]
+        fn should_prefer_base_key(key in gens::key_arb()) {
+            let mut query_request = new_query_request();
+            query_request.set_base_key(key.into());
+            query_request.set_formatted_base_key("invalid".to_string());
+            let mapped = QueryRequest::try_from(query_request).expect("should map");
+            assert_eq!(mapped.key(), key);
+        }
+    }
+
+    #[test]
+    fn should_reject_malformed_formatted_base_key() {
+        let mut query_request = new_query_request();
+        query_request.set_formatted_base_key("account-1234".to_string());
+        assert!(matches!(
+            QueryRequest::try_from(query_request),
+            Err(MappingError::Parsing(_))
+        ));
+    }
+}
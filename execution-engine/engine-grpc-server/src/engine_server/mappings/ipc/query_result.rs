@@ -0,0 +1,22 @@
+use engine_core::engine_state::query::QueryResult;
+use types::bytesrepr::ToBytes;
+
+use crate::engine_server::ipc;
+
+impl From<QueryResult> for ipc::QueryResponse {
+    fn from(query_result: QueryResult) -> Self {
+        let mut query_response = ipc::QueryResponse::new();
+        match query_result {
+            QueryResult::Success(value) => match value.to_bytes() {
+                Ok(serialized_value) => query_response.set_success(serialized_value),
+                Err(error) => query_response
+                    .set_failure(format!("Failed to serialize StoredValue: {}", error)),
+            },
+            QueryResult::RootNotFound => query_response.set_failure("Root not found".to_string()),
+            QueryResult::ValueNotFound(message)
+            | QueryResult::CircularReference(message)
+            | QueryResult::DepthLimitExceeded(message) => query_response.set_failure(message),
+        }
+        query_response
+    }
+}
@@ -1,18 +1,18 @@
-use std::convert::{TryFrom, TryInto};
+use std::convert::TryFrom;
 
 use engine_core::engine_state::upgrade::UpgradeConfig;
 use types::ProtocolVersion;
 
-use crate::engine_server::{ipc::UpgradeRequest, mappings::MappingError};
+use crate::engine_server::{
+    ipc::UpgradeRequest,
+    mappings::{parse_blake2b_hash, MappingError},
+};
 
 impl TryFrom<UpgradeRequest> for UpgradeConfig {
     type Error = MappingError;
 
     fn try_from(mut pb_upgrade_request: UpgradeRequest) -> Result<Self, Self::Error> {
-        let pre_state_hash = pb_upgrade_request
-            .get_parent_state_hash()
-            .try_into()
-            .map_err(|_| MappingError::InvalidStateHash("pre_state_hash".to_string()))?;
+        let pre_state_hash = parse_blake2b_hash(pb_upgrade_request.get_parent_state_hash())?;
 
         let current_protocol_version = pb_upgrade_request.take_protocol_version().into();
 
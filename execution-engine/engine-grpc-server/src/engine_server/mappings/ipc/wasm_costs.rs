@@ -1,6 +1,30 @@
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::wasm_costs::{
+    HostFunctionCost, HostFunctionCosts, WasmCosts, DEFAULT_MAX_MEMORY_PAGES,
+    DEFAULT_MAX_TABLE_SIZE, MISSING_HOST_FUNCTION_COST,
+};
 
-use crate::engine_server::ipc::ChainSpec_CostTable_WasmCosts;
+use crate::engine_server::ipc::{
+    ChainSpec_CostTable_WasmCosts, ChainSpec_CostTable_WasmCosts_HostFunctionCost,
+};
+
+impl From<HostFunctionCost> for ChainSpec_CostTable_WasmCosts_HostFunctionCost {
+    fn from(host_function_cost: HostFunctionCost) -> Self {
+        ChainSpec_CostTable_WasmCosts_HostFunctionCost {
+            cost: host_function_cost.cost,
+            cost_per_byte: host_function_cost.cost_per_byte,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ChainSpec_CostTable_WasmCosts_HostFunctionCost> for HostFunctionCost {
+    fn from(pb_host_function_cost: ChainSpec_CostTable_WasmCosts_HostFunctionCost) -> Self {
+        HostFunctionCost::new(
+            pb_host_function_cost.cost,
+            pb_host_function_cost.cost_per_byte,
+        )
+    }
+}
 
 impl From<WasmCosts> for ChainSpec_CostTable_WasmCosts {
     fn from(wasm_costs: WasmCosts) -> Self {
@@ -15,13 +39,29 @@ impl From<WasmCosts> for ChainSpec_CostTable_WasmCosts {
             max_stack_height: wasm_costs.max_stack_height,
             opcodes_mul: wasm_costs.opcodes_mul,
             opcodes_div: wasm_costs.opcodes_div,
+            max_memory_pages: wasm_costs.max_memory_pages,
+            max_table_size: wasm_costs.max_table_size,
+            host_function_costs: wasm_costs
+                .host_function_costs
+                .iter()
+                .cloned()
+                .map(Into::into)
+                .collect(),
             ..Default::default()
         }
     }
 }
 
 impl From<ChainSpec_CostTable_WasmCosts> for WasmCosts {
-    fn from(pb_wasm_costs: ChainSpec_CostTable_WasmCosts) -> Self {
+    fn from(mut pb_wasm_costs: ChainSpec_CostTable_WasmCosts) -> Self {
+        let mut host_function_costs = HostFunctionCosts::uniform(MISSING_HOST_FUNCTION_COST);
+        for (index, pb_host_function_cost) in pb_wasm_costs
+            .take_host_function_costs()
+            .into_iter()
+            .enumerate()
+        {
+            host_function_costs.set(index, pb_host_function_cost.into());
+        }
         WasmCosts {
             regular: pb_wasm_costs.regular,
             div: pb_wasm_costs.div,
@@ -33,10 +73,22 @@ impl From<ChainSpec_CostTable_WasmCosts> for WasmCosts {
             max_stack_height: pb_wasm_costs.max_stack_height,
             opcodes_mul: pb_wasm_costs.opcodes_mul,
             opcodes_div: pb_wasm_costs.opcodes_div,
+            max_memory_pages: non_zero_or(pb_wasm_costs.max_memory_pages, DEFAULT_MAX_MEMORY_PAGES),
+            max_table_size: non_zero_or(pb_wasm_costs.max_table_size, DEFAULT_MAX_TABLE_SIZE),
+            host_function_costs,
         }
     }
 }
 
+/// Returns `value`, or `default` if `value` is zero, i.e. was left unset in the chainspec.
+fn non_zero_or(value: u32, default: u32) -> u32 {
+    if value == 0 {
+        default
+    } else {
+        value
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::proptest;
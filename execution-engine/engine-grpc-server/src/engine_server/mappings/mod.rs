@@ -11,6 +11,7 @@ use std::{
 };
 
 use engine_core::{engine_state, DEPLOY_HASH_LENGTH};
+use engine_shared::newtypes::{Blake2bHash, BLAKE2B_DIGEST_LENGTH};
 use types::{account::ACCOUNT_HASH_LENGTH, KEY_HASH_LENGTH};
 
 pub use transforms::TransformMap;
@@ -23,6 +24,18 @@ pub(crate) fn vec_to_array(input: Vec<u8>, input_name: &str) -> Result<[u8; 32],
         .map_err(|_| format!("{} must be 32 bytes.", input_name).into())
 }
 
+/// Try to convert the bytes of a state hash taken from a request to a `Blake2bHash`.
+pub(crate) fn parse_blake2b_hash(bytes: &[u8]) -> Result<Blake2bHash, MappingError> {
+    let length = bytes.len();
+    if length != BLAKE2B_DIGEST_LENGTH {
+        return Err(MappingError::InvalidStateHashLength {
+            expected: BLAKE2B_DIGEST_LENGTH,
+            actual: length,
+        });
+    }
+    bytes.try_into().map_err(|_| MappingError::TryFromSlice)
+}
+
 #[derive(Debug, PartialEq)]
 pub enum MappingError {
     InvalidStateHashLength { expected: usize, actual: usize },
@@ -136,7 +149,7 @@ pub mod test_utils {
 
 #[cfg(test)]
 mod tests {
-    use super::vec_to_array;
+    use super::{parse_blake2b_hash, vec_to_array, MappingError};
 
     #[test]
     fn vec_to_array_test() {
@@ -145,4 +158,18 @@ mod tests {
         assert!(vec_to_array(vec![1; 31], "").is_err());
         assert!(vec_to_array(vec![1; 33], "").is_err());
     }
+
+    #[test]
+    fn parse_blake2b_hash_test() {
+        assert_eq!([1; 32], parse_blake2b_hash(&[1; 32]).unwrap().value());
+        for length in &[0, 31, 33] {
+            assert_eq!(
+                parse_blake2b_hash(&vec![1; *length]),
+                Err(MappingError::InvalidStateHashLength {
+                    expected: 32,
+                    actual: *length
+                })
+            );
+        }
+    }
 }
@@ -82,6 +82,7 @@ impl TryFrom<state::ContractPackage> for ContractPackage {
 
 impl From<EntryPoint> for state::Contract_EntryPoint {
     fn from(value: EntryPoint) -> Self {
+        let no_schema = value.no_schema();
         let (name, args, ret, entry_point_access, entry_point_type) = value.into();
 
         let mut res = state::Contract_EntryPoint::new();
@@ -121,6 +122,7 @@ impl From<EntryPoint> for state::Contract_EntryPoint {
                 res.set_contract(state::Contract_EntryPoint_ContractType::new())
             }
         }
+        res.set_no_schema(no_schema);
         res
     }
 }
@@ -132,6 +134,8 @@ impl TryFrom<state::Contract_EntryPoint> for EntryPoint {
         let mut args = Vec::new();
 
         let ret = value.take_ret().try_into()?;
+        // Read up front, as matching on the oneof fields below moves out of `value`.
+        let no_schema = value.get_no_schema();
 
         for mut arg in value.take_args().into_iter() {
             args.push(Parameter::new(
@@ -160,13 +164,12 @@ impl TryFrom<state::Contract_EntryPoint> for EntryPoint {
             }
             None => return Err("Unable to parse Protobuf entry point type".into()),
         };
-        Ok(EntryPoint::new(
-            name,
-            args,
-            ret,
-            entry_point_access,
-            entry_point_type,
-        ))
+        let entry_point = EntryPoint::new(name, args, ret, entry_point_access, entry_point_type);
+        if no_schema {
+            Ok(entry_point.with_no_schema())
+        } else {
+            Ok(entry_point)
+        }
     }
 }
 
@@ -32,6 +32,9 @@ impl From<Transform> for transforms::Transform {
                 let pb_named_keys: Vec<NamedKey> = NamedKeyMap::new(keys_map).into();
                 pb_transform.mut_add_keys().set_value(pb_named_keys.into());
             }
+            Transform::Delete => {
+                pb_transform.set_delete(Default::default());
+            }
             Transform::Failure(transform_error) => pb_transform.set_failure(transform_error.into()),
             Transform::AddUInt128(uint128) => {
                 pb_transform.mut_add_big_int().set_value(uint128.into());
@@ -42,6 +45,9 @@ impl From<Transform> for transforms::Transform {
             Transform::AddUInt512(uint512) => {
                 pb_transform.mut_add_big_int().set_value(uint512.into());
             }
+            Transform::SubUInt512(uint512) => {
+                pb_transform.mut_sub_big_int().set_value(uint512.into());
+            }
         };
         pb_transform
     }
@@ -91,10 +97,18 @@ impl TryFrom<transforms::Transform> for Transform {
                     }
                 }
             }
+            Transform_oneof_transform_instance::sub_big_int(mut pb_big_int) => {
+                let cl_value: CLValue = pb_big_int.take_value().try_into()?;
+                let u512: U512 = cl_value
+                    .into_t()
+                    .map_err(|error| ParsingError(format!("{:?}", error)))?;
+                Transform::SubUInt512(u512)
+            }
             Transform_oneof_transform_instance::write(mut pb_write) => {
                 let value = StoredValue::try_from(pb_write.take_value())?;
                 Transform::Write(value)
             }
+            Transform_oneof_transform_instance::delete(_) => Transform::Delete,
             Transform_oneof_transform_instance::failure(pb_failure) => {
                 let error = TransformError::try_from(pb_failure)?;
                 Transform::Failure(error)
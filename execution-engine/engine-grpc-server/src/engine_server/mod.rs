@@ -20,53 +20,91 @@ use std::{
     collections::BTreeMap,
     convert::{TryFrom, TryInto},
     fmt::Debug,
-    io::ErrorKind,
+    fs::{self, File},
+    io::{BufReader, BufWriter, ErrorKind},
     iter::FromIterator,
     marker::{Send, Sync},
+    net::SocketAddr,
+    sync::Arc,
+    thread,
     time::Instant,
 };
 
-use grpc::{Error as GrpcError, RequestOptions, ServerBuilder, SingleResponse};
-use log::{info, warn, Level};
+use futures::{sync::mpsc, Stream};
+use grpc::{Error as GrpcError, RequestOptions, ServerBuilder, SingleResponse, StreamingResponse};
+use log::{debug, info, log_enabled, warn, Level};
 
 use engine_core::engine_state::{
+    balance::{BalanceRequest, BalanceResult},
+    bonded_validators::{BondedValidatorsRequest, BondedValidatorsResult},
+    deploy_item::DeployItem,
+    executable_deploy_item::ExecutableDeployItem,
     execute_request::ExecuteRequest,
+    execution_result::ExecutionResult,
     genesis::GenesisResult,
-    query::{QueryRequest, QueryResult},
+    query::{BatchQueryRequest, QueryRequest, QueryResult},
     run_genesis_request::RunGenesisRequest,
     upgrade::{UpgradeConfig, UpgradeResult},
-    EngineState, Error as EngineError,
+    validation::ValidationResult,
+    EngineConfig, EngineState, Error as EngineError, ExecuteError,
 };
 use engine_shared::{
     logging::{self, log_duration},
     newtypes::{Blake2bHash, CorrelationId},
+    utils,
 };
 use engine_storage::global_state::{CommitResult, StateProvider};
-use types::{bytesrepr::ToBytes, ProtocolVersion};
+use types::{bytesrepr::ToBytes, Key, ProtocolVersion};
 
 use self::{
     ipc::{
-        BidStateRequest, BidStateResponse, CommitRequest, CommitResponse, DistributeRewardsRequest,
-        DistributeRewardsResponse, ExecuteResponse, GenesisResponse, QueryResponse, SlashRequest,
-        SlashResponse, UnbondPayoutRequest, UnbondPayoutResponse, UpgradeRequest, UpgradeResponse,
+        BalanceResponse, BatchQueryResponse, BidStateRequest, BidStateResponse, CommitRequest,
+        CommitResponse, DeployResult, DeployResultChunk, DistributeRewardsRequest,
+        DistributeRewardsResponse, ExecuteResponse, ExportTrieRequest, ExportTrieResponse,
+        GenesisResponse, GetBondedValidatorsRequest, GetBondedValidatorsResponse,
+        GetBondedValidatorsResponse_BondedValidators, GetRootAncestryRequest,
+        GetRootAncestryResponse, ImportTrieRequest, ImportTrieResponse, PruneRequest,
+        PruneResponse, QueryResponse, SlashRequest, SlashResponse, UnbondPayoutRequest,
+        UnbondPayoutResponse, UpgradeRequest, UpgradeResponse, ValidateRequest, ValidateResponse,
     },
     ipc_grpc::{ExecutionEngineService, ExecutionEngineServiceServer},
-    mappings::{ParsingError, TransformMap},
+    mappings::{parse_blake2b_hash, ParsingError, TransformMap},
 };
 
+const METRIC_DURATION_VALIDATE: &str = "validate_duration";
 const METRIC_DURATION_COMMIT: &str = "commit_duration";
 const METRIC_DURATION_EXEC: &str = "exec_duration";
+const METRIC_DURATION_EXEC_STREAM: &str = "exec_stream_duration";
 const METRIC_DURATION_QUERY: &str = "query_duration";
+const METRIC_DURATION_BATCH_QUERY: &str = "batch_query_duration";
+const METRIC_DURATION_GET_BALANCE: &str = "get_balance_duration";
 const METRIC_DURATION_GENESIS: &str = "genesis_duration";
 const METRIC_DURATION_UPGRADE: &str = "upgrade_duration";
+const METRIC_DURATION_PRUNE: &str = "prune_duration";
+const METRIC_DURATION_EXPORT_TRIE: &str = "export_trie_duration";
+const METRIC_DURATION_IMPORT_TRIE: &str = "import_trie_duration";
+const METRIC_DURATION_GET_ROOT_ANCESTRY: &str = "get_root_ancestry_duration";
+const METRIC_DURATION_GET_BONDED_VALIDATORS: &str = "get_bonded_validators_duration";
 
+const TAG_RESPONSE_VALIDATE: &str = "validate_response";
 const TAG_RESPONSE_COMMIT: &str = "commit_response";
 const TAG_RESPONSE_EXEC: &str = "exec_response";
+const TAG_RESPONSE_EXEC_STREAM: &str = "exec_stream_response";
 const TAG_RESPONSE_QUERY: &str = "query_response";
+const TAG_RESPONSE_BATCH_QUERY: &str = "batch_query_response";
+const TAG_RESPONSE_GET_BALANCE: &str = "get_balance_response";
 const TAG_RESPONSE_GENESIS: &str = "genesis_response";
 const TAG_RESPONSE_UPGRADE: &str = "upgrade_response";
+const TAG_RESPONSE_PRUNE: &str = "prune_response";
+const TAG_RESPONSE_EXPORT_TRIE: &str = "export_trie_response";
+const TAG_RESPONSE_IMPORT_TRIE: &str = "import_trie_response";
+const TAG_RESPONSE_GET_ROOT_ANCESTRY: &str = "get_root_ancestry_response";
+const TAG_RESPONSE_GET_BONDED_VALIDATORS: &str = "get_bonded_validators_response";
 
 const UNIMPLEMENTED: &str = "unimplemented";
+const SHUTTING_DOWN: &str = "execution engine is shutting down";
+const EXEC_STREAM_WORKER_FAILED: &str = "execute_stream worker stopped unexpectedly";
+const TOO_MANY_DEPLOYS: &str = "execute request has more deploys than allowed";
 
 const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 
@@ -77,17 +115,77 @@ const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 // (outer layer) leading to cleaner design.
 impl<S> ExecutionEngineService for EngineState<S>
 where
-    S: StateProvider,
+    S: StateProvider + Sync,
     EngineError: From<S::Error>,
     S::Error: Into<engine_core::execution::Error> + Debug,
 {
+    fn validate(
+        &self,
+        _request_options: RequestOptions,
+        mut validate_request: ValidateRequest,
+    ) -> SingleResponse<ValidateResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let protocol_version = validate_request.take_protocol_version().into();
+        let mut response = ValidateResponse::new();
+
+        if let Err(error) = check_module_size(self.config(), validate_request.get_wasm_code()) {
+            let log_message = error.to_string();
+            info!("{}", log_message);
+            response.set_preprocessing_error(log_message);
+            log_duration(
+                correlation_id,
+                METRIC_DURATION_VALIDATE,
+                TAG_RESPONSE_VALIDATE,
+                start.elapsed(),
+            );
+            return SingleResponse::completed(response);
+        }
+
+        match self.validate_module(protocol_version, validate_request.get_wasm_code()) {
+            Ok(ValidationResult::Valid) => {
+                info!("validate successful; correlation_id: {}", correlation_id);
+                response.mut_success();
+            }
+            Ok(ValidationResult::ParseError(error)) => {
+                info!("{}", error);
+                response.set_parse_error(error);
+            }
+            Ok(ValidationResult::PreprocessingError(error)) => {
+                let log_message = error.to_string();
+                info!("{}", log_message);
+                response.set_preprocessing_error(log_message);
+            }
+            Ok(ValidationResult::MissingCallExport) => {
+                let log_message = "Module does not export a \"call\" function";
+                info!("{}", log_message);
+                response.set_preprocessing_error(log_message.to_string());
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_VALIDATE,
+            TAG_RESPONSE_VALIDATE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
     fn query(
         &self,
         _request_options: RequestOptions,
         query_request: ipc::QueryRequest,
     ) -> SingleResponse<QueryResponse> {
         let start = Instant::now();
-        let correlation_id = CorrelationId::new();
+        let correlation_id = correlation_id_or_new(query_request.get_correlation_id());
 
         let request: QueryRequest = match query_request.try_into() {
             Ok(ret) => ret,
@@ -106,17 +204,33 @@ where
             }
         };
 
-        let result = self.run_query(correlation_id, request);
+        let result = self.run_query_with_proof(correlation_id, request);
 
         let response = match result {
-            Ok(QueryResult::Success(value)) => {
+            Ok((QueryResult::Success(value), proofs)) => {
+                if log_enabled!(Level::Debug) {
+                    debug!(
+                        "query result: {}; correlation_id: {}",
+                        utils::jsonify(&value, false),
+                        correlation_id
+                    );
+                }
                 let mut result = ipc::QueryResponse::new();
-                match value.to_bytes() {
-                    Ok(serialized_value) => {
+                // One entry per value read along the path, so that each proof can be told apart.
+                let serialized_proof: Result<Vec<Vec<u8>>, _> =
+                    proofs.iter().map(ToBytes::to_bytes).collect();
+                match (value.to_bytes(), serialized_proof) {
+                    (Ok(serialized_value), Ok(serialized_proof)) => {
                         info!("query successful; correlation_id: {}", correlation_id);
                         result.set_success(serialized_value);
+                        result.set_proof(serialized_proof.into());
+                    }
+                    (_, Err(error_msg)) => {
+                        let log_message = format!("Failed to serialize proof: {}", error_msg);
+                        warn!("{}", log_message);
+                        result.set_failure(log_message);
                     }
-                    Err(error_msg) => {
+                    (Err(error_msg), _) => {
                         let log_message = format!("Failed to serialize StoredValue: {}", error_msg);
                         warn!("{}", log_message);
                         result.set_failure(log_message);
@@ -124,20 +238,21 @@ where
                 }
                 result
             }
-            Ok(QueryResult::ValueNotFound(msg)) => {
+            Ok((QueryResult::ValueNotFound(msg), _)) => {
                 info!("{}", msg);
                 let mut result = ipc::QueryResponse::new();
                 result.set_failure(msg);
                 result
             }
-            Ok(QueryResult::RootNotFound) => {
+            Ok((QueryResult::RootNotFound, _)) => {
                 let log_message = "Root not found";
                 info!("{}", log_message);
                 let mut result = ipc::QueryResponse::new();
                 result.set_failure(log_message.to_string());
                 result
             }
-            Ok(QueryResult::CircularReference(msg)) => {
+            Ok((QueryResult::CircularReference(msg), _))
+            | Ok((QueryResult::DepthLimitExceeded(msg), _)) => {
                 warn!("{}", msg);
                 let mut result = ipc::QueryResponse::new();
                 result.set_failure(msg);
@@ -162,26 +277,136 @@ where
         SingleResponse::completed(response)
     }
 
+    fn batch_query(
+        &self,
+        _request_options: RequestOptions,
+        batch_query_request: ipc::BatchQueryRequest,
+    ) -> SingleResponse<BatchQueryResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut response = BatchQueryResponse::new();
+
+        match BatchQueryRequest::try_from(batch_query_request) {
+            Ok(request) => match self.run_batch_query(correlation_id, request) {
+                Ok(Some(results)) => {
+                    info!("batch query successful; correlation_id: {}", correlation_id);
+                    let protobuf_results_iter = results.into_iter().map(Into::into);
+                    response
+                        .mut_success()
+                        .set_results(FromIterator::from_iter(protobuf_results_iter));
+                }
+                Ok(None) => {
+                    let log_message = "Root not found";
+                    info!("{}", log_message);
+                    response.set_failure(log_message.to_string());
+                }
+                Err(err) => {
+                    let log_message = format!("{:?}", err);
+                    warn!("{}", log_message);
+                    response.set_failure(log_message);
+                }
+            },
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_BATCH_QUERY,
+            TAG_RESPONSE_BATCH_QUERY,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn get_balance(
+        &self,
+        _request_options: RequestOptions,
+        balance_request: ipc::BalanceRequest,
+    ) -> SingleResponse<BalanceResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut response = BalanceResponse::new();
+
+        match BalanceRequest::try_from(balance_request) {
+            Ok(request) => match self.get_purse_balance(correlation_id, request) {
+                Ok(BalanceResult::Success(balance)) => {
+                    info!("get balance successful; correlation_id: {}", correlation_id);
+                    response.set_success(balance.into());
+                }
+                Ok(BalanceResult::RootNotFound) => {
+                    let log_message = "Root not found";
+                    info!("{}", log_message);
+                    response.set_failure(log_message.to_string());
+                }
+                Ok(BalanceResult::PurseNotFound) => {
+                    let log_message = "Purse not found";
+                    info!("{}", log_message);
+                    response.set_failure(log_message.to_string());
+                }
+                Err(err) => {
+                    let log_message = format!("{:?}", err);
+                    warn!("{}", log_message);
+                    response.set_failure(log_message);
+                }
+            },
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_BALANCE,
+            TAG_RESPONSE_GET_BALANCE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
     fn execute(
         &self,
         _request_options: RequestOptions,
         exec_request: ipc::ExecuteRequest,
     ) -> SingleResponse<ExecuteResponse> {
+        if self.is_closing() {
+            warn!("{}", SHUTTING_DOWN);
+            return SingleResponse::err(GrpcError::Other(SHUTTING_DOWN));
+        }
+
+        if exceeds_max_deploys(self.config(), &exec_request) {
+            return SingleResponse::err(GrpcError::Other(TOO_MANY_DEPLOYS));
+        }
+
         let start = Instant::now();
-        let correlation_id = CorrelationId::new();
+        let correlation_id = correlation_id_or_new(exec_request.get_correlation_id());
 
-        let exec_request: ExecuteRequest = match exec_request.try_into() {
+        let mut exec_request: ExecuteRequest = match exec_request.try_into() {
             Ok(ret) => ret,
             Err(err) => {
                 return SingleResponse::completed(err);
             }
         };
+        reject_oversized_deploys(self.config(), &mut exec_request);
 
         let mut exec_response = ExecuteResponse::new();
 
-        let results = match self.run_execute(correlation_id, exec_request) {
+        let (results, rewards_result) = match self.run_execute(correlation_id, exec_request) {
             Ok(results) => results,
-            Err(error) => {
+            Err(ExecuteError::Closing) => {
+                warn!("{}", SHUTTING_DOWN);
+                return SingleResponse::err(GrpcError::Other(SHUTTING_DOWN));
+            }
+            Err(ExecuteError::RootNotFound(error)) => {
                 info!("deploy results error: RootNotFound");
                 exec_response.mut_missing_parent().set_hash(error.to_vec());
                 log_duration(
@@ -194,10 +419,28 @@ where
             }
         };
 
-        let protobuf_results_iter = results.into_iter().map(Into::into);
+        let conflicting_keys = conflicting_keys(&results);
+        let protobuf_results_iter =
+            results
+                .into_iter()
+                .zip(conflicting_keys)
+                .map(|(result, keys)| {
+                    let mut pb_deploy_result: DeployResult = result.into();
+                    if pb_deploy_result.has_execution_result() {
+                        pb_deploy_result
+                            .mut_execution_result()
+                            .set_conflicting_keys(keys.into_iter().map(Into::into).collect());
+                    }
+                    pb_deploy_result
+                });
         exec_response
             .mut_success()
             .set_deploy_results(FromIterator::from_iter(protobuf_results_iter));
+        if let Some(rewards_result) = rewards_result {
+            exec_response
+                .mut_success()
+                .set_rewards_result(rewards_result.into());
+        }
         log_duration(
             correlation_id,
             METRIC_DURATION_EXEC,
@@ -207,13 +450,42 @@ where
         SingleResponse::completed(exec_response)
     }
 
+    // Without shared ownership of `self` there is no way to keep executing once this returns, so
+    // the chunks are only sent once the whole request has run. The server uses the `Arc`
+    // implementation below, which streams them as each deploy finishes.
+    fn execute_stream(
+        &self,
+        _request_options: RequestOptions,
+        exec_request: ipc::ExecuteRequest,
+    ) -> StreamingResponse<DeployResultChunk> {
+        if self.is_closing() {
+            warn!("{}", SHUTTING_DOWN);
+            return StreamingResponse::err(GrpcError::Other(SHUTTING_DOWN));
+        }
+
+        if exceeds_max_deploys(self.config(), &exec_request) {
+            return StreamingResponse::err(GrpcError::Other(TOO_MANY_DEPLOYS));
+        }
+
+        let mut chunks = Vec::new();
+        match execute_chunks(self, exec_request, |chunk| chunks.push(chunk)) {
+            Ok(()) => StreamingResponse::completed(chunks),
+            Err(error) => StreamingResponse::err(error),
+        }
+    }
+
     fn commit(
         &self,
         _request_options: RequestOptions,
         mut commit_request: CommitRequest,
     ) -> SingleResponse<CommitResponse> {
+        if self.is_closing() {
+            warn!("{}", SHUTTING_DOWN);
+            return SingleResponse::err(GrpcError::Other(SHUTTING_DOWN));
+        }
+
         let start = Instant::now();
-        let correlation_id = CorrelationId::new();
+        let correlation_id = correlation_id_or_new(commit_request.get_correlation_id());
 
         // TODO
         let protocol_version = {
@@ -226,9 +498,9 @@ where
         };
 
         // Acquire pre-state hash
-        let pre_state_hash: Blake2bHash = match commit_request.get_prestate_hash().try_into() {
-            Err(_) => {
-                let error_message = "Could not parse pre-state hash".to_string();
+        let pre_state_hash = match parse_blake2b_hash(commit_request.get_prestate_hash()) {
+            Err(error) => {
+                let error_message = format!("Could not parse pre-state hash: {}", error);
                 warn!("{}", error_message);
                 let mut commit_response = CommitResponse::new();
                 commit_response
@@ -260,11 +532,17 @@ where
                 Ok(CommitResult::Success {
                     state_root,
                     bonded_validators,
+                    stats,
+                    modified_keys,
                 }) => {
                     let properties = {
                         let mut tmp = BTreeMap::new();
                         tmp.insert("post-state-hash", format!("{:?}", state_root));
                         tmp.insert("success", true.to_string());
+                        tmp.insert("leaves-written", stats.leaves_written.to_string());
+                        tmp.insert("nodes-written", stats.nodes_written.to_string());
+                        tmp.insert("bytes-written", stats.bytes_written.to_string());
+                        tmp.insert("keys-modified", modified_keys.len().to_string());
                         tmp
                     };
                     logging::log_details(
@@ -277,6 +555,8 @@ where
                     let commit_result = ret.mut_success();
                     commit_result.set_poststate_hash(state_root.to_vec());
                     commit_result.set_bonded_validators(bonds);
+                    commit_result
+                        .set_modified_keys(modified_keys.into_iter().map(Into::into).collect());
                 }
                 Ok(CommitResult::RootNotFound) => {
                     warn!("RootNotFound");
@@ -295,6 +575,10 @@ where
                     ret.mut_failed_transform()
                         .set_message(format!("{:?}", error));
                 }
+                Err(EngineError::Closing) => {
+                    warn!("{}", SHUTTING_DOWN);
+                    return SingleResponse::err(GrpcError::Other(SHUTTING_DOWN));
+                }
                 Err(error) => {
                     warn!("State error {:?} when applying transforms", error);
                     ret.mut_failed_transform()
@@ -453,6 +737,247 @@ where
         SingleResponse::completed(upgrade_response)
     }
 
+    fn prune(
+        &self,
+        _request_options: RequestOptions,
+        prune_request: PruneRequest,
+    ) -> SingleResponse<PruneResponse> {
+        if self.is_closing() {
+            warn!("{}", SHUTTING_DOWN);
+            return SingleResponse::err(GrpcError::Other(SHUTTING_DOWN));
+        }
+
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut prune_response = PruneResponse::new();
+
+        let retain_roots: Result<Vec<Blake2bHash>, _> = prune_request
+            .get_retain_roots()
+            .iter()
+            .map(|root| parse_blake2b_hash(root))
+            .collect();
+
+        match retain_roots {
+            Ok(retain_roots) => match self.prune(correlation_id, &retain_roots) {
+                Ok(report) => {
+                    info!(
+                        "prune successful; kept: {}, deleted: {}; correlation_id: {}",
+                        report.kept, report.deleted, correlation_id
+                    );
+                    let counts = prune_response.mut_success();
+                    counts.set_kept(report.kept as u64);
+                    counts.set_deleted(report.deleted as u64);
+                }
+                Err(EngineError::Closing) => {
+                    warn!("{}", SHUTTING_DOWN);
+                    return SingleResponse::err(GrpcError::Other(SHUTTING_DOWN));
+                }
+                Err(err) => {
+                    let log_message = format!("{:?}", err);
+                    warn!("{}", log_message);
+                    prune_response.set_failure(log_message);
+                }
+            },
+            Err(error) => {
+                let log_message = format!("Could not parse retained root hash: {}", error);
+                warn!("{}", log_message);
+                prune_response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_PRUNE,
+            TAG_RESPONSE_PRUNE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(prune_response)
+    }
+
+    fn export_trie(
+        &self,
+        _request_options: RequestOptions,
+        export_trie_request: ExportTrieRequest,
+    ) -> SingleResponse<ExportTrieResponse> {
+        if self.is_closing() {
+            warn!("{}", SHUTTING_DOWN);
+            return SingleResponse::err(GrpcError::Other(SHUTTING_DOWN));
+        }
+
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut export_trie_response = ExportTrieResponse::new();
+
+        let state_hash = match parse_blake2b_hash(export_trie_request.get_state_hash()) {
+            Ok(state_hash) => state_hash,
+            Err(error) => {
+                let log_message = format!("Could not parse state hash: {}", error);
+                warn!("{}", log_message);
+                export_trie_response.set_failure(log_message);
+                return SingleResponse::completed(export_trie_response);
+            }
+        };
+
+        let path = export_trie_request.get_path();
+        let result = match File::create(path) {
+            Ok(file) => {
+                match self.export_trie(correlation_id, state_hash, &mut BufWriter::new(file)) {
+                    Err(EngineError::Closing) => {
+                        let _ = fs::remove_file(path);
+                        warn!("{}", SHUTTING_DOWN);
+                        return SingleResponse::err(GrpcError::Other(SHUTTING_DOWN));
+                    }
+                    result => result.map_err(|error| format!("{:?}", error)),
+                }
+            }
+            Err(error) => Err(format!("{:?}", error)),
+        };
+
+        match result {
+            Ok(Some(trie_count)) => {
+                info!(
+                    "export successful; tries written: {}; correlation_id: {}",
+                    trie_count, correlation_id
+                );
+                export_trie_response.set_trie_count(trie_count as u64);
+            }
+            Ok(None) => {
+                // Don't leave an empty snapshot behind.
+                let _ = fs::remove_file(path);
+                info!("Root not found");
+                export_trie_response
+                    .mut_missing_root()
+                    .set_hash(state_hash.to_vec());
+            }
+            Err(log_message) => {
+                warn!("{}", log_message);
+                export_trie_response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_EXPORT_TRIE,
+            TAG_RESPONSE_EXPORT_TRIE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(export_trie_response)
+    }
+
+    fn import_trie(
+        &self,
+        _request_options: RequestOptions,
+        import_trie_request: ImportTrieRequest,
+    ) -> SingleResponse<ImportTrieResponse> {
+        if self.is_closing() {
+            warn!("{}", SHUTTING_DOWN);
+            return SingleResponse::err(GrpcError::Other(SHUTTING_DOWN));
+        }
+
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut import_trie_response = ImportTrieResponse::new();
+
+        let result = match File::open(import_trie_request.get_path()) {
+            Ok(file) => match self.import_trie(correlation_id, &mut BufReader::new(file)) {
+                Err(EngineError::Closing) => {
+                    warn!("{}", SHUTTING_DOWN);
+                    return SingleResponse::err(GrpcError::Other(SHUTTING_DOWN));
+                }
+                result => result.map_err(|error| format!("{:?}", error)),
+            },
+            Err(error) => Err(format!("{:?}", error)),
+        };
+
+        match result {
+            Ok(state_hash) => {
+                info!(
+                    "import successful; state_hash: {}; correlation_id: {}",
+                    state_hash, correlation_id
+                );
+                import_trie_response.set_state_hash(state_hash.to_vec());
+            }
+            Err(log_message) => {
+                warn!("{}", log_message);
+                import_trie_response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_IMPORT_TRIE,
+            TAG_RESPONSE_IMPORT_TRIE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(import_trie_response)
+    }
+
+    fn get_root_ancestry(
+        &self,
+        _request_options: RequestOptions,
+        get_root_ancestry_request: GetRootAncestryRequest,
+    ) -> SingleResponse<GetRootAncestryResponse> {
+        if self.is_closing() {
+            warn!("{}", SHUTTING_DOWN);
+            return SingleResponse::err(GrpcError::Other(SHUTTING_DOWN));
+        }
+
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut get_root_ancestry_response = GetRootAncestryResponse::new();
+
+        let state_hash = match parse_blake2b_hash(get_root_ancestry_request.get_state_hash()) {
+            Ok(state_hash) => state_hash,
+            Err(error) => {
+                let log_message = format!("Could not parse state hash: {}", error);
+                warn!("{}", log_message);
+                get_root_ancestry_response.set_failure(log_message);
+                return SingleResponse::completed(get_root_ancestry_response);
+            }
+        };
+
+        match self.root_ancestry(state_hash) {
+            Ok(ancestors) => {
+                debug!(
+                    "root ancestry found; ancestors: {}; correlation_id: {}",
+                    ancestors.len(),
+                    correlation_id
+                );
+                get_root_ancestry_response.mut_success().set_ancestors(
+                    ancestors
+                        .into_iter()
+                        .map(|ancestor| ancestor.to_vec())
+                        .collect(),
+                );
+            }
+            Err(EngineError::Closing) => {
+                warn!("{}", SHUTTING_DOWN);
+                return SingleResponse::err(GrpcError::Other(SHUTTING_DOWN));
+            }
+            Err(error) => {
+                let log_message = format!("{:?}", error);
+                warn!("{}", log_message);
+                get_root_ancestry_response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_ROOT_ANCESTRY,
+            TAG_RESPONSE_GET_ROOT_ANCESTRY,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(get_root_ancestry_response)
+    }
+
     fn bid_state(
         &self,
         _request_options: RequestOptions,
@@ -484,25 +1009,429 @@ where
     ) -> SingleResponse<UnbondPayoutResponse> {
         SingleResponse::err(GrpcError::Panic(UNIMPLEMENTED.to_string()))
     }
+
+    fn get_bonded_validators(
+        &self,
+        _request_options: RequestOptions,
+        get_bonded_validators_request: GetBondedValidatorsRequest,
+    ) -> SingleResponse<GetBondedValidatorsResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut response = GetBondedValidatorsResponse::new();
+
+        match BondedValidatorsRequest::try_from(get_bonded_validators_request) {
+            Ok(request) => {
+                let state_hash = request.state_hash();
+                match self.get_bonded_validators(correlation_id, request) {
+                    Ok(BondedValidatorsResult::Success(bonded_validators)) => {
+                        info!(
+                            "get bonded validators successful; correlation_id: {}",
+                            correlation_id
+                        );
+                        let mut pb_bonded_validators =
+                            GetBondedValidatorsResponse_BondedValidators::new();
+                        pb_bonded_validators
+                            .set_bonds(bonded_validators.into_iter().map(Into::into).collect());
+                        response.set_success(pb_bonded_validators);
+                    }
+                    Ok(BondedValidatorsResult::RootNotFound) => {
+                        info!("Root not found");
+                        response.mut_missing_state().set_hash(state_hash.to_vec());
+                    }
+                    Err(err) => {
+                        let log_message = format!("{:?}", err);
+                        warn!("{}", log_message);
+                        response.set_failure(log_message);
+                    }
+                }
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_BONDED_VALIDATORS,
+            TAG_RESPONSE_GET_BONDED_VALIDATORS,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+}
+
+// Allows the server to share an `EngineState` with its owner, e.g. so that the owner can close it
+// on shutdown, and lets `execute_stream` keep running after the handler has returned.
+impl<S> ExecutionEngineService for Arc<EngineState<S>>
+where
+    S: StateProvider + Send + Sync + 'static,
+    EngineError: From<S::Error>,
+    S::Error: Into<engine_core::execution::Error> + Debug,
+{
+    fn query(
+        &self,
+        request_options: RequestOptions,
+        query_request: ipc::QueryRequest,
+    ) -> SingleResponse<QueryResponse> {
+        (**self).query(request_options, query_request)
+    }
+
+    fn batch_query(
+        &self,
+        request_options: RequestOptions,
+        batch_query_request: ipc::BatchQueryRequest,
+    ) -> SingleResponse<BatchQueryResponse> {
+        (**self).batch_query(request_options, batch_query_request)
+    }
+
+    fn validate(
+        &self,
+        request_options: RequestOptions,
+        validate_request: ValidateRequest,
+    ) -> SingleResponse<ValidateResponse> {
+        (**self).validate(request_options, validate_request)
+    }
+
+    fn get_balance(
+        &self,
+        request_options: RequestOptions,
+        balance_request: ipc::BalanceRequest,
+    ) -> SingleResponse<BalanceResponse> {
+        (**self).get_balance(request_options, balance_request)
+    }
+
+    fn execute(
+        &self,
+        request_options: RequestOptions,
+        exec_request: ipc::ExecuteRequest,
+    ) -> SingleResponse<ExecuteResponse> {
+        (**self).execute(request_options, exec_request)
+    }
+
+    fn execute_stream(
+        &self,
+        _request_options: RequestOptions,
+        exec_request: ipc::ExecuteRequest,
+    ) -> StreamingResponse<DeployResultChunk> {
+        if self.is_closing() {
+            warn!("{}", SHUTTING_DOWN);
+            return StreamingResponse::err(GrpcError::Other(SHUTTING_DOWN));
+        }
+
+        if exceeds_max_deploys(self.config(), &exec_request) {
+            return StreamingResponse::err(GrpcError::Other(TOO_MANY_DEPLOYS));
+        }
+
+        let (sender, receiver) = mpsc::unbounded();
+        let engine_state = Arc::clone(self);
+        thread::spawn(move || {
+            // The receiver is only dropped once the client has gone away, in which case the
+            // remaining chunks have nowhere to go.
+            let result = execute_chunks(&engine_state, exec_request, |chunk| {
+                let _ = sender.unbounded_send(Ok(chunk));
+            });
+            if let Err(error) = result {
+                let _ = sender.unbounded_send(Err(error));
+            }
+        });
+        StreamingResponse::no_metadata(receiver.then(|received| match received {
+            Ok(result) => result,
+            Err(()) => Err(GrpcError::Other(EXEC_STREAM_WORKER_FAILED)),
+        }))
+    }
+
+    fn commit(
+        &self,
+        request_options: RequestOptions,
+        commit_request: CommitRequest,
+    ) -> SingleResponse<CommitResponse> {
+        (**self).commit(request_options, commit_request)
+    }
+
+    fn run_genesis(
+        &self,
+        request_options: RequestOptions,
+        run_genesis_request: ipc::RunGenesisRequest,
+    ) -> SingleResponse<GenesisResponse> {
+        (**self).run_genesis(request_options, run_genesis_request)
+    }
+
+    fn upgrade(
+        &self,
+        request_options: RequestOptions,
+        upgrade_request: UpgradeRequest,
+    ) -> SingleResponse<UpgradeResponse> {
+        (**self).upgrade(request_options, upgrade_request)
+    }
+
+    fn prune(
+        &self,
+        request_options: RequestOptions,
+        prune_request: PruneRequest,
+    ) -> SingleResponse<PruneResponse> {
+        // `EngineState::prune` would shadow the service method here.
+        ExecutionEngineService::prune(&**self, request_options, prune_request)
+    }
+
+    fn export_trie(
+        &self,
+        request_options: RequestOptions,
+        export_trie_request: ExportTrieRequest,
+    ) -> SingleResponse<ExportTrieResponse> {
+        ExecutionEngineService::export_trie(&**self, request_options, export_trie_request)
+    }
+
+    fn import_trie(
+        &self,
+        request_options: RequestOptions,
+        import_trie_request: ImportTrieRequest,
+    ) -> SingleResponse<ImportTrieResponse> {
+        ExecutionEngineService::import_trie(&**self, request_options, import_trie_request)
+    }
+
+    fn get_root_ancestry(
+        &self,
+        request_options: RequestOptions,
+        get_root_ancestry_request: GetRootAncestryRequest,
+    ) -> SingleResponse<GetRootAncestryResponse> {
+        (**self).get_root_ancestry(request_options, get_root_ancestry_request)
+    }
+
+    fn bid_state(
+        &self,
+        request_options: RequestOptions,
+        bid_state_request: BidStateRequest,
+    ) -> SingleResponse<BidStateResponse> {
+        (**self).bid_state(request_options, bid_state_request)
+    }
+
+    fn distribute_rewards(
+        &self,
+        request_options: RequestOptions,
+        distribute_rewards_request: DistributeRewardsRequest,
+    ) -> SingleResponse<DistributeRewardsResponse> {
+        (**self).distribute_rewards(request_options, distribute_rewards_request)
+    }
+
+    fn slash(
+        &self,
+        request_options: RequestOptions,
+        slash_request: SlashRequest,
+    ) -> SingleResponse<SlashResponse> {
+        (**self).slash(request_options, slash_request)
+    }
+
+    fn unbond_payout(
+        &self,
+        request_options: RequestOptions,
+        unbond_payout_request: UnbondPayoutRequest,
+    ) -> SingleResponse<UnbondPayoutResponse> {
+        (**self).unbond_payout(request_options, unbond_payout_request)
+    }
+
+    fn get_bonded_validators(
+        &self,
+        request_options: RequestOptions,
+        get_bonded_validators_request: GetBondedValidatorsRequest,
+    ) -> SingleResponse<GetBondedValidatorsResponse> {
+        // `EngineState::get_bonded_validators` would shadow the service method here.
+        ExecutionEngineService::get_bonded_validators(
+            &**self,
+            request_options,
+            get_bonded_validators_request,
+        )
+    }
+}
+
+/// Runs `exec_request`, passing a chunk to `send` for each deploy as soon as it has executed, and
+/// then one for the rewards result if the request has a proposer. If the parent state root is
+/// missing, a trailing `missing_parent` chunk is sent instead and execution stops.
+///
+/// Returns an error without sending any chunks if the engine is closing.
+fn execute_chunks<S, F>(
+    engine_state: &EngineState<S>,
+    exec_request: ipc::ExecuteRequest,
+    mut send: F,
+) -> Result<(), GrpcError>
+where
+    S: StateProvider + Sync,
+    EngineError: From<S::Error>,
+    S::Error: Into<engine_core::execution::Error> + Debug,
+    F: FnMut(DeployResultChunk),
+{
+    let start = Instant::now();
+    let correlation_id = correlation_id_or_new(exec_request.get_correlation_id());
+
+    let mut exec_request: ExecuteRequest = match exec_request.try_into() {
+        Ok(ret) => ret,
+        Err(mut err) => {
+            let mut chunk = DeployResultChunk::new();
+            chunk.set_missing_parent(err.take_missing_parent());
+            send(chunk);
+            return Ok(());
+        }
+    };
+    reject_oversized_deploys(engine_state.config(), &mut exec_request);
+
+    let result = engine_state.run_execute_with(correlation_id, exec_request, |result| {
+        let mut chunk = DeployResultChunk::new();
+        chunk.set_deploy_result(result.into());
+        send(chunk);
+    });
+
+    match result {
+        Ok(None) => (),
+        Ok(Some(rewards_result)) => {
+            let mut chunk = DeployResultChunk::new();
+            chunk.set_rewards_result(rewards_result.into());
+            send(chunk);
+        }
+        Err(ExecuteError::Closing) => {
+            warn!("{}", SHUTTING_DOWN);
+            return Err(GrpcError::Other(SHUTTING_DOWN));
+        }
+        Err(ExecuteError::RootNotFound(error)) => {
+            info!("deploy results error: RootNotFound");
+            let mut chunk = DeployResultChunk::new();
+            chunk.mut_missing_parent().set_hash(error.to_vec());
+            send(chunk);
+        }
+    }
+
+    log_duration(
+        correlation_id,
+        METRIC_DURATION_EXEC_STREAM,
+        TAG_RESPONSE_EXEC_STREAM,
+        start.elapsed(),
+    );
+    Ok(())
+}
+
+/// Returns `true` if `exec_request` carries more deploys than `config` allows, in which case the
+/// whole request is rejected before any of it is parsed.
+fn exceeds_max_deploys(config: &EngineConfig, exec_request: &ipc::ExecuteRequest) -> bool {
+    let deploy_count = exec_request.get_deploys().len();
+    let max = config.max_deploys_per_request() as usize;
+    if deploy_count > max {
+        warn!(
+            "{}: {} deploys, maximum {}",
+            TOO_MANY_DEPLOYS, deploy_count, max
+        );
+        return true;
+    }
+    false
+}
+
+/// Replaces each deploy of `exec_request` whose modules or args exceed the limits in `config`
+/// with a precondition failure, so that it is never executed.
+fn reject_oversized_deploys(config: &EngineConfig, exec_request: &mut ExecuteRequest) {
+    for deploy in exec_request.deploys.iter_mut() {
+        if let Ok(deploy_item) = deploy {
+            if let Err(error) = check_deploy_size(config, deploy_item) {
+                info!("{}", error);
+                *deploy = Err(ExecutionResult::precondition_failure(error));
+            }
+        }
+    }
+}
+
+fn check_deploy_size(config: &EngineConfig, deploy_item: &DeployItem) -> Result<(), EngineError> {
+    for executable in &[&deploy_item.session, &deploy_item.payment] {
+        if let ExecutableDeployItem::ModuleBytes { module_bytes, .. } = executable {
+            check_module_size(config, module_bytes)?;
+        }
+    }
+
+    let args_size = deploy_item.session.args().len() + deploy_item.payment.args().len();
+    let max = config.max_args_size();
+    if args_size > max as usize {
+        return Err(EngineError::ArgsTooLarge {
+            actual: args_size,
+            max,
+        });
+    }
+    Ok(())
+}
+
+fn check_module_size(config: &EngineConfig, module_bytes: &[u8]) -> Result<(), EngineError> {
+    let max = config.max_module_size();
+    if module_bytes.len() > max as usize {
+        return Err(EngineError::ModuleTooLarge {
+            actual: module_bytes.len(),
+            max,
+        });
+    }
+    Ok(())
+}
+
+/// Returns, for each of `results`, the keys on which its effects don't commute with those of any
+/// other result.  All deploys of a request run against the same pre-state, so these are the keys
+/// whose final values depend on the order the deploys are committed in.
+fn conflicting_keys(results: &[ExecutionResult]) -> Vec<Vec<Key>> {
+    results
+        .iter()
+        .enumerate()
+        .map(|(index, result)| {
+            let mut keys: Vec<Key> = results
+                .iter()
+                .enumerate()
+                .filter(|(other_index, _)| *other_index != index)
+                .flat_map(|(_, other)| result.effect().conflicts_with(other.effect()))
+                .collect();
+            keys.sort();
+            keys.dedup();
+            keys
+        })
+        .collect()
+}
+
+/// Returns the correlation ID supplied by the client, or a new random one if `supplied` is empty or
+/// can't be parsed.
+fn correlation_id_or_new(supplied: &str) -> CorrelationId {
+    if supplied.is_empty() {
+        return CorrelationId::new();
+    }
+    supplied.parse().unwrap_or_else(|error| {
+        warn!("{}; using a generated one instead", error);
+        CorrelationId::new()
+    })
+}
+
+/// The address on which the gRPC server listens.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Listen {
+    /// A Unix domain socket at the given path.
+    Uds(String),
+    /// A TCP socket at the given address.
+    Tcp(SocketAddr),
 }
 
-// Helper method which returns single DeployResult that is set to be a
-// WasmError.
 pub fn new<E: ExecutionEngineService + Sync + Send + 'static>(
-    socket: &str,
+    listen: Listen,
     thread_count: usize,
     e: E,
 ) -> ServerBuilder {
-    let socket_path = std::path::Path::new(socket);
+    let mut server = ServerBuilder::new_plain();
+    match listen {
+        Listen::Uds(socket) => {
+            let socket_path = std::path::Path::new(&socket);
 
-    if let Err(e) = std::fs::remove_file(socket_path) {
-        if e.kind() != ErrorKind::NotFound {
-            panic!("failed to remove old socket file: {:?}", e);
+            if let Err(e) = std::fs::remove_file(socket_path) {
+                if e.kind() != ErrorKind::NotFound {
+                    panic!("failed to remove old socket file: {:?}", e);
+                }
+            }
+
+            server.http.set_unix_addr(socket).unwrap();
+        }
+        Listen::Tcp(addr) => {
+            server.http.set_addr(addr).unwrap();
         }
     }
-
-    let mut server = ServerBuilder::new_plain();
-    server.http.set_unix_addr(socket.to_owned()).unwrap();
     server.http.set_cpu_pool_threads(thread_count);
     server.add_service(ExecutionEngineServiceServer::new_service_def(e));
     server
@@ -1,7 +1,10 @@
 use std::{
     collections::BTreeMap,
+    convert::TryFrom,
     fs,
+    net::{Ipv4Addr, SocketAddr},
     path::PathBuf,
+    process,
     str::FromStr,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -12,22 +15,39 @@ use std::{
 
 use clap::{App, Arg, ArgMatches};
 use dirs::home_dir;
-use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_core::engine_state::{
+    engine_config::{
+        DEFAULT_MAX_ARGS_SIZE, DEFAULT_MAX_DEPLOYS_PER_REQUEST, DEFAULT_MAX_MODULE_SIZE,
+        DEFAULT_MAX_QUERY_DEPTH, DEFAULT_MAX_STORED_VALUE_SIZE,
+    },
+    module_cache::DEFAULT_MODULE_CACHE_CAPACITY,
+    state_read_cache::DEFAULT_STATE_READ_CACHE_CAPACITY,
+    EngineConfig, EngineState,
+};
 use lmdb::DatabaseFlags;
-use log::{error, info, Level, LevelFilter};
+use log::{error, info, warn, Level};
 
 use engine_shared::{
     logging::{self, Settings, Style},
+    newtypes::{Blake2bHash, CorrelationId},
     os::get_page_size,
     socket,
 };
 use engine_storage::{
-    global_state::lmdb::LmdbGlobalState, transaction_source::lmdb::LmdbEnvironment,
+    global_state::lmdb::LmdbGlobalState,
+    transaction_source::lmdb::{LmdbEnvironment, LmdbEnvironmentOptions, DEFAULT_MAX_READERS},
     trie_store::lmdb::LmdbTrieStore,
 };
+use types::account::MAX_ASSOCIATED_KEYS;
 
-use casperlabs_engine_grpc_server::engine_server;
-use engine_storage::protocol_data_store::lmdb::LmdbProtocolDataStore;
+use casperlabs_engine_grpc_server::{
+    engine_server::{self, Listen},
+    metrics_server,
+};
+use engine_storage::{
+    protocol_data_store::lmdb::LmdbProtocolDataStore,
+    root_ancestry_store::lmdb::LmdbRootAncestryStore,
+};
 
 // exe / proc
 const PROC_NAME: &str = "casperlabs-engine-grpc-server";
@@ -47,6 +67,7 @@ const CREATE_DATA_DIR_EXPECT: &str = "Could not create directory";
 const LMDB_ENVIRONMENT_EXPECT: &str = "Could not create LmdbEnvironment";
 const LMDB_TRIE_STORE_EXPECT: &str = "Could not create LmdbTrieStore";
 const LMDB_PROTOCOL_DATA_STORE_EXPECT: &str = "Could not create LmdbProtocolDataStore";
+const LMDB_ROOT_ANCESTRY_STORE_EXPECT: &str = "Could not create LmdbRootAncestryStore";
 const LMDB_GLOBAL_STATE_EXPECT: &str = "Could not create LmdbGlobalState";
 
 // pages / lmdb
@@ -60,27 +81,70 @@ const GET_PAGES_EXPECT: &str = "Could not parse pages argument";
 // 805306368000 / 4096 = 196608000
 const DEFAULT_PAGES: usize = 196_608_000;
 
+// data-map-size / lmdb
+const ARG_DATA_MAP_SIZE: &str = "data-map-size";
+const ARG_DATA_MAP_SIZE_VALUE: &str = "BYTES";
+const ARG_DATA_MAP_SIZE_HELP: &str =
+    "Sets the max size in bytes of lmdb's mmap.  Cannot be used with the pages argument.";
+const GET_DATA_MAP_SIZE_EXPECT: &str = "Could not parse data-map-size argument";
+
+// max-readers / lmdb
+const ARG_MAX_READERS: &str = "max-readers";
+const ARG_MAX_READERS_VALUE: &str = "NUM";
+const ARG_MAX_READERS_HELP: &str = "Sets the max number of simultaneous lmdb read transactions";
+const GET_MAX_READERS_EXPECT: &str = "Could not parse max-readers argument";
+
+// no-sync / lmdb
+const ARG_NO_SYNC: &str = "no-sync";
+const ARG_NO_SYNC_HELP: &str =
+    "Don't flush lmdb's buffers to disk on each commit.  Faster, but may lose the most recent \
+     commits on a system crash";
+
+// check-integrity
+const ARG_CHECK_INTEGRITY: &str = "check-integrity";
+const ARG_CHECK_INTEGRITY_VALUE: &str = "ROOT_HASH";
+const ARG_CHECK_INTEGRITY_HELP: &str =
+    "Checks the integrity of the trie under the given hex-encoded root hash, prints a report and \
+     exits.  Exits with a non-zero code if any problems are found";
+const ARG_CHECK_INTEGRITY_EXPECT: &str = "Could not parse check-integrity argument";
+const CHECK_INTEGRITY_EXPECT: &str = "Could not check integrity of global state";
+
 // socket
 const ARG_SOCKET: &str = "socket";
 const ARG_SOCKET_HELP: &str =
     "Path to socket.  Note that this path is independent of the data directory.";
 const ARG_SOCKET_EXPECT: &str = "socket required";
 
+// listen-addr
+const ARG_LISTEN_ADDR: &str = "listen-addr";
+const ARG_LISTEN_ADDR_VALUE: &str = "HOST:PORT";
+const ARG_LISTEN_ADDR_HELP: &str =
+    "Listens on the given TCP address instead of a socket.  Cannot be used with the socket argument.";
+const ARG_LISTEN_ADDR_EXPECT: &str = "Could not parse listen-addr argument";
+
 // log level
 const ARG_LOG_LEVEL: &str = "log-level";
 const ARG_LOG_LEVEL_VALUE: &str = "LEVEL";
-const ARG_LOG_LEVEL_HELP: &str = "Sets the max logging level";
-const LOG_LEVEL_OFF: &str = "off";
-const LOG_LEVEL_ERROR: &str = "error";
-const LOG_LEVEL_WARN: &str = "warn";
+const ARG_LOG_LEVEL_HELP: &str =
+    "Sets the max logging level, one of off, error, warn, info, debug or \
+     trace, optionally followed by per-target overrides, e.g. \
+     warn,casperlabs_engine_core::runtime=error";
+const ARG_LOG_LEVEL_ENV: &str = "CASPERLABS_EE_LOG_LEVEL";
+const ARG_LOG_LEVEL_EXPECT: &str = "Could not parse log-level argument";
 const LOG_LEVEL_INFO: &str = "info";
-const LOG_LEVEL_DEBUG: &str = "debug";
-const LOG_LEVEL_TRACE: &str = "trace";
 
 // metrics
 const ARG_LOG_METRICS: &str = "log-metrics";
 const ARG_LOG_METRICS_HELP: &str = "Enables logging of metrics regardless of log-level setting";
 
+// metrics-port
+const ARG_METRICS_PORT: &str = "metrics-port";
+const ARG_METRICS_PORT_VALUE: &str = "PORT";
+const ARG_METRICS_PORT_HELP: &str =
+    "Serves metrics in the Prometheus text format over HTTP at /metrics on the given port";
+const ARG_METRICS_PORT_EXPECT: &str = "Could not parse metrics-port argument";
+const METRICS_SERVER_START_EXPECT: &str = "failed to start metrics server";
+
 // log style
 const ARG_LOG_STYLE: &str = "log-style";
 const ARG_LOG_STYLE_VALUE: &str = "STYLE";
@@ -107,6 +171,79 @@ const ARG_ENABLE_BONDING: &str = "enable-bonding";
 const ARG_ENABLE_BONDING_SHORT: &str = "b";
 const ARG_ENABLE_BONDING_HELP: &str = "Enable bonding";
 
+// parallel execution
+const ARG_PARALLEL_EXECUTION: &str = "parallel-execution";
+const ARG_PARALLEL_EXECUTION_HELP: &str =
+    "Execute the deploys of each execute request concurrently rather than one at a time";
+
+// state read cache
+const ARG_STATE_READ_CACHE_CAPACITY: &str = "state-read-cache-capacity";
+const ARG_STATE_READ_CACHE_CAPACITY_VALUE: &str = "NUM";
+const ARG_STATE_READ_CACHE_CAPACITY_HELP: &str =
+    "Sets the max number of global state values cached across executions against the same state \
+     root, or disables the cache if 0";
+const ARG_STATE_READ_CACHE_CAPACITY_EXPECT: &str =
+    "Could not parse state-read-cache-capacity argument";
+
+// module cache
+const ARG_MODULE_CACHE_CAPACITY: &str = "module-cache-capacity";
+const ARG_MODULE_CACHE_CAPACITY_VALUE: &str = "NUM";
+const ARG_MODULE_CACHE_CAPACITY_HELP: &str =
+    "Sets the max number of preprocessed session and payment modules reused across deploys, or \
+     disables the cache if 0";
+const ARG_MODULE_CACHE_CAPACITY_EXPECT: &str = "Could not parse module-cache-capacity argument";
+
+// max associated keys
+const ARG_MAX_ASSOCIATED_KEYS: &str = "max-associated-keys";
+const ARG_MAX_ASSOCIATED_KEYS_VALUE: &str = "NUM";
+const ARG_MAX_ASSOCIATED_KEYS_HELP: &str =
+    "Sets the max number of associated keys an account can have; can only lower the built-in \
+     limit";
+const ARG_MAX_ASSOCIATED_KEYS_EXPECT: &str = "Could not parse max-associated-keys argument";
+
+// max stored value size
+const ARG_MAX_STORED_VALUE_SIZE: &str = "max-stored-value-size";
+const ARG_MAX_STORED_VALUE_SIZE_VALUE: &str = "BYTES";
+const ARG_MAX_STORED_VALUE_SIZE_HELP: &str =
+    "Sets the max size in bytes of a value which a contract can write to global state";
+const ARG_MAX_STORED_VALUE_SIZE_EXPECT: &str = "Could not parse max-stored-value-size argument";
+
+// max deploys per request
+const ARG_MAX_DEPLOYS_PER_REQUEST: &str = "max-deploys-per-request";
+const ARG_MAX_DEPLOYS_PER_REQUEST_VALUE: &str = "NUM";
+const ARG_MAX_DEPLOYS_PER_REQUEST_HELP: &str =
+    "Sets the max number of deploys in an execute request; larger requests are rejected";
+const ARG_MAX_DEPLOYS_PER_REQUEST_EXPECT: &str = "Could not parse max-deploys-per-request argument";
+
+// max module size
+const ARG_MAX_MODULE_SIZE: &str = "max-module-size";
+const ARG_MAX_MODULE_SIZE_VALUE: &str = "BYTES";
+const ARG_MAX_MODULE_SIZE_HELP: &str =
+    "Sets the max size in bytes of a deploy's session or payment module";
+const ARG_MAX_MODULE_SIZE_EXPECT: &str = "Could not parse max-module-size argument";
+
+// max args size
+const ARG_MAX_ARGS_SIZE: &str = "max-args-size";
+const ARG_MAX_ARGS_SIZE_VALUE: &str = "BYTES";
+const ARG_MAX_ARGS_SIZE_HELP: &str =
+    "Sets the max combined size in bytes of a deploy's session and payment args";
+const ARG_MAX_ARGS_SIZE_EXPECT: &str = "Could not parse max-args-size argument";
+
+// max query depth
+const ARG_MAX_QUERY_DEPTH: &str = "max-query-depth";
+const ARG_MAX_QUERY_DEPTH_VALUE: &str = "NUM";
+const ARG_MAX_QUERY_DEPTH_HELP: &str =
+    "Sets the max number of keys stored as values a query will follow before failing";
+const ARG_MAX_QUERY_DEPTH_EXPECT: &str = "Could not parse max-query-depth argument";
+
+// shutdown timeout
+const ARG_SHUTDOWN_TIMEOUT: &str = "shutdown-timeout";
+const ARG_SHUTDOWN_TIMEOUT_DEFAULT: &str = "30";
+const ARG_SHUTDOWN_TIMEOUT_VALUE: &str = "SECONDS";
+const ARG_SHUTDOWN_TIMEOUT_HELP: &str =
+    "Max number of seconds to wait for in-flight requests to finish when shutting down";
+const ARG_SHUTDOWN_TIMEOUT_EXPECT: &str = "expected valid shutdown timeout";
+
 // runnable
 const SIGINT_HANDLE_EXPECT: &str = "Error setting Ctrl-C handler";
 const RUNNABLE_CHECK_INTERVAL_SECONDS: u64 = 3;
@@ -118,26 +255,40 @@ fn main() {
 
     let _ = logging::initialize(get_log_settings(&arg_matches));
 
+    if let Some(root_hash) = get_check_integrity_root_hash(&arg_matches) {
+        let global_state =
+            get_global_state(get_data_dir(&arg_matches), get_lmdb_options(&arg_matches));
+        check_integrity(&global_state, root_hash);
+    }
+
     info!("starting Execution Engine Server");
 
-    let socket = get_socket(&arg_matches);
+    let listen = get_listen(&arg_matches);
 
-    match socket.remove_file() {
-        Err(e) => panic!("failed to remove old socket file: {:?}", e),
-        Ok(_) => info!("removing old socket file"),
-    };
+    if let Listen::Uds(socket) = &listen {
+        match socket::Socket::new(socket.to_owned()).remove_file() {
+            Err(e) => panic!("failed to remove old socket file: {:?}", e),
+            Ok(_) => info!("removing old socket file"),
+        };
+    }
 
     let data_dir = get_data_dir(&arg_matches);
 
-    let map_size = get_map_size(&arg_matches);
+    let lmdb_options = get_lmdb_options(&arg_matches);
 
     let thread_count = get_thread_count(&arg_matches);
 
     let engine_config: EngineConfig = get_engine_config(&arg_matches);
 
-    let _server = get_grpc_server(&socket, data_dir, map_size, thread_count, engine_config);
+    let shutdown_timeout = get_shutdown_timeout(&arg_matches);
 
-    log_listening_message(&socket);
+    let engine_state = Arc::new(get_engine_state(data_dir, lmdb_options, engine_config));
+
+    let server = get_grpc_server(listen.clone(), thread_count, Arc::clone(&engine_state));
+
+    start_metrics_server(&arg_matches);
+
+    log_listening_message(&listen);
 
     let interval = Duration::from_secs(RUNNABLE_CHECK_INTERVAL_SECONDS);
 
@@ -148,6 +299,20 @@ fn main() {
     }
 
     info!("stopping Execution Engine Server");
+
+    close_engine_state(&engine_state, shutdown_timeout);
+
+    // Dropping the server and the last handle to the engine state closes the LMDB environment.
+    drop(server);
+    drop(engine_state);
+
+    if let Listen::Uds(socket) = &listen {
+        if let Err(e) = socket::Socket::new(socket.to_owned()).remove_file() {
+            error!("failed to remove socket file: {:?}", e);
+        }
+    }
+
+    info!("stopped Execution Engine Server");
 }
 
 /// Sets panic hook for logging panic info
@@ -174,12 +339,12 @@ fn get_args() -> ArgMatches<'static> {
                 .required(false)
                 .long(ARG_LOG_LEVEL)
                 .takes_value(true)
-                .possible_value(LOG_LEVEL_OFF)
-                .possible_value(LOG_LEVEL_ERROR)
-                .possible_value(LOG_LEVEL_WARN)
-                .possible_value(LOG_LEVEL_INFO)
-                .possible_value(LOG_LEVEL_DEBUG)
-                .possible_value(LOG_LEVEL_TRACE)
+                .env(ARG_LOG_LEVEL_ENV)
+                .validator(|spec| {
+                    Settings::from_level_spec(&spec)
+                        .map(|_| ())
+                        .map_err(|error| error.to_string())
+                })
                 .default_value(LOG_LEVEL_INFO)
                 .value_name(ARG_LOG_LEVEL_VALUE)
                 .help(ARG_LOG_LEVEL_HELP),
@@ -191,6 +356,14 @@ fn get_args() -> ArgMatches<'static> {
                 .takes_value(false)
                 .help(ARG_LOG_METRICS_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_METRICS_PORT)
+                .required(false)
+                .long(ARG_METRICS_PORT)
+                .takes_value(true)
+                .value_name(ARG_METRICS_PORT_VALUE)
+                .help(ARG_METRICS_PORT_HELP),
+        )
         .arg(
             Arg::with_name(ARG_LOG_STYLE)
                 .required(false)
@@ -218,6 +391,26 @@ fn get_args() -> ArgMatches<'static> {
                 .help(ARG_PAGES_HELP)
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name(ARG_DATA_MAP_SIZE)
+                .long(ARG_DATA_MAP_SIZE)
+                .value_name(ARG_DATA_MAP_SIZE_VALUE)
+                .help(ARG_DATA_MAP_SIZE_HELP)
+                .conflicts_with(ARG_PAGES)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_READERS)
+                .long(ARG_MAX_READERS)
+                .value_name(ARG_MAX_READERS_VALUE)
+                .help(ARG_MAX_READERS_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_NO_SYNC)
+                .long(ARG_NO_SYNC)
+                .help(ARG_NO_SYNC_HELP),
+        )
         .arg(
             Arg::with_name(ARG_THREAD_COUNT)
                 .short(ARG_THREAD_COUNT_SHORT)
@@ -227,6 +420,70 @@ fn get_args() -> ArgMatches<'static> {
                 .value_name(ARG_THREAD_COUNT_VALUE)
                 .help(ARG_THREAD_COUNT_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_STATE_READ_CACHE_CAPACITY)
+                .long(ARG_STATE_READ_CACHE_CAPACITY)
+                .value_name(ARG_STATE_READ_CACHE_CAPACITY_VALUE)
+                .help(ARG_STATE_READ_CACHE_CAPACITY_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_MODULE_CACHE_CAPACITY)
+                .long(ARG_MODULE_CACHE_CAPACITY)
+                .value_name(ARG_MODULE_CACHE_CAPACITY_VALUE)
+                .help(ARG_MODULE_CACHE_CAPACITY_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_ASSOCIATED_KEYS)
+                .long(ARG_MAX_ASSOCIATED_KEYS)
+                .value_name(ARG_MAX_ASSOCIATED_KEYS_VALUE)
+                .help(ARG_MAX_ASSOCIATED_KEYS_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_STORED_VALUE_SIZE)
+                .long(ARG_MAX_STORED_VALUE_SIZE)
+                .value_name(ARG_MAX_STORED_VALUE_SIZE_VALUE)
+                .help(ARG_MAX_STORED_VALUE_SIZE_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_DEPLOYS_PER_REQUEST)
+                .long(ARG_MAX_DEPLOYS_PER_REQUEST)
+                .value_name(ARG_MAX_DEPLOYS_PER_REQUEST_VALUE)
+                .help(ARG_MAX_DEPLOYS_PER_REQUEST_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_MODULE_SIZE)
+                .long(ARG_MAX_MODULE_SIZE)
+                .value_name(ARG_MAX_MODULE_SIZE_VALUE)
+                .help(ARG_MAX_MODULE_SIZE_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_ARGS_SIZE)
+                .long(ARG_MAX_ARGS_SIZE)
+                .value_name(ARG_MAX_ARGS_SIZE_VALUE)
+                .help(ARG_MAX_ARGS_SIZE_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_QUERY_DEPTH)
+                .long(ARG_MAX_QUERY_DEPTH)
+                .value_name(ARG_MAX_QUERY_DEPTH_VALUE)
+                .help(ARG_MAX_QUERY_DEPTH_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_SHUTDOWN_TIMEOUT)
+                .long(ARG_SHUTDOWN_TIMEOUT)
+                .takes_value(true)
+                .default_value(ARG_SHUTDOWN_TIMEOUT_DEFAULT)
+                .value_name(ARG_SHUTDOWN_TIMEOUT_VALUE)
+                .help(ARG_SHUTDOWN_TIMEOUT_HELP),
+        )
         .arg(
             Arg::with_name(ARG_USE_SYSTEM_CONTRACTS)
                 .short(ARG_USE_SYSTEM_CONTRACTS_SHORT)
@@ -239,16 +496,36 @@ fn get_args() -> ArgMatches<'static> {
                 .long(ARG_ENABLE_BONDING)
                 .help(ARG_ENABLE_BONDING_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_PARALLEL_EXECUTION)
+                .long(ARG_PARALLEL_EXECUTION)
+                .help(ARG_PARALLEL_EXECUTION_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_LISTEN_ADDR)
+                .long(ARG_LISTEN_ADDR)
+                .takes_value(true)
+                .value_name(ARG_LISTEN_ADDR_VALUE)
+                .help(ARG_LISTEN_ADDR_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_CHECK_INTEGRITY)
+                .long(ARG_CHECK_INTEGRITY)
+                .takes_value(true)
+                .value_name(ARG_CHECK_INTEGRITY_VALUE)
+                .help(ARG_CHECK_INTEGRITY_HELP),
+        )
         .arg(
             Arg::with_name(ARG_SOCKET)
-                .required(true)
+                .required_unless_one(&[ARG_LISTEN_ADDR, ARG_CHECK_INTEGRITY])
+                .conflicts_with(ARG_LISTEN_ADDR)
                 .help(ARG_SOCKET_HELP)
                 .index(1),
         )
         .get_matches()
 }
 
-/// Gets SIGINT handle to allow clean exit
+/// Gets SIGINT and SIGTERM handle to allow clean exit
 fn get_sigint_handle() -> Arc<AtomicBool> {
     let handle = Arc::new(AtomicBool::new(true));
     let h = handle.clone();
@@ -259,11 +536,16 @@ fn get_sigint_handle() -> Arc<AtomicBool> {
     handle
 }
 
-/// Gets value of socket argument
-fn get_socket(arg_matches: &ArgMatches) -> socket::Socket {
+/// Gets the address to listen on from either the listen-addr or socket argument
+fn get_listen(arg_matches: &ArgMatches) -> Listen {
+    if let Some(listen_addr) = arg_matches.value_of(ARG_LISTEN_ADDR) {
+        let addr = SocketAddr::from_str(listen_addr).expect(ARG_LISTEN_ADDR_EXPECT);
+        return Listen::Tcp(addr);
+    }
+
     let socket = arg_matches.value_of(ARG_SOCKET).expect(ARG_SOCKET_EXPECT);
 
-    socket::Socket::new(socket.to_owned())
+    Listen::Uds(socket.to_owned())
 }
 
 /// Gets value of data-dir argument
@@ -281,8 +563,11 @@ fn get_data_dir(arg_matches: &ArgMatches) -> PathBuf {
     buf
 }
 
-///  Parses pages argument and returns map size
+///  Parses data-map-size or pages argument and returns map size
 fn get_map_size(arg_matches: &ArgMatches) -> usize {
+    if let Some(map_size) = arg_matches.value_of(ARG_DATA_MAP_SIZE) {
+        return usize::from_str(map_size).expect(GET_DATA_MAP_SIZE_EXPECT);
+    }
     let page_size = get_page_size().unwrap();
     let pages = arg_matches
         .value_of(ARG_PAGES)
@@ -291,6 +576,17 @@ fn get_map_size(arg_matches: &ArgMatches) -> usize {
     page_size * pages
 }
 
+/// Parses lmdb arguments and returns the options for opening the environment
+fn get_lmdb_options(arg_matches: &ArgMatches) -> LmdbEnvironmentOptions {
+    let max_readers = arg_matches
+        .value_of(ARG_MAX_READERS)
+        .map_or(Ok(DEFAULT_MAX_READERS), u32::from_str)
+        .expect(GET_MAX_READERS_EXPECT);
+    LmdbEnvironmentOptions::new(get_map_size(arg_matches))
+        .with_max_readers(max_readers)
+        .with_no_sync(arg_matches.is_present(ARG_NO_SYNC))
+}
+
 fn get_thread_count(arg_matches: &ArgMatches) -> usize {
     arg_matches
         .value_of(ARG_THREAD_COUNT)
@@ -299,39 +595,138 @@ fn get_thread_count(arg_matches: &ArgMatches) -> usize {
         .expect(ARG_THREAD_COUNT_EXPECT)
 }
 
+fn get_shutdown_timeout(arg_matches: &ArgMatches) -> Duration {
+    arg_matches
+        .value_of(ARG_SHUTDOWN_TIMEOUT)
+        .map(str::parse)
+        .expect(ARG_SHUTDOWN_TIMEOUT_EXPECT)
+        .map(Duration::from_secs)
+        .expect(ARG_SHUTDOWN_TIMEOUT_EXPECT)
+}
+
 /// Returns an [`EngineConfig`].
 fn get_engine_config(arg_matches: &ArgMatches) -> EngineConfig {
     // feature flags go here
     let use_system_contracts = arg_matches.is_present(ARG_USE_SYSTEM_CONTRACTS);
     let enable_bonding = arg_matches.is_present(ARG_ENABLE_BONDING);
+    let parallel_execution = arg_matches.is_present(ARG_PARALLEL_EXECUTION);
+    let state_read_cache_capacity = arg_matches
+        .value_of(ARG_STATE_READ_CACHE_CAPACITY)
+        .map_or(Ok(DEFAULT_STATE_READ_CACHE_CAPACITY), usize::from_str)
+        .expect(ARG_STATE_READ_CACHE_CAPACITY_EXPECT);
+    let module_cache_capacity = arg_matches
+        .value_of(ARG_MODULE_CACHE_CAPACITY)
+        .map_or(Ok(DEFAULT_MODULE_CACHE_CAPACITY), usize::from_str)
+        .expect(ARG_MODULE_CACHE_CAPACITY_EXPECT);
+    let max_associated_keys = arg_matches
+        .value_of(ARG_MAX_ASSOCIATED_KEYS)
+        .map_or(Ok(MAX_ASSOCIATED_KEYS as u32), u32::from_str)
+        .expect(ARG_MAX_ASSOCIATED_KEYS_EXPECT);
+    let max_stored_value_size = arg_matches
+        .value_of(ARG_MAX_STORED_VALUE_SIZE)
+        .map_or(Ok(DEFAULT_MAX_STORED_VALUE_SIZE), u32::from_str)
+        .expect(ARG_MAX_STORED_VALUE_SIZE_EXPECT);
+    let max_deploys_per_request = arg_matches
+        .value_of(ARG_MAX_DEPLOYS_PER_REQUEST)
+        .map_or(Ok(DEFAULT_MAX_DEPLOYS_PER_REQUEST), u32::from_str)
+        .expect(ARG_MAX_DEPLOYS_PER_REQUEST_EXPECT);
+    let max_module_size = arg_matches
+        .value_of(ARG_MAX_MODULE_SIZE)
+        .map_or(Ok(DEFAULT_MAX_MODULE_SIZE), u32::from_str)
+        .expect(ARG_MAX_MODULE_SIZE_EXPECT);
+    let max_args_size = arg_matches
+        .value_of(ARG_MAX_ARGS_SIZE)
+        .map_or(Ok(DEFAULT_MAX_ARGS_SIZE), u32::from_str)
+        .expect(ARG_MAX_ARGS_SIZE_EXPECT);
+    let max_query_depth = arg_matches
+        .value_of(ARG_MAX_QUERY_DEPTH)
+        .map_or(Ok(DEFAULT_MAX_QUERY_DEPTH), u32::from_str)
+        .expect(ARG_MAX_QUERY_DEPTH_EXPECT);
     EngineConfig::new()
         .with_use_system_contracts(use_system_contracts)
         .with_enable_bonding(enable_bonding)
+        .with_parallel_execution(parallel_execution)
+        .with_state_read_cache_capacity(state_read_cache_capacity)
+        .with_module_cache_capacity(module_cache_capacity)
+        .with_max_associated_keys(max_associated_keys)
+        .with_max_stored_value_size(max_stored_value_size)
+        .with_max_deploys_per_request(max_deploys_per_request)
+        .with_max_module_size(max_module_size)
+        .with_max_args_size(max_args_size)
+        .with_max_query_depth(max_query_depth)
 }
 
 /// Builds and returns a gRPC server.
 fn get_grpc_server(
-    socket: &socket::Socket,
-    data_dir: PathBuf,
-    map_size: usize,
+    listen: Listen,
     thread_count: usize,
-    engine_config: EngineConfig,
+    engine_state: Arc<EngineState<LmdbGlobalState>>,
 ) -> grpc::Server {
-    let engine_state = get_engine_state(data_dir, map_size, engine_config);
-
-    engine_server::new(socket.as_str(), thread_count, engine_state)
+    engine_server::new(listen, thread_count, engine_state)
         .build()
         .expect(SERVER_START_EXPECT)
 }
 
+/// Starts the metrics server if the metrics-port argument was given
+fn start_metrics_server(arg_matches: &ArgMatches) {
+    let port = match arg_matches.value_of(ARG_METRICS_PORT) {
+        Some(port) => u16::from_str(port).expect(ARG_METRICS_PORT_EXPECT),
+        None => return,
+    };
+    let addr = SocketAddr::from((Ipv4Addr::UNSPECIFIED, port));
+    let addr = metrics_server::start(addr).expect(METRICS_SERVER_START_EXPECT);
+    info!("metrics server is listening on: {}", addr);
+}
+
+/// Parses the check-integrity argument, if present
+fn get_check_integrity_root_hash(arg_matches: &ArgMatches) -> Option<Blake2bHash> {
+    let encoded_root_hash = arg_matches.value_of(ARG_CHECK_INTEGRITY)?;
+    let root_hash_bytes = base16::decode(encoded_root_hash).expect(ARG_CHECK_INTEGRITY_EXPECT);
+    let root_hash =
+        Blake2bHash::try_from(root_hash_bytes.as_slice()).expect(ARG_CHECK_INTEGRITY_EXPECT);
+    Some(root_hash)
+}
+
+/// Checks the integrity of the trie under `root_hash`, prints the report and exits
+fn check_integrity(global_state: &LmdbGlobalState, root_hash: Blake2bHash) -> ! {
+    let report = global_state
+        .check_integrity(CorrelationId::new(), root_hash)
+        .expect(CHECK_INTEGRITY_EXPECT);
+
+    println!("root hash: {:x}", root_hash);
+    println!("nodes: {}", report.node_count);
+    println!("leaves: {}", report.leaf_count);
+    println!("chunks: {}", report.chunk_count);
+    for hash in &report.dangling_pointers {
+        println!("dangling pointer: {:x}", hash);
+    }
+    for hash in &report.hash_mismatches {
+        println!("hash mismatch: {:x}", hash);
+    }
+
+    if report.is_ok() {
+        println!("no problems found");
+        process::exit(0)
+    }
+    process::exit(1)
+}
+
 /// Builds and returns engine global state
 fn get_engine_state(
     data_dir: PathBuf,
-    map_size: usize,
+    lmdb_options: LmdbEnvironmentOptions,
     engine_config: EngineConfig,
 ) -> EngineState<LmdbGlobalState> {
+    let global_state = get_global_state(data_dir, lmdb_options);
+
+    EngineState::new(global_state, engine_config)
+}
+
+/// Opens and returns the lmdb-backed global state
+fn get_global_state(data_dir: PathBuf, lmdb_options: LmdbEnvironmentOptions) -> LmdbGlobalState {
     let environment = {
-        let ret = LmdbEnvironment::new(&data_dir, map_size).expect(LMDB_ENVIRONMENT_EXPECT);
+        let ret =
+            LmdbEnvironment::with_options(&data_dir, lmdb_options).expect(LMDB_ENVIRONMENT_EXPECT);
         Arc::new(ret)
     };
 
@@ -347,26 +742,39 @@ fn get_engine_state(
         Arc::new(ret)
     };
 
-    let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-        .expect(LMDB_GLOBAL_STATE_EXPECT);
+    let root_ancestry_store = {
+        let ret = LmdbRootAncestryStore::new(&environment, None, DatabaseFlags::empty())
+            .expect(LMDB_ROOT_ANCESTRY_STORE_EXPECT);
+        Arc::new(ret)
+    };
 
-    EngineState::new(global_state, engine_config)
+    LmdbGlobalState::empty(
+        environment,
+        trie_store,
+        protocol_data_store,
+        root_ancestry_store,
+    )
+    .expect(LMDB_GLOBAL_STATE_EXPECT)
+}
+
+/// Stops the engine state accepting new requests, waits for in-flight requests to finish and
+/// flushes global state to disk
+fn close_engine_state(engine_state: &EngineState<LmdbGlobalState>, timeout: Duration) {
+    match engine_state.close(timeout) {
+        Ok(true) => info!("all in-flight requests finished"),
+        Ok(false) => warn!(
+            "timed out waiting for {} in-flight requests to finish",
+            engine_state.in_flight_requests()
+        ),
+        Err(error) => error!("failed to flush global state: {:?}", error),
+    }
 }
 
 /// Builds and returns log settings
 fn get_log_settings(arg_matches: &ArgMatches) -> Settings {
-    let max_level = match arg_matches
+    let level_spec = arg_matches
         .value_of(ARG_LOG_LEVEL)
-        .expect("should have default value if not explicitly set")
-    {
-        LOG_LEVEL_OFF => LevelFilter::Off,
-        LOG_LEVEL_ERROR => LevelFilter::Error,
-        LOG_LEVEL_WARN => LevelFilter::Warn,
-        LOG_LEVEL_INFO => LevelFilter::Info,
-        LOG_LEVEL_DEBUG => LevelFilter::Debug,
-        LOG_LEVEL_TRACE => LevelFilter::Trace,
-        _ => unreachable!("should validate log-level arg to match one of the options"),
-    };
+        .expect("should have default value if not explicitly set");
 
     let enable_metrics = arg_matches.is_present(ARG_LOG_METRICS);
 
@@ -375,16 +783,22 @@ fn get_log_settings(arg_matches: &ArgMatches) -> Settings {
         _ => Style::Structured,
     };
 
-    Settings::new(max_level)
+    Settings::from_level_spec(level_spec)
+        .expect(ARG_LOG_LEVEL_EXPECT)
         .with_metrics_enabled(enable_metrics)
         .with_style(style)
 }
 
 /// Logs listening on socket message
-fn log_listening_message(socket: &socket::Socket) {
+fn log_listening_message(listen: &Listen) {
+    let socket = match listen {
+        Listen::Uds(socket) => socket.to_owned(),
+        Listen::Tcp(addr) => addr.to_string(),
+    };
+
     let mut properties = BTreeMap::new();
     properties.insert("listener", PROC_NAME.to_owned());
-    properties.insert("socket", socket.value());
+    properties.insert("socket", socket);
 
     logging::log_details(
         Level::Info,
@@ -0,0 +1,91 @@
+//! A minimal HTTP listener which serves the contents of the
+//! [`metrics`](engine_shared::logging::metrics) registry in the Prometheus text exposition format
+//! on `GET /metrics`.
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    thread,
+    time::Duration,
+};
+
+use log::warn;
+
+use engine_shared::logging::metrics;
+
+const METRICS_PATH: &str = "/metrics";
+const THREAD_NAME: &str = "metrics-server";
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+/// Connections are handled one at a time, so a client which stops sending or receiving must not
+/// hold up the others for long.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+/// The longest request line and headers read, including their line endings.  Longer requests are
+/// rejected.
+pub const MAX_REQUEST_HEAD_LENGTH: usize = 8192;
+
+/// Binds to `addr` and serves metrics from a background thread.
+///
+/// Returns the address actually bound to, which differs from `addr` if its port is `0`.
+pub fn start(addr: SocketAddr) -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)?;
+    let local_addr = listener.local_addr()?;
+    thread::Builder::new()
+        .name(THREAD_NAME.to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let result = stream.and_then(handle_connection);
+                if let Err(error) = result {
+                    warn!("metrics server failed to handle connection: {:?}", error);
+                }
+            }
+        })?;
+    Ok(local_addr)
+}
+
+fn handle_connection(mut stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+    let mut reader = BufReader::new((&stream).take(MAX_REQUEST_HEAD_LENGTH as u64));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Closing the connection with any of the request unread would reset it, and the client could
+    // lose the response, so the headers are read too, though they're not used.
+    let is_complete = request_line.ends_with('\n') && skip_headers(&mut reader)?;
+
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        // The request was cut short by the length limit, or the client stopped sending.
+        _ if !is_complete => ("400 Bad Request", String::new()),
+        (Some("GET"), Some(METRICS_PATH)) => ("200 OK", metrics::render()),
+        (Some("GET"), _) => ("404 Not Found", String::new()),
+        _ => ("405 Method Not Allowed", String::new()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        CONTENT_TYPE,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// Reads up to and including the blank line ending the request headers.  Returns `false` if the
+/// input ends first.
+fn skip_headers<R: BufRead>(reader: &mut R) -> io::Result<bool> {
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        reader.read_until(b'\n', &mut line)?;
+        if !line.ends_with(b"\n") {
+            return Ok(false);
+        }
+        if line == b"\r\n" || line == b"\n" {
+            return Ok(true);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use grpc::RequestOptions;
+
+use casperlabs_engine_grpc_server::engine_server::{
+    ipc::{BatchQueryRequest, BatchQueryRequest_Query, QueryResponse},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_shared::{newtypes::CorrelationId, stored_value::StoredValue};
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use types::{
+    account::AccountHash,
+    bytesrepr::{self, ToBytes},
+    CLValue, Key,
+};
+
+const MISSING_STATE_HASH: [u8; 32] = [255; 32];
+
+fn present_key() -> Key {
+    Key::Account(AccountHash::new([1; 32]))
+}
+
+fn missing_key() -> Key {
+    Key::Account(AccountHash::new([2; 32]))
+}
+
+fn present_value() -> StoredValue {
+    StoredValue::CLValue(CLValue::from_t(42_i32).unwrap())
+}
+
+fn setup() -> (EngineState<InMemoryGlobalState>, Vec<u8>) {
+    let (global_state, root_hash) =
+        InMemoryGlobalState::from_pairs(CorrelationId::new(), &[(present_key(), present_value())])
+            .expect("should create global state");
+    let engine_state = EngineState::new(global_state, EngineConfig::new());
+    (engine_state, root_hash.to_vec())
+}
+
+fn batch_query_request(state_hash: Vec<u8>, keys: &[Key]) -> BatchQueryRequest {
+    let queries: Vec<BatchQueryRequest_Query> = keys
+        .iter()
+        .map(|key| {
+            let mut query = BatchQueryRequest_Query::new();
+            query.set_base_key((*key).into());
+            query
+        })
+        .collect();
+
+    let mut request = BatchQueryRequest::new();
+    request.set_state_hash(state_hash);
+    request.set_queries(queries.into());
+    request
+}
+
+fn assert_success(response: &QueryResponse, expected: &StoredValue) {
+    assert!(response.has_success(), "{:?}", response);
+    let value: StoredValue =
+        bytesrepr::deserialize(response.get_success().to_vec()).expect("should deserialize");
+    assert_eq!(&value, expected);
+    assert_eq!(
+        response.get_success(),
+        expected.to_bytes().unwrap().as_slice()
+    );
+}
+
+#[test]
+fn should_return_per_entry_results_for_mixed_batch() {
+    let (engine_state, root_hash) = setup();
+    let request = batch_query_request(root_hash, &[present_key(), missing_key()]);
+
+    let response = engine_state
+        .batch_query(RequestOptions::new(), request)
+        .wait_drop_metadata()
+        .expect("should receive response");
+
+    assert!(response.has_success(), "{:?}", response);
+    let results = response.get_success().get_results();
+    assert_eq!(results.len(), 2);
+    assert_success(&results[0], &present_value());
+    assert!(results[1].has_failure());
+}
+
+#[test]
+fn should_return_a_result_for_each_duplicate_key() {
+    let (engine_state, root_hash) = setup();
+    let request = batch_query_request(root_hash, &[present_key(), present_key()]);
+
+    let response = engine_state
+        .batch_query(RequestOptions::new(), request)
+        .wait_drop_metadata()
+        .expect("should receive response");
+
+    assert!(response.has_success(), "{:?}", response);
+    let results = response.get_success().get_results();
+    assert_eq!(results.len(), 2);
+    assert_success(&results[0], &present_value());
+    assert_eq!(results[0], results[1]);
+}
+
+#[test]
+fn should_fail_whole_batch_when_root_not_found() {
+    let (engine_state, _) = setup();
+    let request = batch_query_request(MISSING_STATE_HASH.to_vec(), &[present_key()]);
+
+    let response = engine_state
+        .batch_query(RequestOptions::new(), request)
+        .wait_drop_metadata()
+        .expect("should receive response");
+
+    assert!(response.has_failure());
+    assert_eq!(response.get_failure(), "Root not found");
+}
@@ -0,0 +1,139 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use grpc::RequestOptions;
+
+use casperlabs_engine_grpc_server::engine_server::{
+    ipc::{self, DeployResultChunk, ExecuteResponse},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_core::engine_state::{
+    deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem, EngineConfig,
+    EngineState,
+};
+use engine_storage::{
+    global_state::{in_memory::InMemoryGlobalState, StateProvider},
+    protocol_data::ProtocolData,
+};
+use types::{account::AccountHash, ProtocolVersion};
+
+const DEPLOY_COUNT: u8 = 5;
+const MISSING_STATE_HASH: [u8; 32] = [255; 32];
+
+fn setup() -> (EngineState<InMemoryGlobalState>, Vec<u8>) {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    global_state
+        .put_protocol_data(ProtocolVersion::V1_0_0, &ProtocolData::default())
+        .expect("should put protocol data");
+    let root_hash = global_state.empty_root_hash.to_vec();
+    let engine_state = EngineState::new(global_state, EngineConfig::new());
+    (engine_state, root_hash)
+}
+
+fn deploy_item(seed: u8) -> ipc::DeployItem {
+    let address = AccountHash::new([seed; 32]);
+    let code = ExecutableDeployItem::ModuleBytes {
+        module_bytes: vec![],
+        args: vec![],
+    };
+    let mut authorization_keys = BTreeSet::new();
+    authorization_keys.insert(address);
+    DeployItem::new(
+        address,
+        code.clone(),
+        code,
+        1,
+        authorization_keys,
+        [seed; 32],
+    )
+    .into()
+}
+
+fn execute_request(parent_state_hash: Vec<u8>) -> ipc::ExecuteRequest {
+    let mut request = ipc::ExecuteRequest::new();
+    request.set_parent_state_hash(parent_state_hash);
+    request.set_block_time(42);
+    request.set_deploys((0..DEPLOY_COUNT).map(deploy_item).collect());
+    request.set_protocol_version(ProtocolVersion::V1_0_0.into());
+    request
+}
+
+fn execute_unary<E: ExecutionEngineService>(
+    engine: &E,
+    request: ipc::ExecuteRequest,
+) -> ExecuteResponse {
+    engine
+        .execute(RequestOptions::new(), request)
+        .wait_drop_metadata()
+        .expect("should receive response")
+}
+
+fn execute_streamed<E: ExecutionEngineService>(
+    engine: &E,
+    request: ipc::ExecuteRequest,
+) -> Vec<DeployResultChunk> {
+    engine
+        .execute_stream(RequestOptions::new(), request)
+        .wait_drop_metadata()
+        .collect::<Result<_, _>>()
+        .expect("should receive stream")
+}
+
+fn assert_stream_matches_unary(unary: &ExecuteResponse, chunks: &[DeployResultChunk]) {
+    if unary.has_missing_parent() {
+        assert_eq!(chunks.len(), 1, "{:?}", chunks);
+        assert_eq!(chunks[0].get_missing_parent(), unary.get_missing_parent());
+        return;
+    }
+
+    assert!(unary.has_success(), "{:?}", unary);
+    let streamed: Vec<ipc::DeployResult> = chunks
+        .iter()
+        .map(|chunk| {
+            assert!(chunk.has_deploy_result(), "{:?}", chunk);
+            chunk.get_deploy_result().clone()
+        })
+        .collect();
+    assert_eq!(
+        streamed.as_slice(),
+        unary.get_success().get_deploy_results()
+    );
+}
+
+#[test]
+fn should_stream_same_results_as_unary_execute() {
+    let (engine_state, root_hash) = setup();
+
+    let unary = execute_unary(&engine_state, execute_request(root_hash.clone()));
+    let chunks = execute_streamed(&engine_state, execute_request(root_hash));
+
+    assert_eq!(chunks.len(), usize::from(DEPLOY_COUNT));
+    assert_stream_matches_unary(&unary, &chunks);
+}
+
+#[test]
+fn should_stream_same_results_as_unary_execute_from_shared_engine_state() {
+    let (engine_state, root_hash) = setup();
+    let engine_state = Arc::new(engine_state);
+
+    let unary = execute_unary(&engine_state, execute_request(root_hash.clone()));
+    let chunks = execute_streamed(&engine_state, execute_request(root_hash));
+
+    assert_eq!(chunks.len(), usize::from(DEPLOY_COUNT));
+    assert_stream_matches_unary(&unary, &chunks);
+}
+
+#[test]
+fn should_end_stream_with_missing_parent() {
+    let (engine_state, _) = setup();
+    let engine_state = Arc::new(engine_state);
+
+    let unary = execute_unary(&engine_state, execute_request(MISSING_STATE_HASH.to_vec()));
+    let chunks = execute_streamed(&engine_state, execute_request(MISSING_STATE_HASH.to_vec()));
+
+    assert!(unary.has_missing_parent(), "{:?}", unary);
+    assert_eq!(
+        chunks.last().unwrap().get_missing_parent().get_hash(),
+        MISSING_STATE_HASH
+    );
+    assert_stream_matches_unary(&unary, &chunks);
+}
@@ -0,0 +1,48 @@
+use std::net::{SocketAddr, TcpListener};
+
+use grpc::{ClientStubExt, RequestOptions};
+
+use casperlabs_engine_grpc_server::engine_server::{
+    self,
+    ipc::QueryRequest,
+    ipc_grpc::{ExecutionEngineService, ExecutionEngineServiceClient},
+    Listen,
+};
+use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use types::{account::AccountHash, Key};
+
+const LOCALHOST: &str = "127.0.0.1";
+const MISSING_STATE_HASH: [u8; 32] = [255; 32];
+
+fn get_ephemeral_addr() -> SocketAddr {
+    let listener = TcpListener::bind((LOCALHOST, 0)).expect("should bind ephemeral port");
+    listener.local_addr().expect("should have local address")
+}
+
+#[test]
+fn should_serve_query_over_tcp() {
+    let addr = get_ephemeral_addr();
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let engine_state = EngineState::new(global_state, EngineConfig::new());
+
+    let _server = engine_server::new(Listen::Tcp(addr), 1, engine_state)
+        .build()
+        .expect("should start server");
+
+    let client =
+        ExecutionEngineServiceClient::new_plain(LOCALHOST, addr.port(), Default::default())
+            .expect("should create client");
+
+    let mut query_request = QueryRequest::new();
+    query_request.set_state_hash(MISSING_STATE_HASH.to_vec());
+    query_request.set_base_key(Key::Account(AccountHash::new([1; 32])).into());
+
+    let response = client
+        .query(RequestOptions::new(), query_request)
+        .wait_drop_metadata()
+        .expect("should receive response");
+
+    assert!(response.has_failure());
+    assert_eq!(response.get_failure(), "Root not found");
+}
@@ -0,0 +1,81 @@
+use grpc::RequestOptions;
+
+use casperlabs_engine_grpc_server::engine_server::{
+    ipc::{self, CommitRequest, QueryRequest},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use types::{account::AccountHash, Key, ProtocolVersion};
+
+const MALFORMED_STATE_HASH_LENGTHS: [usize; 3] = [0, 31, 33];
+
+fn setup() -> EngineState<InMemoryGlobalState> {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    EngineState::new(global_state, EngineConfig::new())
+}
+
+fn malformed_state_hashes() -> impl Iterator<Item = Vec<u8>> {
+    MALFORMED_STATE_HASH_LENGTHS
+        .iter()
+        .map(|length| vec![1; *length])
+}
+
+#[test]
+fn query_should_fail_on_malformed_state_hash() {
+    let engine_state = setup();
+
+    for state_hash in malformed_state_hashes() {
+        let mut request = QueryRequest::new();
+        request.set_state_hash(state_hash);
+        request.set_base_key(Key::Account(AccountHash::new([1; 32])).into());
+        request.set_protocol_version(ProtocolVersion::V1_0_0.into());
+
+        let response = engine_state
+            .query(RequestOptions::new(), request)
+            .wait_drop_metadata()
+            .expect("should receive response");
+
+        assert!(response.has_failure(), "{:?}", response);
+    }
+}
+
+#[test]
+fn execute_should_report_missing_parent_on_malformed_state_hash() {
+    let engine_state = setup();
+
+    for state_hash in malformed_state_hashes() {
+        let mut request = ipc::ExecuteRequest::new();
+        request.set_parent_state_hash(state_hash.clone());
+        request.set_protocol_version(ProtocolVersion::V1_0_0.into());
+
+        let response = engine_state
+            .execute(RequestOptions::new(), request)
+            .wait_drop_metadata()
+            .expect("should receive response");
+
+        assert!(response.has_missing_parent(), "{:?}", response);
+        assert_eq!(
+            response.get_missing_parent().get_hash(),
+            state_hash.as_slice()
+        );
+    }
+}
+
+#[test]
+fn commit_should_fail_on_malformed_state_hash() {
+    let engine_state = setup();
+
+    for state_hash in malformed_state_hashes() {
+        let mut request = CommitRequest::new();
+        request.set_prestate_hash(state_hash);
+        request.set_protocol_version(ProtocolVersion::V1_0_0.into());
+
+        let response = engine_state
+            .commit(RequestOptions::new(), request)
+            .wait_drop_metadata()
+            .expect("should receive response");
+
+        assert!(response.has_failed_transform(), "{:?}", response);
+    }
+}
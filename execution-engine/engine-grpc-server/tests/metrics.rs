@@ -0,0 +1,130 @@
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+};
+
+use grpc::{ClientStubExt, RequestOptions};
+
+use casperlabs_engine_grpc_server::{
+    engine_server::{
+        self,
+        ipc::QueryRequest,
+        ipc_grpc::{ExecutionEngineService, ExecutionEngineServiceClient},
+        Listen,
+    },
+    metrics_server,
+};
+use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use types::{account::AccountHash, Key};
+
+const LOCALHOST: &str = "127.0.0.1";
+const MISSING_STATE_HASH: [u8; 32] = [255; 32];
+const QUERY_DURATION_COUNT: &str = "query_duration_count{tag=\"query_response\"}";
+const QUERY_DURATION_SUM: &str = "query_duration_sum{tag=\"query_response\"}";
+
+fn get_ephemeral_addr() -> SocketAddr {
+    let listener = TcpListener::bind((LOCALHOST, 0)).expect("should bind ephemeral port");
+    listener.local_addr().expect("should have local address")
+}
+
+fn http_get(addr: SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).expect("should connect to metrics server");
+    write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\n\r\n", path, addr).expect("should send request");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .expect("should read response");
+    response
+}
+
+fn get_sample(body: &str, series: &str) -> f64 {
+    body.lines()
+        .find(|line| line.starts_with(series))
+        .map(|line| &line[series.len()..])
+        .unwrap_or_else(|| panic!("should have {} in {}", series, body))
+        .trim()
+        .parse()
+        .expect("should parse sample")
+}
+
+#[test]
+fn should_serve_query_duration_metrics() {
+    let addr = get_ephemeral_addr();
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let engine_state = EngineState::new(global_state, EngineConfig::new());
+
+    let _server = engine_server::new(Listen::Tcp(addr), 1, engine_state)
+        .build()
+        .expect("should start server");
+
+    let metrics_addr = metrics_server::start(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .expect("should start metrics server");
+
+    let client =
+        ExecutionEngineServiceClient::new_plain(LOCALHOST, addr.port(), Default::default())
+            .expect("should create client");
+
+    let mut query_request = QueryRequest::new();
+    query_request.set_state_hash(MISSING_STATE_HASH.to_vec());
+    query_request.set_base_key(Key::Account(AccountHash::new([1; 32])).into());
+
+    client
+        .query(RequestOptions::new(), query_request)
+        .wait_drop_metadata()
+        .expect("should receive response");
+
+    let response = http_get(metrics_addr, "/metrics");
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+
+    let body = response.split("\r\n\r\n").nth(1).expect("should have body");
+    assert!(get_sample(body, QUERY_DURATION_COUNT) >= 1.0);
+    assert!(get_sample(body, QUERY_DURATION_SUM) > 0.0);
+}
+
+#[test]
+fn should_not_find_other_paths() {
+    let metrics_addr = metrics_server::start(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .expect("should start metrics server");
+
+    let response = http_get(metrics_addr, "/");
+    assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+}
+
+#[test]
+fn should_reject_overlong_request_line() {
+    let metrics_addr = metrics_server::start(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .expect("should start metrics server");
+
+    // Exactly fills the limit without ending the line, so the server reads everything sent.
+    let path = "a".repeat(metrics_server::MAX_REQUEST_HEAD_LENGTH - "GET /".len());
+    let mut stream = TcpStream::connect(metrics_addr).expect("should connect to metrics server");
+    write!(stream, "GET /{}", path).expect("should send request");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .expect("should read response");
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+
+    let response = http_get(metrics_addr, "/metrics");
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+}
+
+#[test]
+fn should_reject_overlong_request_headers() {
+    let metrics_addr = metrics_server::start(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .expect("should start metrics server");
+
+    // Exactly fills the limit without ending the headers, so the server reads everything sent.
+    let request_line = "GET /metrics HTTP/1.1\r\n";
+    let header_name = "X-Padding: ";
+    let padding = "a"
+        .repeat(metrics_server::MAX_REQUEST_HEAD_LENGTH - request_line.len() - header_name.len());
+    let mut stream = TcpStream::connect(metrics_addr).expect("should connect to metrics server");
+    write!(stream, "{}{}{}", request_line, header_name, padding).expect("should send request");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .expect("should read response");
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+}
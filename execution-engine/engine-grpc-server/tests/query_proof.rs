@@ -0,0 +1,71 @@
+use grpc::RequestOptions;
+
+use casperlabs_engine_grpc_server::engine_server::{
+    ipc::QueryRequest, ipc_grpc::ExecutionEngineService,
+};
+use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_shared::{newtypes::CorrelationId, stored_value::StoredValue};
+use engine_storage::{
+    global_state::in_memory::InMemoryGlobalState,
+    trie::{self, TrieMerkleProof},
+};
+use types::{
+    bytesrepr, contracts::NamedKeys, AccessRights, CLValue, Contract, EntryPoints, Key,
+    ProtocolVersion, URef,
+};
+
+const VALUE_NAME: &str = "value";
+
+#[test]
+fn should_return_one_proof_per_value_read_along_the_path() {
+    let value_key = Key::URef(URef::new([255; 32], AccessRights::READ));
+    let value = StoredValue::CLValue(CLValue::from_t(1_u64).unwrap());
+
+    let contract_key = Key::Hash([1; 32]);
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(VALUE_NAME.to_string(), value_key);
+    let contract = StoredValue::Contract(Contract::new(
+        [2; 32],
+        [3; 32],
+        named_keys,
+        EntryPoints::default(),
+        ProtocolVersion::V1_0_0,
+    ));
+
+    let (global_state, root_hash) = InMemoryGlobalState::from_pairs(
+        CorrelationId::new(),
+        &[(value_key, value.clone()), (contract_key, contract.clone())],
+    )
+    .expect("should create global state");
+    let engine_state = EngineState::new(global_state, EngineConfig::new());
+
+    let mut request = QueryRequest::new();
+    request.set_state_hash(root_hash.to_vec());
+    request.set_base_key(contract_key.into());
+    request.set_path(vec![VALUE_NAME.to_string()].into());
+
+    let response = engine_state
+        .query(RequestOptions::new(), request)
+        .wait_drop_metadata()
+        .expect("should receive response");
+
+    assert!(response.has_success(), "{:?}", response);
+    let proofs: Vec<TrieMerkleProof<Key, StoredValue>> = response
+        .get_proof()
+        .iter()
+        .map(|proof| bytesrepr::deserialize(proof.clone()).expect("should deserialize proof"))
+        .collect();
+    assert_eq!(proofs.len(), 2);
+    assert!(trie::validate_proof(
+        &root_hash,
+        &contract_key,
+        &contract,
+        &proofs[0]
+    ));
+    assert!(trie::validate_proof(
+        &root_hash,
+        &value_key.normalize(),
+        &value,
+        &proofs[1]
+    ));
+}
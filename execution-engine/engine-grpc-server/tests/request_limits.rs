@@ -0,0 +1,197 @@
+use std::collections::BTreeSet;
+
+use grpc::RequestOptions;
+
+use casperlabs_engine_grpc_server::engine_server::{
+    ipc::{
+        self, DeployResult, DeployResult_PreconditionFailure_Code, ExecuteResponse, ValidateRequest,
+    },
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_core::engine_state::{
+    deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem, EngineConfig,
+    EngineState,
+};
+use engine_storage::{
+    global_state::{in_memory::InMemoryGlobalState, StateProvider},
+    protocol_data::ProtocolData,
+};
+use types::{account::AccountHash, ProtocolVersion};
+
+const MAX_DEPLOYS_PER_REQUEST: u32 = 3;
+const MAX_MODULE_SIZE: u32 = 16;
+const MAX_ARGS_SIZE: u32 = 8;
+
+fn setup() -> (EngineState<InMemoryGlobalState>, Vec<u8>) {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    global_state
+        .put_protocol_data(ProtocolVersion::V1_0_0, &ProtocolData::default())
+        .expect("should put protocol data");
+    let root_hash = global_state.empty_root_hash.to_vec();
+    let engine_config = EngineConfig::new()
+        .with_max_deploys_per_request(MAX_DEPLOYS_PER_REQUEST)
+        .with_max_module_size(MAX_MODULE_SIZE)
+        .with_max_args_size(MAX_ARGS_SIZE);
+    let engine_state = EngineState::new(global_state, engine_config);
+    (engine_state, root_hash)
+}
+
+fn deploy_item(session: ExecutableDeployItem, payment: ExecutableDeployItem) -> ipc::DeployItem {
+    let address = AccountHash::new([1; 32]);
+    let mut authorization_keys = BTreeSet::new();
+    authorization_keys.insert(address);
+    DeployItem::new(address, session, payment, 1, authorization_keys, [1; 32]).into()
+}
+
+fn module_bytes(module_size: u32, args_size: u32) -> ExecutableDeployItem {
+    ExecutableDeployItem::ModuleBytes {
+        module_bytes: vec![0; module_size as usize],
+        args: vec![0; args_size as usize],
+    }
+}
+
+fn execute_request(
+    parent_state_hash: Vec<u8>,
+    deploys: Vec<ipc::DeployItem>,
+) -> ipc::ExecuteRequest {
+    let mut request = ipc::ExecuteRequest::new();
+    request.set_parent_state_hash(parent_state_hash);
+    request.set_deploys(deploys.into());
+    request.set_protocol_version(ProtocolVersion::V1_0_0.into());
+    request
+}
+
+fn execute(deploys: Vec<ipc::DeployItem>) -> ExecuteResponse {
+    let (engine_state, root_hash) = setup();
+    engine_state
+        .execute(RequestOptions::new(), execute_request(root_hash, deploys))
+        .wait_drop_metadata()
+        .expect("should receive response")
+}
+
+fn assert_too_large(deploy_result: &DeployResult, expected_message: &str) {
+    assert!(
+        deploy_result.has_precondition_failure(),
+        "{:?}",
+        deploy_result
+    );
+    let precondition_failure = deploy_result.get_precondition_failure();
+    assert_eq!(
+        precondition_failure.get_code(),
+        DeployResult_PreconditionFailure_Code::INVALID_ARGUMENT
+    );
+    assert!(
+        precondition_failure
+            .get_message()
+            .contains(expected_message),
+        "{:?}",
+        precondition_failure
+    );
+}
+
+#[test]
+fn execute_should_reject_request_with_too_many_deploys() {
+    let (engine_state, root_hash) = setup();
+    let deploys = (0..=MAX_DEPLOYS_PER_REQUEST)
+        .map(|_| deploy_item(module_bytes(0, 0), module_bytes(0, 0)))
+        .collect();
+    let request = execute_request(root_hash, deploys);
+
+    let unary_result = engine_state
+        .execute(RequestOptions::new(), request.clone())
+        .wait_drop_metadata();
+    assert!(unary_result.is_err(), "{:?}", unary_result);
+
+    let stream_result = engine_state
+        .execute_stream(RequestOptions::new(), request)
+        .wait_drop_metadata()
+        .collect::<Result<Vec<_>, _>>();
+    assert!(stream_result.is_err(), "{:?}", stream_result);
+}
+
+#[test]
+fn execute_should_accept_request_with_max_deploys() {
+    let deploys = (0..MAX_DEPLOYS_PER_REQUEST)
+        .map(|_| deploy_item(module_bytes(0, 0), module_bytes(0, 0)))
+        .collect();
+
+    let response = execute(deploys);
+
+    assert!(response.has_success(), "{:?}", response);
+    assert_eq!(
+        response.get_success().get_deploy_results().len(),
+        MAX_DEPLOYS_PER_REQUEST as usize
+    );
+}
+
+#[test]
+fn execute_should_fail_deploys_with_oversized_modules() {
+    let deploys = vec![
+        deploy_item(module_bytes(MAX_MODULE_SIZE + 1, 0), module_bytes(0, 0)),
+        deploy_item(module_bytes(0, 0), module_bytes(MAX_MODULE_SIZE + 1, 0)),
+    ];
+
+    let response = execute(deploys);
+
+    let deploy_results = response.get_success().get_deploy_results();
+    assert_eq!(deploy_results.len(), 2);
+    for deploy_result in deploy_results {
+        assert_too_large(deploy_result, "maximum module size");
+    }
+}
+
+#[test]
+fn execute_should_fail_deploys_with_oversized_args() {
+    let half = MAX_ARGS_SIZE / 2 + 1;
+    let stored_contract = ExecutableDeployItem::StoredContractByName {
+        name: "contract".to_string(),
+        entry_point: "call".to_string(),
+        args: vec![0; MAX_ARGS_SIZE as usize + 1],
+    };
+    let deploys = vec![
+        deploy_item(stored_contract, module_bytes(0, 0)),
+        // Neither args exceed the limit on their own, but together they do.
+        deploy_item(module_bytes(0, half), module_bytes(0, half)),
+        deploy_item(
+            module_bytes(MAX_MODULE_SIZE, 0),
+            module_bytes(0, MAX_ARGS_SIZE),
+        ),
+    ];
+
+    let response = execute(deploys);
+
+    let deploy_results = response.get_success().get_deploy_results();
+    assert_eq!(deploy_results.len(), 3);
+    assert_too_large(&deploy_results[0], "maximum args size");
+    assert_too_large(&deploy_results[1], "maximum args size");
+    // Deploys within the limits are executed as usual; this one fails for lack of an account.
+    assert!(
+        !deploy_results[2]
+            .get_precondition_failure()
+            .get_message()
+            .contains("maximum"),
+        "{:?}",
+        deploy_results[2]
+    );
+}
+
+#[test]
+fn validate_should_reject_oversized_module() {
+    let (engine_state, _) = setup();
+    let mut request = ValidateRequest::new();
+    request.set_wasm_code(vec![0; MAX_MODULE_SIZE as usize + 1]);
+    request.set_protocol_version(ProtocolVersion::V1_0_0.into());
+
+    let response = engine_state
+        .validate(RequestOptions::new(), request)
+        .wait_drop_metadata()
+        .expect("should receive response");
+
+    assert!(
+        response
+            .get_preprocessing_error()
+            .contains("maximum module size"),
+        "{:?}",
+        response
+    );
+}
@@ -0,0 +1,69 @@
+use std::collections::BTreeSet;
+
+use grpc::RequestOptions;
+
+use casperlabs_engine_grpc_server::engine_server::{
+    ipc::{self, DeployResult_PreconditionFailure_Code},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_core::engine_state::{
+    deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem, EngineConfig,
+    EngineState, Error,
+};
+use engine_storage::{
+    global_state::{in_memory::InMemoryGlobalState, StateProvider},
+    protocol_data::ProtocolData,
+};
+use types::{account::AccountHash, ProtocolVersion};
+
+fn setup() -> (EngineState<InMemoryGlobalState>, Vec<u8>) {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    global_state
+        .put_protocol_data(ProtocolVersion::V1_0_0, &ProtocolData::default())
+        .expect("should put protocol data");
+    let root_hash = global_state.empty_root_hash.to_vec();
+    let engine_state = EngineState::new(global_state, EngineConfig::new());
+    (engine_state, root_hash)
+}
+
+#[test]
+fn should_report_precondition_failure_for_unseen_account() {
+    let (engine_state, root_hash) = setup();
+
+    let address = AccountHash::new(rand::random());
+    let code = ExecutableDeployItem::ModuleBytes {
+        module_bytes: vec![],
+        args: vec![],
+    };
+    let mut authorization_keys = BTreeSet::new();
+    authorization_keys.insert(address);
+    let deploy_item = DeployItem::new(address, code.clone(), code, 1, authorization_keys, [1; 32]);
+
+    let mut request = ipc::ExecuteRequest::new();
+    request.set_parent_state_hash(root_hash);
+    request.set_deploys(vec![deploy_item.into()].into());
+    request.set_protocol_version(ProtocolVersion::V1_0_0.into());
+
+    let response = engine_state
+        .execute(RequestOptions::new(), request)
+        .wait_drop_metadata()
+        .expect("should receive response");
+
+    assert!(response.has_success(), "{:?}", response);
+    let deploy_results = response.get_success().get_deploy_results();
+    assert_eq!(deploy_results.len(), 1);
+    assert!(
+        deploy_results[0].has_precondition_failure(),
+        "{:?}",
+        deploy_results[0]
+    );
+    let precondition_failure = deploy_results[0].get_precondition_failure();
+    assert_eq!(
+        precondition_failure.get_code(),
+        DeployResult_PreconditionFailure_Code::AUTHORIZATION
+    );
+    assert_eq!(
+        precondition_failure.get_message(),
+        Error::AccountNotFound(address).to_string()
+    );
+}
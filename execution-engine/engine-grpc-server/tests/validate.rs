@@ -0,0 +1,101 @@
+use grpc::RequestOptions;
+
+use casperlabs_engine_grpc_server::engine_server::{
+    ipc::{ValidateRequest, ValidateResponse},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_shared::wasm;
+use engine_storage::{
+    global_state::{in_memory::InMemoryGlobalState, StateProvider},
+    protocol_data::ProtocolData,
+};
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use types::ProtocolVersion;
+
+const MODULE_WAT_WITH_FLOAT: &str = r#"
+(module
+    (type (;0;) (func))
+    (func $call (type 0)
+        f32.const 1.5
+        drop)
+    (memory (;0;) 16)
+    (export "memory" (memory 0))
+    (export "call" (func $call)))
+"#;
+
+const MODULE_WAT_WITHOUT_CALL: &str = r#"
+(module
+    (type (;0;) (func))
+    (func $main (type 0))
+    (memory (;0;) 16)
+    (export "memory" (memory 0))
+    (export "main" (func $main)))
+"#;
+
+fn setup() -> EngineState<InMemoryGlobalState> {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let wasm_costs =
+        WasmCosts::from_version(ProtocolVersion::V1_0_0).expect("should have default costs");
+    let protocol_data = ProtocolData::new(
+        wasm_costs,
+        Default::default(),
+        Default::default(),
+        Default::default(),
+    );
+    global_state
+        .put_protocol_data(ProtocolVersion::V1_0_0, &protocol_data)
+        .expect("should put protocol data");
+    EngineState::new(global_state, EngineConfig::new())
+}
+
+fn validate(wasm_code: Vec<u8>, protocol_version: ProtocolVersion) -> ValidateResponse {
+    let mut request = ValidateRequest::new();
+    request.set_wasm_code(wasm_code);
+    request.set_protocol_version(protocol_version.into());
+
+    setup()
+        .validate(RequestOptions::new(), request)
+        .wait_drop_metadata()
+        .expect("should receive response")
+}
+
+#[test]
+fn should_accept_valid_module() {
+    let response = validate(wasm::do_nothing_bytes(), ProtocolVersion::V1_0_0);
+    assert!(response.has_success(), "{:?}", response);
+}
+
+#[test]
+fn should_report_parse_error_for_malformed_module() {
+    let response = validate(vec![0, 1, 2, 3], ProtocolVersion::V1_0_0);
+    assert!(response.has_parse_error(), "{:?}", response);
+}
+
+#[test]
+fn should_reject_module_with_float_opcode() {
+    let wasm_code = wabt::wat2wasm(MODULE_WAT_WITH_FLOAT).expect("should parse");
+    let response = validate(wasm_code, ProtocolVersion::V1_0_0);
+    assert!(response.has_preprocessing_error(), "{:?}", response);
+}
+
+#[test]
+fn should_reject_module_without_call_export() {
+    let wasm_code = wabt::wat2wasm(MODULE_WAT_WITHOUT_CALL).expect("should parse");
+    let response = validate(wasm_code, ProtocolVersion::V1_0_0);
+    assert!(response.has_preprocessing_error(), "{:?}", response);
+    assert!(
+        response.get_preprocessing_error().contains("call"),
+        "{:?}",
+        response
+    );
+}
+
+#[test]
+fn should_fail_for_unknown_protocol_version() {
+    let response = validate(
+        wasm::do_nothing_bytes(),
+        ProtocolVersion::from_parts(9, 9, 9),
+    );
+    assert!(response.has_failure(), "{:?}", response);
+}
@@ -3,6 +3,8 @@ mod associated_keys;
 
 use std::collections::BTreeSet;
 
+use serde::{Deserialize, Serialize};
+
 use types::{
     account::{
         AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
@@ -16,7 +18,7 @@ use types::{
 pub use action_thresholds::ActionThresholds;
 pub use associated_keys::AssociatedKeys;
 
-#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
 pub struct Account {
     account_hash: AccountHash,
     named_keys: NamedKeys,
@@ -95,16 +97,44 @@ impl Account {
         self.associated_keys.add_key(account_hash, weight)
     }
 
-    /// Checks if removing given key would properly satisfy thresholds.
-    fn can_remove_key(&self, account_hash: AccountHash) -> bool {
-        let total_weight_without = self
-            .associated_keys
-            .total_keys_weight_excluding(account_hash);
+    /// Returns the sum of the weights of all associated keys.
+    ///
+    /// The sum saturates at the maximum [`Weight`] rather than overflowing.
+    pub fn total_weight(&self) -> Weight {
+        self.associated_keys.total_keys_weight()
+    }
+
+    /// Returns the sum of the weights of the given keys.
+    ///
+    /// Each key is counted once, however many times it appears in `keys`, and keys which aren't
+    /// associated with the account add nothing.  The sum saturates at the maximum [`Weight`].
+    pub fn weight_of(&self, keys: &[AccountHash]) -> Weight {
+        let keys: BTreeSet<AccountHash> = keys.iter().copied().collect();
+        self.associated_keys.calculate_keys_weight(&keys)
+    }
+
+    /// Checks whether the given keys together meet the account's threshold for `action_type`.
+    pub fn can_authorize_action(&self, keys: &[AccountHash], action_type: ActionType) -> bool {
+        self.weight_of(keys) >= *self.action_thresholds.get(action_type)
+    }
 
-        // Returns true if the total weight calculated without given public key would be greater or
-        // equal to all of the thresholds.
-        total_weight_without >= *self.action_thresholds().deployment()
-            && total_weight_without >= *self.action_thresholds().key_management()
+    /// Checks whether removing the given key would leave the remaining keys unable to meet one of
+    /// the account's thresholds, at which point the account could no longer deploy or manage its
+    /// keys.
+    ///
+    /// Returns `false` if the key isn't associated with the account.
+    pub fn would_be_deadlocked_after_removal(&self, account_hash: AccountHash) -> bool {
+        self.associated_keys.contains_key(&account_hash)
+            && !self.meets_all_thresholds(
+                self.associated_keys
+                    .total_keys_weight_excluding(account_hash),
+            )
+    }
+
+    /// Checks whether `weight` meets every threshold on the account.
+    fn meets_all_thresholds(&self, weight: Weight) -> bool {
+        weight >= *self.action_thresholds.deployment()
+            && weight >= *self.action_thresholds.key_management()
     }
 
     /// Checks if adding a weight to a sum of all weights excluding the given key would make the
@@ -116,23 +146,18 @@ impl Account {
             .total_keys_weight_excluding(account_hash);
 
         // Safely calculate new weight by adding the updated weight
-        let new_weight = total_weight.value().saturating_add(weight.value());
+        let new_weight = Weight::new(total_weight.value().saturating_add(weight.value()));
 
-        // Returns true if the new weight would be greater or equal to all of
-        // the thresholds.
-        new_weight >= self.action_thresholds().deployment().value()
-            && new_weight >= self.action_thresholds().key_management().value()
+        self.meets_all_thresholds(new_weight)
     }
 
     pub fn remove_associated_key(
         &mut self,
         account_hash: AccountHash,
     ) -> Result<(), RemoveKeyFailure> {
-        if self.associated_keys.contains_key(&account_hash) {
-            // Check if removing this weight would fall below thresholds
-            if !self.can_remove_key(account_hash) {
-                return Err(RemoveKeyFailure::ThresholdViolation);
-            }
+        // Check if removing this weight would fall below thresholds
+        if self.would_be_deadlocked_after_removal(account_hash) {
+            return Err(RemoveKeyFailure::ThresholdViolation);
         }
         self.associated_keys.remove_key(&account_hash)
     }
@@ -651,4 +676,147 @@ mod tests {
             .update_associated_key(key_1, Weight::new(1))
             .expect("should work");
     }
+
+    fn account_with_keys(
+        keys: &[(AccountHash, Weight)],
+        deployment_threshold: Weight,
+        key_management_threshold: Weight,
+    ) -> Account {
+        let (identity_key, identity_weight) = keys[0];
+        let mut associated_keys = AssociatedKeys::new(identity_key, identity_weight);
+        for (key, weight) in &keys[1..] {
+            associated_keys
+                .add_key(*key, *weight)
+                .expect("should add key");
+        }
+        Account::new(
+            identity_key,
+            NamedKeys::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            associated_keys,
+            ActionThresholds::new(deployment_threshold, key_management_threshold)
+                .expect("should create thresholds"),
+        )
+    }
+
+    #[test]
+    fn should_calculate_weights() {
+        let identity_key = AccountHash::new([1u8; 32]);
+        let key_1 = AccountHash::new([2u8; 32]);
+        let key_2 = AccountHash::new([3u8; 32]);
+        let unknown_key = AccountHash::new([4u8; 32]);
+        let account = account_with_keys(
+            &[
+                (identity_key, Weight::new(1)),
+                (key_1, Weight::new(2)),
+                (key_2, Weight::new(3)),
+            ],
+            Weight::new(3),
+            Weight::new(5),
+        );
+
+        assert_eq!(account.total_weight(), Weight::new(6));
+        assert_eq!(account.weight_of(&[]), Weight::new(0));
+        assert_eq!(account.weight_of(&[key_1, key_2]), Weight::new(5));
+        // The identity key is only counted once, however many times it is given.
+        assert_eq!(
+            account.weight_of(&[identity_key, identity_key]),
+            Weight::new(1)
+        );
+        // Keys which aren't associated with the account have no weight.
+        assert_eq!(account.weight_of(&[unknown_key]), Weight::new(0));
+        assert_eq!(account.weight_of(&[unknown_key, key_2]), Weight::new(3));
+    }
+
+    #[test]
+    fn should_check_keys_can_authorize_action() {
+        let identity_key = AccountHash::new([1u8; 32]);
+        let key_1 = AccountHash::new([2u8; 32]);
+        let key_2 = AccountHash::new([3u8; 32]);
+        let account = account_with_keys(
+            &[
+                (identity_key, Weight::new(1)),
+                (key_1, Weight::new(2)),
+                (key_2, Weight::new(3)),
+            ],
+            Weight::new(3),
+            Weight::new(5),
+        );
+
+        // Thresholds exactly met.
+        assert!(account.can_authorize_action(&[key_2], ActionType::Deployment));
+        assert!(account.can_authorize_action(&[identity_key, key_1], ActionType::Deployment));
+        assert!(account.can_authorize_action(&[key_1, key_2], ActionType::KeyManagement));
+
+        // Thresholds missed by one.
+        assert!(!account.can_authorize_action(&[key_1], ActionType::Deployment));
+        assert!(!account.can_authorize_action(&[identity_key, key_2], ActionType::KeyManagement));
+
+        // Repeating the identity key doesn't add to its weight.
+        assert!(!account.can_authorize_action(
+            &[identity_key, identity_key, key_1],
+            ActionType::KeyManagement
+        ));
+        assert!(!account.can_authorize_action(&[], ActionType::Deployment));
+    }
+
+    #[test]
+    fn should_detect_deadlock_after_removal() {
+        let identity_key = AccountHash::new([1u8; 32]);
+        let key_1 = AccountHash::new([2u8; 32]);
+        let key_2 = AccountHash::new([3u8; 32]);
+        let unknown_key = AccountHash::new([4u8; 32]);
+        let mut account = account_with_keys(
+            &[
+                (identity_key, Weight::new(1)),
+                (key_1, Weight::new(2)),
+                (key_2, Weight::new(3)),
+            ],
+            Weight::new(3),
+            Weight::new(5),
+        );
+
+        // The remaining keys exactly meet the key management threshold.
+        assert!(!account.would_be_deadlocked_after_removal(identity_key));
+        // The remaining keys would fall short of the key management threshold.
+        assert!(account.would_be_deadlocked_after_removal(key_1));
+        assert!(account.would_be_deadlocked_after_removal(key_2));
+        assert!(!account.would_be_deadlocked_after_removal(unknown_key));
+
+        // Removal is refused exactly when the account would be deadlocked.
+        assert_eq!(
+            account.remove_associated_key(key_2),
+            Err(RemoveKeyFailure::ThresholdViolation)
+        );
+        account
+            .remove_associated_key(identity_key)
+            .expect("should remove identity key");
+        assert!(account.would_be_deadlocked_after_removal(key_1));
+        assert!(account.would_be_deadlocked_after_removal(key_2));
+    }
+
+    #[test]
+    fn should_saturate_weights_near_max() {
+        let identity_key = AccountHash::new([1u8; 32]);
+        let key_1 = AccountHash::new([2u8; 32]);
+        let key_2 = AccountHash::new([3u8; 32]);
+        let account = account_with_keys(
+            &[
+                (identity_key, Weight::new(1)),
+                (key_1, Weight::new(200)),
+                (key_2, Weight::new(100)),
+            ],
+            Weight::new(1),
+            Weight::new(255),
+        );
+
+        assert_eq!(account.total_weight(), Weight::new(255));
+        assert_eq!(account.weight_of(&[key_1, key_2]), Weight::new(255));
+        assert!(account.can_authorize_action(&[key_1, key_2], ActionType::KeyManagement));
+        assert!(!account.can_authorize_action(&[identity_key, key_1], ActionType::KeyManagement));
+
+        // Without the identity key the remaining weight still saturates at the threshold.
+        assert!(!account.would_be_deadlocked_after_removal(identity_key));
+        assert!(account.would_be_deadlocked_after_removal(key_1));
+    }
 }
@@ -1,10 +1,12 @@
+use serde::{Deserialize, Serialize};
+
 use types::{
     account::{ActionType, SetThresholdFailure, Weight, WEIGHT_SERIALIZED_LENGTH},
     bytesrepr::{self, Error, FromBytes, ToBytes},
 };
 
 /// Thresholds that have to be met when executing an action of a certain type.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ActionThresholds {
     deployment: Weight,
     key_management: Weight,
@@ -66,6 +68,14 @@ impl ActionThresholds {
         &self.key_management
     }
 
+    /// Returns the threshold for the given [ActionType].
+    pub fn get(&self, action_type: ActionType) -> &Weight {
+        match action_type {
+            ActionType::Deployment => self.deployment(),
+            ActionType::KeyManagement => self.key_management(),
+        }
+    }
+
     /// Unified function that takes an action type, and changes appropriate
     /// threshold defined by the [ActionType] variants.
     pub fn set_threshold(
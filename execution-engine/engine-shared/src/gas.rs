@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
 
 use num::Zero;
 
@@ -82,11 +82,91 @@ impl Zero for Gas {
     }
 }
 
+/// The kind of work a [`Gas`] charge pays for.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum CostCategory {
+    /// Charges injected by the Wasm gas instrumentation for executing opcodes.
+    Opcode,
+    /// Flat charges for calling host functions, including any computation they perform.
+    HostFunction,
+    /// Per-byte charges for data passed to host functions, such as keys, names, arguments and
+    /// values to be written to global state.
+    HostFunctionBytes,
+}
+
+impl CostCategory {
+    /// All the categories, in the order they're reported.
+    pub const ALL: [CostCategory; 3] = [
+        CostCategory::Opcode,
+        CostCategory::HostFunction,
+        CostCategory::HostFunctionBytes,
+    ];
+
+    /// Returns the machine-readable name of the category.
+    pub fn name(self) -> &'static str {
+        match self {
+            CostCategory::Opcode => "opcode",
+            CostCategory::HostFunction => "host_function",
+            CostCategory::HostFunctionBytes => "host_function_bytes",
+        }
+    }
+}
+
+impl fmt::Display for CostCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The gas charged during execution, split by [`CostCategory`].
+///
+/// The categories always sum to the total gas charged for the same execution.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct CostBreakdown(BTreeMap<CostCategory, Gas>);
+
+impl CostBreakdown {
+    /// Adds `amount` to the given category.
+    pub fn add(&mut self, category: CostCategory, amount: Gas) {
+        let entry = self.0.entry(category).or_default();
+        *entry = *entry + amount;
+    }
+
+    /// Adds every category of `other` to `self`.
+    pub fn merge(&mut self, other: &CostBreakdown) {
+        for (category, amount) in other.0.iter() {
+            self.add(*category, *amount);
+        }
+    }
+
+    /// Returns the gas charged for the given category.
+    pub fn get(&self, category: CostCategory) -> Gas {
+        self.0.get(&category).copied().unwrap_or_default()
+    }
+
+    /// Returns the sum of all categories.
+    pub fn total(&self) -> Gas {
+        self.0
+            .values()
+            .fold(Gas::default(), |total, amount| total + *amount)
+    }
+
+    /// Returns an iterator over every category along with its charged gas, including categories
+    /// which were never charged.
+    pub fn iter(&self) -> impl Iterator<Item = (CostCategory, Gas)> + '_ {
+        CostCategory::ALL
+            .iter()
+            .map(move |category| (*category, self.get(*category)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use types::U512;
 
-    use crate::{gas::Gas, motes::Motes};
+    use crate::{
+        gas::{CostBreakdown, CostCategory, Gas},
+        motes::Motes,
+    };
 
     #[test]
     fn should_be_able_to_get_instance_of_gas() {
@@ -183,4 +263,38 @@ mod tests {
         let maybe = Gas::from_motes(motes, conv_rate);
         assert!(maybe.is_none(), "should be none due to divide by zero");
     }
+
+    #[test]
+    fn cost_breakdown_should_sum_to_total() {
+        let mut breakdown = CostBreakdown::default();
+        breakdown.add(CostCategory::Opcode, Gas::new(U512::from(10)));
+        breakdown.add(CostCategory::HostFunctionBytes, Gas::new(U512::from(5)));
+        breakdown.add(CostCategory::Opcode, Gas::new(U512::from(1)));
+
+        assert_eq!(
+            breakdown.get(CostCategory::Opcode),
+            Gas::new(U512::from(11))
+        );
+        assert_eq!(breakdown.get(CostCategory::HostFunction), Gas::default());
+        assert_eq!(breakdown.total(), Gas::new(U512::from(16)));
+        assert_eq!(breakdown.iter().count(), CostCategory::ALL.len());
+    }
+
+    #[test]
+    fn cost_breakdown_should_merge() {
+        let mut left = CostBreakdown::default();
+        left.add(CostCategory::HostFunction, Gas::new(U512::from(3)));
+        let mut right = CostBreakdown::default();
+        right.add(CostCategory::HostFunction, Gas::new(U512::from(4)));
+        right.add(CostCategory::Opcode, Gas::new(U512::from(2)));
+
+        left.merge(&right);
+
+        assert_eq!(
+            left.get(CostCategory::HostFunction),
+            Gas::new(U512::from(7))
+        );
+        assert_eq!(left.get(CostCategory::Opcode), Gas::new(U512::from(2)));
+        assert_eq!(left.total(), Gas::new(U512::from(9)));
+    }
 }
@@ -0,0 +1,198 @@
+//! An in-process registry of the metrics generated by the Execution Engine, which can be rendered
+//! in the Prometheus text exposition format.
+//!
+//! Durations recorded via [`log_duration()`](crate::logging::log_duration) are held as histograms,
+//! and values recorded via [`log_metric()`](crate::logging::log_metric) are held as gauges.  Each
+//! series is keyed by the metric name and labelled with its tag.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Mutex, MutexGuard},
+};
+
+use lazy_static::lazy_static;
+
+/// Upper bounds, in seconds, of the buckets of each duration histogram.
+pub const DURATION_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// The label under which a metric's tag is rendered.
+pub const TAG_LABEL: &str = "tag";
+
+lazy_static! {
+    static ref REGISTRY: MetricsRegistry = MetricsRegistry::new();
+}
+
+/// Returns the global metrics registry.
+pub fn registry() -> &'static MetricsRegistry {
+    &REGISTRY
+}
+
+/// Renders the global metrics registry in the Prometheus text exposition format.
+pub fn render() -> String {
+    registry().render()
+}
+
+type SeriesKey = (String, String);
+
+#[derive(Clone, Debug, PartialEq)]
+struct Histogram {
+    bucket_counts: [u64; DURATION_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Histogram {
+            bucket_counts: [0; DURATION_BUCKETS.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (upper_bound, bucket_count) in DURATION_BUCKETS.iter().zip(&mut self.bucket_counts) {
+            if value <= *upper_bound {
+                *bucket_count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+#[derive(Default)]
+struct Metrics {
+    histograms: BTreeMap<SeriesKey, Histogram>,
+    gauges: BTreeMap<SeriesKey, f64>,
+}
+
+/// A thread-safe collection of duration histograms and gauges.
+#[derive(Default)]
+pub struct MetricsRegistry {
+    metrics: Mutex<Metrics>,
+}
+
+impl MetricsRegistry {
+    /// Constructs an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a duration (in seconds) in the histogram for `metric` and `tag`.
+    pub fn observe_duration(&self, metric: &str, tag: &str, duration_in_seconds: f64) {
+        self.lock()
+            .histograms
+            .entry((metric.to_string(), tag.to_string()))
+            .or_insert_with(Histogram::new)
+            .observe(duration_in_seconds);
+    }
+
+    /// Sets the gauge for `metric` and `tag` to `value`.
+    pub fn set_gauge(&self, metric: &str, tag: &str, value: f64) {
+        self.lock()
+            .gauges
+            .insert((metric.to_string(), tag.to_string()), value);
+    }
+
+    /// Renders all recorded metrics in the Prometheus text exposition format.
+    ///
+    /// See https://prometheus.io/docs/instrumenting/exposition_formats/
+    pub fn render(&self) -> String {
+        let metrics = self.lock();
+        let mut output = String::new();
+
+        let mut previous_metric = None;
+        for ((metric, tag), histogram) in &metrics.histograms {
+            if previous_metric != Some(metric) {
+                writeln!(output, "# TYPE {} histogram", metric).unwrap();
+                previous_metric = Some(metric);
+            }
+            for (upper_bound, bucket_count) in DURATION_BUCKETS.iter().zip(&histogram.bucket_counts)
+            {
+                writeln!(
+                    output,
+                    "{}_bucket{{{}=\"{}\",le=\"{}\"}} {}",
+                    metric, TAG_LABEL, tag, upper_bound, bucket_count
+                )
+                .unwrap();
+            }
+            writeln!(
+                output,
+                "{}_bucket{{{}=\"{}\",le=\"+Inf\"}} {}",
+                metric, TAG_LABEL, tag, histogram.count
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "{}_sum{{{}=\"{}\"}} {}",
+                metric, TAG_LABEL, tag, histogram.sum
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "{}_count{{{}=\"{}\"}} {}",
+                metric, TAG_LABEL, tag, histogram.count
+            )
+            .unwrap();
+        }
+
+        let mut previous_metric = None;
+        for ((metric, tag), value) in &metrics.gauges {
+            if previous_metric != Some(metric) {
+                writeln!(output, "# TYPE {} gauge", metric).unwrap();
+                previous_metric = Some(metric);
+            }
+            writeln!(output, "{}{{{}=\"{}\"}} {}", metric, TAG_LABEL, tag, value).unwrap();
+        }
+
+        output
+    }
+
+    fn lock(&self) -> MutexGuard<Metrics> {
+        // A panic while holding the lock can't leave the metrics in an invalid state.
+        self.metrics
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_render_histogram() {
+        let registry = MetricsRegistry::new();
+        registry.observe_duration("exec_duration", "exec_response", 0.02);
+        registry.observe_duration("exec_duration", "exec_response", 3.0);
+
+        let output = registry.render();
+        assert!(output.contains("# TYPE exec_duration histogram\n"));
+        assert!(output.contains("exec_duration_bucket{tag=\"exec_response\",le=\"0.01\"} 0\n"));
+        assert!(output.contains("exec_duration_bucket{tag=\"exec_response\",le=\"0.025\"} 1\n"));
+        assert!(output.contains("exec_duration_bucket{tag=\"exec_response\",le=\"5\"} 2\n"));
+        assert!(output.contains("exec_duration_bucket{tag=\"exec_response\",le=\"+Inf\"} 2\n"));
+        assert!(output.contains("exec_duration_sum{tag=\"exec_response\"} 3.02\n"));
+        assert!(output.contains("exec_duration_count{tag=\"exec_response\"} 2\n"));
+    }
+
+    #[test]
+    fn should_render_gauges_with_one_type_line_per_metric() {
+        let registry = MetricsRegistry::new();
+        registry.set_gauge("global_state_commit_reads", "commit", 1.0);
+        registry.set_gauge("global_state_commit_reads", "commit", 4.0);
+        registry.set_gauge("global_state_commit_reads", "other", 2.0);
+
+        let output = registry.render();
+        assert_eq!(
+            output,
+            "# TYPE global_state_commit_reads gauge\n\
+             global_state_commit_reads{tag=\"commit\"} 4\n\
+             global_state_commit_reads{tag=\"other\"} 2\n"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! A logger implementation which outputs log messages from CasperLabs crates to the terminal.
 
+pub mod metrics;
 mod settings;
 mod structured_message;
 mod terminal_logger;
@@ -13,7 +14,7 @@ use log::{self, Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
 
 pub use self::terminal_logger::TerminalLogger;
 use crate::newtypes::CorrelationId;
-pub use settings::{Settings, Style};
+pub use settings::{LevelSpecError, Settings, Style};
 
 #[doc(hidden)]
 pub const PAYLOAD_KEY: &str = "payload=";
@@ -25,8 +26,9 @@ pub(crate) const DEFAULT_MESSAGE_KEY: &str = "message";
 
 /// Initializes the global logger using the given settings.
 ///
-/// The logger will write all log messages from crates prefixed with "casperlabs_" to stdout, and
-/// can also log internal metrics generated by the Execution Engine.
+/// The logger will write all log messages from crates prefixed with "casperlabs_" to stdout, at the
+/// level set for their target, and can also log internal metrics generated by the Execution
+/// Engine.
 ///
 /// Returns an error if the global logger has already been set in this process.
 pub fn initialize(settings: Settings) -> Result<(), SetLoggerError> {
@@ -85,7 +87,7 @@ pub fn log_details(
     logger.log(&record);
 }
 
-/// Logs the duration of a specific operation.
+/// Logs the duration of a specific operation, and records it in the [`metrics`] registry.
 ///
 /// # Arguments
 ///
@@ -97,7 +99,9 @@ pub fn log_details(
 pub fn log_duration(correlation_id: CorrelationId, metric: &str, tag: &str, duration: Duration) {
     let duration_in_seconds: f64 = duration.as_secs_f64();
 
-    log_metric(
+    metrics::registry().observe_duration(metric, tag, duration_in_seconds);
+
+    log_metric_details(
         correlation_id,
         metric,
         tag,
@@ -106,7 +110,8 @@ pub fn log_duration(correlation_id: CorrelationId, metric: &str, tag: &str, dura
     )
 }
 
-/// Logs the details of the specified metric.
+/// Logs the details of the specified metric, and records its value as a gauge in the [`metrics`]
+/// registry.
 ///
 /// # Arguments
 ///
@@ -122,6 +127,19 @@ pub fn log_metric(
     tag: &str,
     metric_key: &str,
     metric_value: f64,
+) {
+    metrics::registry().set_gauge(metric, tag, metric_value);
+
+    log_metric_details(correlation_id, metric, tag, metric_key, metric_value)
+}
+
+#[inline]
+fn log_metric_details(
+    correlation_id: CorrelationId,
+    metric: &str,
+    tag: &str,
+    metric_key: &str,
+    metric_value: f64,
 ) {
     let logger = log::logger();
 
@@ -1,9 +1,17 @@
+use std::{
+    cmp,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
 use log::LevelFilter;
 
 /// Settings used to initialize the global logger.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct Settings {
     max_level: LevelFilter,
+    target_levels: Vec<(String, LevelFilter)>,
     enable_metrics: bool,
     style: Style,
 }
@@ -21,11 +29,51 @@ impl Settings {
     pub fn new(max_level: LevelFilter) -> Self {
         Settings {
             max_level,
+            target_levels: Vec::new(),
             enable_metrics: false,
             style: Style::Structured,
         }
     }
 
+    /// Constructs new `Settings` from a level specification of the form
+    /// `[LEVEL][,TARGET=LEVEL]...`, e.g. `warn,casperlabs_engine_core::runtime=error`.
+    ///
+    /// The level without a target sets the default verbosity level, and defaults to `Info` if
+    /// omitted.  Each `TARGET=LEVEL` pair is applied as per
+    /// [`with_target_level()`](Settings::with_target_level).
+    pub fn from_level_spec(spec: &str) -> Result<Self, LevelSpecError> {
+        let parse_level = |level: &str| {
+            LevelFilter::from_str(level.trim()).map_err(|_| LevelSpecError(spec.to_string()))
+        };
+
+        let mut settings = Settings::new(LevelFilter::Info);
+        for directive in spec.split(',').map(str::trim) {
+            if directive.is_empty() {
+                continue;
+            }
+            let mut parts = directive.splitn(2, '=');
+            match (parts.next(), parts.next()) {
+                (Some(level), None) => settings.max_level = parse_level(level)?,
+                (Some(target), Some(level)) if !target.trim().is_empty() => {
+                    settings = settings.with_target_level(target.trim(), parse_level(level)?)
+                }
+                _ => return Err(LevelSpecError(spec.to_string())),
+            }
+        }
+        Ok(settings)
+    }
+
+    /// Sets the verbosity level for log messages whose target is `target` or one of its
+    /// submodules, e.g. `casperlabs_engine_core::runtime`, overriding the default level.
+    ///
+    /// Where several targets match a message, the longest one applies.
+    pub fn with_target_level(mut self, target: &str, level: LevelFilter) -> Self {
+        self.target_levels
+            .retain(|(existing_target, _)| existing_target != target);
+        self.target_levels.push((target.to_string(), level));
+        self
+    }
+
     /// If `true`, log messages created via [`log_metric()`](crate::logging::log_metric) and
     /// [`log_duration()`](crate::logging::log_duration) are logged, regardless of the log-level.
     pub fn with_metrics_enabled(mut self, value: bool) -> Self {
@@ -39,8 +87,27 @@ impl Settings {
         self
     }
 
+    /// Returns the most verbose level at which any message could be logged.
     pub(crate) fn max_level(&self) -> LevelFilter {
-        self.max_level
+        self.target_levels
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.max_level, cmp::max)
+    }
+
+    /// Returns the verbosity level which applies to messages with the given target.
+    pub(crate) fn level_for(&self, target: &str) -> LevelFilter {
+        self.target_levels
+            .iter()
+            .filter(|(prefix, _)| {
+                target.starts_with(prefix.as_str()) && {
+                    let rest = &target[prefix.len()..];
+                    rest.is_empty() || rest.starts_with("::")
+                }
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.max_level)
     }
 
     pub(crate) fn enable_metrics(&self) -> bool {
@@ -61,3 +128,78 @@ pub enum Style {
     /// Human-readable log-messages.
     HumanReadable,
 }
+
+/// An error returned when a log level specification can't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LevelSpecError(String);
+
+impl Display for LevelSpecError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid log level specification '{}': expected [LEVEL][,TARGET=LEVEL]...",
+            self.0
+        )
+    }
+}
+
+impl Error for LevelSpecError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_level_spec() {
+        let settings = Settings::from_level_spec(
+            "warn, casperlabs_engine_core::runtime=error,casperlabs_engine_storage=off",
+        )
+        .expect("should parse");
+        assert_eq!(
+            settings.level_for("casperlabs_engine_grpc_server"),
+            LevelFilter::Warn
+        );
+        assert_eq!(
+            settings.level_for("casperlabs_engine_core::runtime"),
+            LevelFilter::Error
+        );
+        assert_eq!(
+            settings.level_for("casperlabs_engine_storage::trie_store"),
+            LevelFilter::Off
+        );
+        assert_eq!(settings.max_level(), LevelFilter::Warn);
+
+        let settings = Settings::from_level_spec("").expect("should parse");
+        assert_eq!(settings.level_for("casperlabs_"), LevelFilter::Info);
+
+        assert!(Settings::from_level_spec("loud").is_err());
+        assert!(Settings::from_level_spec("=warn").is_err());
+        assert!(Settings::from_level_spec("casperlabs_engine_core=loud").is_err());
+    }
+
+    #[test]
+    fn should_apply_longest_matching_target() {
+        let settings = Settings::new(LevelFilter::Info)
+            .with_target_level("casperlabs_engine_core", LevelFilter::Warn)
+            .with_target_level("casperlabs_engine_core::runtime", LevelFilter::Trace);
+
+        assert_eq!(settings.max_level(), LevelFilter::Trace);
+        assert_eq!(
+            settings.level_for("casperlabs_engine_core"),
+            LevelFilter::Warn
+        );
+        assert_eq!(
+            settings.level_for("casperlabs_engine_core::engine_state"),
+            LevelFilter::Warn
+        );
+        assert_eq!(
+            settings.level_for("casperlabs_engine_core::runtime::externals"),
+            LevelFilter::Trace
+        );
+        // Only whole path segments match.
+        assert_eq!(
+            settings.level_for("casperlabs_engine_core_extras"),
+            LevelFilter::Info
+        );
+    }
+}
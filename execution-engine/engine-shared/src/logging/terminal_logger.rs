@@ -1,6 +1,6 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 
-use log::{Level, LevelFilter, Log, Metadata, Record};
+use log::{Level, Log, Metadata, Record};
 
 use crate::logging::{
     structured_message::{MessageId, MessageProperties, StructuredMessage, TimestampRfc3999},
@@ -10,18 +10,14 @@ use crate::logging::{
 #[doc(hidden)]
 /// Logs messages from targets with prefix "casperlabs_" or "METRIC" to stdout.
 pub struct TerminalLogger {
-    max_level: LevelFilter,
-    metrics_enabled: bool,
-    style: Style,
+    settings: Settings,
     next_message_id: AtomicUsize,
 }
 
 impl TerminalLogger {
     pub fn new(settings: &Settings) -> Self {
         TerminalLogger {
-            max_level: settings.max_level(),
-            metrics_enabled: settings.enable_metrics(),
-            style: settings.style(),
+            settings: settings.clone(),
             next_message_id: AtomicUsize::new(0),
         }
     }
@@ -34,7 +30,7 @@ impl TerminalLogger {
         let mut properties = MessageProperties::default();
         let _ = record.key_values().visit(&mut properties);
 
-        let log_line = match self.style {
+        let log_line = match self.settings.style() {
             Style::Structured => {
                 if record.key_values().count() == 0 {
                     properties.insert(
@@ -79,11 +75,12 @@ impl TerminalLogger {
 impl Log for TerminalLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
         // If the target starts "casperlabs_" it's either come from a log macro in one of our
-        // crates, or via `logging::log_details`.  In this case, check the level.
+        // crates, or via `logging::log_details`.  In this case, check the level which applies to
+        // the target.
         (metadata.target().starts_with(CASPERLABS_METADATA_TARGET)
-            && metadata.level() <= self.max_level)
+            && metadata.level() <= self.settings.level_for(metadata.target()))
             // Otherwise, check if the target is "METRIC" and if we have metric logging enabled.
-            || (self.metrics_enabled && metadata.target() == METRIC_METADATA_TARGET)
+            || (self.settings.enable_metrics() && metadata.target() == METRIC_METADATA_TARGET)
     }
 
     fn log(&self, record: &Record) {
@@ -2,7 +2,7 @@
 mod macros;
 
 use core::array::TryFromSliceError;
-use std::{convert::TryFrom, fmt};
+use std::{convert::TryFrom, fmt, str::FromStr};
 
 use blake2::{
     digest::{Input, VariableOutput},
@@ -130,10 +130,44 @@ impl fmt::Display for CorrelationId {
     }
 }
 
+/// An error returned when parsing a [`CorrelationId`] from a string.
+#[derive(Debug, PartialEq)]
+pub enum ParseCorrelationIdError {
+    /// The string is not a valid UUID.
+    InvalidUuid(String),
+    /// The string is the nil UUID, which is reserved for an empty `CorrelationId`.
+    Nil,
+}
+
+impl fmt::Display for ParseCorrelationIdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseCorrelationIdError::InvalidUuid(error) => {
+                write!(f, "invalid correlation ID: {}", error)
+            }
+            ParseCorrelationIdError::Nil => write!(f, "correlation ID must not be nil"),
+        }
+    }
+}
+
+/// Parses the format produced by `Display`, i.e. a hyphenated UUID.
+impl FromStr for CorrelationId {
+    type Err = ParseCorrelationIdError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let uuid = Uuid::parse_str(input)
+            .map_err(|error| ParseCorrelationIdError::InvalidUuid(error.to_string()))?;
+        if uuid.is_nil() {
+            return Err(ParseCorrelationIdError::Nil);
+        }
+        Ok(CorrelationId(uuid))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        newtypes::{Blake2bHash, CorrelationId},
+        newtypes::{Blake2bHash, CorrelationId, ParseCorrelationIdError},
         utils,
     };
     use std::hash::{Hash, Hasher};
@@ -202,6 +236,35 @@ mod tests {
         )
     }
 
+    #[test]
+    fn should_roundtrip_through_string() {
+        let correlation_id = CorrelationId::new();
+
+        let parsed: CorrelationId = correlation_id.to_string().parse().unwrap();
+
+        assert_eq!(parsed, correlation_id);
+    }
+
+    #[test]
+    fn should_fail_to_parse_invalid_string() {
+        let result = "not-a-correlation-id".parse::<CorrelationId>();
+
+        match result {
+            Err(ParseCorrelationIdError::InvalidUuid(_)) => (),
+            _ => panic!("should fail to parse: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_parse_nil_id() {
+        let nil = CorrelationId::default().to_string();
+
+        assert_eq!(
+            nil.parse::<CorrelationId>(),
+            Err(ParseCorrelationIdError::Nil)
+        );
+    }
+
     #[test]
     fn should_create_unique_id_on_new() {
         let correlation_id_lhs = CorrelationId::new();
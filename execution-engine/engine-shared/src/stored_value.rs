@@ -1,5 +1,7 @@
 use std::convert::TryFrom;
 
+use serde::{Deserialize, Serialize};
+
 use types::{
     bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
     contracts::ContractPackage,
@@ -17,7 +19,7 @@ enum Tag {
     ContractPackage = 4,
 }
 
-#[derive(Eq, PartialEq, Clone, Debug)]
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub enum StoredValue {
     CLValue(CLValue),
     Account(Account),
@@ -232,5 +234,12 @@ mod tests {
         fn serialization_roundtrip(v in gens::stored_value_arb()) {
             bytesrepr::test_serialization_roundtrip(&v);
         }
+
+        #[test]
+        fn json_roundtrip(v in gens::stored_value_arb()) {
+            let json = serde_json::to_string(&v).expect("should serialize");
+            let parsed: StoredValue = serde_json::from_str(&json).expect("should deserialize");
+            assert_eq!(v, parsed);
+        }
     }
 }
@@ -1,7 +1,9 @@
 //! Some functions to use in tests.
 
-use engine_wasm_prep::wasm_costs::WasmCosts;
-use types::{account::AccountHash, contracts::NamedKeys, AccessRights, Key, URef};
+use engine_wasm_prep::wasm_costs::{
+    HostFunctionCosts, WasmCosts, DEFAULT_MAX_MEMORY_PAGES, DEFAULT_MAX_TABLE_SIZE,
+};
+use types::{account::AccountHash, contracts::NamedKeys, AccessRights, Key, ProtocolVersion, URef};
 
 use crate::{account::Account, stored_value::StoredValue};
 
@@ -13,18 +15,7 @@ pub fn mocked_account(account_hash: AccountHash) -> Vec<(Key, StoredValue)> {
 }
 
 pub fn wasm_costs_mock() -> WasmCosts {
-    WasmCosts {
-        regular: 1,
-        div: 16,
-        mul: 4,
-        mem: 2,
-        initial_mem: 4096,
-        grow_mem: 8192,
-        memcpy: 1,
-        max_stack_height: 64 * 1024,
-        opcodes_mul: 3,
-        opcodes_div: 8,
-    }
+    WasmCosts::from_version(ProtocolVersion::V1_0_0).expect("should have default costs")
 }
 
 pub fn wasm_costs_free() -> WasmCosts {
@@ -39,5 +30,8 @@ pub fn wasm_costs_free() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 1,
         opcodes_div: 1,
+        max_memory_pages: DEFAULT_MAX_MEMORY_PAGES,
+        max_table_size: DEFAULT_MAX_TABLE_SIZE,
+        host_function_costs: HostFunctionCosts::default(),
     }
 }
@@ -58,7 +58,12 @@ pub enum Transform {
     AddUInt128(U128),
     AddUInt256(U256),
     AddUInt512(U512),
+    /// Subtracts the given amount from a `U512` value.  Applying it fails if the result would be
+    /// negative.
+    SubUInt512(U512),
     AddKeys(NamedKeys),
+    /// Removes the value stored under the key from global state.
+    Delete,
     Failure(Error),
 }
 
@@ -135,6 +140,26 @@ where
     Ok(StoredValue::CLValue(CLValue::from_t(result)?))
 }
 
+/// Attempts to subtract `to_sub` from `stored_value`, which must be a `U512`.  Unlike addition,
+/// this does not wrap: a result below zero is an error.
+fn checked_subtraction(stored_value: StoredValue, to_sub: U512) -> Result<StoredValue, Error> {
+    let cl_value = CLValue::try_from(stored_value)?;
+    if *cl_value.cl_type() != CLType::U512 {
+        let expected = format!("{:?}", CLType::U512);
+        let found = format!("{:?}", cl_value.cl_type());
+        return Err(TypeMismatch::new(expected, found).into());
+    }
+    let current: U512 = cl_value.into_t()?;
+    match current.checked_sub(to_sub) {
+        Some(result) => Ok(StoredValue::CLValue(CLValue::from_t(result)?)),
+        None => {
+            let expected = format!("U512 of at least {}", to_sub);
+            let found = format!("U512 of {}", current);
+            Err(TypeMismatch::new(expected, found).into())
+        }
+    }
+}
+
 impl Transform {
     pub fn apply(self, stored_value: StoredValue) -> Result<StoredValue, Error> {
         match self {
@@ -145,6 +170,7 @@ impl Transform {
             Transform::AddUInt128(to_add) => wrapping_addition(stored_value, to_add),
             Transform::AddUInt256(to_add) => wrapping_addition(stored_value, to_add),
             Transform::AddUInt512(to_add) => wrapping_addition(stored_value, to_add),
+            Transform::SubUInt512(to_sub) => checked_subtraction(stored_value, to_sub),
             Transform::AddKeys(mut keys) => match stored_value {
                 StoredValue::Contract(mut contract) => {
                     contract.named_keys_append(&mut keys);
@@ -170,6 +196,13 @@ impl Transform {
                     Err(TypeMismatch::new(expected, found).into())
                 }
             },
+            Transform::Delete => {
+                // A deletion leaves no value behind; it is handled by `commit` before any call to
+                // `apply` is made.
+                let expected = "a transform which yields a value".to_string();
+                let found = "Delete".to_string();
+                Err(TypeMismatch::new(expected, found).into())
+            }
             Transform::Failure(error) => Err(error),
         }
     }
@@ -207,6 +240,18 @@ where
     }
 }
 
+/// Combines an addition of `to_add` and a subtraction of `to_sub` into a single `U512` delta.
+///
+/// The order of the two is not significant: a debit is only ever recorded after the balance it
+/// applies to has been checked, so the intermediate value is assumed not to underflow.
+fn u512_delta(to_add: U512, to_sub: U512) -> Transform {
+    if to_add >= to_sub {
+        Transform::AddUInt512(to_add - to_sub)
+    } else {
+        Transform::SubUInt512(to_sub - to_add)
+    }
+}
+
 impl Add for Transform {
     type Output = Transform;
 
@@ -217,6 +262,10 @@ impl Add for Transform {
             (a @ Transform::Failure(_), _) => a,
             (_, b @ Transform::Failure(_)) => b,
             (_, b @ Transform::Write(_)) => b,
+            (_, Transform::Delete) => Transform::Delete,
+            (Transform::Delete, other) => Transform::Failure(
+                TypeMismatch::new("Delete".to_owned(), format!("{:?}", other)).into(),
+            ),
             (Transform::Write(v), b) => {
                 // second transform changes value being written
                 match b.apply(v) {
@@ -244,6 +293,20 @@ impl Add for Transform {
                     TypeMismatch::new("AddUInt64".to_owned(), format!("{:?}", other)).into(),
                 ),
             },
+            (Transform::AddUInt512(i), Transform::SubUInt512(j)) => u512_delta(i, j),
+            (Transform::SubUInt512(i), b) => match b {
+                Transform::AddUInt512(j) => u512_delta(j, i),
+                Transform::SubUInt512(j) => match i.checked_add(j) {
+                    Some(sum) => Transform::SubUInt512(sum),
+                    None => Transform::Failure(
+                        TypeMismatch::new("SubUInt512".to_owned(), "overflowing U512".to_owned())
+                            .into(),
+                    ),
+                },
+                other => Transform::Failure(
+                    TypeMismatch::new("SubUInt512".to_owned(), format!("{:?}", other)).into(),
+                ),
+            },
             (Transform::AddUInt128(i), b) => wrapped_transform_addition(i, b, "U128"),
             (Transform::AddUInt256(i), b) => wrapped_transform_addition(i, b, "U256"),
             (Transform::AddUInt512(i), b) => wrapped_transform_addition(i, b, "U512"),
@@ -287,6 +350,7 @@ pub mod gens {
     pub fn transform_arb() -> impl Strategy<Value = Transform> {
         prop_oneof![
             Just(Transform::Identity),
+            Just(Transform::Delete),
             stored_value_arb().prop_map(Transform::Write),
             any::<i32>().prop_map(Transform::AddInt32),
             any::<u64>().prop_map(Transform::AddUInt64),
@@ -301,6 +365,7 @@ pub mod gens {
                 buf.copy_from_slice(&u);
                 Transform::AddUInt512(buf.into())
             }),
+            any::<u64>().prop_map(|u| Transform::SubUInt512(u.into())),
         ]
     }
 }
@@ -308,6 +373,7 @@ pub mod gens {
 #[cfg(test)]
 mod tests {
     use num::{Bounded, Num};
+    use proptest::prelude::*;
 
     use types::{account::AccountHash, AccessRights, ContractWasm, Key, URef, U128, U256, U512};
 
@@ -430,6 +496,29 @@ mod tests {
         uint_overflow_test::<U512>();
     }
 
+    #[test]
+    fn delete_should_combine_with_other_transforms() {
+        let value = StoredValue::CLValue(CLValue::from_t(ONE_I32).expect("should create CLValue"));
+        let write = Transform::Write(value.clone());
+
+        assert_eq!(write.clone() + Transform::Delete, Transform::Delete);
+        assert_eq!(
+            Transform::AddInt32(ONE_I32) + Transform::Delete,
+            Transform::Delete
+        );
+        assert_eq!(Transform::Delete + write.clone(), write);
+        assert_eq!(Transform::Delete + Transform::Identity, Transform::Delete);
+
+        match Transform::Delete + Transform::AddInt32(ONE_I32) {
+            Transform::Failure(Error::TypeMismatch(_)) => (),
+            other => panic!("expected a TypeMismatch failure, got {:?}", other),
+        }
+        match Transform::Delete.apply(value) {
+            Err(Error::TypeMismatch(_)) => (),
+            other => panic!("expected a TypeMismatch error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn addition_between_mismatched_types_should_fail() {
         fn assert_yields_type_mismatch_error(stored_value: StoredValue) {
@@ -746,4 +835,111 @@ mod tests {
         assert_eq!(ZERO_U512, add(MAX_U512, ONE_U512));
         assert_eq!(MAX_U512 - 1, add(MAX_U512, MAX_U512));
     }
+
+    #[test]
+    fn u512_subtraction_should_fail_on_underflow() {
+        let one = StoredValue::CLValue(CLValue::from_t(ONE_U512).unwrap());
+        let zero = StoredValue::CLValue(CLValue::from_t(ZERO_U512).unwrap());
+
+        assert_eq!(Transform::SubUInt512(ONE_U512).apply(one.clone()), Ok(zero));
+        match Transform::SubUInt512(ONE_U512 + 1).apply(one) {
+            Err(Error::TypeMismatch(_)) => (),
+            other => panic!("expected a TypeMismatch error, got {:?}", other),
+        }
+
+        let cl_u64 = StoredValue::CLValue(CLValue::from_t(ONE_U64).unwrap());
+        match Transform::SubUInt512(ONE_U512).apply(cl_u64) {
+            Err(Error::TypeMismatch(_)) => (),
+            other => panic!("expected a TypeMismatch error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn u512_subtraction_should_not_combine_with_other_types() {
+        match Transform::SubUInt512(ONE_U512) + Transform::AddUInt64(ONE_U64) {
+            Transform::Failure(Error::TypeMismatch(_)) => (),
+            other => panic!("expected a TypeMismatch failure, got {:?}", other),
+        }
+        match Transform::SubUInt512(MAX_U512) + Transform::SubUInt512(ONE_U512) {
+            Transform::Failure(Error::TypeMismatch(_)) => (),
+            other => panic!("expected a TypeMismatch failure, got {:?}", other),
+        }
+    }
+
+    fn u512_value(value: u64) -> StoredValue {
+        StoredValue::CLValue(CLValue::from_t(U512::from(value)).unwrap())
+    }
+
+    proptest! {
+        #[test]
+        fn sub_after_add_should_compose(
+            balance in any::<u64>(),
+            a in any::<u64>(),
+            b in any::<u64>()
+        ) {
+            let add = Transform::AddUInt512(a.into());
+            let sub = Transform::SubUInt512(b.into());
+            let sequential = add
+                .clone()
+                .apply(u512_value(balance))
+                .and_then(|value| sub.clone().apply(value));
+            let combined = (add + sub).apply(u512_value(balance));
+            prop_assert_eq!(combined.is_ok(), sequential.is_ok());
+            if let Ok(value) = sequential {
+                prop_assert_eq!(combined, Ok(value));
+            }
+        }
+
+        #[test]
+        fn add_after_sub_should_compose(
+            balance in any::<u64>(),
+            a in any::<u64>(),
+            b in any::<u64>()
+        ) {
+            prop_assume!(b <= balance);
+            let sub = Transform::SubUInt512(b.into());
+            let add = Transform::AddUInt512(a.into());
+            let sequential = sub
+                .clone()
+                .apply(u512_value(balance))
+                .and_then(|value| add.clone().apply(value));
+            prop_assert_eq!((sub + add).apply(u512_value(balance)), sequential);
+        }
+
+        #[test]
+        fn sub_after_sub_should_compose(
+            balance in any::<u64>(),
+            a in any::<u64>(),
+            b in any::<u64>()
+        ) {
+            let sub_a = Transform::SubUInt512(a.into());
+            let sub_b = Transform::SubUInt512(b.into());
+            let sequential = sub_a
+                .clone()
+                .apply(u512_value(balance))
+                .and_then(|value| sub_b.clone().apply(value));
+            let combined = (sub_a + sub_b).apply(u512_value(balance));
+            prop_assert_eq!(combined.is_ok(), sequential.is_ok());
+            if let Ok(value) = sequential {
+                prop_assert_eq!(combined, Ok(value));
+            }
+        }
+
+        #[test]
+        fn u128_addition_should_wrap(a in any::<u128>(), b in any::<u128>()) {
+            let stored_value = StoredValue::CLValue(CLValue::from_t(U128::from(a)).unwrap());
+            let expected = StoredValue::CLValue(
+                CLValue::from_t(U128::from(a.wrapping_add(b))).unwrap()
+            );
+            prop_assert_eq!(Transform::AddUInt128(b.into()).apply(stored_value), Ok(expected));
+        }
+
+        #[test]
+        fn u256_addition_should_wrap(a in types::gens::u256_arb(), b in types::gens::u256_arb()) {
+            let stored_value = StoredValue::CLValue(CLValue::from_t(a).unwrap());
+            let (sum, _) = a.overflowing_add(b);
+            let expected = StoredValue::CLValue(CLValue::from_t(sum).unwrap());
+            prop_assert_eq!(Transform::AddUInt256(b).apply(stored_value), Ok(expected));
+        }
+    }
 }
@@ -150,23 +150,31 @@ fn expected_level(level: Level) -> &'static str {
 }
 
 pub fn assert_log_via_macro_is_output(level: Level) {
-    log_via_macro(level, true);
+    log_via_macro(LOG_MSG_TARGET, level, true);
 }
 
 pub fn assert_log_via_macro_is_not_output(level: Level) {
-    log_via_macro(level, false);
+    log_via_macro(LOG_MSG_TARGET, level, false);
 }
 
-fn log_via_macro(level: Level, expect_output: bool) {
+pub fn assert_log_via_macro_with_target_is_output(target: &str, level: Level) {
+    log_via_macro(target, level, true);
+}
+
+pub fn assert_log_via_macro_with_target_is_not_output(target: &str, level: Level) {
+    log_via_macro(target, level, false);
+}
+
+fn log_via_macro(target: &str, level: Level, expect_output: bool) {
     let correlation_id = CorrelationId::new();
 
     let msg = format!("{}-level message with ID {}", level, correlation_id);
     match level {
-        Level::Trace => trace!(target: LOG_MSG_TARGET, "{}", msg),
-        Level::Debug => debug!(target: LOG_MSG_TARGET, "{}", msg),
-        Level::Info => info!(target: LOG_MSG_TARGET, "{}", msg),
-        Level::Warn => warn!(target: LOG_MSG_TARGET, "{}", msg),
-        Level::Error => error!(target: LOG_MSG_TARGET, "{}", msg),
+        Level::Trace => trace!(target: target, "{}", msg),
+        Level::Debug => debug!(target: target, "{}", msg),
+        Level::Info => info!(target: target, "{}", msg),
+        Level::Warn => warn!(target: target, "{}", msg),
+        Level::Error => error!(target: target, "{}", msg),
     }
 
     let msgs = BUFFER.extract(&correlation_id.to_string());
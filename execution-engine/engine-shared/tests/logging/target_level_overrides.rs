@@ -0,0 +1,70 @@
+mod common;
+
+use lazy_static::lazy_static;
+use log::{Level, LevelFilter};
+
+use casperlabs_engine_shared::logging::Settings;
+
+const QUIET_TARGET: &str = "casperlabs_logging::quiet";
+const QUIET_SUBMODULE_TARGET: &str = "casperlabs_logging::quiet::submodule";
+const VERBOSE_TARGET: &str = "casperlabs_logging::verbose";
+const OTHER_TARGET: &str = "casperlabs_logging::quieter";
+
+lazy_static! {
+    static ref WARN_WITH_OVERRIDES: Settings = Settings::from_level_spec(&format!(
+        "warn,{}=error,{}=debug",
+        QUIET_TARGET, VERBOSE_TARGET
+    ))
+    .expect("should parse level spec");
+}
+
+#[test]
+fn should_log_via_macros_at_default_level() {
+    common::set_up_logging(WARN_WITH_OVERRIDES.clone());
+
+    common::assert_log_via_macro_is_not_output(Level::Info);
+    common::assert_log_via_macro_is_output(Level::Warn);
+
+    // Only whole path segments match a target, so this uses the default level.
+    common::assert_log_via_macro_with_target_is_not_output(OTHER_TARGET, Level::Info);
+    common::assert_log_via_macro_with_target_is_output(OTHER_TARGET, Level::Warn);
+}
+
+#[test]
+fn should_log_via_macros_at_lower_target_level() {
+    common::set_up_logging(WARN_WITH_OVERRIDES.clone());
+
+    for target in &[QUIET_TARGET, QUIET_SUBMODULE_TARGET] {
+        common::assert_log_via_macro_with_target_is_not_output(target, Level::Warn);
+        common::assert_log_via_macro_with_target_is_output(target, Level::Error);
+    }
+}
+
+#[test]
+fn should_log_via_macros_at_higher_target_level() {
+    common::set_up_logging(WARN_WITH_OVERRIDES.clone());
+
+    common::assert_log_via_macro_with_target_is_not_output(VERBOSE_TARGET, Level::Trace);
+    common::assert_log_via_macro_with_target_is_output(VERBOSE_TARGET, Level::Debug);
+    common::assert_log_via_macro_with_target_is_output(VERBOSE_TARGET, Level::Info);
+}
+
+#[test]
+fn should_log_via_log_details_at_default_level() {
+    common::set_up_logging(WARN_WITH_OVERRIDES.clone());
+
+    common::assert_log_via_log_details_is_not_output(Level::Info);
+    common::assert_log_via_log_details_is_output(Level::Warn);
+}
+
+#[test]
+fn should_not_log_metrics() {
+    common::set_up_logging(WARN_WITH_OVERRIDES.clone());
+    common::assert_log_via_log_metric_is_not_output();
+}
+
+#[test]
+fn should_enable_most_verbose_level() {
+    common::set_up_logging(WARN_WITH_OVERRIDES.clone());
+    assert_eq!(log::max_level(), LevelFilter::Debug);
+}
@@ -12,7 +12,7 @@ lazy_static! {
 
 #[test]
 fn should_log_via_macros() {
-    common::set_up_logging(*TRACE_WITHOUT_METRICS);
+    common::set_up_logging(TRACE_WITHOUT_METRICS.clone());
 
     common::assert_log_via_macro_is_output(Level::Trace);
     common::assert_log_via_macro_is_output(Level::Debug);
@@ -23,7 +23,7 @@ fn should_log_via_macros() {
 
 #[test]
 fn should_log_via_log_details() {
-    common::set_up_logging(*TRACE_WITHOUT_METRICS);
+    common::set_up_logging(TRACE_WITHOUT_METRICS.clone());
 
     common::assert_log_via_log_details_is_output(Level::Trace);
     common::assert_log_via_log_details_is_output(Level::Debug);
@@ -34,6 +34,6 @@ fn should_log_via_log_details() {
 
 #[test]
 fn should_log_via_log_metric() {
-    common::set_up_logging(*TRACE_WITHOUT_METRICS);
+    common::set_up_logging(TRACE_WITHOUT_METRICS.clone());
     common::assert_log_via_log_metric_is_not_output();
 }
@@ -1,8 +1,9 @@
-use std::sync;
+use std::{io, sync};
 
 use failure::Fail;
 use lmdb as lmdb_external;
 
+use engine_shared::newtypes::Blake2bHash;
 use types::bytesrepr;
 
 use super::in_memory;
@@ -17,6 +18,18 @@ pub enum Error {
 
     #[fail(display = "Another thread panicked while holding a lock")]
     Poison,
+
+    #[fail(display = "I/O error: {:?}", _0)]
+    Io(io::ErrorKind),
+
+    #[fail(display = "Trie snapshot contains unexpected trie {}", _0)]
+    UnexpectedTrie(Blake2bHash),
+
+    #[fail(display = "Trie snapshot is missing trie {}", _0)]
+    MissingTrie(Blake2bHash),
+
+    #[fail(display = "Root {} not found", _0)]
+    RootNotFound(Blake2bHash),
 }
 
 impl wasmi::HostError for Error {}
@@ -33,6 +46,12 @@ impl From<bytesrepr::Error> for Error {
     }
 }
 
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error.kind())
+    }
+}
+
 impl<T> From<sync::PoisonError<T>> for Error {
     fn from(_error: sync::PoisonError<T>) -> Self {
         Error::Poison
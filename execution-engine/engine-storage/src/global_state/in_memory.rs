@@ -1,4 +1,8 @@
-use std::{ops::Deref, sync::Arc};
+use std::{
+    io::{Read, Write},
+    ops::Deref,
+    sync::Arc,
+};
 
 use engine_shared::{
     additive_map::AdditiveMap,
@@ -13,15 +17,19 @@ use crate::{
     global_state::{commit, CommitResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
     protocol_data_store::in_memory::InMemoryProtocolDataStore,
+    root_ancestry_store::{self, in_memory::InMemoryRootAncestryStore},
     store::Store,
     transaction_source::{
         in_memory::{InMemoryEnvironment, InMemoryReadTransaction},
         Transaction, TransactionSource,
     },
-    trie::{operations::create_hashed_empty_trie, Trie},
+    trie::{operations::create_hashed_empty_trie, Trie, TrieMerkleProof},
     trie_store::{
         in_memory::InMemoryTrieStore,
-        operations::{self, read, ReadResult, WriteResult},
+        operations::{
+            self, keys_with_prefix, prune, read, read_with_proof, ReadResult, WriteResult,
+        },
+        PruneReport,
     },
 };
 
@@ -29,6 +37,7 @@ pub struct InMemoryGlobalState {
     pub environment: Arc<InMemoryEnvironment>,
     pub trie_store: Arc<InMemoryTrieStore>,
     pub protocol_data_store: Arc<InMemoryProtocolDataStore>,
+    pub root_ancestry_store: Arc<InMemoryRootAncestryStore>,
     pub empty_root_hash: Blake2bHash,
 }
 
@@ -45,6 +54,7 @@ impl InMemoryGlobalState {
         let environment = Arc::new(InMemoryEnvironment::new());
         let trie_store = Arc::new(InMemoryTrieStore::new(&environment, None));
         let protocol_data_store = Arc::new(InMemoryProtocolDataStore::new(&environment, None));
+        let root_ancestry_store = Arc::new(InMemoryRootAncestryStore::new(&environment, None));
         let root_hash: Blake2bHash = {
             let (root_hash, root) = create_hashed_empty_trie::<Key, StoredValue>()?;
             let mut txn = environment.create_read_write_txn()?;
@@ -56,6 +66,7 @@ impl InMemoryGlobalState {
             environment,
             trie_store,
             protocol_data_store,
+            root_ancestry_store,
             root_hash,
         ))
     }
@@ -66,12 +77,14 @@ impl InMemoryGlobalState {
         environment: Arc<InMemoryEnvironment>,
         trie_store: Arc<InMemoryTrieStore>,
         protocol_data_store: Arc<InMemoryProtocolDataStore>,
+        root_ancestry_store: Arc<InMemoryRootAncestryStore>,
         empty_root_hash: Blake2bHash,
     ) -> Self {
         InMemoryGlobalState {
             environment,
             trie_store,
             protocol_data_store,
+            root_ancestry_store,
             empty_root_hash,
         }
     }
@@ -94,6 +107,7 @@ impl InMemoryGlobalState {
                     &current_root,
                     &key,
                     value,
+                    None,
                 )? {
                     WriteResult::Written(root_hash) => {
                         current_root = root_hash;
@@ -137,6 +151,52 @@ impl StateReader<Key, StoredValue> for InMemoryGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<(StoredValue, TrieMerkleProof<Key, StoredValue>)>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof::<
+            Key,
+            StoredValue,
+            InMemoryReadTransaction,
+            InMemoryTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(value_and_proof) => Some(value_and_proof),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys_iter =
+            keys_with_prefix::<Key, StoredValue, InMemoryReadTransaction, InMemoryTrieStore>(
+                correlation_id,
+                &txn,
+                self.store.deref(),
+                &self.root_hash,
+                prefix,
+            );
+        let ret = keys_iter.collect::<Result<Vec<Key>, _>>()?;
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 impl StateProvider for InMemoryGlobalState {
@@ -162,13 +222,16 @@ impl StateProvider for InMemoryGlobalState {
         correlation_id: CorrelationId,
         prestate_hash: Blake2bHash,
         effects: AdditiveMap<Key, Transform>,
+        chunk_threshold: Option<u32>,
     ) -> Result<CommitResult, Self::Error> {
-        let commit_result = commit::<InMemoryEnvironment, InMemoryTrieStore, _, Self::Error>(
+        let commit_result = commit::<InMemoryEnvironment, InMemoryTrieStore, _, _, Self::Error>(
             &self.environment,
             &self.trie_store,
+            self.root_ancestry_store.deref(),
             correlation_id,
             prestate_hash,
             effects,
+            chunk_threshold,
         )?;
         Ok(commit_result)
     }
@@ -197,6 +260,78 @@ impl StateProvider for InMemoryGlobalState {
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn flush(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retain_roots: &[Blake2bHash],
+    ) -> Result<PruneReport, Self::Error> {
+        // The empty root is always kept, as new state can still be built on it.
+        let mut roots = retain_roots.to_vec();
+        roots.push(self.empty_root_hash);
+
+        let mut txn = self.environment.create_read_write_txn()?;
+        let report = prune::<Key, StoredValue, _, _, Self::Error>(
+            correlation_id,
+            &mut txn,
+            self.trie_store.deref(),
+            &roots,
+        )?;
+        root_ancestry_store::remove_missing_roots(
+            &mut txn,
+            self.root_ancestry_store.deref(),
+            Store::<Blake2bHash, Trie<Key, StoredValue>>::handle(self.trie_store.deref()),
+        )?;
+        txn.commit()?;
+        Ok(report)
+    }
+
+    fn export_trie(
+        &self,
+        correlation_id: CorrelationId,
+        root: Blake2bHash,
+        mut writer: &mut dyn Write,
+    ) -> Result<Option<usize>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let written = operations::export_trie::<Key, StoredValue, _, _, Self::Error, _>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &root,
+            &mut writer,
+        )?;
+        txn.commit()?;
+        Ok(written)
+    }
+
+    fn import_trie(
+        &self,
+        correlation_id: CorrelationId,
+        mut reader: &mut dyn Read,
+    ) -> Result<Blake2bHash, Self::Error> {
+        // If the snapshot is invalid, the transaction is dropped without being committed.
+        let mut txn = self.environment.create_read_write_txn()?;
+        let root = operations::import_trie::<Key, StoredValue, _, _, Self::Error, _>(
+            correlation_id,
+            &mut txn,
+            self.trie_store.deref(),
+            &mut reader,
+        )?;
+        txn.commit()?;
+        Ok(root)
+    }
+
+    fn ancestry(&self, root: Blake2bHash) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ancestors =
+            root_ancestry_store::ancestry(&txn, self.root_ancestry_store.deref(), &root)?;
+        txn.commit()?;
+        Ok(ancestors)
+    }
 }
 
 #[cfg(test)]
@@ -285,7 +420,10 @@ mod tests {
             .map(|TestPair { key, value }| (key, Transform::Write(value)))
             .collect();
 
-        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+        let updated_hash = match state
+            .commit(correlation_id, root_hash, effects, None)
+            .unwrap()
+        {
             CommitResult::Success { state_root, .. } => state_root,
             _ => panic!("commit failed"),
         };
@@ -315,7 +453,10 @@ mod tests {
             tmp
         };
 
-        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+        let updated_hash = match state
+            .commit(correlation_id, root_hash, effects, None)
+            .unwrap()
+        {
             CommitResult::Success { state_root, .. } => state_root,
             _ => panic!("commit failed"),
         };
@@ -343,6 +484,213 @@ mod tests {
         );
     }
 
+    #[test]
+    fn commit_deletes_key() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+
+        let (state, root_hash) = create_test_state();
+
+        let mut effects: AdditiveMap<Key, Transform> = AdditiveMap::new();
+        effects.insert(test_pairs[1].key, Transform::Delete);
+
+        let updated_hash = match state
+            .commit(correlation_id, root_hash, effects, None)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        assert_eq!(
+            Some(test_pairs[0].value.clone()),
+            updated_checkout
+                .read(correlation_id, &test_pairs[0].key)
+                .unwrap()
+        );
+        assert_eq!(
+            None,
+            updated_checkout
+                .read(correlation_id, &test_pairs[1].key)
+                .unwrap()
+        );
+
+        let (_, expected_hash) = InMemoryGlobalState::from_pairs(
+            correlation_id,
+            &[(test_pairs[0].key, test_pairs[0].value.clone())],
+        )
+        .unwrap();
+        assert_eq!(expected_hash, updated_hash);
+    }
+
+    #[test]
+    fn commit_reports_only_modified_keys() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+        let new_pair = create_test_pairs_updated()[2].clone();
+
+        let (state, root_hash) = create_test_state();
+
+        let mut effects: AdditiveMap<Key, Transform> = AdditiveMap::new();
+        effects.insert(test_pairs[0].key, Transform::Identity);
+        effects.insert(test_pairs[1].key, Transform::AddInt32(0));
+        effects.insert(new_pair.key, Transform::Write(new_pair.value));
+
+        let commit_result = state
+            .commit(correlation_id, root_hash, effects, None)
+            .unwrap();
+        assert_eq!(Some(&[new_pair.key][..]), commit_result.modified_keys());
+
+        let mut effects: AdditiveMap<Key, Transform> = AdditiveMap::new();
+        effects.insert(test_pairs[0].key, Transform::AddInt32(1));
+        effects.insert(test_pairs[1].key, Transform::Delete);
+
+        let updated_hash = commit_result.state_root().expect("commit should succeed");
+        let commit_result = state
+            .commit(correlation_id, updated_hash, effects, None)
+            .unwrap();
+        assert_eq!(
+            Some(&[test_pairs[0].key, test_pairs[1].key][..]),
+            commit_result.modified_keys()
+        );
+    }
+
+    #[test]
+    fn many_commits_on_large_state_keep_every_root_readable() {
+        const KEY_COUNT: u32 = 10_000;
+        const COMMIT_COUNT: u32 = 1_000;
+
+        fn key(index: u32) -> Key {
+            let mut addr = [0u8; 32];
+            addr[..4].copy_from_slice(&index.to_le_bytes());
+            Key::Hash(addr)
+        }
+
+        fn value(version: u32) -> StoredValue {
+            StoredValue::CLValue(CLValue::from_t(version).unwrap())
+        }
+
+        let correlation_id = CorrelationId::new();
+        let mut pairs: Vec<(Key, StoredValue)> =
+            (0..KEY_COUNT).map(|index| (key(index), value(0))).collect();
+        let (state, initial_root) =
+            InMemoryGlobalState::from_pairs(correlation_id, &pairs).unwrap();
+
+        let mut roots = vec![initial_root];
+        for version in 1..=COMMIT_COUNT {
+            let index = version % KEY_COUNT;
+            let mut effects = AdditiveMap::new();
+            effects.insert(key(index), Transform::Write(value(version)));
+            let prestate_hash = *roots.last().unwrap();
+            match state
+                .commit(correlation_id, prestate_hash, effects, None)
+                .unwrap()
+            {
+                CommitResult::Success { state_root, .. } => roots.push(state_root),
+                _ => panic!("commit failed"),
+            }
+            pairs[index as usize].1 = value(version);
+        }
+
+        // Every historical root can still be checked out, and sees only the writes before it.
+        let initial_checkout = state.checkout(initial_root).unwrap().unwrap();
+        let latest_checkout = state.checkout(*roots.last().unwrap()).unwrap().unwrap();
+        for version in 1..=COMMIT_COUNT {
+            let index = version % KEY_COUNT;
+            let checkout = state.checkout(roots[version as usize]).unwrap().unwrap();
+            assert_eq!(
+                Some(value(version)),
+                checkout.read(correlation_id, &key(index)).unwrap()
+            );
+            assert_eq!(
+                Some(value(0)),
+                initial_checkout.read(correlation_id, &key(index)).unwrap()
+            );
+            assert_eq!(
+                Some(value(version)),
+                latest_checkout.read(correlation_id, &key(index)).unwrap()
+            );
+        }
+
+        // The root hash only depends on the contents of the state, not how it was built.
+        let (_, expected_root) = InMemoryGlobalState::from_pairs(correlation_id, &pairs).unwrap();
+        assert_eq!(expected_root, *roots.last().unwrap());
+    }
+
+    #[test]
+    fn concurrent_checkouts_and_commits_do_not_interfere() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let updated_effects: AdditiveMap<Key, Transform> = create_test_pairs_updated()
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect();
+        let updated_hash = match state
+            .commit(correlation_id, root_hash, updated_effects, None)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let new_key = Key::Account(AccountHash::new([4u8; 32]));
+        let new_value = |seed: i32| StoredValue::CLValue(CLValue::from_t(seed).unwrap());
+
+        let prestates = vec![
+            (root_hash, create_test_pairs().to_vec()),
+            (updated_hash, create_test_pairs_updated().to_vec()),
+        ];
+
+        let state = Arc::new(state);
+        let handles: Vec<_> = prestates
+            .clone()
+            .into_iter()
+            .enumerate()
+            .map(|(index, (prestate_hash, expected_pairs))| {
+                let state = Arc::clone(&state);
+                std::thread::spawn(move || {
+                    let checkout = state.checkout(prestate_hash).unwrap().unwrap();
+                    for TestPair { key, value } in expected_pairs {
+                        assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+                    }
+                    let mut effects = AdditiveMap::new();
+                    effects.insert(new_key, Transform::Write(new_value(index as i32)));
+                    match state
+                        .commit(correlation_id, prestate_hash, effects, None)
+                        .unwrap()
+                    {
+                        CommitResult::Success { state_root, .. } => state_root,
+                        _ => panic!("commit failed"),
+                    }
+                })
+            })
+            .collect();
+        let post_state_hashes: Vec<Blake2bHash> = handles
+            .into_iter()
+            .map(|handle| handle.join().expect("thread should not panic"))
+            .collect();
+
+        // Each commit applied its effects on top of its own prestate only.
+        for (index, (prestate_hash, expected_pairs)) in prestates.into_iter().enumerate() {
+            let prestate_checkout = state.checkout(prestate_hash).unwrap().unwrap();
+            assert_eq!(
+                None,
+                prestate_checkout.read(correlation_id, &new_key).unwrap()
+            );
+
+            let checkout = state.checkout(post_state_hashes[index]).unwrap().unwrap();
+            assert_eq!(
+                Some(new_value(index as i32)),
+                checkout.read(correlation_id, &new_key).unwrap()
+            );
+            for TestPair { key, value } in expected_pairs {
+                assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+            }
+        }
+    }
+
     #[test]
     fn initial_state_has_the_expected_hash() {
         let correlation_id = CorrelationId::new();
@@ -1,4 +1,8 @@
-use std::{ops::Deref, sync::Arc};
+use std::{
+    io::{Read, Write},
+    ops::Deref,
+    sync::Arc,
+};
 
 use engine_shared::{
     additive_map::AdditiveMap,
@@ -13,12 +17,17 @@ use crate::{
     global_state::{commit, CommitResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
     protocol_data_store::lmdb::LmdbProtocolDataStore,
+    root_ancestry_store::{self, lmdb::LmdbRootAncestryStore},
     store::Store,
     transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
-    trie::{operations::create_hashed_empty_trie, Trie},
+    trie::{operations::create_hashed_empty_trie, Trie, TrieMerkleProof},
     trie_store::{
         lmdb::LmdbTrieStore,
-        operations::{read, ReadResult},
+        operations::{
+            check_integrity, export_trie, import_trie, keys_with_prefix, prune, read,
+            read_with_proof, ReadResult,
+        },
+        IntegrityReport, PruneReport,
     },
 };
 
@@ -26,6 +35,7 @@ pub struct LmdbGlobalState {
     pub environment: Arc<LmdbEnvironment>,
     pub trie_store: Arc<LmdbTrieStore>,
     pub protocol_data_store: Arc<LmdbProtocolDataStore>,
+    pub root_ancestry_store: Arc<LmdbRootAncestryStore>,
     pub empty_root_hash: Blake2bHash,
 }
 
@@ -42,6 +52,7 @@ impl LmdbGlobalState {
         environment: Arc<LmdbEnvironment>,
         trie_store: Arc<LmdbTrieStore>,
         protocol_data_store: Arc<LmdbProtocolDataStore>,
+        root_ancestry_store: Arc<LmdbRootAncestryStore>,
     ) -> Result<Self, error::Error> {
         let root_hash: Blake2bHash = {
             let (root_hash, root) = create_hashed_empty_trie::<Key, StoredValue>()?;
@@ -54,6 +65,7 @@ impl LmdbGlobalState {
             environment,
             trie_store,
             protocol_data_store,
+            root_ancestry_store,
             root_hash,
         ))
     }
@@ -64,15 +76,35 @@ impl LmdbGlobalState {
         environment: Arc<LmdbEnvironment>,
         trie_store: Arc<LmdbTrieStore>,
         protocol_data_store: Arc<LmdbProtocolDataStore>,
+        root_ancestry_store: Arc<LmdbRootAncestryStore>,
         empty_root_hash: Blake2bHash,
     ) -> Self {
         LmdbGlobalState {
             environment,
             trie_store,
             protocol_data_store,
+            root_ancestry_store,
             empty_root_hash,
         }
     }
+
+    /// Walks the trie under `root`, checking that every trie reachable from it is present and
+    /// stored under its own hash.
+    pub fn check_integrity(
+        &self,
+        correlation_id: CorrelationId,
+        root: Blake2bHash,
+    ) -> Result<IntegrityReport, error::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let report = check_integrity::<Key, StoredValue, _, _, error::Error>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &root,
+        )?;
+        txn.commit()?;
+        Ok(report)
+    }
 }
 
 impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
@@ -98,6 +130,51 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<(StoredValue, TrieMerkleProof<Key, StoredValue>)>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof::<
+            Key,
+            StoredValue,
+            lmdb::RoTransaction,
+            LmdbTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadResult::Found(value_and_proof) => Some(value_and_proof),
+            ReadResult::NotFound => None,
+            ReadResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys_iter = keys_with_prefix::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        );
+        let ret = keys_iter.collect::<Result<Vec<Key>, _>>()?;
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 impl StateProvider for LmdbGlobalState {
@@ -122,13 +199,16 @@ impl StateProvider for LmdbGlobalState {
         correlation_id: CorrelationId,
         prestate_hash: Blake2bHash,
         effects: AdditiveMap<Key, Transform>,
+        chunk_threshold: Option<u32>,
     ) -> Result<CommitResult, Self::Error> {
-        let commit_result = commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
+        let commit_result = commit::<LmdbEnvironment, LmdbTrieStore, _, _, Self::Error>(
             &self.environment,
             &self.trie_store,
+            self.root_ancestry_store.deref(),
             correlation_id,
             prestate_hash,
             effects,
+            chunk_threshold,
         )?;
         Ok(commit_result)
     }
@@ -157,6 +237,78 @@ impl StateProvider for LmdbGlobalState {
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn flush(&self) -> Result<(), Self::Error> {
+        self.environment.sync()
+    }
+
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retain_roots: &[Blake2bHash],
+    ) -> Result<PruneReport, Self::Error> {
+        // The empty root is always kept, as new state can still be built on it.
+        let mut roots = retain_roots.to_vec();
+        roots.push(self.empty_root_hash);
+
+        let mut txn = self.environment.create_read_write_txn()?;
+        let report = prune::<Key, StoredValue, _, _, Self::Error>(
+            correlation_id,
+            &mut txn,
+            self.trie_store.deref(),
+            &roots,
+        )?;
+        root_ancestry_store::remove_missing_roots(
+            &mut txn,
+            self.root_ancestry_store.deref(),
+            Store::<Blake2bHash, Trie<Key, StoredValue>>::handle(self.trie_store.deref()),
+        )?;
+        txn.commit()?;
+        Ok(report)
+    }
+
+    fn export_trie(
+        &self,
+        correlation_id: CorrelationId,
+        root: Blake2bHash,
+        mut writer: &mut dyn Write,
+    ) -> Result<Option<usize>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let written = export_trie::<Key, StoredValue, _, _, Self::Error, _>(
+            correlation_id,
+            &txn,
+            self.trie_store.deref(),
+            &root,
+            &mut writer,
+        )?;
+        txn.commit()?;
+        Ok(written)
+    }
+
+    fn import_trie(
+        &self,
+        correlation_id: CorrelationId,
+        mut reader: &mut dyn Read,
+    ) -> Result<Blake2bHash, Self::Error> {
+        // If the snapshot is invalid, the transaction is dropped without being committed.
+        let mut txn = self.environment.create_read_write_txn()?;
+        let root = import_trie::<Key, StoredValue, _, _, Self::Error, _>(
+            correlation_id,
+            &mut txn,
+            self.trie_store.deref(),
+            &mut reader,
+        )?;
+        txn.commit()?;
+        Ok(root)
+    }
+
+    fn ancestry(&self, root: Blake2bHash) -> Result<Vec<Blake2bHash>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ancestors =
+            root_ancestry_store::ancestry(&txn, self.root_ancestry_store.deref(), &root)?;
+        txn.commit()?;
+        Ok(ancestors)
+    }
 }
 
 #[cfg(test)]
@@ -167,6 +319,7 @@ mod tests {
     use types::{account::AccountHash, CLValue};
 
     use crate::{
+        global_state::in_memory::InMemoryGlobalState,
         trie_store::operations::{write, WriteResult},
         TEST_MAP_SIZE,
     };
@@ -220,7 +373,16 @@ mod tests {
         let protocol_data_store = Arc::new(
             LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
         );
-        let ret = LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+        let root_ancestry_store = Arc::new(
+            LmdbRootAncestryStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let ret = LmdbGlobalState::empty(
+            environment,
+            trie_store,
+            protocol_data_store,
+            root_ancestry_store,
+        )
+        .unwrap();
         let mut current_root = ret.empty_root_hash;
         {
             let mut txn = ret.environment.create_read_write_txn().unwrap();
@@ -233,6 +395,7 @@ mod tests {
                     &current_root,
                     key,
                     value,
+                    None,
                 )
                 .unwrap()
                 {
@@ -282,7 +445,10 @@ mod tests {
             tmp
         };
 
-        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+        let updated_hash = match state
+            .commit(correlation_id, root_hash, effects, None)
+            .unwrap()
+        {
             CommitResult::Success { state_root, .. } => state_root,
             _ => panic!("commit failed"),
         };
@@ -312,7 +478,10 @@ mod tests {
             tmp
         };
 
-        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+        let updated_hash = match state
+            .commit(correlation_id, root_hash, effects, None)
+            .unwrap()
+        {
             CommitResult::Success { state_root, .. } => state_root,
             _ => panic!("commit failed"),
         };
@@ -339,4 +508,225 @@ mod tests {
                 .unwrap()
         );
     }
+
+    fn fresh_key_effects(first: u8, count: u8) -> AdditiveMap<Key, Transform> {
+        let mut effects = AdditiveMap::new();
+        for index in first..first + count {
+            let key = Key::Hash([index; 32]);
+            let value = StoredValue::CLValue(CLValue::from_t(i32::from(index)).unwrap());
+            effects.insert(key, Transform::Write(value));
+        }
+        effects
+    }
+
+    #[test]
+    fn commit_reports_tries_written() {
+        const KEY_COUNT: u8 = 10;
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        let effects = fresh_key_effects(100, KEY_COUNT);
+        let stats = state
+            .commit(correlation_id, root_hash, effects, None)
+            .unwrap()
+            .stats()
+            .expect("commit should succeed");
+
+        assert!(stats.leaves_written >= u64::from(KEY_COUNT));
+        assert!(stats.nodes_written > 0);
+        assert!(stats.bytes_written > 0);
+    }
+
+    #[test]
+    fn trie_store_stats_grow_with_commits() {
+        let correlation_id = CorrelationId::new();
+        let (state, mut root_hash) = create_test_state();
+
+        let mut previous = state.trie_store.stats(&state.environment).unwrap();
+        for first in (100..160).step_by(20) {
+            let effects = fresh_key_effects(first, 20);
+            root_hash = state
+                .commit(correlation_id, root_hash, effects, None)
+                .unwrap()
+                .state_root()
+                .expect("commit should succeed");
+
+            let current = state.trie_store.stats(&state.environment).unwrap();
+            assert!(current.entries > previous.entries);
+            assert!(current.pages() >= previous.pages());
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn commits_record_ancestry_and_distinguish_forks() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        let mut chain = vec![root_hash];
+        for index in 0..4 {
+            let parent = *chain.last().unwrap();
+            let child = match state
+                .commit(
+                    correlation_id,
+                    parent,
+                    fresh_key_effects(index * 10, 5),
+                    None,
+                )
+                .unwrap()
+            {
+                CommitResult::Success { state_root, .. } => state_root,
+                _ => panic!("commit failed"),
+            };
+            chain.push(child);
+        }
+
+        let fork = match state
+            .commit(correlation_id, chain[2], fresh_key_effects(200, 5), None)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let tip = chain[4];
+        let expected: Vec<Blake2bHash> = chain[..4].iter().rev().cloned().collect();
+        assert_eq!(state.ancestry(tip).unwrap(), expected);
+        assert_eq!(
+            state.ancestry(fork).unwrap(),
+            vec![chain[2], chain[1], chain[0]]
+        );
+        assert!(state.ancestry(root_hash).unwrap().is_empty());
+
+        assert!(state.is_ancestor(chain[1], tip).unwrap());
+        assert!(state.is_ancestor(chain[2], fork).unwrap());
+        assert!(!state.is_ancestor(chain[3], fork).unwrap());
+        assert!(!state.is_ancestor(fork, tip).unwrap());
+        assert!(!state.is_ancestor(tip, chain[1]).unwrap());
+    }
+
+    #[test]
+    fn prune_removes_unretained_roots_and_keeps_shared_tries() {
+        let correlation_id = CorrelationId::new();
+        let new_pair = create_test_pairs_updated()[2].clone();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            tmp.insert(new_pair.key, Transform::Write(new_pair.value.clone()));
+            tmp
+        };
+
+        let updated_hash = match state
+            .commit(correlation_id, root_hash, effects, None)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let report = state.prune(correlation_id, &[updated_hash]).unwrap();
+        assert!(report.deleted > 0);
+
+        assert!(state.checkout(root_hash).unwrap().is_none());
+        assert!(state.checkout(state.empty_root()).unwrap().is_some());
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+        assert_eq!(
+            Some(new_pair.value),
+            updated_checkout
+                .read(correlation_id, &new_pair.key)
+                .unwrap()
+        );
+
+        let report = state.check_integrity(correlation_id, updated_hash).unwrap();
+        assert!(report.is_ok());
+    }
+
+    #[test]
+    fn prune_fails_without_deleting_if_retained_root_is_missing() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let missing_root = Blake2bHash::new(&[1, 2, 3]);
+
+        let result = state.prune(correlation_id, &[missing_root]);
+        assert_eq!(result, Err(error::Error::RootNotFound(missing_root)));
+
+        assert!(state.checkout(root_hash).unwrap().is_some());
+        let report = state.check_integrity(correlation_id, root_hash).unwrap();
+        assert!(report.is_ok());
+    }
+
+    #[test]
+    fn prune_removes_ancestry_of_deleted_roots() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        let mut chain = vec![root_hash];
+        for TestPair { key, value } in create_test_pairs_updated().iter().cloned() {
+            let mut effects = AdditiveMap::new();
+            effects.insert(key, Transform::Write(value));
+            match state
+                .commit(correlation_id, *chain.last().unwrap(), effects, None)
+                .unwrap()
+            {
+                CommitResult::Success { state_root, .. } => chain.push(state_root),
+                _ => panic!("commit failed"),
+            }
+        }
+        let tip = chain[3];
+
+        state.prune(correlation_id, &[chain[2], tip]).unwrap();
+
+        // Lineage is kept back to the first pruned root, which no longer records a parent.
+        assert_eq!(state.ancestry(tip).unwrap(), vec![chain[2], chain[1]]);
+        assert!(state.ancestry(chain[1]).unwrap().is_empty());
+
+        state.prune(correlation_id, &[tip]).unwrap();
+        assert_eq!(state.ancestry(tip).unwrap(), vec![chain[2]]);
+        assert!(state.ancestry(chain[2]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn prune_is_idempotent() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        let first = state.prune(correlation_id, &[root_hash]).unwrap();
+        let second = state.prune(correlation_id, &[root_hash]).unwrap();
+
+        assert_eq!(second.kept, first.kept);
+        assert_eq!(second.deleted, 0);
+        assert!(state.checkout(root_hash).unwrap().is_some());
+    }
+
+    #[test]
+    fn exported_state_reads_the_same_after_import_into_memory() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        let mut snapshot = Vec::new();
+        let written = state
+            .export_trie(correlation_id, root_hash, &mut snapshot)
+            .unwrap();
+        assert!(written.is_some());
+
+        let imported = InMemoryGlobalState::empty().unwrap();
+        let imported_root = imported
+            .import_trie(correlation_id, &mut snapshot.as_slice())
+            .unwrap();
+        assert_eq!(imported_root, root_hash);
+
+        let checkout = imported.checkout(imported_root).unwrap().unwrap();
+        for TestPair { key, value } in create_test_pairs().iter().cloned() {
+            assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+        }
+    }
 }
@@ -1,7 +1,13 @@
 pub mod in_memory;
 pub mod lmdb;
 
-use std::{collections::HashMap, fmt, hash::BuildHasher, time::Instant};
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::BuildHasher,
+    io::{Read, Write},
+    time::Instant,
+};
 
 use engine_shared::{
     additive_map::AdditiveMap,
@@ -15,11 +21,15 @@ use types::{account::AccountHash, bytesrepr, Key, ProtocolVersion, U512};
 
 use crate::{
     protocol_data::ProtocolData,
-    transaction_source::{Transaction, TransactionSource},
-    trie::Trie,
+    root_ancestry_store::{record_parent, RootAncestryStore},
+    transaction_source::{write_buffer::WriteBuffer, Transaction, TransactionSource},
+    trie::{Trie, TrieMerkleProof},
     trie_store::{
-        operations::{read, write, ReadResult, WriteResult},
-        TrieStore,
+        operations::{
+            delete_with_stats, put_reachable, read, write_with_stats, DeleteResult, ReadResult,
+            WriteResult,
+        },
+        PruneReport, TrieStore, WriteStats,
     },
     GAUGE_METRIC_KEY,
 };
@@ -29,6 +39,9 @@ const GLOBAL_STATE_COMMIT_WRITES: &str = "global_state_commit_writes";
 const GLOBAL_STATE_COMMIT_DURATION: &str = "global_state_commit_duration";
 const GLOBAL_STATE_COMMIT_READ_DURATION: &str = "global_state_commit_read_duration";
 const GLOBAL_STATE_COMMIT_WRITE_DURATION: &str = "global_state_commit_write_duration";
+const GLOBAL_STATE_COMMIT_LEAVES_WRITTEN: &str = "global_state_commit_leaves_written";
+const GLOBAL_STATE_COMMIT_NODES_WRITTEN: &str = "global_state_commit_nodes_written";
+const GLOBAL_STATE_COMMIT_BYTES_WRITTEN: &str = "global_state_commit_bytes_written";
 const COMMIT: &str = "commit";
 
 /// A reader of state
@@ -38,6 +51,19 @@ pub trait StateReader<K, V> {
 
     /// Returns the state value from the corresponding key
     fn read(&self, correlation_id: CorrelationId, key: &K) -> Result<Option<V>, Self::Error>;
+
+    /// Returns the state value from the corresponding key along with a Merkle proof of it
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &K,
+    ) -> Result<Option<(V, TrieMerkleProof<K, V>)>, Self::Error>;
+    /// Returns the keys in the trie whose serialized form starts with `prefix`
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<K>, Self::Error>;
 }
 
 #[derive(Debug)]
@@ -46,12 +72,43 @@ pub enum CommitResult {
     Success {
         state_root: Blake2bHash,
         bonded_validators: HashMap<AccountHash, U512>,
+        /// The tries put into the trie store by the commit.
+        stats: WriteStats,
+        /// The keys whose stored values differ from the prestate, in ascending order.  Keys whose
+        /// transforms left the value unchanged are not included.
+        modified_keys: Vec<Key>,
     },
     KeyNotFound(Key),
     TypeMismatch(TypeMismatch),
     Serialization(bytesrepr::Error),
 }
 
+impl CommitResult {
+    /// Returns the post-state hash if the commit succeeded.
+    pub fn state_root(&self) -> Option<Blake2bHash> {
+        match self {
+            CommitResult::Success { state_root, .. } => Some(*state_root),
+            _ => None,
+        }
+    }
+
+    /// Returns the counts of tries written if the commit succeeded.
+    pub fn stats(&self) -> Option<WriteStats> {
+        match self {
+            CommitResult::Success { stats, .. } => Some(*stats),
+            _ => None,
+        }
+    }
+
+    /// Returns the keys whose stored values were changed if the commit succeeded.
+    pub fn modified_keys(&self) -> Option<&[Key]> {
+        match self {
+            CommitResult::Success { modified_keys, .. } => Some(modified_keys),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for CommitResult {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match self {
@@ -59,10 +116,15 @@ impl fmt::Display for CommitResult {
             CommitResult::Success {
                 state_root,
                 bonded_validators,
+                stats,
+                modified_keys,
             } => write!(
                 f,
-                "Success: state_root: {}, bonded_validators: {:?}",
-                state_root, bonded_validators
+                "Success: state_root: {}, bonded_validators: {:?}, stats: {:?}, modified_keys: {}",
+                state_root,
+                bonded_validators,
+                stats,
+                modified_keys.len()
             ),
             CommitResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
             CommitResult::TypeMismatch(type_mismatch) => {
@@ -93,11 +155,16 @@ pub trait StateProvider {
 
     /// Applies changes and returns a new post state hash.
     /// block_hash is used for computing a deterministic and unique keys.
+    ///
+    /// Values whose serialized length exceeds `chunk_threshold` are stored in chunks.  The
+    /// threshold is part of the [`ProtocolData`] of the protocol version the changes were made
+    /// under, as it determines the post state hash.
     fn commit(
         &self,
         correlation_id: CorrelationId,
         state_hash: Blake2bHash,
         effects: AdditiveMap<Key, Transform>,
+        chunk_threshold: Option<u32>,
     ) -> Result<CommitResult, Self::Error>;
 
     fn put_protocol_data(
@@ -112,26 +179,75 @@ pub trait StateProvider {
     ) -> Result<Option<ProtocolData>, Self::Error>;
 
     fn empty_root(&self) -> Blake2bHash;
+
+    /// Flushes any buffered state to its backing storage.
+    fn flush(&self) -> Result<(), Self::Error>;
+
+    /// Deletes every trie which isn't reachable from one of `retain_roots` or from the empty root,
+    /// along with the recorded ancestry of the roots deleted.
+    ///
+    /// Fails without deleting anything if any of `retain_roots` is missing.
+    fn prune(
+        &self,
+        correlation_id: CorrelationId,
+        retain_roots: &[Blake2bHash],
+    ) -> Result<PruneReport, Self::Error>;
+
+    /// Writes a snapshot of the state under `root` to `writer`, returning the number of tries
+    /// written, or `None` if `root` is not found.
+    fn export_trie(
+        &self,
+        correlation_id: CorrelationId,
+        root: Blake2bHash,
+        writer: &mut dyn Write,
+    ) -> Result<Option<usize>, Self::Error>;
+
+    /// Loads a snapshot written by [`StateProvider::export_trie`], returning its root hash.
+    ///
+    /// Nothing is stored unless the whole snapshot is valid.
+    fn import_trie(
+        &self,
+        correlation_id: CorrelationId,
+        reader: &mut dyn Read,
+    ) -> Result<Blake2bHash, Self::Error>;
+
+    /// Returns the roots `root` was derived from by commits, starting with its parent and ending
+    /// with the earliest recorded ancestor.
+    fn ancestry(&self, root: Blake2bHash) -> Result<Vec<Blake2bHash>, Self::Error>;
+
+    /// Returns `true` if `descendant` was derived from `ancestor` by one or more commits.
+    fn is_ancestor(
+        &self,
+        ancestor: Blake2bHash,
+        descendant: Blake2bHash,
+    ) -> Result<bool, Self::Error> {
+        Ok(self.ancestry(descendant)?.contains(&ancestor))
+    }
 }
 
-pub fn commit<'a, R, S, H, E>(
+pub fn commit<'a, R, S, A, H, E>(
     environment: &'a R,
     store: &S,
+    root_ancestry_store: &A,
     correlation_id: CorrelationId,
     prestate_hash: Blake2bHash,
     effects: AdditiveMap<Key, Transform, H>,
+    chunk_threshold: Option<u32>,
 ) -> Result<CommitResult, E>
 where
     R: TransactionSource<'a, Handle = S::Handle>,
     S: TrieStore<Key, StoredValue>,
+    S::Handle: PartialEq,
     S::Error: From<R::Error>,
-    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+    A: RootAncestryStore<Handle = S::Handle>,
+    A::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<A::Error> + From<types::bytesrepr::Error>,
     H: BuildHasher,
 {
-    let mut txn = environment.create_read_write_txn()?;
+    let read_txn = environment.create_read_txn()?;
     let mut state_root = prestate_hash;
 
-    let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&txn, &state_root)?;
+    let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&read_txn, &state_root)?;
 
     if maybe_root.is_none() {
         return Ok(CommitResult::RootNotFound);
@@ -140,8 +256,44 @@ where
     let start = Instant::now();
     let mut reads: i32 = 0;
     let mut writes: i32 = 0;
+    let mut stats = WriteStats::default();
+    let mut modified_keys = Vec::new();
+    let chunk_threshold = chunk_threshold.map(|chunk_threshold| chunk_threshold as usize);
+
+    // The effects are applied one key at a time against an in-memory buffer, and only the tries
+    // reachable from the final root are put into the store afterwards, so that the read-write
+    // transaction is short and tries superseded within this commit are never stored.
+    let mut txn = WriteBuffer::new(&read_txn, store.handle());
 
     for (key, transform) in effects.into_iter() {
+        if let Transform::Delete = transform {
+            match delete_with_stats::<_, _, _, _, E>(
+                correlation_id,
+                &mut txn,
+                store,
+                &state_root,
+                &key,
+                &mut WriteStats::default(),
+            )? {
+                DeleteResult::Deleted(new_root) => {
+                    state_root = new_root;
+                    writes += 1;
+                    modified_keys.push(key);
+                }
+                DeleteResult::DoesNotExist => (),
+                _x @ DeleteResult::RootNotFound => panic!(stringify!(_x)),
+            }
+
+            log_duration(
+                correlation_id,
+                GLOBAL_STATE_COMMIT_WRITE_DURATION,
+                COMMIT,
+                start.elapsed(),
+            );
+
+            continue;
+        }
+
         let read_result = read::<_, _, _, _, E>(correlation_id, &txn, store, &state_root, &key)?;
 
         log_duration(
@@ -165,8 +317,16 @@ where
             _x @ (ReadResult::RootNotFound, _) => panic!(stringify!(_x._1)),
         };
 
-        let write_result =
-            write::<_, _, _, _, E>(correlation_id, &mut txn, store, &state_root, &key, &value)?;
+        let write_result = write_with_stats::<_, _, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            &state_root,
+            &key,
+            &value,
+            chunk_threshold,
+            &mut WriteStats::default(),
+        )?;
 
         log_duration(
             correlation_id,
@@ -179,12 +339,26 @@ where
             WriteResult::Written(root_hash) => {
                 state_root = root_hash;
                 writes += 1;
+                modified_keys.push(key);
             }
+            // The leaf under `key` already holds the new value, so it isn't modified.
             WriteResult::AlreadyExists => (),
             _x @ WriteResult::RootNotFound => panic!(stringify!(_x)),
         }
     }
 
+    let buffered_writes = txn.into_writes();
+    read_txn.commit()?;
+
+    // A prune between the read transaction and this one may have deleted the prestate's tries, in
+    // which case the transaction is dropped, and so aborted, without storing anything.
+    let mut txn = environment.create_read_write_txn()?;
+    if put_reachable::<_, _, _, _, E>(&mut txn, store, &state_root, buffered_writes, &mut stats)?
+        .is_none()
+    {
+        return Ok(CommitResult::RootNotFound);
+    }
+    record_parent(&mut txn, root_ancestry_store, &state_root, &prestate_hash)?;
     txn.commit()?;
 
     log_duration(
@@ -210,10 +384,37 @@ where
         f64::from(writes),
     );
 
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_COMMIT_LEAVES_WRITTEN,
+        COMMIT,
+        GAUGE_METRIC_KEY,
+        stats.leaves_written as f64,
+    );
+
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_COMMIT_NODES_WRITTEN,
+        COMMIT,
+        GAUGE_METRIC_KEY,
+        stats.nodes_written as f64,
+    );
+
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_COMMIT_BYTES_WRITTEN,
+        COMMIT,
+        GAUGE_METRIC_KEY,
+        stats.bytes_written as f64,
+    );
+
     let bonded_validators = Default::default();
+    modified_keys.sort();
 
     Ok(CommitResult::Success {
         state_root,
         bonded_validators,
+        stats,
+        modified_keys,
     })
 }
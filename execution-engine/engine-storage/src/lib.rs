@@ -3,6 +3,7 @@ pub mod error;
 pub mod global_state;
 pub mod protocol_data;
 pub mod protocol_data_store;
+pub mod root_ancestry_store;
 pub mod store;
 pub mod transaction_source;
 pub mod trie;
@@ -12,7 +13,7 @@ pub mod trie_store;
 use lazy_static::lazy_static;
 
 pub(crate) const GAUGE_METRIC_KEY: &str = "gauge";
-const MAX_DBS: u32 = 2;
+const MAX_DBS: u32 = 3;
 
 #[cfg(test)]
 lazy_static! {
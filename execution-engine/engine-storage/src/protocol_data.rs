@@ -2,7 +2,7 @@ use engine_wasm_prep::wasm_costs::{WasmCosts, WASM_COSTS_SERIALIZED_LENGTH};
 use std::collections::BTreeMap;
 use types::{
     bytesrepr::{self, FromBytes, ToBytes},
-    ContractHash, HashAddr, KEY_HASH_LENGTH,
+    ContractHash, HashAddr, Key, SystemContractRegistry, SystemContractType, KEY_HASH_LENGTH,
 };
 
 const PROTOCOL_DATA_SERIALIZED_LENGTH: usize = WASM_COSTS_SERIALIZED_LENGTH + 3 * KEY_HASH_LENGTH;
@@ -15,6 +15,7 @@ pub struct ProtocolData {
     mint: ContractHash,
     proof_of_stake: ContractHash,
     standard_payment: ContractHash,
+    trie_chunk_threshold: Option<u32>,
 }
 
 /// Provides a default instance with non existing urefs and empty costs table.
@@ -28,6 +29,7 @@ impl Default for ProtocolData {
             mint: DEFAULT_ADDRESS,
             proof_of_stake: DEFAULT_ADDRESS,
             standard_payment: DEFAULT_ADDRESS,
+            trie_chunk_threshold: None,
         }
     }
 }
@@ -45,9 +47,22 @@ impl ProtocolData {
             mint,
             proof_of_stake,
             standard_payment,
+            trie_chunk_threshold: None,
         }
     }
 
+    /// Sets the serialized length above which values committed under this protocol data are
+    /// stored in chunks, or `None` if values are never chunked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `trie_chunk_threshold` is `Some(0)`.
+    pub fn with_trie_chunk_threshold(mut self, trie_chunk_threshold: Option<u32>) -> Self {
+        assert_ne!(trie_chunk_threshold, Some(0), "chunk threshold must be > 0");
+        self.trie_chunk_threshold = trie_chunk_threshold;
+        self
+    }
+
     /// Creates a new, partially-valid [`ProtocolData`] value where only the mint URef is known.
     ///
     /// Used during `commit_genesis` before all system contracts' URefs are known.
@@ -92,6 +107,37 @@ impl ProtocolData {
         self.standard_payment
     }
 
+    /// Returns the serialized length above which values committed under this protocol data are
+    /// stored in chunks, or `None` if values are never chunked.
+    ///
+    /// The threshold determines the tries written for a value, and so the state roots reached, so
+    /// it is fixed at genesis and carried over by upgrades.
+    pub fn trie_chunk_threshold(&self) -> Option<u32> {
+        self.trie_chunk_threshold
+    }
+
+    /// Returns the hash of the given system contract.
+    pub fn system_contract(&self, system_contract_type: SystemContractType) -> ContractHash {
+        match system_contract_type {
+            SystemContractType::Mint => self.mint,
+            SystemContractType::ProofOfStake => self.proof_of_stake,
+            SystemContractType::StandardPayment => self.standard_payment,
+        }
+    }
+
+    /// Returns the system contract registry holding the system contracts of this protocol data.
+    pub fn system_contract_registry(&self) -> SystemContractRegistry {
+        SystemContractType::ALL
+            .iter()
+            .map(|system_contract_type| {
+                (
+                    system_contract_type.to_string(),
+                    Key::Hash(self.system_contract(*system_contract_type)),
+                )
+            })
+            .collect()
+    }
+
     /// Retrieves all valid system contracts stored in protocol version
     pub fn system_contracts(&self) -> Vec<ContractHash> {
         let mut vec = Vec::with_capacity(3);
@@ -123,27 +169,45 @@ impl ProtocolData {
     }
 }
 
+/// The Wasm costs are split around the system contract hashes: the fields of their original
+/// encoding come first, and their versioned extension after. Protocol data written before the
+/// extension existed ends after the hashes, in which case the extension's fields are defaulted.
+///
+/// The trie chunk threshold comes last.  Protocol data written before it existed ends after the
+/// Wasm costs' extension, in which case values are never chunked, as they weren't then.
 impl ToBytes for ProtocolData {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut ret = bytesrepr::unchecked_allocate_buffer(self);
-        ret.append(&mut self.wasm_costs.to_bytes()?);
+        ret.append(&mut self.wasm_costs.legacy_fields_to_bytes()?);
         ret.append(&mut self.mint.to_bytes()?);
         ret.append(&mut self.proof_of_stake.to_bytes()?);
         ret.append(&mut self.standard_payment.to_bytes()?);
+        ret.append(&mut self.wasm_costs.extension_to_bytes()?);
+        ret.append(&mut self.trie_chunk_threshold.to_bytes()?);
         Ok(ret)
     }
 
     fn serialized_length(&self) -> usize {
-        PROTOCOL_DATA_SERIALIZED_LENGTH
+        PROTOCOL_DATA_SERIALIZED_LENGTH + self.trie_chunk_threshold.serialized_length()
     }
 }
 
 impl FromBytes for ProtocolData {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (wasm_costs, rem) = WasmCosts::from_bytes(bytes)?;
+        let (wasm_costs, rem) = WasmCosts::legacy_fields_from_bytes(bytes)?;
         let (mint, rem) = HashAddr::from_bytes(rem)?;
         let (proof_of_stake, rem) = HashAddr::from_bytes(rem)?;
         let (standard_payment, rem) = HashAddr::from_bytes(rem)?;
+        let (wasm_costs, rem) = if rem.is_empty() {
+            (wasm_costs, rem)
+        } else {
+            wasm_costs.extension_from_bytes(rem)?
+        };
+        let (trie_chunk_threshold, rem) = if rem.is_empty() {
+            (None, rem)
+        } else {
+            Option::<u32>::from_bytes(rem)?
+        };
 
         Ok((
             ProtocolData {
@@ -151,6 +215,7 @@ impl FromBytes for ProtocolData {
                 mint,
                 proof_of_stake,
                 standard_payment,
+                trie_chunk_threshold,
             },
             rem,
         ))
@@ -159,7 +224,7 @@ impl FromBytes for ProtocolData {
 
 #[cfg(test)]
 pub(crate) mod gens {
-    use proptest::prop_compose;
+    use proptest::{option, prop_compose};
 
     use engine_wasm_prep::wasm_costs::gens as wasm_costs_gens;
     use types::gens;
@@ -172,12 +237,14 @@ pub(crate) mod gens {
             mint in gens::u8_slice_32(),
             proof_of_stake in gens::u8_slice_32(),
             standard_payment in gens::u8_slice_32(),
+            trie_chunk_threshold in option::of(1u32..),
         ) -> ProtocolData {
             ProtocolData {
                 wasm_costs,
                 mint,
                 proof_of_stake,
                 standard_payment,
+                trie_chunk_threshold,
             }
         }
     }
@@ -187,24 +254,19 @@ pub(crate) mod gens {
 mod tests {
     use proptest::proptest;
 
-    use engine_wasm_prep::wasm_costs::WasmCosts;
-    use types::{bytesrepr, ContractHash};
+    use engine_wasm_prep::wasm_costs::{
+        HostFunctionCosts, WasmCosts, DEFAULT_MAX_MEMORY_PAGES, DEFAULT_MAX_TABLE_SIZE,
+        MISSING_HOST_FUNCTION_COST,
+    };
+    use types::{
+        bytesrepr::{self, ToBytes},
+        ContractHash, Key, ProtocolVersion, SystemContractRegistry,
+    };
 
     use super::{gens, ProtocolData};
 
     fn wasm_costs_mock() -> WasmCosts {
-        WasmCosts {
-            regular: 1,
-            div: 16,
-            mul: 4,
-            mem: 2,
-            initial_mem: 4096,
-            grow_mem: 8192,
-            memcpy: 1,
-            max_stack_height: 64 * 1024,
-            opcodes_mul: 3,
-            opcodes_div: 8,
-        }
+        WasmCosts::from_version(ProtocolVersion::V1_0_0).expect("should have default costs")
     }
 
     fn wasm_costs_free() -> WasmCosts {
@@ -219,6 +281,9 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
+            max_memory_pages: DEFAULT_MAX_MEMORY_PAGES,
+            max_table_size: DEFAULT_MAX_TABLE_SIZE,
+            host_function_costs: HostFunctionCosts::default(),
         }
     }
 
@@ -252,6 +317,71 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&free);
     }
 
+    #[test]
+    fn should_deserialize_legacy_layout() {
+        // The layout written before the memory limits and host function costs were added: ten
+        // `u32` cost fields followed by the three system contract hashes.
+        let mut bytes = Vec::new();
+        for field in 1..=10u32 {
+            bytes.append(&mut field.to_bytes().unwrap());
+        }
+        for hash in &[[1u8; 32], [2u8; 32], [3u8; 32]] {
+            bytes.append(&mut hash.to_bytes().unwrap());
+        }
+
+        let protocol_data: ProtocolData =
+            bytesrepr::deserialize(bytes).expect("should deserialize legacy protocol data");
+
+        let expected_costs = WasmCosts {
+            regular: 1,
+            div: 2,
+            mul: 3,
+            mem: 4,
+            initial_mem: 5,
+            grow_mem: 6,
+            memcpy: 7,
+            max_stack_height: 8,
+            opcodes_mul: 9,
+            opcodes_div: 10,
+            max_memory_pages: DEFAULT_MAX_MEMORY_PAGES,
+            max_table_size: DEFAULT_MAX_TABLE_SIZE,
+            host_function_costs: HostFunctionCosts::uniform(MISSING_HOST_FUNCTION_COST),
+        };
+        assert_eq!(
+            protocol_data,
+            ProtocolData::new(expected_costs, [1u8; 32], [2u8; 32], [3u8; 32])
+        );
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_with_trie_chunk_threshold() {
+        let protocol_data = ProtocolData::new(wasm_costs_mock(), [1u8; 32], [2u8; 32], [3u8; 32])
+            .with_trie_chunk_threshold(Some(1024));
+        bytesrepr::test_serialization_roundtrip(&protocol_data);
+        assert_eq!(protocol_data.trie_chunk_threshold(), Some(1024));
+    }
+
+    #[test]
+    fn should_deserialize_layout_without_trie_chunk_threshold() {
+        // The layout written before the trie chunk threshold was added: the Wasm costs and their
+        // extension around the three system contract hashes.
+        let wasm_costs = wasm_costs_mock();
+        let mut bytes = wasm_costs.legacy_fields_to_bytes().unwrap();
+        for hash in &[[1u8; 32], [2u8; 32], [3u8; 32]] {
+            bytes.append(&mut hash.to_bytes().unwrap());
+        }
+        bytes.append(&mut wasm_costs.extension_to_bytes().unwrap());
+
+        let protocol_data: ProtocolData =
+            bytesrepr::deserialize(bytes).expect("should deserialize protocol data");
+
+        assert_eq!(
+            protocol_data,
+            ProtocolData::new(wasm_costs, [1u8; 32], [2u8; 32], [3u8; 32])
+        );
+        assert_eq!(protocol_data.trie_chunk_threshold(), None);
+    }
+
     #[test]
     fn should_return_all_system_contracts() {
         let mint_reference = [1u8; 32];
@@ -279,6 +409,18 @@ mod tests {
         assert_eq!(actual[2], standard_payment_reference);
     }
 
+    #[test]
+    fn should_register_all_system_contracts_by_name() {
+        let protocol_data = ProtocolData::new(wasm_costs_mock(), [1u8; 32], [2u8; 32], [3u8; 32]);
+
+        let mut expected = SystemContractRegistry::new();
+        expected.insert("mint".to_string(), Key::Hash([1u8; 32]));
+        expected.insert("proof of stake".to_string(), Key::Hash([2u8; 32]));
+        expected.insert("standard payment".to_string(), Key::Hash([3u8; 32]));
+
+        assert_eq!(protocol_data.system_contract_registry(), expected);
+    }
+
     #[test]
     fn should_return_only_valid_system_contracts() {
         let expected: Vec<ContractHash> = vec![];
@@ -0,0 +1,35 @@
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::{
+    error::in_memory::Error,
+    root_ancestry_store::{self, RootAncestryStore},
+    store::Store,
+    transaction_source::in_memory::InMemoryEnvironment,
+};
+
+/// An in-memory root ancestry store
+pub struct InMemoryRootAncestryStore {
+    maybe_name: Option<String>,
+}
+
+impl InMemoryRootAncestryStore {
+    pub fn new(_env: &InMemoryEnvironment, maybe_name: Option<&str>) -> Self {
+        let name = maybe_name
+            .map(|name| format!("{}-{}", root_ancestry_store::NAME, name))
+            .unwrap_or_else(|| String::from(root_ancestry_store::NAME));
+        InMemoryRootAncestryStore {
+            maybe_name: Some(name),
+        }
+    }
+}
+
+impl Store<Blake2bHash, Blake2bHash> for InMemoryRootAncestryStore {
+    type Error = Error;
+    type Handle = Option<String>;
+
+    fn handle(&self) -> Self::Handle {
+        self.maybe_name.to_owned()
+    }
+}
+
+impl RootAncestryStore for InMemoryRootAncestryStore {}
@@ -0,0 +1,53 @@
+use engine_shared::newtypes::Blake2bHash;
+use lmdb::{Database, DatabaseFlags};
+
+use crate::{
+    error,
+    root_ancestry_store::{self, RootAncestryStore},
+    store::Store,
+    transaction_source::lmdb::LmdbEnvironment,
+};
+
+/// An LMDB-backed root ancestry store.
+///
+/// Wraps [`lmdb::Database`].
+#[derive(Debug, Clone)]
+pub struct LmdbRootAncestryStore {
+    db: Database,
+}
+
+impl LmdbRootAncestryStore {
+    pub fn new(
+        env: &LmdbEnvironment,
+        maybe_name: Option<&str>,
+        flags: DatabaseFlags,
+    ) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().create_db(Some(&name), flags)?;
+        Ok(LmdbRootAncestryStore { db })
+    }
+
+    pub fn open(env: &LmdbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().open_db(Some(&name))?;
+        Ok(LmdbRootAncestryStore { db })
+    }
+
+    fn name(maybe_name: Option<&str>) -> String {
+        maybe_name
+            .map(|name| format!("{}-{}", root_ancestry_store::NAME, name))
+            .unwrap_or_else(|| String::from(root_ancestry_store::NAME))
+    }
+}
+
+impl Store<Blake2bHash, Blake2bHash> for LmdbRootAncestryStore {
+    type Error = error::Error;
+
+    type Handle = Database;
+
+    fn handle(&self) -> Self::Handle {
+        self.db
+    }
+}
+
+impl RootAncestryStore for LmdbRootAncestryStore {}
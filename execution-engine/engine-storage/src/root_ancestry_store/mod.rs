@@ -0,0 +1,88 @@
+//! A store for persisting the lineage of state roots, i.e. the root each committed root was
+//! derived from.
+use std::collections::HashSet;
+
+use engine_shared::newtypes::Blake2bHash;
+
+pub mod in_memory;
+pub mod lmdb;
+
+use crate::{
+    store::Store,
+    transaction_source::{Deletable, Readable, Writable},
+};
+
+const NAME: &str = "ROOT_ANCESTRY_STORE";
+
+/// An entity which persists the parent of each committed state root under the root's hash.
+pub trait RootAncestryStore: Store<Blake2bHash, Blake2bHash> {}
+
+/// Records `parent` as the root `child` was derived from.
+///
+/// A root keeps the first parent recorded for it, and a commit which leaves the state unchanged
+/// records nothing.
+pub fn record_parent<T, S>(
+    txn: &mut T,
+    store: &S,
+    child: &Blake2bHash,
+    parent: &Blake2bHash,
+) -> Result<(), S::Error>
+where
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: RootAncestryStore,
+    S::Error: From<T::Error>,
+{
+    if child == parent || store.get(txn, child)?.is_some() {
+        return Ok(());
+    }
+    store.put(txn, child, parent)
+}
+
+/// Returns the ancestors of `root`, starting with its parent and ending with the earliest recorded
+/// ancestor.
+///
+/// The walk stops at the first root seen twice, so a lineage which loops back on itself (e.g. when
+/// a later commit reverts to an earlier root) is still finite.
+pub fn ancestry<T, S>(txn: &T, store: &S, root: &Blake2bHash) -> Result<Vec<Blake2bHash>, S::Error>
+where
+    T: Readable<Handle = S::Handle>,
+    S: RootAncestryStore,
+    S::Error: From<T::Error>,
+{
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    visited.insert(*root);
+    let mut ancestors = Vec::new();
+    let mut current = *root;
+    while let Some(parent) = store.get(txn, &current)? {
+        if !visited.insert(parent) {
+            break;
+        }
+        ancestors.push(parent);
+        current = parent;
+    }
+    Ok(ancestors)
+}
+
+/// Removes the recorded parent of every root which is no longer stored under `trie_handle`, e.g.
+/// after its tries were pruned, returning the number of roots removed.
+pub fn remove_missing_roots<T, S>(
+    txn: &mut T,
+    store: &S,
+    trie_handle: S::Handle,
+) -> Result<usize, S::Error>
+where
+    T: Readable<Handle = S::Handle> + Deletable<Handle = S::Handle>,
+    S: RootAncestryStore,
+    S::Handle: Clone,
+    S::Error: From<T::Error>,
+{
+    let mut removed = 0;
+    // Roots are stored under the same bytes in both stores.
+    for root_bytes in txn.keys(store.handle())? {
+        if txn.read(trie_handle.clone(), &root_bytes)?.is_none() {
+            txn.delete(store.handle(), &root_bytes)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
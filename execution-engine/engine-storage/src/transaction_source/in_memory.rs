@@ -5,7 +5,7 @@ use std::{
 
 use crate::{
     error::in_memory::Error,
-    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+    transaction_source::{Deletable, Readable, Transaction, TransactionSource, Writable},
 };
 
 /// A marker for use in a mutex which represents the capability to perform a
@@ -16,20 +16,23 @@ type WriteLock<'a> = MutexGuard<'a, WriteCapability>;
 
 type BytesMap = HashMap<Vec<u8>, Vec<u8>>;
 
-type PoisonError<'a> = sync::PoisonError<MutexGuard<'a, HashMap<Option<String>, BytesMap>>>;
+/// The contents of every handle at a point in time.  Taking a snapshot only clones the `Arc`s, and
+/// a handle's map is only copied when it is written to while a snapshot of it is still alive.
+type Snapshot = HashMap<Option<String>, Arc<BytesMap>>;
+
+/// Uncommitted changes to a single handle, where `None` marks a deleted key.
+type BytesDelta = HashMap<Vec<u8>, Option<Vec<u8>>>;
+
+type PoisonError<'a> = sync::PoisonError<MutexGuard<'a, Snapshot>>;
 
 /// A read transaction for the in-memory trie store.
 pub struct InMemoryReadTransaction {
-    view: HashMap<Option<String>, BytesMap>,
+    view: Snapshot,
 }
 
 impl InMemoryReadTransaction {
     pub fn new(store: &InMemoryEnvironment) -> Result<InMemoryReadTransaction, Error> {
-        let view = {
-            let db_ref = Arc::clone(&store.data);
-            let view_lock = db_ref.lock()?;
-            view_lock.to_owned()
-        };
+        let view = store.data.lock()?.clone();
         Ok(InMemoryReadTransaction { view })
     }
 }
@@ -50,27 +53,33 @@ impl Readable for InMemoryReadTransaction {
             Some(view) => view,
             None => return Ok(None),
         };
-        Ok(sub_view.get(&key.to_vec()).cloned())
+        Ok(sub_view.get(key).cloned())
     }
 }
 
 /// A read-write transaction for the in-memory trie store.
+///
+/// Writes and deletions are buffered as per-handle deltas over the snapshot taken when the
+/// transaction was created, and only applied to the store on commit, so both creating and
+/// committing the transaction cost time proportional to the number of changes rather than the size
+/// of the store.
 pub struct InMemoryReadWriteTransaction<'a> {
-    view: HashMap<Option<String>, BytesMap>,
-    store_ref: Arc<Mutex<HashMap<Option<String>, BytesMap>>>,
+    view: Snapshot,
+    changes: HashMap<Option<String>, BytesDelta>,
+    store_ref: Arc<Mutex<Snapshot>>,
     _write_lock: WriteLock<'a>,
 }
 
 impl<'a> InMemoryReadWriteTransaction<'a> {
     pub fn new(store: &'a InMemoryEnvironment) -> Result<InMemoryReadWriteTransaction<'a>, Error> {
-        let store_ref = Arc::clone(&store.data);
-        let view = {
-            let view_lock = store_ref.lock()?;
-            view_lock.to_owned()
-        };
+        // Take the write lock before the snapshot, so that no other writer can commit between the
+        // two and have its changes hidden from this transaction.
         let _write_lock = store.write_mutex.lock()?;
+        let store_ref = Arc::clone(&store.data);
+        let view = store_ref.lock()?.clone();
         Ok(InMemoryReadWriteTransaction {
             view,
+            changes: HashMap::new(),
             store_ref,
             _write_lock,
         })
@@ -84,32 +93,78 @@ impl<'a> Transaction for InMemoryReadWriteTransaction<'a> {
 
     fn commit(self) -> Result<(), Self::Error> {
         let mut store_ref_lock = self.store_ref.lock()?;
-        store_ref_lock.extend(self.view);
+        for (handle, delta) in self.changes {
+            let sub_store = Arc::make_mut(store_ref_lock.entry(handle).or_default());
+            for (key, maybe_value) in delta {
+                match maybe_value {
+                    Some(value) => {
+                        sub_store.insert(key, value);
+                    }
+                    None => {
+                        sub_store.remove(&key);
+                    }
+                }
+            }
+        }
         Ok(())
     }
 }
 
 impl<'a> Readable for InMemoryReadWriteTransaction<'a> {
     fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        if let Some(maybe_value) = self.changes.get(&handle).and_then(|delta| delta.get(key)) {
+            return Ok(maybe_value.clone());
+        }
         let sub_view = match self.view.get(&handle) {
             Some(view) => view,
             None => return Ok(None),
         };
-        Ok(sub_view.get(&key.to_vec()).cloned())
+        Ok(sub_view.get(key).cloned())
     }
 }
 
 impl<'a> Writable for InMemoryReadWriteTransaction<'a> {
     fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
-        let sub_view = self.view.entry(handle).or_default();
-        sub_view.insert(key.to_vec(), value.to_vec());
+        let delta = self.changes.entry(handle).or_default();
+        delta.insert(key.to_vec(), Some(value.to_vec()));
+        Ok(())
+    }
+}
+
+impl<'a> Deletable for InMemoryReadWriteTransaction<'a> {
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let empty_delta = BytesDelta::new();
+        let delta = self.changes.get(&handle).unwrap_or(&empty_delta);
+        let mut keys: Vec<Vec<u8>> = self
+            .view
+            .get(&handle)
+            .map(|sub_view| {
+                sub_view
+                    .keys()
+                    .filter(|key| !delta.contains_key(*key))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        keys.extend(
+            delta
+                .iter()
+                .filter(|(_, maybe_value)| maybe_value.is_some())
+                .map(|(key, _)| key.clone()),
+        );
+        Ok(keys)
+    }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        let delta = self.changes.entry(handle).or_default();
+        delta.insert(key.to_vec(), None);
         Ok(())
     }
 }
 
 /// An environment for the in-memory trie store.
 pub struct InMemoryEnvironment {
-    data: Arc<Mutex<HashMap<Option<String>, BytesMap>>>,
+    data: Arc<Mutex<Snapshot>>,
     write_mutex: Arc<Mutex<WriteCapability>>,
 }
 
@@ -133,7 +188,7 @@ impl InMemoryEnvironment {
     pub fn data(&self, name: Option<&str>) -> Result<Option<BytesMap>, PoisonError> {
         let data = self.data.lock()?;
         let name = name.map(ToString::to_string);
-        let ret = data.get(&name).cloned();
+        let ret = data.get(&name).map(|sub_data| (**sub_data).clone());
         Ok(ret)
     }
 }
@@ -155,3 +210,75 @@ impl<'a> TransactionSource<'a> for InMemoryEnvironment {
         InMemoryReadWriteTransaction::new(self).map_err(Into::into)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_1: &[u8] = b"key_1";
+    const KEY_2: &[u8] = b"key_2";
+
+    fn write_and_commit(env: &InMemoryEnvironment, key: &[u8], value: &[u8]) {
+        let mut txn = env.create_read_write_txn().unwrap();
+        txn.write(None, key, value).unwrap();
+        txn.commit().unwrap();
+    }
+
+    #[test]
+    fn read_txn_should_not_see_later_commits() {
+        let env = InMemoryEnvironment::new();
+        write_and_commit(&env, KEY_1, b"old");
+
+        let read_txn = env.create_read_txn().unwrap();
+        write_and_commit(&env, KEY_1, b"new");
+        write_and_commit(&env, KEY_2, b"added");
+
+        assert_eq!(read_txn.read(None, KEY_1).unwrap(), Some(b"old".to_vec()));
+        assert_eq!(read_txn.read(None, KEY_2).unwrap(), None);
+
+        let read_txn = env.create_read_txn().unwrap();
+        assert_eq!(read_txn.read(None, KEY_1).unwrap(), Some(b"new".to_vec()));
+        assert_eq!(read_txn.read(None, KEY_2).unwrap(), Some(b"added".to_vec()));
+    }
+
+    #[test]
+    fn read_write_txn_should_see_its_own_changes() {
+        let env = InMemoryEnvironment::new();
+        write_and_commit(&env, KEY_1, b"value_1");
+
+        let mut txn = env.create_read_write_txn().unwrap();
+        txn.write(None, KEY_2, b"value_2").unwrap();
+        txn.delete(None, KEY_1).unwrap();
+
+        assert_eq!(txn.read(None, KEY_1).unwrap(), None);
+        assert_eq!(txn.read(None, KEY_2).unwrap(), Some(b"value_2".to_vec()));
+        assert_eq!(txn.keys(None).unwrap(), vec![KEY_2.to_vec()]);
+
+        // Nothing is visible to readers until the transaction is committed.
+        assert_eq!(
+            env.create_read_txn().unwrap().read(None, KEY_1).unwrap(),
+            Some(b"value_1".to_vec())
+        );
+        txn.commit().unwrap();
+
+        let read_txn = env.create_read_txn().unwrap();
+        assert_eq!(read_txn.read(None, KEY_1).unwrap(), None);
+        assert_eq!(
+            read_txn.read(None, KEY_2).unwrap(),
+            Some(b"value_2".to_vec())
+        );
+    }
+
+    #[test]
+    fn dropped_read_write_txn_should_not_change_store() {
+        let env = InMemoryEnvironment::new();
+        {
+            let mut txn = env.create_read_write_txn().unwrap();
+            txn.write(None, KEY_1, b"value_1").unwrap();
+        }
+        assert_eq!(
+            env.create_read_txn().unwrap().read(None, KEY_1).unwrap(),
+            None
+        );
+    }
+}
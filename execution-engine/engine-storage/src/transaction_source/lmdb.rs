@@ -1,10 +1,12 @@
 use std::path::PathBuf;
 
-use lmdb::{self, Database, Environment, RoTransaction, RwTransaction, WriteFlags};
+use lmdb::{
+    self, Cursor, Database, Environment, EnvironmentFlags, RoTransaction, RwTransaction, WriteFlags,
+};
 
 use crate::{
     error,
-    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+    transaction_source::{Deletable, Readable, Transaction, TransactionSource, Writable},
     MAX_DBS,
 };
 
@@ -18,6 +20,30 @@ impl<'a> Transaction for RoTransaction<'a> {
     }
 }
 
+/// Reads `keys` from `handle` using a single cursor.
+///
+/// The keys are visited in sorted order, which lets LMDB find a key on the page the cursor is
+/// already positioned on rather than searching down from the root of the B-tree each time.
+fn read_many_with_cursor<T: lmdb::Transaction>(
+    txn: &T,
+    handle: Database,
+    keys: &[&[u8]],
+) -> Result<Vec<Option<Vec<u8>>>, lmdb::Error> {
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    order.sort_by_key(|index| keys[*index]);
+
+    let cursor = txn.open_ro_cursor(handle)?;
+    let mut values = vec![None; keys.len()];
+    for index in order {
+        match cursor.get(Some(keys[index]), None, lmdb_sys::MDB_SET_KEY) {
+            Ok((_, bytes)) => values[index] = Some(bytes.to_vec()),
+            Err(lmdb::Error::NotFound) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(values)
+}
+
 impl<'a> Readable for RoTransaction<'a> {
     fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
         match lmdb::Transaction::get(self, handle, &key) {
@@ -26,6 +52,14 @@ impl<'a> Readable for RoTransaction<'a> {
             Err(e) => Err(e),
         }
     }
+
+    fn read_many(
+        &self,
+        handle: Self::Handle,
+        keys: &[&[u8]],
+    ) -> Result<Vec<Option<Vec<u8>>>, Self::Error> {
+        read_many_with_cursor(self, handle, keys)
+    }
 }
 
 impl<'a> Transaction for RwTransaction<'a> {
@@ -46,6 +80,14 @@ impl<'a> Readable for RwTransaction<'a> {
             Err(e) => Err(e),
         }
     }
+
+    fn read_many(
+        &self,
+        handle: Self::Handle,
+        keys: &[&[u8]],
+    ) -> Result<Vec<Option<Vec<u8>>>, Self::Error> {
+        read_many_with_cursor(self, handle, keys)
+    }
 }
 
 impl<'a> Writable for RwTransaction<'a> {
@@ -55,6 +97,69 @@ impl<'a> Writable for RwTransaction<'a> {
     }
 }
 
+impl<'a> Deletable for RwTransaction<'a> {
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let mut cursor = lmdb::Transaction::open_ro_cursor(self, handle)?;
+        // `iter_start` panics on an empty database, whereas `iter` yields nothing.
+        Ok(cursor.iter().map(|(key, _)| key.to_vec()).collect())
+    }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        match self.del(handle, &key, None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => Ok(()),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// The default max number of simultaneous read transactions, matching LMDB's own default.
+pub const DEFAULT_MAX_READERS: u32 = 126;
+
+/// Options used when opening an [`LmdbEnvironment`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LmdbEnvironmentOptions {
+    map_size: usize,
+    max_readers: u32,
+    no_sync: bool,
+}
+
+impl LmdbEnvironmentOptions {
+    /// Creates options with the given map size and defaults for everything else.
+    ///
+    /// The map size should be a multiple of the OS page size.
+    pub fn new(map_size: usize) -> Self {
+        LmdbEnvironmentOptions {
+            map_size,
+            max_readers: DEFAULT_MAX_READERS,
+            no_sync: false,
+        }
+    }
+
+    pub fn with_max_readers(mut self, max_readers: u32) -> Self {
+        self.max_readers = max_readers;
+        self
+    }
+
+    /// If `true`, LMDB doesn't flush its buffers to disk when committing a transaction.  This is
+    /// faster, but the most recently committed transactions may be lost on a system crash.
+    pub fn with_no_sync(mut self, no_sync: bool) -> Self {
+        self.no_sync = no_sync;
+        self
+    }
+
+    pub fn map_size(&self) -> usize {
+        self.map_size
+    }
+
+    pub fn max_readers(&self) -> u32 {
+        self.max_readers
+    }
+
+    pub fn no_sync(&self) -> bool {
+        self.no_sync
+    }
+}
+
 /// The environment for an LMDB-backed trie store.
 ///
 /// Wraps [`lmdb::Environment`].
@@ -66,9 +171,23 @@ pub struct LmdbEnvironment {
 
 impl LmdbEnvironment {
     pub fn new(path: &PathBuf, map_size: usize) -> Result<Self, error::Error> {
+        Self::with_options(path, LmdbEnvironmentOptions::new(map_size))
+    }
+
+    pub fn with_options(
+        path: &PathBuf,
+        options: LmdbEnvironmentOptions,
+    ) -> Result<Self, error::Error> {
+        let flags = if options.no_sync {
+            EnvironmentFlags::NO_SYNC
+        } else {
+            EnvironmentFlags::empty()
+        };
         let env = Environment::new()
+            .set_flags(flags)
             .set_max_dbs(MAX_DBS)
-            .set_map_size(map_size)
+            .set_max_readers(options.max_readers)
+            .set_map_size(options.map_size)
             .open(path)?;
         let path = path.to_owned();
         Ok(LmdbEnvironment { path, env })
@@ -81,6 +200,12 @@ impl LmdbEnvironment {
     pub fn env(&self) -> &Environment {
         &self.env
     }
+
+    /// Flushes the data buffers to disk.
+    pub fn sync(&self) -> Result<(), error::Error> {
+        self.env.sync(true)?;
+        Ok(())
+    }
 }
 
 impl<'a> TransactionSource<'a> for LmdbEnvironment {
@@ -1,5 +1,6 @@
 pub mod in_memory;
 pub mod lmdb;
+pub mod write_buffer;
 
 /// A transaction which can be committed or aborted.
 pub trait Transaction: Sized {
@@ -8,7 +9,7 @@ pub trait Transaction: Sized {
     type Error;
 
     /// An entity which is being read from or written to during a transaction.
-    type Handle;
+    type Handle: Clone;
 
     /// Commits the transaction.
     fn commit(self) -> Result<(), Self::Error>;
@@ -25,6 +26,18 @@ pub trait Transaction: Sized {
 pub trait Readable: Transaction {
     /// Returns the value from the corresponding key from a given [`Transaction::Handle`].
     fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error>;
+
+    /// Returns the values from the corresponding keys from a given [`Transaction::Handle`], in
+    /// the same order as `keys`.
+    fn read_many(
+        &self,
+        handle: Self::Handle,
+        keys: &[&[u8]],
+    ) -> Result<Vec<Option<Vec<u8>>>, Self::Error> {
+        keys.iter()
+            .map(|key| self.read(handle.clone(), key))
+            .collect()
+    }
 }
 
 /// A transaction with the capability to write to a given [`Handle`](Transaction::Handle).
@@ -33,6 +46,16 @@ pub trait Writable: Transaction {
     fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error>;
 }
 
+/// A transaction with the capability to enumerate and delete the entries of a given
+/// [`Handle`](Transaction::Handle).
+pub trait Deletable: Writable {
+    /// Returns every key in a given [`Transaction::Handle`].
+    fn keys(&self, handle: Self::Handle) -> Result<Vec<Vec<u8>>, Self::Error>;
+
+    /// Removes the entry under the given key from a given [`Transaction::Handle`], if present.
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error>;
+}
+
 /// A source of transactions e.g. values that implement [`Readable`]
 /// and/or [`Writable`].
 pub trait TransactionSource<'a> {
@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+
+use crate::transaction_source::{Readable, Transaction, Writable};
+
+/// Holds the writes made to a single handle on top of a read transaction in memory, so that they
+/// can be inspected and put into a store later in one short-lived read-write transaction.
+///
+/// Reads of the buffered handle see the buffered writes; reads of other handles go straight to
+/// the underlying transaction.  Committing the buffer only discards it: the buffered writes are
+/// retrieved with [`WriteBuffer::into_writes`].
+pub struct WriteBuffer<'a, T: Transaction> {
+    txn: &'a T,
+    handle: T::Handle,
+    writes: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl<'a, T: Transaction> WriteBuffer<'a, T> {
+    pub fn new(txn: &'a T, handle: T::Handle) -> Self {
+        WriteBuffer {
+            txn,
+            handle,
+            writes: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the buffered writes, ordered by key.
+    pub fn into_writes(self) -> BTreeMap<Vec<u8>, Vec<u8>> {
+        self.writes
+    }
+}
+
+impl<'a, T: Transaction> Transaction for WriteBuffer<'a, T> {
+    type Error = T::Error;
+
+    type Handle = T::Handle;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, T> Readable for WriteBuffer<'a, T>
+where
+    T: Readable,
+    T::Handle: PartialEq,
+{
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        if handle == self.handle {
+            if let Some(value) = self.writes.get(key) {
+                return Ok(Some(value.clone()));
+            }
+        }
+        self.txn.read(handle, key)
+    }
+}
+
+impl<'a, T> Writable for WriteBuffer<'a, T>
+where
+    T: Readable,
+    T::Handle: PartialEq,
+{
+    fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
+        assert!(
+            handle == self.handle,
+            "write buffer only holds writes to a single handle"
+        );
+        self.writes.insert(key.to_vec(), value.to_vec());
+        Ok(())
+    }
+}
@@ -34,6 +34,9 @@ pub fn trie_arb() -> impl Strategy<Value = Trie<Key, StoredValue>> {
             pointer_block: Box::new(pointer_block)
         }),
         (vec(any::<u8>(), 0..32), trie_pointer_arb())
-            .prop_map(|(affix, pointer)| Trie::Extension { affix, pointer })
+            .prop_map(|(affix, pointer)| Trie::Extension { affix, pointer }),
+        (key_arb(), vec(blake2b_hash_arb(), 0..8))
+            .prop_map(|(key, chunk_hashes)| Trie::ChunkedLeaf { key, chunk_hashes }),
+        vec(any::<u8>(), 0..1000).prop_map(|bytes| Trie::Chunk { bytes })
     ]
 }
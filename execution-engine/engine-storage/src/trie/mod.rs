@@ -206,9 +206,27 @@ impl ::std::fmt::Debug for PointerBlock {
 /// Represents a Merkle Trie
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Trie<K, V> {
-    Leaf { key: K, value: V },
-    Node { pointer_block: Box<PointerBlock> },
-    Extension { affix: Vec<u8>, pointer: Pointer },
+    Leaf {
+        key: K,
+        value: V,
+    },
+    Node {
+        pointer_block: Box<PointerBlock>,
+    },
+    Extension {
+        affix: Vec<u8>,
+        pointer: Pointer,
+    },
+    /// A leaf whose serialized value is stored across the [`Trie::Chunk`]s with the given hashes,
+    /// in order.
+    ChunkedLeaf {
+        key: K,
+        chunk_hashes: Vec<Blake2bHash>,
+    },
+    /// A piece of the serialized value of a [`Trie::ChunkedLeaf`].
+    Chunk {
+        bytes: Vec<u8>,
+    },
 }
 
 impl<K, V> Trie<K, V> {
@@ -217,6 +235,8 @@ impl<K, V> Trie<K, V> {
             Trie::Leaf { .. } => 0,
             Trie::Node { .. } => 1,
             Trie::Extension { .. } => 2,
+            Trie::ChunkedLeaf { .. } => 3,
+            Trie::Chunk { .. } => 4,
         }
     }
 
@@ -239,10 +259,18 @@ impl<K, V> Trie<K, V> {
 
     pub fn key(&self) -> Option<&K> {
         match self {
-            Trie::Leaf { key, .. } => Some(key),
+            Trie::Leaf { key, .. } | Trie::ChunkedLeaf { key, .. } => Some(key),
             _ => None,
         }
     }
+
+    /// Returns `true` if this is a [`Trie::Leaf`] or a [`Trie::ChunkedLeaf`].
+    pub fn is_leaf(&self) -> bool {
+        match self {
+            Trie::Leaf { .. } | Trie::ChunkedLeaf { .. } => true,
+            _ => false,
+        }
+    }
 }
 
 impl<K, V> ToBytes for Trie<K, V>
@@ -266,6 +294,13 @@ where
                 ret.append(&mut affix.to_bytes()?);
                 ret.append(&mut pointer.to_bytes()?);
             }
+            Trie::ChunkedLeaf { key, chunk_hashes } => {
+                ret.append(&mut key.to_bytes()?);
+                ret.append(&mut chunk_hashes.to_bytes()?);
+            }
+            Trie::Chunk { bytes } => {
+                ret.append(&mut bytes.to_bytes()?);
+            }
         }
         Ok(ret)
     }
@@ -278,6 +313,10 @@ where
                 Trie::Extension { affix, pointer } => {
                     affix.serialized_length() + pointer.serialized_length()
                 }
+                Trie::ChunkedLeaf { key, chunk_hashes } => {
+                    key.serialized_length() + chunk_hashes.serialized_length()
+                }
+                Trie::Chunk { bytes } => bytes.serialized_length(),
             }
     }
 }
@@ -305,11 +344,120 @@ impl<K: FromBytes, V: FromBytes> FromBytes for Trie<K, V> {
                 let (pointer, rem) = Pointer::from_bytes(rem)?;
                 Ok((Trie::Extension { affix, pointer }, rem))
             }
+            3 => {
+                let (key, rem) = K::from_bytes(rem)?;
+                let (chunk_hashes, rem) = Vec::<Blake2bHash>::from_bytes(rem)?;
+                Ok((Trie::ChunkedLeaf { key, chunk_hashes }, rem))
+            }
+            4 => {
+                let (bytes, rem) = Vec::<u8>::from_bytes(rem)?;
+                Ok((Trie::Chunk { bytes }, rem))
+            }
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
 }
 
+/// A Merkle proof that a key maps to a value under a given root hash.
+///
+/// Contains the trie nodes visited while reading the key, ordered from the root down to and
+/// including the [`Trie::Leaf`] holding the key and value.  If the value is held in a
+/// [`Trie::ChunkedLeaf`], that leaf is followed by its [`Trie::Chunk`]s, in order.
+pub type TrieMerkleProof<K, V> = Vec<Trie<K, V>>;
+
+/// Returns `true` if `proof` shows that `key` maps to `value` in the trie with the given `root`.
+///
+/// Each node in the proof is hashed and checked against the pointer followed from its parent
+/// (starting with `root` itself), and the final node must be a leaf containing `key` and `value`,
+/// or a chunked leaf containing `key` followed by chunks which hash to its chunk hashes and which
+/// together hold `value`.
+pub fn validate_proof<K, V>(root: &Blake2bHash, key: &K, value: &V, proof: &[Trie<K, V>]) -> bool
+where
+    K: ToBytes + Eq,
+    V: ToBytes + PartialEq,
+{
+    let (nodes, leaf, chunks) = match proof.iter().position(Trie::is_leaf) {
+        Some(leaf_index) => (
+            &proof[..leaf_index],
+            &proof[leaf_index],
+            &proof[leaf_index + 1..],
+        ),
+        None => return false,
+    };
+
+    match leaf {
+        Trie::Leaf {
+            key: leaf_key,
+            value: leaf_value,
+        } if leaf_key == key && leaf_value == value && chunks.is_empty() => (),
+        Trie::ChunkedLeaf {
+            key: leaf_key,
+            chunk_hashes,
+        } if leaf_key == key && chunk_hashes.len() == chunks.len() => {
+            let mut value_bytes = Vec::new();
+            for (chunk_hash, chunk) in chunk_hashes.iter().zip(chunks) {
+                match (chunk, chunk.to_bytes()) {
+                    (Trie::Chunk { bytes }, Ok(chunk_bytes))
+                        if Blake2bHash::new(&chunk_bytes) == *chunk_hash =>
+                    {
+                        value_bytes.extend_from_slice(bytes)
+                    }
+                    _ => return false,
+                }
+            }
+            match value.to_bytes() {
+                Ok(expected_bytes) if expected_bytes == value_bytes => (),
+                _ => return false,
+            }
+        }
+        _ => return false,
+    }
+
+    let path = match key.to_bytes() {
+        Ok(path) => path,
+        Err(_) => return false,
+    };
+
+    let mut depth: usize = 0;
+    let mut expected_hash = *root;
+
+    for node in nodes {
+        match node.to_bytes() {
+            Ok(bytes) if Blake2bHash::new(&bytes) == expected_hash => (),
+            _ => return false,
+        }
+        match node {
+            Trie::Leaf { .. } | Trie::ChunkedLeaf { .. } | Trie::Chunk { .. } => return false,
+            Trie::Node { pointer_block } => {
+                let index: usize = match path.get(depth) {
+                    Some(index) => (*index).into(),
+                    None => return false,
+                };
+                match pointer_block[index] {
+                    Some(pointer) => {
+                        expected_hash = *pointer.hash();
+                        depth += 1;
+                    }
+                    None => return false,
+                }
+            }
+            Trie::Extension { affix, pointer } => {
+                match path.get(depth..depth + affix.len()) {
+                    Some(sub_path) if sub_path == affix.as_slice() => (),
+                    _ => return false,
+                }
+                expected_hash = *pointer.hash();
+                depth += affix.len();
+            }
+        }
+    }
+
+    match leaf.to_bytes() {
+        Ok(bytes) => Blake2bHash::new(&bytes) == expected_hash,
+        Err(_) => false,
+    }
+}
+
 pub(crate) mod operations {
     use crate::trie::Trie;
     use engine_shared::newtypes::Blake2bHash;
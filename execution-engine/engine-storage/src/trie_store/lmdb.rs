@@ -104,14 +104,17 @@
 //! tmp_dir.close().unwrap();
 //! ```
 
-use lmdb::{Database, DatabaseFlags};
+use std::mem::MaybeUninit;
+
+use lmdb::{Database, DatabaseFlags, Transaction};
 
 use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr::{self, FromBytes, ToBytes};
 
 use crate::{
     error,
     store::Store,
-    transaction_source::lmdb::LmdbEnvironment,
+    transaction_source::{lmdb::LmdbEnvironment, Readable},
     trie::Trie,
     trie_store::{self, TrieStore},
 };
@@ -141,6 +144,30 @@ impl LmdbTrieStore {
         Ok(LmdbTrieStore { db })
     }
 
+    /// Returns the number of tries in the store and the pages they occupy, as of the last
+    /// committed write transaction.
+    pub fn stats(&self, env: &LmdbEnvironment) -> Result<LmdbTrieStoreStats, error::Error> {
+        let txn = env.env().begin_ro_txn()?;
+        let mut stat = MaybeUninit::<lmdb_sys::MDB_stat>::uninit();
+        // Safe as `txn` and `self.db` are valid for the duration of the call, and `stat` is only
+        // read once LMDB has reported filling it in.
+        let stat = unsafe {
+            let code = lmdb_sys::mdb_stat(txn.txn(), self.db.dbi(), stat.as_mut_ptr());
+            if code != lmdb_sys::MDB_SUCCESS {
+                return Err(lmdb::Error::from_err_code(code).into());
+            }
+            stat.assume_init()
+        };
+        txn.commit()?;
+        Ok(LmdbTrieStoreStats {
+            entries: stat.ms_entries,
+            page_size: stat.ms_psize,
+            branch_pages: stat.ms_branch_pages,
+            leaf_pages: stat.ms_leaf_pages,
+            overflow_pages: stat.ms_overflow_pages,
+        })
+    }
+
     fn name(maybe_name: Option<&str>) -> String {
         maybe_name
             .map(|name| format!("{}-{}", trie_store::NAME, name))
@@ -148,6 +175,33 @@ impl LmdbTrieStore {
     }
 }
 
+/// Size information about an [`LmdbTrieStore`], as reported by LMDB.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LmdbTrieStoreStats {
+    /// The number of tries stored.
+    pub entries: usize,
+    /// The size of a database page, in bytes.
+    pub page_size: u32,
+    /// The number of internal B-tree pages.
+    pub branch_pages: usize,
+    /// The number of leaf B-tree pages.
+    pub leaf_pages: usize,
+    /// The number of pages holding values too big to fit in a leaf page.
+    pub overflow_pages: usize,
+}
+
+impl LmdbTrieStoreStats {
+    /// Returns the total number of pages used by the store.
+    pub fn pages(&self) -> usize {
+        self.branch_pages + self.leaf_pages + self.overflow_pages
+    }
+
+    /// Returns the total size of the pages used by the store, in bytes.
+    pub fn bytes(&self) -> usize {
+        self.pages() * self.page_size as usize
+    }
+}
+
 impl<K, V> Store<Blake2bHash, Trie<K, V>> for LmdbTrieStore {
     type Error = error::Error;
 
@@ -158,4 +212,31 @@ impl<K, V> Store<Blake2bHash, Trie<K, V>> for LmdbTrieStore {
     }
 }
 
-impl<K, V> TrieStore<K, V> for LmdbTrieStore {}
+impl<K, V> TrieStore<K, V> for LmdbTrieStore {
+    /// Reads every hash in one pass, so that LMDB transactions can use a single cursor for all
+    /// of them.
+    fn get_many<T>(
+        &self,
+        txn: &T,
+        hashes: &[Blake2bHash],
+    ) -> Result<Vec<Option<Trie<K, V>>>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        K: FromBytes,
+        V: FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        let keys = hashes
+            .iter()
+            .map(ToBytes::to_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+        let keys: Vec<&[u8]> = keys.iter().map(Vec::as_slice).collect();
+        txn.read_many(self.db, &keys)?
+            .into_iter()
+            .map(|maybe_bytes| match maybe_bytes {
+                Some(bytes) => Ok(Some(bytesrepr::deserialize(bytes)?)),
+                None => Ok(None),
+            })
+            .collect()
+    }
+}
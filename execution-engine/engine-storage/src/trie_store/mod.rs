@@ -9,10 +9,30 @@ pub(crate) mod operations;
 mod tests;
 
 use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr::FromBytes;
 
-use crate::{store::Store, trie::Trie};
+pub use self::operations::{IntegrityReport, PruneReport, WriteStats};
+use crate::{store::Store, transaction_source::Readable, trie::Trie};
 
 const NAME: &str = "TRIE_STORE";
 
 /// An entity which persists [`Trie`] values at their hashes.
-pub trait TrieStore<K, V>: Store<Blake2bHash, Trie<K, V>> {}
+pub trait TrieStore<K, V>: Store<Blake2bHash, Trie<K, V>> {
+    /// Returns the tries stored at each of `hashes`, in the same order as `hashes`.
+    ///
+    /// Stores which can read several entries more cheaply than reading each in turn should
+    /// override this; by default it is equivalent to calling [`Store::get`] for each hash.
+    fn get_many<T>(
+        &self,
+        txn: &T,
+        hashes: &[Blake2bHash],
+    ) -> Result<Vec<Option<Trie<K, V>>>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        K: FromBytes,
+        V: FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        hashes.iter().map(|hash| self.get(txn, hash)).collect()
+    }
+}
@@ -1,7 +1,14 @@
 #[cfg(test)]
 mod tests;
 
-use std::{cmp, collections::VecDeque, mem, time::Instant};
+use std::{
+    cmp,
+    collections::{BTreeMap, HashSet, VecDeque},
+    io::{self, Read, Write},
+    mem,
+    ops::AddAssign,
+    time::Instant,
+};
 
 use engine_shared::{
     logging::{log_duration, log_metric},
@@ -10,8 +17,9 @@ use engine_shared::{
 use types::bytesrepr::{self, FromBytes, ToBytes};
 
 use crate::{
-    transaction_source::{Readable, Writable},
-    trie::{self, Parents, Pointer, Trie, RADIX},
+    error,
+    transaction_source::{Deletable, Readable, Writable},
+    trie::{self, Parents, Pointer, Trie, TrieMerkleProof, RADIX},
     trie_store::TrieStore,
     GAUGE_METRIC_KEY,
 };
@@ -22,11 +30,13 @@ const TRIE_STORE_SCAN_DURATION: &str = "trie_store_scan_duration";
 const TRIE_STORE_SCAN_GETS: &str = "trie_store_scan_gets";
 const TRIE_STORE_WRITE_DURATION: &str = "trie_store_write_duration";
 const TRIE_STORE_WRITE_PUTS: &str = "trie_store_write_puts";
+const TRIE_STORE_DELETE_DURATION: &str = "trie_store_delete_duration";
 const READ: &str = "read";
 const GET: &str = "get";
 const SCAN: &str = "scan";
 const WRITE: &str = "write";
 const PUT: &str = "put";
+const DELETE: &str = "delete";
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ReadResult<V> {
@@ -64,16 +74,25 @@ where
 
     loop {
         match current {
-            Trie::Leaf {
-                key: leaf_key,
-                value: leaf_value,
-            } => {
-                let result = if *key == leaf_key {
-                    ReadResult::Found(leaf_value)
-                } else {
+            leaf @ Trie::Leaf { .. }
+            | leaf @ Trie::ChunkedLeaf { .. }
+            | leaf @ Trie::Chunk { .. } => {
+                let result = match leaf {
+                    Trie::Leaf {
+                        key: leaf_key,
+                        value: leaf_value,
+                    } if *key == leaf_key => ReadResult::Found(leaf_value),
+                    Trie::ChunkedLeaf {
+                        key: leaf_key,
+                        chunk_hashes,
+                    } if *key == leaf_key => {
+                        get_counter += chunk_hashes.len() as i32;
+                        let (value, _) = read_chunks::<K, V, T, S, E>(txn, store, &chunk_hashes)?;
+                        ReadResult::Found(value)
+                    }
                     // Keys may not match in the case of a compressed path from
                     // a Node directly to a Leaf
-                    ReadResult::NotFound
+                    _ => ReadResult::NotFound,
                 };
                 log_metric(
                     correlation_id,
@@ -199,6 +218,113 @@ where
     }
 }
 
+/// Returns a value from the corresponding key at a given root in a given store, along with a Merkle
+/// proof of the trie nodes visited from the root down to the leaf holding the value.
+pub fn read_with_proof<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<ReadResult<(V, TrieMerkleProof<K, V>)>, E>
+where
+    K: ToBytes + FromBytes + Eq + Clone + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let path: Vec<u8> = key.to_bytes()?;
+
+    let mut depth: usize = 0;
+    let mut proof: TrieMerkleProof<K, V> = Vec::new();
+    let mut current: Trie<K, V> = match store.get(txn, root)? {
+        Some(root) => root,
+        None => return Ok(ReadResult::RootNotFound),
+    };
+
+    let start = Instant::now();
+    let mut get_counter: i32 = 0;
+
+    let result = loop {
+        let maybe_pointer: Option<Pointer> = match &current {
+            Trie::Leaf {
+                key: leaf_key,
+                value: leaf_value,
+            } => {
+                // Keys may not match in the case of a compressed path from
+                // a Node directly to a Leaf
+                if key != leaf_key {
+                    break ReadResult::NotFound;
+                }
+                let value = leaf_value.clone();
+                proof.push(current);
+                break ReadResult::Found((value, proof));
+            }
+            Trie::ChunkedLeaf {
+                key: leaf_key,
+                chunk_hashes,
+            } => {
+                if key != leaf_key {
+                    break ReadResult::NotFound;
+                }
+                get_counter += chunk_hashes.len() as i32;
+                let (value, chunks) = read_chunks::<K, V, T, S, E>(txn, store, chunk_hashes)?;
+                proof.push(current);
+                proof.extend(chunks);
+                break ReadResult::Found((value, proof));
+            }
+            Trie::Chunk { .. } => break ReadResult::NotFound,
+            Trie::Node { pointer_block } => {
+                let index: usize = {
+                    assert!(depth < path.len(), "depth must be < {}", path.len());
+                    path[depth].into()
+                };
+                assert!(index < trie::RADIX, "key length must be < {}", trie::RADIX);
+                depth += 1;
+                pointer_block[index]
+            }
+            Trie::Extension { affix, pointer } => {
+                let sub_path = &path[depth..depth + affix.len()];
+                if sub_path != affix.as_slice() {
+                    break ReadResult::NotFound;
+                }
+                depth += affix.len();
+                Some(*pointer)
+            }
+        };
+        let pointer = match maybe_pointer {
+            Some(pointer) => pointer,
+            None => break ReadResult::NotFound,
+        };
+        get_counter += 1;
+        match store.get(txn, pointer.hash())? {
+            Some(next) => proof.push(mem::replace(&mut current, next)),
+            None => panic!(
+                "No trie value at key: {:?} (reading from key: {:?})",
+                pointer.hash(),
+                key
+            ),
+        }
+    };
+
+    log_metric(
+        correlation_id,
+        TRIE_STORE_READ_GETS,
+        GET,
+        GAUGE_METRIC_KEY,
+        f64::from(get_counter),
+    );
+    log_duration(
+        correlation_id,
+        TRIE_STORE_READ_DURATION,
+        READ,
+        start.elapsed(),
+    );
+    Ok(result)
+}
+
 struct TrieScan<K, V> {
     tip: Trie<K, V>,
     parents: Parents<K, V>,
@@ -240,7 +366,7 @@ where
 
     loop {
         match current {
-            leaf @ Trie::Leaf { .. } => {
+            leaf @ Trie::Leaf { .. } | leaf @ Trie::ChunkedLeaf { .. } => {
                 log_metric(
                     correlation_id,
                     TRIE_STORE_SCAN_GETS,
@@ -367,10 +493,79 @@ where
                     }
                 }
             }
+            Trie::Chunk { .. } => panic!("chunks should only be reached from chunked leaves"),
         }
     }
 }
 
+/// Reads the chunks with the given hashes from `store`, returning the value they hold along with
+/// the chunks themselves.
+#[allow(clippy::type_complexity)]
+fn read_chunks<K, V, T, S, E>(
+    txn: &T,
+    store: &S,
+    chunk_hashes: &[Blake2bHash],
+) -> Result<(V, Vec<Trie<K, V>>), E>
+where
+    K: FromBytes,
+    V: FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut value_bytes: Vec<u8> = Vec::new();
+    let mut chunks: Vec<Trie<K, V>> = Vec::with_capacity(chunk_hashes.len());
+    for (chunk_hash, maybe_chunk) in chunk_hashes.iter().zip(store.get_many(txn, chunk_hashes)?) {
+        match maybe_chunk {
+            Some(Trie::Chunk { bytes }) => {
+                value_bytes.extend_from_slice(&bytes);
+                chunks.push(Trie::Chunk { bytes });
+            }
+            _ => panic!("No trie chunk at key: {:?}", chunk_hash),
+        }
+    }
+    let value = bytesrepr::deserialize(value_bytes)?;
+    Ok((value, chunks))
+}
+
+/// Returns the leaf to be written for `key` and `value`.
+///
+/// If the serialized value is longer than `chunk_threshold`, the leaf is a [`Trie::ChunkedLeaf`],
+/// and the [`Trie::Chunk`]s it points to are returned with it, each paired with its hash.
+/// Otherwise the leaf is a [`Trie::Leaf`] and no chunks are returned.
+#[allow(clippy::type_complexity)]
+fn create_leaf<K, V>(
+    key: &K,
+    value: &V,
+    chunk_threshold: Option<usize>,
+) -> Result<(Trie<K, V>, Vec<(Blake2bHash, Trie<K, V>)>), bytesrepr::Error>
+where
+    K: ToBytes + Clone,
+    V: ToBytes + Clone,
+{
+    let chunk_size = match chunk_threshold {
+        Some(chunk_threshold) if value.serialized_length() > chunk_threshold => chunk_threshold,
+        _ => return Ok((Trie::leaf(key.to_owned(), value.to_owned()), Vec::new())),
+    };
+    let value_bytes = value.to_bytes()?;
+    let mut chunk_hashes: Vec<Blake2bHash> = Vec::new();
+    let mut chunks: Vec<(Blake2bHash, Trie<K, V>)> = Vec::new();
+    for bytes in value_bytes.chunks(chunk_size) {
+        let chunk = Trie::Chunk {
+            bytes: bytes.to_vec(),
+        };
+        let chunk_hash = Blake2bHash::new(&chunk.to_bytes()?);
+        chunk_hashes.push(chunk_hash);
+        chunks.push((chunk_hash, chunk));
+    }
+    let leaf = Trie::ChunkedLeaf {
+        key: key.to_owned(),
+        chunk_hashes,
+    };
+    Ok((leaf, chunks))
+}
+
 #[allow(clippy::type_complexity)]
 fn rehash<K, V>(
     mut tip: Trie<K, V>,
@@ -389,15 +584,17 @@ where
 
     for (index, parent) in parents.into_iter().rev() {
         match parent {
-            Trie::Leaf { .. } => {
-                panic!("parents should not contain any leaves");
+            Trie::Leaf { .. } | Trie::ChunkedLeaf { .. } | Trie::Chunk { .. } => {
+                panic!("parents should not contain any leaves or chunks");
             }
             Trie::Node { mut pointer_block } => {
                 tip = {
                     let pointer = match tip {
                         Trie::Leaf { .. } => Pointer::LeafPointer(tip_hash),
+                        Trie::ChunkedLeaf { .. } => Pointer::LeafPointer(tip_hash),
                         Trie::Node { .. } => Pointer::NodePointer(tip_hash),
                         Trie::Extension { .. } => Pointer::NodePointer(tip_hash),
+                        Trie::Chunk { .. } => panic!("a chunk should not have a parent node"),
                     };
                     pointer_block[index.into()] = Some(pointer);
                     Trie::Node { pointer_block }
@@ -618,6 +815,45 @@ pub enum WriteResult {
     RootNotFound,
 }
 
+/// Counts of the tries put into a store by one or more writes or deletes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WriteStats {
+    /// The number of `Leaf`s and `ChunkedLeaf`s written.
+    pub leaves_written: u64,
+    /// The number of `Node`s and `Extension`s written.
+    pub nodes_written: u64,
+    /// The number of `Chunk`s written.
+    pub chunks_written: u64,
+    /// The total serialized size of the tries written, in bytes.
+    pub bytes_written: u64,
+}
+
+impl WriteStats {
+    fn record<K: ToBytes, V: ToBytes>(&mut self, trie: &Trie<K, V>) {
+        match trie {
+            Trie::Leaf { .. } | Trie::ChunkedLeaf { .. } => self.leaves_written += 1,
+            Trie::Node { .. } | Trie::Extension { .. } => self.nodes_written += 1,
+            Trie::Chunk { .. } => self.chunks_written += 1,
+        }
+        self.bytes_written += trie.serialized_length() as u64;
+    }
+}
+
+impl AddAssign for WriteStats {
+    fn add_assign(&mut self, other: Self) {
+        self.leaves_written += other.leaves_written;
+        self.nodes_written += other.nodes_written;
+        self.chunks_written += other.chunks_written;
+        self.bytes_written += other.bytes_written;
+    }
+}
+
+/// Writes `value` under `key` in the trie with root `root`.
+///
+/// A value whose serialized length exceeds `chunk_threshold` is stored as a [`Trie::ChunkedLeaf`]
+/// pointing to [`Trie::Chunk`]s of at most `chunk_threshold` bytes.  As the threshold determines
+/// the tries written, and so the resulting root hash, every writer expected to reach the same root
+/// must pass the same threshold.
 pub fn write<K, V, T, S, E>(
     correlation_id: CorrelationId,
     txn: &mut T,
@@ -625,6 +861,40 @@ pub fn write<K, V, T, S, E>(
     root: &Blake2bHash,
     key: &K,
     value: &V,
+    chunk_threshold: Option<usize>,
+) -> Result<WriteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut stats = WriteStats::default();
+    write_with_stats::<K, V, T, S, E>(
+        correlation_id,
+        txn,
+        store,
+        root,
+        key,
+        value,
+        chunk_threshold,
+        &mut stats,
+    )
+}
+
+/// Same as [`write`], but also adds the tries put into `store` to `stats`.
+#[allow(clippy::too_many_arguments)]
+pub fn write_with_stats<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+    value: &V,
+    chunk_threshold: Option<usize>,
+    stats: &mut WriteStats,
 ) -> Result<WriteResult, E>
 where
     K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
@@ -640,40 +910,35 @@ where
     match store.get(txn, root)? {
         None => Ok(WriteResult::RootNotFound),
         Some(current_root) => {
-            let new_leaf = Trie::Leaf {
-                key: key.to_owned(),
-                value: value.to_owned(),
-            };
+            let (new_leaf, new_chunks) = create_leaf(key, value, chunk_threshold)?;
             let path: Vec<u8> = key.to_bytes()?;
             let TrieScan { tip, parents } =
                 scan::<K, V, T, S, E>(correlation_id, txn, store, &path, &current_root)?;
             let new_elements: Vec<(Blake2bHash, Trie<K, V>)> = match tip {
                 // If the "tip" is the same as the new leaf, then the leaf
                 // is already in the Trie.
-                Trie::Leaf { .. } if new_leaf == tip => Vec::new(),
+                ref leaf if *leaf == new_leaf => Vec::new(),
                 // If the "tip" is an existing leaf with the same key as the
-                // new leaf, but the existing leaf and new leaf have different
-                // values, then we are in the situation where we are "updating"
-                // an existing leaf.
-                Trie::Leaf {
-                    key: ref leaf_key,
-                    value: ref leaf_value,
-                } if key == leaf_key && value != leaf_value => rehash(new_leaf, parents)?,
+                // new leaf, but the existing leaf and new leaf differ, then we
+                // are in the situation where we are "updating" an existing
+                // leaf.
+                ref leaf if leaf.key() == Some(key) => rehash(new_leaf, parents)?,
                 // If the "tip" is an existing leaf with a different key than
                 // the new leaf, then we are in a situation where the new leaf
                 // shares some common prefix with the existing leaf.
                 Trie::Leaf {
                     key: ref existing_leaf_key,
                     ..
-                } if key != existing_leaf_key => {
+                }
+                | Trie::ChunkedLeaf {
+                    key: ref existing_leaf_key,
+                    ..
+                } => {
                     let existing_leaf_path = existing_leaf_key.to_bytes()?;
                     let (new_node, parents) = reparent_leaf(&path, &existing_leaf_path, parents)?;
                     let parents = add_node_to_parents(&path, new_node, parents)?;
                     rehash(new_leaf, parents)?
                 }
-                // This case is unreachable, but the compiler can't figure
-                // that out.
-                Trie::Leaf { .. } => unreachable!(),
                 // If the "tip" is an existing node, then we can add a pointer
                 // to the new leaf to the node's pointer block.
                 node @ Trie::Node { .. } => {
@@ -697,6 +962,7 @@ where
                         rehash(new_leaf, parents)?
                     }
                 }
+                Trie::Chunk { .. } => unreachable!("scan never returns a chunk"),
             };
             if new_elements.is_empty() {
                 log_duration(
@@ -707,10 +973,21 @@ where
                 );
                 return Ok(WriteResult::AlreadyExists);
             }
+            for (hash, chunk) in new_chunks.iter() {
+                // Chunks are stored at the hash of their contents, so a chunk shared with a value
+                // which is already in the store doesn't need to be written again.
+                if store.get(txn, hash)?.is_some() {
+                    continue;
+                }
+                put_counter += 1;
+                store.put(txn, hash, chunk)?;
+                stats.record(chunk);
+            }
             let mut root_hash = root.to_owned();
             for (hash, element) in new_elements.iter() {
                 put_counter += 1;
                 store.put(txn, hash, element)?;
+                stats.record(element);
                 root_hash = *hash;
             }
             log_metric(
@@ -731,6 +1008,199 @@ where
     }
 }
 
+/// Puts the tries among `writes` which are reachable from `root` into `store`, in the order of
+/// their hashes, and adds them to `stats`.
+///
+/// `writes` are the serialized tries, keyed by serialized hash, which a batch of writes and deletes
+/// made to a [`WriteBuffer`](crate::transaction_source::write_buffer::WriteBuffer).  Tries which
+/// a later write in the batch superseded aren't reachable from the batch's final root, so they are
+/// never put.  Returns the number of tries put.
+///
+/// The batch is made under a read transaction, so the tries it builds on may have been pruned by
+/// the time `txn` starts.  Each reachable trie which isn't among `writes` is checked to still be in
+/// the store under `txn`, and if any is missing, nothing is put and `None` is returned.
+pub fn put_reachable<K, V, T, S, E>(
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+    mut writes: BTreeMap<Vec<u8>, Vec<u8>>,
+    stats: &mut WriteStats,
+) -> Result<Option<usize>, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut reachable = BTreeMap::new();
+    let mut to_visit = vec![*root];
+    while let Some(hash) = to_visit.pop() {
+        let hash_bytes = hash.to_bytes()?;
+        if let Some(trie_bytes) = writes.remove(&hash_bytes) {
+            let trie: Trie<K, V> = bytesrepr::deserialize(trie_bytes)?;
+            push_children(&trie, &mut to_visit);
+            reachable.insert(hash, trie);
+        } else if txn
+            .read(store.handle(), &hash_bytes)
+            .map_err(S::Error::from)?
+            .is_none()
+        {
+            return Ok(None);
+        }
+    }
+
+    let put_count = reachable.len();
+    for (hash, trie) in reachable {
+        store.put(txn, &hash, &trie)?;
+        stats.record(&trie);
+    }
+    Ok(Some(put_count))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeleteResult {
+    Deleted(Blake2bHash),
+    DoesNotExist,
+    RootNotFound,
+}
+
+/// Removes the leaf with the given key from the trie at a given root in a given store.
+///
+/// Nodes left with a single child are collapsed, so that deleting a key which was just written
+/// yields the root hash from before the write.
+pub fn delete<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+    key_to_delete: &K,
+) -> Result<DeleteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut stats = WriteStats::default();
+    delete_with_stats::<K, V, T, S, E>(correlation_id, txn, store, root, key_to_delete, &mut stats)
+}
+
+/// Same as [`delete`], but also adds the tries put into `store` to `stats`.
+pub fn delete_with_stats<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+    key_to_delete: &K,
+    stats: &mut WriteStats,
+) -> Result<DeleteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let start = Instant::now();
+
+    let current_root = match store.get(txn, root)? {
+        None => return Ok(DeleteResult::RootNotFound),
+        Some(current_root) => current_root,
+    };
+
+    let path: Vec<u8> = key_to_delete.to_bytes()?;
+    let TrieScan { tip, mut parents } =
+        scan::<K, V, T, S, E>(correlation_id, txn, store, &path, &current_root)?;
+
+    match tip {
+        ref leaf if leaf.key() == Some(key_to_delete) => (),
+        _ => {
+            log_duration(
+                correlation_id,
+                TRIE_STORE_DELETE_DURATION,
+                DELETE,
+                start.elapsed(),
+            );
+            return Ok(DeleteResult::DoesNotExist);
+        }
+    }
+
+    let (leaf_index, mut pointer_block) = match parents.pop() {
+        Some((index, Trie::Node { pointer_block })) => (index, pointer_block),
+        _ => panic!("A leaf should have a node for its parent"),
+    };
+    pointer_block[<usize>::from(leaf_index)] = None;
+
+    let remaining_children: Vec<(usize, Pointer)> = pointer_block[..]
+        .iter()
+        .enumerate()
+        .filter_map(|(index, maybe_pointer)| maybe_pointer.map(|pointer| (index, pointer)))
+        .collect();
+
+    let new_elements: Vec<(Blake2bHash, Trie<K, V>)> = match remaining_children.as_slice() {
+        // The root node is never collapsed, and a node which still has several children stays
+        // as it is.
+        _ if parents.is_empty() => rehash(Trie::Node { pointer_block }, parents)?,
+        [_, _, ..] => rehash(Trie::Node { pointer_block }, parents)?,
+        // If the only remaining child is a leaf, it replaces the node along with any extension
+        // leading to that node.
+        [(_, Pointer::LeafPointer(leaf_hash))] => {
+            if let Some((_, Trie::Extension { .. })) = parents.last() {
+                parents.pop();
+            }
+            let leaf = match store.get(txn, leaf_hash)? {
+                Some(leaf) if leaf.is_leaf() => leaf,
+                _ => panic!("No trie leaf at key: {:?}", leaf_hash),
+            };
+            rehash(leaf, parents)?
+        }
+        // If the only remaining child is a node or an extension, the node is replaced by an
+        // extension, merged with any extensions directly above and below it.
+        [(index, Pointer::NodePointer(child_hash))] => {
+            let mut affix = match parents.last() {
+                Some((_, Trie::Extension { .. })) => match parents.pop() {
+                    Some((_, Trie::Extension { affix, .. })) => affix,
+                    _ => unreachable!(),
+                },
+                _ => Vec::new(),
+            };
+            affix.push(*index as u8);
+            let pointer = match store.get(txn, child_hash)? {
+                Some(Trie::Node { .. }) => Pointer::NodePointer(*child_hash),
+                Some(Trie::Extension {
+                    affix: child_affix,
+                    pointer,
+                }) => {
+                    affix.extend(child_affix);
+                    pointer
+                }
+                _ => panic!("No trie node at key: {:?}", child_hash),
+            };
+            rehash(Trie::extension(affix, pointer), parents)?
+        }
+        [] => panic!("A node other than the root should have at least two children"),
+    };
+
+    let mut root_hash = root.to_owned();
+    for (hash, element) in new_elements.iter() {
+        store.put(txn, hash, element)?;
+        stats.record(element);
+        root_hash = *hash;
+    }
+    log_duration(
+        correlation_id,
+        TRIE_STORE_DELETE_DURATION,
+        DELETE,
+        start.elapsed(),
+    );
+    Ok(DeleteResult::Deleted(root_hash))
+}
+
 enum KeysIteratorState<K, V, S: TrieStore<K, V>> {
     /// Iterate normally
     Ok,
@@ -784,7 +1254,7 @@ where
             let mut maybe_next_trie: Option<Trie<K, V>> = None;
 
             match trie {
-                Trie::Leaf { key, .. } => {
+                Trie::Leaf { key, .. } | Trie::ChunkedLeaf { key, .. } => {
                     let key_bytes = match key.to_bytes() {
                         Ok(bytes) => bytes,
                         Err(e) => {
@@ -865,6 +1335,7 @@ where
                         path.extend(affix);
                     }
                 }
+                Trie::Chunk { .. } => (),
             }
 
             if let Some(next_trie) = maybe_next_trie {
@@ -902,7 +1373,6 @@ where
 /// Returns the iterator over the keys in the subtrie matching `prefix`.
 ///
 /// The root should be the apex of the trie.
-#[allow(dead_code)]
 pub fn keys_with_prefix<'a, 'b, K, V, T, S>(
     _correlation_id: CorrelationId,
     txn: &'b T,
@@ -938,3 +1408,307 @@ where
         state: init_state,
     }
 }
+
+/// The outcome of checking the integrity of the trie under a given root.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// The number of `Node`s and `Extension`s reached from the root.
+    pub node_count: usize,
+    /// The number of `Leaf`s and `ChunkedLeaf`s reached from the root.
+    pub leaf_count: usize,
+    /// The number of `Chunk`s reached from the root.
+    pub chunk_count: usize,
+    /// Hashes which are pointed to, but which have no entry in the store.
+    pub dangling_pointers: Vec<Blake2bHash>,
+    /// Hashes whose stored bytes don't hash to the key they are stored under.
+    pub hash_mismatches: Vec<Blake2bHash>,
+}
+
+impl IntegrityReport {
+    /// Returns `true` if no problems were found.
+    pub fn is_ok(&self) -> bool {
+        self.dangling_pointers.is_empty() && self.hash_mismatches.is_empty()
+    }
+}
+
+/// Walks every trie reachable from `root`, checking that each is present in the store and that
+/// its bytes hash to the key it is stored under.
+///
+/// The tries are visited a level at a time, with each level read from the store in one batch.
+/// The children of a trie whose hash doesn't match are not visited.
+pub fn check_integrity<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+) -> Result<IntegrityReport, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<T::Error> + From<types::bytesrepr::Error>,
+{
+    let mut report = IntegrityReport::default();
+    let mut to_visit: Vec<Blake2bHash> = vec![*root];
+
+    while !to_visit.is_empty() {
+        let hashes = mem::take(&mut to_visit);
+        let keys = hashes
+            .iter()
+            .map(ToBytes::to_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+        let keys: Vec<&[u8]> = keys.iter().map(Vec::as_slice).collect();
+
+        for (hash, maybe_trie_bytes) in hashes
+            .into_iter()
+            .zip(txn.read_many(store.handle(), &keys)?)
+        {
+            let trie_bytes = match maybe_trie_bytes {
+                Some(trie_bytes) => trie_bytes,
+                None => {
+                    report.dangling_pointers.push(hash);
+                    continue;
+                }
+            };
+
+            if Blake2bHash::new(&trie_bytes) != hash {
+                report.hash_mismatches.push(hash);
+                continue;
+            }
+
+            let trie = bytesrepr::deserialize::<Trie<K, V>>(trie_bytes)?;
+            match trie {
+                Trie::Leaf { .. } | Trie::ChunkedLeaf { .. } => report.leaf_count += 1,
+                Trie::Node { .. } | Trie::Extension { .. } => report.node_count += 1,
+                Trie::Chunk { .. } => report.chunk_count += 1,
+            }
+            push_children(&trie, &mut to_visit);
+        }
+    }
+
+    Ok(report)
+}
+
+/// Pushes the hashes of the tries which `trie` points to onto `to_visit`.
+fn push_children<K, V>(trie: &Trie<K, V>, to_visit: &mut Vec<Blake2bHash>) {
+    match trie {
+        Trie::Leaf { .. } | Trie::Chunk { .. } => {}
+        Trie::ChunkedLeaf { chunk_hashes, .. } => to_visit.extend(chunk_hashes.iter().copied()),
+        Trie::Node { pointer_block } => to_visit.extend(
+            pointer_block[..]
+                .iter()
+                .flatten()
+                .map(|pointer| *pointer.hash()),
+        ),
+        Trie::Extension { pointer, .. } => to_visit.push(*pointer.hash()),
+    }
+}
+
+/// The outcome of pruning a trie store.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PruneReport {
+    /// The number of tries kept because they are reachable from a retained root.
+    pub kept: usize,
+    /// The number of tries deleted.
+    pub deleted: usize,
+}
+
+/// Deletes every trie in `store` which isn't reachable from one of `retain_roots`.
+///
+/// Tries shared between a retained root and a pruned one are kept. If any of `retain_roots` is
+/// missing from the store, fails with `RootNotFound` without deleting anything.
+pub fn prune<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    retain_roots: &[Blake2bHash],
+) -> Result<PruneReport, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle> + Deletable,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<T::Error> + From<types::bytesrepr::Error> + From<error::Error>,
+{
+    for root in retain_roots {
+        if txn.read(store.handle(), &root.to_bytes()?)?.is_none() {
+            return Err(error::Error::RootNotFound(*root).into());
+        }
+    }
+
+    let mut reachable: HashSet<Blake2bHash> = HashSet::new();
+    let mut to_visit: Vec<Blake2bHash> = retain_roots.to_vec();
+
+    while !to_visit.is_empty() {
+        let mut hashes = mem::take(&mut to_visit);
+        hashes.sort();
+        hashes.dedup();
+        hashes.retain(|hash| !reachable.contains(hash));
+
+        for (hash, maybe_trie) in hashes.iter().zip(store.get_many(txn, &hashes)?) {
+            let trie: Trie<K, V> = match maybe_trie {
+                Some(trie) => trie,
+                None => continue,
+            };
+            reachable.insert(*hash);
+            push_children(&trie, &mut to_visit);
+        }
+    }
+
+    let mut report = PruneReport::default();
+    for key in txn.keys(store.handle())? {
+        let hash: Blake2bHash = bytesrepr::deserialize(key.clone())?;
+        if reachable.contains(&hash) {
+            report.kept += 1;
+        } else {
+            txn.delete(store.handle(), &key)?;
+            report.deleted += 1;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Writes the trie under `root` to `writer` as a snapshot which can be loaded with
+/// [`import_trie`], returning the number of tries written, or `None` if `root` is not in the
+/// store.
+///
+/// A snapshot is the root hash followed by every trie reachable from it, each written once, in
+/// depth-first order, as a little-endian `u32` length followed by the trie's serialized bytes.
+pub fn export_trie<K, V, T, S, E, W>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    writer: &mut W,
+) -> Result<Option<usize>, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<T::Error> + From<bytesrepr::Error> + From<error::Error>,
+    W: Write,
+{
+    if txn.read(store.handle(), &root.to_bytes()?)?.is_none() {
+        return Ok(None);
+    }
+
+    writer
+        .write_all(&root.to_bytes()?)
+        .map_err(error::Error::from)?;
+
+    let mut written: HashSet<Blake2bHash> = HashSet::new();
+    let mut to_visit: Vec<Blake2bHash> = vec![*root];
+
+    while let Some(hash) = to_visit.pop() {
+        if written.contains(&hash) {
+            continue;
+        }
+        let trie_bytes = match txn.read(store.handle(), &hash.to_bytes()?)? {
+            Some(trie_bytes) => trie_bytes,
+            None => return Err(error::Error::MissingTrie(hash).into()),
+        };
+
+        writer
+            .write_all(&(trie_bytes.len() as u32).to_bytes()?)
+            .and_then(|_| writer.write_all(&trie_bytes))
+            .map_err(error::Error::from)?;
+        written.insert(hash);
+
+        let trie = bytesrepr::deserialize::<Trie<K, V>>(trie_bytes)?;
+        push_children(&trie, &mut to_visit);
+    }
+
+    writer.flush().map_err(error::Error::from)?;
+    Ok(Some(written.len()))
+}
+
+/// Reads a snapshot written by [`export_trie`] from `reader` into `store`, returning the
+/// snapshot's root hash.
+///
+/// Every trie is hashed before it is written, and must be pointed to by a trie read before it.
+/// The whole snapshot must be read without error, and must contain every trie reachable from
+/// its root, otherwise an error is returned; in that case `txn` should be aborted rather than
+/// committed.
+pub fn import_trie<K, V, T, S, E, R>(
+    _correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    reader: &mut R,
+) -> Result<Blake2bHash, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<T::Error> + From<bytesrepr::Error> + From<error::Error>,
+    R: Read,
+{
+    let mut root_bytes = [0u8; 32];
+    reader
+        .read_exact(&mut root_bytes)
+        .map_err(error::Error::from)?;
+    let root = Blake2bHash::from(root_bytes);
+
+    let mut imported: HashSet<Blake2bHash> = HashSet::new();
+    let mut expected: HashSet<Blake2bHash> = HashSet::new();
+    expected.insert(root);
+
+    while let Some(length) = read_frame_length(reader).map_err(error::Error::from)? {
+        let mut trie_bytes = Vec::new();
+        reader
+            .by_ref()
+            .take(u64::from(length))
+            .read_to_end(&mut trie_bytes)
+            .map_err(error::Error::from)?;
+        if trie_bytes.len() != length as usize {
+            return Err(error::Error::Io(io::ErrorKind::UnexpectedEof).into());
+        }
+
+        let hash = Blake2bHash::new(&trie_bytes);
+        if !expected.remove(&hash) {
+            return Err(error::Error::UnexpectedTrie(hash).into());
+        }
+
+        let trie = bytesrepr::deserialize::<Trie<K, V>>(trie_bytes.clone())?;
+        let mut children = Vec::new();
+        push_children(&trie, &mut children);
+        expected.extend(
+            children
+                .into_iter()
+                .filter(|child| !imported.contains(child)),
+        );
+
+        txn.write(store.handle(), &hash.to_bytes()?, &trie_bytes)?;
+        imported.insert(hash);
+    }
+
+    if let Some(missing) = expected.into_iter().next() {
+        return Err(error::Error::MissingTrie(missing).into());
+    }
+
+    Ok(root)
+}
+
+/// Reads the length prefix of the next snapshot frame, or returns `None` if `reader` is exhausted
+/// before the frame starts.
+fn read_frame_length<R: Read>(reader: &mut R) -> io::Result<Option<u32>> {
+    let mut length_bytes = [0u8; 4];
+    let mut filled = 0;
+    while filled < length_bytes.len() {
+        match reader.read(&mut length_bytes[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(read) => filled += read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(Some(u32::from_le_bytes(length_bytes)))
+}
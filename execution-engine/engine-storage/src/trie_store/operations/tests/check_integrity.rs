@@ -0,0 +1,83 @@
+use super::*;
+use crate::trie_store::operations::IntegrityReport;
+
+fn check_integrity<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+) -> Result<IntegrityReport, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let txn = environment.create_read_txn()?;
+    let report = operations::check_integrity::<TestKey, TestValue, _, _, E>(
+        CorrelationId::new(),
+        &txn,
+        store,
+        root_hash,
+    )?;
+    txn.commit()?;
+    Ok(report)
+}
+
+#[test]
+fn lmdb_intact_tries_pass() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+
+        let report =
+            check_integrity::<_, _, error::Error>(&context.environment, &context.store, &root_hash)
+                .unwrap();
+
+        assert!(report.is_ok());
+        assert_eq!(report.leaf_count, num_leaves);
+        assert_eq!(report.node_count + report.leaf_count, tries.len());
+    }
+}
+
+#[test]
+fn lmdb_corrupted_trie_is_reported() {
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[5]().unwrap();
+
+    for HashedTrie { hash, .. } in &tries {
+        let context = LmdbTestContext::new(&tries).unwrap();
+        // store a different trie under the hash
+        context
+            .update(&[HashedTrie {
+                hash: *hash,
+                trie: TEST_LEAVES_UPDATED[0].clone(),
+            }])
+            .unwrap();
+
+        let report =
+            check_integrity::<_, _, error::Error>(&context.environment, &context.store, &root_hash)
+                .unwrap();
+
+        assert!(!report.is_ok());
+        assert_eq!(report.hash_mismatches, vec![*hash]);
+        assert!(report.dangling_pointers.is_empty());
+    }
+}
+
+#[test]
+fn lmdb_missing_trie_is_reported() {
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[5]().unwrap();
+
+    for index in 0..tries.len() {
+        let mut remaining_tries = tries.clone();
+        let HashedTrie { hash: missing, .. } = remaining_tries.remove(index);
+        let context = LmdbTestContext::new(&remaining_tries).unwrap();
+
+        let report =
+            check_integrity::<_, _, error::Error>(&context.environment, &context.store, &root_hash)
+                .unwrap();
+
+        assert!(!report.is_ok());
+        assert_eq!(report.dangling_pointers, vec![missing]);
+        assert!(report.hash_mismatches.is_empty());
+    }
+}
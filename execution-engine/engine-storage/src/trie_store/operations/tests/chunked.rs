@@ -0,0 +1,430 @@
+use super::*;
+use crate::{
+    trie::{operations::create_hashed_empty_trie, validate_proof},
+    trie_store::operations::{
+        check_integrity, delete, read_with_proof, write_with_stats, DeleteResult, WriteStats,
+    },
+};
+
+const CHUNK_THRESHOLD: usize = 1024;
+const LARGE_VALUE_LENGTH: usize = 2 * 1024 * 1024;
+const NAMED_KEYS_LENGTH: usize = 100;
+
+const TEST_KEY_LARGE: TestKey = TestKey([1u8, 0, 0, 0, 0, 0, 0]);
+const TEST_KEY_SMALL: TestKey = TestKey([1u8, 0, 0, 0, 0, 0, 1]);
+
+type ChunkedTestValue = Vec<u8>;
+
+/// Returns a value whose chunks are all distinct, so that none are shared within the value.
+fn test_value(length: usize, seed: u8) -> ChunkedTestValue {
+    (0..length as u64)
+        .map(|i| (i.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 56) as u8 ^ seed)
+        .collect()
+}
+
+/// Returns the number of chunks `value` is expected to be split into.
+fn expected_chunk_count(value: &ChunkedTestValue) -> u64 {
+    let value_length = value.serialized_length();
+    ((value_length + CHUNK_THRESHOLD - 1) / CHUNK_THRESHOLD) as u64
+}
+
+fn create_empty_trie() -> (Blake2bHash, Vec<HashedTrie<TestKey, ChunkedTestValue>>) {
+    let (hash, trie) = create_hashed_empty_trie::<TestKey, ChunkedTestValue>().unwrap();
+    (hash, vec![HashedTrie { hash, trie }])
+}
+
+fn lmdb_context() -> (LmdbTestContext, Blake2bHash) {
+    let (root_hash, tries) = create_empty_trie();
+    let context = LmdbTestContext::new(&tries).unwrap();
+    (context, root_hash)
+}
+
+fn in_memory_context() -> (InMemoryTestContext, Blake2bHash) {
+    let (root_hash, tries) = create_empty_trie();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    (context, root_hash)
+}
+
+/// Writes `value` under `key`, chunking it with a threshold of [`CHUNK_THRESHOLD`].
+fn write_value<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    root: &Blake2bHash,
+    key: &TestKey,
+    value: &ChunkedTestValue,
+) -> Result<(Blake2bHash, WriteStats), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, ChunkedTestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    write_value_with_threshold::<_, _, E>(
+        environment,
+        store,
+        root,
+        key,
+        value,
+        Some(CHUNK_THRESHOLD),
+    )
+}
+
+fn write_value_with_threshold<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    root: &Blake2bHash,
+    key: &TestKey,
+    value: &ChunkedTestValue,
+    chunk_threshold: Option<usize>,
+) -> Result<(Blake2bHash, WriteStats), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, ChunkedTestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut stats = WriteStats::default();
+    let mut txn = environment.create_read_write_txn()?;
+    let new_root = match write_with_stats::<_, _, _, _, E>(
+        CorrelationId::new(),
+        &mut txn,
+        store,
+        root,
+        key,
+        value,
+        chunk_threshold,
+        &mut stats,
+    )? {
+        WriteResult::Written(new_root) => new_root,
+        WriteResult::AlreadyExists => *root,
+        WriteResult::RootNotFound => panic!("write_value given an invalid root"),
+    };
+    txn.commit()?;
+    Ok((new_root, stats))
+}
+
+fn read_value<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    root: &Blake2bHash,
+    key: &TestKey,
+) -> Result<Option<ChunkedTestValue>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, ChunkedTestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let txn = environment.create_read_txn()?;
+    let maybe_value = match read::<_, _, _, _, E>(CorrelationId::new(), &txn, store, root, key)? {
+        ReadResult::Found(value) => Some(value),
+        ReadResult::NotFound => None,
+        ReadResult::RootNotFound => panic!("read_value given an invalid root"),
+    };
+    txn.commit()?;
+    Ok(maybe_value)
+}
+
+fn large_value_round_trips<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    empty_root: &Blake2bHash,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, ChunkedTestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let large_value = test_value(LARGE_VALUE_LENGTH, 0);
+    let small_value = test_value(CHUNK_THRESHOLD / 2, 1);
+
+    let (root, small_stats) = write_value::<_, _, E>(
+        environment,
+        store,
+        empty_root,
+        &TEST_KEY_SMALL,
+        &small_value,
+    )?;
+    assert_eq!(small_stats.chunks_written, 0);
+
+    let (root, large_stats) =
+        write_value::<_, _, E>(environment, store, &root, &TEST_KEY_LARGE, &large_value)?;
+    assert_eq!(large_stats.leaves_written, 1);
+    assert_eq!(
+        large_stats.chunks_written,
+        expected_chunk_count(&large_value)
+    );
+
+    assert_eq!(
+        read_value::<_, _, E>(environment, store, &root, &TEST_KEY_LARGE)?,
+        Some(large_value.clone())
+    );
+    assert_eq!(
+        read_value::<_, _, E>(environment, store, &root, &TEST_KEY_SMALL)?,
+        Some(small_value)
+    );
+
+    let txn = environment.create_read_txn()?;
+    let report = check_integrity::<TestKey, ChunkedTestValue, _, _, E>(
+        CorrelationId::new(),
+        &txn,
+        store,
+        &root,
+    )?;
+    let proof = match read_with_proof::<_, _, _, _, E>(
+        CorrelationId::new(),
+        &txn,
+        store,
+        &root,
+        &TEST_KEY_LARGE,
+    )? {
+        ReadResult::Found((_, proof)) => proof,
+        _ => panic!("should find large value"),
+    };
+    txn.commit()?;
+
+    assert!(report.is_ok());
+    assert_eq!(report.leaf_count, 2);
+    assert_eq!(
+        report.chunk_count as u64,
+        expected_chunk_count(&large_value)
+    );
+
+    assert!(validate_proof(&root, &TEST_KEY_LARGE, &large_value, &proof));
+    let mut tampered_value = large_value;
+    tampered_value[LARGE_VALUE_LENGTH / 2] ^= 1;
+    assert!(!validate_proof(
+        &root,
+        &TEST_KEY_LARGE,
+        &tampered_value,
+        &proof
+    ));
+
+    Ok(())
+}
+
+fn rewriting_suffix_writes_only_last_chunk<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    empty_root: &Blake2bHash,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, ChunkedTestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    // A value laid out like a contract, with its code followed by its named keys.
+    let code = test_value(64 * CHUNK_THRESHOLD, 0);
+    let contract_value = |named_keys_seed: u8| -> ChunkedTestValue {
+        let mut value = code.clone();
+        value.extend(test_value(NAMED_KEYS_LENGTH, named_keys_seed));
+        value
+    };
+    let original_value = contract_value(1);
+    let updated_value = contract_value(2);
+
+    let (root, original_stats) = write_value::<_, _, E>(
+        environment,
+        store,
+        empty_root,
+        &TEST_KEY_LARGE,
+        &original_value,
+    )?;
+    assert_eq!(
+        original_stats.chunks_written,
+        expected_chunk_count(&original_value)
+    );
+
+    let (root, updated_stats) =
+        write_value::<_, _, E>(environment, store, &root, &TEST_KEY_LARGE, &updated_value)?;
+    assert_eq!(updated_stats.leaves_written, 1);
+    assert_eq!(updated_stats.chunks_written, 1);
+
+    assert_eq!(
+        read_value::<_, _, E>(environment, store, &root, &TEST_KEY_LARGE)?,
+        Some(updated_value.clone())
+    );
+
+    let (unchanged_root, unchanged_stats) =
+        write_value::<_, _, E>(environment, store, &root, &TEST_KEY_LARGE, &updated_value)?;
+    assert_eq!(unchanged_root, root);
+    assert_eq!(unchanged_stats, WriteStats::default());
+
+    Ok(())
+}
+
+fn deleting_large_value_restores_root<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    empty_root: &Blake2bHash,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, ChunkedTestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let small_value = test_value(CHUNK_THRESHOLD / 2, 1);
+    let (small_root, _) = write_value::<_, _, E>(
+        environment,
+        store,
+        empty_root,
+        &TEST_KEY_SMALL,
+        &small_value,
+    )?;
+    let (large_root, _) = write_value::<_, _, E>(
+        environment,
+        store,
+        &small_root,
+        &TEST_KEY_LARGE,
+        &test_value(LARGE_VALUE_LENGTH, 0),
+    )?;
+
+    let mut txn = environment.create_read_write_txn()?;
+    let delete_result = delete::<TestKey, ChunkedTestValue, _, _, E>(
+        CorrelationId::new(),
+        &mut txn,
+        store,
+        &large_root,
+        &TEST_KEY_LARGE,
+    )?;
+    txn.commit()?;
+
+    assert_eq!(delete_result, DeleteResult::Deleted(small_root));
+    Ok(())
+}
+
+#[test]
+fn lmdb_large_value_round_trips() {
+    let (context, empty_root) = lmdb_context();
+    large_value_round_trips::<_, _, error::Error>(
+        &context.environment,
+        &context.store,
+        &empty_root,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_large_value_round_trips() {
+    let (context, empty_root) = in_memory_context();
+    large_value_round_trips::<_, _, in_memory::Error>(
+        &context.environment,
+        &context.store,
+        &empty_root,
+    )
+    .unwrap();
+}
+
+#[test]
+fn lmdb_rewriting_suffix_writes_only_last_chunk() {
+    let (context, empty_root) = lmdb_context();
+    rewriting_suffix_writes_only_last_chunk::<_, _, error::Error>(
+        &context.environment,
+        &context.store,
+        &empty_root,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_rewriting_suffix_writes_only_last_chunk() {
+    let (context, empty_root) = in_memory_context();
+    rewriting_suffix_writes_only_last_chunk::<_, _, in_memory::Error>(
+        &context.environment,
+        &context.store,
+        &empty_root,
+    )
+    .unwrap();
+}
+
+#[test]
+fn lmdb_deleting_large_value_restores_root() {
+    let (context, empty_root) = lmdb_context();
+    deleting_large_value_restores_root::<_, _, error::Error>(
+        &context.environment,
+        &context.store,
+        &empty_root,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_deleting_large_value_restores_root() {
+    let (context, empty_root) = in_memory_context();
+    deleting_large_value_restores_root::<_, _, in_memory::Error>(
+        &context.environment,
+        &context.store,
+        &empty_root,
+    )
+    .unwrap();
+}
+
+#[test]
+fn chunked_root_hashes_match_across_stores() {
+    let (lmdb, lmdb_root) = lmdb_context();
+    let (in_memory, in_memory_root) = in_memory_context();
+    let (unchunked, unchunked_root) = in_memory_context();
+    assert_eq!(lmdb_root, in_memory_root);
+
+    let small_value = test_value(CHUNK_THRESHOLD / 2, 1);
+    let (lmdb_root, _) = write_value::<_, _, error::Error>(
+        &lmdb.environment,
+        &lmdb.store,
+        &lmdb_root,
+        &TEST_KEY_SMALL,
+        &small_value,
+    )
+    .unwrap();
+    let (in_memory_root, _) = write_value::<_, _, in_memory::Error>(
+        &in_memory.environment,
+        &in_memory.store,
+        &in_memory_root,
+        &TEST_KEY_SMALL,
+        &small_value,
+    )
+    .unwrap();
+    let (unchunked_root, _) = write_value_with_threshold::<_, _, in_memory::Error>(
+        &unchunked.environment,
+        &unchunked.store,
+        &unchunked_root,
+        &TEST_KEY_SMALL,
+        &small_value,
+        None,
+    )
+    .unwrap();
+    // Values no longer than the threshold are stored as they would be without chunking.
+    assert_eq!(lmdb_root, in_memory_root);
+    assert_eq!(in_memory_root, unchunked_root);
+
+    let large_value = test_value(LARGE_VALUE_LENGTH, 0);
+    let (lmdb_root, _) = write_value::<_, _, error::Error>(
+        &lmdb.environment,
+        &lmdb.store,
+        &lmdb_root,
+        &TEST_KEY_LARGE,
+        &large_value,
+    )
+    .unwrap();
+    let (in_memory_root, _) = write_value::<_, _, in_memory::Error>(
+        &in_memory.environment,
+        &in_memory.store,
+        &in_memory_root,
+        &TEST_KEY_LARGE,
+        &large_value,
+    )
+    .unwrap();
+    let (unchunked_root, _) = write_value_with_threshold::<_, _, in_memory::Error>(
+        &unchunked.environment,
+        &unchunked.store,
+        &unchunked_root,
+        &TEST_KEY_LARGE,
+        &large_value,
+        None,
+    )
+    .unwrap();
+    assert_eq!(lmdb_root, in_memory_root);
+    assert_ne!(in_memory_root, unchunked_root);
+}
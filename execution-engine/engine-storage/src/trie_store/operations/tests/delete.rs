@@ -0,0 +1,177 @@
+use super::*;
+use crate::trie_store::operations::DeleteResult;
+
+fn delete_from_n_leaf_trie_yields_n_minus_one_leaf_trie<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    num_leaves: usize,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let (root_hash, _) = TEST_TRIE_GENERATORS[num_leaves]()?;
+    let (expected_root_hash, _) = TEST_TRIE_GENERATORS[num_leaves - 1]()?;
+    let key = TEST_LEAVES[num_leaves - 1].key().unwrap();
+
+    let mut txn = environment.create_read_write_txn()?;
+    let result = operations::delete::<TestKey, TestValue, _, _, E>(
+        correlation_id,
+        &mut txn,
+        store,
+        &root_hash,
+        key,
+    )?;
+    txn.commit()?;
+
+    assert_eq!(result, DeleteResult::Deleted(expected_root_hash));
+    Ok(())
+}
+
+fn delete_all_leaves_yields_empty_trie<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    num_leaves: usize,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let (mut root_hash, _) = TEST_TRIE_GENERATORS[num_leaves]()?;
+    let (empty_root_hash, _) = TEST_TRIE_GENERATORS[0]()?;
+
+    let mut txn = environment.create_read_write_txn()?;
+    // delete in insertion order, which differs from the reverse order used to build the tries
+    for leaf in &TEST_LEAVES[..num_leaves] {
+        match operations::delete::<TestKey, TestValue, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            &root_hash,
+            leaf.key().unwrap(),
+        )? {
+            DeleteResult::Deleted(new_root_hash) => root_hash = new_root_hash,
+            other => panic!("unexpected delete result: {:?}", other),
+        }
+    }
+    txn.commit()?;
+
+    assert_eq!(root_hash, empty_root_hash);
+    Ok(())
+}
+
+mod partial_tries {
+    use super::*;
+
+    #[test]
+    fn lmdb_delete_from_n_leaf_trie_yields_n_minus_one_leaf_trie() {
+        let correlation_id = CorrelationId::new();
+        for num_leaves in 1..TEST_TRIE_GENERATORS_LENGTH {
+            let (_, tries) = TEST_TRIE_GENERATORS[num_leaves]().unwrap();
+            let context = LmdbTestContext::new(&tries).unwrap();
+            delete_from_n_leaf_trie_yields_n_minus_one_leaf_trie::<_, _, error::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                num_leaves,
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn in_memory_delete_from_n_leaf_trie_yields_n_minus_one_leaf_trie() {
+        let correlation_id = CorrelationId::new();
+        for num_leaves in 1..TEST_TRIE_GENERATORS_LENGTH {
+            let (_, tries) = TEST_TRIE_GENERATORS[num_leaves]().unwrap();
+            let context = InMemoryTestContext::new(&tries).unwrap();
+            delete_from_n_leaf_trie_yields_n_minus_one_leaf_trie::<_, _, in_memory::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                num_leaves,
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn lmdb_delete_all_leaves_yields_empty_trie() {
+        let correlation_id = CorrelationId::new();
+        for num_leaves in 1..TEST_TRIE_GENERATORS_LENGTH {
+            let (_, tries) = TEST_TRIE_GENERATORS[num_leaves]().unwrap();
+            let context = LmdbTestContext::new(&tries).unwrap();
+            delete_all_leaves_yields_empty_trie::<_, _, error::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                num_leaves,
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn in_memory_delete_all_leaves_yields_empty_trie() {
+        let correlation_id = CorrelationId::new();
+        for num_leaves in 1..TEST_TRIE_GENERATORS_LENGTH {
+            let (_, tries) = TEST_TRIE_GENERATORS[num_leaves]().unwrap();
+            let context = InMemoryTestContext::new(&tries).unwrap();
+            delete_all_leaves_yields_empty_trie::<_, _, in_memory::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                num_leaves,
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn in_memory_delete_missing_key_does_not_exist() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = TEST_TRIE_GENERATORS[3]().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        let missing_key = TEST_LEAVES[4].key().unwrap();
+
+        let mut txn = context.environment.create_read_write_txn().unwrap();
+        let result = operations::delete::<TestKey, TestValue, _, _, in_memory::Error>(
+            correlation_id,
+            &mut txn,
+            &context.store,
+            &root_hash,
+            missing_key,
+        )
+        .unwrap();
+        txn.commit().unwrap();
+
+        assert_eq!(result, DeleteResult::DoesNotExist);
+    }
+
+    #[test]
+    fn in_memory_delete_with_missing_root_is_root_not_found() {
+        let correlation_id = CorrelationId::new();
+        let (_, tries) = TEST_TRIE_GENERATORS[1]().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        let missing_root_hash = Blake2bHash::new(&[1u8; 32]);
+
+        let mut txn = context.environment.create_read_write_txn().unwrap();
+        let result = operations::delete::<TestKey, TestValue, _, _, in_memory::Error>(
+            correlation_id,
+            &mut txn,
+            &context.store,
+            &missing_root_hash,
+            TEST_LEAVES[0].key().unwrap(),
+        )
+        .unwrap();
+        txn.commit().unwrap();
+
+        assert_eq!(result, DeleteResult::RootNotFound);
+    }
+}
@@ -1,8 +1,14 @@
+mod check_integrity;
+mod chunked;
+mod delete;
 mod keys;
+mod proof;
 mod proptests;
 mod read;
 mod scan;
+mod snapshot;
 mod write;
+mod write_batch;
 
 use std::{collections::HashMap, convert};
 
@@ -693,8 +699,15 @@ where
 
     for leaf in leaves.iter() {
         if let Trie::Leaf { key, value } = leaf {
-            let write_result =
-                write::<_, _, _, _, E>(correlation_id, &mut txn, store, &root_hash, key, value)?;
+            let write_result = write::<_, _, _, _, E>(
+                correlation_id,
+                &mut txn,
+                store,
+                &root_hash,
+                key,
+                value,
+                None,
+            )?;
             match write_result {
                 WriteResult::Written(hash) => {
                     root_hash = hash;
@@ -780,7 +793,15 @@ where
     let mut txn = environment.create_read_write_txn()?;
 
     for (key, value) in pairs.iter() {
-        match write::<_, _, _, _, E>(correlation_id, &mut txn, store, &root_hash, key, value)? {
+        match write::<_, _, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            &root_hash,
+            key,
+            value,
+            None,
+        )? {
             WriteResult::Written(hash) => {
                 root_hash = hash;
             }
@@ -0,0 +1,151 @@
+//! This module contains tests for [`read_with_proof`](operations::read_with_proof) and
+//! [`validate_proof`](trie::validate_proof).
+//!
+//! Proofs are produced for each of the leaves in the well-known partial test tries, and checked
+//! against the root they were read from.  Tampering with any part of a proof, or checking it
+//! against a different root, must cause validation to fail.
+
+use super::*;
+use crate::{
+    error::{self, in_memory},
+    trie::{self, PointerBlock, TrieMerkleProof},
+};
+
+fn read_proofs<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root: &Blake2bHash,
+    leaves: &[TestTrie],
+) -> Result<Vec<(TestKey, TestValue, TrieMerkleProof<TestKey, TestValue>)>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let txn: R::ReadTransaction = environment.create_read_txn()?;
+    let mut ret = Vec::new();
+
+    for leaf in leaves {
+        if let Trie::Leaf { key, value } = leaf {
+            match operations::read_with_proof::<_, _, _, _, E>(
+                correlation_id,
+                &txn,
+                store,
+                root,
+                key,
+            )? {
+                ReadResult::Found((found_value, proof)) => {
+                    assert_eq!(*value, found_value);
+                    ret.push((*key, *value, proof));
+                }
+                _ => panic!("leaf should be found"),
+            }
+        } else {
+            panic!("leaves should only contain leaves")
+        }
+    }
+
+    txn.commit()?;
+    Ok(ret)
+}
+
+fn check_proofs(
+    root: &Blake2bHash,
+    proofs: &[(TestKey, TestValue, TrieMerkleProof<TestKey, TestValue>)],
+) {
+    let wrong_root = Blake2bHash::new(&[1u8; 32]);
+    let wrong_value = TestValue(*b"wrong!");
+
+    for (key, value, proof) in proofs {
+        assert!(trie::validate_proof(root, key, value, proof));
+        assert!(!trie::validate_proof(&wrong_root, key, value, proof));
+        assert!(!trie::validate_proof(root, key, &wrong_value, proof));
+        assert!(!trie::validate_proof(root, key, value, &proof[1..]));
+        assert!(!trie::validate_proof(root, key, value, &[]));
+
+        let mut tampered_leaf = proof.clone();
+        if let Some(Trie::Leaf { value, .. }) = tampered_leaf.last_mut() {
+            *value = wrong_value;
+        }
+        assert!(!trie::validate_proof(root, key, value, &tampered_leaf));
+        assert!(!trie::validate_proof(
+            root,
+            key,
+            &wrong_value,
+            &tampered_leaf
+        ));
+
+        let mut tampered_root = proof.clone();
+        tampered_root[0] = Trie::Node {
+            pointer_block: Box::new(PointerBlock::new()),
+        };
+        assert!(!trie::validate_proof(root, key, value, &tampered_root));
+    }
+}
+
+#[test]
+fn lmdb_proofs_from_n_leaf_partial_trie_are_valid() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+        let test_leaves = TEST_LEAVES;
+        let (used, _) = test_leaves.split_at(num_leaves);
+
+        let proofs = read_proofs::<_, _, error::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            used,
+        )
+        .unwrap();
+        check_proofs(&root_hash, &proofs);
+    }
+}
+
+#[test]
+fn in_memory_proofs_from_n_leaf_partial_trie_are_valid() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        let test_leaves = TEST_LEAVES;
+        let (used, _) = test_leaves.split_at(num_leaves);
+
+        let proofs = read_proofs::<_, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            used,
+        )
+        .unwrap();
+        check_proofs(&root_hash, &proofs);
+    }
+}
+
+#[test]
+fn proof_is_not_returned_for_absent_leaves() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[2]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let txn = context.environment.create_read_txn().unwrap();
+
+    for leaf in &TEST_LEAVES[2..] {
+        let key = leaf.key().unwrap();
+        let result = operations::read_with_proof::<TestKey, TestValue, _, _, in_memory::Error>(
+            correlation_id,
+            &txn,
+            &context.store,
+            &root_hash,
+            key,
+        )
+        .unwrap();
+        assert_eq!(result, ReadResult::NotFound);
+    }
+
+    txn.commit().unwrap();
+}
@@ -1,4 +1,7 @@
-use std::ops::RangeInclusive;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+};
 
 use proptest::{
     array,
@@ -7,6 +10,7 @@ use proptest::{
 };
 
 use super::*;
+use crate::trie_store::operations::DeleteResult;
 
 const DEFAULT_MIN_LENGTH: usize = 0;
 
@@ -76,10 +80,161 @@ fn in_memory_roundtrip_succeeds(pairs: &[(TestKey, TestValue)]) -> bool {
     .unwrap()
 }
 
+fn keys_with_prefix_succeeds<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    pairs: &[(TestKey, TestValue)],
+    extra_prefix: &[u8],
+) -> Result<bool, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error> + From<types::bytesrepr::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let correlation_id = CorrelationId::new();
+    let (empty_root_hash, _) = TEST_TRIE_GENERATORS[0]()?;
+    let root_hashes =
+        write_pairs::<_, _, _, _, E>(correlation_id, environment, store, &empty_root_hash, pairs)?;
+    let root_hash = root_hashes.last().unwrap_or(&empty_root_hash);
+
+    let inserted_keys: BTreeSet<TestKey> = pairs.iter().map(|(key, _)| *key).collect();
+    let mut prefixes: BTreeSet<Vec<u8>> = BTreeSet::new();
+    prefixes.insert(extra_prefix.to_vec());
+    for key in &inserted_keys {
+        for prefix_length in 0..=TEST_KEY_LENGTH {
+            prefixes.insert(key.0[..prefix_length].to_vec());
+        }
+    }
+
+    let txn: R::ReadTransaction = environment.create_read_txn()?;
+    let mut ret = true;
+    for prefix in &prefixes {
+        let expected: Vec<TestKey> = inserted_keys
+            .iter()
+            .filter(|key| key.0.starts_with(prefix))
+            .cloned()
+            .collect();
+        let mut actual = operations::keys_with_prefix::<TestKey, TestValue, _, _>(
+            correlation_id,
+            &txn,
+            store,
+            root_hash,
+            prefix,
+        )
+        .collect::<Result<Vec<TestKey>, S::Error>>()?;
+        actual.sort();
+        ret &= expected == actual;
+    }
+    txn.commit()?;
+    Ok(ret)
+}
+
+fn lmdb_keys_with_prefix_succeeds(pairs: &[(TestKey, TestValue)], extra_prefix: &[u8]) -> bool {
+    let (_, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+    keys_with_prefix_succeeds::<_, _, error::Error>(
+        &context.environment,
+        &context.store,
+        pairs,
+        extra_prefix,
+    )
+    .unwrap()
+}
+
+fn in_memory_keys_with_prefix_succeeds(
+    pairs: &[(TestKey, TestValue)],
+    extra_prefix: &[u8],
+) -> bool {
+    let (_, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    keys_with_prefix_succeeds::<_, _, in_memory::Error>(
+        &context.environment,
+        &context.store,
+        pairs,
+        extra_prefix,
+    )
+    .unwrap()
+}
+
+/// Writes `pairs` one at a time, then deletes them in reverse order, checking that each delete
+/// yields the root hash from before the corresponding write.
+fn insert_then_delete_succeeds<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    pairs: &[(TestKey, TestValue)],
+) -> Result<bool, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let correlation_id = CorrelationId::new();
+    let (empty_root_hash, _) = TEST_TRIE_GENERATORS[0]()?;
+    let pairs: Vec<(TestKey, TestValue)> = pairs
+        .iter()
+        .cloned()
+        .collect::<BTreeMap<TestKey, TestValue>>()
+        .into_iter()
+        .collect();
+
+    let mut expected_root_hashes = vec![empty_root_hash];
+    expected_root_hashes.extend(write_pairs::<_, _, _, _, E>(
+        correlation_id,
+        environment,
+        store,
+        &empty_root_hash,
+        &pairs,
+    )?);
+
+    let mut txn = environment.create_read_write_txn()?;
+    let mut root_hash = *expected_root_hashes.last().unwrap();
+    let mut ret = true;
+    for (index, (key, _)) in pairs.iter().enumerate().rev() {
+        match operations::delete::<TestKey, TestValue, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            &root_hash,
+            key,
+        )? {
+            DeleteResult::Deleted(new_root_hash) => root_hash = new_root_hash,
+            _ => return Ok(false),
+        }
+        ret &= root_hash == expected_root_hashes[index];
+    }
+    txn.commit()?;
+    Ok(ret)
+}
+
+fn lmdb_insert_then_delete_succeeds(pairs: &[(TestKey, TestValue)]) -> bool {
+    let (_, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+    insert_then_delete_succeeds::<_, _, error::Error>(&context.environment, &context.store, pairs)
+        .unwrap()
+}
+
+fn in_memory_insert_then_delete_succeeds(pairs: &[(TestKey, TestValue)]) -> bool {
+    let (_, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    insert_then_delete_succeeds::<_, _, in_memory::Error>(
+        &context.environment,
+        &context.store,
+        pairs,
+    )
+    .unwrap()
+}
+
 fn test_key_arb() -> impl Strategy<Value = TestKey> {
     array::uniform7(any::<u8>()).prop_map(TestKey)
 }
 
+/// Keys drawn from a small alphabet, so that they share prefixes and exercise extensions.
+fn colliding_test_key_arb() -> impl Strategy<Value = TestKey> {
+    array::uniform7(0u8..3).prop_map(TestKey)
+}
+
 fn test_value_arb() -> impl Strategy<Value = TestValue> {
     array::uniform6(any::<u8>()).prop_map(TestValue)
 }
@@ -94,4 +249,34 @@ proptest! {
     fn prop_lmdb_roundtrip_succeeds(inputs in vec((test_key_arb(), test_value_arb()), get_range())) {
         assert!(lmdb_roundtrip_succeeds(&inputs));
     }
+
+    #[test]
+    fn prop_in_memory_keys_with_prefix_succeeds(
+        inputs in vec((test_key_arb(), test_value_arb()), get_range()),
+        prefix in vec(any::<u8>(), 0..=TEST_KEY_LENGTH),
+    ) {
+        assert!(in_memory_keys_with_prefix_succeeds(&inputs, &prefix));
+    }
+
+    #[test]
+    fn prop_lmdb_keys_with_prefix_succeeds(
+        inputs in vec((test_key_arb(), test_value_arb()), get_range()),
+        prefix in vec(any::<u8>(), 0..=TEST_KEY_LENGTH),
+    ) {
+        assert!(lmdb_keys_with_prefix_succeeds(&inputs, &prefix));
+    }
+
+    #[test]
+    fn prop_in_memory_insert_then_delete_succeeds(
+        inputs in vec((colliding_test_key_arb(), test_value_arb()), get_range()),
+    ) {
+        assert!(in_memory_insert_then_delete_succeeds(&inputs));
+    }
+
+    #[test]
+    fn prop_lmdb_insert_then_delete_succeeds(
+        inputs in vec((colliding_test_key_arb(), test_value_arb()), get_range()),
+    ) {
+        assert!(lmdb_insert_then_delete_succeeds(&inputs));
+    }
 }
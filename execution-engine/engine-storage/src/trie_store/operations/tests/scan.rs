@@ -37,7 +37,9 @@ where
             Blake2bHash::new(&tip_bytes)
         };
         match parent {
-            Trie::Leaf { .. } => panic!("parents should not contain any leaves"),
+            Trie::Leaf { .. } | Trie::ChunkedLeaf { .. } | Trie::Chunk { .. } => {
+                panic!("parents should not contain any leaves or chunks")
+            }
             Trie::Node { pointer_block } => {
                 let pointer_tip_hash = pointer_block[<usize>::from(index)].map(|ptr| *ptr.hash());
                 assert_eq!(Some(expected_tip_hash), pointer_tip_hash);
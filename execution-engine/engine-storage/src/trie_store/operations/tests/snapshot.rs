@@ -0,0 +1,135 @@
+use super::*;
+use crate::store::Store;
+
+fn export_from_lmdb(context: &LmdbTestContext, root_hash: &Blake2bHash) -> Option<Vec<u8>> {
+    let txn = context.environment.create_read_txn().unwrap();
+    let mut snapshot = Vec::new();
+    let written = operations::export_trie::<TestKey, TestValue, _, _, error::Error, _>(
+        CorrelationId::new(),
+        &txn,
+        &context.store,
+        root_hash,
+        &mut snapshot,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    written.map(|_| snapshot)
+}
+
+fn import_into_in_memory(
+    context: &InMemoryTestContext,
+    snapshot: &[u8],
+) -> Result<Blake2bHash, error::Error> {
+    let mut txn = context.environment.create_read_write_txn()?;
+    let root_hash = operations::import_trie::<TestKey, TestValue, _, _, error::Error, _>(
+        CorrelationId::new(),
+        &mut txn,
+        &context.store,
+        &mut &snapshot[..],
+    )?;
+    txn.commit()?;
+    Ok(root_hash)
+}
+
+fn in_memory_is_empty(context: &InMemoryTestContext, hashes: &[HashedTestTrie]) -> bool {
+    let txn = context.environment.create_read_txn().unwrap();
+    let is_empty = hashes.iter().all(|HashedTrie { hash, .. }| {
+        let maybe_trie: Option<TestTrie> = context.store.get(&txn, hash).unwrap();
+        maybe_trie.is_none()
+    });
+    txn.commit().unwrap();
+    is_empty
+}
+
+#[test]
+fn lmdb_export_imports_into_in_memory() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let (root_hash, tries) = generator().unwrap();
+        let source = LmdbTestContext::new(&tries).unwrap();
+        let target = InMemoryTestContext::new(EMPTY_HASHED_TEST_TRIES).unwrap();
+
+        let snapshot = export_from_lmdb(&source, &root_hash).unwrap();
+        let imported_root = import_into_in_memory(&target, &snapshot).unwrap();
+        assert_eq!(imported_root, root_hash);
+
+        let txn = target.environment.create_read_txn().unwrap();
+        let leaves_exist = check_leaves_exist::<_, _, _, _, in_memory::Error>(
+            CorrelationId::new(),
+            &txn,
+            &target.store,
+            &root_hash,
+            &TEST_LEAVES[..num_leaves],
+        )
+        .unwrap();
+        txn.commit().unwrap();
+        assert!(leaves_exist.into_iter().all(convert::identity));
+    }
+}
+
+#[test]
+fn lmdb_export_of_missing_root_writes_nothing() {
+    let (_, tries) = TEST_TRIE_GENERATORS[5]().unwrap();
+    let source = LmdbTestContext::new(&tries).unwrap();
+    let missing_root: Blake2bHash = [1u8; 32].into();
+
+    assert!(export_from_lmdb(&source, &missing_root).is_none());
+}
+
+#[test]
+fn in_memory_import_rejects_corrupted_snapshot() {
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[5]().unwrap();
+    let source = LmdbTestContext::new(&tries).unwrap();
+    let snapshot = export_from_lmdb(&source, &root_hash).unwrap();
+
+    // flip a byte in the last trie of the snapshot
+    let mut corrupted = snapshot;
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 0xff;
+
+    let target = InMemoryTestContext::new(EMPTY_HASHED_TEST_TRIES).unwrap();
+    match import_into_in_memory(&target, &corrupted) {
+        Err(error::Error::UnexpectedTrie(_)) => {}
+        other => panic!("unexpected import result: {:?}", other),
+    }
+    assert!(in_memory_is_empty(&target, &tries));
+}
+
+/// Returns the offset at which each frame of `snapshot` ends.
+fn frame_ends(snapshot: &[u8]) -> Vec<usize> {
+    let mut ends = Vec::new();
+    let mut offset = 32;
+    while offset < snapshot.len() {
+        let mut length_bytes = [0u8; 4];
+        length_bytes.copy_from_slice(&snapshot[offset..offset + 4]);
+        offset += 4 + u32::from_le_bytes(length_bytes) as usize;
+        ends.push(offset);
+    }
+    ends
+}
+
+#[test]
+fn in_memory_import_rejects_truncated_snapshot() {
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[5]().unwrap();
+    let source = LmdbTestContext::new(&tries).unwrap();
+    let snapshot = export_from_lmdb(&source, &root_hash).unwrap();
+    let ends = frame_ends(&snapshot);
+    assert_eq!(ends.len(), tries.len());
+
+    // drop the last trie
+    let target = InMemoryTestContext::new(EMPTY_HASHED_TEST_TRIES).unwrap();
+    let without_last_trie = &snapshot[..ends[ends.len() - 2]];
+    match import_into_in_memory(&target, without_last_trie) {
+        Err(error::Error::MissingTrie(_)) => {}
+        other => panic!("unexpected import result: {:?}", other),
+    }
+    assert!(in_memory_is_empty(&target, &tries));
+
+    // cut the last trie short
+    let target = InMemoryTestContext::new(EMPTY_HASHED_TEST_TRIES).unwrap();
+    let partial_last_trie = &snapshot[..snapshot.len() - 1];
+    assert_eq!(
+        import_into_in_memory(&target, partial_last_trie),
+        Err(error::Error::Io(std::io::ErrorKind::UnexpectedEof))
+    );
+    assert!(in_memory_is_empty(&target, &tries));
+}
@@ -0,0 +1,307 @@
+use std::collections::BTreeMap;
+
+use proptest::{array, collection::vec, prelude::*};
+
+use super::*;
+use crate::{
+    store::Store,
+    transaction_source::write_buffer::WriteBuffer,
+    trie_store::operations::{put_reachable, write_with_stats, WriteStats},
+};
+
+/// Writes `pairs` one at a time against a [`WriteBuffer`], then puts the reachable tries into
+/// `store` in a single read-write transaction.  Returns the new root hash and the number of tries
+/// put.
+fn write_batch<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    pairs: &[(TestKey, TestValue)],
+) -> Result<(Blake2bHash, usize), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Handle: PartialEq,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let correlation_id = CorrelationId::new();
+    let read_txn = environment.create_read_txn()?;
+    let mut buffer = WriteBuffer::new(&read_txn, store.handle());
+    let mut root_hash = *root_hash;
+    for (key, value) in pairs {
+        match write::<_, _, _, _, E>(
+            correlation_id,
+            &mut buffer,
+            store,
+            &root_hash,
+            key,
+            value,
+            None,
+        )? {
+            WriteResult::Written(hash) => root_hash = hash,
+            WriteResult::AlreadyExists => (),
+            WriteResult::RootNotFound => panic!("write_batch given an invalid root"),
+        }
+    }
+    let writes = buffer.into_writes();
+    read_txn.commit()?;
+
+    let mut txn = environment.create_read_write_txn()?;
+    let put_count = put_reachable::<TestKey, TestValue, _, _, E>(
+        &mut txn,
+        store,
+        &root_hash,
+        writes,
+        &mut WriteStats::default(),
+    )?
+    .expect("tries written on should be in the store");
+    txn.commit()?;
+    Ok((root_hash, put_count))
+}
+
+/// Writes `pairs` one at a time, each putting its tries into `store` directly.  Returns the new
+/// root hash and the number of tries put.
+fn write_sequentially<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    pairs: &[(TestKey, TestValue)],
+) -> Result<(Blake2bHash, u64), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let correlation_id = CorrelationId::new();
+    let mut txn = environment.create_read_write_txn()?;
+    let mut stats = WriteStats::default();
+    let mut root_hash = *root_hash;
+    for (key, value) in pairs {
+        match write_with_stats::<_, _, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            &root_hash,
+            key,
+            value,
+            None,
+            &mut stats,
+        )? {
+            WriteResult::Written(hash) => root_hash = hash,
+            WriteResult::AlreadyExists => (),
+            WriteResult::RootNotFound => panic!("write_sequentially given an invalid root"),
+        }
+    }
+    txn.commit()?;
+    let put_count = stats.leaves_written + stats.nodes_written + stats.chunks_written;
+    Ok((root_hash, put_count))
+}
+
+fn batch_matches_sequential_writes<'a, R, S, E>(
+    batch_environment: &'a R,
+    batch_store: &S,
+    sequential_environment: &'a R,
+    sequential_store: &S,
+    pairs: &[(TestKey, TestValue)],
+) -> Result<bool, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Handle: PartialEq,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let correlation_id = CorrelationId::new();
+    let (empty_root_hash, _) = TEST_TRIE_GENERATORS[0]()?;
+
+    let (batch_root_hash, _) =
+        write_batch::<_, _, E>(batch_environment, batch_store, &empty_root_hash, pairs)?;
+    let (sequential_root_hash, _) = write_sequentially::<_, _, E>(
+        sequential_environment,
+        sequential_store,
+        &empty_root_hash,
+        pairs,
+    )?;
+    if batch_root_hash != sequential_root_hash {
+        return Ok(false);
+    }
+
+    // Every trie under the new root must have been put, even though superseded ones weren't.
+    let txn = batch_environment.create_read_txn()?;
+    let report = operations::check_integrity::<TestKey, TestValue, _, _, E>(
+        correlation_id,
+        &txn,
+        batch_store,
+        &batch_root_hash,
+    )?;
+    txn.commit()?;
+    if !report.is_ok() {
+        return Ok(false);
+    }
+
+    // Later writes to a key win, as they do when writing sequentially.
+    let expected: BTreeMap<TestKey, TestValue> = pairs.iter().cloned().collect();
+    let txn = batch_environment.create_read_txn()?;
+    for (key, value) in &expected {
+        let result =
+            read::<_, _, _, _, E>(correlation_id, &txn, batch_store, &batch_root_hash, key)?;
+        if result != ReadResult::Found(*value) {
+            return Ok(false);
+        }
+    }
+    txn.commit()?;
+    Ok(true)
+}
+
+fn in_memory_batch_matches_sequential_writes(pairs: &[(TestKey, TestValue)]) -> bool {
+    let (_, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let batch_context = InMemoryTestContext::new(&tries).unwrap();
+    let sequential_context = InMemoryTestContext::new(&tries).unwrap();
+    batch_matches_sequential_writes::<_, _, in_memory::Error>(
+        &batch_context.environment,
+        &batch_context.store,
+        &sequential_context.environment,
+        &sequential_context.store,
+        pairs,
+    )
+    .unwrap()
+}
+
+fn lmdb_batch_matches_sequential_writes(pairs: &[(TestKey, TestValue)]) -> bool {
+    let (_, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let batch_context = LmdbTestContext::new(&tries).unwrap();
+    let sequential_context = LmdbTestContext::new(&tries).unwrap();
+    batch_matches_sequential_writes::<_, _, error::Error>(
+        &batch_context.environment,
+        &batch_context.store,
+        &sequential_context.environment,
+        &sequential_context.store,
+        pairs,
+    )
+    .unwrap()
+}
+
+fn test_key_arb() -> impl Strategy<Value = TestKey> {
+    array::uniform7(0u8..4).prop_map(TestKey)
+}
+
+fn test_value_arb() -> impl Strategy<Value = TestValue> {
+    array::uniform6(any::<u8>()).prop_map(TestValue)
+}
+
+proptest! {
+    #[test]
+    fn prop_in_memory_batch_matches_sequential_writes(
+        inputs in vec((test_key_arb(), test_value_arb()), 0..100),
+    ) {
+        assert!(in_memory_batch_matches_sequential_writes(&inputs));
+    }
+
+    #[test]
+    fn prop_lmdb_batch_matches_sequential_writes(
+        inputs in vec((test_key_arb(), test_value_arb()), 0..100),
+    ) {
+        assert!(lmdb_batch_matches_sequential_writes(&inputs));
+    }
+}
+
+#[test]
+fn batch_of_10k_writes_puts_fewer_tries_than_sequential_writes() {
+    const KEY_COUNT: u32 = 10_000;
+
+    let pairs: Vec<(TestKey, TestValue)> = (0..KEY_COUNT)
+        .map(|index| {
+            // Spread the keys over the whole trie rather than filling it in order.
+            let hash = Blake2bHash::new(&index.to_le_bytes());
+            let mut key = [0; TEST_KEY_LENGTH];
+            key.copy_from_slice(&hash.value()[..TEST_KEY_LENGTH]);
+            let mut value = [0; TEST_VAL_LENGTH];
+            value[..4].copy_from_slice(&index.to_le_bytes());
+            (TestKey(key), TestValue(value))
+        })
+        .collect();
+
+    let (empty_root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let batch_context = InMemoryTestContext::new(&tries).unwrap();
+    let sequential_context = InMemoryTestContext::new(&tries).unwrap();
+
+    let (batch_root_hash, batch_puts) = write_batch::<_, _, in_memory::Error>(
+        &batch_context.environment,
+        &batch_context.store,
+        &empty_root_hash,
+        &pairs,
+    )
+    .unwrap();
+    let (sequential_root_hash, sequential_puts) = write_sequentially::<_, _, in_memory::Error>(
+        &sequential_context.environment,
+        &sequential_context.store,
+        &empty_root_hash,
+        &pairs,
+    )
+    .unwrap();
+
+    assert_eq!(batch_root_hash, sequential_root_hash);
+    // Each leaf is put once either way, but sequential writes also put every intermediate version
+    // of the nodes above it.
+    assert!(
+        (batch_puts as u64) < sequential_puts / 2,
+        "batch puts: {}, sequential puts: {}",
+        batch_puts,
+        sequential_puts
+    );
+}
+
+#[test]
+fn put_reachable_puts_nothing_if_tries_written_on_were_pruned() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[6]().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+
+    let read_txn = context.environment.create_read_txn().unwrap();
+    let mut buffer = WriteBuffer::new(
+        &read_txn,
+        Store::<Blake2bHash, Trie<TestKey, TestValue>>::handle(&context.store),
+    );
+    let new_root_hash = match write::<_, _, _, _, error::Error>(
+        correlation_id,
+        &mut buffer,
+        &context.store,
+        &root_hash,
+        &TestKey([7u8, 0, 0, 0, 0, 0, 0]),
+        &TestValue(*b"value7"),
+        None,
+    )
+    .unwrap()
+    {
+        WriteResult::Written(hash) => hash,
+        result => panic!("unexpected write result: {:?}", result),
+    };
+    let writes = buffer.into_writes();
+    read_txn.commit().unwrap();
+
+    // The tries the new root shares with the old one are pruned before the batch is put.
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    operations::prune::<TestKey, TestValue, _, _, error::Error>(
+        correlation_id,
+        &mut txn,
+        &context.store,
+        &[],
+    )
+    .unwrap();
+    txn.commit().unwrap();
+
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    let put_count = put_reachable::<TestKey, TestValue, _, _, error::Error>(
+        &mut txn,
+        &context.store,
+        &new_root_hash,
+        writes,
+        &mut WriteStats::default(),
+    )
+    .unwrap();
+    assert_eq!(put_count, None);
+    let maybe_root: Option<TestTrie> = context.store.get(&txn, &new_root_hash).unwrap();
+    assert!(maybe_root.is_none());
+}
@@ -0,0 +1,107 @@
+use lmdb::DatabaseFlags;
+use tempfile::tempdir;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr::ToBytes;
+
+use crate::{
+    error,
+    store::Store,
+    transaction_source::{
+        lmdb::{LmdbEnvironment, LmdbEnvironmentOptions},
+        Transaction, TransactionSource,
+    },
+    trie::Trie,
+    trie_store::lmdb::LmdbTrieStore,
+};
+
+const TINY_MAP_PAGES: usize = 16;
+const LARGE_MAP_PAGES: usize = 1024;
+const VALUE_LENGTH: usize = 1024;
+
+fn create_leaf(index: u32) -> (Blake2bHash, Trie<Vec<u8>, Vec<u8>>) {
+    let leaf = Trie::Leaf {
+        key: index.to_le_bytes().to_vec(),
+        value: vec![index as u8; VALUE_LENGTH],
+    };
+    let hash = Blake2bHash::new(&leaf.to_bytes().unwrap());
+    (hash, leaf)
+}
+
+fn put_leaf(
+    env: &LmdbEnvironment,
+    store: &LmdbTrieStore,
+    hash: &Blake2bHash,
+    leaf: &Trie<Vec<u8>, Vec<u8>>,
+) -> Result<(), error::Error> {
+    let mut txn = env.create_read_write_txn()?;
+    store.put(&mut txn, hash, leaf)?;
+    txn.commit()?;
+    Ok(())
+}
+
+#[test]
+fn lmdb_should_fail_when_map_full_and_recover_with_larger_map_size() {
+    let tmp_dir = tempdir().unwrap();
+    let path = tmp_dir.path().to_path_buf();
+    let page_size = engine_shared::os::get_page_size().unwrap();
+
+    let written = {
+        let options = LmdbEnvironmentOptions::new(page_size * TINY_MAP_PAGES);
+        let env = LmdbEnvironment::with_options(&path, options).unwrap();
+        let store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+
+        let mut written = Vec::new();
+        let mut index = 0;
+        loop {
+            let (hash, leaf) = create_leaf(index);
+            match put_leaf(&env, &store, &hash, &leaf) {
+                Ok(()) => written.push((hash, leaf)),
+                Err(error::Error::Lmdb(lmdb::Error::MapFull)) => break,
+                Err(error) => panic!("unexpected error: {:?}", error),
+            }
+            index += 1;
+        }
+        assert!(!written.is_empty(), "should write at least one trie");
+        written
+    };
+
+    let options = LmdbEnvironmentOptions::new(page_size * LARGE_MAP_PAGES);
+    let env = LmdbEnvironment::with_options(&path, options).unwrap();
+    let store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+
+    let (hash, leaf) = create_leaf(written.len() as u32);
+    put_leaf(&env, &store, &hash, &leaf).expect("should write after growing map");
+
+    let txn = env.create_read_txn().unwrap();
+    for (hash, expected) in written.iter().chain(std::iter::once(&(hash, leaf))) {
+        let actual: Option<Trie<Vec<u8>, Vec<u8>>> = store.get(&txn, hash).unwrap();
+        assert_eq!(actual.as_ref(), Some(expected));
+    }
+    txn.commit().unwrap();
+
+    tmp_dir.close().unwrap();
+}
+
+#[test]
+fn lmdb_should_put_get_with_max_readers_and_no_sync() {
+    let tmp_dir = tempdir().unwrap();
+    let page_size = engine_shared::os::get_page_size().unwrap();
+    let options = LmdbEnvironmentOptions::new(page_size * LARGE_MAP_PAGES)
+        .with_max_readers(7)
+        .with_no_sync(true);
+    assert_eq!(options.max_readers(), 7);
+    assert!(options.no_sync());
+
+    let env = LmdbEnvironment::with_options(&tmp_dir.path().to_path_buf(), options).unwrap();
+    let store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+    let (hash, leaf) = create_leaf(0);
+    put_leaf(&env, &store, &hash, &leaf).unwrap();
+
+    let txn = env.create_read_txn().unwrap();
+    let actual: Option<Trie<Vec<u8>, Vec<u8>>> = store.get(&txn, &hash).unwrap();
+    assert_eq!(actual, Some(leaf));
+    txn.commit().unwrap();
+
+    tmp_dir.close().unwrap();
+}
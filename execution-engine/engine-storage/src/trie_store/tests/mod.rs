@@ -1,4 +1,5 @@
 mod concurrent;
+mod map_size;
 mod proptests;
 mod simple;
 
@@ -1,6 +1,9 @@
+use std::fmt::Debug;
+
 use lmdb::DatabaseFlags;
 use tempfile::tempdir;
 
+use engine_shared::newtypes::Blake2bHash;
 use types::bytesrepr::{FromBytes, ToBytes};
 
 use super::TestData;
@@ -8,7 +11,8 @@ use crate::{
     error::{self, in_memory},
     store::StoreExt,
     transaction_source::{
-        in_memory::InMemoryEnvironment, lmdb::LmdbEnvironment, Transaction, TransactionSource,
+        in_memory::InMemoryEnvironment, lmdb::LmdbEnvironment, Readable, Transaction,
+        TransactionSource,
     },
     trie::Trie,
     trie_store::{in_memory::InMemoryTrieStore, lmdb::LmdbTrieStore, TrieStore},
@@ -56,14 +60,37 @@ fn lmdb_put_succeeds() {
     tmp_dir.close().unwrap();
 }
 
+/// Reads `hashes` with [`TrieStore::get_many`], checking the result against reading each hash
+/// in turn.
+fn get_many_matches_get<K, V, S, T>(
+    store: &S,
+    txn: &T,
+    hashes: &[Blake2bHash],
+) -> Result<Vec<Option<Trie<K, V>>>, S::Error>
+where
+    K: FromBytes + PartialEq + Debug,
+    V: FromBytes + PartialEq + Debug,
+    S: TrieStore<K, V>,
+    T: Readable<Handle = S::Handle>,
+    S::Error: From<T::Error>,
+{
+    let ret = store.get_many(txn, hashes)?;
+    let expected = hashes
+        .iter()
+        .map(|hash| store.get(txn, hash))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(ret, expected);
+    Ok(ret)
+}
+
 fn put_get_succeeds<'a, K, V, S, X, E>(
     store: &S,
     transaction_source: &'a X,
     items: &[TestData<K, V>],
 ) -> Result<Vec<Option<Trie<K, V>>>, E>
 where
-    K: ToBytes + FromBytes,
-    V: ToBytes + FromBytes,
+    K: ToBytes + FromBytes + PartialEq + Debug,
+    V: ToBytes + FromBytes + PartialEq + Debug,
     S: TrieStore<K, V>,
     X: TransactionSource<'a, Handle = S::Handle>,
     S::Error: From<X::Error>,
@@ -72,8 +99,8 @@ where
     let mut txn: X::ReadWriteTransaction = transaction_source.create_read_write_txn()?;
     let items = items.iter().map(Into::into);
     store.put_many(&mut txn, items.clone())?;
-    let keys = items.map(|(k, _)| k);
-    let ret = store.get_many(&txn, keys)?;
+    let hashes: Vec<Blake2bHash> = items.map(|(k, _)| *k).collect();
+    let ret = get_many_matches_get(store, &txn, &hashes)?;
     txn.commit()?;
     Ok(ret)
 }
@@ -166,8 +193,8 @@ fn uncommitted_read_write_txn_does_not_persist<'a, K, V, S, X, E>(
     items: &[TestData<K, V>],
 ) -> Result<Vec<Option<Trie<K, V>>>, E>
 where
-    K: ToBytes + FromBytes,
-    V: ToBytes + FromBytes,
+    K: ToBytes + FromBytes + PartialEq + Debug,
+    V: ToBytes + FromBytes + PartialEq + Debug,
     S: TrieStore<K, V>,
     X: TransactionSource<'a, Handle = S::Handle>,
     S::Error: From<X::Error>,
@@ -180,8 +207,8 @@ where
     }
     {
         let txn: X::ReadTransaction = transaction_source.create_read_txn()?;
-        let keys = items.iter().map(|TestData(k, _)| k);
-        let ret = store.get_many(&txn, keys)?;
+        let hashes: Vec<Blake2bHash> = items.iter().map(|TestData(k, _)| *k).collect();
+        let ret = get_many_matches_get(store, &txn, &hashes)?;
         txn.commit()?;
         Ok(ret)
     }
@@ -46,6 +46,11 @@ impl ExecuteRequestBuilder {
         self
     }
 
+    pub fn with_proposer(mut self, proposer: AccountHash) -> Self {
+        self.execute_request.proposer = Some(Ok(proposer));
+        self
+    }
+
     pub fn build(self) -> ExecuteRequest {
         self.execute_request
     }
@@ -91,6 +96,27 @@ impl ExecuteRequestBuilder {
         ExecuteRequestBuilder::new().push_deploy(deploy)
     }
 
+    /// Calls a contract stored under `name` in the sender's named keys
+    pub fn contract_call_by_name(
+        sender: AccountHash,
+        name: &str,
+        entry_point: &str,
+        args: RuntimeArgs,
+    ) -> Self {
+        let mut rng = rand::thread_rng();
+        let deploy_hash: [u8; 32] = rng.gen();
+
+        let deploy = DeployItemBuilder::new()
+            .with_address(sender)
+            .with_stored_session_named_key(name, entry_point, args)
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+            .with_authorization_keys(&[sender])
+            .with_deploy_hash(deploy_hash)
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy)
+    }
+
     /// Calls a versioned contract from contract package hash key_name
     pub fn versioned_contract_call_by_hash_key_name(
         sender: AccountHash,
@@ -23,7 +23,7 @@ pub use deploy_item_builder::DeployItemBuilder;
 pub use execute_request_builder::ExecuteRequestBuilder;
 pub use upgrade_request_builder::UpgradeRequestBuilder;
 pub use wasm_test_builder::{
-    InMemoryWasmTestBuilder, LmdbWasmTestBuilder, WasmTestBuilder, WasmTestResult,
+    InMemoryWasmTestBuilder, LmdbWasmTestBuilder, StateSnapshot, WasmTestBuilder, WasmTestResult,
 };
 
 pub const MINT_INSTALL_CONTRACT: &str = "mint_install.wasm";
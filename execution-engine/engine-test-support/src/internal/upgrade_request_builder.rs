@@ -54,6 +54,16 @@ impl UpgradeRequestBuilder {
         new_costs.set_max_stack_height(wasm_costs.max_stack_height);
         new_costs.set_mem(wasm_costs.mem);
         new_costs.set_memcpy(wasm_costs.memcpy);
+        new_costs.set_max_memory_pages(wasm_costs.max_memory_pages);
+        new_costs.set_max_table_size(wasm_costs.max_table_size);
+        new_costs.set_host_function_costs(
+            wasm_costs
+                .host_function_costs
+                .iter()
+                .cloned()
+                .map(Into::into)
+                .collect(),
+        );
         self.new_costs = Some(new_costs);
         self
     }
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     convert::{TryFrom, TryInto},
     ffi::OsStr,
     fs,
@@ -11,18 +11,23 @@ use std::{
 use grpc::RequestOptions;
 use lmdb::DatabaseFlags;
 use log::LevelFilter;
+use rand::Rng;
 
 use engine_core::{
     engine_state::{
-        execute_request::ExecuteRequest, execution_result::ExecutionResult,
-        run_genesis_request::RunGenesisRequest, EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
+        execute_request::ExecuteRequest,
+        execution_effect::ContractEvent,
+        execution_result::ExecutionResult,
+        genesis::{MINT_TOTAL_SUPPLY, POS_PAYMENT_PURSE, POS_REWARDS_PURSE},
+        run_genesis_request::RunGenesisRequest,
+        EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
     },
-    execution,
+    execution::{self, ErrorCode, GasExhaustionContext, HostFunctionProfile},
 };
 use engine_grpc_server::engine_server::{
     ipc::{
-        CommitRequest, CommitResponse, GenesisResponse, QueryRequest, UpgradeRequest,
-        UpgradeResponse,
+        CommitRequest, CommitResponse, GenesisResponse, GetBondedValidatorsRequest, QueryRequest,
+        UpgradeRequest, UpgradeResponse,
     },
     ipc_grpc::ExecutionEngineService,
     mappings::{MappingError, TransformMap},
@@ -31,7 +36,7 @@ use engine_grpc_server::engine_server::{
 use engine_shared::{
     account::Account,
     additive_map::AdditiveMap,
-    gas::Gas,
+    gas::{CostBreakdown, Gas},
     logging::{self, Settings, Style},
     newtypes::{Blake2bHash, CorrelationId},
     os::get_page_size,
@@ -41,16 +46,17 @@ use engine_shared::{
 use engine_storage::{
     global_state::{in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, StateProvider},
     protocol_data_store::lmdb::LmdbProtocolDataStore,
+    root_ancestry_store::lmdb::LmdbRootAncestryStore,
     transaction_source::lmdb::LmdbEnvironment,
     trie_store::lmdb::LmdbTrieStore,
 };
 use types::{
     account::AccountHash,
     bytesrepr::{self},
-    CLValue, Contract, ContractHash, ContractWasm, Key, URef, U512,
+    ApiError, CLValue, Contract, ContractHash, ContractWasm, Key, RuntimeArgs, URef, U512,
 };
 
-use crate::internal::utils;
+use crate::internal::{utils, DeployItemBuilder, ExecuteRequestBuilder, DEFAULT_PROTOCOL_VERSION};
 
 /// LMDB initial map size is calculated based on DEFAULT_LMDB_PAGES and systems page size.
 ///
@@ -70,6 +76,8 @@ pub struct WasmTestBuilder<S> {
     engine_state: Rc<EngineState<S>>,
     /// [`ExecutionResult`] is wrapped in [`Rc`] to work around a missing [`Clone`] implementation
     exec_responses: Vec<Vec<Rc<ExecutionResult>>>,
+    /// The result of distributing the rewards for each exec request, if it named a proposer
+    rewards_results: Vec<Option<Rc<ExecutionResult>>>,
     upgrade_responses: Vec<UpgradeResponse>,
     genesis_hash: Option<Vec<u8>>,
     post_state_hash: Option<Vec<u8>>,
@@ -87,6 +95,10 @@ pub struct WasmTestBuilder<S> {
     pos_contract_hash: Option<ContractHash>,
     /// Standard payment contract key
     standard_payment_hash: Option<ContractHash>,
+    /// Whether exec requests ask for a profile of the host function calls made
+    profile_host_functions: bool,
+    /// The context in which each exec request ran out of gas, if it did
+    gas_exhaustion_contexts: Vec<Option<GasExhaustionContext>>,
 }
 
 impl<S> WasmTestBuilder<S> {
@@ -98,28 +110,10 @@ impl<S> WasmTestBuilder<S> {
 
 impl Default for InMemoryWasmTestBuilder {
     fn default() -> Self {
-        Self::initialize_logging();
         let engine_config = EngineConfig::new()
             .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
             .with_enable_bonding(cfg!(feature = "enable-bonding"));
-
-        let global_state = InMemoryGlobalState::empty().expect("should create global state");
-        let engine_state = EngineState::new(global_state, engine_config);
-
-        WasmTestBuilder {
-            engine_state: Rc::new(engine_state),
-            exec_responses: Vec::new(),
-            upgrade_responses: Vec::new(),
-            genesis_hash: None,
-            post_state_hash: None,
-            transforms: Vec::new(),
-            bonded_validators: Vec::new(),
-            genesis_account: None,
-            genesis_transforms: None,
-            mint_contract_hash: None,
-            pos_contract_hash: None,
-            standard_payment_hash: None,
-        }
+        Self::new_with_config(engine_config)
     }
 }
 
@@ -130,6 +124,7 @@ impl<S> Clone for WasmTestBuilder<S> {
         WasmTestBuilder {
             engine_state: Rc::clone(&self.engine_state),
             exec_responses: self.exec_responses.clone(),
+            rewards_results: self.rewards_results.clone(),
             upgrade_responses: self.upgrade_responses.clone(),
             genesis_hash: self.genesis_hash.clone(),
             post_state_hash: self.post_state_hash.clone(),
@@ -140,10 +135,36 @@ impl<S> Clone for WasmTestBuilder<S> {
             mint_contract_hash: self.mint_contract_hash,
             pos_contract_hash: self.pos_contract_hash,
             standard_payment_hash: self.standard_payment_hash,
+            profile_host_functions: self.profile_host_functions,
+            gas_exhaustion_contexts: self.gas_exhaustion_contexts.clone(),
         }
     }
 }
 
+/// The state of a [`WasmTestBuilder`] at some point, which it can later be reset to via
+/// [`WasmTestBuilder::restore`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateSnapshot {
+    post_state_hash: Option<Vec<u8>>,
+    exec_responses_count: usize,
+    upgrade_responses_count: usize,
+    transforms_count: usize,
+    bonded_validators_count: usize,
+    mint_contract_hash: Option<ContractHash>,
+    pos_contract_hash: Option<ContractHash>,
+    standard_payment_hash: Option<ContractHash>,
+}
+
+impl StateSnapshot {
+    /// Returns the post-state hash at the time the snapshot was taken.
+    ///
+    /// The snapshot can only be restored while the global state still holds this root, so it
+    /// should be among the roots retained by any prune.
+    pub fn post_state_hash(&self) -> Option<&[u8]> {
+        self.post_state_hash.as_deref()
+    }
+}
+
 /// A wrapper type to disambiguate builder from an actual result
 #[derive(Clone)]
 pub struct WasmTestResult<S>(WasmTestBuilder<S>);
@@ -156,6 +177,31 @@ impl<S> WasmTestResult<S> {
 }
 
 impl InMemoryWasmTestBuilder {
+    /// Creates a builder over empty in-memory global state, using the given engine config.
+    pub fn new_with_config(engine_config: EngineConfig) -> Self {
+        Self::initialize_logging();
+        let global_state = InMemoryGlobalState::empty().expect("should create global state");
+        let engine_state = EngineState::new(global_state, engine_config);
+
+        WasmTestBuilder {
+            engine_state: Rc::new(engine_state),
+            exec_responses: Vec::new(),
+            rewards_results: Vec::new(),
+            upgrade_responses: Vec::new(),
+            genesis_hash: None,
+            post_state_hash: None,
+            transforms: Vec::new(),
+            bonded_validators: Vec::new(),
+            genesis_account: None,
+            genesis_transforms: None,
+            mint_contract_hash: None,
+            pos_contract_hash: None,
+            standard_payment_hash: None,
+            profile_host_functions: false,
+            gas_exhaustion_contexts: Vec::new(),
+        }
+    }
+
     pub fn new(
         global_state: InMemoryGlobalState,
         engine_config: EngineConfig,
@@ -192,12 +238,22 @@ impl LmdbWasmTestBuilder {
             LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty())
                 .expect("should create LmdbProtocolDataStore"),
         );
-        let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-            .expect("should create LmdbGlobalState");
+        let root_ancestry_store = Arc::new(
+            LmdbRootAncestryStore::new(&environment, None, DatabaseFlags::empty())
+                .expect("should create LmdbRootAncestryStore"),
+        );
+        let global_state = LmdbGlobalState::empty(
+            environment,
+            trie_store,
+            protocol_data_store,
+            root_ancestry_store,
+        )
+        .expect("should create LmdbGlobalState");
         let engine_state = EngineState::new(global_state, engine_config);
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            rewards_results: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
@@ -208,6 +264,8 @@ impl LmdbWasmTestBuilder {
             mint_contract_hash: None,
             pos_contract_hash: None,
             standard_payment_hash: None,
+            profile_host_functions: false,
+            gas_exhaustion_contexts: Vec::new(),
         }
     }
 
@@ -235,6 +293,9 @@ impl LmdbWasmTestBuilder {
 
     /// Creates a new instance of builder using the supplied configurations, opening wrapped LMDBs
     /// (e.g. in the Trie and Data stores) rather than creating them.
+    ///
+    /// The system contract hashes are restored from the protocol data stored by a previous run's
+    /// genesis, so the returned builder can carry on exec'ing and querying from `post_state_hash`.
     pub fn open<T: AsRef<OsStr> + ?Sized>(
         data_dir: &T,
         engine_config: EngineConfig,
@@ -253,12 +314,25 @@ impl LmdbWasmTestBuilder {
             LmdbProtocolDataStore::open(&environment, None)
                 .expect("should open LmdbProtocolDataStore"),
         );
-        let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-            .expect("should create LmdbGlobalState");
+        let root_ancestry_store = Arc::new(
+            LmdbRootAncestryStore::open(&environment, None)
+                .expect("should open LmdbRootAncestryStore"),
+        );
+        let global_state = LmdbGlobalState::empty(
+            environment,
+            trie_store,
+            protocol_data_store,
+            root_ancestry_store,
+        )
+        .expect("should create LmdbGlobalState");
         let engine_state = EngineState::new(global_state, engine_config);
+        let maybe_protocol_data = engine_state
+            .get_protocol_data(*DEFAULT_PROTOCOL_VERSION)
+            .expect("should read protocol data");
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            rewards_results: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: None,
             post_state_hash: Some(post_state_hash),
@@ -266,9 +340,13 @@ impl LmdbWasmTestBuilder {
             bonded_validators: Vec::new(),
             genesis_account: None,
             genesis_transforms: None,
-            mint_contract_hash: None,
-            pos_contract_hash: None,
-            standard_payment_hash: None,
+            mint_contract_hash: maybe_protocol_data.map(|protocol_data| protocol_data.mint()),
+            pos_contract_hash: maybe_protocol_data
+                .map(|protocol_data| protocol_data.proof_of_stake()),
+            standard_payment_hash: maybe_protocol_data
+                .map(|protocol_data| protocol_data.standard_payment()),
+            profile_host_functions: false,
+            gas_exhaustion_contexts: Vec::new(),
         }
     }
 
@@ -286,7 +364,7 @@ impl LmdbWasmTestBuilder {
 
 impl<S> WasmTestBuilder<S>
 where
-    S: StateProvider,
+    S: StateProvider + Sync,
     S::Error: Into<execution::Error>,
     EngineState<S>: ExecutionEngineService,
 {
@@ -295,6 +373,7 @@ where
         WasmTestBuilder {
             engine_state: result.0.engine_state,
             exec_responses: Vec::new(),
+            rewards_results: Vec::new(),
             upgrade_responses: Vec::new(),
             genesis_hash: result.0.genesis_hash,
             post_state_hash: result.0.post_state_hash,
@@ -305,9 +384,59 @@ where
             pos_contract_hash: result.0.pos_contract_hash,
             standard_payment_hash: result.0.standard_payment_hash,
             genesis_transforms: result.0.genesis_transforms,
+            profile_host_functions: result.0.profile_host_functions,
+            gas_exhaustion_contexts: Vec::new(),
+        }
+    }
+
+    /// Makes every subsequent exec request ask the engine to profile the host function calls it
+    /// makes.  The profile of each deploy is available via [`get_host_function_profile`].
+    ///
+    /// [`get_host_function_profile`]: WasmTestBuilder::get_host_function_profile
+    pub fn with_profiling(&mut self) -> &mut Self {
+        self.profile_host_functions = true;
+        self
+    }
+
+    /// Captures the current post-state hash along with the results recorded so far, so that several
+    /// alternative continuations can be run from the same point via [`restore`].
+    ///
+    /// [`restore`]: WasmTestBuilder::restore
+    pub fn snapshot(&self) -> StateSnapshot {
+        StateSnapshot {
+            post_state_hash: self.post_state_hash.clone(),
+            exec_responses_count: self.exec_responses.len(),
+            upgrade_responses_count: self.upgrade_responses.len(),
+            transforms_count: self.transforms.len(),
+            bonded_validators_count: self.bonded_validators.len(),
+            mint_contract_hash: self.mint_contract_hash,
+            pos_contract_hash: self.pos_contract_hash,
+            standard_payment_hash: self.standard_payment_hash,
         }
     }
 
+    /// Resets the builder to the point at which `snapshot` was taken.
+    ///
+    /// Subsequent execs and commits continue from the snapshot's post-state hash, and every exec
+    /// response, transform and commit result recorded since the snapshot is discarded.  The tries
+    /// committed since the snapshot remain in global state.
+    pub fn restore(&mut self, snapshot: &StateSnapshot) -> &mut Self {
+        self.post_state_hash = snapshot.post_state_hash.clone();
+        self.exec_responses.truncate(snapshot.exec_responses_count);
+        self.rewards_results.truncate(snapshot.exec_responses_count);
+        self.gas_exhaustion_contexts
+            .truncate(snapshot.exec_responses_count);
+        self.upgrade_responses
+            .truncate(snapshot.upgrade_responses_count);
+        self.transforms.truncate(snapshot.transforms_count);
+        self.bonded_validators
+            .truncate(snapshot.bonded_validators_count);
+        self.mint_contract_hash = snapshot.mint_contract_hash;
+        self.pos_contract_hash = snapshot.pos_contract_hash;
+        self.standard_payment_hash = snapshot.standard_payment_hash;
+        self
+    }
+
     pub fn run_genesis(&mut self, run_genesis_request: &RunGenesisRequest) -> &mut Self {
         let system_account = Key::Account(SYSTEM_ACCOUNT_ADDR);
         let run_genesis_request_proto = run_genesis_request
@@ -380,6 +509,43 @@ where
         bytesrepr::deserialize(query_response.take_success()).map_err(|err| format!("{}", err))
     }
 
+    /// Queries the validators bonded at `maybe_post_state`, or at the current post-state hash if
+    /// that is `None`.
+    pub fn query_bonded_validators(
+        &self,
+        maybe_post_state: Option<Vec<u8>>,
+    ) -> Result<BTreeMap<AccountHash, U512>, String> {
+        let post_state = maybe_post_state
+            .or_else(|| self.post_state_hash.clone())
+            .expect("builder must have a post-state hash");
+
+        let mut request = GetBondedValidatorsRequest::new();
+        request.set_state_hash(post_state);
+        request.set_protocol_version((*DEFAULT_PROTOCOL_VERSION).into());
+
+        // `EngineState::get_bonded_validators` would shadow the service method here.
+        let mut response = ExecutionEngineService::get_bonded_validators(
+            &*self.engine_state,
+            RequestOptions::new(),
+            request,
+        )
+        .wait_drop_metadata()
+        .expect("should get bonded validators response");
+
+        if response.has_failure() {
+            return Err(response.take_failure());
+        }
+        if response.has_missing_state() {
+            return Err("Root not found".to_string());
+        }
+        response
+            .take_success()
+            .take_bonds()
+            .into_iter()
+            .map(|bond| bond.try_into().map_err(|error| format!("{:?}", error)))
+            .collect()
+    }
+
     pub fn exec(&mut self, mut exec_request: ExecuteRequest) -> &mut Self {
         let exec_request = {
             let hash = self
@@ -388,25 +554,74 @@ where
                 .expect("expected post_state_hash");
             exec_request.parent_state_hash =
                 hash.as_slice().try_into().expect("expected a valid hash");
+            exec_request.profile_host_functions |= self.profile_host_functions;
             exec_request
         };
+        // Discard any context left over from executions outside this builder.
+        #[cfg(feature = "test-support")]
+        let _ = engine_core::runtime::take_gas_exhaustion_context();
         let exec_response = self
             .engine_state
             .run_execute(CorrelationId::new(), exec_request);
         assert!(exec_response.is_ok());
+        #[cfg(feature = "test-support")]
+        self.gas_exhaustion_contexts
+            .push(engine_core::runtime::take_gas_exhaustion_context());
+        #[cfg(not(feature = "test-support"))]
+        self.gas_exhaustion_contexts.push(None);
         // Parse deploy results
-        let execution_results = exec_response.as_ref().unwrap();
+        let (execution_results, rewards_result) = exec_response.unwrap();
         // Cache transformations
         self.transforms.extend(
             execution_results
                 .iter()
                 .map(|res| res.effect().transforms.clone()),
         );
+        // The rewards are paid out on top of the deploys' effects, so they are committed along
+        // with those of the last deploy.
+        if let Some(rewards_result) = rewards_result.as_ref() {
+            if execution_results.is_empty() {
+                self.transforms.push(AdditiveMap::new());
+            }
+            let transforms = self.transforms.last_mut().expect("should have transforms");
+            for (key, transform) in rewards_result.effect().transforms.iter() {
+                transforms.insert_add(*key, transform.to_owned());
+            }
+        }
         self.exec_responses
-            .push(exec_response.unwrap().into_iter().map(Rc::new).collect());
+            .push(execution_results.into_iter().map(Rc::new).collect());
+        self.rewards_results.push(rewards_result.map(Rc::new));
         self
     }
 
+    /// Executes a single deploy from `account_hash` which runs `payment_file` as its payment code
+    /// and `session_file` as its session code.
+    pub fn exec_with_payment(
+        &mut self,
+        account_hash: AccountHash,
+        payment_file: &str,
+        payment_args: RuntimeArgs,
+        session_file: &str,
+        session_args: RuntimeArgs,
+        block_time: u64,
+    ) -> &mut Self {
+        let deploy_hash: [u8; 32] = rand::thread_rng().gen();
+
+        let deploy = DeployItemBuilder::new()
+            .with_address(account_hash)
+            .with_payment_code(payment_file, payment_args)
+            .with_session_code(session_file, session_args)
+            .with_authorization_keys(&[account_hash])
+            .with_deploy_hash(deploy_hash)
+            .build();
+
+        let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy)
+            .with_block_time(block_time)
+            .build();
+
+        self.exec(exec_request)
+    }
+
     /// Commit effects of previous exec call on the latest post-state hash.
     pub fn commit(&mut self) -> &mut Self {
         let prestate_hash = self
@@ -497,6 +712,12 @@ where
             .get(0)
             .expect("Unable to get first deploy result");
 
+        if exec_result.has_precondition_failure() {
+            panic!(
+                "Expected successful execution result, but the deploy failed a precondition: {:?}",
+                exec_response,
+            );
+        }
         if exec_result.is_failure() {
             panic!(
                 "Expected successful execution result, but instead got: {:?}",
@@ -506,7 +727,79 @@ where
         self
     }
 
+    /// Returns `true` if the first deploy of the last run failed, whether it failed a precondition
+    /// or failed during execution.
     pub fn is_error(&self) -> bool {
+        self.last_first_result().is_failure()
+    }
+
+    /// Returns `true` if the first deploy of the last run failed a precondition, i.e. it was
+    /// rejected without being executed or charged for.
+    pub fn is_precondition_failure(&self) -> bool {
+        self.last_first_result().has_precondition_failure()
+    }
+
+    fn last_first_result(&self) -> &ExecutionResult {
+        let exec_response = self
+            .exec_responses
+            .last()
+            .expect("Expected to be called after run()");
+        exec_response
+            .get(0)
+            .expect("Unable to get first execution result")
+    }
+
+    /// Returns the error message of the first failed deploy in the exec response at `exec_index`,
+    /// or `None` if every deploy in it succeeded.
+    pub fn get_error_message(&self, exec_index: usize) -> Option<String> {
+        self.exec_responses
+            .get(exec_index)
+            .expect("should have exec response at given index")
+            .iter()
+            .find_map(|exec_result| exec_result.as_error())
+            .map(|error| error.to_string())
+    }
+
+    /// Expects the last run to have failed with an error classified as `expected`.
+    pub fn expect_error_code(&mut self, expected: ErrorCode) -> &mut Self {
+        let actual = self.last_error_code();
+        if actual != expected {
+            panic!(
+                "Expected execution to fail with {:?}, but instead got {:?}: {}",
+                expected,
+                actual,
+                self.get_error_message(self.exec_responses.len() - 1)
+                    .unwrap_or_default(),
+            );
+        }
+        self
+    }
+
+    /// Expects the last run to have reverted with `expected`.
+    pub fn expect_revert_with(&mut self, expected: ApiError) -> &mut Self {
+        let actual = self.last_error_code();
+        if actual != ErrorCode::Revert(expected.into()) {
+            let actual = match actual {
+                ErrorCode::Revert(status) => format!("revert with {:?}", ApiError::from(status)),
+                other => format!("{:?}", other),
+            };
+            panic!(
+                "Expected execution to revert with {:?}, but instead got {}: {}",
+                expected,
+                actual,
+                self.get_error_message(self.exec_responses.len() - 1)
+                    .unwrap_or_default(),
+            );
+        }
+        self
+    }
+
+    /// Expects the last run to have reverted with `ApiError::User(code)`.
+    pub fn expect_user_error(&mut self, code: u16) -> &mut Self {
+        self.expect_revert_with(ApiError::User(code))
+    }
+
+    fn last_error_code(&self) -> ErrorCode {
         let exec_response = self
             .exec_responses
             .last()
@@ -514,7 +807,13 @@ where
         let exec_result = exec_response
             .get(0)
             .expect("Unable to get first execution result");
-        exec_result.is_failure()
+        match exec_result.as_error() {
+            Some(error) => error.code(),
+            None => panic!(
+                "Expected execution to fail, but it succeeded: {:?}",
+                exec_response
+            ),
+        }
     }
 
     /// Gets the transform map that's cached between runs
@@ -583,6 +882,15 @@ where
         self.exec_responses.len()
     }
 
+    /// Returns the result of distributing the rewards for the exec request at `index`, or `None`
+    /// if it didn't name a proposer.
+    pub fn get_rewards_result(&self, index: usize) -> Option<&ExecutionResult> {
+        self.rewards_results
+            .get(index)
+            .and_then(Option::as_ref)
+            .map(AsRef::as_ref)
+    }
+
     pub fn get_upgrade_response(&self, index: usize) -> Option<&UpgradeResponse> {
         self.upgrade_responses.get(index)
     }
@@ -617,6 +925,46 @@ where
             .expect("should parse balance into a U512")
     }
 
+    /// Returns the balance of the PoS contract's payment purse.
+    pub fn get_pos_payment_purse_balance(&self) -> U512 {
+        let purse = self
+            .get_pos_purse_by_name(POS_PAYMENT_PURSE)
+            .expect("should find PoS payment purse");
+        self.get_purse_balance(purse)
+    }
+
+    /// Returns the text printed by contracts via `runtime::print` since the last call, in the order
+    /// it was printed.
+    #[cfg(feature = "test-support")]
+    pub fn take_printed_messages(&self) -> Vec<String> {
+        engine_core::runtime::take_printed_messages()
+    }
+
+    /// Returns the balance of the PoS contract's rewards purse.
+    pub fn get_pos_rewards_purse_balance(&self) -> U512 {
+        let purse = self
+            .get_pos_purse_by_name(POS_REWARDS_PURSE)
+            .expect("should find PoS rewards purse");
+        self.get_purse_balance(purse)
+    }
+
+    /// Returns the total supply of motes tracked by the mint contract.
+    pub fn get_total_supply(&self) -> U512 {
+        let mint_contract_hash = self.get_mint_contract_hash();
+        self.query(None, mint_contract_hash.into(), &[MINT_TOTAL_SUPPLY])
+            .and_then(|v| CLValue::try_from(v).map_err(|error| format!("{:?}", error)))
+            .and_then(|cl_value| cl_value.into_t().map_err(|error| format!("{:?}", error)))
+            .expect("should parse total supply into a U512")
+    }
+
+    fn get_pos_purse_by_name(&self, purse_name: &str) -> Option<URef> {
+        self.get_pos_contract()
+            .named_keys()
+            .get(purse_name)
+            .and_then(Key::as_uref)
+            .cloned()
+    }
+
     pub fn get_account(&self, account_hash: AccountHash) -> Option<Account> {
         match self.query(None, Key::Account(account_hash), &[]) {
             Ok(account_value) => match account_value {
@@ -658,6 +1006,37 @@ where
         utils::get_exec_costs(exec_response)
     }
 
+    /// Returns the cost breakdown of the deploy executed by the `deploy_index`-th exec request.
+    pub fn get_cost_breakdown(&self, deploy_index: usize) -> CostBreakdown {
+        let exec_response = self
+            .get_exec_response(deploy_index)
+            .expect("should have exec response");
+        let exec_result = exec_response.get(0).expect("should have result");
+        exec_result.cost_breakdown().clone()
+    }
+
+    /// Returns the host function calls made by the deploy executed by the `deploy_index`-th exec
+    /// request.  This is empty unless the builder was created [`with_profiling`].
+    ///
+    /// [`with_profiling`]: WasmTestBuilder::with_profiling
+    pub fn get_host_function_profile(&self, deploy_index: usize) -> HostFunctionProfile {
+        let exec_response = self
+            .get_exec_response(deploy_index)
+            .expect("should have exec response");
+        let exec_result = exec_response.get(0).expect("should have result");
+        exec_result.host_function_profile().clone()
+    }
+
+    /// Returns how far the deploy executed by the `deploy_index`-th exec request got before it ran
+    /// out of gas, or `None` if it didn't run out of gas.  Always `None` unless the `test-support`
+    /// feature is enabled and the deploys are executed one at a time.
+    pub fn get_gas_exhaustion_context(&self, deploy_index: usize) -> Option<&GasExhaustionContext> {
+        self.gas_exhaustion_contexts
+            .get(deploy_index)
+            .expect("should have exec response")
+            .as_ref()
+    }
+
     pub fn last_exec_gas_cost(&self) -> Gas {
         let exec_response = self
             .exec_responses
@@ -667,6 +1046,14 @@ where
         exec_result.cost()
     }
 
+    /// Returns the events emitted by the first deploy of the `index`th exec call, in emission
+    /// order.
+    pub fn get_events(&self, index: usize) -> Option<Vec<ContractEvent>> {
+        let exec_response = self.get_exec_response(index)?;
+        let exec_result = exec_response.get(0)?;
+        Some(exec_result.effect().events.clone())
+    }
+
     pub fn exec_error_message(&self, index: usize) -> Option<String> {
         let response = self.get_exec_response(index)?;
         Some(utils::get_error_message(response))
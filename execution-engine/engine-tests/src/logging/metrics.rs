@@ -1,9 +1,12 @@
 use std::sync::{Arc, Mutex};
 
+use grpc::RequestOptions;
+use lazy_static::lazy_static;
 use log::{LevelFilter, Metadata, Record};
 use serde_json::Value;
 
-use engine_core::engine_state::EngineConfig;
+use engine_core::engine_state::{EngineConfig, EngineState};
+use engine_grpc_server::engine_server::{ipc::QueryRequest, ipc_grpc::ExecutionEngineService};
 use engine_shared::{
     logging::{self, Settings, TerminalLogger, PAYLOAD_KEY},
     newtypes::CorrelationId,
@@ -11,10 +14,22 @@ use engine_shared::{
 };
 use engine_storage::global_state::in_memory::InMemoryGlobalState;
 use engine_test_support::internal::{InMemoryWasmTestBuilder, MOCKED_ACCOUNT_ADDRESS};
+use types::{account::AccountHash, Key};
 
 const PROPERTIES_KEY: &str = "properties";
 const CORRELATION_ID_KEY: &str = "correlation_id";
 
+lazy_static! {
+    // The global logger can only be set once per process, so all tests share its buffer.
+    static ref LOG_LINES: Arc<Mutex<Vec<String>>> = {
+        let settings = Settings::new(LevelFilter::Trace).with_metrics_enabled(true);
+        let log_lines = Arc::new(Mutex::new(vec![]));
+        let logger = Box::new(Logger::new(Arc::clone(&log_lines), &settings));
+        let _ = logging::initialize_with_logger(logger, settings);
+        log_lines
+    };
+}
+
 struct Logger {
     terminal_logger: TerminalLogger,
     log_lines: Arc<Mutex<Vec<String>>>,
@@ -58,12 +73,20 @@ fn extract_correlation_id_property(line: &str) -> Option<String> {
     }
 }
 
+fn log_lines_containing(correlation_id: CorrelationId) -> Vec<String> {
+    let expected_fragment = format!(r#""{}":"{}""#, CORRELATION_ID_KEY, correlation_id);
+    LOG_LINES
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|line| line.contains(&expected_fragment))
+        .cloned()
+        .collect()
+}
+
 #[test]
 fn should_commit_with_metrics() {
-    let settings = Settings::new(LevelFilter::Trace).with_metrics_enabled(true);
-    let log_lines = Arc::new(Mutex::new(vec![]));
-    let logger = Box::new(Logger::new(Arc::clone(&log_lines), &settings));
-    let _ = logging::initialize_with_logger(logger, settings);
+    lazy_static::initialize(&LOG_LINES);
 
     let correlation_id = CorrelationId::new();
     let mocked_account = test_utils::mocked_account(MOCKED_ACCOUNT_ADDRESS);
@@ -79,10 +102,7 @@ fn should_commit_with_metrics() {
         .builder()
         .commit_transforms(root_hash.to_vec(), Default::default());
 
-    let mut log_lines = log_lines.lock().unwrap();
-
-    let expected_fragment = format!(r#""{}":"{}""#, CORRELATION_ID_KEY, correlation_id);
-    log_lines.retain(|line| line.contains(&expected_fragment));
+    let log_lines = log_lines_containing(correlation_id);
     assert!(
         !log_lines.is_empty(),
         "at least one log line should contain the expected correlation ID"
@@ -93,3 +113,33 @@ fn should_commit_with_metrics() {
         assert_eq!(correlation_id.to_string(), extracted_correlation_id);
     }
 }
+
+#[test]
+fn should_log_with_supplied_correlation_id() {
+    lazy_static::initialize(&LOG_LINES);
+
+    let global_state = InMemoryGlobalState::empty().unwrap();
+    let engine_state = EngineState::new(global_state, EngineConfig::new());
+
+    let correlation_id = CorrelationId::new();
+    let mut query_request = QueryRequest::new();
+    query_request.set_state_hash(vec![255; 32]);
+    query_request.set_base_key(Key::Account(AccountHash::new([1; 32])).into());
+    query_request.set_correlation_id(correlation_id.to_string());
+
+    let _query_response = engine_state
+        .query(RequestOptions::new(), query_request)
+        .wait_drop_metadata()
+        .expect("should receive response");
+
+    let log_lines = log_lines_containing(correlation_id);
+    assert!(
+        !log_lines.is_empty(),
+        "at least one log line should contain the supplied correlation ID"
+    );
+
+    for line in log_lines.iter() {
+        let extracted_correlation_id = extract_correlation_id_property(line).unwrap();
+        assert_eq!(correlation_id.to_string(), extracted_correlation_id);
+    }
+}
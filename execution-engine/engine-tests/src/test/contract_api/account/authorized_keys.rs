@@ -1,18 +1,36 @@
-use engine_core::{engine_state, execution};
+use engine_core::{
+    engine_state::{self, run_genesis_request::RunGenesisRequest},
+    execution,
+};
 use engine_test_support::{
     internal::{
         DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
-        DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+        DEFAULT_EXEC_CONFIG, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT,
     },
     DEFAULT_ACCOUNT_ADDR,
 };
 use types::{
     account::{AccountHash, Weight},
-    runtime_args, RuntimeArgs,
+    runtime_args, ProtocolVersion, RuntimeArgs,
 };
 
 const CONTRACT_ADD_UPDATE_ASSOCIATED_KEY: &str = "add_update_associated_key.wasm";
+
+/// `authorized_keys.wasm` reads back thresholds and keys with host functions which are only
+/// available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+fn run_genesis_request() -> RunGenesisRequest {
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    )
+}
 const CONTRACT_AUTHORIZED_KEYS: &str = "authorized_keys.wasm";
+const CONTRACT_LIST_AUTHORIZATION_KEYS: &str = "list_authorization_keys.wasm";
 
 #[ignore]
 #[test]
@@ -25,10 +43,11 @@ fn should_deploy_with_authorized_identity_key() {
             "deploy_threshold" => Weight::new(1),
         },
     )
+    .with_protocol_version(protocol_version_2())
     .build();
     // Basic deploy with single key
     InMemoryWasmTestBuilder::default()
-        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .run_genesis(&run_genesis_request())
         .exec(exec_request)
         .commit()
         .expect_success();
@@ -52,31 +71,23 @@ fn should_raise_auth_failure_with_invalid_key() {
             .with_deploy_hash([1u8; 32])
             .with_authorization_keys(&[key_1])
             .build();
-        ExecuteRequestBuilder::from_deploy_item(deploy).build()
+        ExecuteRequestBuilder::from_deploy_item(deploy)
+            .with_protocol_version(protocol_version_2())
+            .build()
     };
 
     // Basic deploy with single key
-    let result = InMemoryWasmTestBuilder::default()
-        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request())
         .exec(exec_request)
-        .commit()
-        .finish();
-
-    let deploy_result = result
-        .builder()
-        .get_exec_response(0)
-        .expect("should have exec response")
-        .get(0)
-        .expect("should have at least one deploy result");
+        .commit();
 
-    assert!(
-        deploy_result.has_precondition_failure(),
-        "{:?}",
-        deploy_result
+    assert!(builder.is_error());
+    assert_eq!(
+        builder.get_error_message(0),
+        Some(format!("{}", engine_state::Error::Authorization))
     );
-    let message = format!("{}", deploy_result.as_error().unwrap());
-
-    assert_eq!(message, format!("{}", engine_state::Error::Authorization))
 }
 
 #[ignore]
@@ -101,12 +112,14 @@ fn should_raise_auth_failure_with_invalid_keys() {
             .with_deploy_hash([1u8; 32])
             .with_authorization_keys(&[key_2, key_1, key_3])
             .build();
-        ExecuteRequestBuilder::from_deploy_item(deploy).build()
+        ExecuteRequestBuilder::from_deploy_item(deploy)
+            .with_protocol_version(protocol_version_2())
+            .build()
     };
 
     // Basic deploy with single key
     let result = InMemoryWasmTestBuilder::default()
-        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .run_genesis(&run_genesis_request())
         .exec(exec_request)
         .commit()
         .finish();
@@ -140,18 +153,21 @@ fn should_raise_deploy_authorization_failure() {
         CONTRACT_ADD_UPDATE_ASSOCIATED_KEY,
         runtime_args! { "account" => key_1, },
     )
+    .with_protocol_version(protocol_version_2())
     .build();
     let exec_request_2 = ExecuteRequestBuilder::standard(
         DEFAULT_ACCOUNT_ADDR,
         CONTRACT_ADD_UPDATE_ASSOCIATED_KEY,
         runtime_args! { "account" => key_2, },
     )
+    .with_protocol_version(protocol_version_2())
     .build();
     let exec_request_3 = ExecuteRequestBuilder::standard(
         DEFAULT_ACCOUNT_ADDR,
         CONTRACT_ADD_UPDATE_ASSOCIATED_KEY,
         runtime_args! { "account" => key_3, },
     )
+    .with_protocol_version(protocol_version_2())
     .build();
     // Deploy threshold is equal to 3, keymgmnt is still 1.
     // Even after verifying weights and thresholds to not
@@ -163,10 +179,11 @@ fn should_raise_deploy_authorization_failure() {
         CONTRACT_AUTHORIZED_KEYS,
         runtime_args! { "key_management_threshold" => Weight::new(4), "deploy_threshold" => Weight::new(3) },
     )
-        .build();
+    .with_protocol_version(protocol_version_2())
+    .build();
     // Basic deploy with single key
     let result1 = InMemoryWasmTestBuilder::default()
-        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .run_genesis(&run_genesis_request())
         // Reusing a test contract that would add new key
         .exec(exec_request_1)
         .expect_success()
@@ -189,7 +206,8 @@ fn should_raise_deploy_authorization_failure() {
         CONTRACT_AUTHORIZED_KEYS,
         runtime_args! { "key_management_threshold" => Weight::new(5), "deploy_threshold" => Weight::new(4) }, //args
     )
-        .build();
+    .with_protocol_version(protocol_version_2())
+    .build();
 
     // With deploy threshold == 3 using single secondary key
     // with weight == 2 should raise deploy authorization failure.
@@ -224,7 +242,9 @@ fn should_raise_deploy_authorization_failure() {
             .with_deploy_hash([6u8; 32])
             .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR, key_1, key_2, key_3])
             .build();
-        ExecuteRequestBuilder::from_deploy_item(deploy).build()
+        ExecuteRequestBuilder::from_deploy_item(deploy)
+            .with_protocol_version(protocol_version_2())
+            .build()
     };
     // identity key (w: 1) and key_1 (w: 2) passes threshold of 3
     let result3 = InMemoryWasmTestBuilder::from_result(result2)
@@ -238,7 +258,8 @@ fn should_raise_deploy_authorization_failure() {
         CONTRACT_AUTHORIZED_KEYS,
         runtime_args! { "key_management_threshold" => Weight::new(0), "deploy_threshold" => Weight::new(0) }, //args
     )
-        .build();
+    .with_protocol_version(protocol_version_2())
+    .build();
 
     // deployment threshold is now 4
     // failure: key_2 weight + key_1 weight < deployment threshold
@@ -277,7 +298,9 @@ fn should_raise_deploy_authorization_failure() {
             .with_deploy_hash([8u8; 32])
             .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR, key_1, key_2, key_3])
             .build();
-        ExecuteRequestBuilder::from_deploy_item(deploy).build()
+        ExecuteRequestBuilder::from_deploy_item(deploy)
+            .with_protocol_version(protocol_version_2())
+            .build()
     };
 
     // success: identity key weight + key_1 weight + key_2 weight >= deployment
@@ -305,16 +328,18 @@ fn should_authorize_deploy_with_multiple_keys() {
         CONTRACT_ADD_UPDATE_ASSOCIATED_KEY,
         runtime_args! { "account" => key_1, },
     )
+    .with_protocol_version(protocol_version_2())
     .build();
     let exec_request_2 = ExecuteRequestBuilder::standard(
         DEFAULT_ACCOUNT_ADDR,
         CONTRACT_ADD_UPDATE_ASSOCIATED_KEY,
         runtime_args! { "account" => key_2, },
     )
+    .with_protocol_version(protocol_version_2())
     .build();
     // Basic deploy with single key
     let result1 = InMemoryWasmTestBuilder::default()
-        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .run_genesis(&run_genesis_request())
         // Reusing a test contract that would add new key
         .exec(exec_request_1)
         .expect_success()
@@ -334,6 +359,7 @@ fn should_authorize_deploy_with_multiple_keys() {
             "deploy_threshold" => Weight::new(0),
         },
     )
+    .with_protocol_version(protocol_version_2())
     .build();
     InMemoryWasmTestBuilder::from_result(result1)
         .exec(exec_request_3)
@@ -354,6 +380,7 @@ fn should_not_authorize_deploy_with_duplicated_keys() {
         CONTRACT_ADD_UPDATE_ASSOCIATED_KEY,
         runtime_args! { "account" => key_1, },
     )
+    .with_protocol_version(protocol_version_2())
     .build();
 
     let exec_request_2 = ExecuteRequestBuilder::standard(
@@ -361,10 +388,11 @@ fn should_not_authorize_deploy_with_duplicated_keys() {
         CONTRACT_AUTHORIZED_KEYS,
         runtime_args! { "key_management_threshold" => Weight::new(4), "deploy_threshold" => Weight::new(3) },
     )
-        .build();
+    .with_protocol_version(protocol_version_2())
+    .build();
     // Basic deploy with single key
     let result1 = InMemoryWasmTestBuilder::default()
-        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .run_genesis(&run_genesis_request())
         // Reusing a test contract that would add new key
         .exec(exec_request_1)
         .expect_success()
@@ -386,7 +414,9 @@ fn should_not_authorize_deploy_with_duplicated_keys() {
                 key_1, key_1, key_1, key_1, key_1, key_1, key_1, key_1, key_1, key_1,
             ])
             .build();
-        ExecuteRequestBuilder::from_deploy_item(deploy).build()
+        ExecuteRequestBuilder::from_deploy_item(deploy)
+            .with_protocol_version(protocol_version_2())
+            .build()
     };
     // success: identity key weight + key_1 weight + key_2 weight >= deployment
     // threshold
@@ -412,3 +442,113 @@ fn should_not_authorize_deploy_with_duplicated_keys() {
         execution::Error::DeploymentAuthorizationFailure
     )))
 }
+
+#[ignore]
+#[test]
+fn should_revert_when_key_management_threshold_is_not_met() {
+    // The contract reverts with `ApiError::User(50)` if it isn't permitted to add a key.
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_AUTHORIZED_KEYS,
+        runtime_args! { "key_management_threshold" => Weight::new(2), "deploy_threshold" => Weight::new(1) },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_AUTHORIZED_KEYS,
+        runtime_args! { "key_management_threshold" => Weight::new(0), "deploy_threshold" => Weight::new(0) },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+
+    // The identity key alone (w: 1) no longer meets the key management threshold of 2.
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&run_genesis_request())
+        .exec(exec_request_1)
+        .expect_success()
+        .commit()
+        .exec(exec_request_2)
+        .commit()
+        .expect_user_error(50);
+}
+
+#[ignore]
+#[test]
+fn should_expose_authorization_keys_and_enforce_deploy_threshold() {
+    let key_1 = AccountHash::new([254; 32]);
+    let key_2 = AccountHash::new([253; 32]);
+
+    let add_key_request = |key: AccountHash| {
+        ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_ADD_UPDATE_ASSOCIATED_KEY,
+            runtime_args! { "account" => key, },
+        )
+        .with_protocol_version(protocol_version_2())
+        .build()
+    };
+    let set_thresholds_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_AUTHORIZED_KEYS,
+        runtime_args! {
+            "key_management_threshold" => Weight::new(4),
+            "deploy_threshold" => Weight::new(3),
+        },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+    let list_keys_request = |authorization_keys: &[AccountHash], deploy_hash: [u8; 32]| {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+            .with_session_code(
+                CONTRACT_LIST_AUTHORIZATION_KEYS,
+                runtime_args! { "expected_authorization_keys" => authorization_keys.to_vec() },
+            )
+            .with_deploy_hash(deploy_hash)
+            .with_authorization_keys(authorization_keys)
+            .build();
+        ExecuteRequestBuilder::from_deploy_item(deploy)
+            .with_protocol_version(protocol_version_2())
+            .build()
+    };
+
+    // key_1 (w: 2) and key_2 (w: 2) are added, and the deploy threshold raised to 3.
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request())
+        .exec(add_key_request(key_1))
+        .expect_success()
+        .commit()
+        .exec(add_key_request(key_2))
+        .expect_success()
+        .commit()
+        .exec(set_thresholds_request)
+        .expect_success()
+        .commit();
+
+    // A single weight 2 key doesn't meet the deploy threshold of 3.
+    builder.exec(list_keys_request(&[key_1], [1; 32])).commit();
+    let deploy_result = builder
+        .get_exec_response(3)
+        .expect("should have exec response")
+        .get(0)
+        .expect("should have at least one deploy result");
+    assert!(
+        deploy_result.has_precondition_failure(),
+        "{:?}",
+        deploy_result
+    );
+    let message = format!("{}", deploy_result.as_error().unwrap());
+    assert!(message.contains(&format!(
+        "{}",
+        execution::Error::DeploymentAuthorizationFailure
+    )));
+
+    // Both keys together (w: 4) do, and the session sees exactly the keys which signed.
+    builder
+        .exec(list_keys_request(&[key_1, key_2], [2; 32]))
+        .expect_success()
+        .commit();
+}
@@ -0,0 +1,62 @@
+use engine_core::engine_state::run_genesis_request::RunGenesisRequest;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{AccountHash, ActionType},
+    runtime_args, ProtocolVersion, RuntimeArgs,
+};
+
+const CONTRACT_CAN_AUTHORIZE: &str = "can_authorize.wasm";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ARG_KEYS: &str = "keys";
+const ARG_ACTION_TYPE: &str = "action_type";
+const ARG_EXPECTED: &str = "expected";
+
+/// `can_authorize` is only available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+fn assert_can_authorize(keys: Vec<AccountHash>, action_type: ActionType, expected: bool) {
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_CAN_AUTHORIZE,
+        runtime_args! {
+            ARG_KEYS => keys,
+            ARG_ACTION_TYPE => action_type as u32,
+            ARG_EXPECTED => expected,
+        },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&run_genesis_request)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_authorize_with_identity_key() {
+    assert_can_authorize(vec![DEFAULT_ACCOUNT_ADDR], ActionType::Deployment, true);
+    assert_can_authorize(vec![DEFAULT_ACCOUNT_ADDR], ActionType::KeyManagement, true);
+}
+
+#[ignore]
+#[test]
+fn should_not_authorize_without_associated_keys() {
+    assert_can_authorize(vec![], ActionType::Deployment, false);
+    assert_can_authorize(vec![ACCOUNT_1_ADDR], ActionType::KeyManagement, false);
+}
@@ -0,0 +1,132 @@
+use engine_core::{engine_state::run_genesis_request::RunGenesisRequest, execution};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_EXEC_CONFIG, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{AccountHash, Weight},
+    runtime_args, ProtocolVersion, RuntimeArgs,
+};
+
+const CONTRACT_AUTHORIZED_KEYS: &str = "authorized_keys.wasm";
+const CONTRACT_KEY_ROTATION: &str = "key_rotation.wasm";
+const ARG_NEW_KEY: &str = "new_key";
+const ARG_WEIGHT: &str = "weight";
+const NEW_KEY: AccountHash = AccountHash::new([254; 32]);
+
+/// `authorized_keys.wasm` reads back thresholds and keys with host functions which are only
+/// available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+fn run_genesis_request() -> RunGenesisRequest {
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    )
+}
+
+fn authorized_keys_request(
+    authorization_key: AccountHash,
+    deploy_hash: [u8; 32],
+) -> ExecuteRequestBuilder {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+        .with_session_code(
+            CONTRACT_AUTHORIZED_KEYS,
+            runtime_args! {
+                "key_management_threshold" => Weight::new(1),
+                "deploy_threshold" => Weight::new(1),
+            },
+        )
+        .with_deploy_hash(deploy_hash)
+        .with_authorization_keys(&[authorization_key])
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy).with_protocol_version(protocol_version_2())
+}
+
+#[ignore]
+#[test]
+fn should_rotate_identity_key() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_KEY_ROTATION,
+        runtime_args! { ARG_NEW_KEY => NEW_KEY, ARG_WEIGHT => Weight::new(1) },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request())
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        account.get_associated_key_weight(DEFAULT_ACCOUNT_ADDR),
+        Some(&Weight::new(0))
+    );
+    assert_eq!(
+        account.get_associated_key_weight(NEW_KEY),
+        Some(&Weight::new(1))
+    );
+
+    // The original identity key on its own no longer meets the deployment threshold.
+    builder
+        .exec(authorized_keys_request(DEFAULT_ACCOUNT_ADDR, [2; 32]).build())
+        .commit();
+    let deploy_result = builder
+        .get_exec_response(1)
+        .expect("should have exec response")
+        .get(0)
+        .expect("should have at least one deploy result");
+    assert!(deploy_result.has_precondition_failure());
+    let message = format!("{}", deploy_result.as_error().unwrap());
+    assert!(message.contains(&format!(
+        "{}",
+        execution::Error::DeploymentAuthorizationFailure
+    )));
+
+    // The new key can deploy on the account's behalf.
+    builder
+        .exec(authorized_keys_request(NEW_KEY, [3; 32]).build())
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_rotate_to_key_below_thresholds() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_KEY_ROTATION,
+        runtime_args! { ARG_NEW_KEY => NEW_KEY, ARG_WEIGHT => Weight::new(0) },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request())
+        .exec(exec_request)
+        .commit();
+
+    assert!(builder.is_error());
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        account.get_associated_key_weight(DEFAULT_ACCOUNT_ADDR),
+        Some(&Weight::new(1))
+    );
+}
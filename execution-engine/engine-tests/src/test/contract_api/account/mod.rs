@@ -1,4 +1,6 @@
 mod associated_keys;
 mod authorized_keys;
+mod can_authorize;
 mod key_management_thresholds;
+mod key_rotation;
 mod named_keys;
@@ -0,0 +1,127 @@
+use engine_core::engine_state::run_genesis_request::RunGenesisRequest;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, ApiError, Key, ProtocolVersion, RuntimeArgs};
+
+const CONTRACT_ARGS_SCHEMA: &str = "args_schema.wasm";
+const ARG_COMMAND: &str = "command";
+const COMMAND_VALID: &str = "valid";
+const COMMAND_OMIT: &str = "omit";
+const COMMAND_WRONG_TYPE: &str = "wrong_type";
+const COMMAND_OMIT_NO_SCHEMA: &str = "omit_no_schema";
+const VALUE_KEY: &str = "value";
+const MISSING_ARGUMENT_KEY: &str = "missing_argument";
+const INVALID_ARGUMENT_KEY: &str = "invalid_argument";
+const PARAMETER_NAME: &str = "value";
+
+/// Arguments are only checked against entry point schemas from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+fn run_command(command: &str) -> InMemoryWasmTestBuilder {
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_ARGS_SCHEMA,
+        runtime_args! { ARG_COMMAND => command },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request).exec(exec_request);
+    builder
+}
+
+fn query_string(builder: &InMemoryWasmTestBuilder, name: &str) -> String {
+    builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+        .expect("should have named key")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should be String")
+}
+
+#[ignore]
+#[test]
+fn should_call_entry_point_with_declared_arguments() {
+    let mut builder = run_command(COMMAND_VALID);
+    builder.expect_success().commit();
+
+    let value: u64 = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[VALUE_KEY])
+        .expect("should have value")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should be u64");
+    assert_eq!(value, 42);
+}
+
+#[ignore]
+#[test]
+fn should_report_missing_argument_without_running_callee() {
+    let mut builder = run_command(COMMAND_OMIT);
+    builder.expect_success().commit();
+
+    assert_eq!(query_string(&builder, MISSING_ARGUMENT_KEY), PARAMETER_NAME);
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(
+        !account.named_keys().contains_key(VALUE_KEY),
+        "callee should not have run"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_report_invalid_argument_without_running_callee() {
+    let mut builder = run_command(COMMAND_WRONG_TYPE);
+    builder.expect_success().commit();
+
+    assert_eq!(query_string(&builder, INVALID_ARGUMENT_KEY), PARAMETER_NAME);
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(
+        !account.named_keys().contains_key(VALUE_KEY),
+        "callee should not have run"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_check_arguments_of_entry_point_without_schema() {
+    // The callee runs and reverts itself on failing to find its argument.
+    run_command(COMMAND_OMIT_NO_SCHEMA).expect_revert_with(ApiError::MissingArgument);
+}
+
+#[ignore]
+#[test]
+fn should_not_check_arguments_under_legacy_protocol_version() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_ARGS_SCHEMA,
+        runtime_args! { ARG_COMMAND => COMMAND_OMIT },
+    )
+    .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_revert_with(ApiError::MissingArgument);
+}
@@ -0,0 +1,69 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::run_genesis_request::RunGenesisRequest;
+use engine_shared::newtypes::Blake2bHash;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, CLValue, ProtocolVersion, RuntimeArgs, BLAKE2B_DIGEST_LENGTH};
+
+const CONTRACT_BLAKE2B: &str = "blake2b.wasm";
+const ARG_BYTES: &str = "bytes";
+const HASH_RESULT: &str = "hash_result";
+
+/// The blake2b host function is only available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+fn get_digest(input: Vec<u8>) -> [u8; BLAKE2B_DIGEST_LENGTH] {
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_BLAKE2B,
+        runtime_args! { ARG_BYTES => input },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+
+    builder.exec(exec_request).commit().expect_success();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let hash_result_key = account.named_keys()[HASH_RESULT];
+    let stored_value = builder
+        .query(None, hash_result_key, &[])
+        .expect("should have hash result");
+    CLValue::try_from(stored_value)
+        .expect("should be a CLValue")
+        .into_t()
+        .expect("should be a digest")
+}
+
+#[ignore]
+#[test]
+fn should_calculate_blake2b_digest_on_host() {
+    let inputs: Vec<Vec<u8>> = vec![
+        vec![],
+        b"CasperLabs".to_vec(),
+        (0..=u8::max_value()).cycle().take(10_000).collect(),
+    ];
+
+    for input in inputs {
+        let expected = Blake2bHash::new(&input).value();
+        let actual = get_digest(input);
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,98 @@
+use engine_core::engine_state::run_genesis_request::RunGenesisRequest;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, ApiError, ProtocolVersion, RuntimeArgs, U512};
+
+const CONTRACT_CREATE_NAMED_PURSE: &str = "create_named_purse_api.wasm";
+const CONTRACT_CHECK_NAMED_PURSE: &str = "check_named_purse.wasm";
+const ARG_AMOUNT: &str = "amount";
+const ARG_EXPECTED_BALANCE: &str = "expected_balance";
+const SAVINGS: &str = "savings";
+const SAVINGS_AMOUNT: u64 = 1_000_000;
+
+/// `create_named_purse` is only available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+fn exec(builder: &mut InMemoryWasmTestBuilder, contract: &str, args: RuntimeArgs) {
+    let exec_request = ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, contract, args)
+        .with_protocol_version(protocol_version_2())
+        .build();
+    builder.exec(exec_request).commit();
+}
+
+#[ignore]
+#[test]
+fn should_create_fund_and_read_back_named_purse() {
+    let mut builder = setup();
+    let amount = U512::from(SAVINGS_AMOUNT);
+
+    exec(
+        &mut builder,
+        CONTRACT_CREATE_NAMED_PURSE,
+        runtime_args! { ARG_AMOUNT => amount },
+    );
+    builder.expect_success();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let savings = account.named_keys()[SAVINGS]
+        .into_uref()
+        .expect("savings should be a uref");
+    assert_eq!(builder.get_purse_balance(savings), amount);
+
+    exec(
+        &mut builder,
+        CONTRACT_CHECK_NAMED_PURSE,
+        runtime_args! { ARG_EXPECTED_BALANCE => amount },
+    );
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_not_replace_existing_named_key() {
+    let mut builder = setup();
+    let amount = U512::from(SAVINGS_AMOUNT);
+
+    exec(
+        &mut builder,
+        CONTRACT_CREATE_NAMED_PURSE,
+        runtime_args! { ARG_AMOUNT => amount },
+    );
+    builder.expect_success();
+    let savings = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()[SAVINGS];
+
+    exec(
+        &mut builder,
+        CONTRACT_CREATE_NAMED_PURSE,
+        runtime_args! { ARG_AMOUNT => amount },
+    );
+    builder.expect_revert_with(ApiError::NamedKeyExists);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(account.named_keys()[SAVINGS], savings);
+}
@@ -0,0 +1,98 @@
+use std::collections::BTreeSet;
+
+use engine_core::{
+    engine_state::run_genesis_request::RunGenesisRequest, runtime_context::MAX_EVENTS_PER_DEPLOY,
+};
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::AccountHash, bytesrepr::ToBytes, runtime_args, ApiError, Key, ProtocolVersion,
+    RuntimeArgs, U512,
+};
+
+const CONTRACT_EMIT_TRANSFER_EVENTS: &str = "emit_transfer_events.wasm";
+const ARG_EVENT_COUNT: &str = "event_count";
+const TOPIC_TRANSFER: &str = "transfer";
+
+/// `emit_event` is only available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+fn emit_transfer_events(event_count: u8) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_EMIT_TRANSFER_EVENTS,
+        runtime_args! { ARG_EVENT_COUNT => event_count },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+    builder.run_genesis(&run_genesis_request).exec(exec_request);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_report_events_in_emission_order() {
+    let mut builder = emit_transfer_events(2);
+    builder.expect_success();
+
+    let events = builder.get_events(0).expect("should have events");
+    assert_eq!(events.len(), 2);
+    for (index, event) in events.iter().enumerate() {
+        let recipient = AccountHash::new([index as u8; 32]);
+        let amount = U512::from(index) + 1;
+        let expected_payload = (DEFAULT_ACCOUNT_ADDR, recipient, amount)
+            .to_bytes()
+            .unwrap();
+        assert_eq!(event.contract_key, Key::Account(DEFAULT_ACCOUNT_ADDR));
+        assert_eq!(event.topic, TOPIC_TRANSFER);
+        assert_eq!(event.payload, expected_payload);
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_write_events_to_global_state() {
+    let written_keys = |builder: &InMemoryWasmTestBuilder| -> BTreeSet<Key> {
+        builder
+            .get_transforms()
+            .last()
+            .expect("should have transforms")
+            .keys()
+            .copied()
+            .collect()
+    };
+
+    let without_events = emit_transfer_events(0);
+    let with_events = emit_transfer_events(2);
+
+    assert_eq!(written_keys(&without_events), written_keys(&with_events));
+}
+
+#[ignore]
+#[test]
+fn should_fail_when_exceeding_event_limit() {
+    let builder = emit_transfer_events(MAX_EVENTS_PER_DEPLOY as u8 + 1);
+
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have error message");
+    assert!(
+        error_message.contains(&format!("{:?}", ApiError::EventLimitExceeded)),
+        "{}",
+        error_message
+    );
+    assert_eq!(builder.get_events(0), Some(Vec::new()));
+}
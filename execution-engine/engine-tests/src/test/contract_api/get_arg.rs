@@ -83,3 +83,39 @@ fn should_revert_with_invalid_argument() {
         res2
     );
 }
+
+#[ignore]
+#[test]
+fn should_print_name_and_type_of_invalid_argument() {
+    let args = runtime_args! {
+        ARG_VALUE0 => String::from(ARG0_VALUE),
+        ARG_VALUE1 => ARG1_VALUE,
+    };
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_GET_ARG, args).build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder.take_printed_messages();
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have a response");
+    let error_message = utils::get_error_message(response);
+    assert!(
+        error_message.contains(&format!("{:?}", ApiError::InvalidArgument)),
+        "{}",
+        error_message
+    );
+
+    // A `u64` was passed where a `U512` was expected.
+    let printed_messages = builder.take_printed_messages();
+    assert!(
+        printed_messages.iter().any(|message| {
+            message.contains(ARG_VALUE1) && message.contains("U512") && message.contains("8 bytes")
+        }),
+        "{:?}",
+        printed_messages
+    );
+}
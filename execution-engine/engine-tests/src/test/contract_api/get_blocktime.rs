@@ -10,8 +10,17 @@ const ARG_KNOWN_BLOCK_TIME: &str = "known_block_time";
 #[ignore]
 #[test]
 fn should_run_get_blocktime_contract() {
-    let block_time: u64 = 42;
+    run_get_blocktime_contract(42);
+}
+
+#[ignore]
+#[test]
+fn should_saturate_blocktime_arithmetic_at_bounds() {
+    run_get_blocktime_contract(0);
+    run_get_blocktime_contract(u64::max_value());
+}
 
+fn run_get_blocktime_contract(block_time: u64) {
     let exec_request = ExecuteRequestBuilder::standard(
         DEFAULT_ACCOUNT_ADDR,
         CONTRACT_GET_BLOCKTIME,
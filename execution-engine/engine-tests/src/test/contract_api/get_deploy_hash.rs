@@ -0,0 +1,70 @@
+use engine_core::engine_state::run_genesis_request::RunGenesisRequest;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, Key, ProtocolVersion, RuntimeArgs};
+
+const CONTRACT_GET_DEPLOY_HASH: &str = "get_deploy_hash.wasm";
+const DEPLOY_HASH: &str = "deploy_hash";
+const ARG_AMOUNT: &str = "amount";
+const DEPLOY_HASH_1: [u8; 32] = [1; 32];
+const DEPLOY_HASH_2: [u8; 32] = [2; 32];
+
+/// The get_deploy_hash host function is only available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+fn exec_and_get_stored_deploy_hash(
+    builder: &mut InMemoryWasmTestBuilder,
+    deploy_hash: [u8; 32],
+) -> [u8; 32] {
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(CONTRACT_GET_DEPLOY_HASH, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash(deploy_hash)
+            .build();
+        ExecuteRequestBuilder::new()
+            .push_deploy(deploy)
+            .with_protocol_version(protocol_version_2())
+            .build()
+    };
+
+    builder.exec(exec_request).expect_success().commit();
+
+    builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[DEPLOY_HASH])
+        .expect("should have deploy hash")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should be [u8; 32]")
+}
+
+#[ignore]
+#[test]
+fn should_store_distinct_deploy_hash_for_each_deploy() {
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let stored_hash_1 = exec_and_get_stored_deploy_hash(&mut builder, DEPLOY_HASH_1);
+    assert_eq!(stored_hash_1, DEPLOY_HASH_1);
+
+    let stored_hash_2 = exec_and_get_stored_deploy_hash(&mut builder, DEPLOY_HASH_2);
+    assert_eq!(stored_hash_2, DEPLOY_HASH_2);
+
+    assert_ne!(stored_hash_1, stored_hash_2);
+}
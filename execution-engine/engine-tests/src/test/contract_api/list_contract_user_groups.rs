@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+
+use engine_core::engine_state::run_genesis_request::RunGenesisRequest;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, Key, ProtocolVersion, RuntimeArgs, URef};
+
+const CONTRACT_LIST_CONTRACT_USER_GROUPS: &str = "list_contract_user_groups.wasm";
+const ARG_FORGE_ACCESS_KEY: &str = "forge_access_key";
+const GROUP_A: &str = "group_a";
+const GROUP_B: &str = "group_b";
+const GROUPS_BEFORE_REMOVAL: &str = "groups_before_removal";
+const GROUPS_AFTER_REMOVAL: &str = "groups_after_removal";
+
+/// `list_contract_user_groups` is only available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+fn list_contract_user_groups(forge_access_key: bool) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_LIST_CONTRACT_USER_GROUPS,
+        runtime_args! { ARG_FORGE_ACCESS_KEY => forge_access_key },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+    builder
+        .run_genesis(&run_genesis_request)
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+fn query_groups(builder: &InMemoryWasmTestBuilder, name: &str) -> BTreeMap<String, Vec<URef>> {
+    builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+        .expect("should have groups")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should be BTreeMap<String, Vec<URef>>")
+}
+
+#[ignore]
+#[test]
+fn should_list_contract_user_groups_before_and_after_removal() {
+    let mut builder = list_contract_user_groups(false);
+    builder.expect_success();
+
+    let groups_before = query_groups(&builder, GROUPS_BEFORE_REMOVAL);
+    assert_eq!(groups_before.len(), 2);
+    assert_eq!(groups_before[GROUP_A].len(), 1);
+    assert_eq!(groups_before[GROUP_B].len(), 3);
+
+    let mut groups_after = query_groups(&builder, GROUPS_AFTER_REMOVAL);
+    assert_eq!(groups_after.len(), 1);
+    assert_eq!(
+        groups_after.remove(GROUP_A),
+        groups_before.get(GROUP_A).cloned()
+    );
+    assert!(!groups_after.contains_key(GROUP_B));
+}
+
+#[ignore]
+#[test]
+fn should_not_list_contract_user_groups_without_access_key() {
+    let builder = list_contract_user_groups(true);
+    assert!(builder.is_error());
+
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("ForgedReference"),
+        "{}",
+        error_message
+    );
+}
@@ -1,14 +1,29 @@
+use engine_core::engine_state::run_genesis_request::RunGenesisRequest;
 use engine_test_support::{
-    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_RUN_GENESIS_REQUEST,
+    },
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::{account::AccountHash, contracts::NamedKeys, runtime_args, Key, RuntimeArgs};
+use types::{
+    account::AccountHash, contracts::NamedKeys, runtime_args, ApiError, Key, ProtocolVersion,
+    RuntimeArgs,
+};
 
 const CONTRACT_LIST_NAMED_KEYS: &str = "list_named_keys.wasm";
+const CONTRACT_LIST_NAMED_KEYS_PAGED: &str = "list_named_keys_paged.wasm";
 const NEW_NAME_ACCOUNT: &str = "Account";
 const NEW_NAME_HASH: &str = "Hash";
 const ARG_INITIAL_NAMED_KEYS: &str = "initial_named_args";
 const ARG_NEW_NAMED_KEYS: &str = "new_named_keys";
+const ARG_TOTAL_NAMED_KEYS: &str = "total_named_keys";
+const ARG_PAGE_SIZE: &str = "page_size";
+
+/// The load_named_keys_chunk host function is only available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
 
 #[ignore]
 #[test]
@@ -42,3 +57,45 @@ fn should_list_named_keys() {
 
     builder.exec(exec_request).commit().expect_success();
 }
+
+fn exec_list_named_keys_paged(total_named_keys: u32, page_size: u32) -> InMemoryWasmTestBuilder {
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_LIST_NAMED_KEYS_PAGED,
+        runtime_args! {
+            ARG_TOTAL_NAMED_KEYS => total_named_keys,
+            ARG_PAGE_SIZE => page_size,
+        },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+
+    builder.exec(exec_request);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_list_named_keys_paged() {
+    let mut builder = exec_list_named_keys_paged(1_000, 64);
+    builder.commit().expect_success();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(account.named_keys().len(), 1_000);
+}
+
+#[ignore]
+#[test]
+fn should_reject_empty_page_size() {
+    exec_list_named_keys_paged(10, 0).expect_revert_with(ApiError::InvalidArgument);
+}
@@ -1,12 +1,22 @@
 mod account;
+mod args_schema;
+mod blake2b;
+mod create_named_purse;
 mod create_purse;
+mod dictionary;
+mod emit_event;
 mod get_arg;
 mod get_blocktime;
 mod get_caller;
+mod get_deploy_hash;
 mod get_phase;
+mod list_contract_user_groups;
 mod list_named_keys;
 mod main_purse;
 mod mint_purse;
+mod new_hash_address;
+mod option_tuple_return;
+mod remove;
 mod revert;
 mod subcall;
 mod transfer;
@@ -14,3 +24,7 @@ mod transfer_purse_to_account;
 mod transfer_purse_to_purse;
 mod transfer_stored;
 mod transfer_u512_stored;
+mod transfer_with_id;
+mod uref_attenuation;
+mod verify_ed25519;
+mod write_bytes;
@@ -0,0 +1,92 @@
+use engine_core::engine_state::run_genesis_request::RunGenesisRequest;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, Key, ProtocolVersion, RuntimeArgs};
+
+const CONTRACT_NEW_HASH_ADDRESS: &str = "new_hash_address.wasm";
+const ADDRESSES: &str = "addresses";
+const ARG_AMOUNT: &str = "amount";
+const DEPLOY_HASH_1: [u8; 32] = [1; 32];
+const DEPLOY_HASH_2: [u8; 32] = [2; 32];
+
+/// `new_hash_address` is only available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+fn exec_and_get_stored_addresses(
+    builder: &mut InMemoryWasmTestBuilder,
+    deploy_hash: [u8; 32],
+) -> ([u8; 32], [u8; 32]) {
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(CONTRACT_NEW_HASH_ADDRESS, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash(deploy_hash)
+            .build();
+        ExecuteRequestBuilder::new()
+            .push_deploy(deploy)
+            .with_protocol_version(protocol_version_2())
+            .build()
+    };
+
+    builder.exec(exec_request).expect_success().commit();
+
+    builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[ADDRESSES])
+        .expect("should have addresses")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should be ([u8; 32], [u8; 32])")
+}
+
+#[ignore]
+#[test]
+fn should_return_distinct_addresses_within_a_deploy() {
+    let mut builder = setup();
+
+    let (address_1, address_2) = exec_and_get_stored_addresses(&mut builder, DEPLOY_HASH_1);
+    assert_ne!(address_1, address_2);
+}
+
+#[ignore]
+#[test]
+fn should_return_same_addresses_when_deploy_is_replayed() {
+    let mut builder_1 = setup();
+    let mut builder_2 = setup();
+
+    let addresses_1 = exec_and_get_stored_addresses(&mut builder_1, DEPLOY_HASH_1);
+    let addresses_2 = exec_and_get_stored_addresses(&mut builder_2, DEPLOY_HASH_1);
+    assert_eq!(addresses_1, addresses_2);
+}
+
+#[ignore]
+#[test]
+fn should_return_different_addresses_for_different_deploys() {
+    let mut builder_1 = setup();
+    let mut builder_2 = setup();
+
+    let addresses_1 = exec_and_get_stored_addresses(&mut builder_1, DEPLOY_HASH_1);
+    let addresses_2 = exec_and_get_stored_addresses(&mut builder_2, DEPLOY_HASH_2);
+    assert_ne!(addresses_1, addresses_2);
+}
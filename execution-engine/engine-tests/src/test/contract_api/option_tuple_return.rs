@@ -0,0 +1,55 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, CLTyped, Key, RuntimeArgs, U512};
+
+const CONTRACT_OPTION_TUPLE_RETURN: &str = "option_tuple_return.wasm";
+const ARG_NAME: &str = "name";
+const ARG_AMOUNT: &str = "amount";
+const RESULT_KEY: &str = "option_tuple_result";
+
+type OptionTuple = Option<(String, U512)>;
+
+fn call_and_query(name: &str, amount: U512) -> OptionTuple {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_OPTION_TUPLE_RETURN,
+        runtime_args! { ARG_NAME => name.to_string(), ARG_AMOUNT => amount },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[RESULT_KEY])
+        .expect("should query result");
+    let cl_value = match stored_value {
+        StoredValue::CLValue(cl_value) => cl_value,
+        other => panic!("expected a CLValue, but got {:?}", other),
+    };
+    assert_eq!(*cl_value.cl_type(), OptionTuple::cl_type());
+    cl_value.into_t().expect("should convert")
+}
+
+#[ignore]
+#[test]
+fn should_return_some_tuple_from_contract() {
+    let amount = U512::from(123_456_789u64);
+    assert_eq!(
+        call_and_query("motes", amount),
+        Some(("motes".to_string(), amount))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_return_none_from_contract() {
+    assert_eq!(call_and_query("", U512::one()), None);
+}
@@ -0,0 +1,65 @@
+use engine_core::engine_state::run_genesis_request::RunGenesisRequest;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, Key, ProtocolVersion, RuntimeArgs};
+
+const CONTRACT_REMOVE_UREF: &str = "remove_uref.wasm";
+const ARG_COMMAND: &str = "command";
+const COMMAND_WRITE: &str = "write";
+const COMMAND_REMOVE: &str = "remove";
+const REMOVABLE_KEY: &str = "removable";
+const VALUE: u64 = 42;
+
+/// The remove host function is only available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+fn run_command(builder: &mut InMemoryWasmTestBuilder, command: &str) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_REMOVE_UREF,
+        runtime_args! { ARG_COMMAND => command },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_remove_value_from_global_state() {
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    run_command(&mut builder, COMMAND_WRITE);
+    let removable_key: Key = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()[REMOVABLE_KEY];
+    let value: u64 = builder
+        .query(None, removable_key, &[])
+        .expect("should have value")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should be u64");
+    assert_eq!(value, VALUE);
+
+    run_command(&mut builder, COMMAND_REMOVE);
+    assert!(
+        builder.query(None, removable_key, &[]).is_err(),
+        "value should have been removed"
+    );
+}
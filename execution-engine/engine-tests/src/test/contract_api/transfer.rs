@@ -1,7 +1,7 @@
 use lazy_static::lazy_static;
 
 use engine_core::engine_state::CONV_RATE;
-use engine_shared::motes::Motes;
+use engine_shared::{motes::Motes, transform::Transform};
 use engine_test_support::{
     internal::{
         utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
@@ -9,7 +9,7 @@ use engine_test_support::{
     },
     DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
 };
-use types::{account::AccountHash, runtime_args, ApiError, RuntimeArgs, U512};
+use types::{account::AccountHash, runtime_args, ApiError, Key, RuntimeArgs, URef, U512};
 
 const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
 const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
@@ -352,3 +352,116 @@ fn should_transfer_total_amount() {
         .expect_success()
         .finish();
 }
+
+#[ignore]
+#[test]
+fn should_merge_concurrent_debits_from_same_purse() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    // create both target accounts first so that the concurrent deploys only move funds
+    for target in &[ACCOUNT_1_ADDR, ACCOUNT_2_ADDR] {
+        let exec_request = ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! { ARG_TARGET => *target, ARG_AMOUNT => *TRANSFER_1_AMOUNT },
+        )
+        .build();
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    let default_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account")
+        .main_purse();
+    let account_1_purse = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1")
+        .main_purse();
+    let account_2_purse = builder
+        .get_account(ACCOUNT_2_ADDR)
+        .expect("should have account 2")
+        .main_purse();
+
+    let default_balance_before = builder.get_purse_balance(default_purse);
+    let account_1_balance_before = builder.get_purse_balance(account_1_purse);
+    let account_2_balance_before = builder.get_purse_balance(account_2_purse);
+
+    let pre_state_hash = builder.get_post_state_hash();
+
+    // execute both deploys against the same pre-state without committing in between
+    for target in &[ACCOUNT_1_ADDR, ACCOUNT_2_ADDR] {
+        let exec_request = ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! { ARG_TARGET => *target, ARG_AMOUNT => *TRANSFER_2_AMOUNT },
+        )
+        .build();
+        builder.exec(exec_request).expect_success();
+    }
+
+    let balance_key = |purse: URef| {
+        let balance_mapping_key = Key::Hash(purse.addr());
+        builder
+            .query(None, balance_mapping_key, &[])
+            .expect("should have balance mapping")
+            .as_cl_value()
+            .cloned()
+            .expect("should be CLValue")
+            .into_t::<Key>()
+            .expect("should be Key")
+            .normalize()
+    };
+    let default_balance_key = balance_key(default_purse);
+
+    // Neither deploy reads the balance they both debit, nor those they credit.  They still
+    // conflict on the proof-of-stake payment purse, which every deploy pays into and is refunded
+    // from.
+    let exec_responses = builder.get_exec_responses();
+    let deploy_1_effect = exec_responses[exec_responses.len() - 2][0].effect();
+    let deploy_2_effect = exec_responses[exec_responses.len() - 1][0].effect();
+    let conflicts = deploy_1_effect.conflicts_with(deploy_2_effect);
+    for purse in &[default_purse, account_1_purse, account_2_purse] {
+        assert!(
+            !conflicts.contains(&balance_key(*purse)),
+            "{:?} should not conflict",
+            purse
+        );
+    }
+
+    let transforms = builder.get_transforms();
+    let deploy_1_effects = &transforms[transforms.len() - 2];
+    let deploy_2_effects = &transforms[transforms.len() - 1];
+
+    let mut deploy_debits = U512::zero();
+    for effects in &[deploy_1_effects, deploy_2_effects] {
+        match effects.get(&default_balance_key) {
+            Some(Transform::SubUInt512(debit)) => deploy_debits += *debit,
+            other => panic!("expected the source purse to be debited, got {:?}", other),
+        }
+    }
+
+    let mut merged_effects = deploy_1_effects.clone();
+    for (key, transform) in deploy_2_effects.iter() {
+        merged_effects.insert_add(*key, transform.clone());
+    }
+    assert_eq!(
+        merged_effects.get(&default_balance_key),
+        Some(&Transform::SubUInt512(deploy_debits))
+    );
+
+    builder.commit_effects(pre_state_hash, merged_effects);
+
+    assert_eq!(
+        builder.get_purse_balance(default_purse),
+        default_balance_before - deploy_debits
+    );
+    assert_eq!(
+        builder.get_purse_balance(account_1_purse),
+        account_1_balance_before + *TRANSFER_2_AMOUNT
+    );
+    assert_eq!(
+        builder.get_purse_balance(account_2_purse),
+        account_2_balance_before + *TRANSFER_2_AMOUNT
+    );
+}
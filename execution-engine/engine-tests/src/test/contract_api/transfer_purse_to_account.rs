@@ -1,12 +1,19 @@
 use std::convert::TryFrom;
 
 use lazy_static::lazy_static;
+use tempfile::TempDir;
 
+use engine_core::{
+    engine_state::{EngineConfig, EngineState},
+    execution,
+};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
 use engine_shared::{stored_value::StoredValue, transform::Transform};
+use engine_storage::global_state::StateProvider;
 use engine_test_support::{
     internal::{
-        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
-        DEFAULT_RUN_GENESIS_REQUEST,
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, LmdbWasmTestBuilder, WasmTestBuilder,
+        ARG_AMOUNT, DEFAULT_BLOCK_TIME, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
     },
     DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
 };
@@ -15,30 +22,29 @@ use types::{
     TransferredTo, U512,
 };
 
+const CONTRACT_STANDARD_PAYMENT: &str = "standard_payment.wasm";
 const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
 const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
 lazy_static! {
     static ref ACCOUNT_1_INITIAL_FUND: U512 = *DEFAULT_PAYMENT + 42;
 }
 
-#[ignore]
-#[test]
-fn should_run_purse_to_account_transfer() {
+/// Runs the purse to account transfer test against `builder`, so that it can be checked with
+/// each global state backend.
+fn run_purse_to_account_transfer<S>(mut builder: WasmTestBuilder<S>)
+where
+    S: StateProvider + Sync,
+    S::Error: Into<execution::Error>,
+    EngineState<S>: ExecutionEngineService,
+{
     let account_1_account_hash = ACCOUNT_1_ADDR;
     let genesis_account_hash = DEFAULT_ACCOUNT_ADDR;
-    let exec_request_1 = ExecuteRequestBuilder::standard(
-        DEFAULT_ACCOUNT_ADDR,
-        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
-        runtime_args! { "target" => account_1_account_hash, "amount" => *ACCOUNT_1_INITIAL_FUND },
-    )
-    .build();
     let exec_request_2 = ExecuteRequestBuilder::standard(
         account_1_account_hash,
         CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
         runtime_args! { "target" => genesis_account_hash, "amount" => U512::from(1) },
     )
     .build();
-    let mut builder = InMemoryWasmTestBuilder::default();
 
     //
     // Exec 1 - New account [42; 32] is created
@@ -46,7 +52,14 @@ fn should_run_purse_to_account_transfer() {
 
     builder
         .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
-        .exec(exec_request_1)
+        .exec_with_payment(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_STANDARD_PAYMENT,
+            runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT },
+            CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+            runtime_args! { "target" => account_1_account_hash, "amount" => *ACCOUNT_1_INITIAL_FUND },
+            DEFAULT_BLOCK_TIME,
+        )
         .expect_success()
         .commit();
 
@@ -55,6 +68,15 @@ fn should_run_purse_to_account_transfer() {
         .get_account(DEFAULT_ACCOUNT_ADDR)
         .expect("should get genesis account");
 
+    // The payment purse is emptied by finalization, and whatever isn't refunded is paid to the
+    // rewards purse
+    assert_eq!(builder.get_pos_payment_purse_balance(), U512::zero());
+    assert_eq!(
+        builder.get_purse_balance(default_account.main_purse())
+            + builder.get_pos_rewards_purse_balance(),
+        U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE) - *ACCOUNT_1_INITIAL_FUND
+    );
+
     // Obtain main purse's balance
     let final_balance_key = default_account.named_keys()["final_balance"].normalize();
     let final_balance = CLValue::try_from(
@@ -192,6 +214,81 @@ fn should_run_purse_to_account_transfer() {
     assert_eq!(updated_balance, &Transform::AddUInt512(U512::from(1)));
 }
 
+#[ignore]
+#[test]
+fn should_run_purse_to_account_transfer() {
+    run_purse_to_account_transfer(InMemoryWasmTestBuilder::default());
+}
+
+#[ignore]
+#[test]
+fn should_run_purse_to_account_transfer_with_lmdb() {
+    let data_dir = TempDir::new().expect("should create temp dir");
+    run_purse_to_account_transfer(LmdbWasmTestBuilder::new(data_dir.path()));
+}
+
+#[ignore]
+#[test]
+fn should_continue_purse_to_account_transfer_after_reopening_lmdb() {
+    let data_dir = TempDir::new().expect("should create temp dir");
+
+    let post_state_hash = {
+        let mut builder = LmdbWasmTestBuilder::new(data_dir.path());
+        builder
+            .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+            .exec_with_payment(
+                DEFAULT_ACCOUNT_ADDR,
+                CONTRACT_STANDARD_PAYMENT,
+                runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT },
+                CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+                runtime_args! { "target" => ACCOUNT_1_ADDR, "amount" => *ACCOUNT_1_INITIAL_FUND },
+                DEFAULT_BLOCK_TIME,
+            )
+            .expect_success()
+            .commit();
+        builder.get_post_state_hash()
+    };
+
+    // The first builder, and with it the LMDB environment, has been dropped; continue from the
+    // recorded root using the data left on disk.
+    let mut builder =
+        LmdbWasmTestBuilder::open(data_dir.path(), EngineConfig::default(), post_state_hash);
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1 after reopening");
+    assert_eq!(
+        builder.get_purse_balance(account_1.main_purse()),
+        *ACCOUNT_1_INITIAL_FUND
+    );
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        runtime_args! { "target" => DEFAULT_ACCOUNT_ADDR, "amount" => U512::from(1) },
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should get account 1");
+    let transfer_result_key = account_1.named_keys()["transfer_result"].normalize();
+    let transfer_result = CLValue::try_from(
+        builder
+            .query(None, transfer_result_key, &[])
+            .expect("should have transfer result"),
+    )
+    .expect("should be a CLValue")
+    .into_t::<String>()
+    .expect("should be String");
+    assert_eq!(
+        transfer_result,
+        format!("{:?}", TransferResult::Ok(TransferredTo::ExistingAccount))
+    );
+    assert_eq!(builder.get_transforms().len(), 1);
+}
+
 #[ignore]
 #[test]
 fn should_fail_when_sending_too_much_from_purse_to_account() {
@@ -1,6 +1,8 @@
 use std::convert::TryFrom;
 
-use types::{runtime_args, ApiError, CLValue, Key, RuntimeArgs, U512};
+use types::{
+    runtime_args, system_contract_errors::mint, ApiError, CLValue, Key, RuntimeArgs, U512,
+};
 
 use engine_test_support::{
     internal::{
@@ -11,6 +13,9 @@ use engine_test_support::{
 };
 
 const CONTRACT_TRANSFER_PURSE_TO_PURSE: &str = "transfer_purse_to_purse.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_INVALID_PURSE: &str = "transfer_purse_to_invalid_purse.wasm";
+const TARGET_UNKNOWN: &str = "unknown";
+const TARGET_READ_ONLY: &str = "read_only";
 const PURSE_TO_PURSE_AMOUNT: u64 = 42;
 const ARG_SOURCE: &str = "source";
 const ARG_TARGET: &str = "target";
@@ -148,7 +153,10 @@ fn should_run_purse_to_purse_transfer_with_error() {
     // Main assertion for the result of `transfer_from_purse_to_purse`
     assert_eq!(
         purse_transfer_result,
-        format!("{:?}", Result::<(), _>::Err(ApiError::Transfer)),
+        format!(
+            "{:?}",
+            Result::<(), _>::Err(ApiError::from(mint::Error::InsufficientFunds))
+        ),
     );
 
     // Obtain main purse's balance
@@ -199,3 +207,32 @@ fn should_run_purse_to_purse_transfer_with_error() {
         U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE) - *DEFAULT_PAYMENT
     );
 }
+
+fn transfer_to_invalid_purse(target: &str) -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_INVALID_PURSE,
+        runtime_args! { ARG_TARGET => target, ARG_AMOUNT => U512::from(PURSE_TO_PURSE_AMOUNT) },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_fail_purse_to_purse_transfer_to_unknown_purse() {
+    transfer_to_invalid_purse(TARGET_UNKNOWN)
+        .expect_revert_with(ApiError::from(mint::Error::DestNotFound));
+}
+
+#[ignore]
+#[test]
+fn should_fail_purse_to_purse_transfer_to_purse_without_add_access() {
+    transfer_to_invalid_purse(TARGET_READ_ONLY)
+        .expect_revert_with(ApiError::from(mint::Error::InvalidAccessRights));
+}
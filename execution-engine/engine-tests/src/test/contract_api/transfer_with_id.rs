@@ -0,0 +1,194 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{run_genesis_request::RunGenesisRequest, Error},
+    execution,
+};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::AccountHash, runtime_args, Key, ProtocolVersion, RuntimeArgs, TransferRecord, U512,
+};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT_WITH_ID: &str = "transfer_to_account_with_id.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const DEPLOY_HASH: [u8; 32] = [42; 32];
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+const ID_1: u64 = 123;
+const ID_2: u64 = 456;
+const ARG_AMOUNT: &str = "amount";
+const ARG_TARGET: &str = "target";
+const ARG_ID_1: &str = "id_1";
+const ARG_ID_2: &str = "id_2";
+
+/// The transfer host functions taking an id are only available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+fn run_genesis(builder: &mut InMemoryWasmTestBuilder, protocol_version: ProtocolVersion) {
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version,
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    builder.run_genesis(&run_genesis_request);
+}
+
+fn exec(
+    builder: &mut InMemoryWasmTestBuilder,
+    protocol_version: ProtocolVersion,
+    session_file: &str,
+    session_args: RuntimeArgs,
+) -> &mut InMemoryWasmTestBuilder {
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(session_file, session_args)
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash(DEPLOY_HASH)
+            .build();
+        ExecuteRequestBuilder::new()
+            .push_deploy(deploy)
+            .with_protocol_version(protocol_version)
+            .build()
+    };
+
+    builder.exec(exec_request)
+}
+
+fn transfer_with_ids_args() -> RuntimeArgs {
+    runtime_args! {
+        ARG_TARGET => ACCOUNT_1_ADDR,
+        ARG_AMOUNT => U512::from(TRANSFER_AMOUNT),
+        ARG_ID_1 => ID_1,
+        ARG_ID_2 => ID_2,
+    }
+}
+
+fn query_transfer_record(builder: &InMemoryWasmTestBuilder, index: u32) -> Option<TransferRecord> {
+    let key = Key::Hash(TransferRecord::addr(DEPLOY_HASH, index));
+    let stored_value = builder.query(None, key, &[]).ok()?;
+    let record = stored_value
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should be TransferRecord");
+    Some(record)
+}
+
+#[ignore]
+#[test]
+fn should_record_transfers_with_ids() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    run_genesis(&mut builder, protocol_version_2());
+
+    exec(
+        &mut builder,
+        protocol_version_2(),
+        CONTRACT_TRANSFER_TO_ACCOUNT_WITH_ID,
+        transfer_with_ids_args(),
+    )
+    .expect_success()
+    .commit();
+
+    let main_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get default account")
+        .main_purse();
+
+    let first = query_transfer_record(&builder, 0).expect("should have first record");
+    assert_eq!(
+        first,
+        TransferRecord::new(
+            DEPLOY_HASH,
+            main_purse.addr(),
+            ACCOUNT_1_ADDR,
+            U512::from(TRANSFER_AMOUNT),
+            Some(ID_1),
+        )
+    );
+
+    let second = query_transfer_record(&builder, 1).expect("should have second record");
+    assert_eq!(
+        second,
+        TransferRecord::new(
+            DEPLOY_HASH,
+            main_purse.addr(),
+            ACCOUNT_1_ADDR,
+            U512::from(TRANSFER_AMOUNT),
+            Some(ID_2),
+        )
+    );
+
+    assert!(query_transfer_record(&builder, 2).is_none());
+}
+
+#[ignore]
+#[test]
+fn should_record_transfer_without_id() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    exec(
+        &mut builder,
+        *DEFAULT_PROTOCOL_VERSION,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => ACCOUNT_1_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT),
+        },
+    )
+    .expect_success()
+    .commit();
+
+    let record = query_transfer_record(&builder, 0).expect("should have record");
+    assert_eq!(record.target, ACCOUNT_1_ADDR);
+    assert_eq!(record.amount, U512::from(TRANSFER_AMOUNT));
+    assert_eq!(record.id, None);
+
+    assert!(query_transfer_record(&builder, 1).is_none());
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_with_id_before_protocol_version_2() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    exec(
+        &mut builder,
+        *DEFAULT_PROTOCOL_VERSION,
+        CONTRACT_TRANSFER_TO_ACCOUNT_WITH_ID,
+        transfer_with_ids_args(),
+    )
+    .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    let error = response[0].as_error().expect("should have error");
+    // The contract imports both transfer functions taking an id; either may be resolved first.
+    let (name, required_version) = assert_matches!(
+        error,
+        Error::Exec(execution::Error::UnsupportedHostFunction { name, required_version })
+            => (name, *required_version)
+    );
+    assert!(
+        name == "transfer_to_account_with_id" || name == "transfer_from_purse_to_account_with_id",
+        "unexpected host function {}",
+        name
+    );
+    assert_eq!(required_version, protocol_version_2());
+
+    assert!(query_transfer_record(&builder, 0).is_none());
+}
@@ -0,0 +1,54 @@
+use ed25519_dalek::{Keypair, PublicKey, SecretKey};
+
+use engine_core::engine_state::run_genesis_request::RunGenesisRequest;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, ProtocolVersion, RuntimeArgs};
+
+const CONTRACT_VERIFY_ED25519: &str = "verify_ed25519.wasm";
+const ARG_PUBLIC_KEY: &str = "public_key";
+const ARG_SIGNATURE: &str = "signature";
+const ARG_MESSAGE: &str = "message";
+const MESSAGE: &[u8] = b"Transfer 100 motes from escrow to counterparty";
+
+/// The verify_ed25519 host function is only available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+#[ignore]
+#[test]
+fn should_verify_ed25519_signature() {
+    let secret = SecretKey::from_bytes(&[7; 32]).expect("should create secret key");
+    let public = PublicKey::from(&secret);
+    let keypair = Keypair { secret, public };
+    let signature = keypair.sign(MESSAGE);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_VERIFY_ED25519,
+        runtime_args! {
+            ARG_PUBLIC_KEY => keypair.public.to_bytes(),
+            ARG_SIGNATURE => signature.to_bytes(),
+            ARG_MESSAGE => MESSAGE.to_vec(),
+        },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&run_genesis_request)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
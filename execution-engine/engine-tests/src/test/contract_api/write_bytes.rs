@@ -0,0 +1,90 @@
+use engine_core::{engine_state::EngineConfig, resolvers::v1_function_index::FunctionIndex};
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
+        DEFAULT_WASM_COSTS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_WRITE_BYTES: &str = "write_bytes.wasm";
+const ARG_BYTES: &str = "bytes";
+const SMALL_VALUE_LENGTH: usize = 10;
+const LARGE_VALUE_LENGTH: usize = 100_000;
+const LOW_MAX_STORED_VALUE_SIZE: u32 = 1_000;
+
+fn write_bytes(builder: &mut InMemoryWasmTestBuilder, length: usize) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_WRITE_BYTES,
+        runtime_args! { ARG_BYTES => vec![1u8; length] },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn write_bytes_gas_cost(length: usize) -> Gas {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_WRITE_BYTES,
+        runtime_args! { ARG_BYTES => vec![1u8; length] },
+    )
+    .build();
+
+    builder.exec(exec_request).commit().expect_success();
+    builder.last_exec_gas_cost()
+}
+
+#[ignore]
+#[test]
+fn should_charge_more_gas_for_writing_larger_value() {
+    let small_cost = write_bytes_gas_cost(SMALL_VALUE_LENGTH);
+    let large_cost = write_bytes_gas_cost(LARGE_VALUE_LENGTH);
+
+    let write_cost = DEFAULT_WASM_COSTS
+        .host_function_costs
+        .get(FunctionIndex::WriteFuncIndex.into());
+    let extra_bytes = (LARGE_VALUE_LENGTH - SMALL_VALUE_LENGTH) as u32;
+    let min_extra_cost = Gas::new(U512::from(write_cost.bytes_cost(extra_bytes)));
+
+    assert!(
+        large_cost > small_cost + min_extra_cost,
+        "writing {} bytes cost {} but writing {} bytes cost {}",
+        LARGE_VALUE_LENGTH,
+        large_cost,
+        SMALL_VALUE_LENGTH,
+        small_cost
+    );
+}
+
+#[ignore]
+#[test]
+fn should_write_large_value_with_default_config() {
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(EngineConfig::new());
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    write_bytes(&mut builder, LARGE_VALUE_LENGTH);
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_write_value_larger_than_configured_max() {
+    let engine_config = EngineConfig::new().with_max_stored_value_size(LOW_MAX_STORED_VALUE_SIZE);
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    write_bytes(&mut builder, SMALL_VALUE_LENGTH);
+    builder.expect_success();
+
+    write_bytes(&mut builder, LARGE_VALUE_LENGTH);
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have error message");
+    assert!(error_message.contains("ValueTooLarge"), "{}", error_message);
+}
@@ -1,3 +1,4 @@
+use engine_core::{engine_state::Error, execution};
 use engine_test_support::{
     internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
     DEFAULT_ACCOUNT_ADDR,
@@ -195,3 +196,118 @@ fn should_call_counter_contract_directly() {
 
     assert_eq!(value, 1);
 }
+
+fn setup() -> (InMemoryWasmTestBuilder, Key) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_COUNTER_DEFINE,
+        RuntimeArgs::new(),
+    )
+    .build();
+
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let counter_contract_hash_key = *builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[])
+        .expect("should query account")
+        .as_account()
+        .expect("should be account")
+        .named_keys()
+        .get(COUNTER_CONTRACT_HASH_KEY_NAME)
+        .expect("should have counter contract hash key");
+
+    (builder, counter_contract_hash_key)
+}
+
+fn query_counter(builder: &InMemoryWasmTestBuilder, counter_contract_hash_key: Key) -> i32 {
+    builder
+        .query(None, counter_contract_hash_key, &[COUNTER_VALUE_UREF])
+        .expect("should have counter value")
+        .as_cl_value()
+        .expect("should be CLValue")
+        .clone()
+        .into_t()
+        .expect("should cast CLValue to integer")
+}
+
+#[ignore]
+#[test]
+fn should_call_stored_counter_contract_by_hash() {
+    let (mut builder, counter_contract_hash_key) = setup();
+    let counter_contract_hash = counter_contract_hash_key
+        .into_hash()
+        .expect("should be hash");
+
+    for expected in 1..=2 {
+        let exec_request = ExecuteRequestBuilder::contract_call_by_hash(
+            DEFAULT_ACCOUNT_ADDR,
+            counter_contract_hash,
+            ENTRYPOINT_COUNTER,
+            runtime_args! { ARG_COUNTER_METHOD => METHOD_INC },
+        )
+        .build();
+
+        builder.exec(exec_request).expect_success().commit();
+
+        assert_eq!(query_counter(&builder, counter_contract_hash_key), expected);
+    }
+}
+
+#[ignore]
+#[test]
+fn should_call_stored_counter_contract_by_name() {
+    let (mut builder, counter_contract_hash_key) = setup();
+
+    let exec_request = ExecuteRequestBuilder::contract_call_by_name(
+        DEFAULT_ACCOUNT_ADDR,
+        COUNTER_CONTRACT_HASH_KEY_NAME,
+        ENTRYPOINT_COUNTER,
+        runtime_args! { ARG_COUNTER_METHOD => METHOD_INC },
+    )
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    assert_eq!(query_counter(&builder, counter_contract_hash_key), 1);
+}
+
+#[ignore]
+#[test]
+fn should_fail_precondition_calling_missing_contract_by_hash() {
+    let (mut builder, _) = setup();
+    let missing_contract_hash = [255u8; 32];
+
+    let exec_request = ExecuteRequestBuilder::contract_call_by_hash(
+        DEFAULT_ACCOUNT_ADDR,
+        missing_contract_hash,
+        ENTRYPOINT_COUNTER,
+        runtime_args! { ARG_COUNTER_METHOD => METHOD_INC },
+    )
+    .build();
+
+    builder.exec(exec_request).commit();
+
+    assert!(builder.is_precondition_failure());
+    let exec_response = builder
+        .get_exec_response(1)
+        .expect("should have exec response");
+    let error = exec_response[0].as_error().expect("should have error");
+    assert!(
+        matches_key_not_found(error, Key::Hash(missing_contract_hash)),
+        "unexpected error: {:?}",
+        error
+    );
+}
+
+fn matches_key_not_found(error: &Error, expected: Key) -> bool {
+    match error {
+        Error::Exec(execution::Error::KeyNotFound(key)) => *key == expected,
+        _ => false,
+    }
+}
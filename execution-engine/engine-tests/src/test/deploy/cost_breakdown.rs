@@ -0,0 +1,99 @@
+use engine_core::{
+    engine_state::{genesis::ExecConfig, run_genesis_request::RunGenesisRequest},
+    resolvers::v1_function_index::FunctionIndex,
+};
+use engine_shared::gas::CostCategory;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+        DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+        STANDARD_PAYMENT_INSTALL_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::wasm_costs::HostFunctionCost;
+use types::{runtime_args, RuntimeArgs};
+
+const CONTRACT_HEAVY_WRITE: &str = "heavy_write.wasm";
+const ARG_LENGTH: &str = "length";
+const VALUE_LENGTH: u32 = 10_000;
+/// Per-byte cost of `write` which outweighs the opcodes spent building and serializing the value.
+const WRITE_COST_PER_BYTE: u32 = 1_000;
+
+fn write_priced_run_genesis_request() -> RunGenesisRequest {
+    let mut wasm_costs = *DEFAULT_WASM_COSTS;
+    let write_index = FunctionIndex::WriteFuncIndex.into();
+    let write_cost = wasm_costs.host_function_costs.get(write_index);
+    assert!(wasm_costs.host_function_costs.set(
+        write_index,
+        HostFunctionCost::new(write_cost.cost, WRITE_COST_PER_BYTE)
+    ));
+
+    let exec_config = ExecConfig::new(
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT),
+        DEFAULT_ACCOUNTS.clone(),
+        wasm_costs,
+    );
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+fn exec_heavy_write(run_genesis_request: &RunGenesisRequest) -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_HEAVY_WRITE,
+        runtime_args! { ARG_LENGTH => VALUE_LENGTH },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(run_genesis_request)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_sum_cost_breakdown_to_total_cost() {
+    let builder = exec_heavy_write(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let cost = builder.exec_costs(0)[0];
+    let cost_breakdown = builder.get_cost_breakdown(0);
+    assert_eq!(cost_breakdown.total(), cost);
+    for category in CostCategory::ALL.iter() {
+        assert!(
+            cost_breakdown.get(*category) > Default::default(),
+            "{} should have been charged",
+            category
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_attribute_heavy_write_to_host_function_bytes() {
+    let builder = exec_heavy_write(&write_priced_run_genesis_request());
+
+    let cost_breakdown = builder.get_cost_breakdown(0);
+    assert_eq!(cost_breakdown.total(), builder.exec_costs(0)[0]);
+
+    let bytes_cost = cost_breakdown.get(CostCategory::HostFunctionBytes);
+    let opcode_cost = cost_breakdown.get(CostCategory::Opcode);
+    let host_function_cost = cost_breakdown.get(CostCategory::HostFunction);
+    assert!(
+        bytes_cost > opcode_cost && bytes_cost > host_function_cost,
+        "host function bytes cost {} should dominate opcode cost {} and host function cost {}",
+        bytes_cost,
+        opcode_cost,
+        host_function_cost
+    );
+}
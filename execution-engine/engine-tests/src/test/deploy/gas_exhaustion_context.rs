@@ -0,0 +1,70 @@
+use engine_core::resolvers::v1_function_index::FunctionIndex;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_KEY,
+};
+use types::{runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_GAS_STARVED: &str = "gas_starved.wasm";
+const COUNTER_KEY: &str = "gas_starved_counter";
+const ARG_AMOUNT: &str = "amount";
+const PAYMENT_AMOUNT: u64 = 10_000_000;
+
+#[ignore]
+#[test]
+fn should_record_context_when_out_of_gas() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    // The first run only creates the counter.
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GAS_STARVED,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+    assert_eq!(builder.get_gas_exhaustion_context(0), None);
+
+    let counter_key = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(COUNTER_KEY)
+        .expect("should have counter")
+        .normalize();
+
+    // The second run writes the counter until it runs out of gas.
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash([2; 32])
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => U512::from(PAYMENT_AMOUNT) })
+            .with_session_code(CONTRACT_GAS_STARVED, RuntimeArgs::default())
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .build();
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+    builder.exec(exec_request).commit();
+    assert!(builder.is_error());
+
+    let context = builder
+        .get_gas_exhaustion_context(1)
+        .expect("should have gas exhaustion context");
+    assert!(
+        context.keys_written.contains(&counter_key),
+        "{:?} should be written in {:?}",
+        counter_key,
+        context
+    );
+    assert!(context
+        .keys_read
+        .contains(&Key::Account(DEFAULT_ACCOUNT_ADDR)));
+    assert_eq!(
+        context.last_host_function,
+        Some(FunctionIndex::WriteFuncIndex)
+    );
+}
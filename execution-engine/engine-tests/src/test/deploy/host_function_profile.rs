@@ -0,0 +1,57 @@
+use engine_core::resolvers::v1_function_index::FunctionIndex;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, RuntimeArgs};
+
+const CONTRACT_READ_MANY: &str = "read_many.wasm";
+const ARG_COUNT: &str = "count";
+const READ_COUNT: u32 = 100;
+
+fn exec_read_many(builder: &mut InMemoryWasmTestBuilder, count: u32) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_READ_MANY,
+        runtime_args! { ARG_COUNT => count },
+    )
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_profile_host_function_calls() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .with_profiling()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    exec_read_many(&mut builder, 0);
+    exec_read_many(&mut builder, READ_COUNT);
+
+    // The payment code makes the same calls in both deploys, so the sessions account for the
+    // difference.
+    let baseline = builder.get_host_function_profile(0);
+    let profile = builder.get_host_function_profile(1);
+    let read_stats = profile.get(FunctionIndex::ReadFuncIndex);
+    assert_eq!(
+        read_stats.count - baseline.get(FunctionIndex::ReadFuncIndex).count,
+        u64::from(READ_COUNT)
+    );
+    assert!(read_stats.total_nanos > 0);
+    assert_eq!(
+        profile.get(FunctionIndex::NewFuncIndex).count,
+        baseline.get(FunctionIndex::NewFuncIndex).count
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_profile_host_function_calls_by_default() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    exec_read_many(&mut builder, READ_COUNT);
+
+    assert!(builder.get_host_function_profile(0).is_empty());
+}
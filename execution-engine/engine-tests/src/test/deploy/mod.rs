@@ -1,3 +1,8 @@
+mod cost_breakdown;
+mod gas_exhaustion_context;
+mod host_function_profile;
 mod non_standard_payment;
+mod payment_phase_restrictions;
 mod preconditions;
 mod stored_contracts;
+mod wasm_limits;
@@ -0,0 +1,70 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{Error, MAX_PAYMENT},
+    execution,
+    resolvers::v1_function_index::FunctionIndex,
+};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, Phase, RuntimeArgs, U512};
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const PAYMENT_ADD_ASSOCIATED_KEY_WASM: &str = "payment_add_associated_key.wasm";
+const ARG_ACCOUNT: &str = "account";
+const ARG_AMOUNT: &str = "amount";
+
+#[ignore]
+#[test]
+fn should_not_allow_key_management_during_payment() {
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash([1; 32])
+            .with_payment_code(
+                PAYMENT_ADD_ASSOCIATED_KEY_WASM,
+                runtime_args! { ARG_ACCOUNT => ACCOUNT_1_ADDR, ARG_AMOUNT => *DEFAULT_PAYMENT },
+            )
+            .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let execution_result = utils::get_success_result(response);
+    let error = execution_result.as_error().expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::InvalidContextForFunction(
+            FunctionIndex::AddAssociatedKeyFuncIndex,
+            Phase::Payment
+        ))
+    );
+
+    // A failed payment forfeits the maximum payment to the rewards purse.
+    assert_eq!(
+        builder.get_pos_rewards_purse_balance(),
+        U512::from(MAX_PAYMENT)
+    );
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account.get_associated_key_weight(ACCOUNT_1_ADDR).is_none());
+}
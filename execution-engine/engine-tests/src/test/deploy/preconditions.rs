@@ -48,7 +48,11 @@ fn should_raise_precondition_authorization_failure_invalid_account() {
         .expect("there should be a response");
 
     let precondition_failure = utils::get_precondition_failure(response);
-    assert_matches!(precondition_failure, Error::Authorization);
+    assert_matches!(
+        precondition_failure,
+        Error::AccountNotFound(account_hash) if *account_hash == nonexistent_account_addr
+    );
+    assert!(transfer_result.builder().is_precondition_failure());
 }
 
 #[ignore]
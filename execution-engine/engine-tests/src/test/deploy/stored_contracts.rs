@@ -9,7 +9,8 @@ use engine_storage::global_state::in_memory::InMemoryGlobalState;
 use engine_test_support::{
     internal::{
         utils, AdditiveMapDiff, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
-        UpgradeRequestBuilder, WasmTestBuilder, DEFAULT_ACCOUNT_KEY, DEFAULT_RUN_GENESIS_REQUEST,
+        UpgradeRequestBuilder, WasmTestBuilder, DEFAULT_ACCOUNT_KEY, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
     },
     DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
 };
@@ -651,7 +652,8 @@ fn should_have_equivalent_transforms_with_stored_contract_pointers() {
                 // la has stored contracts under named urefs
                 assert_ne!(la.named_keys(), ra.named_keys());
             }
-            (Transform::AddUInt512(_), Transform::AddUInt512(_)) => {
+            (Transform::AddUInt512(_), Transform::AddUInt512(_))
+            | (Transform::SubUInt512(_), Transform::SubUInt512(_)) => {
                 // differing payment
             }
             _ => {
@@ -1206,3 +1208,58 @@ fn should_execute_stored_payment_and_session_code_with_new_major_version() {
         .expect_success()
         .commit();
 }
+
+#[ignore]
+#[test]
+fn should_call_contract_by_hash_from_another_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        &format!("{}.wasm", TRANSFER_PURSE_TO_ACCOUNT_CONTRACT_NAME),
+        runtime_args! {
+            ARG_TARGET => ACCOUNT_1_ADDR,
+            ARG_AMOUNT => *DEFAULT_PAYMENT * 10
+        },
+    )
+    .build();
+
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        &format!("{}_stored.wasm", DO_NOTHING_NAME),
+        RuntimeArgs::default(),
+    )
+    .build();
+
+    builder.exec(exec_request_1).expect_success().commit();
+    builder.exec(exec_request_2).expect_success().commit();
+
+    let contract_hash: ContractHash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account")
+        .named_keys()
+        .get(DO_NOTHING_CONTRACT_HASH_NAME)
+        .expect("should have contract hash")
+        .into_hash()
+        .expect("should be a hash key");
+
+    // the hash key resolves to the stored contract
+    let stored_value = builder
+        .query(None, Key::Hash(contract_hash), &[])
+        .expect("should query contract hash");
+    assert!(
+        stored_value.as_contract().is_some(),
+        "hash key should hold a contract"
+    );
+
+    // a different account calls the contract knowing nothing but its hash
+    let exec_request_3 = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        "local_state_stored_caller.wasm",
+        runtime_args! { "seed" => contract_hash },
+    )
+    .build();
+
+    builder.exec(exec_request_3).expect_success().commit();
+}
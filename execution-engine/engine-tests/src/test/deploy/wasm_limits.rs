@@ -0,0 +1,122 @@
+use assert_matches::assert_matches;
+
+use engine_core::engine_state::Error;
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+        DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::{
+    wasm_costs::{WasmCosts, DEFAULT_MAX_MEMORY_PAGES, DEFAULT_MAX_TABLE_SIZE},
+    PreprocessingError, Preprocessor,
+};
+use types::{runtime_args, ProtocolVersion, RuntimeArgs};
+
+const OVERSIZED_MEMORY_PAGES: u32 = 65_536;
+const OVERSIZED_TABLE_SIZE: u32 = 100_000;
+
+const CONTRACT_WAT_WITH_OVERSIZED_MEMORY: &str = r#"
+(module
+    (type (;0;) (func))
+    (func $call (type 0))
+    (memory (;0;) 1 65536)
+    (export "memory" (memory 0))
+    (export "call" (func $call)))
+"#;
+
+const CONTRACT_WAT_WITH_OVERSIZED_TABLE: &str = r#"
+(module
+    (type (;0;) (func))
+    (func $call (type 0))
+    (table (;0;) 100000 funcref)
+    (memory (;0;) 16)
+    (export "memory" (memory 0))
+    (export "call" (func $call)))
+"#;
+
+fn preprocess(wat: &str) -> Result<(), PreprocessingError> {
+    let wasm_costs =
+        WasmCosts::from_version(ProtocolVersion::V1_0_0).expect("should have default costs");
+    let wasm_binary = wabt::wat2wasm(wat).expect("should parse");
+    Preprocessor::new(wasm_costs)
+        .preprocess(&wasm_binary)
+        .map(|_| ())
+}
+
+fn exec_session(wat: &str) -> InMemoryWasmTestBuilder {
+    let wasm_binary = wabt::wat2wasm(wat).expect("should parse");
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_bytes(wasm_binary, RuntimeArgs::new())
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([123; 32])
+        .build();
+
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request);
+    builder
+}
+
+#[test]
+fn should_accept_module_within_limits_on_validation() {
+    let wasm_costs =
+        WasmCosts::from_version(ProtocolVersion::V1_0_0).expect("should have default costs");
+    let preprocessor = Preprocessor::new(wasm_costs);
+    assert!(engine_shared::wasm::do_nothing_module(&preprocessor).is_ok());
+}
+
+#[test]
+fn should_reject_oversized_memory_on_validation() {
+    let error = preprocess(CONTRACT_WAT_WITH_OVERSIZED_MEMORY).expect_err("should fail");
+    assert_matches!(
+        error,
+        PreprocessingError::MemoryLimitExceeded { requested, max }
+            if requested == OVERSIZED_MEMORY_PAGES && max == DEFAULT_MAX_MEMORY_PAGES
+    );
+}
+
+#[test]
+fn should_reject_oversized_table_on_validation() {
+    let error = preprocess(CONTRACT_WAT_WITH_OVERSIZED_TABLE).expect_err("should fail");
+    assert_matches!(
+        error,
+        PreprocessingError::TableLimitExceeded { requested, max }
+            if requested == OVERSIZED_TABLE_SIZE && max == DEFAULT_MAX_TABLE_SIZE
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_oversized_memory_on_exec() {
+    let builder = exec_session(CONTRACT_WAT_WITH_OVERSIZED_MEMORY);
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let error = utils::get_precondition_failure(response);
+    assert_matches!(
+        error,
+        Error::WasmPreprocessing(PreprocessingError::MemoryLimitExceeded { .. })
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_oversized_table_on_exec() {
+    let builder = exec_session(CONTRACT_WAT_WITH_OVERSIZED_TABLE);
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let error = utils::get_precondition_failure(response);
+    assert_matches!(
+        error,
+        Error::WasmPreprocessing(PreprocessingError::TableLimitExceeded { .. })
+    );
+}
@@ -0,0 +1,118 @@
+use std::convert::TryFrom;
+
+use grpc::RequestOptions;
+
+use engine_core::engine_state::CONV_RATE;
+use engine_grpc_server::engine_server::{
+    ipc::{BalanceRequest, BalanceResponse},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use types::{account::AccountHash, runtime_args, AccessRights, RuntimeArgs, URef, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+const TRANSFER_AMOUNT: u64 = 250_000_000 + 1000;
+const MISSING_STATE_HASH: [u8; 32] = [255; 32];
+
+fn get_balance(
+    builder: &InMemoryWasmTestBuilder,
+    state_hash: Vec<u8>,
+    purse: URef,
+) -> BalanceResponse {
+    let mut request = BalanceRequest::new();
+    request.set_state_hash(state_hash);
+    request.set_purse_uref(purse.into());
+
+    builder
+        .get_engine_state()
+        .get_balance(RequestOptions::new(), request)
+        .wait_drop_metadata()
+        .expect("should get balance response")
+}
+
+fn expect_balance(response: BalanceResponse) -> U512 {
+    assert!(response.has_success(), "{:?}", response);
+    U512::try_from(response.get_success().clone()).expect("should parse balance")
+}
+
+#[ignore]
+#[test]
+fn should_get_balances_after_transfer() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => U512::from(TRANSFER_AMOUNT) },
+    )
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let post_state_hash = builder.get_post_state_hash();
+    let default_account_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get default account")
+        .main_purse();
+    let account_1_purse = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should get account 1")
+        .main_purse();
+
+    let gas_cost =
+        Motes::from_gas(builder.exec_costs(0)[0], CONV_RATE).expect("should convert gas to motes");
+
+    let default_account_balance = expect_balance(get_balance(
+        &builder,
+        post_state_hash.clone(),
+        default_account_purse,
+    ));
+    assert_eq!(
+        default_account_balance,
+        U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE) - gas_cost.value() - TRANSFER_AMOUNT
+    );
+    assert_eq!(
+        default_account_balance,
+        builder.get_purse_balance(default_account_purse)
+    );
+
+    let account_1_balance = expect_balance(get_balance(&builder, post_state_hash, account_1_purse));
+    assert_eq!(account_1_balance, U512::from(TRANSFER_AMOUNT));
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_get_balance_of_missing_purse() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let missing_purse = URef::new([42; 32], AccessRights::READ_ADD_WRITE);
+    let response = get_balance(&builder, builder.get_post_state_hash(), missing_purse);
+
+    assert!(response.has_failure());
+    assert_eq!(response.get_failure(), "Purse not found");
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_get_balance_at_missing_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let default_account_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get default account")
+        .main_purse();
+    let response = get_balance(&builder, MISSING_STATE_HASH.to_vec(), default_account_purse);
+
+    assert!(response.has_failure());
+    assert_eq!(response.get_failure(), "Root not found");
+}
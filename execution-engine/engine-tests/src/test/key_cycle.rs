@@ -0,0 +1,36 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{Key, RuntimeArgs};
+
+const CONTRACT_KEY_CYCLE: &str = "key_cycle.wasm";
+const KEY_CYCLE: &str = "key_cycle";
+
+#[ignore]
+#[test]
+fn should_fail_query_through_key_cycle() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_KEY_CYCLE,
+        RuntimeArgs::new(),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    // The trailing path element makes the query dereference the keys rather than return the first.
+    let error = builder
+        .query(
+            None,
+            Key::Account(DEFAULT_ACCOUNT_ADDR),
+            &[KEY_CYCLE, "value"],
+        )
+        .expect_err("query through a key cycle should fail");
+    assert!(error.contains("circular reference"), "{}", error);
+}
@@ -4,9 +4,16 @@ mod contract_context;
 mod counter;
 mod deploy;
 mod explorer;
+mod get_balance;
 mod groups;
+mod key_cycle;
 mod manage_groups;
+mod module_cache;
+mod parallel_execution;
 mod regression;
+mod shutdown;
+mod state_read_cache;
+mod state_snapshot;
 mod system_contracts;
 mod upgrade;
 mod wasmless_transfer;
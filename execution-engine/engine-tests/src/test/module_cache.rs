@@ -0,0 +1,34 @@
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, RuntimeArgs};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const ARG_AMOUNT: &str = "amount";
+const DEPLOY_COUNT: u8 = 100;
+
+#[ignore]
+#[test]
+fn should_preprocess_repeated_session_code_once() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    for index in 0..DEPLOY_COUNT {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(CONTRACT_DO_NOTHING, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash([index; 32])
+            .build();
+        let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    let preprocess_count = builder.get_engine_state().module_cache().preprocess_count();
+    assert_eq!(preprocess_count, 1);
+}
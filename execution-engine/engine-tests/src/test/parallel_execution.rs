@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use engine_core::engine_state::{execute_request::ExecuteRequest, EngineConfig};
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+const TRANSFER_COUNT: u8 = 20;
+const TRANSFER_AMOUNT: u64 = 1_000;
+const FUND_AMOUNT: u64 = 1_000_000_000;
+
+fn engine_config() -> EngineConfig {
+    EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+}
+
+fn source_account(index: u8) -> AccountHash {
+    AccountHash::new([index + 1; 32])
+}
+
+fn target_account(index: u8) -> AccountHash {
+    AccountHash::new([index + 1 + TRANSFER_COUNT; 32])
+}
+
+/// Returns a builder over its own view of the stores backing `global_state`.
+fn builder_sharing(
+    global_state: &InMemoryGlobalState,
+    engine_config: EngineConfig,
+    post_state_hash: Vec<u8>,
+) -> InMemoryWasmTestBuilder {
+    let builder_global_state = InMemoryGlobalState {
+        environment: Arc::clone(&global_state.environment),
+        trie_store: Arc::clone(&global_state.trie_store),
+        protocol_data_store: Arc::clone(&global_state.protocol_data_store),
+        root_ancestry_store: Arc::clone(&global_state.root_ancestry_store),
+        empty_root_hash: global_state.empty_root_hash,
+    };
+    InMemoryWasmTestBuilder::new(builder_global_state, engine_config, post_state_hash)
+}
+
+/// A request holding one transfer from each funded source account to a distinct target account.
+fn transfers_request() -> ExecuteRequest {
+    (0..TRANSFER_COUNT)
+        .fold(ExecuteRequestBuilder::new(), |request, index| {
+            let deploy = DeployItemBuilder::new()
+                .with_address(source_account(index))
+                .with_deploy_hash([index + 1; 32])
+                .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+                .with_session_code(
+                    CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+                    runtime_args! {
+                        ARG_TARGET => target_account(index),
+                        ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+                    },
+                )
+                .with_authorization_keys(&[source_account(index)])
+                .build();
+            request.push_deploy(deploy)
+        })
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_produce_same_results_in_sequential_and_parallel_modes() {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+
+    let mut sequential_builder = builder_sharing(
+        &global_state,
+        engine_config(),
+        global_state.empty_root_hash.to_vec(),
+    );
+    sequential_builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    for index in 0..TRANSFER_COUNT {
+        let fund_request = ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+            runtime_args! {
+                ARG_TARGET => source_account(index),
+                ARG_AMOUNT => U512::from(FUND_AMOUNT)
+            },
+        )
+        .build();
+        sequential_builder
+            .exec(fund_request)
+            .expect_success()
+            .commit();
+    }
+
+    let mut parallel_builder = builder_sharing(
+        &global_state,
+        engine_config().with_parallel_execution(true),
+        sequential_builder.get_post_state_hash(),
+    );
+
+    sequential_builder
+        .exec(transfers_request())
+        .expect_success()
+        .commit();
+    parallel_builder
+        .exec(transfers_request())
+        .expect_success()
+        .commit();
+
+    let sequential_results = sequential_builder
+        .get_exec_responses()
+        .last()
+        .expect("should have sequential results");
+    let parallel_results = parallel_builder
+        .get_exec_responses()
+        .last()
+        .expect("should have parallel results");
+    assert_eq!(sequential_results.len(), usize::from(TRANSFER_COUNT));
+    assert_eq!(parallel_results.len(), usize::from(TRANSFER_COUNT));
+
+    for (sequential, parallel) in sequential_results.iter().zip(parallel_results) {
+        assert_eq!(sequential.effect(), parallel.effect());
+        assert_eq!(sequential.cost(), parallel.cost());
+    }
+
+    assert_eq!(
+        sequential_builder.get_post_state_hash(),
+        parallel_builder.get_post_state_hash()
+    );
+
+    for index in 0..TRANSFER_COUNT {
+        assert!(parallel_builder
+            .get_account(target_account(index))
+            .is_some());
+    }
+}
@@ -41,7 +41,7 @@ fn should_run_ee_532_get_uref_regression_test() {
     let message = deploy_result.as_error().map(|err| format!("{}", err));
     assert_eq!(
         message,
-        Some(format!("{}", Error::Authorization)),
-        "expected Error::Authorization"
+        Some(format!("{}", Error::AccountNotFound(UNKNOWN_ADDR))),
+        "expected Error::AccountNotFound"
     )
 }
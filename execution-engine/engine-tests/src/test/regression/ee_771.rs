@@ -1,3 +1,4 @@
+use engine_core::execution::ErrorCode;
 use engine_test_support::{
     internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
     DEFAULT_ACCOUNT_ADDR,
@@ -16,21 +17,9 @@ fn should_run_ee_771_regression() {
     )
     .build();
 
-    let result = InMemoryWasmTestBuilder::default()
+    InMemoryWasmTestBuilder::default()
         .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
         .exec(exec_request)
         .commit()
-        .finish();
-
-    let response = result
-        .builder()
-        .get_exec_response(0)
-        .expect("should have a response")
-        .to_owned();
-
-    let error = response[0].as_error().expect("should have error");
-    assert_eq!(
-        format!("{}", error),
-        "Function not found: functiondoesnotexist"
-    );
+        .expect_error_code(ErrorCode::FunctionNotFound);
 }
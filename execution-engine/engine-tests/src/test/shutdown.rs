@@ -0,0 +1,118 @@
+use std::{convert::TryInto, sync::Arc, thread, time::Duration};
+
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{EngineConfig, EngineState, Error, ExecuteError},
+    execution,
+};
+use engine_shared::newtypes::CorrelationId;
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_KEY,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, RuntimeArgs, U512};
+
+const ENDLESS_LOOP_WASM: &str = "endless_loop.wasm";
+const ARG_AMOUNT: &str = "amount";
+const PAYMENT_AMOUNT: u64 = 10_000_000;
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+fn engine_config() -> EngineConfig {
+    EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+}
+
+#[ignore]
+#[test]
+fn should_finish_in_flight_exec_when_closing() {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+
+    // run genesis through a builder sharing the same underlying stores
+    let post_state_hash = {
+        let builder_global_state = InMemoryGlobalState {
+            environment: Arc::clone(&global_state.environment),
+            trie_store: Arc::clone(&global_state.trie_store),
+            protocol_data_store: Arc::clone(&global_state.protocol_data_store),
+            root_ancestry_store: Arc::clone(&global_state.root_ancestry_store),
+            empty_root_hash: global_state.empty_root_hash,
+        };
+        let mut builder = InMemoryWasmTestBuilder::new(
+            builder_global_state,
+            engine_config(),
+            global_state.empty_root_hash.to_vec(),
+        );
+        builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+        builder.get_post_state_hash()
+    };
+
+    let engine_state = Arc::new(EngineState::new(global_state, engine_config()));
+
+    let mut exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash([1; 32])
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => U512::from(PAYMENT_AMOUNT) })
+            .with_session_code(ENDLESS_LOOP_WASM, RuntimeArgs::default())
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+    exec_request.parent_state_hash = post_state_hash
+        .as_slice()
+        .try_into()
+        .expect("should have valid post state hash");
+
+    let exec_handle = {
+        let engine_state = Arc::clone(&engine_state);
+        thread::spawn(move || engine_state.run_execute(CorrelationId::new(), exec_request))
+    };
+
+    while engine_state.in_flight_requests() == 0 {
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    let all_finished = engine_state
+        .close(SHUTDOWN_TIMEOUT)
+        .expect("should close engine state");
+    assert!(all_finished, "in-flight exec should finish before timeout");
+    assert!(engine_state.is_closing());
+    assert_eq!(engine_state.in_flight_requests(), 0);
+
+    let (execution_results, _) = exec_handle
+        .join()
+        .expect("exec thread should not panic")
+        .expect("should find parent state");
+    assert_eq!(execution_results.len(), 1);
+    // the endless loop should have run until it exhausted its gas rather than being cut short
+    assert_matches!(
+        execution_results[0].as_error(),
+        Some(Error::Exec(execution::Error::GasLimit))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_refuse_exec_once_closed() {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root_hash = global_state.empty_root_hash;
+    let engine_state = EngineState::new(global_state, engine_config());
+
+    let all_finished = engine_state
+        .close(SHUTDOWN_TIMEOUT)
+        .expect("should close engine state");
+    assert!(all_finished);
+
+    let mut exec_request = ExecuteRequestBuilder::new().build();
+    exec_request.parent_state_hash = empty_root_hash;
+    let result = engine_state.run_execute(CorrelationId::new(), exec_request);
+    assert_eq!(result.err(), Some(ExecuteError::Closing));
+    assert_eq!(engine_state.in_flight_requests(), 0);
+}
@@ -0,0 +1,55 @@
+use std::convert::TryFrom;
+
+use engine_shared::newtypes::Blake2bHash;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ARG_AMOUNT: &str = "amount";
+const ARG_TARGET: &str = "target";
+const DEPLOY_COUNT: u8 = 50;
+
+#[ignore]
+#[test]
+fn should_fetch_mint_contract_once_for_deploys_against_one_prestate() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let prestate_hash =
+        Blake2bHash::try_from(builder.get_post_state_hash().as_slice()).expect("should convert");
+
+    let mut exec_request_builder = ExecuteRequestBuilder::new();
+    for index in 0..DEPLOY_COUNT {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(
+                CONTRACT_TRANSFER_TO_ACCOUNT,
+                runtime_args! { ARG_TARGET => ACCOUNT_1_ADDR, ARG_AMOUNT => U512::one() },
+            )
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash([index; 32])
+            .build();
+        exec_request_builder = exec_request_builder.push_deploy(deploy);
+    }
+
+    builder.exec(exec_request_builder.build()).expect_success();
+
+    let exec_results = &builder.get_exec_responses()[0];
+    assert_eq!(exec_results.len(), DEPLOY_COUNT as usize);
+    assert!(exec_results.iter().all(|result| !result.is_failure()));
+
+    let mint_key = Key::from(builder.get_mint_contract_hash());
+    let fetch_count = builder
+        .get_engine_state()
+        .state_read_cache()
+        .fetch_count(prestate_hash, &mint_key);
+    assert_eq!(fetch_count, 1);
+}
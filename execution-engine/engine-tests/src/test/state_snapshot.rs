@@ -0,0 +1,56 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ACCOUNT_2_ADDR: AccountHash = AccountHash::new([2u8; 32]);
+const ACCOUNT_3_ADDR: AccountHash = AccountHash::new([3u8; 32]);
+const ARG_AMOUNT: &str = "amount";
+const ARG_TARGET: &str = "target";
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+
+fn transfer(builder: &mut InMemoryWasmTestBuilder, target: AccountHash) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! { ARG_TARGET => target, ARG_AMOUNT => U512::from(TRANSFER_AMOUNT) },
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_run_alternative_continuations_from_snapshot() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    transfer(&mut builder, ACCOUNT_1_ADDR);
+
+    let snapshot = builder.snapshot();
+    let snapshot_root = builder.get_post_state_hash();
+    assert_eq!(snapshot.post_state_hash(), Some(snapshot_root.as_slice()));
+
+    transfer(&mut builder, ACCOUNT_2_ADDR);
+    let first_branch_root = builder.get_post_state_hash();
+    assert_eq!(builder.get_exec_responses_count(), 2);
+    assert!(builder.get_account(ACCOUNT_2_ADDR).is_some());
+
+    builder.restore(&snapshot);
+    assert_eq!(builder.get_post_state_hash(), snapshot_root);
+    assert_eq!(builder.get_exec_responses_count(), 1);
+    assert_eq!(builder.get_transforms().len(), 1);
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_some());
+    assert!(builder.get_account(ACCOUNT_2_ADDR).is_none());
+
+    transfer(&mut builder, ACCOUNT_3_ADDR);
+    let second_branch_root = builder.get_post_state_hash();
+    assert_eq!(builder.get_exec_responses_count(), 2);
+    assert!(builder.get_account(ACCOUNT_2_ADDR).is_none());
+    assert!(builder.get_account(ACCOUNT_3_ADDR).is_some());
+
+    assert_ne!(first_branch_root, second_branch_root);
+    assert_eq!(snapshot.post_state_hash(), Some(snapshot_root.as_slice()));
+}
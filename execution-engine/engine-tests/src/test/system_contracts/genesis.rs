@@ -1,14 +1,21 @@
+use std::convert::TryFrom;
+
 use engine_core::engine_state::{
-    genesis::{ExecConfig, GenesisAccount},
+    genesis::{ExecConfig, GenesisAccount, MINT_ROUND_SEIGNIORAGE_RATE},
     run_genesis_request::RunGenesisRequest,
     SYSTEM_ACCOUNT_ADDR,
 };
-use engine_shared::{motes::Motes, stored_value::StoredValue};
+use engine_shared::{
+    motes::Motes,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+};
 use engine_test_support::internal::{
-    utils, InMemoryWasmTestBuilder, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT,
+    utils, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT,
     POS_INSTALL_CONTRACT, STANDARD_PAYMENT_INSTALL_CONTRACT,
 };
-use types::{account::AccountHash, ProtocolVersion, U512};
+use types::{account::AccountHash, bytesrepr::ToBytes, Key, ProtocolVersion, U512};
 
 #[cfg(feature = "use-system-contracts")]
 const BAD_INSTALL: &str = "standard_payment.wasm";
@@ -20,6 +27,9 @@ const ACCOUNT_1_BONDED_AMOUNT: u64 = 1_000_000;
 const ACCOUNT_2_BONDED_AMOUNT: u64 = 2_000_000;
 const ACCOUNT_1_BALANCE: u64 = 1_000_000_000;
 const ACCOUNT_2_BALANCE: u64 = 2_000_000_000;
+const MANY_ACCOUNTS_COUNT: u8 = 50;
+const ROUND_SEIGNIORAGE_RATE: (u64, u64) = (1, 1_000);
+const TRIE_CHUNK_THRESHOLD: u32 = 32;
 
 #[ignore]
 #[test]
@@ -60,7 +70,8 @@ fn should_run_genesis() {
         standard_payment_installer_bytes,
         accounts,
         wasm_costs,
-    );
+    )
+    .with_round_seigniorage_rate(ROUND_SEIGNIORAGE_RATE.0, ROUND_SEIGNIORAGE_RATE.1);
     let run_genesis_request =
         RunGenesisRequest::new(GENESIS_CONFIG_HASH.into(), protocol_version, exec_config);
 
@@ -88,6 +99,14 @@ fn should_run_genesis() {
     assert_eq!(account_1_balance_actual, account_1_balance.value());
     assert_eq!(account_2_balance_actual, account_2_balance.value());
 
+    // The total supply includes both the account balances and the bonded amounts.
+    let expected_total_supply =
+        ACCOUNT_1_BALANCE + ACCOUNT_2_BALANCE + ACCOUNT_1_BONDED_AMOUNT + ACCOUNT_2_BONDED_AMOUNT;
+    assert_eq!(
+        builder.get_total_supply(),
+        U512::from(expected_total_supply)
+    );
+
     let mint_contract_hash = builder.get_mint_contract_hash();
     let pos_contract_hash = builder.get_pos_contract_hash();
 
@@ -103,6 +122,134 @@ fn should_run_genesis() {
     } else {
         panic!("contract not found at pos hash");
     }
+    let round_seigniorage_rate: (u64, u64) = builder
+        .query(
+            None,
+            mint_contract_hash.into(),
+            &[MINT_ROUND_SEIGNIORAGE_RATE],
+        )
+        .expect("should find round seigniorage rate")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should be (u64, u64)");
+    assert_eq!(round_seigniorage_rate, ROUND_SEIGNIORAGE_RATE);
+}
+
+#[ignore]
+#[test]
+fn should_enumerate_accounts_after_genesis() {
+    let accounts = vec![
+        GenesisAccount::new(
+            ACCOUNT_1_ADDR,
+            Motes::new(ACCOUNT_1_BALANCE.into()),
+            Motes::new(ACCOUNT_1_BONDED_AMOUNT.into()),
+        ),
+        GenesisAccount::new(
+            ACCOUNT_2_ADDR,
+            Motes::new(ACCOUNT_2_BALANCE.into()),
+            Motes::new(ACCOUNT_2_BONDED_AMOUNT.into()),
+        ),
+    ];
+    let exec_config = ExecConfig::new(
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT),
+        accounts,
+        *DEFAULT_WASM_COSTS,
+    );
+    let run_genesis_request = RunGenesisRequest::new(
+        GENESIS_CONFIG_HASH.into(),
+        ProtocolVersion::V1_0_0,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&run_genesis_request);
+
+    let state_hash = Blake2bHash::try_from(builder.get_post_state_hash().as_slice())
+        .expect("should have valid post state hash");
+    // all account keys share the same leading tag byte
+    let account_prefix = Key::Account(ACCOUNT_1_ADDR)
+        .to_bytes()
+        .expect("should serialize key")[..1]
+        .to_vec();
+
+    let mut account_keys = builder
+        .get_engine_state()
+        .enumerate_keys(CorrelationId::new(), state_hash, &account_prefix)
+        .expect("should enumerate keys")
+        .expect("should find state root");
+    account_keys.sort();
+
+    let mut expected_keys = vec![
+        Key::Account(SYSTEM_ACCOUNT_ADDR),
+        Key::Account(ACCOUNT_1_ADDR),
+        Key::Account(ACCOUNT_2_ADDR),
+    ];
+    expected_keys.sort();
+
+    assert_eq!(account_keys, expected_keys);
+}
+
+#[ignore]
+#[test]
+fn should_run_genesis_with_many_accounts() {
+    let accounts: Vec<GenesisAccount> = (1..=MANY_ACCOUNTS_COUNT)
+        .map(|i| {
+            GenesisAccount::new(
+                AccountHash::new([i; 32]),
+                Motes::new(U512::from(u64::from(i) * ACCOUNT_1_BALANCE)),
+                Motes::new(U512::from(u64::from(i) * ACCOUNT_1_BONDED_AMOUNT)),
+            )
+        })
+        .collect();
+    let exec_config = ExecConfig::new(
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT),
+        accounts.clone(),
+        *DEFAULT_WASM_COSTS,
+    );
+    let run_genesis_request = RunGenesisRequest::new(
+        GENESIS_CONFIG_HASH.into(),
+        ProtocolVersion::V1_0_0,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&run_genesis_request);
+
+    for genesis_account in &accounts {
+        let account = builder
+            .get_account(genesis_account.account_hash())
+            .expect("genesis account should exist");
+        assert_eq!(
+            builder.get_purse_balance(account.main_purse()),
+            genesis_account.balance().value()
+        );
+    }
+
+    let pos_contract_key = Key::Hash(builder.get_pos_contract_hash());
+    let pos_contract = match builder.get_genesis_transforms().get(&pos_contract_key) {
+        Some(Transform::Write(StoredValue::Contract(contract))) => contract.clone(),
+        other => panic!("expected PoS contract write, got {:?}", other),
+    };
+    for genesis_account in &accounts {
+        let stake_key = format!(
+            "v_{}_{}",
+            base16::encode_lower(&genesis_account.account_hash().as_bytes()),
+            genesis_account.bonded_amount().value()
+        );
+        assert!(
+            pos_contract.named_keys().contains_key(&stake_key),
+            "PoS should record stake {}",
+            stake_key
+        );
+    }
 }
 
 #[cfg(feature = "use-system-contracts")]
@@ -201,3 +348,51 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
 
     builder.run_genesis(&run_genesis_request);
 }
+
+#[ignore]
+#[test]
+fn should_record_trie_chunk_threshold_in_genesis_protocol_data() {
+    let run_genesis_request = RunGenesisRequest::new(
+        GENESIS_CONFIG_HASH.into(),
+        ProtocolVersion::V1_0_0,
+        DEFAULT_EXEC_CONFIG
+            .clone()
+            .with_trie_chunk_threshold(TRIE_CHUNK_THRESHOLD),
+    );
+    let mut chunked_builder = InMemoryWasmTestBuilder::default();
+    chunked_builder.run_genesis(&run_genesis_request);
+
+    let protocol_data = chunked_builder
+        .get_engine_state()
+        .get_protocol_data(ProtocolVersion::V1_0_0)
+        .expect("should read protocol data")
+        .expect("should have protocol data");
+    assert_eq!(
+        protocol_data.trie_chunk_threshold(),
+        Some(TRIE_CHUNK_THRESHOLD)
+    );
+
+    // The installed contracts are longer than the threshold, so are stored in chunks and reached
+    // through a different state root, but read back the same.
+    let run_genesis_request = RunGenesisRequest::new(
+        GENESIS_CONFIG_HASH.into(),
+        ProtocolVersion::V1_0_0,
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    assert_ne!(
+        chunked_builder.get_genesis_hash(),
+        builder.get_genesis_hash()
+    );
+    let mint_contract_hash = builder.get_mint_contract_hash();
+    assert_eq!(
+        chunked_builder
+            .query(None, mint_contract_hash.into(), &[])
+            .expect("should find mint contract"),
+        builder
+            .query(None, mint_contract_hash.into(), &[])
+            .expect("should find mint contract")
+    );
+}
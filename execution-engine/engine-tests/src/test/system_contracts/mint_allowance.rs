@@ -0,0 +1,155 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{Error, CONV_RATE},
+    execution,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::AccountHash, runtime_args, system_contract_errors::mint, ApiError, RuntimeArgs, U512,
+};
+
+const CONTRACT_MINT_APPROVE: &str = "mint_approve.wasm";
+const CONTRACT_MINT_TRANSFER_FROM: &str = "mint_transfer_from.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ACCOUNT_1_INITIAL_BALANCE: u64 = 100_000_000_000;
+const ALLOWANCE: u64 = 1_000_000;
+const ARG_AMOUNT: &str = "amount";
+const ARG_OWNER: &str = "owner";
+const ARG_SPENDER: &str = "spender";
+const ARG_TARGET: &str = "target";
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => ACCOUNT_1_ADDR,
+            ARG_AMOUNT => U512::from(ACCOUNT_1_INITIAL_BALANCE),
+        },
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+    builder
+}
+
+fn approve(builder: &mut InMemoryWasmTestBuilder, amount: u64) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MINT_APPROVE,
+        runtime_args! {
+            ARG_SPENDER => ACCOUNT_1_ADDR,
+            ARG_AMOUNT => U512::from(amount),
+        },
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+fn transfer_from(builder: &mut InMemoryWasmTestBuilder, amount: u64) {
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get default account");
+    let exec_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        CONTRACT_MINT_TRANSFER_FROM,
+        runtime_args! {
+            ARG_OWNER => default_account.main_purse().addr(),
+            ARG_AMOUNT => U512::from(amount),
+        },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn assert_insufficient_allowance(builder: &InMemoryWasmTestBuilder) {
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    let exec_response = response.last().expect("should have response");
+    let error = exec_response.as_error().expect("should have error");
+    let error = assert_matches!(error, Error::Exec(execution::Error::Revert(e)) => e);
+    assert_eq!(error, &ApiError::from(mint::Error::InsufficientAllowance));
+}
+
+#[ignore]
+#[test]
+fn should_transfer_exact_allowance() {
+    let mut builder = setup();
+    approve(&mut builder, ALLOWANCE);
+
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get default account");
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should get account 1");
+    let owner_balance = builder.get_purse_balance(default_account.main_purse());
+    let spender_balance = builder.get_purse_balance(account_1.main_purse());
+
+    transfer_from(&mut builder, ALLOWANCE);
+    assert!(!builder.is_error());
+
+    let transfer_cost = Motes::from_gas(builder.last_exec_gas_cost(), CONV_RATE)
+        .expect("should convert")
+        .value();
+
+    assert_eq!(
+        builder.get_purse_balance(default_account.main_purse()),
+        owner_balance - ALLOWANCE
+    );
+    assert_eq!(
+        builder.get_purse_balance(account_1.main_purse()),
+        spender_balance - transfer_cost + ALLOWANCE
+    );
+
+    // The allowance has been used up entirely.
+    transfer_from(&mut builder, 1);
+    assert_insufficient_allowance(&builder);
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_more_than_allowance() {
+    let mut builder = setup();
+    approve(&mut builder, ALLOWANCE);
+
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get default account");
+    let owner_balance = builder.get_purse_balance(default_account.main_purse());
+
+    transfer_from(&mut builder, ALLOWANCE + 1);
+    assert_insufficient_allowance(&builder);
+
+    assert_eq!(
+        builder.get_purse_balance(default_account.main_purse()),
+        owner_balance
+    );
+}
+
+#[ignore]
+#[test]
+fn should_overwrite_previous_allowance() {
+    let mut builder = setup();
+    approve(&mut builder, ALLOWANCE);
+    approve(&mut builder, ALLOWANCE / 2);
+
+    transfer_from(&mut builder, ALLOWANCE);
+    assert_insufficient_allowance(&builder);
+
+    transfer_from(&mut builder, ALLOWANCE / 2);
+    assert!(!builder.is_error());
+
+    transfer_from(&mut builder, 1);
+    assert_insufficient_allowance(&builder);
+}
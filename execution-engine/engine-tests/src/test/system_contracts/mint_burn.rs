@@ -0,0 +1,70 @@
+use engine_core::engine_state::CONV_RATE;
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_MINT_BURN: &str = "mint_burn.wasm";
+const ARG_AMOUNT: &str = "amount";
+const BURN_AMOUNT: u64 = 1_000_000;
+
+#[ignore]
+#[test]
+fn should_burn_and_decrease_total_supply() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get default account");
+    let main_purse_balance = builder.get_purse_balance(default_account.main_purse());
+    let total_supply = builder.get_total_supply();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MINT_BURN,
+        runtime_args! { ARG_AMOUNT => U512::from(BURN_AMOUNT) },
+    )
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let burn_cost = Motes::from_gas(builder.last_exec_gas_cost(), CONV_RATE)
+        .expect("should convert")
+        .value();
+
+    // Paying for the deploy only moves motes between purses, so only the burned amount is
+    // removed from the total supply.
+    assert_eq!(builder.get_total_supply(), total_supply - BURN_AMOUNT);
+    assert_eq!(
+        builder.get_purse_balance(default_account.main_purse()),
+        main_purse_balance - burn_cost - BURN_AMOUNT
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_burn_more_than_purse_balance() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get default account");
+    let main_purse_balance = builder.get_purse_balance(default_account.main_purse());
+    let total_supply = builder.get_total_supply();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_MINT_BURN,
+        runtime_args! { ARG_AMOUNT => main_purse_balance + 1 },
+    )
+    .build();
+
+    builder.exec(exec_request).commit();
+
+    assert!(builder.is_error());
+    assert_eq!(builder.get_total_supply(), total_supply);
+}
@@ -1,4 +1,7 @@
-use engine_core::engine_state::EngineConfig;
+use engine_core::engine_state::{
+    genesis::{MINT_ROUND_SEIGNIORAGE_RATE, MINT_TOTAL_SUPPLY},
+    EngineConfig,
+};
 use engine_shared::{stored_value::StoredValue, transform::Transform};
 use engine_test_support::{
     internal::{
@@ -7,11 +10,13 @@ use engine_test_support::{
     DEFAULT_ACCOUNT_ADDR,
 };
 use types::{
-    contracts::CONTRACT_INITIAL_VERSION, ContractHash, ContractPackageHash, ContractVersionKey,
-    ProtocolVersion, RuntimeArgs,
+    contracts::CONTRACT_INITIAL_VERSION, runtime_args, ContractHash, ContractPackageHash,
+    ContractVersionKey, ProtocolVersion, RuntimeArgs, U512,
 };
 
 const DEPLOY_HASH_1: [u8; 32] = [1u8; 32];
+const ARG_ROUND_SEIGNIORAGE_RATE: &str = "round_seigniorage_rate";
+const ROUND_SEIGNIORAGE_RATE: (u64, u64) = (1, 1_000);
 
 #[ignore]
 #[test]
@@ -35,7 +40,7 @@ fn should_run_mint_install_contract() {
         DEFAULT_BLOCK_TIME,
         DEPLOY_HASH_1,
         "install",
-        RuntimeArgs::new(),
+        runtime_args! { ARG_ROUND_SEIGNIORAGE_RATE => ROUND_SEIGNIORAGE_RATE },
         vec![],
     )
     .expect("should run successfully");
@@ -72,4 +77,33 @@ fn should_run_mint_install_contract() {
         _ => panic!("Expected contract to be written under the key"),
     };
     assert_eq!(contract.contract_package_hash(), contract_package_hash,);
+
+    // The total supply starts at zero.
+    let total_supply_key = contract
+        .named_keys()
+        .get(MINT_TOTAL_SUPPLY)
+        .expect("should have total supply key");
+    let total_supply: U512 = match effect.transforms.get(total_supply_key) {
+        Some(Transform::Write(StoredValue::CLValue(cl_value))) => {
+            cl_value.clone().into_t().expect("should be U512")
+        }
+
+        _ => panic!("Expected total supply to be written under the key"),
+    };
+    assert_eq!(total_supply, U512::zero());
+
+    // The round seigniorage rate is stored as given.
+    let round_seigniorage_rate_key = contract
+        .named_keys()
+        .get(MINT_ROUND_SEIGNIORAGE_RATE)
+        .expect("should have round seigniorage rate key");
+    let round_seigniorage_rate: (u64, u64) = match effect.transforms.get(round_seigniorage_rate_key)
+    {
+        Some(Transform::Write(StoredValue::CLValue(cl_value))) => {
+            cl_value.clone().into_t().expect("should be (u64, u64)")
+        }
+
+        _ => panic!("Expected round seigniorage rate to be written under the key"),
+    };
+    assert_eq!(round_seigniorage_rate, ROUND_SEIGNIORAGE_RATE);
 }
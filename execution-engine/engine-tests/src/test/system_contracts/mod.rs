@@ -1,7 +1,10 @@
 mod genesis;
+mod mint_allowance;
+mod mint_burn;
 mod mint_install;
 mod pos_install;
 mod proof_of_stake;
+mod registry;
 mod standard_payment;
 mod standard_payment_install;
 mod upgrade;
@@ -22,15 +22,19 @@ const SYSTEM_ADDR: AccountHash = AccountHash::new([0u8; 32]);
 const DEPLOY_HASH_2: [u8; 32] = [2u8; 32];
 const N_VALIDATORS: u8 = 5;
 
-// one named_key for each validator and three for the purses
-const EXPECTED_KNOWN_KEYS_LEN: usize = (N_VALIDATORS as usize) + 3;
+const UNBONDING_DELAY_MILLIS: u64 = 1_000;
+
+// one named_key for each validator, three for the purses and one for the unbonding delay
+const EXPECTED_KNOWN_KEYS_LEN: usize = (N_VALIDATORS as usize) + 4;
 
 const POS_BONDING_PURSE: &str = "pos_bonding_purse";
 const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
 const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
+const UNBONDING_DELAY_KEY: &str = "unbonding_delay_millis";
 
 const ARG_MINT_PACKAGE_HASH: &str = "mint_contract_package_hash";
 const ARG_GENESIS_VALIDATORS: &str = "genesis_validators";
+const ARG_UNBONDING_DELAY_MILLIS: &str = "unbonding_delay_millis";
 
 #[ignore]
 #[test]
@@ -78,6 +82,7 @@ fn should_run_pos_install_contract() {
         runtime_args! {
             ARG_MINT_PACKAGE_HASH => mint_package_hash,
             ARG_GENESIS_VALIDATORS => genesis_validators,
+            ARG_UNBONDING_DELAY_MILLIS => UNBONDING_DELAY_MILLIS,
         },
         vec![],
     );
@@ -127,6 +132,21 @@ fn should_run_pos_install_contract() {
 
     let rewards_purse_balance = builder.get_purse_balance(rewards_purse);
     assert_eq!(rewards_purse_balance, U512::zero());
+
+    // unbonding delay is stored under its named key
+    let unbonding_delay_uref = named_keys
+        .get(UNBONDING_DELAY_KEY)
+        .and_then(|key| key.into_uref())
+        .expect("should find unbonding delay in named_keys");
+    let unbonding_delay: u64 = builder
+        .query(None, unbonding_delay_uref.into(), &[])
+        .expect("should query unbonding delay")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t()
+        .expect("should be u64");
+    assert_eq!(unbonding_delay, UNBONDING_DELAY_MILLIS);
 }
 
 fn get_purse(named_keys: &NamedKeys, name: &str) -> Option<URef> {
@@ -0,0 +1,167 @@
+use engine_core::engine_state::{genesis::GenesisAccount, run_genesis_request::RunGenesisRequest};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::AccountHash, runtime_args, system_contract_errors::pos, ApiError, RuntimeArgs, U512,
+};
+
+const CONTRACT_BONDING: &str = "bonding.wasm";
+const CONTRACT_UNBONDING: &str = "unbonding.wasm";
+
+const VALIDATOR_1_ADDR: AccountHash = AccountHash::new([42; 32]);
+const VALIDATOR_2_ADDR: AccountHash = AccountHash::new([43; 32]);
+const VALIDATOR_BALANCE: u64 = 1_000_000_000;
+const VALIDATOR_STAKE: u64 = 50_000;
+
+const MIN_BOND_AMOUNT: u64 = 10_000;
+const MAX_BOND_AMOUNT: u64 = 100_000;
+
+const ARG_AMOUNT: &str = "amount";
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        for validator in &[VALIDATOR_1_ADDR, VALIDATOR_2_ADDR] {
+            tmp.push(GenesisAccount::new(
+                *validator,
+                Motes::new(VALIDATOR_BALANCE.into()),
+                Motes::new(VALIDATOR_STAKE.into()),
+            ));
+        }
+        tmp
+    };
+    let exec_config = utils::create_exec_config(accounts).with_bond_amount_bounds(
+        Motes::new(MIN_BOND_AMOUNT.into()),
+        Motes::new(MAX_BOND_AMOUNT.into()),
+    );
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+fn bond(builder: &mut InMemoryWasmTestBuilder, amount: u64) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_BONDING,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(amount),
+        },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn unbond(builder: &mut InMemoryWasmTestBuilder, amount: u64) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        VALIDATOR_1_ADDR,
+        CONTRACT_UNBONDING,
+        runtime_args! {
+            ARG_AMOUNT => Some(amount),
+        },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn has_stake(builder: &InMemoryWasmTestBuilder, validator: AccountHash, stake: u64) -> bool {
+    let lookup_key = format!(
+        "v_{}_{}",
+        base16::encode_lower(&validator.as_bytes()),
+        stake
+    );
+    builder
+        .get_pos_contract()
+        .named_keys()
+        .contains_key(&lookup_key)
+}
+
+fn assert_last_error(builder: &InMemoryWasmTestBuilder, expected: pos::Error) {
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have a response")
+        .to_owned();
+    let error_message = utils::get_error_message(response);
+
+    if !cfg!(feature = "enable-bonding") {
+        assert!(
+            error_message.contains(&format!("{:?}", ApiError::Unhandled)),
+            "error is {:?}",
+            error_message
+        );
+    } else {
+        assert!(
+            error_message.contains(&format!("{:?}", ApiError::from(expected))),
+            "error is {:?}",
+            error_message
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_reject_bond_below_minimum() {
+    let mut builder = setup();
+
+    bond(&mut builder, MIN_BOND_AMOUNT - 1);
+    assert_last_error(&builder, pos::Error::StakeBelowMinimum);
+    assert!(!has_stake(
+        &builder,
+        DEFAULT_ACCOUNT_ADDR,
+        MIN_BOND_AMOUNT - 1
+    ));
+}
+
+#[ignore]
+#[test]
+fn should_reject_bond_above_maximum() {
+    let mut builder = setup();
+
+    bond(&mut builder, MAX_BOND_AMOUNT + 1);
+    assert_last_error(&builder, pos::Error::StakeAboveMaximum);
+    assert!(!has_stake(
+        &builder,
+        DEFAULT_ACCOUNT_ADDR,
+        MAX_BOND_AMOUNT + 1
+    ));
+}
+
+#[ignore]
+#[test]
+fn should_bond_exactly_at_bounds() {
+    let mut builder = setup();
+
+    bond(&mut builder, MAX_BOND_AMOUNT);
+    if !cfg!(feature = "enable-bonding") && builder.is_error() {
+        return;
+    }
+    builder.expect_success();
+    assert!(has_stake(&builder, DEFAULT_ACCOUNT_ADDR, MAX_BOND_AMOUNT));
+
+    // Unbonding down to exactly the minimum leaves a valid stake.
+    unbond(&mut builder, VALIDATOR_STAKE - MIN_BOND_AMOUNT);
+    builder.expect_success();
+    assert!(has_stake(&builder, VALIDATOR_1_ADDR, MIN_BOND_AMOUNT));
+}
+
+#[ignore]
+#[test]
+fn should_reject_partial_unbond_below_minimum() {
+    let mut builder = setup();
+
+    unbond(&mut builder, VALIDATOR_STAKE - MIN_BOND_AMOUNT + 1);
+    assert_last_error(&builder, pos::Error::StakeBelowMinimum);
+    assert!(has_stake(&builder, VALIDATOR_1_ADDR, VALIDATOR_STAKE));
+}
@@ -9,7 +9,10 @@ use engine_test_support::{
     },
     DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
 };
-use types::{account::AccountHash, runtime_args, ApiError, Key, RuntimeArgs, URef, U512};
+use types::{
+    account::AccountHash, runtime_args, system_contract_errors::pos, ApiError, Key, RuntimeArgs,
+    URef, U512,
+};
 
 const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
 const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
@@ -27,10 +30,14 @@ const TEST_BOND: &str = "bond";
 const TEST_BOND_FROM_MAIN_PURSE: &str = "bond-from-main-purse";
 const TEST_SEED_NEW_ACCOUNT: &str = "seed_new_account";
 const TEST_UNBOND: &str = "unbond";
+const TEST_BOND_FROM_FORGED_PURSE: &str = "bond-from-forged-purse";
+const TEST_BOND_FROM_ADD_ONLY_PURSE: &str = "bond-from-add-only-purse";
+const TEST_DELEGATE_FROM_ADD_ONLY_PURSE: &str = "delegate-from-add-only-purse";
 
 const ARG_AMOUNT: &str = "amount";
 const ARG_ENTRY_POINT: &str = "entry_point";
 const ARG_ACCOUNT_PK: &str = "account_hash";
+const ARG_VALIDATOR: &str = "validator";
 
 fn get_pos_purse_by_name(builder: &InMemoryWasmTestBuilder, purse_name: &str) -> Option<URef> {
     let pos_contract = builder.get_pos_contract();
@@ -535,3 +542,86 @@ fn should_fail_unbonding_validator_without_bonding_first() {
         assert!(error_message.contains(&format!("{:?}", ApiError::ProofOfStake(0))));
     }
 }
+
+fn bond_with_command(command: &str) -> String {
+    exec_pos_bonding(runtime_args! {
+        ARG_ENTRY_POINT => command,
+        ARG_AMOUNT => U512::from(GENESIS_ACCOUNT_STAKE),
+    })
+}
+
+fn exec_pos_bonding(args: RuntimeArgs) -> String {
+    let run_genesis_request = utils::create_run_genesis_request(DEFAULT_ACCOUNTS.clone());
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_POS_BONDING, args).build();
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(&run_genesis_request)
+        .exec(exec_request)
+        .commit()
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(0)
+        .expect("should have a response")
+        .to_owned();
+
+    utils::get_error_message(response)
+}
+
+#[ignore]
+#[test]
+fn should_reject_forged_purse_before_bonding() {
+    let error_message = bond_with_command(TEST_BOND_FROM_FORGED_PURSE);
+
+    // pos-bonding's Error::ForgedPurse => 2
+    assert!(
+        error_message.contains(&format!("{:?}", ApiError::User(2))),
+        "error is {:?}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_bonding_from_add_only_purse() {
+    let error_message = bond_with_command(TEST_BOND_FROM_ADD_ONLY_PURSE);
+
+    if !cfg!(feature = "enable-bonding") {
+        assert!(error_message.contains(&format!("{:?}", ApiError::Unhandled)));
+    } else {
+        assert!(
+            error_message.contains(&format!(
+                "{:?}",
+                ApiError::from(pos::Error::InvalidBondingPurse)
+            )),
+            "error is {:?}",
+            error_message
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_fail_delegating_from_add_only_purse() {
+    let error_message = exec_pos_bonding(runtime_args! {
+        ARG_ENTRY_POINT => TEST_DELEGATE_FROM_ADD_ONLY_PURSE,
+        ARG_VALIDATOR => ACCOUNT_1_ADDR,
+        ARG_AMOUNT => U512::from(GENESIS_ACCOUNT_STAKE),
+    });
+
+    if !cfg!(feature = "enable-bonding") {
+        assert!(error_message.contains(&format!("{:?}", ApiError::Unhandled)));
+    } else {
+        assert!(
+            error_message.contains(&format!(
+                "{:?}",
+                ApiError::from(pos::Error::InvalidBondingPurse)
+            )),
+            "error is {:?}",
+            error_message
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use num_traits::Zero;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use engine_core::engine_state::genesis::GenesisAccount;
 use engine_shared::motes::Motes;
@@ -7,9 +7,10 @@ use engine_test_support::{
     internal::{utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS},
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::{account::AccountHash, RuntimeArgs, U512};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
 
 const CONTRACT_LOCAL_STATE: &str = "do_nothing.wasm";
+const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
 const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
 const ACCOUNT_1_BALANCE: u64 = 2000;
 const ACCOUNT_1_BOND: u64 = 1000;
@@ -18,25 +19,31 @@ const ACCOUNT_2_ADDR: AccountHash = AccountHash::new([2u8; 32]);
 const ACCOUNT_2_BALANCE: u64 = 2000;
 const ACCOUNT_2_BOND: u64 = 200;
 
+const DEFAULT_ACCOUNT_BOND: u64 = 100_000;
+
+const TEST_BOND: &str = "bond";
+const ARG_AMOUNT: &str = "amount";
+const ARG_ENTRY_POINT: &str = "entry_point";
+
+fn genesis_validators() -> Vec<GenesisAccount> {
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::new(
+        ACCOUNT_1_ADDR,
+        Motes::new(ACCOUNT_1_BALANCE.into()),
+        Motes::new(ACCOUNT_1_BOND.into()),
+    ));
+    accounts.push(GenesisAccount::new(
+        ACCOUNT_2_ADDR,
+        Motes::new(ACCOUNT_2_BALANCE.into()),
+        Motes::new(ACCOUNT_2_BOND.into()),
+    ));
+    accounts
+}
+
 #[ignore]
 #[test]
 fn should_return_bonded_validators() {
-    let accounts = {
-        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
-        let account_1 = GenesisAccount::new(
-            ACCOUNT_1_ADDR,
-            Motes::new(ACCOUNT_1_BALANCE.into()),
-            Motes::new(ACCOUNT_1_BOND.into()),
-        );
-        let account_2 = GenesisAccount::new(
-            ACCOUNT_2_ADDR,
-            Motes::new(ACCOUNT_2_BALANCE.into()),
-            Motes::new(ACCOUNT_2_BOND.into()),
-        );
-        tmp.push(account_1);
-        tmp.push(account_2);
-        tmp
-    };
+    let accounts = genesis_validators();
 
     let run_genesis_request = utils::create_run_genesis_request(accounts.clone());
 
@@ -73,3 +80,63 @@ fn should_return_bonded_validators() {
 
     assert_eq!(actual, expected);
 }
+
+#[ignore]
+#[test]
+fn should_query_bonded_validators_at_state_root() {
+    let run_genesis_request = utils::create_run_genesis_request(genesis_validators());
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let genesis_validators = builder
+        .query_bonded_validators(None)
+        .expect("should get genesis validators");
+    let expected: BTreeMap<AccountHash, U512> = vec![
+        (ACCOUNT_1_ADDR, U512::from(ACCOUNT_1_BOND)),
+        (ACCOUNT_2_ADDR, U512::from(ACCOUNT_2_BOND)),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(genesis_validators, expected);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        runtime_args! {
+            ARG_ENTRY_POINT => String::from(TEST_BOND),
+            ARG_AMOUNT => U512::from(DEFAULT_ACCOUNT_BOND)
+        },
+    )
+    .build();
+
+    let result = builder.exec(exec_request);
+    if !cfg!(feature = "enable-bonding") && result.is_error() {
+        return;
+    }
+    builder.expect_success().commit();
+
+    let actual = builder
+        .query_bonded_validators(None)
+        .expect("should get bonded validators");
+
+    let mut expected = expected;
+    expected.insert(DEFAULT_ACCOUNT_ADDR, U512::from(DEFAULT_ACCOUNT_BOND));
+    assert_eq!(actual, expected);
+
+    // The genesis state root still reflects only the genesis validators.
+    let at_genesis = builder
+        .query_bonded_validators(Some(builder.get_genesis_hash()))
+        .expect("should get bonded validators at genesis");
+    assert_eq!(at_genesis, genesis_validators);
+}
+
+#[ignore]
+#[test]
+fn should_not_find_bonded_validators_at_missing_state_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&utils::create_run_genesis_request(genesis_validators()));
+
+    let result = builder.query_bonded_validators(Some(vec![255u8; 32]));
+    assert!(result.is_err(), "{:?}", result);
+}
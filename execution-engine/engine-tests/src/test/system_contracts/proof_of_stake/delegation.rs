@@ -0,0 +1,286 @@
+use engine_core::engine_state::{
+    genesis::{GenesisAccount, POS_BONDING_PURSE},
+    CONV_RATE,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U512};
+
+const CONTRACT_DELEGATION: &str = "delegation.wasm";
+const CONTRACT_UNDELEGATION: &str = "undelegation.wasm";
+const CONTRACT_UNBONDING: &str = "unbonding.wasm";
+
+const VALIDATOR_ADDR: AccountHash = AccountHash::new([42; 32]);
+const VALIDATOR_BALANCE: u64 = 100_000_000;
+const VALIDATOR_STAKE: u64 = 50_000;
+const UNBONDED_ADDR: AccountHash = AccountHash::new([7; 32]);
+
+const DELEGATION_AMOUNT: u64 = 20_000;
+const UNDELEGATION_AMOUNT: u64 = 5_000;
+
+const ARG_AMOUNT: &str = "amount";
+const ARG_VALIDATOR: &str = "validator";
+
+fn get_pos_bonding_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let purse = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_BONDING_PURSE)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS bonding purse");
+    builder.get_purse_balance(purse)
+}
+
+fn get_effective_stake(builder: &InMemoryWasmTestBuilder, validator: AccountHash) -> Option<U512> {
+    builder
+        .get_bonded_validators()
+        .last()
+        .expect("should have bonded validators")
+        .get(&validator)
+        .cloned()
+}
+
+fn delegation_key_name(delegator: AccountHash, validator: AccountHash, amount: u64) -> String {
+    format!(
+        "d_{}_{}_{}",
+        base16::encode_lower(&delegator.as_bytes()),
+        base16::encode_lower(&validator.as_bytes()),
+        amount
+    )
+}
+
+fn has_delegations(builder: &InMemoryWasmTestBuilder) -> bool {
+    builder
+        .get_pos_contract()
+        .named_keys()
+        .keys()
+        .any(|name| name.starts_with("d_"))
+}
+
+fn last_exec_cost_in_motes(builder: &InMemoryWasmTestBuilder) -> U512 {
+    Motes::from_gas(builder.last_exec_gas_cost(), CONV_RATE)
+        .expect("should convert")
+        .value()
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account = GenesisAccount::new(
+            VALIDATOR_ADDR,
+            Motes::new(VALIDATOR_BALANCE.into()),
+            Motes::new(VALIDATOR_STAKE.into()),
+        );
+        tmp.push(account);
+        tmp
+    };
+
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_run_successful_delegate_and_undelegate() {
+    let mut builder = setup();
+
+    // Genesis doesn't create any delegations.
+    assert!(!has_delegations(&builder));
+
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get default account");
+    let main_purse_balance = builder.get_purse_balance(default_account.main_purse());
+
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DELEGATION,
+        runtime_args! {
+            ARG_VALIDATOR => VALIDATOR_ADDR,
+            ARG_AMOUNT => U512::from(DELEGATION_AMOUNT),
+        },
+    )
+    .build();
+
+    builder.exec(exec_request_1);
+    if !cfg!(feature = "enable-bonding") && builder.is_error() {
+        return;
+    }
+    builder.expect_success().commit();
+
+    // The delegated amount is added to the validator's stake.
+    assert_eq!(
+        get_effective_stake(&builder, VALIDATOR_ADDR),
+        Some(U512::from(VALIDATOR_STAKE + DELEGATION_AMOUNT))
+    );
+    assert!(builder
+        .get_pos_contract()
+        .named_keys()
+        .contains_key(&delegation_key_name(
+            DEFAULT_ACCOUNT_ADDR,
+            VALIDATOR_ADDR,
+            DELEGATION_AMOUNT
+        )));
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(VALIDATOR_STAKE + DELEGATION_AMOUNT)
+    );
+    let main_purse_balance =
+        main_purse_balance - last_exec_cost_in_motes(&builder) - DELEGATION_AMOUNT;
+    assert_eq!(
+        builder.get_purse_balance(default_account.main_purse()),
+        main_purse_balance
+    );
+
+    //
+    // Partially undelegate
+    //
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_UNDELEGATION,
+        runtime_args! {
+            ARG_VALIDATOR => VALIDATOR_ADDR,
+            ARG_AMOUNT => Some(UNDELEGATION_AMOUNT),
+        },
+    )
+    .build();
+
+    builder.exec(exec_request_2).expect_success().commit();
+
+    assert_eq!(
+        get_effective_stake(&builder, VALIDATOR_ADDR),
+        Some(U512::from(
+            VALIDATOR_STAKE + DELEGATION_AMOUNT - UNDELEGATION_AMOUNT
+        ))
+    );
+    assert!(builder
+        .get_pos_contract()
+        .named_keys()
+        .contains_key(&delegation_key_name(
+            DEFAULT_ACCOUNT_ADDR,
+            VALIDATOR_ADDR,
+            DELEGATION_AMOUNT - UNDELEGATION_AMOUNT
+        )));
+    // The undelegated amount is refunded to the delegator's main purse.
+    let main_purse_balance =
+        main_purse_balance - last_exec_cost_in_motes(&builder) + UNDELEGATION_AMOUNT;
+    assert_eq!(
+        builder.get_purse_balance(default_account.main_purse()),
+        main_purse_balance
+    );
+
+    //
+    // Fully undelegate
+    //
+    let exec_request_3 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_UNDELEGATION,
+        runtime_args! {
+            ARG_VALIDATOR => VALIDATOR_ADDR,
+            ARG_AMOUNT => None::<u64>,
+        },
+    )
+    .build();
+
+    builder.exec(exec_request_3).expect_success().commit();
+
+    assert_eq!(
+        get_effective_stake(&builder, VALIDATOR_ADDR),
+        Some(U512::from(VALIDATOR_STAKE))
+    );
+    assert!(!has_delegations(&builder));
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(VALIDATOR_STAKE)
+    );
+    let main_purse_balance = main_purse_balance - last_exec_cost_in_motes(&builder)
+        + (DELEGATION_AMOUNT - UNDELEGATION_AMOUNT);
+    assert_eq!(
+        builder.get_purse_balance(default_account.main_purse()),
+        main_purse_balance
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_delegate_to_unbonded_validator() {
+    let mut builder = setup();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DELEGATION,
+        runtime_args! {
+            ARG_VALIDATOR => UNBONDED_ADDR,
+            ARG_AMOUNT => U512::from(DELEGATION_AMOUNT),
+        },
+    )
+    .build();
+
+    builder.exec(exec_request).commit();
+
+    assert!(builder.is_error());
+    assert!(!has_delegations(&builder));
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(VALIDATOR_STAKE)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_keep_delegated_stake_when_validator_unbonds() {
+    let mut builder = setup();
+
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DELEGATION,
+        runtime_args! {
+            ARG_VALIDATOR => VALIDATOR_ADDR,
+            ARG_AMOUNT => U512::from(DELEGATION_AMOUNT),
+        },
+    )
+    .build();
+
+    builder.exec(exec_request_1);
+    if !cfg!(feature = "enable-bonding") && builder.is_error() {
+        return;
+    }
+    builder.expect_success().commit();
+
+    let validator_account = builder
+        .get_account(VALIDATOR_ADDR)
+        .expect("should get validator account");
+    let validator_balance = builder.get_purse_balance(validator_account.main_purse());
+
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        VALIDATOR_ADDR,
+        CONTRACT_UNBONDING,
+        runtime_args! {
+            ARG_AMOUNT => None::<u64>,
+        },
+    )
+    .build();
+
+    builder.exec(exec_request_2).expect_success().commit();
+
+    // Only the validator's own stake is paid out; the delegated stake stays bonded.
+    assert_eq!(
+        get_effective_stake(&builder, VALIDATOR_ADDR),
+        Some(U512::from(DELEGATION_AMOUNT))
+    );
+    assert_eq!(
+        builder.get_purse_balance(validator_account.main_purse()),
+        validator_balance - last_exec_cost_in_motes(&builder) + VALIDATOR_STAKE
+    );
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(DELEGATION_AMOUNT)
+    );
+}
@@ -0,0 +1,174 @@
+use grpc::RequestOptions;
+
+use engine_core::engine_state::{run_genesis_request::RunGenesisRequest, CONV_RATE};
+use engine_grpc_server::engine_server::{ipc, ipc_grpc::ExecutionEngineService};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const PROPOSER_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const ROUND_SEIGNIORAGE_RATE_NUMERATOR: u64 = 1;
+const ROUND_SEIGNIORAGE_RATE_DENOMINATOR: u64 = 1_000_000;
+const ARG_AMOUNT: &str = "amount";
+
+fn exec_with_proposer(builder: &mut InMemoryWasmTestBuilder) -> U512 {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DO_NOTHING,
+        RuntimeArgs::default(),
+    )
+    .with_proposer(PROPOSER_ADDR)
+    .build();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_index = builder.get_exec_responses_count() - 1;
+    let response = builder
+        .get_exec_response(exec_index)
+        .expect("should have exec response");
+    // the finalization result is reported apart from the deploy results
+    assert_eq!(response.len(), 1);
+    let rewards_result = builder
+        .get_rewards_result(exec_index)
+        .expect("should have rewards result");
+    assert!(rewards_result.as_error().is_none(), "{:?}", rewards_result);
+
+    Motes::from_gas(response[0].cost(), CONV_RATE)
+        .expect("should convert gas to motes")
+        .value()
+}
+
+fn proposer_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let proposer = builder
+        .get_account(PROPOSER_ADDR)
+        .expect("proposer should have an account");
+    builder.get_purse_balance(proposer.main_purse())
+}
+
+#[ignore]
+#[test]
+fn should_distribute_fees_to_proposer() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let fee_1 = exec_with_proposer(&mut builder);
+    assert_eq!(proposer_balance(&builder), fee_1);
+    assert_eq!(builder.get_pos_rewards_purse_balance(), U512::zero());
+    assert_eq!(builder.get_pos_payment_purse_balance(), U512::zero());
+
+    let fee_2 = exec_with_proposer(&mut builder);
+    assert_eq!(proposer_balance(&builder), fee_1 + fee_2);
+    assert_eq!(builder.get_pos_rewards_purse_balance(), U512::zero());
+}
+
+#[ignore]
+#[test]
+fn should_not_distribute_rewards_without_proposer() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DO_NOTHING,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    assert_eq!(response.len(), 1);
+    assert!(builder.get_rewards_result(0).is_none());
+    assert!(builder.get_account(PROPOSER_ADDR).is_none());
+    assert_ne!(builder.get_pos_rewards_purse_balance(), U512::zero());
+}
+
+#[ignore]
+#[test]
+fn should_mint_round_reward_and_grow_total_supply() {
+    let exec_config = utils::create_exec_config(DEFAULT_ACCOUNTS.clone())
+        .with_round_seigniorage_rate(
+            ROUND_SEIGNIORAGE_RATE_NUMERATOR,
+            ROUND_SEIGNIORAGE_RATE_DENOMINATOR,
+        );
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let total_supply_before = builder.get_total_supply();
+    let round_reward = total_supply_before * U512::from(ROUND_SEIGNIORAGE_RATE_NUMERATOR)
+        / U512::from(ROUND_SEIGNIORAGE_RATE_DENOMINATOR);
+    assert!(!round_reward.is_zero());
+
+    let fee = exec_with_proposer(&mut builder);
+
+    // Fees only move existing motes, so the supply grows by the round reward alone.
+    assert_eq!(
+        builder.get_total_supply(),
+        total_supply_before + round_reward
+    );
+    assert_eq!(proposer_balance(&builder), fee + round_reward);
+    assert_eq!(builder.get_pos_rewards_purse_balance(), U512::zero());
+}
+
+#[ignore]
+#[test]
+fn should_report_one_deploy_result_per_deploy_with_proposer() {
+    const DEPLOY_COUNT: u8 = 3;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = (0..DEPLOY_COUNT)
+        .map(|deploy_index| {
+            DeployItemBuilder::new()
+                .with_address(DEFAULT_ACCOUNT_ADDR)
+                .with_session_code(CONTRACT_DO_NOTHING, RuntimeArgs::default())
+                .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+                .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+                .with_deploy_hash([deploy_index; 32])
+                .build()
+        })
+        .fold(
+            ExecuteRequestBuilder::new(),
+            ExecuteRequestBuilder::push_deploy,
+        )
+        .with_pre_state_hash(&builder.get_post_state_hash())
+        .with_proposer(PROPOSER_ADDR)
+        .build();
+
+    let response = builder
+        .get_engine_state()
+        .execute(
+            RequestOptions::new(),
+            ipc::ExecuteRequest::from(exec_request),
+        )
+        .wait_drop_metadata()
+        .expect("should receive response");
+
+    assert!(response.has_success(), "{:?}", response);
+    let exec_result = response.get_success();
+    assert_eq!(
+        exec_result.get_deploy_results().len(),
+        usize::from(DEPLOY_COUNT)
+    );
+    for deploy_result in exec_result.get_deploy_results() {
+        assert!(deploy_result.has_execution_result(), "{:?}", deploy_result);
+    }
+    assert!(exec_result.has_rewards_result());
+    assert!(exec_result.get_rewards_result().has_execution_result());
+}
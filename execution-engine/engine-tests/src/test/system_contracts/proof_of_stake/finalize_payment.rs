@@ -1,9 +1,6 @@
 use std::convert::TryInto;
 
-use engine_core::engine_state::{
-    genesis::{POS_PAYMENT_PURSE, POS_REWARDS_PURSE},
-    CONV_RATE,
-};
+use engine_core::engine_state::CONV_RATE;
 use engine_shared::{account::Account, motes::Motes};
 use engine_test_support::{
     internal::{
@@ -99,8 +96,8 @@ fn finalize_payment_should_refund_to_specified_purse() {
 
     builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
 
-    let payment_pre_balance = get_pos_payment_purse_balance(&builder);
-    let rewards_pre_balance = get_pos_rewards_purse_balance(&builder);
+    let payment_pre_balance = builder.get_pos_payment_purse_balance();
+    let rewards_pre_balance = builder.get_pos_rewards_purse_balance();
     let refund_pre_balance =
         get_named_account_balance(&builder, DEFAULT_ACCOUNT_ADDR, LOCAL_REFUND_PURSE)
             .unwrap_or_else(U512::zero);
@@ -140,8 +137,8 @@ fn finalize_payment_should_refund_to_specified_purse() {
             .value()
     };
 
-    let payment_post_balance = get_pos_payment_purse_balance(&builder);
-    let rewards_post_balance = get_pos_rewards_purse_balance(&builder);
+    let payment_post_balance = builder.get_pos_payment_purse_balance();
+    let rewards_post_balance = builder.get_pos_rewards_purse_balance();
     let refund_post_balance =
         get_named_account_balance(&builder, DEFAULT_ACCOUNT_ADDR, LOCAL_REFUND_PURSE)
             .expect("should have refund balance");
@@ -171,18 +168,6 @@ fn finalize_payment_should_refund_to_specified_purse() {
 
 // ------------- utility functions -------------------- //
 
-fn get_pos_payment_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
-    let purse =
-        get_pos_purse_by_name(builder, POS_PAYMENT_PURSE).expect("should find PoS payment purse");
-    builder.get_purse_balance(purse)
-}
-
-fn get_pos_rewards_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
-    let purse =
-        get_pos_purse_by_name(builder, POS_REWARDS_PURSE).expect("should find PoS rewards purse");
-    builder.get_purse_balance(purse)
-}
-
 fn get_pos_refund_purse(builder: &InMemoryWasmTestBuilder) -> Option<Key> {
     let pos_contract = builder.get_pos_contract();
     pos_contract
@@ -191,15 +176,6 @@ fn get_pos_refund_purse(builder: &InMemoryWasmTestBuilder) -> Option<Key> {
         .cloned()
 }
 
-fn get_pos_purse_by_name(builder: &InMemoryWasmTestBuilder, purse_name: &str) -> Option<URef> {
-    let pos_contract = builder.get_pos_contract();
-    pos_contract
-        .named_keys()
-        .get(purse_name)
-        .and_then(Key::as_uref)
-        .cloned()
-}
-
 fn get_named_account_balance(
     builder: &InMemoryWasmTestBuilder,
     account_address: AccountHash,
@@ -0,0 +1,132 @@
+use engine_core::engine_state::{run_genesis_request::RunGenesisRequest, CONV_RATE};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_SET_GAS_PRICE: &str = "pos_set_gas_price.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+
+const SYSTEM_ADDR: AccountHash = AccountHash::new([0u8; 32]);
+
+const ARG_AMOUNT: &str = "amount";
+const ARG_GAS_PRICE: &str = "gas_price";
+const ARG_TARGET: &str = "target";
+
+/// Runs `do_nothing.wasm` from the default account and returns the gas used and the motes
+/// charged, checking the latter were taken from the account's main purse.
+fn exec_do_nothing(builder: &mut InMemoryWasmTestBuilder) -> (U512, U512) {
+    let main_purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account")
+        .main_purse();
+    let balance_before = builder.get_purse_balance(main_purse);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_DO_NOTHING,
+        RuntimeArgs::default(),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let response = builder
+        .get_exec_response(builder.get_exec_responses_count() - 1)
+        .expect("should have exec response");
+    let cost = response[0].cost().value();
+    let charged = response[0].charged().value();
+
+    let balance_after = builder.get_purse_balance(main_purse);
+    assert_eq!(balance_before - balance_after, charged);
+
+    (cost, charged)
+}
+
+fn run_genesis_with_gas_price(gas_price: u64) -> InMemoryWasmTestBuilder {
+    let exec_config = utils::create_exec_config(DEFAULT_ACCOUNTS.clone()).with_gas_price(gas_price);
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_charge_motes_scaled_by_genesis_gas_price() {
+    const PRICE_FACTOR: u64 = 3;
+
+    let mut builder = run_genesis_with_gas_price(CONV_RATE);
+    let (cost_1, charged_1) = exec_do_nothing(&mut builder);
+    assert_eq!(charged_1, cost_1 * CONV_RATE);
+
+    let mut builder = run_genesis_with_gas_price(CONV_RATE * PRICE_FACTOR);
+    let (cost_2, charged_2) = exec_do_nothing(&mut builder);
+    assert_eq!(charged_2, cost_2 * CONV_RATE * PRICE_FACTOR);
+
+    assert_eq!(cost_1, cost_2);
+    assert_eq!(charged_2, charged_1 * PRICE_FACTOR);
+}
+
+#[ignore]
+#[test]
+fn should_charge_at_gas_price_set_by_system_account() {
+    const NEW_GAS_PRICE: u64 = CONV_RATE * 2;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let fund_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        runtime_args! { ARG_TARGET => SYSTEM_ADDR, ARG_AMOUNT => *DEFAULT_PAYMENT },
+    )
+    .build();
+    builder.exec(fund_request).expect_success().commit();
+
+    let (cost, charged) = exec_do_nothing(&mut builder);
+    assert_eq!(charged, cost * CONV_RATE);
+
+    let set_gas_price_request = ExecuteRequestBuilder::standard(
+        SYSTEM_ADDR,
+        CONTRACT_SET_GAS_PRICE,
+        runtime_args! { ARG_GAS_PRICE => NEW_GAS_PRICE },
+    )
+    .build();
+    builder
+        .exec(set_gas_price_request)
+        .expect_success()
+        .commit();
+
+    let (cost, charged) = exec_do_nothing(&mut builder);
+    assert_eq!(charged, cost * NEW_GAS_PRICE);
+}
+
+#[ignore]
+#[test]
+fn should_not_set_gas_price_from_user_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_SET_GAS_PRICE,
+        runtime_args! { ARG_GAS_PRICE => CONV_RATE * 2 },
+    )
+    .build();
+
+    assert!(builder.exec(exec_request).is_error());
+
+    let (cost, charged) = exec_do_nothing(&mut builder);
+    assert_eq!(charged, cost * CONV_RATE);
+}
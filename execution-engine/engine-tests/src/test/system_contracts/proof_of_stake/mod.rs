@@ -1,5 +1,11 @@
+mod bond_amount_bounds;
 mod bonding;
 mod commit_validators;
+mod delegation;
+mod distribute_rewards;
 mod finalize_payment;
+mod gas_price;
 mod get_payment_purse;
 mod refund_purse;
+mod slash;
+mod unbonding_delay;
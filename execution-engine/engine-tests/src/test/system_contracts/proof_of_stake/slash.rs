@@ -0,0 +1,200 @@
+use engine_core::engine_state::{
+    genesis::{GenesisAccount, POS_BONDING_PURSE, POS_REWARDS_PURSE},
+    run_genesis_request::RunGenesisRequest,
+    CONV_RATE,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U512};
+
+const CONTRACT_POS_SLASH: &str = "pos_slash.wasm";
+const CONTRACT_PROCESS_UNBOND_REQUESTS: &str = "process_unbond_requests.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const CONTRACT_UNBONDING: &str = "unbonding.wasm";
+
+const SYSTEM_ADDR: AccountHash = AccountHash::new([0u8; 32]);
+const VALIDATOR_1_ADDR: AccountHash = AccountHash::new([42; 32]);
+const VALIDATOR_2_ADDR: AccountHash = AccountHash::new([43; 32]);
+const VALIDATOR_BALANCE: u64 = 100_000_000;
+const VALIDATOR_STAKE: u64 = 50_000;
+const UNBOND_AMOUNT: u64 = 10_000;
+
+const UNBONDING_DELAY_MILLIS: u64 = 60_000;
+const UNBOND_BLOCK_TIME: u64 = 1_000;
+const SLASH_BLOCK_TIME: u64 = 2_000;
+
+const ARG_AMOUNT: &str = "amount";
+const ARG_TARGET: &str = "target";
+const ARG_VALIDATOR_PUBLIC_KEYS: &str = "validator_public_keys";
+
+fn get_pos_purse_balance(builder: &InMemoryWasmTestBuilder, name: &str) -> U512 {
+    let purse = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(name)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS purse");
+    builder.get_purse_balance(purse)
+}
+
+fn is_bonded(builder: &InMemoryWasmTestBuilder, validator: AccountHash) -> bool {
+    let prefix = format!("v_{}_", base16::encode_lower(&validator.as_bytes()));
+    builder
+        .get_pos_contract()
+        .named_keys()
+        .keys()
+        .any(|name| name.starts_with(&prefix))
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        for validator in &[VALIDATOR_1_ADDR, VALIDATOR_2_ADDR] {
+            tmp.push(GenesisAccount::new(
+                *validator,
+                Motes::new(VALIDATOR_BALANCE.into()),
+                Motes::new(VALIDATOR_STAKE.into()),
+            ));
+        }
+        tmp
+    };
+    let exec_config =
+        utils::create_exec_config(accounts).with_unbonding_delay_millis(UNBONDING_DELAY_MILLIS);
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    // The system account needs funds to pay for the slashing deploy.
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        runtime_args! { ARG_TARGET => SYSTEM_ADDR, ARG_AMOUNT => *DEFAULT_PAYMENT },
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_slash_validator_and_cancel_its_unbond_requests() {
+    let mut builder = setup();
+
+    let validator_account = builder
+        .get_account(VALIDATOR_1_ADDR)
+        .expect("should get validator account");
+
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        VALIDATOR_1_ADDR,
+        CONTRACT_UNBONDING,
+        runtime_args! {
+            ARG_AMOUNT => Some(UNBOND_AMOUNT),
+        },
+    )
+    .with_block_time(UNBOND_BLOCK_TIME)
+    .build();
+
+    builder.exec(exec_request_1);
+    if !cfg!(feature = "enable-bonding") && builder.is_error() {
+        return;
+    }
+    builder.expect_success().commit();
+
+    let validator_balance = builder.get_purse_balance(validator_account.main_purse());
+    let rewards_balance = get_pos_purse_balance(&builder, POS_REWARDS_PURSE);
+
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        SYSTEM_ADDR,
+        CONTRACT_POS_SLASH,
+        runtime_args! {
+            ARG_VALIDATOR_PUBLIC_KEYS => vec![VALIDATOR_1_ADDR],
+        },
+    )
+    .with_block_time(SLASH_BLOCK_TIME)
+    .build();
+
+    builder.exec(exec_request_2).expect_success().commit();
+
+    let slash_cost = Motes::from_gas(builder.last_exec_gas_cost(), CONV_RATE)
+        .expect("should convert")
+        .value();
+
+    // The remaining stake and the pending unbond are both moved to the rewards purse.
+    assert!(!is_bonded(&builder, VALIDATOR_1_ADDR));
+    assert!(is_bonded(&builder, VALIDATOR_2_ADDR));
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        U512::from(VALIDATOR_STAKE)
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_REWARDS_PURSE),
+        rewards_balance + slash_cost + VALIDATOR_STAKE
+    );
+
+    // The cancelled unbond request is never paid out.
+    let exec_request_3 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_PROCESS_UNBOND_REQUESTS,
+        RuntimeArgs::default(),
+    )
+    .with_block_time(UNBOND_BLOCK_TIME + UNBONDING_DELAY_MILLIS)
+    .build();
+
+    builder.exec(exec_request_3).expect_success().commit();
+
+    assert_eq!(
+        builder.get_purse_balance(validator_account.main_purse()),
+        validator_balance
+    );
+
+    // The slashed validator can't unbond anymore.
+    let exec_request_4 = ExecuteRequestBuilder::standard(
+        VALIDATOR_1_ADDR,
+        CONTRACT_UNBONDING,
+        runtime_args! {
+            ARG_AMOUNT => None::<u64>,
+        },
+    )
+    .build();
+
+    builder.exec(exec_request_4).commit();
+
+    assert!(builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_not_slash_if_not_called_by_system_account() {
+    let mut builder = setup();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_SLASH,
+        runtime_args! {
+            ARG_VALIDATOR_PUBLIC_KEYS => vec![VALIDATOR_1_ADDR],
+        },
+    )
+    .build();
+
+    builder.exec(exec_request).commit();
+
+    assert!(builder.is_error());
+    assert!(is_bonded(&builder, VALIDATOR_1_ADDR));
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        U512::from(2 * VALIDATOR_STAKE)
+    );
+}
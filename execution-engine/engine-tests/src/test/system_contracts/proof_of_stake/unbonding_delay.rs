@@ -0,0 +1,143 @@
+use engine_core::engine_state::{
+    genesis::{GenesisAccount, POS_BONDING_PURSE},
+    run_genesis_request::RunGenesisRequest,
+    CONV_RATE,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U512};
+
+const CONTRACT_UNBONDING: &str = "unbonding.wasm";
+const CONTRACT_PROCESS_UNBOND_REQUESTS: &str = "process_unbond_requests.wasm";
+
+const VALIDATOR_1_ADDR: AccountHash = AccountHash::new([42; 32]);
+const VALIDATOR_2_ADDR: AccountHash = AccountHash::new([43; 32]);
+const VALIDATOR_BALANCE: u64 = 100_000_000;
+const VALIDATOR_STAKE: u64 = 50_000;
+const UNBOND_AMOUNT: u64 = 10_000;
+
+const UNBONDING_DELAY_MILLIS: u64 = 60_000;
+const UNBOND_BLOCK_TIME: u64 = 1_000;
+
+const ARG_AMOUNT: &str = "amount";
+
+fn get_pos_bonding_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let purse = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_BONDING_PURSE)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS bonding purse");
+    builder.get_purse_balance(purse)
+}
+
+fn process_unbond_requests(builder: &mut InMemoryWasmTestBuilder, block_time: u64) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_PROCESS_UNBOND_REQUESTS,
+        RuntimeArgs::default(),
+    )
+    .with_block_time(block_time)
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_release_unbonded_stake_only_after_delay() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        for validator in &[VALIDATOR_1_ADDR, VALIDATOR_2_ADDR] {
+            tmp.push(GenesisAccount::new(
+                *validator,
+                Motes::new(VALIDATOR_BALANCE.into()),
+                Motes::new(VALIDATOR_STAKE.into()),
+            ));
+        }
+        tmp
+    };
+    let exec_config =
+        utils::create_exec_config(accounts).with_unbonding_delay_millis(UNBONDING_DELAY_MILLIS);
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let validator_account = builder
+        .get_account(VALIDATOR_1_ADDR)
+        .expect("should get validator account");
+    let validator_balance = builder.get_purse_balance(validator_account.main_purse());
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        VALIDATOR_1_ADDR,
+        CONTRACT_UNBONDING,
+        runtime_args! {
+            ARG_AMOUNT => Some(UNBOND_AMOUNT),
+        },
+    )
+    .with_block_time(UNBOND_BLOCK_TIME)
+    .build();
+
+    builder.exec(exec_request);
+    if !cfg!(feature = "enable-bonding") && builder.is_error() {
+        return;
+    }
+    builder.expect_success().commit();
+
+    let unbond_cost = Motes::from_gas(builder.last_exec_gas_cost(), CONV_RATE)
+        .expect("should convert")
+        .value();
+
+    // The stake is decreased immediately, but the funds are held back.
+    let lookup_key = format!(
+        "v_{}_{}",
+        base16::encode_lower(&VALIDATOR_1_ADDR.as_bytes()),
+        VALIDATOR_STAKE - UNBOND_AMOUNT
+    );
+    assert!(builder
+        .get_pos_contract()
+        .named_keys()
+        .contains_key(&lookup_key));
+    let validator_balance = validator_balance - unbond_cost;
+    assert_eq!(
+        builder.get_purse_balance(validator_account.main_purse()),
+        validator_balance
+    );
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(2 * VALIDATOR_STAKE)
+    );
+
+    // Just before the delay has passed, the funds are still held back.
+    process_unbond_requests(&mut builder, UNBOND_BLOCK_TIME + UNBONDING_DELAY_MILLIS - 1);
+    assert_eq!(
+        builder.get_purse_balance(validator_account.main_purse()),
+        validator_balance
+    );
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(2 * VALIDATOR_STAKE)
+    );
+
+    // Once the delay has passed, the funds are returned to the validator.
+    process_unbond_requests(&mut builder, UNBOND_BLOCK_TIME + UNBONDING_DELAY_MILLIS);
+    assert_eq!(
+        builder.get_purse_balance(validator_account.main_purse()),
+        validator_balance + UNBOND_AMOUNT
+    );
+    assert_eq!(
+        get_pos_bonding_purse_balance(&builder),
+        U512::from(2 * VALIDATOR_STAKE - UNBOND_AMOUNT)
+    );
+}
@@ -0,0 +1,135 @@
+use assert_matches::assert_matches;
+
+use engine_core::{
+    engine_state::{run_genesis_request::RunGenesisRequest, Error},
+    execution,
+};
+use engine_shared::{stored_value::StoredValue, transform::Transform};
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::AccountHash, runtime_args, ApiError, CLValue, Key, ProtocolVersion, RuntimeArgs,
+    SystemContractRegistry, SystemContractType, SYSTEM_CONTRACT_REGISTRY_ADDR, U512,
+};
+
+const CONTRACT_GET_SYSTEM_CONTRACT_BY_NAME: &str = "get_system_contract_by_name.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const ARG_NAME: &str = "name";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+const SYSTEM_CONTRACT: &str = "system_contract";
+
+/// `get_system_contract_by_name` is only available from this version onwards.
+fn protocol_version_2() -> ProtocolVersion {
+    ProtocolVersion::from_parts(2, 0, 0)
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let run_genesis_request = RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        protocol_version_2(),
+        DEFAULT_EXEC_CONFIG.clone(),
+    );
+    let fund_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => ACCOUNT_1_ADDR,
+            ARG_AMOUNT => *DEFAULT_PAYMENT * U512::from(10),
+        },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request)
+        .exec(fund_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn exec_get_system_contract_by_name(builder: &mut InMemoryWasmTestBuilder, name: &str) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        CONTRACT_GET_SYSTEM_CONTRACT_BY_NAME,
+        runtime_args! { ARG_NAME => name.to_string() },
+    )
+    .with_protocol_version(protocol_version_2())
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+#[ignore]
+#[test]
+fn should_write_system_contract_registry_at_genesis() {
+    let builder = setup();
+
+    let registry_key = Key::Hash(SYSTEM_CONTRACT_REGISTRY_ADDR);
+    let registry: SystemContractRegistry = match builder.get_genesis_transforms().get(&registry_key)
+    {
+        Some(Transform::Write(StoredValue::CLValue(cl_value))) => {
+            cl_value.clone().into_t().expect("should be a registry")
+        }
+        other => panic!("expected registry write, got {:?}", other),
+    };
+
+    let mut expected = SystemContractRegistry::new();
+    expected.insert(
+        SystemContractType::Mint.to_string(),
+        Key::Hash(builder.get_mint_contract_hash()),
+    );
+    expected.insert(
+        SystemContractType::ProofOfStake.to_string(),
+        Key::Hash(builder.get_pos_contract_hash()),
+    );
+    expected.insert(
+        SystemContractType::StandardPayment.to_string(),
+        Key::Hash(builder.get_standard_payment_contract_hash()),
+    );
+    assert_eq!(registry, expected);
+}
+
+#[ignore]
+#[test]
+fn should_resolve_mint_via_registry() {
+    let mut builder = setup();
+    exec_get_system_contract_by_name(&mut builder, "mint");
+    builder.expect_success();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1");
+    assert_eq!(
+        account_1.named_keys().get(SYSTEM_CONTRACT),
+        Some(&Key::Hash(builder.get_mint_contract_hash()))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_resolve_unknown_system_contract() {
+    let mut builder = setup();
+    exec_get_system_contract_by_name(&mut builder, "unknown");
+
+    let response = builder
+        .get_exec_responses()
+        .last()
+        .expect("should have last response");
+    let error = response
+        .get(0)
+        .expect("should have first result")
+        .as_error()
+        .expect("should have error");
+    assert_matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::InvalidSystemContract))
+    );
+}
@@ -1,10 +1,10 @@
 use assert_matches::assert_matches;
 
 use engine_core::{
-    engine_state::{genesis::POS_REWARDS_PURSE, Error, CONV_RATE, MAX_PAYMENT},
+    engine_state::{Error, CONV_RATE, MAX_PAYMENT},
     execution,
 };
-use engine_shared::{motes::Motes, transform::Transform};
+use engine_shared::{gas::Gas, motes::Motes, transform::Transform};
 use engine_test_support::{
     internal::{
         utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
@@ -12,7 +12,7 @@ use engine_test_support::{
     },
     DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
 };
-use types::{account::AccountHash, runtime_args, ApiError, Key, RuntimeArgs, URef, U512};
+use types::{account::AccountHash, runtime_args, ApiError, RuntimeArgs, U512};
 
 const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
 const DO_NOTHING_WASM: &str = "do_nothing.wasm";
@@ -108,7 +108,7 @@ fn should_raise_insufficient_payment_when_payment_code_does_not_pay_enough() {
             .expect("should have account")
             .main_purse(),
     );
-    let reward_balance = get_pos_rewards_purse_balance(&builder);
+    let reward_balance = builder.get_pos_rewards_purse_balance();
 
     let initial_balance: U512 = U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE);
     let expected_reward_balance: U512 = U512::from(MAX_PAYMENT);
@@ -144,6 +144,22 @@ fn should_raise_insufficient_payment_when_payment_code_does_not_pay_enough() {
         error_message, "Insufficient payment",
         "expected insufficient payment"
     );
+
+    let phase_costs = execution_result.phase_costs();
+    assert!(
+        phase_costs.payment > Gas::default(),
+        "payment should have run"
+    );
+    assert_eq!(
+        phase_costs.session,
+        Gas::default(),
+        "session should not have run"
+    );
+    assert_eq!(
+        phase_costs.finalize,
+        Gas::default(),
+        "finalize should not have run"
+    );
 }
 
 #[cfg(feature = "use-system-contracts")]
@@ -186,7 +202,7 @@ fn should_raise_insufficient_payment_error_when_out_of_gas() {
             .expect("should have account")
             .main_purse(),
     );
-    let reward_balance = get_pos_rewards_purse_balance(&builder);
+    let reward_balance = builder.get_pos_rewards_purse_balance();
 
     assert_eq!(
         modified_balance,
@@ -259,7 +275,7 @@ fn should_forward_payment_execution_runtime_error() {
             .expect("should have account")
             .main_purse(),
     );
-    let reward_balance = get_pos_rewards_purse_balance(&builder);
+    let reward_balance = builder.get_pos_rewards_purse_balance();
 
     assert_eq!(
         modified_balance,
@@ -328,7 +344,7 @@ fn should_forward_payment_execution_gas_limit_error() {
             .expect("should have account")
             .main_purse(),
     );
-    let reward_balance = get_pos_rewards_purse_balance(&builder);
+    let reward_balance = builder.get_pos_rewards_purse_balance();
 
     assert_eq!(
         modified_balance,
@@ -509,6 +525,18 @@ fn should_correctly_charge_when_session_code_fails() {
         initial_balance, tally,
         "no net resources should be gained or lost post-distribution"
     );
+
+    // The reverted session is still reported, and charged for, alongside payment.
+    let phase_costs = success_result.phase_costs();
+    assert!(
+        phase_costs.payment > Gas::default(),
+        "payment should have run"
+    );
+    assert!(
+        phase_costs.session > Gas::default(),
+        "session should have run"
+    );
+    assert_eq!(phase_costs.payment + phase_costs.session, gas);
 }
 
 #[ignore]
@@ -574,22 +602,6 @@ fn should_correctly_charge_when_session_code_succeeds() {
     )
 }
 
-fn get_pos_purse_by_name(builder: &InMemoryWasmTestBuilder, purse_name: &str) -> Option<URef> {
-    let pos_contract = builder.get_pos_contract();
-
-    pos_contract
-        .named_keys()
-        .get(purse_name)
-        .and_then(Key::as_uref)
-        .cloned()
-}
-
-fn get_pos_rewards_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
-    let purse =
-        get_pos_purse_by_name(builder, POS_REWARDS_PURSE).expect("should find PoS payment purse");
-    builder.get_purse_balance(purse)
-}
-
 #[ignore]
 #[test]
 fn should_finalize_to_rewards_purse() {
@@ -616,12 +628,12 @@ fn should_finalize_to_rewards_purse() {
 
     builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
 
-    let rewards_purse_balance = get_pos_rewards_purse_balance(&builder);
+    let rewards_purse_balance = builder.get_pos_rewards_purse_balance();
     assert!(rewards_purse_balance.is_zero());
 
     builder.exec(exec_request).expect_success().commit();
 
-    let rewards_purse_balance = get_pos_rewards_purse_balance(&builder);
+    let rewards_purse_balance = builder.get_pos_rewards_purse_balance();
     assert!(!rewards_purse_balance.is_zero());
 }
 
@@ -710,3 +722,90 @@ fn independent_standard_payments_should_not_write_the_same_keys() {
 
     assert_eq!(common_write_keys.count(), 0);
 }
+
+#[ignore]
+#[test]
+fn should_refund_unspent_payment_to_main_purse() {
+    let payment_purse_amount = U512::from(10_000_000);
+
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash([1; 32])
+            .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => payment_purse_amount })
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get genesis account");
+    let modified_balance: U512 = builder.get_purse_balance(default_account.main_purse());
+    let initial_balance: U512 = U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let gas = utils::get_success_result(&response).cost();
+    let motes = Motes::from_gas(gas, CONV_RATE).expect("should have motes");
+
+    assert!(
+        motes.value() < payment_purse_amount,
+        "a cheap session should not use up the whole payment"
+    );
+    assert_eq!(
+        initial_balance - modified_balance,
+        motes.value(),
+        "only the spent part of the payment should be taken from the account"
+    );
+    assert!(builder.get_pos_payment_purse_balance().is_zero());
+}
+
+#[ignore]
+#[test]
+fn should_not_refund_when_session_code_exhausts_gas() {
+    let payment_purse_amount = U512::from(10_000_000);
+
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash([1; 32])
+            .with_session_code(ENDLESS_LOOP_WASM, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => payment_purse_amount })
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get genesis account");
+    let modified_balance: U512 = builder.get_purse_balance(default_account.main_purse());
+    let initial_balance: U512 = U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE);
+
+    assert_eq!(
+        initial_balance - modified_balance,
+        payment_purse_amount,
+        "the whole payment should be spent when the session runs out of gas"
+    );
+    assert!(builder.get_pos_payment_purse_balance().is_zero());
+}
@@ -8,7 +8,9 @@ use engine_test_support::internal::{
 };
 #[cfg(feature = "use-system-contracts")]
 use engine_test_support::{internal::ExecuteRequestBuilder, DEFAULT_ACCOUNT_ADDR};
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::wasm_costs::{
+    HostFunctionCosts, WasmCosts, DEFAULT_MAX_MEMORY_PAGES, DEFAULT_MAX_TABLE_SIZE,
+};
 use types::ProtocolVersion;
 #[cfg(feature = "use-system-contracts")]
 use types::{runtime_args, CLValue, Key, RuntimeArgs, U512};
@@ -35,6 +37,9 @@ fn get_upgraded_wasm_costs() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        max_memory_pages: DEFAULT_MAX_MEMORY_PAGES,
+        max_table_size: DEFAULT_MAX_TABLE_SIZE,
+        host_function_costs: HostFunctionCosts::default(),
     }
 }
 
@@ -1,11 +1,18 @@
+use assert_matches::assert_matches;
+
+use engine_core::{engine_state::Error, execution};
 use engine_shared::stored_value::StoredValue;
 use engine_test_support::{
-    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
     DEFAULT_ACCOUNT_ADDR,
 };
 use types::{
+    account::AccountHash,
     contracts::{ContractVersion, CONTRACT_INITIAL_VERSION},
-    runtime_args, CLValue, ContractPackageHash, RuntimeArgs,
+    runtime_args, CLValue, ContractPackageHash, RuntimeArgs, U512,
 };
 
 const DO_NOTHING_STORED_CONTRACT_NAME: &str = "do_nothing_stored";
@@ -31,6 +38,10 @@ const ENTRY_POINT_ADD: &str = "add_named_purse";
 const ARG_CONTRACT_PACKAGE: &str = "contract_package";
 const ARG_VERSION: &str = "version";
 const ARG_NEW_PURSE_NAME: &str = "new_purse_name";
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
 
 /// Performs define and execution of versioned contracts, calling them directly from hash
 #[ignore]
@@ -573,3 +584,110 @@ fn should_maintain_named_keys_across_upgrade() {
         );
     }
 }
+
+fn current_contract_version(
+    builder: &InMemoryWasmTestBuilder,
+    contract_package_hash: ContractPackageHash,
+) -> Option<ContractVersion> {
+    builder
+        .query(None, contract_package_hash.into(), &[])
+        .expect("should query contract package")
+        .as_contract_package()
+        .expect("should be contract package")
+        .current_contract_version()
+        .map(|contract_version_key| contract_version_key.contract_version())
+}
+
+#[ignore]
+#[test]
+fn should_only_let_holder_of_package_access_key_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    {
+        let contract_name = format!("{}.wasm", DO_NOTHING_STORED_CONTRACT_NAME);
+        let exec_request = ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            &contract_name,
+            RuntimeArgs::new(),
+        )
+        .build();
+
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    {
+        let exec_request = ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! {
+                ARG_TARGET => ACCOUNT_1_ADDR,
+                ARG_AMOUNT => *DEFAULT_PAYMENT * U512::from(10),
+            },
+        )
+        .build();
+
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    let contract_package_hash: ContractPackageHash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account")
+        .named_keys()
+        .get(DO_NOTHING_PACKAGE_HASH_KEY_NAME)
+        .expect("should have package hash")
+        .into_hash()
+        .expect("should be hash");
+
+    // Account 1 can read and call the package by its hash, but holds no access key for it.
+    {
+        let contract_name = format!("{}.wasm", DO_NOTHING_STORED_UPGRADER_CONTRACT_NAME);
+        let exec_request = ExecuteRequestBuilder::standard(
+            ACCOUNT_1_ADDR,
+            &contract_name,
+            runtime_args! { ARG_CONTRACT_PACKAGE => contract_package_hash },
+        )
+        .build();
+
+        builder.exec(exec_request).commit();
+
+        let response = builder
+            .get_exec_responses()
+            .last()
+            .expect("should have last response");
+        let error = response
+            .last()
+            .expect("should have response")
+            .as_error()
+            .expect("should have error");
+        assert_matches!(
+            error,
+            Error::Exec(execution::Error::UpgradeAuthorizationFailure(hash))
+                if *hash == contract_package_hash
+        );
+    }
+
+    assert_eq!(
+        current_contract_version(&builder, contract_package_hash),
+        Some(INITIAL_VERSION)
+    );
+
+    // The installer holds the access key, so the same upgrade succeeds for it.
+    {
+        let contract_name = format!("{}.wasm", DO_NOTHING_STORED_UPGRADER_CONTRACT_NAME);
+        let exec_request = ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            &contract_name,
+            runtime_args! { ARG_CONTRACT_PACKAGE => contract_package_hash },
+        )
+        .build();
+
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    assert_eq!(
+        current_contract_version(&builder, contract_package_hash),
+        Some(UPGRADED_VERSION)
+    );
+}
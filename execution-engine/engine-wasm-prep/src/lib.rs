@@ -2,7 +2,7 @@ pub mod wasm_costs;
 
 use std::fmt::{self, Display, Formatter};
 
-use parity_wasm::elements::{self, Module};
+use parity_wasm::elements::{self, External, Module, ResizableLimits};
 use pwasm_utils::{self, stack_height};
 
 use crate::wasm_costs::WasmCosts;
@@ -15,6 +15,9 @@ pub enum PreprocessingError {
     Deserialize(String),
     OperationForbiddenByGasRules,
     StackLimiter,
+    MemoryLimitExceeded { requested: u32, max: u32 },
+    TableLimitExceeded { requested: u32, max: u32 },
+    MissingMemorySection,
 }
 
 impl From<elements::Error> for PreprocessingError {
@@ -29,6 +32,19 @@ impl Display for PreprocessingError {
             PreprocessingError::Deserialize(error) => write!(f, "Deserialization error: {}", error),
             PreprocessingError::OperationForbiddenByGasRules => write!(f, "Encountered operation forbidden by gas rules. Consult instruction -> metering config map"),
             PreprocessingError::StackLimiter => write!(f, "Stack limiter error"),
+            PreprocessingError::MemoryLimitExceeded { requested, max } => write!(
+                f,
+                "Module declares {} memory pages, but at most {} are allowed",
+                requested, max
+            ),
+            PreprocessingError::TableLimitExceeded { requested, max } => write!(
+                f,
+                "Module declares a table of {} entries, but at most {} are allowed",
+                requested, max
+            ),
+            PreprocessingError::MissingMemorySection => {
+                write!(f, "Module does not declare a memory")
+            }
         }
     }
 }
@@ -47,8 +63,21 @@ impl Preprocessor {
         }
     }
 
+    /// Returns the costs this preprocessor meters modules with.
+    pub fn wasm_costs(&self) -> &WasmCosts {
+        &self.wasm_costs
+    }
+
     pub fn preprocess(&self, module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
         let module = deserialize(module_bytes)?;
+        self.ensure_limits(&module)?;
+        // The declared memory is turned into the one imported from the host.
+        if module
+            .memory_section()
+            .map_or(true, |section| section.entries().is_empty())
+        {
+            return Err(PreprocessingError::MissingMemorySection);
+        }
         let module = pwasm_utils::externalize_mem(module, None, self.mem_pages);
         let module = pwasm_utils::inject_gas_counter(module, &self.wasm_costs.to_set())
             .map_err(|_| PreprocessingError::OperationForbiddenByGasRules)?;
@@ -56,6 +85,61 @@ impl Preprocessor {
             .map_err(|_| PreprocessingError::StackLimiter)?;
         Ok(module)
     }
+
+    /// Checks that the memories and tables declared or imported by `module` stay within the
+    /// limits configured in the [`WasmCosts`].
+    fn ensure_limits(&self, module: &Module) -> Result<(), PreprocessingError> {
+        let imported = module
+            .import_section()
+            .map(|section| section.entries())
+            .unwrap_or_default()
+            .iter()
+            .map(|entry| entry.external());
+
+        let imported_memories = imported.clone().filter_map(|external| match external {
+            External::Memory(memory_type) => Some(memory_type.limits()),
+            _ => None,
+        });
+        let declared_memories = module
+            .memory_section()
+            .map(|section| section.entries())
+            .unwrap_or_default()
+            .iter()
+            .map(|memory_type| memory_type.limits());
+        let max = self.wasm_costs.max_memory_pages;
+        if let Some(requested) = largest_exceeding(imported_memories.chain(declared_memories), max)
+        {
+            return Err(PreprocessingError::MemoryLimitExceeded { requested, max });
+        }
+
+        let imported_tables = imported.filter_map(|external| match external {
+            External::Table(table_type) => Some(table_type.limits()),
+            _ => None,
+        });
+        let declared_tables = module
+            .table_section()
+            .map(|section| section.entries())
+            .unwrap_or_default()
+            .iter()
+            .map(|table_type| table_type.limits());
+        let max = self.wasm_costs.max_table_size;
+        if let Some(requested) = largest_exceeding(imported_tables.chain(declared_tables), max) {
+            return Err(PreprocessingError::TableLimitExceeded { requested, max });
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the largest initial or maximum size among `limits` if it exceeds `max`.
+fn largest_exceeding<'a, I: Iterator<Item = &'a ResizableLimits>>(
+    limits: I,
+    max: u32,
+) -> Option<u32> {
+    limits
+        .flat_map(|limits| Some(limits.initial()).into_iter().chain(limits.maximum()))
+        .max()
+        .filter(|requested| *requested > max)
 }
 
 // Returns a parity Module from bytes without making modifications or limits
@@ -1,11 +1,176 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, fmt};
 
 use pwasm_utils::rules::{InstructionType, Metering, Set};
 
-use types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
+    ProtocolVersion,
+};
 
-const NUM_FIELDS: usize = 10;
-pub const WASM_COSTS_SERIALIZED_LENGTH: usize = NUM_FIELDS * U32_SERIALIZED_LENGTH;
+/// The number of host functions, i.e. one more than the highest host function index.
+pub const HOST_FUNCTION_COUNT: usize = 59;
+
+/// The number of `u32` fields in the original encoding, which has no version.
+const LEGACY_NUM_FIELDS: usize = 10;
+/// The number of `u32` fields in the extension, excluding the host function costs.
+const EXTENSION_NUM_FIELDS: usize = 2;
+/// The version of the extension written after the original fields.
+const EXTENSION_VERSION: u8 = 1;
+const HOST_FUNCTION_COST_SERIALIZED_LENGTH: usize = 2 * U32_SERIALIZED_LENGTH;
+const HOST_FUNCTION_COSTS_SERIALIZED_LENGTH: usize =
+    U32_SERIALIZED_LENGTH + HOST_FUNCTION_COUNT * HOST_FUNCTION_COST_SERIALIZED_LENGTH;
+pub const LEGACY_WASM_COSTS_SERIALIZED_LENGTH: usize = LEGACY_NUM_FIELDS * U32_SERIALIZED_LENGTH;
+pub const WASM_COSTS_EXTENSION_SERIALIZED_LENGTH: usize = U8_SERIALIZED_LENGTH
+    + EXTENSION_NUM_FIELDS * U32_SERIALIZED_LENGTH
+    + HOST_FUNCTION_COSTS_SERIALIZED_LENGTH;
+pub const WASM_COSTS_SERIALIZED_LENGTH: usize =
+    LEGACY_WASM_COSTS_SERIALIZED_LENGTH + WASM_COSTS_EXTENSION_SERIALIZED_LENGTH;
+
+/// Default maximum number of memory pages a module may declare.
+pub const DEFAULT_MAX_MEMORY_PAGES: u32 = 64;
+/// Default maximum number of entries a module's table may declare.
+pub const DEFAULT_MAX_TABLE_SIZE: u32 = 4096;
+/// Default flat cost of calling any host function.
+const DEFAULT_HOST_FUNCTION_COST: u32 = 100;
+/// Default cost per byte of the data passed to a host function.
+const DEFAULT_HOST_FUNCTION_COST_PER_BYTE: u32 = 1;
+/// Cost of a host function missing from a serialized or configured table, e.g. one added after
+/// the table was written.
+pub const MISSING_HOST_FUNCTION_COST: HostFunctionCost = HostFunctionCost {
+    cost: DEFAULT_HOST_FUNCTION_COST,
+    cost_per_byte: DEFAULT_HOST_FUNCTION_COST_PER_BYTE,
+};
+
+/// The cost of calling a single host function.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct HostFunctionCost {
+    /// Flat cost charged on every call.
+    pub cost: u32,
+    /// Cost per byte of the host function's size argument, if it has one.
+    pub cost_per_byte: u32,
+}
+
+impl HostFunctionCost {
+    pub fn new(cost: u32, cost_per_byte: u32) -> Self {
+        HostFunctionCost {
+            cost,
+            cost_per_byte,
+        }
+    }
+
+    /// Returns the per-byte cost of `size` bytes.
+    pub fn bytes_cost(&self, size: u32) -> u64 {
+        u64::from(self.cost_per_byte) * u64::from(size)
+    }
+}
+
+impl ToBytes for HostFunctionCost {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut self.cost.to_bytes()?);
+        ret.append(&mut self.cost_per_byte.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        HOST_FUNCTION_COST_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for HostFunctionCost {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (cost, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (cost_per_byte, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((HostFunctionCost::new(cost, cost_per_byte), rem))
+    }
+}
+
+/// The costs of calling host functions, indexed by host function index.
+#[derive(Copy, Clone)]
+pub struct HostFunctionCosts([HostFunctionCost; HOST_FUNCTION_COUNT]);
+
+impl HostFunctionCosts {
+    /// Creates a table where every host function has the same cost.
+    pub fn uniform(host_function_cost: HostFunctionCost) -> Self {
+        HostFunctionCosts([host_function_cost; HOST_FUNCTION_COUNT])
+    }
+
+    /// Returns the cost of the host function with the given index, or a zero cost if the index is
+    /// out of range.
+    pub fn get(&self, index: usize) -> HostFunctionCost {
+        self.0.get(index).copied().unwrap_or_default()
+    }
+
+    /// Sets the cost of the host function with the given index.
+    ///
+    /// Returns `false` if the index is out of range.
+    pub fn set(&mut self, index: usize, host_function_cost: HostFunctionCost) -> bool {
+        match self.0.get_mut(index) {
+            Some(entry) => {
+                *entry = host_function_cost;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &HostFunctionCost> {
+        self.0.iter()
+    }
+}
+
+impl Default for HostFunctionCosts {
+    fn default() -> Self {
+        HostFunctionCosts::uniform(HostFunctionCost::default())
+    }
+}
+
+impl fmt::Debug for HostFunctionCosts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.0.iter()).finish()
+    }
+}
+
+impl PartialEq for HostFunctionCosts {
+    fn eq(&self, other: &Self) -> bool {
+        self.0[..] == other.0[..]
+    }
+}
+
+impl Eq for HostFunctionCosts {}
+
+/// Serialized as a length-prefixed list so that tables written before host functions were added
+/// can still be read. Missing trailing entries are filled with [`MISSING_HOST_FUNCTION_COST`].
+impl ToBytes for HostFunctionCosts {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut (HOST_FUNCTION_COUNT as u32).to_bytes()?);
+        for host_function_cost in self.0.iter() {
+            ret.append(&mut host_function_cost.to_bytes()?);
+        }
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        HOST_FUNCTION_COSTS_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for HostFunctionCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (count, mut rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        if count as usize > HOST_FUNCTION_COUNT {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let mut host_function_costs = HostFunctionCosts::uniform(MISSING_HOST_FUNCTION_COST);
+        for entry in host_function_costs.0.iter_mut().take(count as usize) {
+            let (host_function_cost, next) = HostFunctionCost::from_bytes(rem)?;
+            *entry = host_function_cost;
+            rem = next;
+        }
+        Ok((host_function_costs, rem))
+    }
+}
 
 // Taken (partially) from parity-ethereum
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
@@ -33,9 +198,38 @@ pub struct WasmCosts {
     /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` /
     /// `opcodes_div`
     pub opcodes_div: u32,
+    /// Max number of memory pages (64kb) a module may declare, either initially or as its maximum
+    pub max_memory_pages: u32,
+    /// Max number of table entries a module may declare, either initially or as its maximum
+    pub max_table_size: u32,
+    /// Costs of calling host functions, charged by the runtime on top of the opcode costs.
+    pub host_function_costs: HostFunctionCosts,
 }
 
 impl WasmCosts {
+    /// Returns the default costs for the given protocol version, or `None` if there are no
+    /// defaults for that version.
+    pub fn from_version(protocol_version: ProtocolVersion) -> Option<WasmCosts> {
+        match protocol_version.value().major {
+            1 => Some(WasmCosts {
+                regular: 1,
+                div: 16,
+                mul: 4,
+                mem: 2,
+                initial_mem: 4096,
+                grow_mem: 8192,
+                memcpy: 1,
+                max_stack_height: 64 * 1024,
+                opcodes_mul: 3,
+                opcodes_div: 8,
+                max_memory_pages: DEFAULT_MAX_MEMORY_PAGES,
+                max_table_size: DEFAULT_MAX_TABLE_SIZE,
+                host_function_costs: HostFunctionCosts::uniform(MISSING_HOST_FUNCTION_COST),
+            }),
+            _ => None,
+        }
+    }
+
     pub(crate) fn to_set(&self) -> Set {
         let meterings = {
             let mut tmp = BTreeMap::new();
@@ -51,9 +245,16 @@ impl WasmCosts {
     }
 }
 
-impl ToBytes for WasmCosts {
-    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+/// Serialization in two parts: the fields of the original, unversioned encoding, followed by a
+/// versioned extension holding the fields added since.
+///
+/// `ProtocolData` written before the extension existed holds only the original fields, so
+/// containers of `WasmCosts` may serialize the two parts separately and treat a missing extension
+/// as [`WasmCosts::legacy_fields_from_bytes`] does.
+impl WasmCosts {
+    /// Serializes the fields of the original encoding.
+    pub fn legacy_fields_to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = Vec::with_capacity(LEGACY_WASM_COSTS_SERIALIZED_LENGTH);
         ret.append(&mut self.regular.to_bytes()?);
         ret.append(&mut self.div.to_bytes()?);
         ret.append(&mut self.mul.to_bytes()?);
@@ -67,13 +268,20 @@ impl ToBytes for WasmCosts {
         Ok(ret)
     }
 
-    fn serialized_length(&self) -> usize {
-        WASM_COSTS_SERIALIZED_LENGTH
+    /// Serializes the fields added since the original encoding, prefixed with the extension's
+    /// version.
+    pub fn extension_to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = Vec::with_capacity(WASM_COSTS_EXTENSION_SERIALIZED_LENGTH);
+        ret.append(&mut EXTENSION_VERSION.to_bytes()?);
+        ret.append(&mut self.max_memory_pages.to_bytes()?);
+        ret.append(&mut self.max_table_size.to_bytes()?);
+        ret.append(&mut self.host_function_costs.to_bytes()?);
+        Ok(ret)
     }
-}
 
-impl FromBytes for WasmCosts {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+    /// Deserializes the fields of the original encoding. The fields added since are given the
+    /// defaults which apply when they are absent.
+    pub fn legacy_fields_from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (regular, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
         let (div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
@@ -95,15 +303,79 @@ impl FromBytes for WasmCosts {
             max_stack_height,
             opcodes_mul,
             opcodes_div,
+            max_memory_pages: DEFAULT_MAX_MEMORY_PAGES,
+            max_table_size: DEFAULT_MAX_TABLE_SIZE,
+            host_function_costs: HostFunctionCosts::uniform(MISSING_HOST_FUNCTION_COST),
         };
         Ok((wasm_costs, rem))
     }
+
+    /// Deserializes the extension, replacing the corresponding fields of `self`.
+    ///
+    /// Returns an error if the extension has an unknown version.
+    pub fn extension_from_bytes(self, bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (version, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+        if version != EXTENSION_VERSION {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let (max_memory_pages, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_table_size, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (host_function_costs, rem): (HostFunctionCosts, &[u8]) = FromBytes::from_bytes(rem)?;
+        let wasm_costs = WasmCosts {
+            max_memory_pages,
+            max_table_size,
+            host_function_costs,
+            ..self
+        };
+        Ok((wasm_costs, rem))
+    }
+}
+
+impl ToBytes for WasmCosts {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::unchecked_allocate_buffer(self);
+        ret.append(&mut self.legacy_fields_to_bytes()?);
+        ret.append(&mut self.extension_to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        WASM_COSTS_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for WasmCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (wasm_costs, rem) = WasmCosts::legacy_fields_from_bytes(bytes)?;
+        wasm_costs.extension_from_bytes(rem)
+    }
 }
 
 pub mod gens {
-    use proptest::{num, prop_compose};
+    use proptest::{collection::vec, num, prop_compose};
+
+    use crate::wasm_costs::{HostFunctionCost, HostFunctionCosts, WasmCosts, HOST_FUNCTION_COUNT};
+
+    prop_compose! {
+        pub fn host_function_cost_arb()(
+            cost in num::u32::ANY,
+            cost_per_byte in num::u32::ANY,
+        ) -> HostFunctionCost {
+            HostFunctionCost::new(cost, cost_per_byte)
+        }
+    }
 
-    use crate::wasm_costs::WasmCosts;
+    prop_compose! {
+        pub fn host_function_costs_arb()(
+            costs in vec(host_function_cost_arb(), HOST_FUNCTION_COUNT),
+        ) -> HostFunctionCosts {
+            let mut host_function_costs = HostFunctionCosts::default();
+            for (index, cost) in costs.into_iter().enumerate() {
+                host_function_costs.set(index, cost);
+            }
+            host_function_costs
+        }
+    }
 
     prop_compose! {
         pub fn wasm_costs_arb()(
@@ -117,6 +389,9 @@ pub mod gens {
             max_stack_height in num::u32::ANY,
             opcodes_mul in num::u32::ANY,
             opcodes_div in num::u32::ANY,
+            max_memory_pages in 1..=u32::max_value(),
+            max_table_size in 1..=u32::max_value(),
+            host_function_costs in host_function_costs_arb(),
         ) -> WasmCosts {
             WasmCosts {
                 regular,
@@ -129,6 +404,9 @@ pub mod gens {
                 max_stack_height,
                 opcodes_mul,
                 opcodes_div,
+                max_memory_pages,
+                max_table_size,
+                host_function_costs,
             }
         }
     }
@@ -138,24 +416,19 @@ pub mod gens {
 mod tests {
     use proptest::proptest;
 
-    use types::bytesrepr;
+    use types::{
+        bytesrepr::{self, FromBytes, ToBytes},
+        ProtocolVersion,
+    };
 
     use super::gens;
-    use crate::wasm_costs::WasmCosts;
+    use crate::wasm_costs::{
+        HostFunctionCost, HostFunctionCosts, WasmCosts, DEFAULT_MAX_MEMORY_PAGES,
+        DEFAULT_MAX_TABLE_SIZE, HOST_FUNCTION_COUNT, MISSING_HOST_FUNCTION_COST,
+    };
 
     fn wasm_costs_mock() -> WasmCosts {
-        WasmCosts {
-            regular: 1,
-            div: 16,
-            mul: 4,
-            mem: 2,
-            initial_mem: 4096,
-            grow_mem: 8192,
-            memcpy: 1,
-            max_stack_height: 64 * 1024,
-            opcodes_mul: 3,
-            opcodes_div: 8,
-        }
+        WasmCosts::from_version(ProtocolVersion::V1_0_0).expect("should have default costs")
     }
 
     fn wasm_costs_free() -> WasmCosts {
@@ -170,6 +443,9 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
+            max_memory_pages: DEFAULT_MAX_MEMORY_PAGES,
+            max_table_size: DEFAULT_MAX_TABLE_SIZE,
+            host_function_costs: HostFunctionCosts::default(),
         }
     }
 
@@ -181,6 +457,100 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&free);
     }
 
+    #[test]
+    fn should_have_defaults_only_for_known_versions() {
+        assert!(WasmCosts::from_version(ProtocolVersion::from_parts(1, 2, 3)).is_some());
+        assert!(WasmCosts::from_version(ProtocolVersion::from_parts(2, 0, 0)).is_none());
+    }
+
+    #[test]
+    fn should_get_and_set_host_function_costs() {
+        let mut host_function_costs = HostFunctionCosts::default();
+        let cost = HostFunctionCost::new(10, 2);
+        assert!(host_function_costs.set(3, cost));
+        assert!(!host_function_costs.set(HOST_FUNCTION_COUNT, cost));
+        assert_eq!(host_function_costs.get(3), cost);
+        assert_eq!(host_function_costs.get(4), HostFunctionCost::default());
+        assert_eq!(
+            host_function_costs.get(HOST_FUNCTION_COUNT),
+            HostFunctionCost::default()
+        );
+        assert_eq!(
+            cost.bytes_cost(u32::max_value()),
+            2 * u64::from(u32::max_value())
+        );
+    }
+
+    #[test]
+    fn should_fill_host_function_costs_missing_from_older_table() {
+        const OLDER_COUNT: usize = 3;
+        let mut bytes = (OLDER_COUNT as u32).to_bytes().unwrap();
+        for index in 0..OLDER_COUNT as u32 {
+            bytes.append(&mut HostFunctionCost::new(index, 2).to_bytes().unwrap());
+        }
+        bytes.push(u8::max_value());
+
+        let (host_function_costs, rem) =
+            HostFunctionCosts::from_bytes(&bytes).expect("should deserialize older table");
+        assert_eq!(rem, &[u8::max_value()]);
+        for index in 0..OLDER_COUNT {
+            assert_eq!(
+                host_function_costs.get(index),
+                HostFunctionCost::new(index as u32, 2)
+            );
+        }
+        for index in OLDER_COUNT..HOST_FUNCTION_COUNT {
+            assert_eq!(host_function_costs.get(index), MISSING_HOST_FUNCTION_COST);
+        }
+        assert_ne!(MISSING_HOST_FUNCTION_COST, HostFunctionCost::default());
+
+        // Re-serializing writes the full-length table.
+        bytesrepr::test_serialization_roundtrip(&host_function_costs);
+    }
+
+    #[test]
+    fn should_not_deserialize_host_function_costs_from_longer_table() {
+        let count = HOST_FUNCTION_COUNT + 1;
+        let mut bytes = (count as u32).to_bytes().unwrap();
+        for _ in 0..count {
+            bytes.append(&mut HostFunctionCost::default().to_bytes().unwrap());
+        }
+        assert_eq!(
+            HostFunctionCosts::from_bytes(&bytes).err(),
+            Some(bytesrepr::Error::Formatting)
+        );
+    }
+
+    #[test]
+    fn should_default_fields_missing_from_legacy_encoding() {
+        let mock = wasm_costs_mock();
+        let mut bytes = mock.legacy_fields_to_bytes().unwrap();
+        assert_eq!(bytes.len(), super::LEGACY_WASM_COSTS_SERIALIZED_LENGTH);
+        bytes.push(u8::max_value());
+
+        let (legacy, rem) =
+            WasmCosts::legacy_fields_from_bytes(&bytes).expect("should deserialize legacy fields");
+        assert_eq!(rem, &[u8::max_value()]);
+        assert_eq!(legacy.regular, mock.regular);
+        assert_eq!(legacy.opcodes_div, mock.opcodes_div);
+        assert_eq!(legacy.max_memory_pages, DEFAULT_MAX_MEMORY_PAGES);
+        assert_eq!(legacy.max_table_size, DEFAULT_MAX_TABLE_SIZE);
+        assert_eq!(
+            legacy.host_function_costs,
+            HostFunctionCosts::uniform(MISSING_HOST_FUNCTION_COST)
+        );
+    }
+
+    #[test]
+    fn should_not_deserialize_unknown_extension_version() {
+        let mut bytes = wasm_costs_mock().to_bytes().unwrap();
+        bytes[super::LEGACY_WASM_COSTS_SERIALIZED_LENGTH] += 1;
+        assert_eq!(
+            bytesrepr::deserialize::<WasmCosts>(bytes).err(),
+            Some(bytesrepr::Error::Formatting)
+        );
+    }
+
     proptest! {
         #[test]
         fn should_serialize_and_deserialize_with_arbitrary_values(
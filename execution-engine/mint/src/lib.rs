@@ -1,16 +1,37 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
 mod runtime_provider;
 mod storage_provider;
 
-use core::convert::TryFrom;
+use alloc::string::String;
+use core::{convert::TryFrom, fmt::Write};
+
+use blake2::{
+    digest::{Input, VariableOutput},
+    VarBlake2b,
+};
 
-use types::{account::AccountHash, system_contract_errors::mint::Error, Key, URef, U512};
+use types::{
+    account::AccountHash, system_contract_errors::mint::Error, Key, URef, URefAddr,
+    BLAKE2B_DIGEST_LENGTH, U512,
+};
 
 pub use crate::{runtime_provider::RuntimeProvider, storage_provider::StorageProvider};
 
 const SYSTEM_ACCOUNT: AccountHash = AccountHash::new([0; 32]);
 
+/// The name of the mint's named key holding the URef of the total supply of motes.
+pub const TOTAL_SUPPLY_KEY: &str = "total_supply";
+
+/// The name of the mint's named key holding the URef of the round seigniorage rate, stored as a
+/// `(numerator, denominator)` pair.
+pub const ROUND_SEIGNIORAGE_RATE_KEY: &str = "round_seigniorage_rate";
+
+/// The prefix of the names of the mint's named keys holding the URefs of transfer allowances.
+pub const ALLOWANCE_NAME_PREFIX: &str = "allowance-";
+
 pub trait Mint: RuntimeProvider + StorageProvider {
     fn mint(&mut self, initial_balance: U512) -> Result<URef, Error> {
         let caller = self.get_caller();
@@ -18,6 +39,11 @@ pub trait Mint: RuntimeProvider + StorageProvider {
             return Err(Error::InvalidNonEmptyPurseCreation);
         }
 
+        if !initial_balance.is_zero() {
+            let total_supply_uref = get_total_supply_uref(self)?;
+            self.add(total_supply_uref, initial_balance)?;
+        }
+
         let balance_key: Key = self.new_uref(initial_balance).into();
         let purse_uref: URef = self.new_uref(());
         let purse_uref_name = purse_uref.remove_access_rights().as_string();
@@ -51,19 +77,182 @@ pub trait Mint: RuntimeProvider + StorageProvider {
             Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
             None => return Err(Error::SourceNotFound),
         };
-        let source_value: U512 = match self.read(source_balance)? {
-            Some(source_value) => source_value,
-            None => return Err(Error::SourceNotFound),
+        let target_balance: URef = match self.read_local(&target.addr())? {
+            Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
+            None => return Err(Error::DestNotFound),
         };
-        if amount > source_value {
-            return Err(Error::InsufficientFunds);
+        // The source balance isn't read first: `sub` fails on insufficient funds, and leaving the
+        // balance unread lets transfers from the same purse commute.
+        self.sub(source_balance, amount)?;
+        self.add(target_balance, amount)?;
+        Ok(())
+    }
+
+    /// Destroys `amount` motes held in `purse`, decreasing the total supply accordingly.
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), Error> {
+        if !purse.is_writeable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        let purse_balance: URef = match self.read_local(&purse.addr())? {
+            Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
+            None => return Err(Error::PurseNotFound),
+        };
+        let purse_value: U512 = match self.read(purse_balance)? {
+            Some(purse_value) => purse_value,
+            None => return Err(Error::PurseNotFound),
+        };
+        let new_purse_value = purse_value
+            .checked_sub(amount)
+            .ok_or(Error::InsufficientFunds)?;
+        let total_supply_uref = get_total_supply_uref(self)?;
+        let total_supply: U512 = self
+            .read(total_supply_uref)?
+            .ok_or(Error::TotalSupplyNotFound)?;
+        self.write(purse_balance, new_purse_value)?;
+        self.write(total_supply_uref, total_supply.saturating_sub(amount))?;
+        Ok(())
+    }
+
+    /// Returns the total supply of motes.
+    fn read_total_supply(&mut self) -> Result<U512, Error> {
+        let total_supply_uref = get_total_supply_uref(self)?;
+        self.read(total_supply_uref)?
+            .ok_or(Error::TotalSupplyNotFound)
+    }
+
+    /// Returns the number of motes to be minted as seigniorage for a single round, derived from
+    /// the total supply and the round seigniorage rate set at genesis.
+    fn round_reward(&mut self) -> Result<U512, Error> {
+        let round_seigniorage_rate_uref = self
+            .get_key(ROUND_SEIGNIORAGE_RATE_KEY)
+            .and_then(Key::into_uref)
+            .ok_or(Error::RoundSeigniorageRateNotFound)?;
+        let (numerator, denominator): (u64, u64) = self
+            .read(round_seigniorage_rate_uref)?
+            .ok_or(Error::RoundSeigniorageRateNotFound)?;
+        let total_supply = self.read_total_supply()?;
+        total_supply
+            .checked_mul(U512::from(numerator))
+            .and_then(|product| product.checked_div(U512::from(denominator)))
+            .ok_or(Error::ArithmeticOverflow)
+    }
+
+    /// Creates `amount` new motes in `purse`, increasing the total supply accordingly. Only the
+    /// system account may mint into a purse.
+    fn mint_into(&mut self, purse: URef, amount: U512) -> Result<(), Error> {
+        if self.get_caller() != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+        if !purse.is_addable() {
+            return Err(Error::InvalidAccessRights);
         }
+        let purse_balance: URef = match self.read_local(&purse.addr())? {
+            Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
+            None => return Err(Error::PurseNotFound),
+        };
+        let total_supply_uref = get_total_supply_uref(self)?;
+        self.add(purse_balance, amount)?;
+        self.add(total_supply_uref, amount)?;
+        Ok(())
+    }
+
+    /// Allows `spender` to transfer up to `amount` motes out of `purse` via `transfer_from`,
+    /// replacing any allowance previously given to `spender` for `purse`.
+    fn approve(&mut self, purse: URef, spender: AccountHash, amount: U512) -> Result<(), Error> {
+        if !purse.is_writeable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        if self.read_local::<URefAddr, Key>(&purse.addr())?.is_none() {
+            return Err(Error::PurseNotFound);
+        }
+        let name = allowance_name(purse.addr(), spender);
+        match get_allowance_uref(self, &name)? {
+            Some(allowance_uref) => self.write(allowance_uref, amount)?,
+            None => {
+                let allowance_uref = self.new_uref(amount);
+                self.put_key(&name, allowance_uref.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of motes `spender` may still transfer out of the purse with address
+    /// `owner`.
+    fn allowance(&mut self, owner: URefAddr, spender: AccountHash) -> Result<U512, Error> {
+        match get_allowance_uref(self, &allowance_name(owner, spender))? {
+            Some(allowance_uref) => Ok(self.read(allowance_uref)?.unwrap_or_default()),
+            None => Ok(U512::zero()),
+        }
+    }
+
+    /// Transfers `amount` motes from the purse with address `owner` to `target` on behalf of the
+    /// caller, spending the allowance given to the caller by the purse's owner.
+    ///
+    /// Only the address of the owner's purse is needed, as the allowance stands in for the write
+    /// access which a plain `transfer` requires.
+    fn transfer_from(&mut self, owner: URefAddr, target: URef, amount: U512) -> Result<(), Error> {
+        if !target.is_addable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        let spender = self.get_caller();
+        let allowance_uref = get_allowance_uref(self, &allowance_name(owner, spender))?
+            .ok_or(Error::InsufficientAllowance)?;
+        let remaining_allowance = self
+            .read::<U512>(allowance_uref)?
+            .unwrap_or_default()
+            .checked_sub(amount)
+            .ok_or(Error::InsufficientAllowance)?;
+        let source_balance: URef = match self.read_local(&owner)? {
+            Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
+            None => return Err(Error::SourceNotFound),
+        };
         let target_balance: URef = match self.read_local(&target.addr())? {
             Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
             None => return Err(Error::DestNotFound),
         };
-        self.write(source_balance, source_value - amount)?;
+        // The source balance isn't read first: `sub` fails on insufficient funds, and leaving the
+        // balance unread lets transfers from the same purse commute.
+        self.sub(source_balance, amount)?;
         self.add(target_balance, amount)?;
+        self.write(allowance_uref, remaining_allowance)?;
         Ok(())
     }
 }
+
+/// Returns the name of the mint's named key holding the URef of the allowance given to `spender`
+/// for the purse with address `owner`.
+///
+/// Allowances are kept under URefs in the mint's own named keys rather than in local storage, as
+/// any contract can write to any local key.  The pair is hashed to keep the name short.  The hash
+/// is computed in the mint itself rather than via the `blake2b` host function, which isn't
+/// available under protocol versions earlier than 2.0.0.
+fn allowance_name(owner: URefAddr, spender: AccountHash) -> String {
+    let mut hasher = VarBlake2b::new(BLAKE2B_DIGEST_LENGTH).unwrap();
+    hasher.input(&owner);
+    hasher.input(spender.as_bytes());
+    let mut name = String::from(ALLOWANCE_NAME_PREFIX);
+    hasher.variable_result(|hash| {
+        for byte in hash {
+            // Writing to a `String` can't fail.
+            let _ = write!(name, "{:02x}", byte);
+        }
+    });
+    name
+}
+
+fn get_allowance_uref<R: RuntimeProvider + ?Sized>(
+    runtime_provider: &R,
+    name: &str,
+) -> Result<Option<URef>, Error> {
+    match runtime_provider.get_key(name) {
+        Some(key) => key.into_uref().map(Some).ok_or(Error::InvalidAccessRights),
+        None => Ok(None),
+    }
+}
+
+fn get_total_supply_uref<R: RuntimeProvider + ?Sized>(runtime_provider: &R) -> Result<URef, Error> {
+    runtime_provider
+        .get_key(TOTAL_SUPPLY_KEY)
+        .and_then(Key::into_uref)
+        .ok_or(Error::TotalSupplyNotFound)
+}
@@ -1,7 +1,7 @@
 use types::{
     bytesrepr::{FromBytes, ToBytes},
     system_contract_errors::mint::Error,
-    CLTyped, URef,
+    CLTyped, URef, U512,
 };
 
 pub trait StorageProvider {
@@ -19,4 +19,8 @@ pub trait StorageProvider {
     fn write<T: CLTyped + ToBytes>(&mut self, uref: URef, value: T) -> Result<(), Error>;
 
     fn add<T: CLTyped + ToBytes>(&mut self, uref: URef, value: T) -> Result<(), Error>;
+
+    /// Subtracts `amount` from the balance stored under `uref`, failing with
+    /// [`Error::InsufficientFunds`] if it would drop below zero.
+    fn sub(&mut self, uref: URef, amount: U512) -> Result<(), Error>;
 }
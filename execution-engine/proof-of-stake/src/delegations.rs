@@ -0,0 +1,220 @@
+use alloc::{
+    collections::{btree_map::Iter, BTreeMap},
+    format,
+    string::String,
+    vec::Vec,
+};
+
+use types::{
+    account::AccountHash,
+    system_contract_errors::pos::{Error, Result},
+    U512,
+};
+
+/// The prefix of the uref names under which the delegations are stored.
+const DELEGATION_PREFIX: &str = "d";
+
+/// The delegations map, assigning the amount of motes each delegator has staked with each
+/// validator.  Keys are `(delegator, validator)` pairs.
+///
+/// Delegated amounts are also included in the validators' [`Stakes`](crate::Stakes), so the
+/// stakes always hold the effective stake of each validator.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Delegations(pub BTreeMap<(AccountHash, AccountHash), U512>);
+
+impl Delegations {
+    pub fn new(map: BTreeMap<(AccountHash, AccountHash), U512>) -> Delegations {
+        Delegations(map)
+    }
+
+    pub fn iter(&self) -> Iter<(AccountHash, AccountHash), U512> {
+        self.0.iter()
+    }
+
+    /// Encodes the delegations as uref names of the form
+    /// `d_{delegator_hex}_{validator_hex}_{amount}`.
+    pub fn strings(&self) -> impl Iterator<Item = String> + '_ {
+        self.iter().map(|((delegator, validator), amount)| {
+            format!(
+                "{}_{}_{}_{}",
+                DELEGATION_PREFIX,
+                base16::encode_lower(&delegator.as_bytes()),
+                base16::encode_lower(&validator.as_bytes()),
+                amount
+            )
+        })
+    }
+
+    /// Decodes the delegations from the given uref names, ignoring all names which weren't
+    /// produced by [`Delegations::strings`].
+    pub fn from_strings<'a, I: IntoIterator<Item = &'a str>>(names: I) -> Result<Delegations> {
+        let mut delegations = BTreeMap::new();
+        for name in names {
+            let mut split_name = name.split('_');
+            if Some(DELEGATION_PREFIX) != split_name.next() {
+                continue;
+            }
+            let delegator = parse_account_hash(split_name.next())?;
+            let validator = parse_account_hash(split_name.next())?;
+            let amount = split_name
+                .next()
+                .and_then(|amount| U512::from_dec_str(amount).ok())
+                .ok_or(Error::DelegationsDeserializationFailed)?;
+            delegations.insert((delegator, validator), amount);
+        }
+        Ok(Delegations(delegations))
+    }
+
+    /// Returns the total amount delegated to `validator`.
+    pub fn total_delegated_to(&self, validator: &AccountHash) -> U512 {
+        self.iter()
+            .filter(|((_, v), _)| v == validator)
+            .fold(U512::zero(), |sum, (_, amount)| sum.saturating_add(*amount))
+    }
+
+    /// Adds `amount` to the stake `delegator` has delegated to `validator`.
+    pub fn delegate(&mut self, delegator: &AccountHash, validator: &AccountHash, amount: U512) {
+        self.0
+            .entry((*delegator, *validator))
+            .and_modify(|x| *x += amount)
+            .or_insert(amount);
+    }
+
+    /// If `maybe_amount` is `None`, removes the whole delegation, otherwise subtracts the given
+    /// amount from it.
+    ///
+    /// Returns the amount that was actually subtracted, or an error if
+    /// * the delegator has no stake delegated to the validator,
+    /// * the amount is larger than the delegated stake.
+    pub fn undelegate(
+        &mut self,
+        delegator: &AccountHash,
+        validator: &AccountHash,
+        maybe_amount: Option<U512>,
+    ) -> Result<U512> {
+        let key = (*delegator, *validator);
+        let delegated = *self.0.get(&key).ok_or(Error::NotDelegated)?;
+        let amount = maybe_amount.unwrap_or(delegated);
+        let remaining = delegated
+            .checked_sub(amount)
+            .ok_or(Error::UndelegateTooLarge)?;
+        if remaining.is_zero() {
+            self.0.remove(&key);
+        } else {
+            self.0.insert(key, remaining);
+        }
+        Ok(amount)
+    }
+
+    /// Removes all stakes delegated to `validator`.
+    pub fn remove_validator(&mut self, validator: &AccountHash) {
+        let keys: Vec<_> = self
+            .0
+            .keys()
+            .filter(|(_, v)| v == validator)
+            .cloned()
+            .collect();
+        for key in keys {
+            self.0.remove(&key);
+        }
+    }
+}
+
+fn parse_account_hash(maybe_hex_key: Option<&str>) -> Result<AccountHash> {
+    let hex_key = maybe_hex_key.ok_or(Error::DelegationsKeyDeserializationFailed)?;
+    if hex_key.len() != 64 {
+        return Err(Error::DelegationsKeyDeserializationFailed);
+    }
+    let mut key_bytes = [0u8; 32];
+    let _bytes_written = base16::decode_slice(hex_key, &mut key_bytes)
+        .map_err(|_| Error::DelegationsKeyDeserializationFailed)?;
+    debug_assert!(_bytes_written == key_bytes.len());
+    Ok(AccountHash::new(key_bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec::Vec};
+
+    use types::{account::AccountHash, system_contract_errors::pos::Error, U512};
+
+    use super::Delegations;
+
+    const DELEGATOR: [u8; 32] = [1; 32];
+    const VALIDATOR1: [u8; 32] = [2; 32];
+    const VALIDATOR2: [u8; 32] = [3; 32];
+
+    fn new_delegations(delegations: &[([u8; 32], [u8; 32], u64)]) -> Delegations {
+        Delegations(
+            delegations
+                .iter()
+                .map(|&(delegator, validator, amount)| {
+                    (
+                        (AccountHash::new(delegator), AccountHash::new(validator)),
+                        U512::from(amount),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_delegate() {
+        let mut delegations = new_delegations(&[(DELEGATOR, VALIDATOR1, 10)]);
+        delegations.delegate(
+            &AccountHash::new(DELEGATOR),
+            &AccountHash::new(VALIDATOR1),
+            U512::from(5),
+        );
+        delegations.delegate(
+            &AccountHash::new(DELEGATOR),
+            &AccountHash::new(VALIDATOR2),
+            U512::from(7),
+        );
+        assert_eq!(
+            new_delegations(&[(DELEGATOR, VALIDATOR1, 15), (DELEGATOR, VALIDATOR2, 7)]),
+            delegations
+        );
+        assert_eq!(
+            U512::from(15),
+            delegations.total_delegated_to(&AccountHash::new(VALIDATOR1))
+        );
+    }
+
+    #[test]
+    fn test_undelegate() {
+        let mut delegations = new_delegations(&[(DELEGATOR, VALIDATOR1, 10)]);
+        let delegator = AccountHash::new(DELEGATOR);
+        let validator = AccountHash::new(VALIDATOR1);
+        assert_eq!(
+            Err(Error::UndelegateTooLarge),
+            delegations.undelegate(&delegator, &validator, Some(U512::from(11)))
+        );
+        assert_eq!(
+            Ok(U512::from(4)),
+            delegations.undelegate(&delegator, &validator, Some(U512::from(4)))
+        );
+        assert_eq!(new_delegations(&[(DELEGATOR, VALIDATOR1, 6)]), delegations);
+        assert_eq!(
+            Ok(U512::from(6)),
+            delegations.undelegate(&delegator, &validator, None)
+        );
+        assert_eq!(Delegations::default(), delegations);
+        assert_eq!(
+            Err(Error::NotDelegated),
+            delegations.undelegate(&delegator, &validator, None)
+        );
+    }
+
+    #[test]
+    fn test_strings_roundtrip() {
+        let delegations =
+            new_delegations(&[(DELEGATOR, VALIDATOR1, 10), (DELEGATOR, VALIDATOR2, 7)]);
+        let mut names: Vec<_> = delegations.strings().collect();
+        names.push(format!("v_{}_100", base16::encode_lower(&VALIDATOR1)));
+        assert_eq!(
+            Ok(delegations),
+            Delegations::from_strings(names.iter().map(AsRef::as_ref))
+        );
+    }
+}
@@ -0,0 +1,9 @@
+use crate::{delegations::Delegations, Result};
+
+/// A `DelegationsProvider` that reads and writes the delegations to/from the contract's known
+/// urefs.
+pub trait DelegationsProvider {
+    fn read_delegations(&self) -> Result<Delegations>;
+
+    fn write_delegations(&mut self, delegations: &Delegations);
+}
@@ -0,0 +1,4 @@
+pub trait GasPriceProvider {
+    /// Writes the number of motes charged per unit of gas.
+    fn write_gas_price(&mut self, gas_price: u64);
+}
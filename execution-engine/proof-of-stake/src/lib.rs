@@ -2,6 +2,9 @@
 
 extern crate alloc;
 
+mod delegations;
+mod delegations_provider;
+mod gas_price_provider;
 mod mint_provider;
 mod queue;
 mod queue_provider;
@@ -9,26 +12,40 @@ mod runtime_provider;
 mod stakes;
 mod stakes_provider;
 
+use alloc::vec::Vec;
 use core::marker::Sized;
 
 use types::{
     account::AccountHash,
     system_contract_errors::pos::{Error, Result},
-    AccessRights, TransferredTo, URef, U512,
+    AccessRights, URef, U512,
 };
 
 pub use crate::{
-    mint_provider::MintProvider, queue::Queue, queue_provider::QueueProvider,
-    runtime_provider::RuntimeProvider, stakes::Stakes, stakes_provider::StakesProvider,
+    delegations::Delegations, delegations_provider::DelegationsProvider,
+    gas_price_provider::GasPriceProvider, mint_provider::MintProvider, queue::Queue,
+    queue_provider::QueueProvider, runtime_provider::RuntimeProvider, stakes::Stakes,
+    stakes_provider::StakesProvider,
 };
 
 pub trait ProofOfStake:
-    MintProvider + QueueProvider + RuntimeProvider + StakesProvider + Sized
+    DelegationsProvider
+    + GasPriceProvider
+    + MintProvider
+    + QueueProvider
+    + RuntimeProvider
+    + StakesProvider
+    + Sized
 {
     fn bond(&mut self, validator: AccountHash, amount: U512, source: URef) -> Result<()> {
         if amount.is_zero() {
             return Err(Error::BondTooSmall);
         }
+        // The caller must actually hold `source` with write access; a forged or attenuated URef
+        // must not be usable to move funds into the bonding purse.
+        if !source.is_writeable() || !self.is_valid_uref(source) {
+            return Err(Error::InvalidBondingPurse);
+        }
         let target = internal::get_bonding_purse(self)?;
         let timestamp = self.get_block_time();
         // Transfer `amount` from the `source` purse to PoS internal purse. POS_PURSE is a constant,
@@ -38,30 +55,73 @@ pub trait ProofOfStake:
             .map_err(|_| Error::BondTransferFailed)?;
         internal::bond(self, amount, validator, timestamp)?;
 
-        // TODO: Remove this and set nonzero delays once the system calls `step` in each block.
-        let unbonds = internal::step(self, timestamp)?;
-        for entry in unbonds {
-            let _: TransferredTo = self
-                .transfer_purse_to_account(source, entry.validator, entry.amount)
-                .map_err(|_| Error::BondTransferFailed)?;
-        }
-        Ok(())
+        // TODO: Remove this once the system calls `process_unbond_requests` in each block.
+        self.process_unbond_requests()
     }
 
+    /// Enqueues an unbonding request for `validator`. The funds are paid out by
+    /// `process_unbond_requests` once the unbonding delay has passed.
     fn unbond(&mut self, validator: AccountHash, maybe_amount: Option<U512>) -> Result<()> {
-        let pos_purse = internal::get_bonding_purse(self)?;
         let timestamp = self.get_block_time();
         internal::unbond(self, maybe_amount, validator, timestamp)?;
 
-        // TODO: Remove this and set nonzero delays once the system calls `step` in each block.
+        // TODO: Remove this once the system calls `process_unbond_requests` in each block.
+        self.process_unbond_requests()
+    }
+
+    fn delegate(
+        &mut self,
+        delegator: AccountHash,
+        validator: AccountHash,
+        amount: U512,
+        source: URef,
+    ) -> Result<()> {
+        if amount.is_zero() {
+            return Err(Error::BondTooSmall);
+        }
+        // As in `bond`, the delegator must actually hold `source` with write access.
+        if !source.is_writeable() || !self.is_valid_uref(source) {
+            return Err(Error::InvalidBondingPurse);
+        }
+        let target = internal::get_bonding_purse(self)?;
+        self.transfer_purse_to_purse(source, target, amount)
+            .map_err(|_| Error::BondTransferFailed)?;
+        internal::delegate(self, amount, delegator, validator)
+    }
+
+    fn undelegate(
+        &mut self,
+        delegator: AccountHash,
+        validator: AccountHash,
+        maybe_amount: Option<U512>,
+    ) -> Result<()> {
+        let timestamp = self.get_block_time();
+        internal::undelegate(self, maybe_amount, delegator, validator, timestamp)?;
+
+        // TODO: Remove this once the system calls `process_unbond_requests` in each block.
+        self.process_unbond_requests()
+    }
+
+    /// Applies all due bonding requests, and pays out all unbonding requests which were made at
+    /// least the unbonding delay before the current block time.
+    fn process_unbond_requests(&mut self) -> Result<()> {
+        let pos_purse = internal::get_bonding_purse(self)?;
+        let timestamp = self.get_block_time();
         let unbonds = internal::step(self, timestamp)?;
         for entry in unbonds {
-            self.transfer_purse_to_account(pos_purse, entry.validator, entry.amount)
+            self.transfer_purse_to_account(pos_purse, entry.account, entry.amount)
                 .map_err(|_| Error::UnbondTransferFailed)?;
         }
         Ok(())
     }
 
+    /// Slashes the given validators, forfeiting their stakes, the stakes delegated to them and
+    /// their pending bonding and unbonding requests to the rewards purse. Only the system account
+    /// may slash validators.
+    fn slash(&mut self, validators: Vec<AccountHash>) -> Result<()> {
+        internal::slash(self, &validators)
+    }
+
     fn get_payment_purse(&self) -> Result<URef> {
         let purse = internal::get_payment_purse(self)?;
         // Limit the access rights so only balance query and deposit are allowed.
@@ -83,6 +143,19 @@ pub trait ProofOfStake:
     fn finalize_payment(&mut self, amount_spent: U512, account: AccountHash) -> Result<()> {
         internal::finalize_payment(self, amount_spent, account)
     }
+
+    /// Mints the round's seigniorage into the rewards purse, then pays the purse's whole balance
+    /// out to the main purse of `validator`, the proposer of the block being finalized. Only the
+    /// system account may distribute rewards.
+    fn distribute_rewards(&mut self, validator: AccountHash) -> Result<()> {
+        internal::distribute_rewards(self, validator)
+    }
+
+    /// Sets the number of motes charged per unit of gas for subsequent deploys. Only the system
+    /// account may set the gas price.
+    fn set_gas_price(&mut self, gas_price: u64) -> Result<()> {
+        internal::set_gas_price(self, gas_price)
+    }
 }
 
 mod internal {
@@ -95,8 +168,9 @@ mod internal {
     };
 
     use crate::{
+        delegations_provider::DelegationsProvider, gas_price_provider::GasPriceProvider,
         mint_provider::MintProvider, queue::QueueEntry, queue_provider::QueueProvider,
-        runtime_provider::RuntimeProvider, stakes_provider::StakesProvider,
+        runtime_provider::RuntimeProvider, stakes::Stakes, stakes_provider::StakesProvider,
     };
 
     /// Account used to run system functions (in particular `finalize_payment`).
@@ -119,9 +193,6 @@ mod internal {
     /// The time from a bonding request until the bond becomes effective and part of the stake.
     const BOND_DELAY: u64 = 0;
 
-    /// The time from an unbonding request until the stakes are paid out.
-    const UNBOND_DELAY: u64 = 0;
-
     /// The maximum number of pending bonding requests.
     const MAX_BOND_LEN: usize = 100;
 
@@ -146,17 +217,44 @@ mod internal {
         for entry in &queue.0 {
             stakes.bond(&entry.validator, entry.amount);
         }
+        validate_bond_amount_bounds(provider, &stakes, &validator, amount)?;
         stakes.validate_bonding(&validator, amount)?;
 
-        queue.push(validator, amount, timestamp)?;
+        queue.push(validator, validator, amount, timestamp)?;
         provider.write_bonding(queue);
         Ok(())
     }
 
+    /// Returns an error if adding `amount` to the stake of `validator` would leave it outside the
+    /// minimum and maximum bond amounts.
+    fn validate_bond_amount_bounds<P: StakesProvider>(
+        provider: &mut P,
+        stakes: &Stakes,
+        validator: &AccountHash,
+        amount: U512,
+    ) -> Result<()> {
+        let stake = stakes
+            .0
+            .get(validator)
+            .copied()
+            .unwrap_or_default()
+            .saturating_add(amount);
+        if stake < provider.read_min_bond_amount() {
+            return Err(Error::StakeBelowMinimum);
+        }
+        if stake > provider.read_max_bond_amount() {
+            return Err(Error::StakeAboveMaximum);
+        }
+        Ok(())
+    }
+
     /// Enqueues the deploy's creator for unbonding. Their vote weight as a validator is decreased
     /// immediately, but the funds will only be released after a delay. If `maybe_amount` is `None`,
     /// all funds are enqueued for withdrawal, terminating the validator status.
-    pub fn unbond<P: QueueProvider + StakesProvider>(
+    ///
+    /// Stakes delegated to the validator can only be withdrawn by their delegators, so while there
+    /// are any, `None` withdraws only the validator's own stake and the validator remains bonded.
+    pub fn unbond<P: DelegationsProvider + QueueProvider + StakesProvider>(
         provider: &mut P,
         maybe_amount: Option<U512>,
         validator: AccountHash,
@@ -168,12 +266,83 @@ mod internal {
         }
 
         let mut stakes = provider.read()?;
+        // A partial unbond must not leave a nonzero stake below the minimum bond amount.
+        if let Some(amount) = maybe_amount {
+            let stake = stakes.0.get(&validator).ok_or(Error::NotBonded)?;
+            let remaining = stake.saturating_sub(amount);
+            if !remaining.is_zero() && remaining < provider.read_min_bond_amount() {
+                return Err(Error::StakeBelowMinimum);
+            }
+        }
+        let delegated = provider.read_delegations()?.total_delegated_to(&validator);
+        let maybe_amount = if delegated.is_zero() {
+            maybe_amount
+        } else {
+            let stake = stakes.0.get(&validator).ok_or(Error::NotBonded)?;
+            let own_stake = stake.saturating_sub(delegated);
+            match maybe_amount {
+                Some(amount) if amount > own_stake => return Err(Error::UnbondTooLarge),
+                Some(amount) => Some(amount),
+                None => Some(own_stake),
+            }
+        };
         let payout = stakes.unbond(&validator, maybe_amount)?;
         provider.write(&stakes);
         // TODO: Make sure the destination is valid and the amount can be paid. The actual payment
         // will be made later, after the unbonding delay. contract_api::transfer_dry_run(POS_PURSE,
         // dest, amount)?;
-        queue.push(validator, payout, timestamp)?;
+        queue.push(validator, validator, payout, timestamp)?;
+        provider.write_unbonding(queue);
+        Ok(())
+    }
+
+    /// Delegates `amount` from `delegator` to the bonded `validator`. The delegated amount becomes
+    /// part of the validator's stake immediately.
+    pub fn delegate<P: DelegationsProvider + StakesProvider>(
+        provider: &mut P,
+        amount: U512,
+        delegator: AccountHash,
+        validator: AccountHash,
+    ) -> Result<()> {
+        let mut stakes = provider.read()?;
+        if !stakes.0.contains_key(&validator) {
+            return Err(Error::NotBonded);
+        }
+        validate_bond_amount_bounds(provider, &stakes, &validator, amount)?;
+        stakes.validate_bonding(&validator, amount)?;
+        stakes.bond(&validator, amount);
+
+        let mut delegations = provider.read_delegations()?;
+        delegations.delegate(&delegator, &validator, amount);
+
+        provider.write(&stakes);
+        provider.write_delegations(&delegations);
+        Ok(())
+    }
+
+    /// Enqueues the stake `delegator` has delegated to `validator` for withdrawal. The validator's
+    /// stake is decreased immediately, but the funds will only be released to the delegator after
+    /// the unbonding delay. If `maybe_amount` is `None`, the whole delegation is withdrawn.
+    pub fn undelegate<P: DelegationsProvider + QueueProvider + StakesProvider>(
+        provider: &mut P,
+        maybe_amount: Option<U512>,
+        delegator: AccountHash,
+        validator: AccountHash,
+        timestamp: BlockTime,
+    ) -> Result<()> {
+        let mut queue = provider.read_unbonding();
+        if queue.0.len() >= MAX_UNBOND_LEN {
+            return Err(Error::TooManyEventsInQueue);
+        }
+
+        let mut delegations = provider.read_delegations()?;
+        let amount = delegations.undelegate(&delegator, &validator, maybe_amount)?;
+        let mut stakes = provider.read()?;
+        let payout = stakes.unbond(&validator, Some(amount))?;
+        provider.write(&stakes);
+        provider.write_delegations(&delegations);
+
+        queue.push(delegator, validator, payout, timestamp)?;
         provider.write_unbonding(queue);
         Ok(())
     }
@@ -187,8 +356,9 @@ mod internal {
         let mut unbonding_queue = provider.read_unbonding();
 
         let bonds = bonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(BOND_DELAY)));
+        let unbond_delay = provider.read_unbonding_delay();
         let unbonds =
-            unbonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(UNBOND_DELAY)));
+            unbonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(unbond_delay)));
 
         if !unbonds.is_empty() {
             provider.write_unbonding(unbonding_queue);
@@ -206,6 +376,63 @@ mod internal {
         Ok(unbonds)
     }
 
+    /// Removes the given validators' stakes, the stakes delegated to them and their pending bonding
+    /// and unbonding requests, and transfers the removed motes from the bonding purse to the
+    /// rewards purse. Validators which are not bonded are ignored.
+    pub fn slash<P>(provider: &mut P, validators: &[AccountHash]) -> Result<()>
+    where
+        P: DelegationsProvider + MintProvider + QueueProvider + RuntimeProvider + StakesProvider,
+    {
+        let caller = provider.get_caller();
+        if caller != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+
+        let amount = remove_slashed(provider, validators)?;
+        if amount.is_zero() {
+            return Ok(());
+        }
+
+        let bonding_purse = get_bonding_purse(provider)?;
+        let rewards_purse = get_rewards_purse(provider)?;
+        provider
+            .transfer_purse_to_purse(bonding_purse, rewards_purse, amount)
+            .map_err(|_| Error::FailedTransferToRewardsPurse)
+    }
+
+    /// Removes the given validators from the stakes, the delegations and both queues, and returns
+    /// the total amount removed. Pending undelegations from the given validators are removed too,
+    /// while requests the validators made as delegators of other validators are kept.
+    ///
+    /// Returns an error if this would leave no validators bonded.
+    pub fn remove_slashed<P: DelegationsProvider + QueueProvider + StakesProvider>(
+        provider: &mut P,
+        validators: &[AccountHash],
+    ) -> Result<U512> {
+        let mut stakes = provider.read()?;
+        let mut delegations = provider.read_delegations()?;
+        let mut bonding_queue = provider.read_bonding();
+        let mut unbonding_queue = provider.read_unbonding();
+
+        let mut amount = U512::zero();
+        for validator in validators {
+            amount += stakes.slash(validator);
+            delegations.remove_validator(validator);
+            amount += bonding_queue.remove_validator(validator);
+            amount += unbonding_queue.remove_validator(validator);
+        }
+
+        if stakes.0.is_empty() {
+            return Err(Error::CannotUnbondLastValidator);
+        }
+
+        provider.write(&stakes);
+        provider.write_delegations(&delegations);
+        provider.write_bonding(bonding_queue);
+        provider.write_unbonding(unbonding_queue);
+        Ok(amount)
+    }
+
     /// Attempts to look up a purse from the named_keys
     fn get_purse<R: RuntimeProvider>(
         runtime_provider: &R,
@@ -275,10 +502,9 @@ mod internal {
             None => return Err(Error::PaymentPurseBalanceNotFound),
         };
 
-        if total < amount_spent {
-            return Err(Error::InsufficientPaymentForAmountSpent);
-        }
-        let refund_amount = total - amount_spent;
+        let refund_amount = total
+            .checked_sub(amount_spent)
+            .ok_or(Error::InsufficientPaymentForAmountSpent)?;
 
         let rewards_purse = get_rewards_purse(provider)?;
         let refund_purse = get_refund_purse(provider)?;
@@ -310,6 +536,58 @@ mod internal {
         Ok(())
     }
 
+    /// Transfers the balance of the rewards purse, i.e. the fees collected by `finalize_payment`
+    /// and any slashed stakes, to the main purse of the block proposer `validator`.
+    pub fn distribute_rewards<P: MintProvider + RuntimeProvider>(
+        provider: &mut P,
+        validator: AccountHash,
+    ) -> Result<()> {
+        let caller = provider.get_caller();
+        if caller != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+
+        let rewards_purse = get_rewards_purse(provider)?;
+        let round_reward = provider
+            .round_reward()
+            .map_err(|_| Error::FailedToMintRoundReward)?;
+        if !round_reward.is_zero() {
+            provider
+                .mint_into(rewards_purse, round_reward)
+                .map_err(|_| Error::FailedToMintRoundReward)?;
+        }
+
+        let amount = provider
+            .balance(rewards_purse)
+            .ok_or(Error::RewardsPurseBalanceNotFound)?;
+        if amount.is_zero() {
+            return Ok(());
+        }
+
+        match provider.transfer_purse_to_account(rewards_purse, validator, amount) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::FailedTransferToProposerPurse),
+        }
+    }
+
+    /// Sets the gas price read by the engine when charging for deploys.
+    pub fn set_gas_price<P: GasPriceProvider + RuntimeProvider>(
+        provider: &mut P,
+        gas_price: u64,
+    ) -> Result<()> {
+        let caller = provider.get_caller();
+        if caller != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+        // Gas limits are derived by dividing a payment by the gas price.
+        if gas_price == 0 {
+            return Err(Error::InvalidGasPrice);
+        }
+
+        provider.write_gas_price(gas_price);
+        Ok(())
+    }
+
     pub fn refund_to_account<M: MintProvider>(
         mint_provider: &mut M,
         payment_purse: URef,
@@ -326,18 +604,31 @@ mod internal {
     mod tests {
         extern crate std;
 
-        use std::{cell::RefCell, iter, thread_local};
+        use std::{
+            cell::{Cell, RefCell},
+            iter, thread_local, vec,
+        };
 
-        use types::{account::AccountHash, system_contract_errors::pos::Result, BlockTime, U512};
+        use types::{
+            account::AccountHash,
+            system_contract_errors::pos::{Error, Result},
+            BlockTime, U512,
+        };
 
-        use super::{bond, step, unbond, BOND_DELAY, UNBOND_DELAY};
+        use super::{
+            bond, delegate, remove_slashed, step, unbond, undelegate, QueueEntry, BOND_DELAY,
+        };
         use crate::{
-            queue::Queue, queue_provider::QueueProvider, stakes::Stakes,
-            stakes_provider::StakesProvider,
+            delegations::Delegations, delegations_provider::DelegationsProvider, queue::Queue,
+            queue_provider::QueueProvider, stakes::Stakes, stakes_provider::StakesProvider,
         };
 
         const KEY1: [u8; 32] = [1; 32];
         const KEY2: [u8; 32] = [2; 32];
+        const KEY3: [u8; 32] = [3; 32];
+
+        /// The unbonding delay the tests start with.
+        const UNBOND_DELAY: u64 = 0;
 
         thread_local! {
             static BONDING: RefCell<Queue> = RefCell::new(Queue(Default::default()));
@@ -345,10 +636,24 @@ mod internal {
             static STAKES: RefCell<Stakes> = RefCell::new(
                 Stakes(iter::once((AccountHash::new(KEY1), U512::from(1_000))).collect())
             );
+            static DELEGATIONS: RefCell<Delegations> = RefCell::new(Delegations::default());
+            static UNBONDING_DELAY: Cell<u64> = Cell::new(UNBOND_DELAY);
+            static MIN_BOND_AMOUNT: Cell<U512> = Cell::new(U512::zero());
+            static MAX_BOND_AMOUNT: Cell<U512> = Cell::new(U512::MAX);
         }
 
         struct Provider;
 
+        impl DelegationsProvider for Provider {
+            fn read_delegations(&self) -> Result<Delegations> {
+                DELEGATIONS.with(|d| Ok(d.borrow().clone()))
+            }
+
+            fn write_delegations(&mut self, delegations: &Delegations) {
+                DELEGATIONS.with(|d| d.replace(delegations.clone()));
+            }
+        }
+
         impl QueueProvider for Provider {
             fn read_bonding(&mut self) -> Queue {
                 BONDING.with(|b| b.borrow().clone())
@@ -365,6 +670,10 @@ mod internal {
             fn write_unbonding(&mut self, queue: Queue) {
                 UNBONDING.with(|ub| ub.replace(queue));
             }
+
+            fn read_unbonding_delay(&mut self) -> u64 {
+                UNBONDING_DELAY.with(Cell::get)
+            }
         }
 
         impl StakesProvider for Provider {
@@ -375,6 +684,14 @@ mod internal {
             fn write(&mut self, stakes: &Stakes) {
                 STAKES.with(|s| s.replace(stakes.clone()));
             }
+
+            fn read_min_bond_amount(&mut self) -> U512 {
+                MIN_BOND_AMOUNT.with(Cell::get)
+            }
+
+            fn read_max_bond_amount(&mut self) -> U512 {
+                MAX_BOND_AMOUNT.with(Cell::get)
+            }
         }
 
         fn assert_stakes(stakes: &[([u8; 32], usize)]) {
@@ -418,5 +735,317 @@ mod internal {
             step::<Provider>(&mut provider, BlockTime::new(2 + UNBOND_DELAY)).expect("step 3");
             assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
         }
+
+        #[test]
+        fn test_unbond_waits_for_delay() {
+            const DELAY: u64 = 10;
+            UNBONDING_DELAY.with(|d| d.set(DELAY));
+            let mut provider = Provider;
+            bond(
+                &mut provider,
+                U512::from(500),
+                AccountHash::new(KEY2),
+                BlockTime::new(1),
+            )
+            .expect("bond validator 2");
+            step(&mut provider, BlockTime::new(1 + BOND_DELAY)).expect("step 1");
+
+            unbond(
+                &mut provider,
+                Some(U512::from(200)),
+                AccountHash::new(KEY2),
+                BlockTime::new(5),
+            )
+            .expect("partly unbond validator 2");
+            assert_stakes(&[(KEY1, 1_000), (KEY2, 300)]);
+
+            // The request isn't paid out before the delay has passed.
+            let unbonds = step(&mut provider, BlockTime::new(5 + DELAY - 1)).expect("step 2");
+            assert!(unbonds.is_empty());
+
+            let unbonds = step(&mut provider, BlockTime::new(5 + DELAY)).expect("step 3");
+            assert_eq!(
+                vec![QueueEntry {
+                    account: AccountHash::new(KEY2),
+                    validator: AccountHash::new(KEY2),
+                    amount: U512::from(200),
+                    timestamp: BlockTime::new(5),
+                }],
+                unbonds
+            );
+            assert!(provider.read_unbonding().0.is_empty());
+        }
+
+        #[test]
+        fn test_bond_amount_bounds() {
+            MIN_BOND_AMOUNT.with(|min| min.set(U512::from(100)));
+            MAX_BOND_AMOUNT.with(|max| max.set(U512::from(1_200)));
+            let mut provider = Provider;
+            let validator1 = AccountHash::new(KEY1);
+            let validator2 = AccountHash::new(KEY2);
+
+            assert_eq!(
+                Err(Error::StakeBelowMinimum),
+                bond(&mut provider, U512::from(99), validator2, BlockTime::new(1))
+            );
+            assert_eq!(
+                Err(Error::StakeAboveMaximum),
+                bond(
+                    &mut provider,
+                    U512::from(201),
+                    validator1,
+                    BlockTime::new(1)
+                )
+            );
+            assert_eq!(
+                Err(Error::StakeAboveMaximum),
+                delegate(
+                    &mut provider,
+                    U512::from(201),
+                    AccountHash::new(KEY3),
+                    validator1
+                )
+            );
+            assert!(provider.read_bonding().0.is_empty());
+            assert_stakes(&[(KEY1, 1_000)]);
+
+            // Stakes exactly at the bounds are accepted.
+            bond(
+                &mut provider,
+                U512::from(100),
+                validator2,
+                BlockTime::new(1),
+            )
+            .expect("bond validator 2");
+            bond(
+                &mut provider,
+                U512::from(200),
+                validator1,
+                BlockTime::new(1),
+            )
+            .expect("bond validator 1");
+            step(&mut provider, BlockTime::new(1 + BOND_DELAY)).expect("step 1");
+            assert_stakes(&[(KEY1, 1_200), (KEY2, 100)]);
+
+            // A partial unbond can't leave less than the minimum, but a full unbond is allowed.
+            assert_eq!(
+                Err(Error::StakeBelowMinimum),
+                unbond(
+                    &mut provider,
+                    Some(U512::from(1_101)),
+                    validator1,
+                    BlockTime::new(2)
+                )
+            );
+            unbond(
+                &mut provider,
+                Some(U512::from(1_100)),
+                validator1,
+                BlockTime::new(2),
+            )
+            .expect("partly unbond validator 1");
+            unbond(
+                &mut provider,
+                Some(U512::from(100)),
+                validator2,
+                BlockTime::new(3),
+            )
+            .expect("fully unbond validator 2");
+            assert_stakes(&[(KEY1, 100)]);
+        }
+
+        #[test]
+        fn test_delegate_undelegate() {
+            let mut provider = Provider;
+            let delegator = AccountHash::new(KEY3);
+            assert_eq!(
+                Err(Error::NotBonded),
+                delegate(
+                    &mut provider,
+                    U512::from(300),
+                    delegator,
+                    AccountHash::new(KEY2)
+                )
+            );
+            delegate(
+                &mut provider,
+                U512::from(300),
+                delegator,
+                AccountHash::new(KEY1),
+            )
+            .expect("delegate to validator 1");
+
+            // Delegating becomes effective immediately.
+            assert_stakes(&[(KEY1, 1_300)]);
+
+            // The validator can't withdraw the delegated stake.
+            assert_eq!(
+                Err(Error::UnbondTooLarge),
+                unbond(
+                    &mut provider,
+                    Some(U512::from(1_001)),
+                    AccountHash::new(KEY1),
+                    BlockTime::new(1),
+                )
+            );
+
+            undelegate(
+                &mut provider,
+                Some(U512::from(100)),
+                delegator,
+                AccountHash::new(KEY1),
+                BlockTime::new(2),
+            )
+            .expect("partly undelegate");
+            assert_stakes(&[(KEY1, 1_200)]);
+
+            let unbonds = step(&mut provider, BlockTime::new(2 + UNBOND_DELAY)).expect("step 1");
+            assert_eq!(
+                vec![QueueEntry {
+                    account: delegator,
+                    validator: AccountHash::new(KEY1),
+                    amount: U512::from(100),
+                    timestamp: BlockTime::new(2),
+                }],
+                unbonds
+            );
+
+            undelegate(
+                &mut provider,
+                None,
+                delegator,
+                AccountHash::new(KEY1),
+                BlockTime::new(3),
+            )
+            .expect("fully undelegate");
+            assert_stakes(&[(KEY1, 1_000)]);
+            assert_eq!(Ok(Delegations::default()), provider.read_delegations());
+        }
+
+        #[test]
+        fn test_several_pending_undelegations() {
+            const DELAY: u64 = 10;
+            UNBONDING_DELAY.with(|d| d.set(DELAY));
+            let mut provider = Provider;
+            let validator = AccountHash::new(KEY1);
+            let delegator = AccountHash::new(KEY3);
+            delegate(&mut provider, U512::from(300), delegator, validator)
+                .expect("delegate to validator 1");
+
+            undelegate(
+                &mut provider,
+                Some(U512::from(100)),
+                delegator,
+                validator,
+                BlockTime::new(1),
+            )
+            .expect("first undelegation");
+            undelegate(
+                &mut provider,
+                Some(U512::from(50)),
+                delegator,
+                validator,
+                BlockTime::new(2),
+            )
+            .expect("second undelegation within the delay");
+            assert_stakes(&[(KEY1, 1_150)]);
+            assert_eq!(2, provider.read_unbonding().0.len());
+
+            let unbonds = step(&mut provider, BlockTime::new(2 + DELAY)).expect("step 1");
+            assert_eq!(
+                vec![
+                    QueueEntry {
+                        account: delegator,
+                        validator,
+                        amount: U512::from(100),
+                        timestamp: BlockTime::new(1),
+                    },
+                    QueueEntry {
+                        account: delegator,
+                        validator,
+                        amount: U512::from(50),
+                        timestamp: BlockTime::new(2),
+                    }
+                ],
+                unbonds
+            );
+            assert!(provider.read_unbonding().0.is_empty());
+        }
+
+        #[test]
+        fn test_remove_slashed() {
+            let mut provider = Provider;
+            UNBONDING_DELAY.with(|delay| delay.set(100));
+            let validator1 = AccountHash::new(KEY1);
+            let validator2 = AccountHash::new(KEY2);
+            let delegator = AccountHash::new(KEY3);
+
+            bond(
+                &mut provider,
+                U512::from(1_000),
+                validator2,
+                BlockTime::new(0),
+            )
+            .expect("bond validator 2");
+            step(&mut provider, BlockTime::new(BOND_DELAY)).expect("step 1");
+            delegate(&mut provider, U512::from(200), delegator, validator1)
+                .expect("delegate to validator 1");
+            unbond(
+                &mut provider,
+                Some(U512::from(300)),
+                validator1,
+                BlockTime::new(1),
+            )
+            .expect("unbond validator 1");
+            bond(&mut provider, U512::from(50), validator1, BlockTime::new(2))
+                .expect("bond validator 1");
+            undelegate(
+                &mut provider,
+                Some(U512::from(20)),
+                delegator,
+                validator1,
+                BlockTime::new(3),
+            )
+            .expect("undelegate from validator 1");
+            delegate(&mut provider, U512::from(10), validator1, validator2)
+                .expect("validator 1 delegates to validator 2");
+            undelegate(
+                &mut provider,
+                None,
+                validator1,
+                validator2,
+                BlockTime::new(4),
+            )
+            .expect("validator 1 undelegates from validator 2");
+            assert_stakes(&[(KEY1, 880), (KEY2, 1_000)]);
+
+            // The remaining stake including the delegated stake, the pending bond, the pending
+            // unbond and the pending undelegation are all removed. Validators which are not bonded
+            // are ignored.
+            assert_eq!(
+                Ok(U512::from(1_250)),
+                remove_slashed(&mut provider, &[validator1, AccountHash::new([4; 32])])
+            );
+            assert_stakes(&[(KEY2, 1_000)]);
+            assert_eq!(Ok(Delegations::default()), provider.read_delegations());
+            assert!(provider.read_bonding().0.is_empty());
+
+            // Validator 1's undelegation from validator 2 is not forfeited.
+            assert_eq!(
+                vec![QueueEntry {
+                    account: validator1,
+                    validator: validator2,
+                    amount: U512::from(10),
+                    timestamp: BlockTime::new(4),
+                }],
+                provider.read_unbonding().0
+            );
+
+            assert_eq!(
+                Err(Error::CannotUnbondLastValidator),
+                remove_slashed(&mut provider, &[validator2])
+            );
+            assert_stakes(&[(KEY2, 1_000)]);
+        }
     }
 }
@@ -16,4 +16,10 @@ pub trait MintProvider {
     ) -> Result<(), ()>;
 
     fn balance(&mut self, purse: URef) -> Option<U512>;
+
+    /// Returns the seigniorage the mint pays out for a single round.
+    fn round_reward(&mut self) -> Result<U512, ()>;
+
+    /// Creates `amount` new motes in `purse`, increasing the mint's total supply.
+    fn mint_into(&mut self, purse: URef, amount: U512) -> Result<(), ()>;
 }
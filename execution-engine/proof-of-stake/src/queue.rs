@@ -11,7 +11,10 @@ use types::{
 /// A pending entry in the bonding or unbonding queue.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct QueueEntry {
-    /// The validator who is bonding or unbonding.
+    /// The account which made the request: the validator itself, or the delegator withdrawing a
+    /// delegation. Unbonded motes are paid out to this account.
+    pub account: AccountHash,
+    /// The validator whose stake is changed.
     pub validator: AccountHash,
     /// The amount by which to change the stakes.
     pub amount: U512,
@@ -21,8 +24,14 @@ pub struct QueueEntry {
 
 impl QueueEntry {
     /// Creates a new `QueueEntry` with the current block's timestamp.
-    fn new(validator: AccountHash, amount: U512, timestamp: BlockTime) -> QueueEntry {
+    fn new(
+        account: AccountHash,
+        validator: AccountHash,
+        amount: U512,
+        timestamp: BlockTime,
+    ) -> QueueEntry {
         QueueEntry {
+            account,
             validator,
             amount,
             timestamp,
@@ -33,6 +42,7 @@ impl QueueEntry {
 impl ToBytes for QueueEntry {
     fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
         let mut bytes = bytesrepr::allocate_buffer(self)?;
+        bytes.append(&mut self.account.to_bytes()?);
         bytes.append(&mut self.validator.to_bytes()?);
         bytes.append(&mut self.amount.to_bytes()?);
         bytes.append(&mut self.timestamp.to_bytes()?);
@@ -40,7 +50,8 @@ impl ToBytes for QueueEntry {
     }
 
     fn serialized_length(&self) -> usize {
-        self.validator.serialized_length()
+        self.account.serialized_length()
+            + self.validator.serialized_length()
             + self.amount.serialized_length()
             + self.timestamp.serialized_length()
     }
@@ -48,10 +59,12 @@ impl ToBytes for QueueEntry {
 
 impl FromBytes for QueueEntry {
     fn from_bytes(bytes: &[u8]) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
+        let (account, bytes) = AccountHash::from_bytes(bytes)?;
         let (validator, bytes) = AccountHash::from_bytes(bytes)?;
         let (amount, bytes) = U512::from_bytes(bytes)?;
         let (timestamp, bytes) = BlockTime::from_bytes(bytes)?;
         let entry = QueueEntry {
+            account,
             validator,
             amount,
             timestamp,
@@ -71,27 +84,40 @@ impl CLTyped for QueueEntry {
 pub struct Queue(pub Vec<QueueEntry>);
 
 impl Queue {
-    /// Pushes a new entry to the end of the queue.
+    /// Pushes a new entry to the end of the queue. An account may have several pending requests,
+    /// including several for the same validator.
     ///
-    /// Returns an error if the validator already has a request in the queue.
+    /// Returns an error if the entry is older than the last one in the queue.
     pub fn push(
         &mut self,
+        account: AccountHash,
         validator: AccountHash,
         amount: U512,
         timestamp: BlockTime,
     ) -> Result<()> {
-        if self.0.iter().any(|entry| entry.validator == validator) {
-            return Err(Error::MultipleRequests);
-        }
         if let Some(entry) = self.0.last() {
             if entry.timestamp > timestamp {
                 return Err(Error::TimeWentBackwards);
             }
         }
-        self.0.push(QueueEntry::new(validator, amount, timestamp));
+        self.0
+            .push(QueueEntry::new(account, validator, amount, timestamp));
         Ok(())
     }
 
+    /// Removes all entries changing the stake of `validator`, whichever account made them, and
+    /// returns their total amount.
+    pub fn remove_validator(&mut self, validator: &AccountHash) -> U512 {
+        let (removed, rest): (Vec<QueueEntry>, Vec<QueueEntry>) = self
+            .0
+            .iter()
+            .partition(|entry| entry.validator == *validator);
+        self.0 = rest;
+        removed
+            .iter()
+            .fold(U512::zero(), |sum, entry| sum.saturating_add(entry.amount))
+    }
+
     /// Returns all queue entries at least as old as the specified timestamp.
     pub fn pop_due(&mut self, timestamp: BlockTime) -> Vec<QueueEntry> {
         let (older_than, rest) = self
@@ -157,15 +183,50 @@ mod tests {
         let val2 = AccountHash::new(KEY2);
         let val3 = AccountHash::new(KEY3);
         let mut queue: Queue = Default::default();
-        assert_eq!(Ok(()), queue.push(val1, U512::from(5), BlockTime::new(100)));
-        assert_eq!(Ok(()), queue.push(val2, U512::from(5), BlockTime::new(101)));
         assert_eq!(
-            Err(Error::MultipleRequests),
-            queue.push(val1, U512::from(5), BlockTime::new(102))
+            Ok(()),
+            queue.push(val1, val1, U512::from(5), BlockTime::new(100))
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(val2, val2, U512::from(5), BlockTime::new(101))
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(val1, val1, U512::from(5), BlockTime::new(102))
         );
         assert_eq!(
             Err(Error::TimeWentBackwards),
-            queue.push(val3, U512::from(5), BlockTime::new(100))
+            queue.push(val3, val3, U512::from(5), BlockTime::new(100))
+        );
+        assert_eq!(3, queue.0.len());
+    }
+
+    #[test]
+    fn test_push_several_undelegations() {
+        let delegator = AccountHash::new(KEY1);
+        let val2 = AccountHash::new(KEY2);
+        let val3 = AccountHash::new(KEY3);
+        let mut queue: Queue = Default::default();
+        assert_eq!(
+            Ok(()),
+            queue.push(delegator, val2, U512::from(5), BlockTime::new(100))
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(delegator, val2, U512::from(6), BlockTime::new(101))
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(delegator, val3, U512::from(7), BlockTime::new(102))
+        );
+        assert_eq!(
+            vec![
+                QueueEntry::new(delegator, val2, U512::from(5), BlockTime::new(100)),
+                QueueEntry::new(delegator, val2, U512::from(6), BlockTime::new(101)),
+                QueueEntry::new(delegator, val3, U512::from(7), BlockTime::new(102)),
+            ],
+            queue.pop_due(BlockTime::new(102))
         );
     }
 
@@ -175,31 +236,85 @@ mod tests {
         let val2 = AccountHash::new(KEY2);
         let val3 = AccountHash::new(KEY3);
         let mut queue: Queue = Default::default();
-        assert_eq!(Ok(()), queue.push(val1, U512::from(5), BlockTime::new(100)));
-        assert_eq!(Ok(()), queue.push(val2, U512::from(6), BlockTime::new(101)));
-        assert_eq!(Ok(()), queue.push(val3, U512::from(7), BlockTime::new(102)));
+        assert_eq!(
+            Ok(()),
+            queue.push(val1, val1, U512::from(5), BlockTime::new(100))
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(val2, val2, U512::from(6), BlockTime::new(101))
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(val3, val3, U512::from(7), BlockTime::new(102))
+        );
         assert_eq!(
             vec![
-                QueueEntry::new(val1, U512::from(5), BlockTime::new(100)),
-                QueueEntry::new(val2, U512::from(6), BlockTime::new(101)),
+                QueueEntry::new(val1, val1, U512::from(5), BlockTime::new(100)),
+                QueueEntry::new(val2, val2, U512::from(6), BlockTime::new(101)),
             ],
             queue.pop_due(BlockTime::new(101))
         );
         assert_eq!(
-            vec![QueueEntry::new(val3, U512::from(7), BlockTime::new(102)),],
+            vec![QueueEntry::new(
+                val3,
+                val3,
+                U512::from(7),
+                BlockTime::new(102)
+            ),],
             queue.pop_due(BlockTime::new(105))
         );
     }
 
+    #[test]
+    fn test_remove_validator() {
+        let val1 = AccountHash::new(KEY1);
+        let val2 = AccountHash::new(KEY2);
+        let delegator = AccountHash::new(KEY3);
+        let mut queue: Queue = Default::default();
+        assert_eq!(
+            Ok(()),
+            queue.push(val1, val1, U512::from(5), BlockTime::new(100))
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(val2, val2, U512::from(6), BlockTime::new(101))
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(delegator, val1, U512::from(7), BlockTime::new(102))
+        );
+        // A request made by `val1` as a delegator of another validator is kept.
+        assert_eq!(
+            Ok(()),
+            queue.push(val1, val2, U512::from(8), BlockTime::new(103))
+        );
+        assert_eq!(U512::from(12), queue.remove_validator(&val1));
+        assert_eq!(U512::zero(), queue.remove_validator(&val1));
+        assert_eq!(
+            vec![
+                QueueEntry::new(val2, val2, U512::from(6), BlockTime::new(101)),
+                QueueEntry::new(val1, val2, U512::from(8), BlockTime::new(103)),
+            ],
+            queue.0
+        );
+    }
+
     #[test]
     fn serialization_roundtrip() {
         let val1 = AccountHash::new(KEY1);
         let val2 = AccountHash::new(KEY2);
         let val3 = AccountHash::new(KEY3);
         let mut queue: Queue = Default::default();
-        queue.push(val1, U512::from(5), BlockTime::new(0)).unwrap();
-        queue.push(val2, U512::from(6), BlockTime::new(1)).unwrap();
-        queue.push(val3, U512::from(7), BlockTime::new(2)).unwrap();
+        queue
+            .push(val1, val1, U512::from(5), BlockTime::new(0))
+            .unwrap();
+        queue
+            .push(val2, val2, U512::from(6), BlockTime::new(1))
+            .unwrap();
+        queue
+            .push(val3, val1, U512::from(7), BlockTime::new(2))
+            .unwrap();
         bytesrepr::test_serialization_roundtrip(&queue);
     }
 }
@@ -12,4 +12,7 @@ pub trait QueueProvider {
 
     /// Writes unbonding queue.
     fn write_unbonding(&mut self, queue: Queue);
+
+    /// Reads the time in milliseconds from an unbonding request until the stakes are paid out.
+    fn read_unbonding_delay(&mut self) -> u64;
 }
@@ -1,4 +1,4 @@
-use types::{account::AccountHash, BlockTime, Key, Phase};
+use types::{account::AccountHash, BlockTime, Key, Phase, URef};
 
 pub trait RuntimeProvider {
     fn get_key(&self, name: &str) -> Option<Key>;
@@ -12,4 +12,6 @@ pub trait RuntimeProvider {
     fn get_block_time(&self) -> BlockTime;
 
     fn get_caller(&self) -> AccountHash;
+
+    fn is_valid_uref(&self, uref: URef) -> bool;
 }
@@ -57,7 +57,7 @@ impl Stakes {
     }
 
     pub fn total_bonds(&self) -> U512 {
-        self.values().fold(U512::zero(), |x, y| x + y)
+        self.sum()
     }
 
     /// If `maybe_amount` is `None`, removes all the validator's stakes,
@@ -79,14 +79,15 @@ impl Stakes {
         if let Some(amount) = maybe_amount {
             // The minimum stake value to not violate the maximum spread.
             let stake = self.0.get_mut(validator).ok_or(Error::NotBonded)?;
-            if *stake > amount {
-                if *stake - amount < min {
+            let partial = stake.checked_sub(amount).filter(|rem| !rem.is_zero());
+            if let Some(remaining) = partial {
+                if remaining < min {
                     return Err(Error::SpreadTooHigh);
                 }
                 if amount > max_decrease {
                     return Err(Error::UnbondTooLarge);
                 }
-                *stake -= amount;
+                *stake = remaining;
                 return Ok(amount);
             }
         }
@@ -109,6 +110,13 @@ impl Stakes {
         Ok(stake)
     }
 
+    /// Removes all the validator's stakes, regardless of the bonding limits.
+    ///
+    /// Returns the amount that was removed, which is zero if the validator was not bonded.
+    pub fn slash(&mut self, validator: &AccountHash) -> U512 {
+        self.0.remove(validator).unwrap_or_default()
+    }
+
     /// Adds `amount` to the validator's stakes.
     pub fn bond(&mut self, validator: &AccountHash, amount: U512) {
         self.0
@@ -127,7 +135,10 @@ impl Stakes {
             .max_without(validator)
             .unwrap_or_else(U512::zero)
             .saturating_sub(MAX_SPREAD);
-        let stake = self.0.get(validator).map(|s| *s + amount).unwrap_or(amount);
+        let stake = match self.0.get(validator) {
+            Some(current) => current.checked_add(amount).ok_or(Error::BondTooLarge)?,
+            None => amount,
+        };
         if stake > max || stake < min {
             return Err(Error::SpreadTooHigh);
         }
@@ -240,6 +251,14 @@ mod tests {
         assert_eq!(new_stakes(&[(KEY2, 100)]), stakes);
     }
 
+    #[test]
+    fn test_slash() {
+        let mut stakes = new_stakes(&[(KEY1, 5), (KEY2, 100)]);
+        assert_eq!(U512::from(100), stakes.slash(&AccountHash::new(KEY2)));
+        assert_eq!(U512::zero(), stakes.slash(&AccountHash::new(KEY2)));
+        assert_eq!(new_stakes(&[(KEY1, 5)]), stakes);
+    }
+
     #[test]
     fn test_unbond_last_validator() {
         let mut stakes = new_stakes(&[(KEY1, 5)]);
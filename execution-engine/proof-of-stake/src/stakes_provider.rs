@@ -1,3 +1,5 @@
+use types::U512;
+
 use crate::{stakes::Stakes, Result};
 
 /// A `StakesProvider` that reads and writes the stakes to/from the contract's known urefs.
@@ -5,4 +7,10 @@ pub trait StakesProvider {
     fn read(&self) -> Result<Stakes>;
 
     fn write(&mut self, stakes: &Stakes);
+
+    /// Reads the smallest nonzero stake a validator may hold.
+    fn read_min_bond_amount(&mut self) -> U512;
+
+    /// Reads the largest stake a validator may hold.
+    fn read_max_bond_amount(&mut self) -> U512;
 }
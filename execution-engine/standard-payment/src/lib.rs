@@ -14,10 +14,14 @@ pub use crate::{
 };
 
 pub trait StandardPayment: AccountProvider + MintProvider + ProofOfStakeProvider + Sized {
+    /// Moves `amount` from the caller's main purse into the PoS payment purse.
+    ///
+    /// No refund purse is set: whatever isn't spent is returned to the main purse by
+    /// `finalize_payment` by default, and setting one would make every deploy write to the PoS
+    /// contract.
     fn pay(&mut self, amount: U512) -> Result<(), ApiError> {
         let main_purse = self.get_main_purse()?;
         let payment_purse = self.get_payment_purse()?;
         self.transfer_purse_to_purse(main_purse, payment_purse, amount)
-            .map_err(|_| ApiError::Transfer)
     }
 }
@@ -7,6 +7,8 @@ use core::{
 };
 
 use failure::Fail;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
     bytesrepr::{Error, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
@@ -106,6 +108,7 @@ pub const WEIGHT_SERIALIZED_LENGTH: usize = U8_SERIALIZED_LENGTH;
 
 /// The weight attributed to a given [`AccountHash`] in an account's associated keys.
 #[derive(PartialOrd, Ord, PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Weight(u8);
 
 impl Weight {
@@ -229,6 +232,20 @@ impl FromBytes for AccountHash {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for AccountHash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serde_helpers::base16_array::serialize(&self.0, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for AccountHash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde_helpers::base16_array::deserialize(deserializer).map(AccountHash)
+    }
+}
+
 /// Errors that can occur while adding a new [`AccountHash`] to an account's associated keys map.
 #[derive(PartialEq, Eq, Fail, Debug, Copy, Clone)]
 #[repr(i32)]
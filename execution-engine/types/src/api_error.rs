@@ -182,6 +182,15 @@ const HEADER_ERROR_MAX: u32 = HEADER_ERROR_OFFSET + u8::MAX as u32;
 /// # show_and_check!(
 /// 34 => HostBufferFull
 /// # );
+/// # show_and_check!(
+/// 35 => AllocLayout
+/// # );
+/// # show_and_check!(
+/// 36 => NamedKeyExists
+/// # );
+/// # show_and_check!(
+/// 37 => EventLimitExceeded
+/// # );
 /// // Contract header errors:
 /// use casperlabs_types::contracts::Error as ContractHeaderError;
 /// # show_and_check!(
@@ -225,6 +234,12 @@ const HEADER_ERROR_MAX: u32 = HEADER_ERROR_OFFSET + u8::MAX as u32;
 /// # show_and_check!(
 /// 65_031 => MintError::PurseNotFound
 /// # );
+/// # show_and_check!(
+/// 65_032 => MintError::TotalSupplyNotFound
+/// # );
+/// # show_and_check!(
+/// 65_033 => MintError::InsufficientAllowance
+/// # );
 ///
 /// // Proof of stake errors:
 /// use casperlabs_types::system_contract_errors::pos::Error as PosError;
@@ -309,6 +324,18 @@ const HEADER_ERROR_MAX: u32 = HEADER_ERROR_OFFSET + u8::MAX as u32;
 /// # show_and_check!(
 /// 65_306 => PosError::SetRefundPurseCalledOutsidePayment
 /// # );
+/// # show_and_check!(
+/// 65_307 => PosError::NotDelegated
+/// # );
+/// # show_and_check!(
+/// 65_308 => PosError::UndelegateTooLarge
+/// # );
+/// # show_and_check!(
+/// 65_309 => PosError::DelegationsKeyDeserializationFailed
+/// # );
+/// # show_and_check!(
+/// 65_310 => PosError::DelegationsDeserializationFailed
+/// # );
 ///
 /// // User-defined errors:
 /// # show_and_check!(
@@ -426,6 +453,10 @@ pub enum ApiError {
     HostBufferFull,
     /// Could not lay out an array in memory
     AllocLayout,
+    /// A named key with the given name already exists in the current context.
+    NamedKeyExists,
+    /// Emitting an event would exceed the deploy's event count or size limit.
+    EventLimitExceeded,
     /// Contract header errors.
     ContractHeader(u8),
     /// Error specific to Mint contract.
@@ -568,6 +599,8 @@ impl From<ApiError> for u32 {
             ApiError::HostBufferEmpty => 33,
             ApiError::HostBufferFull => 34,
             ApiError::AllocLayout => 35,
+            ApiError::NamedKeyExists => 36,
+            ApiError::EventLimitExceeded => 37,
             ApiError::ContractHeader(value) => HEADER_ERROR_OFFSET + u32::from(value),
             ApiError::Mint(value) => MINT_ERROR_OFFSET + u32::from(value),
             ApiError::ProofOfStake(value) => POS_ERROR_OFFSET + u32::from(value),
@@ -614,6 +647,8 @@ impl From<u32> for ApiError {
             33 => ApiError::HostBufferEmpty,
             34 => ApiError::HostBufferFull,
             35 => ApiError::AllocLayout,
+            36 => ApiError::NamedKeyExists,
+            37 => ApiError::EventLimitExceeded,
             USER_ERROR_MIN..=USER_ERROR_MAX => ApiError::User(value as u16),
             POS_ERROR_MIN..=POS_ERROR_MAX => ApiError::ProofOfStake(value as u8),
             MINT_ERROR_MIN..=MINT_ERROR_MAX => ApiError::Mint(value as u8),
@@ -663,6 +698,8 @@ impl Debug for ApiError {
             ApiError::HostBufferEmpty => write!(f, "ApiError::HostBufferEmpty")?,
             ApiError::HostBufferFull => write!(f, "ApiError::HostBufferFull")?,
             ApiError::AllocLayout => write!(f, "ApiError::AllocLayout")?,
+            ApiError::NamedKeyExists => write!(f, "ApiError::NamedKeyExists")?,
+            ApiError::EventLimitExceeded => write!(f, "ApiError::EventLimitExceeded")?,
             ApiError::ContractHeader(value) => write!(f, "ApiError::ContractHeader({})", value)?,
             ApiError::Mint(value) => write!(f, "ApiError::Mint({})", value)?,
             ApiError::ProofOfStake(value) => write!(f, "ApiError::ProofOfStake({})", value)?,
@@ -821,6 +858,8 @@ mod tests {
         round_trip(Err(ApiError::HostBufferEmpty));
         round_trip(Err(ApiError::HostBufferFull));
         round_trip(Err(ApiError::AllocLayout));
+        round_trip(Err(ApiError::NamedKeyExists));
+        round_trip(Err(ApiError::EventLimitExceeded));
         round_trip(Err(ApiError::ContractHeader(0)));
         round_trip(Err(ApiError::ContractHeader(u8::MAX)));
         round_trip(Err(ApiError::Mint(0)));
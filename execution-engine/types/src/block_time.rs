@@ -6,7 +6,7 @@ use crate::bytesrepr::{Error, FromBytes, ToBytes, U64_SERIALIZED_LENGTH};
 pub const BLOCKTIME_SERIALIZED_LENGTH: usize = U64_SERIALIZED_LENGTH;
 
 /// A newtype wrapping a [`u64`] which represents the block time.
-#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd)]
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BlockTime(u64);
 
 impl BlockTime {
@@ -15,6 +15,12 @@ impl BlockTime {
         BlockTime(value)
     }
 
+    /// Saturating addition of a number of milliseconds. Computes `self + millis`, saturating at
+    /// [`u64::MAX`] instead of overflowing.
+    pub fn saturating_add(self, millis: u64) -> Self {
+        BlockTime(self.0.saturating_add(millis))
+    }
+
     /// Saturating integer subtraction. Computes `self - other`, saturating at `0` instead of
     /// overflowing.
     pub fn saturating_sub(self, other: BlockTime) -> Self {
@@ -44,3 +50,47 @@ impl FromBytes for BlockTime {
         Ok((BlockTime::new(time), rem))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bytesrepr;
+
+    #[test]
+    fn should_serialize_as_u64() {
+        let block_time = BlockTime::new(1_600_000_000_000);
+        assert_eq!(
+            block_time.to_bytes().unwrap(),
+            1_600_000_000_000u64.to_bytes().unwrap()
+        );
+        assert_eq!(
+            block_time.to_bytes().unwrap().len(),
+            BLOCKTIME_SERIALIZED_LENGTH
+        );
+        bytesrepr::test_serialization_roundtrip(&block_time);
+    }
+
+    #[test]
+    fn should_saturate_arithmetic() {
+        assert_eq!(
+            BlockTime::new(1_000).saturating_add(500),
+            BlockTime::new(1_500)
+        );
+        assert_eq!(
+            BlockTime::new(u64::max_value()).saturating_add(1),
+            BlockTime::new(u64::max_value())
+        );
+        assert_eq!(
+            BlockTime::new(1_000).saturating_sub(BlockTime::new(1_500)),
+            BlockTime::new(0)
+        );
+    }
+
+    #[test]
+    fn should_compare_block_times() {
+        let earlier = BlockTime::new(1_000);
+        let later = earlier.saturating_add(1);
+        assert!(earlier < later);
+        assert_eq!(earlier.max(later), later);
+    }
+}
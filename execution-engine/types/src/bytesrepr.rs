@@ -907,17 +907,51 @@ mod tests {
         // Assert the `INSTANCE_COUNT` has dropped to zero again.
         INSTANCE_COUNT.with(|count| assert_eq!(0, *count.borrow()));
     }
+
+    #[test]
+    fn map_encoding_should_not_depend_on_insertion_order() {
+        let ascending: BTreeMap<u8, u8> = (0..10).map(|i| (i, i * 2)).collect();
+        let descending: BTreeMap<u8, u8> = (0..10).rev().map(|i| (i, i * 2)).collect();
+        let bytes = ascending.to_bytes().unwrap();
+        assert_eq!(bytes, descending.to_bytes().unwrap());
+
+        let mut expected = 10u32.to_bytes().unwrap();
+        for i in 0..10u8 {
+            expected.push(i);
+            expected.push(i * 2);
+        }
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn decoded_map_should_reencode_canonically() {
+        // Maps serialized by other implementations (e.g. the AssemblyScript contract API) may
+        // list entries in insertion order.  They're accepted, but always re-encoded sorted.
+        let unsorted = vec![2, 0, 0, 0, 2, 20, 1, 10];
+        let (map, rem) = BTreeMap::<u8, u8>::from_bytes(&unsorted).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(map.to_bytes().unwrap(), vec![2, 0, 0, 0, 1, 10, 2, 20]);
+    }
+
+    #[test]
+    fn decoded_set_should_reencode_canonically() {
+        let unsorted = vec![3, 0, 0, 0, 3, 1, 2];
+        let (set, rem) = BTreeSet::<u8>::from_bytes(&unsorted).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(set.to_bytes().unwrap(), vec![3, 0, 0, 0, 1, 2, 3]);
+    }
 }
 
 #[cfg(test)]
 mod proptests {
-    use std::vec::Vec;
+    use std::{string::ToString, vec::Vec};
 
     use proptest::{collection::vec, prelude::*};
 
     use crate::{
         bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
         gens::*,
+        U512,
     };
 
     proptest! {
@@ -1016,6 +1050,19 @@ mod proptests {
             bytesrepr::test_serialization_roundtrip(&u);
         }
 
+        #[test]
+        fn test_u512_serialization_is_trimmed(u in u512_arb()) {
+            let serialized = u.to_bytes().unwrap();
+            let significant_bytes = (u.bits() + 7) / 8;
+            prop_assert_eq!(serialized[0] as usize, significant_bytes);
+            prop_assert_eq!(serialized.len(), significant_bytes + 1);
+        }
+
+        #[test]
+        fn test_u512_decimal_string_roundtrip(u in u512_arb()) {
+            prop_assert_eq!(u.to_string().parse::<U512>().unwrap(), u);
+        }
+
         #[test]
         fn test_key_serialization(key in key_arb()) {
             bytesrepr::test_serialization_roundtrip(&key);
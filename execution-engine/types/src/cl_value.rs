@@ -2,6 +2,8 @@ use alloc::vec::Vec;
 use core::fmt;
 
 use failure::Fail;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::{
     bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
@@ -44,8 +46,10 @@ pub enum CLValueError {
 /// It holds the underlying data as a type-erased, serialized `Vec<u8>` and also holds the
 /// [`CLType`] of the underlying data as a separate member.
 #[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CLValue {
     cl_type: CLType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::base16_bytes"))]
     bytes: Vec<u8>,
 }
 
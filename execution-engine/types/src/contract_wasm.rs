@@ -2,11 +2,16 @@ use crate::bytesrepr::{Error, FromBytes, ToBytes};
 use alloc::vec::Vec;
 use core::fmt::Debug;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 const CONTRACT_WASM_MAX_DISPLAY_LEN: usize = 16;
 
 /// A container for contract's WASM bytes.
 #[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ContractWasm {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::base16_bytes"))]
     bytes: Vec<u8>,
 }
 
@@ -14,6 +14,9 @@ use alloc::{
 };
 use core::fmt;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Maximum number of distinct user groups.
 pub const MAX_GROUPS: u8 = 10;
 /// Maximum number of URefs which can be assigned across all user groups.
@@ -52,6 +55,7 @@ pub enum Error {
 /// A (labelled) "user group". Each method of a versioned contract may be
 /// assoicated with one or more user groups which are allowed to call it.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Group(String);
 
 impl Group {
@@ -99,6 +103,7 @@ pub type ProtocolVersionMajor = u32;
 
 /// Major element of `ProtocolVersion` combined with `ContractVersion`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ContractVersionKey(ProtocolVersionMajor, ContractVersion);
 
 impl ContractVersionKey {
@@ -170,10 +175,15 @@ pub type Groups = BTreeMap<Group, BTreeSet<URef>>;
 
 /// Contract definition, metadata, and security container.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ContractPackage {
     /// Key used to add or disable versions
     access_key: URef,
     /// All versions (enabled & disabled)
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::serde_helpers::contract_versions")
+    )]
     versions: ContractVersions,
     /// Disabled versions
     disabled_versions: DisabledVersions,
@@ -385,6 +395,7 @@ pub type EntryPointsMap = BTreeMap<String, EntryPoint>;
 
 /// Collection of named entry points
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EntryPoints(EntryPointsMap);
 
 impl Default for EntryPoints {
@@ -459,8 +470,11 @@ pub type NamedKeys = BTreeMap<String, Key>;
 
 /// Methods and type signatures supported by a contract.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Contract {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::base16_array"))]
     contract_package_hash: ContractPackageHash,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::base16_array"))]
     contract_wasm_hash: ContractWasmHash,
     named_keys: NamedKeys,
     entry_points: EntryPoints,
@@ -626,6 +640,7 @@ impl Default for Contract {
 /// Context of method execution
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EntryPointType {
     /// Runs as session code
     Session = 0,
@@ -669,12 +684,14 @@ pub type Parameters = Vec<Parameter>;
 /// Type signature of a method. Order of arguments matter since can be
 /// referenced by index as well as name.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EntryPoint {
     name: String,
     args: Parameters,
     ret: CLType,
     access: EntryPointAccess,
     entry_point_type: EntryPointType,
+    no_schema: bool,
 }
 
 impl From<EntryPoint> for (String, Parameters, CLType, EntryPointAccess, EntryPointType) {
@@ -704,9 +721,17 @@ impl EntryPoint {
             ret,
             access,
             entry_point_type,
+            no_schema: false,
         }
     }
 
+    /// Marks this entry point as taking arguments which don't follow its declared parameters, so
+    /// that the host doesn't check the arguments of calls made to it against them.
+    pub fn with_no_schema(mut self) -> Self {
+        self.no_schema = true;
+        self
+    }
+
     /// Create a default [`EntryPoint`] with specified name.
     pub fn default_with_name<T: Into<String>>(name: T) -> Self {
         EntryPoint {
@@ -739,6 +764,12 @@ impl EntryPoint {
     pub fn entry_point_type(&self) -> EntryPointType {
         self.entry_point_type
     }
+
+    /// Returns `true` if calls to this entry point aren't checked against its declared
+    /// parameters.
+    pub fn no_schema(&self) -> bool {
+        self.no_schema
+    }
 }
 
 impl Default for EntryPoint {
@@ -750,24 +781,56 @@ impl Default for EntryPoint {
             ret: CLType::Unit,
             access: EntryPointAccess::Public,
             entry_point_type: EntryPointType::Session,
+            no_schema: false,
         }
     }
 }
 
+/// Written in place of an entry point's name length to mark a versioned layout.  No entry point
+/// serialized before the layout was versioned can have a name this long, so entry points without
+/// the marker are read using the original layout.
+const ENTRY_POINT_VERSION_MARKER: u32 = u32::MAX;
+
+/// The version of the layout which follows the original fields with the `no_schema` flag.
+const ENTRY_POINT_VERSION_NO_SCHEMA: u8 = 1;
+
+impl EntryPoint {
+    fn is_versioned(&self) -> bool {
+        // Entry points keep the original layout unless they need the `no_schema` flag, so that
+        // the serialized form, and hence the hash, of existing contracts doesn't change.
+        self.no_schema
+    }
+}
+
 impl ToBytes for EntryPoint {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut result = bytesrepr::allocate_buffer(self)?;
+        if self.is_versioned() {
+            result.append(&mut ENTRY_POINT_VERSION_MARKER.to_bytes()?);
+            result.append(&mut ENTRY_POINT_VERSION_NO_SCHEMA.to_bytes()?);
+        }
         result.append(&mut self.name.to_bytes()?);
         result.append(&mut self.args.to_bytes()?);
         self.ret.append_bytes(&mut result);
         result.append(&mut self.access.to_bytes()?);
         result.append(&mut self.entry_point_type.to_bytes()?);
+        if self.is_versioned() {
+            result.append(&mut self.no_schema.to_bytes()?);
+        }
 
         Ok(result)
     }
 
     fn serialized_length(&self) -> usize {
-        self.name.serialized_length()
+        let versioned_length = if self.is_versioned() {
+            ENTRY_POINT_VERSION_MARKER.serialized_length()
+                + ENTRY_POINT_VERSION_NO_SCHEMA.serialized_length()
+                + self.no_schema.serialized_length()
+        } else {
+            0
+        };
+        versioned_length
+            + self.name.serialized_length()
             + self.args.serialized_length()
             + self.ret.serialized_length()
             + self.access.serialized_length()
@@ -777,11 +840,25 @@ impl ToBytes for EntryPoint {
 
 impl FromBytes for EntryPoint {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (marker, rem) = u32::from_bytes(bytes)?;
+        let (versioned, bytes) = if marker == ENTRY_POINT_VERSION_MARKER {
+            match u8::from_bytes(rem)? {
+                (ENTRY_POINT_VERSION_NO_SCHEMA, rem) => (true, rem),
+                _ => return Err(bytesrepr::Error::Formatting),
+            }
+        } else {
+            (false, bytes)
+        };
         let (name, bytes) = String::from_bytes(bytes)?;
         let (args, bytes) = Vec::<Parameter>::from_bytes(bytes)?;
         let (ret, bytes) = CLType::from_bytes(bytes)?;
         let (access, bytes) = EntryPointAccess::from_bytes(bytes)?;
         let (entry_point_type, bytes) = EntryPointType::from_bytes(bytes)?;
+        let (no_schema, bytes) = if versioned {
+            bool::from_bytes(bytes)?
+        } else {
+            (false, bytes)
+        };
 
         Ok((
             EntryPoint {
@@ -790,6 +867,7 @@ impl FromBytes for EntryPoint {
                 ret,
                 access,
                 entry_point_type,
+                no_schema,
             },
             bytes,
         ))
@@ -799,6 +877,7 @@ impl FromBytes for EntryPoint {
 /// Enum describing the possible access control options for a contract entry
 /// point (method).
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EntryPointAccess {
     /// Anyone can call this method (no access controls).
     Public,
@@ -861,6 +940,7 @@ impl FromBytes for EntryPointAccess {
 
 /// Parameter to a method
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Parameter {
     name: String,
     cl_type: CLType,
@@ -875,6 +955,11 @@ impl Parameter {
         }
     }
 
+    /// Get the name of this argument.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Get the type of this argument.
     pub fn cl_type(&self) -> &CLType {
         &self.cl_type
@@ -1049,4 +1134,104 @@ mod tests {
             "version should not be enabled"
         );
     }
+
+    fn make_legacy_contract_bytes() -> Vec<u8> {
+        // A contract with a single entry point, as serialized before entry points could carry the
+        // `no_schema` flag.
+        let mut bytes = vec![1u8; KEY_HASH_LENGTH];
+        bytes.extend(&[2u8; KEY_HASH_LENGTH]);
+        // No named keys.
+        bytes.extend(&[0, 0, 0, 0]);
+        // One entry point "delegate", taking a single `U512` parameter "param1", returning `Unit`,
+        // with public access and of contract type.
+        bytes.extend(&[1, 0, 0, 0]);
+        bytes.extend(&[8, 0, 0, 0]);
+        bytes.extend(b"delegate");
+        bytes.extend(&[8, 0, 0, 0]);
+        bytes.extend(b"delegate");
+        bytes.extend(&[1, 0, 0, 0]);
+        bytes.extend(&[6, 0, 0, 0]);
+        bytes.extend(b"param1");
+        bytes.extend(&[8, 9, 1, 1]);
+        // Protocol version 1.0.0.
+        bytes.extend(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        bytes
+    }
+
+    fn make_delegate_entry_point() -> EntryPoint {
+        EntryPoint::new(
+            "delegate",
+            vec![Parameter::new("param1", CLType::U512)],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        )
+    }
+
+    #[test]
+    fn should_deserialize_contract_serialized_before_no_schema() {
+        let legacy_bytes = make_legacy_contract_bytes();
+        let contract: Contract =
+            bytesrepr::deserialize(legacy_bytes.clone()).expect("should deserialize");
+
+        let mut entry_points = EntryPoints::new();
+        entry_points.add_entry_point(make_delegate_entry_point());
+        let expected = Contract::new(
+            [1; KEY_HASH_LENGTH],
+            [2; KEY_HASH_LENGTH],
+            NamedKeys::new(),
+            entry_points,
+            ProtocolVersion::V1_0_0,
+        );
+        assert_eq!(contract, expected);
+        assert!(!contract.entry_point("delegate").unwrap().no_schema());
+
+        // Contracts without the flag keep their original serialized form.
+        assert_eq!(contract.to_bytes().unwrap(), legacy_bytes);
+    }
+
+    #[test]
+    fn should_serialize_no_schema_entry_point_in_versioned_layout() {
+        let legacy_bytes = make_delegate_entry_point().to_bytes().unwrap();
+        let entry_point = make_delegate_entry_point().with_no_schema();
+        let bytes = entry_point.to_bytes().unwrap();
+
+        let mut expected = vec![0xff, 0xff, 0xff, 0xff, ENTRY_POINT_VERSION_NO_SCHEMA];
+        expected.extend(&legacy_bytes);
+        expected.push(1);
+        assert_eq!(bytes, expected);
+
+        let (deserialized, rem) = EntryPoint::from_bytes(&bytes).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(deserialized, entry_point);
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use proptest::prelude::*;
+
+    use crate::{bytesrepr, gens};
+
+    proptest! {
+        #[test]
+        fn test_value_entry_point(entry_point in gens::entry_point_arb()) {
+            bytesrepr::test_serialization_roundtrip(&entry_point);
+        }
+
+        #[test]
+        fn test_value_entry_points(entry_points in gens::entry_points_arb()) {
+            bytesrepr::test_serialization_roundtrip(&entry_points);
+        }
+
+        #[test]
+        fn test_value_contract(contract in gens::contract_arb()) {
+            bytesrepr::test_serialization_roundtrip(&contract);
+        }
+
+        #[test]
+        fn test_value_contract_package(contract_package in gens::contract_package_arb()) {
+            bytesrepr::test_serialization_roundtrip(&contract_package);
+        }
+    }
 }
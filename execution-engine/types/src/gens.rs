@@ -84,15 +84,15 @@ pub fn protocol_version_arb() -> impl Strategy<Value = ProtocolVersion> {
 }
 
 pub fn u128_arb() -> impl Strategy<Value = U128> {
-    vec(any::<u8>(), 0..16).prop_map(|b| U128::from_little_endian(b.as_slice()))
+    vec(any::<u8>(), 0..=16).prop_map(|b| U128::from_little_endian(b.as_slice()))
 }
 
 pub fn u256_arb() -> impl Strategy<Value = U256> {
-    vec(any::<u8>(), 0..32).prop_map(|b| U256::from_little_endian(b.as_slice()))
+    vec(any::<u8>(), 0..=32).prop_map(|b| U256::from_little_endian(b.as_slice()))
 }
 
 pub fn u512_arb() -> impl Strategy<Value = U512> {
-    vec(any::<u8>(), 0..64).prop_map(|b| U512::from_little_endian(b.as_slice()))
+    vec(any::<u8>(), 0..=64).prop_map(|b| U512::from_little_endian(b.as_slice()))
 }
 
 pub fn cl_simple_type_arb() -> impl Strategy<Value = CLType> {
@@ -212,6 +212,11 @@ pub fn cl_value_arb() -> impl Strategy<Value = CLValue> {
             .prop_map(|x| CLValue::from_t(x).expect("should create CLValue")),
         (any::<bool>(), any::<i32>(), any::<i64>())
             .prop_map(|x| CLValue::from_t(x).expect("should create CLValue")),
+        // Nested composite types, as returned by contracts
+        option::of((".*", u512_arb()))
+            .prop_map(|x| CLValue::from_t(x).expect("should create CLValue")),
+        (option::of(key_arb()), Just(()), vec(".*", 0..10))
+            .prop_map(|x| CLValue::from_t(x).expect("should create CLValue")),
     ]
 }
 
@@ -256,10 +261,17 @@ pub fn entry_point_arb() -> impl Strategy<Value = EntryPoint> {
         entry_point_type_arb(),
         entry_point_access_arb(),
         cl_type_arb(),
+        any::<bool>(),
     )
         .prop_map(
-            |(name, parameters, entry_point_type, entry_point_access, ret)| {
-                EntryPoint::new(name, parameters, ret, entry_point_access, entry_point_type)
+            |(name, parameters, entry_point_type, entry_point_access, ret, no_schema)| {
+                let entry_point =
+                    EntryPoint::new(name, parameters, ret, entry_point_access, entry_point_type);
+                if no_schema {
+                    entry_point.with_no_schema()
+                } else {
+                    entry_point
+                }
             },
         )
 }
@@ -1,18 +1,28 @@
 use alloc::{format, string::String, vec::Vec};
-use core::fmt::{self, Debug, Display, Formatter};
+use core::{
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
 
+use failure::Fail;
 use hex_fmt::HexFmt;
+#[cfg(feature = "serde")]
+use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
     account::AccountHash,
     bytesrepr::{self, Error, FromBytes, ToBytes},
-    URef, UREF_SERIALIZED_LENGTH,
+    AccessRights, URef, UREF_SERIALIZED_LENGTH,
 };
 
 const ACCOUNT_ID: u8 = 0;
 const HASH_ID: u8 = 1;
 const UREF_ID: u8 = 2;
 
+const ACCOUNT_PREFIX: &str = "account-";
+const HASH_PREFIX: &str = "hash-";
+const UREF_PREFIX: &str = "uref-";
+
 /// The number of bytes in a Blake2b hash
 pub const BLAKE2B_DIGEST_LENGTH: usize = 32;
 /// The number of bytes in a [`Key::Hash`].
@@ -39,6 +49,28 @@ pub type ContractWasmHash = HashAddr;
 /// An alias for [`Key`]s hash variant.
 pub type ContractPackageHash = HashAddr;
 
+/// Errors which can occur while parsing a [`Key`] from its formatted string representation.
+#[derive(Fail, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FromStrError {
+    /// The string doesn't start with one of the `account-`, `hash-` or `uref-` prefixes.
+    #[fail(display = "Invalid prefix")]
+    InvalidPrefix,
+    /// The address isn't valid base16.
+    #[fail(display = "Invalid hex")]
+    InvalidHex,
+    /// The address has the wrong number of hex digits.
+    #[fail(display = "Invalid length: expected {}, actual {}", expected, actual)]
+    InvalidLength {
+        /// The expected number of hex digits.
+        expected: usize,
+        /// The actual number of hex digits.
+        actual: usize,
+    },
+    /// The access rights suffix of a `uref-` string is missing or invalid.
+    #[fail(display = "Invalid access rights")]
+    InvalidAccessRights,
+}
+
 /// The type under which data (e.g. [`CLValue`](crate::CLValue)s, smart contracts, user accounts)
 /// are indexed on the network.
 #[repr(C)]
@@ -90,6 +122,44 @@ impl Key {
         }
     }
 
+    /// Formats `self` as `account-{hex}`, `hash-{hex}` or `uref-{hex}-{access_rights}`, where the
+    /// access rights are given as three octal digits.
+    ///
+    /// The result can be parsed back using [`Key::from_formatted_string`].
+    pub fn to_formatted_string(&self) -> String {
+        match self {
+            Key::Account(account_hash) => format!(
+                "{}{}",
+                ACCOUNT_PREFIX,
+                base16::encode_lower(&account_hash.value())
+            ),
+            Key::Hash(addr) => format!("{}{}", HASH_PREFIX, base16::encode_lower(addr)),
+            Key::URef(uref) => uref.as_string(),
+        }
+    }
+
+    /// Parses a string produced by [`Key::to_formatted_string`] back into a `Key`.
+    pub fn from_formatted_string(input: &str) -> Result<Key, FromStrError> {
+        if input.starts_with(ACCOUNT_PREFIX) {
+            let addr = parse_addr(&input[ACCOUNT_PREFIX.len()..])?;
+            return Ok(Key::Account(AccountHash::new(addr)));
+        }
+        if input.starts_with(HASH_PREFIX) {
+            let addr = parse_addr(&input[HASH_PREFIX.len()..])?;
+            return Ok(Key::Hash(addr));
+        }
+        if input.starts_with(UREF_PREFIX) {
+            let remainder = &input[UREF_PREFIX.len()..];
+            let separator = remainder
+                .rfind('-')
+                .ok_or(FromStrError::InvalidAccessRights)?;
+            let addr = parse_addr(&remainder[..separator])?;
+            let access_rights = parse_access_rights(&remainder[separator + 1..])?;
+            return Ok(Key::URef(URef::new(addr, access_rights)));
+        }
+        Err(FromStrError::InvalidPrefix)
+    }
+
     /// Returns the inner bytes of `self` if `self` is of type [`Key::Account`], otherwise returns
     /// `None`.
     pub fn into_account(self) -> Option<AccountHash> {
@@ -135,6 +205,36 @@ impl Key {
     }
 }
 
+fn parse_addr(hex: &str) -> Result<[u8; KEY_HASH_LENGTH], FromStrError> {
+    if hex.len() != 2 * KEY_HASH_LENGTH {
+        return Err(FromStrError::InvalidLength {
+            expected: 2 * KEY_HASH_LENGTH,
+            actual: hex.len(),
+        });
+    }
+    let mut addr = [0u8; KEY_HASH_LENGTH];
+    base16::decode_slice(hex, &mut addr).map_err(|_| FromStrError::InvalidHex)?;
+    Ok(addr)
+}
+
+fn parse_access_rights(octal: &str) -> Result<AccessRights, FromStrError> {
+    if octal.len() != 3 || !octal.bytes().all(|digit| (b'0'..=b'7').contains(&digit)) {
+        return Err(FromStrError::InvalidAccessRights);
+    }
+    u8::from_str_radix(octal, 8)
+        .ok()
+        .and_then(AccessRights::from_bits)
+        .ok_or(FromStrError::InvalidAccessRights)
+}
+
+impl FromStr for Key {
+    type Err = FromStrError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Key::from_formatted_string(input)
+    }
+}
+
 impl Display for Key {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
@@ -215,6 +315,21 @@ impl FromBytes for Key {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for Key {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_formatted_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Key {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let formatted = String::deserialize(deserializer)?;
+        Key::from_formatted_string(&formatted).map_err(SerdeError::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -341,3 +456,80 @@ mod tests {
         assert!(key_uref.serialized_length() <= Key::max_serialized_length());
     }
 }
+
+#[cfg(test)]
+mod proptests {
+    use std::format;
+
+    use proptest::prelude::*;
+
+    use super::{FromStrError, Key, ACCOUNT_PREFIX, HASH_PREFIX, KEY_HASH_LENGTH, UREF_PREFIX};
+    use crate::gens;
+
+    fn invalid_length(actual: usize) -> FromStrError {
+        FromStrError::InvalidLength {
+            expected: 2 * KEY_HASH_LENGTH,
+            actual,
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn formatted_string_roundtrip(key in gens::key_arb()) {
+            let formatted = key.to_formatted_string();
+            prop_assert_eq!(Key::from_formatted_string(&formatted), Ok(key));
+            prop_assert_eq!(formatted.parse::<Key>(), Ok(key));
+        }
+
+        #[test]
+        fn should_reject_invalid_prefix(prefix in "[a-z]{0,10}-", hex in "[0-9a-f]{64}") {
+            prop_assume!(
+                prefix != ACCOUNT_PREFIX && prefix != HASH_PREFIX && prefix != UREF_PREFIX
+            );
+            prop_assert_eq!(
+                Key::from_formatted_string(&format!("{}{}", prefix, hex)),
+                Err(FromStrError::InvalidPrefix)
+            );
+        }
+
+        #[test]
+        fn should_reject_wrong_length_hex(hex in "[0-9a-f]{0,63}|[0-9a-f]{65,80}") {
+            let expected = Err(invalid_length(hex.len()));
+            prop_assert_eq!(
+                Key::from_formatted_string(&format!("{}{}", ACCOUNT_PREFIX, hex)),
+                expected
+            );
+            prop_assert_eq!(
+                Key::from_formatted_string(&format!("{}{}", HASH_PREFIX, hex)),
+                expected
+            );
+            prop_assert_eq!(
+                Key::from_formatted_string(&format!("{}{}-007", UREF_PREFIX, hex)),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_hex() {
+        let input = format!("{}{}", HASH_PREFIX, "zz".repeat(KEY_HASH_LENGTH));
+        assert_eq!(
+            Key::from_formatted_string(&input),
+            Err(FromStrError::InvalidHex)
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_access_rights() {
+        let hex = "ab".repeat(KEY_HASH_LENGTH);
+        for suffix in &["", "-", "-7", "-0007", "-010", "-008", "-rwx"] {
+            let input = format!("{}{}{}", UREF_PREFIX, hex, suffix);
+            assert_eq!(
+                Key::from_formatted_string(&input),
+                Err(FromStrError::InvalidAccessRights),
+                "{}",
+                input
+            );
+        }
+    }
+}
@@ -39,8 +39,11 @@ mod phase;
 mod protocol_version;
 pub mod runtime_args;
 mod semver;
+#[cfg(feature = "serde")]
+mod serde_helpers;
 pub mod system_contract_errors;
 pub mod system_contract_type;
+mod transfer_record;
 mod transfer_result;
 mod uint;
 mod uref;
@@ -60,13 +63,16 @@ pub use contracts::{
 pub use contract_wasm::ContractWasm;
 #[doc(inline)]
 pub use key::{
-    ContractHash, ContractPackageHash, ContractWasmHash, HashAddr, Key, BLAKE2B_DIGEST_LENGTH,
-    KEY_HASH_LENGTH,
+    ContractHash, ContractPackageHash, ContractWasmHash, FromStrError, HashAddr, Key,
+    BLAKE2B_DIGEST_LENGTH, KEY_HASH_LENGTH,
 };
 pub use phase::{Phase, PHASE_SERIALIZED_LENGTH};
 pub use protocol_version::{ProtocolVersion, VersionCheckResult};
 pub use runtime_args::{NamedArg, RuntimeArgs};
 pub use semver::{SemVer, SEM_VER_SERIALIZED_LENGTH};
-pub use system_contract_type::SystemContractType;
+pub use system_contract_type::{
+    SystemContractRegistry, SystemContractType, SYSTEM_CONTRACT_REGISTRY_ADDR,
+};
+pub use transfer_record::TransferRecord;
 pub use transfer_result::{TransferResult, TransferredTo};
-pub use uref::{URef, UREF_ADDR_LENGTH, UREF_SERIALIZED_LENGTH};
+pub use uref::{URef, URefAddr, UREF_ADDR_LENGTH, UREF_SERIALIZED_LENGTH};
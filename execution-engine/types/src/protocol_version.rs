@@ -1,6 +1,9 @@
 use alloc::vec::Vec;
 use core::fmt;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::{
     bytesrepr::{Error, FromBytes, ToBytes},
     SemVer,
@@ -8,6 +11,7 @@ use crate::{
 
 /// A newtype wrapping a [`SemVer`] which represents a CasperLabs Platform protocol version.
 #[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProtocolVersion(SemVer);
 
 /// The result of [`ProtocolVersion::check_next_version`].
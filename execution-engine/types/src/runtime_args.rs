@@ -174,7 +174,10 @@ macro_rules! runtime_args {
 
 #[cfg(test)]
 mod tests {
+    use alloc::string::ToString;
+
     use super::*;
+    use crate::{AccessRights, URef, U512};
 
     #[test]
     fn test_runtime_args() {
@@ -227,6 +230,30 @@ mod tests {
         assert_eq!(tagless, runtime_args_2.to_bytes().unwrap());
     }
 
+    #[test]
+    fn macro_should_serialize_like_hand_built_args() {
+        let amount = U512::from(100);
+        let purse = URef::new([1; 32], AccessRights::READ_ADD_WRITE);
+
+        let from_macro = runtime_args! {
+            "amount" => amount,
+            "purse" => purse,
+        };
+        let by_hand = RuntimeArgs::from(vec![
+            NamedArg::new("amount".to_string(), CLValue::from_t(amount).unwrap()),
+            NamedArg::new("purse".to_string(), CLValue::from_t(purse).unwrap()),
+        ]);
+        assert_eq!(from_macro.to_bytes().unwrap(), by_hand.to_bytes().unwrap());
+
+        // Lookups are by name, so callers don't depend on the order the args were given in.
+        let reordered = runtime_args! {
+            "purse" => purse,
+            "amount" => amount,
+        };
+        assert_eq!(reordered.get("amount"), from_macro.get("amount"));
+        assert_eq!(reordered.get("purse"), from_macro.get("purse"));
+    }
+
     #[test]
     fn named_serialization_roundtrip() {
         let args = runtime_args! {
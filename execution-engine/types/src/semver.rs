@@ -2,6 +2,8 @@ use alloc::vec::Vec;
 use core::{convert::TryFrom, fmt, num::ParseIntError};
 
 use failure::Fail;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::bytesrepr::{self, Error, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
 
@@ -10,6 +12,7 @@ pub const SEM_VER_SERIALIZED_LENGTH: usize = 3 * U32_SERIALIZED_LENGTH;
 
 /// A struct for semantic versioning.
 #[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SemVer {
     /// Major version.
     pub major: u32,
@@ -0,0 +1,157 @@
+//! Helpers for the human-readable `serde` representations of types in this crate.
+
+use alloc::{string::String, vec::Vec};
+use core::convert::TryFrom;
+
+use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serializer};
+
+/// Serializes a byte slice as a lowercase base16 string.
+pub(crate) mod base16_bytes {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base16::encode_lower(bytes))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        base16::decode(&hex).map_err(SerdeError::custom)
+    }
+}
+
+/// Serializes a 32-byte array as a lowercase base16 string.
+pub(crate) mod base16_array {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        bytes: &[u8; 32],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        base16_bytes::serialize(bytes, serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<[u8; 32], D::Error> {
+        let bytes = base16_bytes::deserialize(deserializer)?;
+        <[u8; 32]>::try_from(bytes.as_slice())
+            .map_err(|_| SerdeError::invalid_length(bytes.len(), &"a base16 string of 32 bytes"))
+    }
+}
+
+/// Serializes [`ContractVersions`] as a list of entries, since JSON maps only support string keys.
+pub(crate) mod contract_versions {
+    use serde::Serialize;
+
+    use super::*;
+    use crate::{
+        contracts::{ContractVersion, ContractVersions, ProtocolVersionMajor},
+        ContractHash, ContractVersionKey,
+    };
+
+    #[derive(Serialize, Deserialize)]
+    struct ContractVersionEntry {
+        protocol_version_major: ProtocolVersionMajor,
+        contract_version: ContractVersion,
+        #[serde(with = "base16_array")]
+        contract_hash: ContractHash,
+    }
+
+    pub(crate) fn serialize<S: Serializer>(
+        versions: &ContractVersions,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(versions.iter().map(|(version_key, contract_hash)| {
+            ContractVersionEntry {
+                protocol_version_major: version_key.protocol_version_major(),
+                contract_version: version_key.contract_version(),
+                contract_hash: *contract_hash,
+            }
+        }))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ContractVersions, D::Error> {
+        let entries = Vec::<ContractVersionEntry>::deserialize(deserializer)?;
+        Ok(entries
+            .into_iter()
+            .map(|entry| {
+                let version_key =
+                    ContractVersionKey::new(entry.protocol_version_major, entry.contract_version);
+                (version_key, entry.contract_hash)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+
+    use serde_json::json;
+
+    use crate::{
+        account::AccountHash,
+        contracts::{ContractPackage, Group},
+        AccessRights, CLValue, ContractVersionKey, Key, URef, U512,
+    };
+
+    #[test]
+    fn should_serialize_u512_as_decimal_string() {
+        let value = U512::from(123_456_789u64);
+        assert_eq!(serde_json::to_value(value).unwrap(), json!("123456789"));
+        assert_eq!(
+            serde_json::from_value::<U512>(json!("123456789")).unwrap(),
+            value
+        );
+        assert!(serde_json::from_value::<U512>(json!("0x10")).is_err());
+    }
+
+    #[test]
+    fn should_serialize_key_as_formatted_string() {
+        let key = Key::Account(AccountHash::new([1; 32]));
+        let expected = json!(key.to_formatted_string());
+        assert_eq!(serde_json::to_value(key).unwrap(), expected);
+        assert_eq!(serde_json::from_value::<Key>(expected).unwrap(), key);
+    }
+
+    #[test]
+    fn should_serialize_cl_value_bytes_as_base16() {
+        let cl_value = CLValue::from_t(258u32).unwrap();
+        assert_eq!(
+            serde_json::to_value(&cl_value).unwrap(),
+            json!({ "cl_type": "U32", "bytes": "02010000" })
+        );
+    }
+
+    #[test]
+    fn should_reject_short_account_hash() {
+        assert!(serde_json::from_value::<AccountHash>(json!("0101")).is_err());
+    }
+
+    #[test]
+    fn should_roundtrip_contract_versions() {
+        let mut versions = BTreeMap::new();
+        versions.insert(ContractVersionKey::new(1, 1), [2; 32]);
+        versions.insert(ContractVersionKey::new(1, 2), [3; 32]);
+        let mut groups = BTreeMap::new();
+        groups.insert(
+            Group::new("group"),
+            vec![URef::new([4; 32], AccessRights::READ)]
+                .into_iter()
+                .collect(),
+        );
+        let contract_package = ContractPackage::new(
+            URef::new([1; 32], AccessRights::READ_ADD_WRITE),
+            versions,
+            Default::default(),
+            groups,
+        );
+        let json = serde_json::to_string(&contract_package).unwrap();
+        let parsed: ContractPackage = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, contract_package);
+    }
+}
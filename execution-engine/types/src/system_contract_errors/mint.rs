@@ -38,6 +38,21 @@ pub enum Error {
     /// Purse not found while trying to get balance.
     #[fail(display = "Purse not found")]
     PurseNotFound = 7,
+    /// The mint's total supply was not found.
+    #[fail(display = "Total supply not found")]
+    TotalSupplyNotFound = 8,
+    /// The amount exceeds the allowance given to the spender by the purse's owner.
+    #[fail(display = "Insufficient allowance")]
+    InsufficientAllowance = 9,
+    /// The mint's round seigniorage rate was not found.
+    #[fail(display = "Round seigniorage rate not found")]
+    RoundSeigniorageRateNotFound = 10,
+    /// A function reserved for the system account was called by a user account.
+    #[fail(display = "System function was called by user account")]
+    SystemFunctionCalledByUserAccount = 11,
+    /// Computing the round reward overflowed or divided by zero.
+    #[fail(display = "Arithmetic overflow")]
+    ArithmeticOverflow = 12,
 }
 
 impl From<PurseError> for Error {
@@ -79,6 +94,17 @@ impl TryFrom<u8> for Error {
             d if d == Error::InvalidNonEmptyPurseCreation as u8 => {
                 Ok(Error::InvalidNonEmptyPurseCreation)
             }
+            d if d == Error::Storage as u8 => Ok(Error::Storage),
+            d if d == Error::PurseNotFound as u8 => Ok(Error::PurseNotFound),
+            d if d == Error::TotalSupplyNotFound as u8 => Ok(Error::TotalSupplyNotFound),
+            d if d == Error::InsufficientAllowance as u8 => Ok(Error::InsufficientAllowance),
+            d if d == Error::RoundSeigniorageRateNotFound as u8 => {
+                Ok(Error::RoundSeigniorageRateNotFound)
+            }
+            d if d == Error::SystemFunctionCalledByUserAccount as u8 => {
+                Ok(Error::SystemFunctionCalledByUserAccount)
+            }
+            d if d == Error::ArithmeticOverflow as u8 => Ok(Error::ArithmeticOverflow),
             _ => Err(TryFromU8ForError(())),
         }
     }
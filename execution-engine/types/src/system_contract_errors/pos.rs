@@ -105,6 +105,43 @@ pub enum Error {
     /// deploy, but was called by the session code.
     #[fail(display = "Set refund purse was called outside payment")]
     SetRefundPurseCalledOutsidePayment,
+    /// The given delegator has no stake delegated to the given validator.
+    #[fail(display = "Not delegated")]
+    NotDelegated,
+    /// Attempted to undelegate an amount which was larger than the delegated stake.
+    #[fail(display = "Undelegate is too large")]
+    UndelegateTooLarge,
+    /// Internal error: failed to deserialize the delegation's key.
+    #[fail(display = "Failed to deserialize delegation's key")]
+    DelegationsKeyDeserializationFailed,
+    /// Internal error: failed to deserialize the delegation's balance.
+    #[fail(display = "Failed to deserialize delegation's balance")]
+    DelegationsDeserializationFailed,
+    /// The purse given to bond or delegate from is not valid in the caller's context, or lacks
+    /// write access.
+    #[fail(display = "Invalid bonding purse")]
+    InvalidBondingPurse,
+    /// Internal error: couldn't retrieve the balance for the PoS contract's rewards purse.
+    #[fail(display = "Rewards purse balance not found")]
+    RewardsPurseBalanceNotFound,
+    /// Internal error: while distributing rewards, failed to pay the block proposer (the transfer
+    /// from the PoS contract's rewards purse to the proposer's main purse failed).
+    #[fail(display = "Transfer to proposer's purse failed")]
+    FailedTransferToProposerPurse,
+    /// The gas price must be greater than zero.
+    #[fail(display = "Invalid gas price")]
+    InvalidGasPrice,
+    /// Internal error: while distributing rewards, failed to mint the round reward into the PoS
+    /// contract's rewards purse.
+    #[fail(display = "Minting the round reward failed")]
+    FailedToMintRoundReward,
+    /// Failed to bond or unbond as this would have left a nonzero stake below the minimum bond
+    /// amount.
+    #[fail(display = "Stake is below the minimum bond amount")]
+    StakeBelowMinimum,
+    /// Failed to bond or delegate as this would have left a stake above the maximum bond amount.
+    #[fail(display = "Stake is above the maximum bond amount")]
+    StakeAboveMaximum,
 }
 
 impl CLTyped for Error {
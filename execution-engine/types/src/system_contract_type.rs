@@ -1,11 +1,20 @@
 //! Home of system contract type enum.
 
+use alloc::{collections::BTreeMap, string::String};
 use core::{
     convert::TryFrom,
     fmt::{self, Display, Formatter},
 };
 
-use crate::ApiError;
+use crate::{ApiError, HashAddr, Key};
+
+/// The address of the system contract registry, stored in global state under
+/// `Key::Hash(SYSTEM_CONTRACT_REGISTRY_ADDR)`.
+pub const SYSTEM_CONTRACT_REGISTRY_ADDR: HashAddr = [255; 32];
+
+/// The system contract registry, mapping the name of each system contract (as given by
+/// `SystemContractType`'s `Display` impl) to its key.
+pub type SystemContractRegistry = BTreeMap<String, Key>;
 
 /// System contract types.
 ///
@@ -28,6 +37,15 @@ pub const PROOF_OF_STAKE: &str = "proof of stake";
 /// Name of standard payment system contract
 const STANDARD_PAYMENT: &str = "standard payment";
 
+impl SystemContractType {
+    /// All the system contract types.
+    pub const ALL: [SystemContractType; 3] = [
+        SystemContractType::Mint,
+        SystemContractType::ProofOfStake,
+        SystemContractType::StandardPayment,
+    ];
+}
+
 impl From<SystemContractType> for u32 {
     fn from(system_contract_type: SystemContractType) -> u32 {
         match system_contract_type {
@@ -0,0 +1,142 @@
+use alloc::vec::Vec;
+
+use blake2::{
+    digest::{Input, VariableOutput},
+    VarBlake2b,
+};
+
+use crate::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, HashAddr, URefAddr, KEY_HASH_LENGTH, U512,
+};
+
+/// A record of a successful transfer of motes to an account.
+///
+/// The host writes one record for each transfer to an account made by a deploy, under the address
+/// returned by [`TransferRecord::addr`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TransferRecord {
+    /// The hash of the deploy which made the transfer.
+    pub deploy_hash: [u8; KEY_HASH_LENGTH],
+    /// The address of the purse the motes were transferred from.
+    pub source: URefAddr,
+    /// The account the motes were transferred to.
+    pub target: AccountHash,
+    /// The number of motes transferred.
+    pub amount: U512,
+    /// The user-supplied identifier of the transfer, if any.
+    pub id: Option<u64>,
+}
+
+impl TransferRecord {
+    /// Constructs a `TransferRecord`.
+    pub fn new(
+        deploy_hash: [u8; KEY_HASH_LENGTH],
+        source: URefAddr,
+        target: AccountHash,
+        amount: U512,
+        id: Option<u64>,
+    ) -> Self {
+        TransferRecord {
+            deploy_hash,
+            source,
+            target,
+            amount,
+            id,
+        }
+    }
+
+    /// Returns the address under which the record of the `index`th transfer made by the deploy
+    /// with hash `deploy_hash` is stored.
+    pub fn addr(deploy_hash: [u8; KEY_HASH_LENGTH], index: u32) -> HashAddr {
+        let mut hasher = VarBlake2b::new(KEY_HASH_LENGTH).unwrap();
+        hasher.input(&deploy_hash);
+        hasher.input(&index.to_le_bytes());
+        let mut addr = HashAddr::default();
+        hasher.variable_result(|hash| addr.clone_from_slice(hash));
+        addr
+    }
+}
+
+impl ToBytes for TransferRecord {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut bytes = bytesrepr::allocate_buffer(self)?;
+        bytes.append(&mut self.deploy_hash.to_bytes()?);
+        bytes.append(&mut self.source.to_bytes()?);
+        bytes.append(&mut self.target.to_bytes()?);
+        bytes.append(&mut self.amount.to_bytes()?);
+        bytes.append(&mut self.id.to_bytes()?);
+        Ok(bytes)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.deploy_hash.serialized_length()
+            + self.source.serialized_length()
+            + self.target.serialized_length()
+            + self.amount.serialized_length()
+            + self.id.serialized_length()
+    }
+}
+
+impl FromBytes for TransferRecord {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (deploy_hash, bytes) = FromBytes::from_bytes(bytes)?;
+        let (source, bytes) = FromBytes::from_bytes(bytes)?;
+        let (target, bytes) = AccountHash::from_bytes(bytes)?;
+        let (amount, bytes) = U512::from_bytes(bytes)?;
+        let (id, bytes) = Option::<u64>::from_bytes(bytes)?;
+        let record = TransferRecord {
+            deploy_hash,
+            source,
+            target,
+            amount,
+            id,
+        };
+        Ok((record, bytes))
+    }
+}
+
+impl CLTyped for TransferRecord {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        let record = TransferRecord::new(
+            [1; KEY_HASH_LENGTH],
+            [2; 32],
+            AccountHash::new([3; 32]),
+            U512::from(4),
+            Some(5),
+        );
+        bytesrepr::test_serialization_roundtrip(&record);
+
+        let record = TransferRecord { id: None, ..record };
+        bytesrepr::test_serialization_roundtrip(&record);
+    }
+
+    #[test]
+    fn should_have_distinct_addrs_per_deploy_and_index() {
+        let deploy_hash = [1; KEY_HASH_LENGTH];
+        let other_deploy_hash = [2; KEY_HASH_LENGTH];
+        assert_eq!(
+            TransferRecord::addr(deploy_hash, 0),
+            TransferRecord::addr(deploy_hash, 0)
+        );
+        assert_ne!(
+            TransferRecord::addr(deploy_hash, 0),
+            TransferRecord::addr(deploy_hash, 1)
+        );
+        assert_ne!(
+            TransferRecord::addr(deploy_hash, 0),
+            TransferRecord::addr(other_deploy_hash, 0)
+        );
+    }
+}
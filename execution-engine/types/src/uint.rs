@@ -1,7 +1,12 @@
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
 
 use num_integer::Integer;
 use num_traits::{AsPrimitive, Bounded, Num, One, Unsigned, WrappingAdd, WrappingSub, Zero};
+#[cfg(feature = "serde")]
+use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::bytesrepr::{self, Error, FromBytes, ToBytes, U8_SERIALIZED_LENGTH};
 
@@ -38,6 +43,20 @@ pub enum UIntParseError {
     InvalidRadix,
 }
 
+impl fmt::Display for UIntParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UIntParseError::FromDecStr(uint::FromDecStrErr::InvalidCharacter) => {
+                write!(f, "invalid character in decimal string")
+            }
+            UIntParseError::FromDecStr(uint::FromDecStrErr::InvalidLength) => {
+                write!(f, "decimal string overflows the integer type")
+            }
+            UIntParseError::InvalidRadix => write!(f, "only base 10 is supported"),
+        }
+    }
+}
+
 macro_rules! impl_traits_for_uint {
     ($type:ident, $total_bytes:expr, $test_mod:ident) => {
         impl ToBytes for $type {
@@ -74,6 +93,31 @@ macro_rules! impl_traits_for_uint {
             }
         }
 
+        /// Parses the decimal format produced by `Display`.
+        impl FromStr for $type {
+            type Err = UIntParseError;
+
+            fn from_str(input: &str) -> Result<Self, Self::Err> {
+                $type::from_dec_str(input).map_err(UIntParseError::FromDecStr)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.to_string())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let decimal = String::deserialize(deserializer)?;
+                $type::from_dec_str(&decimal)
+                    .map_err(|_| SerdeError::custom("expected a decimal string"))
+            }
+        }
+
         // Trait implementations for unifying U* as numeric types
         impl Zero for $type {
             fn zero() -> Self {
@@ -296,6 +340,8 @@ macro_rules! impl_traits_for_uint {
 
         #[cfg(test)]
         mod $test_mod {
+            use std::{format, string::ToString, vec};
+
             use super::*;
 
             #[test]
@@ -388,6 +434,63 @@ macro_rules! impl_traits_for_uint {
             fn underflow_sub_test() {
                 let _ = $type::zero() - $type::from(1);
             }
+
+            #[test]
+            fn checked_arithmetic_test() {
+                let two = $type::from(2);
+                assert_eq!($type::MAX.checked_add(two), None);
+                assert_eq!($type::zero().checked_sub(two), None);
+                assert_eq!($type::MAX.checked_mul(two), None);
+                assert_eq!(two.checked_div($type::zero()), None);
+                assert_eq!($type::from(5).checked_sub(two), Some($type::from(3)));
+                assert_eq!(two.saturating_sub($type::from(5)), $type::zero());
+            }
+
+            #[test]
+            fn should_display_and_parse_decimal() {
+                assert_eq!($type::zero().to_string(), "0");
+                assert_eq!($type::from(1_234_567).to_string(), "1234567");
+                assert_eq!("1234567".parse::<$type>().unwrap(), $type::from(1_234_567));
+                assert_eq!($type::MAX.to_string().parse::<$type>().unwrap(), $type::MAX);
+            }
+
+            #[test]
+            fn should_fail_to_parse_invalid_decimal() {
+                match "12a".parse::<$type>() {
+                    Err(UIntParseError::FromDecStr(uint::FromDecStrErr::InvalidCharacter)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                }
+                let too_long = format!("{}0", $type::MAX);
+                match too_long.parse::<$type>() {
+                    Err(UIntParseError::FromDecStr(uint::FromDecStrErr::InvalidLength)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                }
+            }
+
+            #[test]
+            fn should_serialize_zero_as_single_length_byte() {
+                assert_eq!($type::zero().to_bytes().unwrap(), vec![0]);
+                assert_eq!($type::zero().serialized_length(), 1);
+                let (parsed, rem) = $type::from_bytes(&[0]).unwrap();
+                assert_eq!(parsed, $type::zero());
+                assert!(rem.is_empty());
+            }
+
+            #[test]
+            fn should_serialize_max_with_full_width() {
+                let bytes = $type::MAX.to_bytes().unwrap();
+                assert_eq!(bytes.len(), $total_bytes + 1);
+                assert_eq!(bytes[0], $total_bytes);
+                assert_eq!($type::MAX.serialized_length(), $total_bytes + 1);
+                bytesrepr::test_serialization_roundtrip(&$type::MAX);
+            }
+
+            #[test]
+            fn should_reject_overlong_length_prefix() {
+                let mut bytes = vec![$total_bytes + 1];
+                bytes.extend(vec![0xff; $total_bytes + 1]);
+                assert_eq!($type::from_bytes(&bytes), Err(Error::Formatting));
+            }
         }
     };
 }
@@ -5,6 +5,8 @@ use core::{
 };
 
 use hex_fmt::HexFmt;
+#[cfg(feature = "serde")]
+use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{bytesrepr, AccessRights, ApiError, Key, ACCESS_RIGHTS_SERIALIZED_LENGTH};
 
@@ -45,6 +47,12 @@ impl URef {
         URef(self.0, access_rights)
     }
 
+    /// Returns a new [`URef`] with the same address and only those of `access_rights` which this
+    /// [`URef`] already has, e.g. for handing a read-only view of it to another contract.
+    pub fn attenuate(self, access_rights: AccessRights) -> Self {
+        URef(self.0, self.1 & access_rights)
+    }
+
     /// Removes the access rights from this [`URef`].
     pub fn remove_access_rights(self) -> Self {
         URef(self.0, AccessRights::NONE)
@@ -141,6 +149,23 @@ impl TryFrom<Key> for URef {
     }
 }
 
+/// Uses the same formatted string as [`Key::URef`].
+#[cfg(feature = "serde")]
+impl Serialize for URef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Key::URef(*self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for URef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Key::deserialize(deserializer)?
+            .into_uref()
+            .ok_or_else(|| SerdeError::custom("expected a uref- formatted string"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,4 +193,23 @@ mod tests {
             "uref-0000000000000000000000000000000000000000000000000000000000000000-000"
         );
     }
+
+    #[test]
+    fn attenuate_never_adds_rights() {
+        let uref = URef::new([0u8; 32], AccessRights::READ_ADD);
+
+        assert_eq!(
+            uref.attenuate(AccessRights::READ).access_rights(),
+            AccessRights::READ
+        );
+        assert_eq!(
+            uref.attenuate(AccessRights::READ_WRITE).access_rights(),
+            AccessRights::READ
+        );
+        assert_eq!(
+            uref.attenuate(AccessRights::WRITE).access_rights(),
+            AccessRights::NONE
+        );
+        assert_eq!(uref.attenuate(AccessRights::READ).addr(), uref.addr());
+    }
 }